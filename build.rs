@@ -34,7 +34,6 @@ fn main() {
             .file("include/yyjson/yyjson.c")
             .include("include/yyjson")
             .define("YYJSON_DISABLE_WRITER", "1")
-            .define("YYJSON_DISABLE_NON_STANDARD", "1")
             .try_compile("yyjson")
         {
             Ok(_) => {
@@ -19,10 +19,10 @@ use serde::{forward_to_deserialize_any, serde_if_integer128};
 #[cfg(feature = "arbitrary_precision")]
 use crate::number::NumberDeserializer;
 
-pub use crate::read::{Read, SliceRead, StrRead};
+pub use crate::read::{LoneSurrogates, Read, SliceRead, StrRead};
 
 #[cfg(feature = "std")]
-pub use crate::read::IoRead;
+pub use crate::read::{IoRead, ReadPosition};
 
 //////////////////////////////////////////////////////////////////////////////
 
@@ -30,11 +30,19 @@ pub use crate::read::IoRead;
 pub struct Deserializer<R> {
     read: R,
     scratch: Vec<u8>,
-    remaining_depth: u8,
+    remaining_depth: u32,
     #[cfg(feature = "float_roundtrip")]
     single_precision: bool,
     #[cfg(feature = "unbounded_depth")]
     disable_recursion_limit: bool,
+    allow_nan: bool,
+    #[cfg(feature = "arbitrary_precision")]
+    raw_numbers: bool,
+    jsonc: bool,
+    lone_surrogates: LoneSurrogates,
+    /// Offset of the value being deserialized or, once it is, of the last
+    /// element or key in it, where an error of a visitor is.
+    value_start: usize,
 }
 
 impl<'de, R> Deserializer<R>
@@ -58,8 +66,61 @@ where
             single_precision: false,
             #[cfg(feature = "unbounded_depth")]
             disable_recursion_limit: false,
+            allow_nan: false,
+            #[cfg(feature = "arbitrary_precision")]
+            raw_numbers: false,
+            jsonc: false,
+            lone_surrogates: LoneSurrogates::Error,
+            value_start: 0,
+        }
+    }
+
+    /// Parse the `NaN`, `Infinity`, and `-Infinity` literals, which are not
+    /// JSON, as floats when deserializing any value.
+    pub fn allow_nan(&mut self) {
+        self.allow_nan = true;
+    }
+
+    /// Give every number that is not a 64-bit integer as written rather
+    /// than only integers out of that range.
+    #[cfg(feature = "arbitrary_precision")]
+    pub fn raw_numbers(&mut self) {
+        self.raw_numbers = true;
+    }
+
+    /// Allow containers to be nested `depth` levels deep rather than 127.
+    pub fn set_max_depth(&mut self, depth: u32) {
+        self.remaining_depth = depth.saturating_add(1);
+    }
+
+    /// Skip `//` and `/* */` comments as whitespace and allow a trailing comma
+    /// in arrays and objects, as in JSONC.
+    pub fn allow_jsonc(&mut self) {
+        self.jsonc = true;
+    }
+
+    /// Handle an escaped surrogate that is not part of a pair in a string or
+    /// key as `lone` says rather than failing.
+    pub fn set_lone_surrogates(&mut self, lone: LoneSurrogates) {
+        self.lone_surrogates = lone;
+    }
+
+    /// Parse a string or key, which fails on a lone surrogate unless it is
+    /// allowed.
+    fn parse_str_value<'s>(&'s mut self) -> Result<Reference<'de, 's, str>> {
+        if self.lone_surrogates == LoneSurrogates::Error {
+            self.read.parse_str(&mut self.scratch)
+        } else {
+            self.read
+                .parse_str_lone_surrogates(&mut self.scratch, self.lone_surrogates)
         }
     }
+
+    /// The number of bytes of the input consumed, e.g., to find where a value
+    /// ends in input having more after it.
+    pub fn byte_offset(&self) -> usize {
+        self.read.byte_offset()
+    }
 }
 
 #[cfg(feature = "std")]
@@ -75,6 +136,11 @@ where
     pub fn from_reader(reader: R) -> Self {
         Deserializer::new(read::IoRead::new(reader))
     }
+
+    /// Where the deserializer is in its input, including any byte peeked.
+    pub fn read_position(&self) -> read::ReadPosition {
+        self.read.read_position()
+    }
 }
 
 impl<'a> Deserializer<read::SliceRead<'a>> {
@@ -251,6 +317,10 @@ impl<'de, R: Read<'de>> Deserializer<R> {
                 Some(b' ') | Some(b'\n') | Some(b'\t') | Some(b'\r') => {
                     self.eat_char();
                 }
+                Some(b'/') if self.jsonc => {
+                    self.eat_char();
+                    tri!(self.parse_comment());
+                }
                 other => {
                     return Ok(other);
                 }
@@ -258,6 +328,29 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
+    /// Skip a comment of which the leading `/` has been consumed.
+    fn parse_comment(&mut self) -> Result<()> {
+        match tri!(self.next_char()) {
+            Some(b'/') => loop {
+                match tri!(self.next_char()) {
+                    Some(b'\n') | None => return Ok(()),
+                    Some(_) => {}
+                }
+            },
+            Some(b'*') => {
+                let mut star = false;
+                loop {
+                    match tri!(self.next_char()) {
+                        Some(b'/') if star => return Ok(()),
+                        Some(c) => star = c == b'*',
+                        None => return Err(self.error(ErrorCode::EofWhileParsingValue)),
+                    }
+                }
+            }
+            _ => Err(self.error(ErrorCode::ExpectedSomeValue)),
+        }
+    }
+
     #[cold]
     fn peek_invalid_type(&mut self, exp: &dyn Expected) -> Error {
         let err = match self.peek_or_null().unwrap_or(b'\x00') {
@@ -368,6 +461,16 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         err.fix_position(move |code| self.error(code))
     }
 
+    /// Fill in the position of an error of a visitor as that of the byte at
+    /// `self.value_start`.
+    #[cold]
+    fn fix_value_position(&self, err: Error) -> Error {
+        err.fix_position(move |code| {
+            let position = self.read.position_of_offset(self.value_start);
+            Error::syntax(code, position.line, position.column)
+        })
+    }
+
     fn parse_ident(&mut self, ident: &[u8]) -> Result<()> {
         for expected in ident {
             match tri!(self.next_char()) {
@@ -852,6 +955,28 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
+    /// Parse a `NaN`, `Infinity`, or `-Infinity` literal, of which the sign
+    /// and `first` letter have been consumed.
+    fn parse_nan_or_inf(&mut self, negative: bool, first: u8) -> Result<f64> {
+        let mut buf = [0u8; 8];
+        buf[0] = first;
+        let mut len = 1;
+        while let c @ (b'a'..=b'z' | b'A'..=b'Z') = tri!(self.peek_or_null()) {
+            if len == buf.len() {
+                return Err(self.peek_error(ErrorCode::ExpectedSomeIdent));
+            }
+            self.eat_char();
+            buf[len] = c;
+            len += 1;
+        }
+        match (negative, &buf[..len]) {
+            (false, b"NaN") => Ok(f64::NAN),
+            (false, b"Infinity") => Ok(f64::INFINITY),
+            (true, b"Infinity") => Ok(f64::NEG_INFINITY),
+            _ => Err(self.error(ErrorCode::ExpectedSomeIdent)),
+        }
+    }
+
     #[cfg(not(feature = "arbitrary_precision"))]
     fn parse_any_number(&mut self, positive: bool) -> Result<ParserNumber> {
         self.parse_integer(positive)
@@ -864,11 +989,18 @@ impl<'de, R: Read<'de>> Deserializer<R> {
             buf.push('-');
         }
         self.scan_integer(&mut buf)?;
+        if !self.raw_numbers && buf.bytes().any(|b| matches!(b, b'.' | b'e' | b'E')) {
+            return match buf.parse::<f64>() {
+                Ok(value) if value.is_finite() || self.allow_nan => Ok(ParserNumber::F64(value)),
+                _ => Err(self.error(ErrorCode::NumberOutOfRange)),
+            };
+        }
         if positive {
             if let Ok(unsigned) = buf.parse() {
                 return Ok(ParserNumber::U64(unsigned));
             }
-        } else {
+        } else if buf != "-0" {
+            // `-0` is given as written so that its sign is not lost
             if let Ok(signed) = buf.parse() {
                 return Ok(ParserNumber::I64(signed));
             }
@@ -1317,8 +1449,19 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
                 return Err(self.peek_error(ErrorCode::EofWhileParsingValue));
             }
         };
+        let start = self.read.byte_offset();
+        self.value_start = start;
 
         let value = match peek {
+            b'n' if self.allow_nan => {
+                self.eat_char();
+                if tri!(self.peek_or_null()) == b'u' {
+                    tri!(self.parse_ident(b"ull"));
+                    visitor.visit_unit()
+                } else {
+                    visitor.visit_f64(tri!(self.parse_nan_or_inf(false, b'n')))
+                }
+            }
             b'n' => {
                 self.eat_char();
                 tri!(self.parse_ident(b"ull"));
@@ -1336,13 +1479,23 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
             }
             b'-' => {
                 self.eat_char();
-                tri!(self.parse_any_number(false)).visit(visitor)
+                match tri!(self.peek_or_null()) {
+                    c @ (b'N' | b'n' | b'I' | b'i') if self.allow_nan => {
+                        self.eat_char();
+                        visitor.visit_f64(tri!(self.parse_nan_or_inf(true, c)))
+                    }
+                    _ => tri!(self.parse_any_number(false)).visit(visitor),
+                }
             }
             b'0'..=b'9' => tri!(self.parse_any_number(true)).visit(visitor),
+            c @ (b'N' | b'I' | b'i') if self.allow_nan => {
+                self.eat_char();
+                visitor.visit_f64(tri!(self.parse_nan_or_inf(false, c)))
+            }
             b'"' => {
                 self.eat_char();
                 self.scratch.clear();
-                match tri!(self.read.parse_str(&mut self.scratch)) {
+                match tri!(self.parse_str_value()) {
                     Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
                     Reference::Copied(s) => visitor.visit_str(s),
                 }
@@ -1373,13 +1526,15 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
         };
 
         match value {
-            Ok(value) => Ok(value),
+            Ok(value) => {
+                self.value_start = start;
+                Ok(value)
+            }
             // The de::Error impl creates errors with unknown line and column.
-            // Fill in the position here by looking at the current index in the
-            // input. There is no way to tell whether this should call `error`
-            // or `peek_error` so pick the one that seems correct more often.
-            // Worst case, the position is off by one character.
-            Err(err) => Err(self.fix_position(err)),
+            // Fill in the position as that of the value, or of the element or
+            // key of it that the visitor last had, as an error about it, e.g.,
+            // that it exceeds a limit, is found once it has been read.
+            Err(err) => Err(self.fix_value_position(err)),
         }
     }
 
@@ -1523,7 +1678,7 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut Deserializer<R> {
             b'"' => {
                 self.eat_char();
                 self.scratch.clear();
-                match tri!(self.read.parse_str(&mut self.scratch)) {
+                match tri!(self.parse_str_value()) {
                     Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
                     Reference::Copied(s) => visitor.visit_str(s),
                 }
@@ -1939,6 +2094,7 @@ impl<'de, 'a, R: Read<'de> + 'a> de::SeqAccess<'de> for SeqAccess<'a, R> {
         };
 
         match peek {
+            Some(b']') if self.de.jsonc => Ok(None),
             Some(b']') => Err(self.de.peek_error(ErrorCode::TrailingComma)),
             Some(_) => Ok(Some(tri!(seed.deserialize(&mut *self.de)))),
             None => Err(self.de.peek_error(ErrorCode::EofWhileParsingValue)),
@@ -1986,7 +2142,11 @@ impl<'de, 'a, R: Read<'de> + 'a> de::MapAccess<'de> for MapAccess<'a, R> {
         };
 
         match peek {
-            Some(b'"') => seed.deserialize(MapKey { de: &mut *self.de }).map(Some),
+            Some(b'"') => {
+                self.de.value_start = self.de.read.byte_offset();
+                seed.deserialize(MapKey { de: &mut *self.de }).map(Some)
+            }
+            Some(b'}') if self.de.jsonc => Ok(None),
             Some(b'}') => Err(self.de.peek_error(ErrorCode::TrailingComma)),
             Some(_) => Err(self.de.peek_error(ErrorCode::KeyMustBeAString)),
             None => Err(self.de.peek_error(ErrorCode::EofWhileParsingValue)),
@@ -1999,7 +2159,11 @@ impl<'de, 'a, R: Read<'de> + 'a> de::MapAccess<'de> for MapAccess<'a, R> {
     {
         tri!(self.de.parse_object_colon());
 
-        seed.deserialize(&mut *self.de)
+        // an error about the member once its value is read is at its key
+        let key_start = self.de.value_start;
+        let value = tri!(seed.deserialize(&mut *self.de));
+        self.de.value_start = key_start;
+        Ok(value)
     }
 }
 
@@ -2131,7 +2295,7 @@ macro_rules! deserialize_integer_key {
         {
             self.de.eat_char();
             self.de.scratch.clear();
-            let string = tri!(self.de.read.parse_str(&mut self.de.scratch));
+            let string = tri!(self.de.parse_str_value());
             match (string.parse(), string) {
                 (Ok(integer), _) => visitor.$visit(integer),
                 (Err(_), Reference::Borrowed(s)) => visitor.visit_borrowed_str(s),
@@ -2154,7 +2318,7 @@ where
     {
         self.de.eat_char();
         self.de.scratch.clear();
-        match tri!(self.de.read.parse_str(&mut self.de.scratch)) {
+        match tri!(self.de.parse_str_value()) {
             Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
             Reference::Copied(s) => visitor.visit_str(s),
         }
@@ -42,6 +42,12 @@ impl Error {
         self.err.column
     }
 
+    /// Description of the error without the line and column that its
+    /// `Display` implementation appends.
+    pub fn message(&self) -> String {
+        self.err.code.to_string()
+    }
+
     /// Categorizes the cause of this error.
     ///
     /// - `Category::Io` - failure to read or write bytes on an IO stream
@@ -283,33 +289,35 @@ impl Error {
 }
 
 impl Display for ErrorCode {
+    // worded as yyjson words them, so that orjson reports an error the same
+    // way whichever parser it uses
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             ErrorCode::Message(msg) => f.write_str(msg),
             ErrorCode::Io(err) => Display::fmt(err, f),
-            ErrorCode::EofWhileParsingList => f.write_str("EOF while parsing a list"),
-            ErrorCode::EofWhileParsingObject => f.write_str("EOF while parsing an object"),
-            ErrorCode::EofWhileParsingString => f.write_str("EOF while parsing a string"),
-            ErrorCode::EofWhileParsingValue => f.write_str("EOF while parsing a value"),
-            ErrorCode::ExpectedColon => f.write_str("expected `:`"),
-            ErrorCode::ExpectedListCommaOrEnd => f.write_str("expected `,` or `]`"),
-            ErrorCode::ExpectedObjectCommaOrEnd => f.write_str("expected `,` or `}`"),
-            ErrorCode::ExpectedSomeIdent => f.write_str("expected ident"),
-            ErrorCode::ExpectedSomeValue => f.write_str("expected value"),
-            ErrorCode::InvalidEscape => f.write_str("invalid escape"),
+            ErrorCode::EofWhileParsingList
+            | ErrorCode::EofWhileParsingObject
+            | ErrorCode::EofWhileParsingValue => f.write_str("unexpected end of data"),
+            ErrorCode::EofWhileParsingString => f.write_str("unclosed string"),
+            ErrorCode::ExpectedColon
+            | ErrorCode::ExpectedListCommaOrEnd
+            | ErrorCode::ExpectedObjectCommaOrEnd
+            | ErrorCode::ExpectedSomeValue
+            | ErrorCode::KeyMustBeAString => f.write_str("unexpected character"),
+            ErrorCode::ExpectedSomeIdent => f.write_str("invalid literal"),
+            ErrorCode::InvalidEscape => f.write_str("invalid escaped character in string"),
             ErrorCode::InvalidNumber => f.write_str("invalid number"),
-            ErrorCode::NumberOutOfRange => f.write_str("number out of range"),
-            ErrorCode::InvalidUnicodeCodePoint => f.write_str("invalid unicode code point"),
+            ErrorCode::NumberOutOfRange => f.write_str("number is infinity when parsed as double"),
+            ErrorCode::InvalidUnicodeCodePoint => f.write_str("invalid utf-8 encoding in string"),
             ErrorCode::ControlCharacterWhileParsingString => {
-                f.write_str("control character (\\u0000-\\u001F) found while parsing a string")
+                f.write_str("unexpected control character in string")
             }
-            ErrorCode::KeyMustBeAString => f.write_str("key must be a string"),
             ErrorCode::LoneLeadingSurrogateInHexEscape => {
-                f.write_str("lone leading surrogate in hex escape")
+                f.write_str("no matched low surrogate in string")
             }
-            ErrorCode::TrailingComma => f.write_str("trailing comma"),
-            ErrorCode::TrailingCharacters => f.write_str("trailing characters"),
-            ErrorCode::UnexpectedEndOfHexEscape => f.write_str("unexpected end of hex escape"),
+            ErrorCode::TrailingComma => f.write_str("trailing comma is not allowed"),
+            ErrorCode::TrailingCharacters => f.write_str("unexpected content after document"),
+            ErrorCode::UnexpectedEndOfHexEscape => f.write_str("invalid escaped unicode in string"),
             ErrorCode::RecursionLimitExceeded => f.write_str("recursion limit exceeded"),
         }
     }
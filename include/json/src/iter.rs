@@ -17,8 +17,20 @@ pub struct LineColIterator<I> {
     /// computation of the current line, column, and byte offset while only
     /// updating one of the counters in `next()` in the common case.
     start_of_line: usize,
+
+    /// Characters read, counting each byte that does not continue a UTF-8
+    /// sequence, and those before the current line.
+    chars: usize,
+    start_of_line_chars: usize,
+
+    /// The last bytes read, each at its byte offset modulo `TAIL_LEN`, to
+    /// show where an error is in input that is not kept.
+    tail: [u8; TAIL_LEN],
 }
 
+/// Bytes kept by `LineColIterator`.
+pub const TAIL_LEN: usize = 256;
+
 impl<I> LineColIterator<I>
 where
     I: Iterator<Item = io::Result<u8>>,
@@ -29,6 +41,9 @@ where
             line: 1,
             col: 0,
             start_of_line: 0,
+            chars: 0,
+            start_of_line_chars: 0,
+            tail: [0; TAIL_LEN],
         }
     }
 
@@ -43,6 +58,21 @@ where
     pub fn byte_offset(&self) -> usize {
         self.start_of_line + self.col
     }
+
+    pub fn chars(&self) -> usize {
+        self.chars
+    }
+
+    pub fn start_of_line_chars(&self) -> usize {
+        self.start_of_line_chars
+    }
+
+    /// The last bytes read, at most `TAIL_LEN`.
+    pub fn tail(&self) -> Vec<u8> {
+        let end = self.byte_offset();
+        let start = end.saturating_sub(TAIL_LEN);
+        (start..end).map(|idx| self.tail[idx % TAIL_LEN]).collect()
+    }
 }
 
 impl<I> Iterator for LineColIterator<I>
@@ -55,13 +85,18 @@ where
         match self.iter.next() {
             None => None,
             Some(Ok(b'\n')) => {
+                self.tail[self.byte_offset() % TAIL_LEN] = b'\n';
                 self.start_of_line += self.col + 1;
                 self.line += 1;
                 self.col = 0;
+                self.chars += 1;
+                self.start_of_line_chars = self.chars;
                 Some(Ok(b'\n'))
             }
             Some(Ok(c)) => {
+                self.tail[self.byte_offset() % TAIL_LEN] = c;
                 self.col += 1;
+                self.chars += (c & 0xc0 != 0x80) as usize;
                 Some(Ok(c))
             }
             Some(Err(e)) => Some(Err(e)),
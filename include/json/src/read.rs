@@ -17,6 +17,19 @@ use crate::raw::OwnedRawDeserializer;
 #[cfg(feature = "raw_value")]
 use serde::de::Visitor;
 
+/// What to do with an escaped surrogate that is not part of a pair, e.g.,
+/// `"\uD800"`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum LoneSurrogates {
+    /// Fail, as it is not a character.
+    Error,
+    /// Read it as U+FFFD REPLACEMENT CHARACTER.
+    Replace,
+    /// Read it as the three bytes UTF-8 would encode it as if it were a
+    /// character, as WTF-8 does.
+    Encode,
+}
+
 /// Trait used by the deserializer for iterating over input. This is manually
 /// "specialized" for iterating over &[u8]. Once feature(specialization) is
 /// stable we can use actual specialization.
@@ -58,6 +71,14 @@ pub trait Read<'de>: private::Sealed {
     #[doc(hidden)]
     fn byte_offset(&self) -> usize;
 
+    /// Position of the byte at `offset`, which has been read. A reader that
+    /// does not keep its input gives the current position for a byte before
+    /// the current line.
+    ///
+    /// Only called in case of an error, so performance is not important.
+    #[doc(hidden)]
+    fn position_of_offset(&self, offset: usize) -> Position;
+
     /// Assumes the previous byte was a quotation mark. Parses a JSON-escaped
     /// string until the next quotation mark using the given scratch space if
     /// necessary. The scratch space is initially empty.
@@ -76,6 +97,17 @@ pub trait Read<'de>: private::Sealed {
         scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'de, 's, [u8]>>;
 
+    /// Parses a string as `parse_str` does, but with an escaped surrogate
+    /// that is not part of a pair handled as `lone` says. With
+    /// `LoneSurrogates::Encode`, the returned string is not valid UTF-8 if it
+    /// has one.
+    #[doc(hidden)]
+    fn parse_str_lone_surrogates<'s>(
+        &'s mut self,
+        scratch: &'s mut Vec<u8>,
+        lone: LoneSurrogates,
+    ) -> Result<Reference<'de, 's, str>>;
+
     /// Assumes the previous byte was a quotation mark. Parses a JSON-escaped
     /// string until the next quotation mark but discards the data.
     #[doc(hidden)]
@@ -202,6 +234,40 @@ where
 #[cfg(feature = "std")]
 impl<R> private::Sealed for IoRead<R> where R: io::Read {}
 
+/// Where an `IoRead` is in its input, to locate an error in input that is
+/// not kept. Characters are counted as the bytes that do not continue a UTF-8
+/// sequence.
+#[cfg(feature = "std")]
+pub struct ReadPosition {
+    /// Bytes read.
+    pub bytes: usize,
+    /// Characters read.
+    pub chars: usize,
+    /// Bytes read before the current line.
+    pub line_start: usize,
+    /// Characters read before the current line.
+    pub line_start_chars: usize,
+    /// The last bytes read, at most 256.
+    pub tail: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<R> IoRead<R>
+where
+    R: io::Read,
+{
+    /// Where the reader is in its input, including any byte peeked.
+    pub fn read_position(&self) -> ReadPosition {
+        ReadPosition {
+            bytes: self.iter.byte_offset(),
+            chars: self.iter.chars(),
+            line_start: self.iter.byte_offset() - self.iter.col(),
+            line_start_chars: self.iter.start_of_line_chars(),
+            tail: self.iter.tail(),
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 impl<R> IoRead<R>
 where
@@ -211,6 +277,7 @@ where
         &'s mut self,
         scratch: &'s mut Vec<u8>,
         validate: bool,
+        lone: LoneSurrogates,
         result: F,
     ) -> Result<T>
     where
@@ -228,7 +295,7 @@ where
                     return result(self, scratch);
                 }
                 b'\\' => {
-                    tri!(parse_escape(self, validate, scratch));
+                    tri!(parse_escape(self, lone, scratch));
                 }
                 _ => {
                     if validate {
@@ -324,8 +391,19 @@ where
         }
     }
 
+    fn position_of_offset(&self, offset: usize) -> Position {
+        let line_start = self.iter.byte_offset() - self.iter.col();
+        if offset < line_start {
+            return self.position();
+        }
+        Position {
+            line: self.iter.line(),
+            column: offset - line_start + 1,
+        }
+    }
+
     fn parse_str<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'de, 's, str>> {
-        self.parse_str_bytes(scratch, true, as_str)
+        self.parse_str_bytes(scratch, true, LoneSurrogates::Error, as_str)
             .map(Reference::Copied)
     }
 
@@ -333,10 +411,21 @@ where
         &'s mut self,
         scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'de, 's, [u8]>> {
-        self.parse_str_bytes(scratch, false, |_, bytes| Ok(bytes))
+        self.parse_str_bytes(scratch, false, LoneSurrogates::Encode, |_, bytes| Ok(bytes))
             .map(Reference::Copied)
     }
 
+    fn parse_str_lone_surrogates<'s>(
+        &'s mut self,
+        scratch: &'s mut Vec<u8>,
+        lone: LoneSurrogates,
+    ) -> Result<Reference<'de, 's, str>> {
+        self.parse_str_bytes(scratch, true, lone, |read, bytes| {
+            as_str_lone_surrogates(read, bytes, lone)
+        })
+        .map(Reference::Copied)
+    }
+
     fn ignore_str(&mut self) -> Result<()> {
         loop {
             let ch = tri!(next_or_eof(self));
@@ -435,6 +524,7 @@ impl<'a> SliceRead<'a> {
         &'s mut self,
         scratch: &'s mut Vec<u8>,
         validate: bool,
+        lone: LoneSurrogates,
         result: F,
     ) -> Result<Reference<'a, 's, T>>
     where
@@ -468,7 +558,7 @@ impl<'a> SliceRead<'a> {
                 b'\\' => {
                     scratch.extend_from_slice(&self.slice[start..self.index]);
                     self.index += 1;
-                    tri!(parse_escape(self, validate, scratch));
+                    tri!(parse_escape(self, lone, scratch));
                     start = self.index;
                 }
                 _ => {
@@ -528,15 +618,29 @@ impl<'a> Read<'a> for SliceRead<'a> {
         self.index
     }
 
+    fn position_of_offset(&self, offset: usize) -> Position {
+        self.position_of_index(cmp::min(self.slice.len(), offset + 1))
+    }
+
     fn parse_str<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'a, 's, str>> {
-        self.parse_str_bytes(scratch, true, as_str)
+        self.parse_str_bytes(scratch, true, LoneSurrogates::Error, as_str)
     }
 
     fn parse_str_raw<'s>(
         &'s mut self,
         scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'a, 's, [u8]>> {
-        self.parse_str_bytes(scratch, false, |_, bytes| Ok(bytes))
+        self.parse_str_bytes(scratch, false, LoneSurrogates::Encode, |_, bytes| Ok(bytes))
+    }
+
+    fn parse_str_lone_surrogates<'s>(
+        &'s mut self,
+        scratch: &'s mut Vec<u8>,
+        lone: LoneSurrogates,
+    ) -> Result<Reference<'a, 's, str>> {
+        self.parse_str_bytes(scratch, true, lone, |read, bytes| {
+            as_str_lone_surrogates(read, bytes, lone)
+        })
     }
 
     fn ignore_str(&mut self) -> Result<()> {
@@ -655,11 +759,28 @@ impl<'a> Read<'a> for StrRead<'a> {
         self.delegate.byte_offset()
     }
 
+    fn position_of_offset(&self, offset: usize) -> Position {
+        self.delegate.position_of_offset(offset)
+    }
+
     fn parse_str<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'a, 's, str>> {
-        self.delegate.parse_str_bytes(scratch, true, |_, bytes| {
-            // The deserialization input came in as &str with a UTF-8 guarantee,
-            // and the \u-escapes are checked along the way, so don't need to
-            // check here.
+        self.delegate
+            .parse_str_bytes(scratch, true, LoneSurrogates::Error, |_, bytes| {
+                // The deserialization input came in as &str with a UTF-8 guarantee,
+                // and the \u-escapes are checked along the way, so don't need to
+                // check here.
+                Ok(unsafe { str::from_utf8_unchecked(bytes) })
+            })
+    }
+
+    fn parse_str_lone_surrogates<'s>(
+        &'s mut self,
+        scratch: &'s mut Vec<u8>,
+        lone: LoneSurrogates,
+    ) -> Result<Reference<'a, 's, str>> {
+        self.delegate.parse_str_bytes(scratch, true, lone, |_, bytes| {
+            // As for `parse_str`, only a surrogate encoded from an escape is
+            // not valid UTF-8.
             Ok(unsafe { str::from_utf8_unchecked(bytes) })
         })
     }
@@ -736,6 +857,10 @@ where
         R::byte_offset(self)
     }
 
+    fn position_of_offset(&self, offset: usize) -> Position {
+        R::position_of_offset(self, offset)
+    }
+
     fn parse_str<'s>(&'s mut self, scratch: &'s mut Vec<u8>) -> Result<Reference<'de, 's, str>> {
         R::parse_str(self, scratch)
     }
@@ -747,6 +872,14 @@ where
         R::parse_str_raw(self, scratch)
     }
 
+    fn parse_str_lone_surrogates<'s>(
+        &'s mut self,
+        scratch: &'s mut Vec<u8>,
+        lone: LoneSurrogates,
+    ) -> Result<Reference<'de, 's, str>> {
+        R::parse_str_lone_surrogates(self, scratch, lone)
+    }
+
     fn ignore_str(&mut self) -> Result<()> {
         R::ignore_str(self)
     }
@@ -838,15 +971,44 @@ where
     Err(Error::syntax(reason, position.line, position.column))
 }
 
+// Strings read from a reader are not validated up front, so this is the
+// validation of each, which for long strings is faster with SIMD.
 fn as_str<'de, 's, R: Read<'de>>(read: &R, slice: &'s [u8]) -> Result<&'s str> {
-    str::from_utf8(slice).or_else(|_| error(read, ErrorCode::InvalidUnicodeCodePoint))
+    simdutf8::basic::from_utf8(slice).or_else(|_| error(read, ErrorCode::InvalidUnicodeCodePoint))
+}
+
+/// Validates a string read with `lone`. If it is `LoneSurrogates::Encode`, the
+/// three bytes of a surrogate, `ED A0..=BF 80..=BF`, are accepted among the
+/// valid UTF-8 and the string returned is not a valid `str` if it has one.
+fn as_str_lone_surrogates<'de, 's, R: Read<'de>>(
+    read: &R,
+    slice: &'s [u8],
+    lone: LoneSurrogates,
+) -> Result<&'s str> {
+    if lone != LoneSurrogates::Encode {
+        return as_str(read, slice);
+    }
+    let mut rest = slice;
+    loop {
+        match str::from_utf8(rest) {
+            Ok(_) => return Ok(unsafe { str::from_utf8_unchecked(slice) }),
+            Err(err) => {
+                let valid = err.valid_up_to();
+                match rest[valid..] {
+                    [0xED, 0xA0..=0xBF, 0x80..=0xBF, ..] => rest = &rest[valid + 3..],
+                    _ => return error(read, ErrorCode::InvalidUnicodeCodePoint),
+                }
+            }
+        }
+    }
 }
 
 /// Parses a JSON escape sequence and appends it into the scratch space. Assumes
-/// the previous byte read was a backslash.
+/// the previous byte read was a backslash. An escaped surrogate that is not
+/// part of a pair is handled as `lone` says.
 fn parse_escape<'de, R: Read<'de>>(
     read: &mut R,
-    validate: bool,
+    lone: LoneSurrogates,
     scratch: &mut Vec<u8>,
 ) -> Result<()> {
     let ch = tri!(next_or_eof(read));
@@ -869,69 +1031,86 @@ fn parse_escape<'de, R: Read<'de>>(
                 ]);
             }
 
-            let c = match tri!(read.decode_hex_escape()) {
-                n @ 0xDC00..=0xDFFF => {
-                    return if validate {
-                        error(read, ErrorCode::LoneLeadingSurrogateInHexEscape)
-                    } else {
-                        encode_surrogate(scratch, n);
-                        Ok(())
-                    };
+            fn lone_surrogate(scratch: &mut Vec<u8>, n: u16, lone: LoneSurrogates) {
+                match lone {
+                    LoneSurrogates::Replace => scratch.extend_from_slice("\u{FFFD}".as_bytes()),
+                    _ => encode_surrogate(scratch, n),
                 }
+            }
 
-                // Non-BMP characters are encoded as a sequence of two hex
-                // escapes, representing UTF-16 surrogates. If deserializing a
-                // utf-8 string the surrogates are required to be paired,
-                // whereas deserializing a byte string accepts lone surrogates.
-                n1 @ 0xD800..=0xDBFF => {
-                    if tri!(peek_or_eof(read)) == b'\\' {
-                        read.discard();
-                    } else {
-                        return if validate {
+            let mut n = tri!(read.decode_hex_escape());
+            // A high surrogate followed by an escape of anything but a low
+            // surrogate is lone, and the escape after it is parsed in turn.
+            let c = loop {
+                match n {
+                    0xDC00..=0xDFFF => {
+                        if lone == LoneSurrogates::Error {
+                            return error(read, ErrorCode::LoneLeadingSurrogateInHexEscape);
+                        }
+                        lone_surrogate(scratch, n, lone);
+                        return Ok(());
+                    }
+
+                    // Non-BMP characters are encoded as a sequence of two hex
+                    // escapes, representing UTF-16 surrogates. If deserializing a
+                    // utf-8 string the surrogates are required to be paired,
+                    // unless lone surrogates are replaced or encoded, as they
+                    // are when deserializing a byte string.
+                    n1 @ 0xD800..=0xDBFF => {
+                        if tri!(peek_or_eof(read)) == b'\\' {
                             read.discard();
-                            error(read, ErrorCode::UnexpectedEndOfHexEscape)
                         } else {
-                            encode_surrogate(scratch, n1);
-                            Ok(())
-                        };
-                    }
+                            return if lone == LoneSurrogates::Error {
+                                read.discard();
+                                error(read, ErrorCode::UnexpectedEndOfHexEscape)
+                            } else {
+                                lone_surrogate(scratch, n1, lone);
+                                Ok(())
+                            };
+                        }
 
-                    if tri!(peek_or_eof(read)) == b'u' {
-                        read.discard();
-                    } else {
-                        return if validate {
+                        if tri!(peek_or_eof(read)) == b'u' {
                             read.discard();
-                            error(read, ErrorCode::UnexpectedEndOfHexEscape)
                         } else {
-                            encode_surrogate(scratch, n1);
-                            // The \ prior to this byte started an escape sequence,
-                            // so we need to parse that now. This recursive call
-                            // does not blow the stack on malicious input because
-                            // the escape is not \u, so it will be handled by one
-                            // of the easy nonrecursive cases.
-                            parse_escape(read, validate, scratch)
-                        };
-                    }
+                            return if lone == LoneSurrogates::Error {
+                                read.discard();
+                                error(read, ErrorCode::UnexpectedEndOfHexEscape)
+                            } else {
+                                lone_surrogate(scratch, n1, lone);
+                                // The \ prior to this byte started an escape sequence,
+                                // so we need to parse that now. This recursive call
+                                // does not blow the stack on malicious input because
+                                // the escape is not \u, so it will be handled by one
+                                // of the easy nonrecursive cases.
+                                parse_escape(read, lone, scratch)
+                            };
+                        }
 
-                    let n2 = tri!(read.decode_hex_escape());
+                        let n2 = tri!(read.decode_hex_escape());
 
-                    if n2 < 0xDC00 || n2 > 0xDFFF {
-                        return error(read, ErrorCode::LoneLeadingSurrogateInHexEscape);
-                    }
+                        if n2 < 0xDC00 || n2 > 0xDFFF {
+                            if lone == LoneSurrogates::Error {
+                                return error(read, ErrorCode::LoneLeadingSurrogateInHexEscape);
+                            }
+                            lone_surrogate(scratch, n1, lone);
+                            n = n2;
+                            continue;
+                        }
 
-                    let n = (((n1 - 0xD800) as u32) << 10 | (n2 - 0xDC00) as u32) + 0x1_0000;
+                        let n = (((n1 - 0xD800) as u32) << 10 | (n2 - 0xDC00) as u32) + 0x1_0000;
 
-                    match char::from_u32(n) {
-                        Some(c) => c,
-                        None => {
-                            return error(read, ErrorCode::InvalidUnicodeCodePoint);
+                        match char::from_u32(n) {
+                            Some(c) => break c,
+                            None => {
+                                return error(read, ErrorCode::InvalidUnicodeCodePoint);
+                            }
                         }
                     }
-                }
 
-                // Every u16 outside of the surrogate ranges above is guaranteed
-                // to be a legal char.
-                n => char::from_u32(n as u32).unwrap(),
+                    // Every u16 outside of the surrogate ranges above is guaranteed
+                    // to be a legal char.
+                    n => break char::from_u32(n as u32).unwrap(),
+                }
             };
 
             scratch.extend_from_slice(c.encode_utf8(&mut [0_u8; 4]).as_bytes());
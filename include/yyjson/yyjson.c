@@ -3300,6 +3300,7 @@ static const f64 f64_pow10_table[] = {
 static_inline bool read_number(u8 **ptr,
                                u8 **pre,
                                bool ext,
+                               bool big,
                                yyjson_val *val,
                                const char **msg) {
     
@@ -3327,6 +3328,13 @@ static_inline bool read_number(u8 **ptr,
     *end = cur; return true; \
 } while (false)
     
+#define return_bignum_raw() do { \
+    if (*pre) **pre = '\0'; /* add null-terminator for previous raw string */ \
+    val->tag = ((u64)(cur - hdr) << YYJSON_TAG_BIT) | YYJSON_TYPE_RAW; \
+    val->uni.str = (const char *)hdr; \
+    *pre = cur; *end = cur; return true; \
+} while (false)
+    
 #define return_inf() do { \
     if (unlikely(ext)) return_f64_raw(F64_RAW_INF); \
     else return_err(hdr, "number is infinity when parsed as double"); \
@@ -3351,7 +3359,7 @@ static_inline bool read_number(u8 **ptr,
     bool sign;
     
     /* read number as raw string if has flag */
-    if (unlikely(pre)) {
+    if (unlikely(pre && !big)) {
         return read_number_raw(ptr, pre, ext, val, msg);
     }
     
@@ -3420,6 +3428,7 @@ static_inline bool read_number(u8 **ptr,
     if (!digi_is_digit_or_fp(*cur)) {
         /* this number is an integer consisting of 19 digits */
         if (sign && (sig > ((u64)1 << 63))) { /* overflow */
+            if (unlikely(big)) return_bignum_raw();
             return_f64(normalized_u64_to_f64(sig));
         }
         return_i64(sig);
@@ -3472,12 +3481,25 @@ digi_intg_more:
                 sig = num + sig * 10;
                 cur++;
                 /* convert to double if overflow */
-                if (sign) return_f64(normalized_u64_to_f64(sig));
+                if (sign) {
+                    if (unlikely(big)) return_bignum_raw();
+                    return_f64(normalized_u64_to_f64(sig));
+                }
                 return_i64(sig);
             }
         }
     }
     
+    /* an integer too large to fit in u64 */
+    if (unlikely(big)) {
+        tmp = cur;
+        while (digi_is_digit(*tmp)) tmp++;
+        if (!digi_is_fp(*tmp)) {
+            cur = tmp;
+            return_bignum_raw();
+        }
+    }
+    
     if (digi_is_exp(*cur)) {
         dot_pos = cur;
         goto digi_exp_more;
@@ -3876,6 +3898,7 @@ digi_finish:
 #undef return_i64
 #undef return_f64
 #undef return_f64_raw
+#undef return_bignum_raw
 }
 
 
@@ -3890,6 +3913,7 @@ digi_finish:
 static_noinline bool read_number(u8 **ptr,
                                  u8 **pre,
                                  bool ext,
+                                 bool big,
                                  yyjson_val *val,
                                  const char **msg) {
     
@@ -3917,6 +3941,13 @@ static_noinline bool read_number(u8 **ptr,
     *end = cur; return true; \
 } while (false)
     
+#define return_bignum_raw() do { \
+    if (*pre) **pre = '\0'; /* add null-terminator for previous raw string */ \
+    val->tag = ((u64)(cur - hdr) << YYJSON_TAG_BIT) | YYJSON_TYPE_RAW; \
+    val->uni.str = (const char *)hdr; \
+    *pre = cur; *end = cur; return true; \
+} while (false)
+    
     u64 sig, num;
     u8 *hdr = *ptr;
     u8 *cur = *ptr;
@@ -3926,7 +3957,7 @@ static_noinline bool read_number(u8 **ptr,
     bool sign;
     
     /* read number as raw string if has flag */
-    if (unlikely(pre)) {
+    if (unlikely(pre && !big)) {
         return read_number_raw(ptr, pre, ext, val, msg);
     }
     
@@ -3969,7 +4000,10 @@ static_noinline bool read_number(u8 **ptr,
             (sig == (U64_MAX / 10) && num <= (U64_MAX % 10))) {
             sig = num + sig * 10;
             cur++;
-            if (sign) return_f64(normalized_u64_to_f64(sig));
+            if (sign) {
+                if (unlikely(big)) return_bignum_raw();
+                return_f64(normalized_u64_to_f64(sig));
+            }
             return_i64(sig);
         }
     }
@@ -3979,6 +4013,7 @@ intg_end:
     if (!digi_is_digit_or_fp(*cur)) {
         /* this number is an integer consisting of 1 to 19 digits */
         if (sign && (sig > ((u64)1 << 63))) {
+            if (unlikely(big)) return_bignum_raw();
             return_f64(normalized_u64_to_f64(sig));
         }
         return_i64(sig);
@@ -3987,6 +4022,10 @@ intg_end:
 read_double:
     /* this number should be read as double */
     while (digi_is_digit(*cur)) cur++;
+    if (unlikely(big) && !digi_is_fp(*cur)) {
+        /* an integer too large to fit in u64 */
+        return_bignum_raw();
+    }
     if (*cur == '.') {
         /* skip fraction part */
         dot = cur;
@@ -4041,6 +4080,7 @@ read_double:
 #undef return_err
 #undef return_i64
 #undef return_f64
+#undef return_bignum_raw
 }
 
 #endif /* FP_READER */
@@ -4055,14 +4095,14 @@ read_double:
  Read a JSON string.
  @param ptr The head pointer of string before '"' prefix (inout).
  @param lst JSON last position.
- @param inv Allow invalid unicode.
+ @param flg The read flags, for invalid unicode and lone surrogates.
  @param val The string value to be written.
  @param msg The error message pointer.
  @return Whether success.
  */
 static_inline bool read_string(u8 **ptr,
                                u8 *lst,
-                               bool inv,
+                               yyjson_read_flag flg,
                                yyjson_val *val,
                                const char **msg) {
     /*
@@ -4201,6 +4241,7 @@ static_inline bool read_string(u8 **ptr,
     u8 *src = ++cur, *dst, *pos;
     u16 hi, lo;
     u32 uni, tmp;
+    bool inv = (flg & YYJSON_READ_ALLOW_INVALID_UNICODE) != 0;
     
 skip_ascii:
     /* Most strings have no escaped characters, so we can jump them quickly. */
@@ -4316,18 +4357,11 @@ copy_escape:
                     } else {
                         *dst++ = (u8)hi;
                     }
-                } else {
+                } else if (likely((hi & 0xFC00) == 0xD800 &&
+                                  byte_match_2(src, "\\u") &&
+                                  read_hex_u16(src + 2, &lo) &&
+                                  (lo & 0xFC00) == 0xDC00)) {
                     /* a non-BMP character, represented as a surrogate pair */
-                    if (unlikely((hi & 0xFC00) != 0xD800)) {
-                        return_err(src - 6, "invalid high surrogate in string");
-                    }
-                    if (unlikely(!byte_match_2(src, "\\u")) ||
-                        unlikely(!read_hex_u16(src + 2, &lo))) {
-                        return_err(src, "no matched low surrogate in string");
-                    }
-                    if (unlikely((lo & 0xFC00) != 0xDC00)) {
-                        return_err(src, "invalid low surrogate in string");
-                    }
                     uni = ((((u32)hi - 0xD800) << 10) |
                             ((u32)lo - 0xDC00)) + 0x10000;
                     *dst++ = (u8)(0xF0 | (uni >> 18));
@@ -4335,6 +4369,23 @@ copy_escape:
                     *dst++ = (u8)(0x80 | ((uni >> 6) & 0x3F));
                     *dst++ = (u8)(0x80 | (uni & 0x3F));
                     src += 6;
+                } else if (flg & YYJSON_READ_REPLACE_LONE_SURROGATES) {
+                    /* a lone surrogate, replaced by U+FFFD */
+                    *dst++ = 0xEF;
+                    *dst++ = 0xBF;
+                    *dst++ = 0xBD;
+                } else if (flg & YYJSON_READ_ALLOW_LONE_SURROGATES) {
+                    /* a lone surrogate, encoded as if it were a character */
+                    *dst++ = (u8)(0xE0 | (hi >> 12));
+                    *dst++ = (u8)(0x80 | ((hi >> 6) & 0x3F));
+                    *dst++ = (u8)(0x80 | (hi & 0x3F));
+                } else if ((hi & 0xFC00) != 0xD800) {
+                    return_err(src - 6, "invalid high surrogate in string");
+                } else if (!byte_match_2(src, "\\u") ||
+                           !read_hex_u16(src + 2, &lo)) {
+                    return_err(src, "no matched low surrogate in string");
+                } else {
+                    return_err(src, "invalid low surrogate in string");
                 }
                 break;
             default: return_err(src, "invalid escaped character in string");
@@ -4545,8 +4596,8 @@ static_noinline yyjson_doc *read_root_single(u8 *hdr,
     const char *msg; /* error message */
     
     bool raw; /* read number as raw */
+    bool big; /* read integers too large for u64 and i64 as raw */
     bool ext; /* allow inf and nan */
-    bool inv; /* allow invalid unicode */
     u8 *raw_end; /* raw end for null-terminator */
     u8 **pre; /* previous raw end pointer */
     
@@ -4558,17 +4609,17 @@ static_noinline yyjson_doc *read_root_single(u8 *hdr,
     if (unlikely(!val_hdr)) goto fail_alloc;
     val = val_hdr + hdr_len;
     raw = (flg & YYJSON_READ_NUMBER_AS_RAW) != 0;
+    big = !raw && (flg & YYJSON_READ_BIGNUM_AS_RAW) != 0;
     ext = (flg & YYJSON_READ_ALLOW_INF_AND_NAN) != 0;
-    inv = (flg & YYJSON_READ_ALLOW_INVALID_UNICODE) != 0;
     raw_end = NULL;
-    pre = raw ? &raw_end : NULL;
+    pre = (raw || big) ? &raw_end : NULL;
     
     if (char_is_number(*cur)) {
-        if (likely(read_number(&cur, pre, ext, val, &msg))) goto doc_end;
+        if (likely(read_number(&cur, pre, ext, big, val, &msg))) goto doc_end;
         goto fail_number;
     }
     if (*cur == '"') {
-        if (likely(read_string(&cur, end, inv, val, &msg))) goto doc_end;
+        if (likely(read_string(&cur, end, flg, val, &msg))) goto doc_end;
         goto fail_string;
     }
     if (*cur == 't') {
@@ -4686,8 +4737,8 @@ static_inline yyjson_doc *read_root_minify(u8 *hdr,
     const char *msg; /* error message */
     
     bool raw; /* read number as raw */
+    bool big; /* read integers too large for u64 and i64 as raw */
     bool ext; /* allow inf and nan */
-    bool inv; /* allow invalid unicode */
     u8 *raw_end; /* raw end for null-terminator */
     u8 **pre; /* previous raw end pointer */
     
@@ -4705,10 +4756,10 @@ static_inline yyjson_doc *read_root_minify(u8 *hdr,
     ctn = val;
     ctn_len = 0;
     raw = (flg & YYJSON_READ_NUMBER_AS_RAW) != 0;
+    big = !raw && (flg & YYJSON_READ_BIGNUM_AS_RAW) != 0;
     ext = (flg & YYJSON_READ_ALLOW_INF_AND_NAN) != 0;
-    inv = (flg & YYJSON_READ_ALLOW_INVALID_UNICODE) != 0;
     raw_end = NULL;
-    pre = raw ? &raw_end : NULL;
+    pre = (raw || big) ? &raw_end : NULL;
     
     if (*cur++ == '{') {
         ctn->tag = YYJSON_TYPE_OBJ;
@@ -4746,13 +4797,13 @@ arr_val_begin:
     if (char_is_number(*cur)) {
         val_incr();
         ctn_len++;
-        if (likely(read_number(&cur, pre, ext, val, &msg))) goto arr_val_end;
+        if (likely(read_number(&cur, pre, ext, big, val, &msg))) goto arr_val_end;
         goto fail_number;
     }
     if (*cur == '"') {
         val_incr();
         ctn_len++;
-        if (likely(read_string(&cur, end, inv, val, &msg))) goto arr_val_end;
+        if (likely(read_string(&cur, end, flg, val, &msg))) goto arr_val_end;
         goto fail_string;
     }
     if (*cur == 't') {
@@ -4850,7 +4901,7 @@ obj_key_begin:
     if (likely(*cur == '"')) {
         val_incr();
         ctn_len++;
-        if (likely(read_string(&cur, end, inv, val, &msg))) goto obj_key_end;
+        if (likely(read_string(&cur, end, flg, val, &msg))) goto obj_key_end;
         goto fail_string;
     }
     if (likely(*cur == '}')) {
@@ -4888,13 +4939,13 @@ obj_val_begin:
     if (*cur == '"') {
         val++;
         ctn_len++;
-        if (likely(read_string(&cur, end, inv, val, &msg))) goto obj_val_end;
+        if (likely(read_string(&cur, end, flg, val, &msg))) goto obj_val_end;
         goto fail_string;
     }
     if (char_is_number(*cur)) {
         val++;
         ctn_len++;
-        if (likely(read_number(&cur, pre, ext, val, &msg))) goto obj_val_end;
+        if (likely(read_number(&cur, pre, ext, big, val, &msg))) goto obj_val_end;
         goto fail_number;
     }
     if (*cur == '{') {
@@ -5069,8 +5120,8 @@ static_inline yyjson_doc *read_root_pretty(u8 *hdr,
     const char *msg; /* error message */
     
     bool raw; /* read number as raw */
+    bool big; /* read integers too large for u64 and i64 as raw */
     bool ext; /* allow inf and nan */
-    bool inv; /* allow invalid unicode */
     u8 *raw_end; /* raw end for null-terminator */
     u8 **pre; /* previous raw end pointer */
     
@@ -5088,10 +5139,10 @@ static_inline yyjson_doc *read_root_pretty(u8 *hdr,
     ctn = val;
     ctn_len = 0;
     raw = (flg & YYJSON_READ_NUMBER_AS_RAW) != 0;
+    big = !raw && (flg & YYJSON_READ_BIGNUM_AS_RAW) != 0;
     ext = (flg & YYJSON_READ_ALLOW_INF_AND_NAN) != 0;
-    inv = (flg & YYJSON_READ_ALLOW_INVALID_UNICODE) != 0;
     raw_end = NULL;
-    pre = raw ? &raw_end : NULL;
+    pre = (raw || big) ? &raw_end : NULL;
     
     if (*cur++ == '{') {
         ctn->tag = YYJSON_TYPE_OBJ;
@@ -5144,13 +5195,13 @@ arr_val_begin:
     if (char_is_number(*cur)) {
         val_incr();
         ctn_len++;
-        if (likely(read_number(&cur, pre, ext, val, &msg))) goto arr_val_end;
+        if (likely(read_number(&cur, pre, ext, big, val, &msg))) goto arr_val_end;
         goto fail_number;
     }
     if (*cur == '"') {
         val_incr();
         ctn_len++;
-        if (likely(read_string(&cur, end, inv, val, &msg))) goto arr_val_end;
+        if (likely(read_string(&cur, end, flg, val, &msg))) goto arr_val_end;
         goto fail_string;
     }
     if (*cur == 't') {
@@ -5265,7 +5316,7 @@ obj_key_begin:
     if (likely(*cur == '"')) {
         val_incr();
         ctn_len++;
-        if (likely(read_string(&cur, end, inv, val, &msg))) goto obj_key_end;
+        if (likely(read_string(&cur, end, flg, val, &msg))) goto obj_key_end;
         goto fail_string;
     }
     if (likely(*cur == '}')) {
@@ -5307,13 +5358,13 @@ obj_val_begin:
     if (*cur == '"') {
         val++;
         ctn_len++;
-        if (likely(read_string(&cur, end, inv, val, &msg))) goto obj_val_end;
+        if (likely(read_string(&cur, end, flg, val, &msg))) goto obj_val_end;
         goto fail_string;
     }
     if (char_is_number(*cur)) {
         val++;
         ctn_len++;
-        if (likely(read_number(&cur, pre, ext, val, &msg))) goto obj_val_end;
+        if (likely(read_number(&cur, pre, ext, big, val, &msg))) goto obj_val_end;
         goto fail_number;
     }
     if (*cur == '{') {
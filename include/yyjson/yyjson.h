@@ -608,6 +608,19 @@ static const yyjson_read_flag YYJSON_READ_NUMBER_AS_RAW         = 1 << 5;
     risks. */
 static const yyjson_read_flag YYJSON_READ_ALLOW_INVALID_UNICODE = 1 << 6;
 
+/** Read integers that cannot be represented by int64_t and uint64_t as raw
+    strings (value with YYJSON_TYPE_RAW type) rather than as double. */
+static const yyjson_read_flag YYJSON_READ_BIGNUM_AS_RAW         = 1 << 7;
+
+/** Read an escaped surrogate that is not part of a pair, e.g., "\uD800", as
+    U+FFFD rather than reporting an error. */
+static const yyjson_read_flag YYJSON_READ_REPLACE_LONE_SURROGATES = 1 << 8;
+
+/** Read an escaped surrogate that is not part of a pair as the three bytes
+    UTF-8 would encode it as if it were a character, which is not valid UTF-8,
+    rather than reporting an error. */
+static const yyjson_read_flag YYJSON_READ_ALLOW_LONE_SURROGATES = 1 << 9;
+
 
 
 /** Result code for JSON reader. */
@@ -1,28 +1,715 @@
+import enum
 import json
-from typing import Any, Callable, Optional, Union
+import os
+from typing import (
+    IO,
+    Any,
+    Awaitable,
+    Callable,
+    Dict,
+    Iterable,
+    Iterator,
+    List,
+    Optional,
+    Protocol,
+    Tuple,
+    Type,
+    Union,
+)
 
 __version__: str
 
+# the functions of orjson.stdlib have the signatures of those of json
+stdlib = json
+
 def dumps(
     __obj: Any,
-    default: Optional[Callable[[Any], Any]] = ...,
+    default: Optional[Union[Callable[..., Any], Dict[type, Callable[..., Any]]]] = ...,
     option: Optional[int] = ...,
+    *,
+    max_size: Optional[int] = ...,
+    stats: Optional[Dict[str, Any]] = ...,
+    float_precision: Optional[int] = ...,
+    max_depth: Optional[int] = ...,
+    redact_keys: Optional[Iterable[str]] = ...,
+    include_keys: Optional[Iterable[str]] = ...,
+    max_str_len: Optional[int] = ...,
+    transform: Optional[Callable[[Any], Any]] = ...,
+    separators: Optional[Tuple[str, str]] = ...,
+    append_newline: bool = ...,
+    bytes_keys: bool = ...,
+    default_path: bool = ...,
+    fallback_repr: bool = ...,
+    fallback_str: bool = ...,
+    fallback_vars: bool = ...,
+    indent: Optional[int] = ...,
+    js: bool = ...,
+    naive_utc: bool = ...,
+    non_str_keys: bool = ...,
+    normalize_float: bool = ...,
+    normalize_negative_zero: bool = ...,
+    numbers_as_strings: bool = ...,
+    omit_microseconds: bool = ...,
+    passthrough_dataclass: bool = ...,
+    passthrough_datetime: bool = ...,
+    passthrough_subclass: bool = ...,
+    serialize_geo_interface: bool = ...,
+    serialize_mapping: bool = ...,
+    serialize_multidict: bool = ...,
+    serialize_numpy: bool = ...,
+    serialize_pairs: bool = ...,
+    serialize_sequence: bool = ...,
+    sort_keys: bool = ...,
+    strict_integer: bool = ...,
+    utc_z: bool = ...,
 ) -> bytes: ...
-def loads(__obj: Union[bytes, bytearray, memoryview, str]) -> Any: ...
+def compile(
+    __cls: type,
+    default: Optional[Union[Callable[..., Any], Dict[type, Callable[..., Any]]]] = ...,
+    option: Optional[int] = ...,
+    *,
+    max_size: Optional[int] = ...,
+    float_precision: Optional[int] = ...,
+    separators: Optional[Tuple[str, str]] = ...,
+    max_depth: Optional[int] = ...,
+    append_newline: bool = ...,
+    bytes_keys: bool = ...,
+    default_path: bool = ...,
+    fallback_repr: bool = ...,
+    fallback_str: bool = ...,
+    fallback_vars: bool = ...,
+    indent: Optional[int] = ...,
+    js: bool = ...,
+    naive_utc: bool = ...,
+    non_str_keys: bool = ...,
+    normalize_float: bool = ...,
+    normalize_negative_zero: bool = ...,
+    numbers_as_strings: bool = ...,
+    omit_microseconds: bool = ...,
+    passthrough_dataclass: bool = ...,
+    passthrough_datetime: bool = ...,
+    passthrough_subclass: bool = ...,
+    serialize_geo_interface: bool = ...,
+    serialize_mapping: bool = ...,
+    serialize_multidict: bool = ...,
+    serialize_numpy: bool = ...,
+    serialize_pairs: bool = ...,
+    serialize_sequence: bool = ...,
+    sort_keys: bool = ...,
+    strict_integer: bool = ...,
+    utc_z: bool = ...,
+) -> CompiledSerializer: ...
+def adump(
+    __obj: Any,
+    __path: Union[str, bytes, os.PathLike],
+    default: Optional[Union[Callable[..., Any], Dict[type, Callable[..., Any]]]] = ...,
+    option: Optional[int] = ...,
+    *,
+    max_size: Optional[int] = ...,
+    float_precision: Optional[int] = ...,
+    separators: Optional[Tuple[str, str]] = ...,
+    append_newline: bool = ...,
+    bytes_keys: bool = ...,
+    default_path: bool = ...,
+    fallback_repr: bool = ...,
+    fallback_str: bool = ...,
+    fallback_vars: bool = ...,
+    indent: Optional[int] = ...,
+    js: bool = ...,
+    naive_utc: bool = ...,
+    non_str_keys: bool = ...,
+    normalize_float: bool = ...,
+    normalize_negative_zero: bool = ...,
+    numbers_as_strings: bool = ...,
+    omit_microseconds: bool = ...,
+    passthrough_dataclass: bool = ...,
+    passthrough_datetime: bool = ...,
+    passthrough_subclass: bool = ...,
+    serialize_geo_interface: bool = ...,
+    serialize_mapping: bool = ...,
+    serialize_multidict: bool = ...,
+    serialize_numpy: bool = ...,
+    serialize_pairs: bool = ...,
+    serialize_sequence: bool = ...,
+    sort_keys: bool = ...,
+    strict_integer: bool = ...,
+    utc_z: bool = ...,
+) -> Awaitable[None]: ...
+def dump_fd(
+    __obj: Any,
+    __fd: Union[int, _HasFileno],
+    default: Optional[Union[Callable[..., Any], Dict[type, Callable[..., Any]]]] = ...,
+    option: Optional[int] = ...,
+    *,
+    lines: bool = ...,
+    max_size: Optional[int] = ...,
+    float_precision: Optional[int] = ...,
+    separators: Optional[Tuple[str, str]] = ...,
+    append_newline: bool = ...,
+    bytes_keys: bool = ...,
+    default_path: bool = ...,
+    fallback_repr: bool = ...,
+    fallback_str: bool = ...,
+    fallback_vars: bool = ...,
+    indent: Optional[int] = ...,
+    js: bool = ...,
+    naive_utc: bool = ...,
+    non_str_keys: bool = ...,
+    normalize_float: bool = ...,
+    normalize_negative_zero: bool = ...,
+    numbers_as_strings: bool = ...,
+    omit_microseconds: bool = ...,
+    passthrough_dataclass: bool = ...,
+    passthrough_datetime: bool = ...,
+    passthrough_subclass: bool = ...,
+    serialize_geo_interface: bool = ...,
+    serialize_mapping: bool = ...,
+    serialize_multidict: bool = ...,
+    serialize_numpy: bool = ...,
+    serialize_pairs: bool = ...,
+    serialize_sequence: bool = ...,
+    sort_keys: bool = ...,
+    strict_integer: bool = ...,
+    utc_z: bool = ...,
+) -> int: ...
+def dumps_into(
+    __obj: Any,
+    __buffer: Union[bytearray, memoryview],
+    default: Optional[Union[Callable[..., Any], Dict[type, Callable[..., Any]]]] = ...,
+    option: Optional[int] = ...,
+    *,
+    offset: int = ...,
+    max_size: Optional[int] = ...,
+    float_precision: Optional[int] = ...,
+    separators: Optional[Tuple[str, str]] = ...,
+    append_newline: bool = ...,
+    bytes_keys: bool = ...,
+    default_path: bool = ...,
+    fallback_repr: bool = ...,
+    fallback_str: bool = ...,
+    fallback_vars: bool = ...,
+    indent: Optional[int] = ...,
+    js: bool = ...,
+    naive_utc: bool = ...,
+    non_str_keys: bool = ...,
+    normalize_float: bool = ...,
+    normalize_negative_zero: bool = ...,
+    numbers_as_strings: bool = ...,
+    omit_microseconds: bool = ...,
+    passthrough_dataclass: bool = ...,
+    passthrough_datetime: bool = ...,
+    passthrough_subclass: bool = ...,
+    serialize_geo_interface: bool = ...,
+    serialize_mapping: bool = ...,
+    serialize_multidict: bool = ...,
+    serialize_numpy: bool = ...,
+    serialize_pairs: bool = ...,
+    serialize_sequence: bool = ...,
+    sort_keys: bool = ...,
+    strict_integer: bool = ...,
+    utc_z: bool = ...,
+) -> int: ...
+def set_default_options(__option: Optional[int]) -> None: ...
+def get_default_options() -> int: ...
+def loads(
+    __obj: Union[bytes, bytearray, memoryview, str],
+    *,
+    parse_float: Optional[Callable[[str], Any]] = ...,
+    parse_int: Optional[Callable[[str], Any]] = ...,
+    object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+    object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+    duplicate_keys: Optional[str] = ...,
+    surrogates: Optional[str] = ...,
+    replace_invalid_utf8: bool = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    jsonc: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    tuples: bool = ...,
+    immutable: bool = ...,
+    datetimes: bool = ...,
+    uuids: bool = ...,
+    numpy: bool = ...,
+    raw_numbers: bool = ...,
+    offset: Optional[int] = ...,
+    length: Optional[int] = ...,
+    threads: Optional[int] = ...,
+    schema: Optional[Schema] = ...,
+) -> Any: ...
+def load(
+    __fp: Union[IO[bytes], IO[str], int],
+    *,
+    chunk_size: Optional[int] = ...,
+    parse_float: Optional[Callable[[str], Any]] = ...,
+    parse_int: Optional[Callable[[str], Any]] = ...,
+    object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+    object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+    duplicate_keys: Optional[str] = ...,
+    surrogates: Optional[str] = ...,
+    replace_invalid_utf8: bool = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    jsonc: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    tuples: bool = ...,
+    immutable: bool = ...,
+    datetimes: bool = ...,
+    uuids: bool = ...,
+    numpy: bool = ...,
+    raw_numbers: bool = ...,
+    threads: Optional[int] = ...,
+    schema: Optional[Schema] = ...,
+) -> Any: ...
+def load_mmap(
+    __path: Union[str, bytes, os.PathLike],
+    *,
+    parse_float: Optional[Callable[[str], Any]] = ...,
+    parse_int: Optional[Callable[[str], Any]] = ...,
+    object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+    object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+    duplicate_keys: Optional[str] = ...,
+    surrogates: Optional[str] = ...,
+    replace_invalid_utf8: bool = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    jsonc: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    tuples: bool = ...,
+    immutable: bool = ...,
+    datetimes: bool = ...,
+    uuids: bool = ...,
+    numpy: bool = ...,
+    raw_numbers: bool = ...,
+    offset: Optional[int] = ...,
+    length: Optional[int] = ...,
+    threads: Optional[int] = ...,
+    schema: Optional[Schema] = ...,
+) -> Any: ...
+def aload(
+    __path: Union[str, bytes, os.PathLike],
+    *,
+    parse_float: Optional[Callable[[str], Any]] = ...,
+    parse_int: Optional[Callable[[str], Any]] = ...,
+    object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+    object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+    duplicate_keys: Optional[str] = ...,
+    surrogates: Optional[str] = ...,
+    replace_invalid_utf8: bool = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    jsonc: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    tuples: bool = ...,
+    immutable: bool = ...,
+    datetimes: bool = ...,
+    uuids: bool = ...,
+    numpy: bool = ...,
+    raw_numbers: bool = ...,
+    threads: Optional[int] = ...,
+    schema: Optional[Schema] = ...,
+) -> Awaitable[Any]: ...
+def loads_compressed(
+    __data_or_fp: Union[bytes, bytearray, memoryview, IO[bytes], int],
+    *,
+    compression: Optional[str] = ...,
+    parse_float: Optional[Callable[[str], Any]] = ...,
+    parse_int: Optional[Callable[[str], Any]] = ...,
+    object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+    object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+    duplicate_keys: Optional[str] = ...,
+    surrogates: Optional[str] = ...,
+    replace_invalid_utf8: bool = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    jsonc: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    tuples: bool = ...,
+    immutable: bool = ...,
+    datetimes: bool = ...,
+    uuids: bool = ...,
+    numpy: bool = ...,
+    raw_numbers: bool = ...,
+    schema: Optional[Schema] = ...,
+) -> Any: ...
+def loads_into(
+    __type: Any,
+    __obj: Union[bytes, bytearray, memoryview, str],
+    *,
+    duplicate_keys: Optional[str] = ...,
+    surrogates: Optional[str] = ...,
+    replace_invalid_utf8: bool = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    jsonc: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    offset: Optional[int] = ...,
+    length: Optional[int] = ...,
+    threads: Optional[int] = ...,
+    schema: Optional[Schema] = ...,
+) -> Any: ...
+def loads_columns(
+    __obj: Union[bytes, bytearray, memoryview, str],
+    *,
+    parse_float: Optional[Callable[[str], Any]] = ...,
+    parse_int: Optional[Callable[[str], Any]] = ...,
+    object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+    object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+    duplicate_keys: Optional[str] = ...,
+    surrogates: Optional[str] = ...,
+    replace_invalid_utf8: bool = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    jsonc: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    tuples: bool = ...,
+    immutable: bool = ...,
+    datetimes: bool = ...,
+    uuids: bool = ...,
+    raw_numbers: bool = ...,
+    offset: Optional[int] = ...,
+    length: Optional[int] = ...,
+) -> Dict[str, List[Any]]: ...
+def iter_array(
+    __data_or_fp: Union[bytes, bytearray, memoryview, str, IO[bytes], IO[str], int]
+) -> Iterator[Any]: ...
+def select(
+    __data: Union[bytes, bytearray, memoryview, str],
+    __path: str,
+    *,
+    paths: bool = ...,
+) -> List[Any]: ...
+def loads_lines(
+    __data_or_fp: Union[bytes, bytearray, memoryview, str, IO[bytes], IO[str], int]
+) -> Iterator[Any]: ...
+def iter_documents(
+    __data_or_fp: Union[bytes, bytearray, memoryview, str, IO[bytes], IO[str], int]
+) -> Iterator[Any]: ...
+
+def loads_prefix(
+    __obj: Union[bytes, bytearray, memoryview, str],
+    *,
+    parse_float: Optional[Callable[[str], Any]] = ...,
+    parse_int: Optional[Callable[[str], Any]] = ...,
+    object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+    object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+    duplicate_keys: Optional[str] = ...,
+    surrogates: Optional[str] = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    jsonc: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    tuples: bool = ...,
+    immutable: bool = ...,
+    datetimes: bool = ...,
+    uuids: bool = ...,
+    numpy: bool = ...,
+    raw_numbers: bool = ...,
+    offset: Optional[int] = ...,
+    length: Optional[int] = ...,
+    schema: Optional[Schema] = ...,
+) -> Tuple[Any, int]: ...
+def tokenize(
+    __data_or_fp: Union[bytes, bytearray, memoryview, str, IO[bytes], IO[str], int]
+) -> Iterator[Tuple[str, Any, int]]: ...
+
+def unpackb(
+    __data: Union[bytes, bytearray, memoryview],
+    *,
+    parse_float: Optional[Callable[[str], Any]] = ...,
+    parse_int: Optional[Callable[[str], Any]] = ...,
+    object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+    object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+    duplicate_keys: Optional[str] = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    tuples: bool = ...,
+    immutable: bool = ...,
+    datetimes: bool = ...,
+    uuids: bool = ...,
+    offset: Optional[int] = ...,
+    length: Optional[int] = ...,
+    schema: Optional[Schema] = ...,
+) -> Any: ...
+def loads_cbor(
+    __data: Union[bytes, bytearray, memoryview],
+    *,
+    parse_float: Optional[Callable[[str], Any]] = ...,
+    parse_int: Optional[Callable[[str], Any]] = ...,
+    object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+    object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+    duplicate_keys: Optional[str] = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    tuples: bool = ...,
+    immutable: bool = ...,
+    datetimes: bool = ...,
+    uuids: bool = ...,
+    offset: Optional[int] = ...,
+    length: Optional[int] = ...,
+    schema: Optional[Schema] = ...,
+) -> Any: ...
+def is_valid(
+    __data: Union[bytes, bytearray, memoryview, str],
+    *,
+    duplicate_keys: Optional[str] = ...,
+    surrogates: Optional[str] = ...,
+    allow_nan: bool = ...,
+    strict: bool = ...,
+    jsonc: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_items: Optional[int] = ...,
+    max_string_len: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+    max_bytes: Optional[int] = ...,
+    position: bool = ...,
+) -> Union[bool, Optional[int]]: ...
+def find_errors(
+    __data: Union[bytes, bytearray, memoryview, str],
+    *,
+    surrogates: Optional[str] = ...,
+    allow_nan: bool = ...,
+    jsonc: bool = ...,
+    max_depth: Optional[int] = ...,
+    max_errors: Optional[int] = ...,
+) -> List[JSONDecodeError]: ...
+
+class Options(enum.IntFlag):
+    APPEND_NEWLINE: int
+    BYTES_KEYS: int
+    DEFAULT_PATH: int
+    FALLBACK_REPR: int
+    FALLBACK_STR: int
+    FALLBACK_VARS: int
+    INDENT_2: int
+    JS: int
+    NAIVE_UTC: int
+    NON_STR_KEYS: int
+    NORMALIZE_FLOAT: int
+    NORMALIZE_NEGATIVE_ZERO: int
+    NUMBERS_AS_STRINGS: int
+    OMIT_MICROSECONDS: int
+    PASSTHROUGH_DATACLASS: int
+    PASSTHROUGH_DATETIME: int
+    PASSTHROUGH_SUBCLASS: int
+    SERIALIZE_GEO_INTERFACE: int
+    SERIALIZE_MAPPING: int
+    SERIALIZE_MULTIDICT: int
+    SERIALIZE_NUMPY: int
+    SERIALIZE_PAIRS: int
+    SERIALIZE_SEQUENCE: int
+    SORT_KEYS: int
+    STRICT_INTEGER: int
+    UTC_Z: int
+
+class CompiledSerializer:
+    def __call__(self, __obj: Any) -> bytes: ...
+
+class _HasFileno(Protocol):
+    def fileno(self) -> int: ...
+
+class _Stream(Protocol):
+    def write(self, __data: bytes) -> Any: ...
+    def drain(self) -> Awaitable[Any]: ...
+
+class AsyncWriter:
+    def __init__(
+        self,
+        __write: Union[Callable[[bytes], Awaitable[Any]], _Stream],
+        default: Optional[
+            Union[Callable[..., Any], Dict[type, Callable[..., Any]]]
+        ] = ...,
+        option: Optional[int] = ...,
+        *,
+        chunk_size: Optional[int] = ...,
+        append_newline: bool = ...,
+        bytes_keys: bool = ...,
+        default_path: bool = ...,
+        fallback_repr: bool = ...,
+        fallback_str: bool = ...,
+        fallback_vars: bool = ...,
+        indent: Optional[int] = ...,
+        js: bool = ...,
+        naive_utc: bool = ...,
+        non_str_keys: bool = ...,
+        normalize_float: bool = ...,
+        normalize_negative_zero: bool = ...,
+        numbers_as_strings: bool = ...,
+        omit_microseconds: bool = ...,
+        passthrough_dataclass: bool = ...,
+        passthrough_datetime: bool = ...,
+        passthrough_subclass: bool = ...,
+        serialize_geo_interface: bool = ...,
+        serialize_mapping: bool = ...,
+        serialize_multidict: bool = ...,
+        serialize_numpy: bool = ...,
+        serialize_pairs: bool = ...,
+        serialize_sequence: bool = ...,
+        sort_keys: bool = ...,
+        strict_integer: bool = ...,
+        utc_z: bool = ...,
+    ) -> None: ...
+    def dump(self, __obj: Any) -> Awaitable[None]: ...
+
+class Decoder:
+    def __init__(
+        self,
+        *,
+        parse_float: Optional[Callable[[str], Any]] = ...,
+        parse_int: Optional[Callable[[str], Any]] = ...,
+        object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+        object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+        dict_class: Optional[Type[dict]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+        duplicate_keys: Optional[str] = ...,
+        surrogates: Optional[str] = ...,
+        replace_invalid_utf8: bool = ...,
+        allow_nan: bool = ...,
+        strict: bool = ...,
+        jsonc: bool = ...,
+        max_depth: Optional[int] = ...,
+        max_items: Optional[int] = ...,
+        max_string_len: Optional[int] = ...,
+        max_string_bytes: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+        max_bytes: Optional[int] = ...,
+        tuples: bool = ...,
+        immutable: bool = ...,
+        datetimes: bool = ...,
+        uuids: bool = ...,
+        numpy: bool = ...,
+        raw_numbers: bool = ...,
+        threads: Optional[int] = ...,
+        schema: Optional[Schema] = ...,
+    ) -> None: ...
+    def decode(
+        self,
+        __obj: Union[bytes, bytearray, memoryview, str],
+        *,
+        offset: Optional[int] = ...,
+        length: Optional[int] = ...,
+    ) -> Any: ...
+
+class Parser:
+    def __init__(
+        self,
+        *,
+        parse_float: Optional[Callable[[str], Any]] = ...,
+        parse_int: Optional[Callable[[str], Any]] = ...,
+        object_hook: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+        object_pairs_hook: Optional[Callable[[List[Tuple[str, Any]]], Any]] = ...,
+        dict_class: Optional[Type[dict]] = ...,
+    dict_class: Optional[Type[dict]] = ...,
+        duplicate_keys: Optional[str] = ...,
+        surrogates: Optional[str] = ...,
+        replace_invalid_utf8: bool = ...,
+        allow_nan: bool = ...,
+        strict: bool = ...,
+        max_depth: Optional[int] = ...,
+        max_items: Optional[int] = ...,
+        max_string_len: Optional[int] = ...,
+        max_string_bytes: Optional[int] = ...,
+    max_string_bytes: Optional[int] = ...,
+        max_bytes: Optional[int] = ...,
+        tuples: bool = ...,
+        immutable: bool = ...,
+        datetimes: bool = ...,
+        uuids: bool = ...,
+        numpy: bool = ...,
+        raw_numbers: bool = ...,
+        threads: Optional[int] = ...,
+        schema: Optional[Schema] = ...,
+    ) -> None: ...
+    def feed(self, __data: Union[bytes, bytearray, memoryview, str]) -> List[Any]: ...
+    def close(self) -> List[Any]: ...
+
+class Schema:
+    def __init__(self, __schema: Union[Dict[str, Any], bool]) -> None: ...
+
+class RawNumber:
+    def __init__(self, __literal: str) -> None: ...
+    def __float__(self) -> float: ...
+
+class JSONDecodeError(json.JSONDecodeError):
+    snippet: str
 
-class JSONDecodeError(json.JSONDecodeError): ...
 class JSONEncodeError(TypeError): ...
+class JSONEncodeSizeError(JSONEncodeError): ...
 
 OPT_APPEND_NEWLINE: int
+OPT_BYTES_KEYS: int
+OPT_DEFAULT_PATH: int
+OPT_FALLBACK_REPR: int
+OPT_FALLBACK_STR: int
+OPT_FALLBACK_VARS: int
 OPT_INDENT_2: int
+OPT_JS: int
 OPT_NAIVE_UTC: int
 OPT_NON_STR_KEYS: int
+OPT_NORMALIZE_FLOAT: int
+OPT_NORMALIZE_NEGATIVE_ZERO: int
+OPT_NUMBERS_AS_STRINGS: int
 OPT_OMIT_MICROSECONDS: int
 OPT_PASSTHROUGH_DATACLASS: int
 OPT_PASSTHROUGH_DATETIME: int
 OPT_PASSTHROUGH_SUBCLASS: int
 OPT_SERIALIZE_DATACLASS: int
+OPT_SERIALIZE_GEO_INTERFACE: int
+OPT_SERIALIZE_MAPPING: int
+OPT_SERIALIZE_MULTIDICT: int
 OPT_SERIALIZE_NUMPY: int
+OPT_SERIALIZE_PAIRS: int
+OPT_SERIALIZE_SEQUENCE: int
 OPT_SERIALIZE_UUID: int
 OPT_SORT_KEYS: int
 OPT_STRICT_INTEGER: int
@@ -0,0 +1,261 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+// `aload()` and `adump()`, which read or write a file on a thread of the
+// default executor of the running event loop.
+
+use crate::deserialize::{deserialize_bytes, hooks, raise_os_error, LoadsArgs};
+use crate::ffi::{PyBytes_AS_STRING, PyBytes_GET_SIZE};
+use crate::opt::Opt;
+use crate::serialize::{serialize, DumpsArgs};
+use crate::typeref::*;
+use std::io::Read;
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::path::PathBuf;
+use std::ptr::{null_mut, NonNull};
+
+enum FileOp {
+    Load {
+        path: PathBuf,
+        // the hooks are owned references
+        args: Box<LoadsArgs>,
+    },
+    Dump {
+        // strong references
+        obj: *mut pyo3_ffi::PyObject,
+        default: Option<NonNull<pyo3_ffi::PyObject>>,
+        path: PathBuf,
+        opts: Opt,
+        args: DumpsArgs,
+    },
+}
+
+impl Drop for FileOp {
+    fn drop(&mut self) {
+        match self {
+            FileOp::Load { args, .. } => {
+                for func in hooks(args).iter().flatten() {
+                    ffi!(Py_DECREF(func.as_ptr()));
+                }
+            }
+            FileOp::Dump { obj, default, .. } => {
+                ffi!(Py_DECREF(*obj));
+                if let Some(func) = default {
+                    ffi!(Py_DECREF(func.as_ptr()));
+                }
+            }
+        }
+    }
+}
+
+impl FileOp {
+    unsafe fn call(&self) -> *mut pyo3_ffi::PyObject {
+        match self {
+            FileOp::Load { path, args } => {
+                // reading stops past `max_bytes`, which `deserialize_bytes()`
+                // then checks
+                let limit = args.max_bytes.unwrap_or(usize::MAX);
+                let mut buf: Vec<u8> = Vec::new();
+                let tstate = pyo3_ffi::PyEval_SaveThread();
+                let res = std::fs::File::open(path).and_then(|file| {
+                    file.take((limit as u64).saturating_add(1))
+                        .read_to_end(&mut buf)
+                });
+                pyo3_ffi::PyEval_RestoreThread(tstate);
+                if let Err(err) = res {
+                    raise_os_error(err);
+                    return null_mut();
+                }
+                // per call, so that the count of items is not shared
+                match deserialize_bytes(&buf, &args.clone_options(), true) {
+                    Ok(val) => val.as_ptr(),
+                    Err(Some(err)) => crate::raise_loads_exception(err),
+                    Err(None) => null_mut(),
+                }
+            }
+            FileOp::Dump {
+                obj,
+                default,
+                path,
+                opts,
+                args,
+            } => {
+                let bytes = match serialize(*obj, *default, *opts, args.clone_options()) {
+                    Ok(val) => val.as_ptr(),
+                    Err(err) => return crate::raise_encode_error(err),
+                };
+                // the bytes object is immutable and kept alive, so is read
+                // without the GIL
+                let contents = std::slice::from_raw_parts(
+                    PyBytes_AS_STRING(bytes) as *const u8,
+                    PyBytes_GET_SIZE(bytes) as usize,
+                );
+                let tstate = pyo3_ffi::PyEval_SaveThread();
+                let res = std::fs::write(path, contents);
+                pyo3_ffi::PyEval_RestoreThread(tstate);
+                pyo3_ffi::Py_DECREF(bytes);
+                if let Err(err) = res {
+                    raise_os_error(err);
+                    return null_mut();
+                }
+                pyo3_ffi::Py_INCREF(NONE);
+                NONE
+            }
+        }
+    }
+}
+
+/// The path of `ptr`, a `str`, `bytes`, or `os.PathLike`, or an error if an
+/// exception was raised.
+#[cfg(unix)]
+unsafe fn to_path(ptr: *mut pyo3_ffi::PyObject) -> Result<PathBuf, ()> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut bytes: *mut pyo3_ffi::PyObject = null_mut();
+    if pyo3_ffi::PyUnicode_FSConverter(ptr, std::ptr::addr_of_mut!(bytes) as *mut c_void) == 0 {
+        return Err(());
+    }
+    let path = PathBuf::from(std::ffi::OsStr::from_bytes(std::slice::from_raw_parts(
+        PyBytes_AS_STRING(bytes) as *const u8,
+        PyBytes_GET_SIZE(bytes) as usize,
+    )));
+    pyo3_ffi::Py_DECREF(bytes);
+    Ok(path)
+}
+
+#[cfg(not(unix))]
+unsafe fn to_path(ptr: *mut pyo3_ffi::PyObject) -> Result<PathBuf, ()> {
+    let mut string: *mut pyo3_ffi::PyObject = null_mut();
+    if pyo3_ffi::PyUnicode_FSDecoder(ptr, std::ptr::addr_of_mut!(string) as *mut c_void) == 0 {
+        return Err(());
+    }
+    let path = crate::unicode::unicode_to_str(string).map(PathBuf::from);
+    pyo3_ffi::Py_DECREF(string);
+    path.ok_or(())
+}
+
+/// Run `op` on the default executor of the running event loop, returning
+/// the `asyncio.Future` of its result, or null if an exception was raised.
+unsafe fn run_in_executor(op: FileOp) -> *mut pyo3_ffi::PyObject {
+    let asyncio = pyo3_ffi::PyImport_ImportModule("asyncio\0".as_ptr() as *const c_char);
+    if asyncio.is_null() {
+        return null_mut();
+    }
+    // raises if there is no running event loop
+    let event_loop = pyo3_ffi::PyObject_CallMethod(
+        asyncio,
+        "get_running_loop\0".as_ptr() as *const c_char,
+        std::ptr::null::<c_char>(),
+    );
+    pyo3_ffi::Py_DECREF(asyncio);
+    if event_loop.is_null() {
+        return null_mut();
+    }
+    let task = pyo3_ffi::PyType_GenericAlloc(FILE_TASK_TYPE, 0);
+    if task.is_null() {
+        pyo3_ffi::Py_DECREF(event_loop);
+        return null_mut();
+    }
+    std::ptr::write(std::ptr::addr_of_mut!((*(task as *mut FileTask)).op), op);
+    let future = pyo3_ffi::PyObject_CallMethod(
+        event_loop,
+        "run_in_executor\0".as_ptr() as *const c_char,
+        "OO\0".as_ptr() as *const c_char,
+        NONE,
+        task,
+    );
+    pyo3_ffi::Py_DECREF(task);
+    pyo3_ffi::Py_DECREF(event_loop);
+    future
+}
+
+/// Return the `asyncio.Future` of deserializing the file at `path` with
+/// `args`, whose hooks are borrowed.
+pub unsafe fn aload(path: *mut pyo3_ffi::PyObject, args: LoadsArgs) -> *mut pyo3_ffi::PyObject {
+    let path = match to_path(path) {
+        Ok(path) => path,
+        Err(()) => return null_mut(),
+    };
+    for func in hooks(&args).iter().flatten() {
+        pyo3_ffi::Py_INCREF(func.as_ptr());
+    }
+    run_in_executor(FileOp::Load {
+        path: path,
+        args: Box::new(args),
+    })
+}
+
+/// Return the `asyncio.Future` of serializing `obj` to the file at `path`,
+/// which is created or truncated.
+pub unsafe fn adump(
+    obj: *mut pyo3_ffi::PyObject,
+    path: *mut pyo3_ffi::PyObject,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    opts: Opt,
+    args: DumpsArgs,
+) -> *mut pyo3_ffi::PyObject {
+    let path = match to_path(path) {
+        Ok(path) => path,
+        Err(()) => return null_mut(),
+    };
+    pyo3_ffi::Py_INCREF(obj);
+    if let Some(func) = default {
+        pyo3_ffi::Py_INCREF(func.as_ptr());
+    }
+    run_in_executor(FileOp::Dump {
+        obj: obj,
+        default: default,
+        path: path,
+        opts: opts,
+        args: args,
+    })
+}
+
+/// The callable run by the executor, which holds the GIL only to make or
+/// serialize Python objects.
+#[repr(C)]
+pub struct FileTask {
+    ob_base: pyo3_ffi::PyObject,
+    op: FileOp,
+}
+
+unsafe extern "C" fn file_task_call(
+    slf: *mut pyo3_ffi::PyObject,
+    _args: *mut pyo3_ffi::PyObject,
+    _kwds: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    (*(slf as *mut FileTask)).op.call()
+}
+
+unsafe extern "C" fn file_task_dealloc(slf: *mut pyo3_ffi::PyObject) {
+    std::ptr::drop_in_place(std::ptr::addr_of_mut!((*(slf as *mut FileTask)).op));
+    let tp = ob_type!(slf);
+    ((*tp).tp_free.unwrap())(slf as *mut c_void);
+    pyo3_ffi::Py_DECREF(tp as *mut pyo3_ffi::PyObject);
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub unsafe fn create_file_task_type() -> *mut pyo3_ffi::PyTypeObject {
+    let slots: Box<[pyo3_ffi::PyType_Slot; 3]> = Box::new([
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_call,
+            pfunc: file_task_call as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_dealloc,
+            pfunc: file_task_dealloc as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: 0,
+            pfunc: std::ptr::null_mut(),
+        },
+    ]);
+    let spec = Box::new(pyo3_ffi::PyType_Spec {
+        name: "orjson.FileTask\0".as_ptr() as *const c_char,
+        basicsize: std::mem::size_of::<FileTask>() as c_int,
+        itemsize: 0,
+        flags: pyo3_ffi::Py_TPFLAGS_DEFAULT as c_uint,
+        slots: Box::into_raw(slots) as *mut pyo3_ffi::PyType_Slot,
+    });
+    pyo3_ffi::PyType_FromSpec(Box::into_raw(spec)) as *mut pyo3_ffi::PyTypeObject
+}
@@ -0,0 +1,57 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! Serialization and deserialization for other Rust extensions of the same
+//! interpreter, called on object pointers rather than through the functions
+//! of the `orjson` module. Each function must be called holding the GIL.
+
+use crate::typeref::init_typerefs;
+use pyo3_ffi::PyObject;
+use std::ptr::NonNull;
+
+pub use crate::opt::{
+    Opt, APPEND_NEWLINE, BYTES_KEYS, DEFAULT_PATH, FALLBACK_REPR, FALLBACK_STR, FALLBACK_VARS,
+    INDENT_2, JS, NAIVE_UTC, NON_STR_KEYS, NORMALIZE_FLOAT, NORMALIZE_NEGATIVE_ZERO,
+    NUMBERS_AS_STRINGS, OMIT_MICROSECONDS, PASSTHROUGH_DATACLASS, PASSTHROUGH_DATETIME,
+    PASSTHROUGH_SUBCLASS, SERIALIZE_GEO_INTERFACE, SERIALIZE_MAPPING, SERIALIZE_MULTIDICT,
+    SERIALIZE_NUMPY, SERIALIZE_PAIRS, SERIALIZE_SEQUENCE, SORT_KEYS, STRICT_INTEGER, UTC_Z,
+};
+
+/// Look up the types and make the strings that serialization and
+/// deserialization use, once per process. `serialize()` and `deserialize()`
+/// call it, so it need only be called to do so ahead of them.
+pub fn init() {
+    init_typerefs();
+}
+
+/// Serialize `obj` to a new `bytes` object, as `orjson.dumps(obj, default,
+/// option=opts)` does, with `default` borrowed. `None` means
+/// `orjson.JSONEncodeError` or another exception was raised.
+pub unsafe fn serialize(
+    obj: *mut PyObject,
+    default: Option<NonNull<PyObject>>,
+    opts: Opt,
+) -> Option<NonNull<PyObject>> {
+    init();
+    match crate::serialize::serialize(obj, default, opts, crate::serialize::DumpsArgs::default()) {
+        Ok(val) => Some(val),
+        Err(err) => {
+            crate::raise_encode_error(err);
+            None
+        }
+    }
+}
+
+/// Deserialize `obj`, a `bytes`, `bytearray`, `memoryview`, or `str` of JSON,
+/// to a new object, as `orjson.loads(obj)` does. `None` means
+/// `orjson.JSONDecodeError` or another exception was raised.
+pub unsafe fn deserialize(obj: *mut PyObject) -> Option<NonNull<PyObject>> {
+    init();
+    match crate::deserialize::deserialize(obj, &crate::deserialize::LoadsArgs::default()) {
+        Ok(val) => Some(val),
+        Err(Some(err)) => {
+            crate::raise_loads_exception(err);
+            None
+        }
+        Err(None) => None,
+    }
+}
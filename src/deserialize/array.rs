@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::deserializer::deserialize_bytes;
+use crate::deserialize::error::*;
+use crate::deserialize::iter::{new_iterator, InputReader, Scanner};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use std::borrow::Cow;
+use std::ptr::NonNull;
+
+/// Create an iterator over the elements of the top-level array in `ptr`.
+pub fn iter_array(
+    ptr: *mut pyo3_ffi::PyObject,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let scanner = ArrayScanner {
+        reader: InputReader::new(ptr)?,
+        state: State::Start,
+        element: Vec::new(),
+    };
+    new_iterator(unsafe { ARRAY_ITERATOR_TYPE }, Box::new(scanner))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    // before `[`
+    Start,
+    // after an element
+    Next,
+    Done,
+}
+
+/// Finds the bytes of each element of a top-level array so they can be
+/// deserialized as a document.
+struct ArrayScanner {
+    reader: InputReader,
+    state: State,
+    // the bytes of the current element, reused across elements
+    element: Vec<u8>,
+}
+
+impl ArrayScanner {
+    /// Read the bytes of the element starting at the next byte into
+    /// `self.element`. Strings are skipped so that brackets and commas in
+    /// them do not end the element. Anything else invalid is left to be
+    /// reported when the element is deserialized.
+    fn read_element(&mut self) -> Result<(), Option<DeserializeError<'static>>> {
+        self.element.clear();
+        let mut depth: usize = 0;
+        let mut in_string = false;
+        let mut escaped = false;
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                return Ok(());
+            }
+            let mut end: Option<usize> = None;
+            for (idx, &c) in buf.iter().enumerate() {
+                if in_string {
+                    if escaped {
+                        escaped = false;
+                    } else if c == b'\\' {
+                        escaped = true;
+                    } else if c == b'"' {
+                        in_string = false;
+                        if depth == 0 {
+                            end = Some(idx + 1);
+                            break;
+                        }
+                    }
+                    continue;
+                }
+                match c {
+                    b'"' => in_string = true,
+                    b'[' | b'{' => depth += 1,
+                    b']' | b'}' => {
+                        if depth == 0 {
+                            end = Some(idx);
+                            break;
+                        }
+                        depth -= 1;
+                        if depth == 0 {
+                            end = Some(idx + 1);
+                            break;
+                        }
+                    }
+                    b',' | b' ' | b'\t' | b'\n' | b'\r' if depth == 0 => {
+                        end = Some(idx);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            let len = end.unwrap_or(buf.len());
+            self.element.extend_from_slice(&buf[..len]);
+            self.reader.consume(len);
+            if end.is_some() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// An error at the current position of the input.
+    #[cold]
+    fn error(&self, message: &'static str) -> Option<DeserializeError<'static>> {
+        Some(DeserializeError::at_location(
+            Cow::Borrowed(message),
+            self.reader.location(),
+        ))
+    }
+
+    /// Check that only whitespace follows the array.
+    fn finish(
+        &mut self,
+    ) -> Result<Option<NonNull<pyo3_ffi::PyObject>>, Option<DeserializeError<'static>>> {
+        if self.reader.skip_whitespace()?.is_some() {
+            return Err(self.error(UNEXPECTED_CONTENT));
+        }
+        self.state = State::Done;
+        Ok(None)
+    }
+}
+
+impl Scanner for ArrayScanner {
+    fn next(
+        &mut self,
+    ) -> Result<Option<NonNull<pyo3_ffi::PyObject>>, Option<DeserializeError<'static>>> {
+        match self.state {
+            State::Done => return Ok(None),
+            State::Start => {
+                if self.reader.skip_whitespace()? != Some(b'[') {
+                    return Err(self.error("Input must be a JSON array"));
+                }
+                self.reader.consume(1);
+                if self.reader.skip_whitespace()? == Some(b']') {
+                    self.reader.consume(1);
+                    return self.finish();
+                }
+            }
+            State::Next => match self.reader.skip_whitespace()? {
+                Some(b',') => self.reader.consume(1),
+                Some(b']') => {
+                    self.reader.consume(1);
+                    return self.finish();
+                }
+                Some(_) => return Err(self.error(UNEXPECTED_CHARACTER)),
+                None => return Err(self.error(UNEXPECTED_END)),
+            },
+        }
+        if self.reader.skip_whitespace()?.is_none() {
+            return Err(self.error(UNEXPECTED_END));
+        }
+        let at = self.reader.location();
+        self.read_element()?;
+        // e.g., `[1,]` or `[,1]`
+        if self.element.is_empty() {
+            return Err(self.error(UNEXPECTED_CHARACTER));
+        }
+        self.state = State::Next;
+        // the iterator can be advanced by another thread if the GIL is released
+        deserialize_bytes(&self.element, &LoadsArgs::default(), false)
+            .map(Some)
+            .map_err(|err| err.map(|err| err.within(at)))
+    }
+}
@@ -5,6 +5,7 @@ use ahash::CallHasher;
 use associative_cache::replacement::RoundRobinReplacement;
 use associative_cache::*;
 use once_cell::unsync::OnceCell;
+use std::collections::HashMap;
 use std::os::raw::c_void;
 
 #[repr(transparent)]
@@ -43,3 +44,37 @@ pub static mut KEY_MAP: OnceCell<KeyMap> = OnceCell::new();
 pub fn cache_hash(key: &[u8]) -> u64 {
     <[u8]>::get_hash(&key, unsafe { &*HASH_BUILDER })
 }
+
+// Keys at most this long are cached in `KEY_MAP`.
+pub const KEY_MAP_MAX_LEN: usize = 64;
+
+// Entries stored in a `LongKeyCache`.
+const LONG_KEY_CACHE_CAPACITY: usize = 1024;
+
+/// Keys too long for `KEY_MAP` deserialized by one call, so that a key
+/// repeated in the document, e.g., in an array of objects of the same shape,
+/// is one `str`. Keys are compared in full rather than by hash.
+#[derive(Default)]
+pub struct LongKeyCache {
+    map: HashMap<Box<str>, CachedKey>,
+}
+
+impl LongKeyCache {
+    /// Return a new reference to the `str` of `key`, which is created by
+    /// `create` if `key` is not cached.
+    pub fn get_or_insert(
+        &mut self,
+        key: &str,
+        create: impl FnOnce() -> *mut pyo3_ffi::PyObject,
+    ) -> *mut pyo3_ffi::PyObject {
+        if let Some(entry) = self.map.get_mut(key) {
+            return entry.get();
+        }
+        let pykey = create();
+        if self.map.len() < LONG_KEY_CACHE_CAPACITY {
+            ffi!(Py_INCREF(pykey));
+            self.map.insert(key.into(), CachedKey::new(pykey));
+        }
+        pykey
+    }
+}
@@ -0,0 +1,617 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::datetime::{new_datetime, new_datetime_from_timestamp, parse_datetime};
+use crate::deserialize::error::*;
+use crate::deserialize::pyobject::*;
+use crate::deserialize::schema::check_schema;
+use crate::deserialize::utf8::{is_valid_utf8, BufferInput};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use crate::unicode::*;
+use std::borrow::Cow;
+use std::os::raw::c_char;
+use std::ptr::NonNull;
+
+// the additional information of an item of indefinite length
+const INDEFINITE: u8 = 31;
+// the item ending one of indefinite length
+const BREAK: u8 = 0xff;
+
+/// Deserialize CBOR `ptr`, an object supporting the buffer protocol, to the
+/// objects `loads()` makes with `args`, and the `datetime.datetime`,
+/// `uuid.UUID`, and `bytes` of items JSON does not have. An error of `None`
+/// means an exception was raised by a callable in `args`.
+pub fn loads_cbor(
+    ptr: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if is_type!(ob_type!(ptr), STR_TYPE) || ffi!(PyObject_CheckBuffer(ptr)) == 0 {
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+            "Input must be bytes, bytearray, memoryview, or a buffer",
+        ))));
+    }
+    let input = BufferInput::new(ptr)?;
+    let data = args.slice(input.as_bytes())?;
+    args.check_binary_bytes(data.len())?;
+    let mut decoder = CborDecoder {
+        data: data,
+        pos: 0,
+        args: args,
+    };
+    let obj = decoder.parse(args.stack_max_depth())?;
+    if decoder.pos < data.len() {
+        ffi!(Py_DECREF(obj.as_ptr()));
+        return Err(decoder.error(UNEXPECTED_CONTENT));
+    }
+    check_schema(obj, args, None)
+}
+
+/// The value of the IEEE 754 half-precision float `bits`.
+fn f16_to_f64(bits: u16) -> f64 {
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f64;
+    let val = match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (mantissa + 1024.0) * 2f64.powi(exponent - 25),
+    };
+    if bits & 0x8000 != 0 {
+        -val
+    } else {
+        val
+    }
+}
+
+struct CborDecoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+    args: &'a LoadsArgs,
+}
+
+impl<'a> CborDecoder<'a> {
+    /// An error at the current position.
+    #[cold]
+    fn error(&self, message: &str) -> Option<DeserializeError<'static>> {
+        self.error_at(self.pos, message)
+    }
+
+    #[cold]
+    fn error_at(&self, pos: usize, message: &str) -> Option<DeserializeError<'static>> {
+        Some(DeserializeError::at_offset(
+            Cow::Owned(String::from(message)),
+            pos,
+        ))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Option<DeserializeError<'static>>> {
+        if unlikely!(self.data.len() - self.pos < len) {
+            return Err(self.error(UNEXPECTED_END));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Option<DeserializeError<'static>>> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Read the argument of an item given by its additional information
+    /// `info`, which is not `INDEFINITE`.
+    fn read_arg(
+        &mut self,
+        start: usize,
+        info: u8,
+    ) -> Result<u64, Option<DeserializeError<'static>>> {
+        let len = match info {
+            0..=23 => return Ok(info as u64),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(self.error_at(start, "invalid CBOR item")),
+        };
+        Ok(self
+            .take(len)?
+            .iter()
+            .fold(0, |acc, &c| (acc << 8) | c as u64))
+    }
+
+    /// Read the number of items of a container or bytes of a string, which
+    /// cannot be more than there are bytes left, as each is at least one.
+    fn read_len(
+        &mut self,
+        start: usize,
+        info: u8,
+    ) -> Result<usize, Option<DeserializeError<'static>>> {
+        let len = self.read_arg(start, info)?;
+        if unlikely!(len > (self.data.len() - self.pos) as u64) {
+            return Err(self.error(UNEXPECTED_END));
+        }
+        Ok(len as usize)
+    }
+
+    /// Consume the break ending an item of indefinite length, if it is next.
+    fn read_break(&mut self) -> Result<bool, Option<DeserializeError<'static>>> {
+        match self.data.get(self.pos) {
+            Some(&BREAK) => {
+                self.pos += 1;
+                Ok(true)
+            }
+            Some(_) => Ok(false),
+            None => Err(self.error(UNEXPECTED_END)),
+        }
+    }
+
+    /// Read the contents of a byte string or text string, of major type
+    /// `major`, including one of indefinite length in chunks.
+    fn read_string_bytes(
+        &mut self,
+        start: usize,
+        major: u8,
+        info: u8,
+    ) -> Result<Cow<'a, [u8]>, Option<DeserializeError<'static>>> {
+        if info != INDEFINITE {
+            let len = self.read_len(start, info)?;
+            return Ok(Cow::Borrowed(self.take(len)?));
+        }
+        let mut buf = Vec::new();
+        while !self.read_break()? {
+            let chunk_start = self.pos;
+            let initial = self.read_u8()?;
+            if initial >> 5 != major || initial & 0x1f == INDEFINITE {
+                return Err(self.error_at(chunk_start, "invalid chunk of CBOR string"));
+            }
+            let len = self.read_len(chunk_start, initial & 0x1f)?;
+            buf.extend_from_slice(self.take(len)?);
+        }
+        Ok(Cow::Owned(buf))
+    }
+
+    fn read_text(
+        &mut self,
+        start: usize,
+        info: u8,
+    ) -> Result<Cow<'a, str>, Option<DeserializeError<'static>>> {
+        let bytes = self.read_string_bytes(start, 3, info)?;
+        if unlikely!(!is_valid_utf8(&bytes)) {
+            return Err(self.error_at(start, INVALID_UTF8));
+        }
+        let val = match bytes {
+            Cow::Borrowed(bytes) => Cow::Borrowed(str_from_slice!(bytes.as_ptr(), bytes.len())),
+            Cow::Owned(bytes) => Cow::Owned(unsafe { String::from_utf8_unchecked(bytes) }),
+        };
+        self.args.check_string_len(&val)?;
+        Ok(val)
+    }
+
+    /// Read the head of the item an item of major type 6, a tag, applies to,
+    /// which must be of major type `major`.
+    fn read_tagged_head(
+        &mut self,
+        tag: u64,
+        major: u8,
+    ) -> Result<(usize, u8), Option<DeserializeError<'static>>> {
+        let start = self.pos;
+        let initial = self.read_u8()?;
+        if initial >> 5 != major {
+            return Err(self.error_at(start, &format!("invalid item for CBOR tag {}", tag)));
+        }
+        Ok((start, initial & 0x1f))
+    }
+
+    /// Read the text of a map key, which must be a string as in JSON.
+    fn read_key(&mut self) -> Result<Cow<'a, str>, Option<DeserializeError<'static>>> {
+        let start = self.pos;
+        let initial = self.read_u8()?;
+        if initial >> 5 != 3 {
+            return Err(self.error_at(start, "map key must be a string"));
+        }
+        self.read_text(start, initial & 0x1f)
+    }
+
+    fn parse_int(
+        &self,
+        val: i64,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        if unlikely!(self.args.parse_int.is_some()) {
+            return parse_number_literal(itoa::Buffer::new().format(val), self.args);
+        }
+        Ok(parse_i64(val))
+    }
+
+    fn parse_uint(
+        &self,
+        val: u64,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        if unlikely!(self.args.parse_int.is_some()) {
+            return parse_number_literal(itoa::Buffer::new().format(val), self.args);
+        }
+        Ok(parse_u64(val))
+    }
+
+    /// Deserialize the negative integer `-1 - val`.
+    fn parse_negative(
+        &self,
+        val: u64,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        if val <= i64::MAX as u64 {
+            return self.parse_int(-1 - val as i64);
+        }
+        parse_number_literal(&format!("-{}", val as u128 + 1), self.args)
+    }
+
+    /// Deserialize a float, given to `parse_float` as its shortest `str`.
+    /// `NaN` and infinity are refused unless `allow_nan`, as in JSON.
+    fn parse_float(
+        &self,
+        start: usize,
+        val: f64,
+        literal: &str,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        if unlikely!(!val.is_finite()) {
+            if !self.args.allow_nan {
+                return Err(self.error_at(start, "nan or inf number is not allowed"));
+            }
+            return Ok(parse_f64(val));
+        }
+        if unlikely!(self.args.parse_float.is_some()) {
+            return parse_number_literal(literal, self.args);
+        }
+        Ok(parse_f64(val))
+    }
+
+    /// Deserialize a bignum, tag 2 or 3, of big-endian `bytes`.
+    #[cold]
+    fn parse_bignum(
+        &self,
+        start: usize,
+        bytes: &[u8],
+        negative: bool,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let skip = bytes.iter().take_while(|&&c| c == 0).count();
+        let bytes = &bytes[skip..];
+        if bytes.len() <= 8 {
+            let val = bytes.iter().fold(0, |acc, &c| (acc << 8) | c as u64);
+            return if negative {
+                self.parse_negative(val)
+            } else {
+                self.parse_uint(val)
+            };
+        }
+        if self.args.strict {
+            return Err(self.error_at(start, "integer is out of range of a 64-bit integer"));
+        }
+        let mut obj = ffi!(_PyLong_FromByteArray(bytes.as_ptr(), bytes.len(), 0, 0));
+        if !obj.is_null() && negative {
+            let inverted = ffi!(PyNumber_Invert(obj));
+            ffi!(Py_DECREF(obj));
+            obj = inverted;
+        }
+        let obj = NonNull::new(obj).ok_or(None)?;
+        if self.args.parse_int.is_none() {
+            return Ok(obj);
+        }
+        let literal = ffi!(PyObject_Str(obj.as_ptr()));
+        ffi!(Py_DECREF(obj.as_ptr()));
+        if literal.is_null() {
+            return Err(None);
+        }
+        let res = parse_number_literal(unicode_to_str(literal).unwrap_or_default(), self.args);
+        ffi!(Py_DECREF(literal));
+        res
+    }
+
+    /// Deserialize the item tagged `tag`. Tags other than those of a
+    /// date/time, bignum, UUID, or self-described CBOR are refused.
+    fn parse_tag(
+        &mut self,
+        start: usize,
+        tag: u64,
+        remaining_depth: u32,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        match tag {
+            0 => {
+                let (start, info) = self.read_tagged_head(tag, 3)?;
+                let val = self.read_text(start, info)?;
+                match parse_datetime(&val) {
+                    Some(val) => new_datetime(&val).ok_or(None),
+                    None => Err(self.error_at(start, "invalid RFC 3339 date/time")),
+                }
+            }
+            1 => {
+                let timestamp = self.parse_timestamp(tag)?;
+                let obj = new_datetime_from_timestamp(timestamp);
+                ffi!(Py_DECREF(timestamp.as_ptr()));
+                obj.ok_or_else(|| {
+                    ffi!(PyErr_Clear());
+                    self.error_at(start, "timestamp is out of range")
+                })
+            }
+            2 | 3 => {
+                let (start, info) = self.read_tagged_head(tag, 2)?;
+                let bytes = self.read_string_bytes(start, 2, info)?;
+                self.parse_bignum(start, &bytes, tag == 3)
+            }
+            37 => {
+                let (start, info) = self.read_tagged_head(tag, 2)?;
+                let bytes = self.read_string_bytes(start, 2, info)?;
+                if bytes.len() != 16 {
+                    return Err(self.error_at(start, "UUID must be 16 bytes"));
+                }
+                let mut buf = [0u8; 16];
+                buf.copy_from_slice(&bytes);
+                parse_uuid(&buf)
+            }
+            55799 => self.parse(remaining_depth - 1),
+            _ => Err(self.error_at(start, &format!("CBOR tag {} is not supported", tag))),
+        }
+    }
+
+    /// Deserialize the number of seconds since the epoch tagged 1 to an
+    /// `int` or `float`, without hooks.
+    fn parse_timestamp(
+        &mut self,
+        tag: u64,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let start = self.pos;
+        let initial = self.read_u8()?;
+        let info = initial & 0x1f;
+        let val = match (initial >> 5, info) {
+            (0, _) => return Ok(parse_u64(self.read_arg(start, info)?)),
+            (1, _) => {
+                let val = self.read_arg(start, info)?;
+                if val > i64::MAX as u64 {
+                    return Err(self.error_at(start, "timestamp is out of range"));
+                }
+                return Ok(parse_i64(-1 - val as i64));
+            }
+            (7, 25) => f16_to_f64(self.read_arg(start, info)? as u16),
+            (7, 26) => f32::from_bits(self.read_arg(start, info)? as u32) as f64,
+            (7, 27) => f64::from_bits(self.read_arg(start, info)?),
+            _ => return Err(self.error_at(start, &format!("invalid item for CBOR tag {}", tag))),
+        };
+        if !val.is_finite() {
+            return Err(self.error_at(start, "timestamp is out of range"));
+        }
+        Ok(parse_f64(val))
+    }
+
+    /// Deserialize an item of major type 7: a simple value or float.
+    fn parse_simple(
+        &mut self,
+        start: usize,
+        info: u8,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        match info {
+            20 => Ok(parse_false()),
+            21 => Ok(parse_true()),
+            // null and undefined
+            22 | 23 => Ok(parse_none()),
+            25 => {
+                let val = f16_to_f64(self.read_arg(start, info)? as u16);
+                self.parse_float(start, val, ryu::Buffer::new().format(val as f32))
+            }
+            26 => {
+                let val = f32::from_bits(self.read_arg(start, info)? as u32);
+                self.parse_float(start, val as f64, ryu::Buffer::new().format(val))
+            }
+            27 => {
+                let val = f64::from_bits(self.read_arg(start, info)?);
+                self.parse_float(start, val, ryu::Buffer::new().format(val))
+            }
+            INDEFINITE => Err(self.error_at(start, "unexpected break")),
+            _ => Err(self.error_at(start, "CBOR simple values are not supported")),
+        }
+    }
+
+    fn parse_array(
+        &mut self,
+        len: usize,
+        remaining_depth: u32,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        self.args.add_items(len)?;
+        let array = new_array(len, self.args);
+        for idx in 0..len {
+            match self.parse(remaining_depth - 1) {
+                Ok(each) => set_array_item(array, idx, each.as_ptr(), self.args),
+                Err(err) => {
+                    // items not set are NULL, which are skipped when it is freed
+                    ffi!(Py_DECREF(array));
+                    return Err(err);
+                }
+            }
+        }
+        Ok(nonnull!(array))
+    }
+
+    /// Deserialize an array of indefinite length, whose items are collected
+    /// until its break.
+    #[cold]
+    fn parse_indefinite_array(
+        &mut self,
+        remaining_depth: u32,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let mut items: Vec<*mut pyo3_ffi::PyObject> = Vec::new();
+        let res = loop {
+            match self.read_break() {
+                Ok(true) => break Ok(()),
+                Ok(false) => {}
+                Err(err) => break Err(err),
+            }
+            if let Err(err) = self.args.add_items(1) {
+                break Err(Some(err));
+            }
+            match self.parse(remaining_depth - 1) {
+                Ok(each) => items.push(each.as_ptr()),
+                Err(err) => break Err(err),
+            }
+        };
+        if let Err(err) = res {
+            for &each in items.iter() {
+                ffi!(Py_DECREF(each));
+            }
+            return Err(err);
+        }
+        let array = new_array(items.len(), self.args);
+        for (idx, &each) in items.iter().enumerate() {
+            set_array_item(array, idx, each, self.args);
+        }
+        Ok(nonnull!(array))
+    }
+
+    /// Deserialize a map of `len` pairs, or of indefinite length if `None`.
+    fn parse_map(
+        &mut self,
+        len: Option<usize>,
+        remaining_depth: u32,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        if let Some(len) = len {
+            self.args.add_items(len)?;
+        }
+        if let Some(func) = self.args.object_pairs_hook {
+            return self.parse_map_pairs(len, func, remaining_depth);
+        }
+        let dict = ffi!(PyDict_New());
+        let mut collected = Vec::new();
+        let mut count = 0;
+        while !self.read_map_end(len, count).map_err(|err| {
+            ffi!(Py_DECREF(dict));
+            err
+        })? {
+            count += 1;
+            let key = match self.read_key() {
+                Ok(key) => key,
+                Err(err) => {
+                    ffi!(Py_DECREF(dict));
+                    return Err(err);
+                }
+            };
+            let (pykey, pyhash) = get_unicode_key(&key, self.args);
+            let pyval = match self.parse(remaining_depth - 1) {
+                Ok(pyval) => pyval,
+                Err(err) => {
+                    ffi!(Py_DECREF(pykey));
+                    ffi!(Py_DECREF(dict));
+                    return Err(err);
+                }
+            };
+            if let Err(err) = set_object_item(dict, pykey, pyhash, pyval, self.args, &mut collected)
+            {
+                ffi!(Py_DECREF(dict));
+                return Err(Some(err));
+            }
+        }
+        parse_object_hook(nonnull!(dict), &collected, self.args)
+    }
+
+    /// Whether a map of `len` pairs, or of indefinite length if `None`, ends
+    /// after `count` pairs, counting each pair of one of indefinite length.
+    fn read_map_end(
+        &mut self,
+        len: Option<usize>,
+        count: usize,
+    ) -> Result<bool, Option<DeserializeError<'static>>> {
+        match len {
+            Some(len) => Ok(count == len),
+            None if self.read_break()? => Ok(true),
+            None => {
+                self.args.add_items(1)?;
+                Ok(false)
+            }
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn parse_map_pairs(
+        &mut self,
+        len: Option<usize>,
+        func: NonNull<pyo3_ffi::PyObject>,
+        remaining_depth: u32,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let list = ffi!(PyList_New(0));
+        let mut count = 0;
+        loop {
+            let res = self.read_map_end(len, count).and_then(|end| {
+                if end {
+                    return Ok(None);
+                }
+                let key = self.read_key()?;
+                let (pykey, _) = get_unicode_key(&key, self.args);
+                match self.parse(remaining_depth - 1) {
+                    Ok(pyval) => Ok(Some(parse_pair(pykey, pyval))),
+                    Err(err) => {
+                        ffi!(Py_DECREF(pykey));
+                        Err(err)
+                    }
+                }
+            });
+            match res {
+                Ok(Some(pair)) => {
+                    ffi!(PyList_Append(list, pair));
+                    ffi!(Py_DECREF(pair));
+                    count += 1;
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    ffi!(Py_DECREF(list));
+                    return Err(err);
+                }
+            }
+        }
+        call_object_hook(func, nonnull!(list))
+    }
+
+    /// Deserialize the next item, in which containers and tags may be nested
+    /// `remaining_depth` levels deep.
+    fn parse(
+        &mut self,
+        remaining_depth: u32,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let start = self.pos;
+        let initial = self.read_u8()?;
+        let major = initial >> 5;
+        let info = initial & 0x1f;
+        if unlikely!(remaining_depth == 0 && matches!(major, 4..=6)) {
+            return Err(self.error_at(start, RECURSION_LIMIT));
+        }
+        match major {
+            0 => {
+                let val = self.read_arg(start, info)?;
+                self.parse_uint(val)
+            }
+            1 => {
+                let val = self.read_arg(start, info)?;
+                self.parse_negative(val)
+            }
+            2 => {
+                let bytes = self.read_string_bytes(start, major, info)?;
+                Ok(nonnull!(ffi!(PyBytes_FromStringAndSize(
+                    bytes.as_ptr() as *const c_char,
+                    bytes.len() as isize
+                ))))
+            }
+            3 => {
+                let val = self.read_text(start, info)?;
+                parse_str(&val, self.args)
+            }
+            4 if info == INDEFINITE => self.parse_indefinite_array(remaining_depth),
+            4 => {
+                let len = self.read_len(start, info)?;
+                self.parse_array(len, remaining_depth)
+            }
+            5 if info == INDEFINITE => self.parse_map(None, remaining_depth),
+            5 => {
+                let len = self.read_len(start, info)?;
+                self.parse_map(Some(len), remaining_depth)
+            }
+            6 => {
+                let tag = self.read_arg(start, info)?;
+                self.parse_tag(start, tag, remaining_depth)
+            }
+            _ => self.parse_simple(start, info),
+        }
+    }
+}
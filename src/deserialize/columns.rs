@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::deserializer::deserialize;
+use crate::deserialize::{DeserializeError, DuplicateKeys, LoadsArgs};
+use crate::typeref::*;
+use crate::unicode::unicode_to_str;
+use std::borrow::Cow;
+use std::ptr::NonNull;
+
+/// Deserialize `ptr`, a top-level array of objects, to a `dict` of a `list`
+/// for each key of them, of its value in each object in order, or `None` for
+/// an object without the key. No `dict` is made for the objects themselves.
+pub fn loads_columns(
+    ptr: *mut pyo3_ffi::PyObject,
+    args: &mut LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if args.duplicate_keys == DuplicateKeys::List {
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+            "duplicate_keys=\"list\" cannot be used with loads_columns()",
+        ))));
+    }
+    args.columns = true;
+    deserialize(ptr, args)
+}
+
+/// The error of a document that is not an array of objects.
+#[cold]
+pub fn not_records() -> DeserializeError<'static> {
+    DeserializeError::invalid(Cow::Borrowed("Expected an array of objects"))
+}
+
+/// The `dict` of columns made from the objects of an array, one row at a
+/// time.
+pub struct Columns {
+    // owned until `finish()`
+    dict: *mut pyo3_ffi::PyObject,
+    // the number of rows that have ended
+    rows: usize,
+}
+
+impl Columns {
+    pub fn new() -> Self {
+        Columns {
+            dict: ffi!(PyDict_New()),
+            rows: 0,
+        }
+    }
+
+    /// Set `key` of the current row to `value`, consuming them. A key already
+    /// set in the row is handled according to `duplicate_keys` in `args`.
+    pub fn set(
+        &mut self,
+        key: *mut pyo3_ffi::PyObject,
+        hash: pyo3_ffi::Py_hash_t,
+        value: NonNull<pyo3_ffi::PyObject>,
+        args: &LoadsArgs,
+    ) -> Result<(), DeserializeError<'static>> {
+        let mut list = ffi!(PyDict_GetItem(self.dict, key));
+        if list.is_null() {
+            list = ffi!(PyList_New(0));
+            ffi!(_PyDict_SetItem_KnownHash(self.dict, key, list, hash));
+            ffi!(Py_DECREF(list));
+        }
+        let len = ffi!(PyList_GET_SIZE(list)) as usize;
+        let mut res = Ok(());
+        if unlikely!(len > self.rows) {
+            match args.duplicate_keys {
+                DuplicateKeys::First => ffi!(Py_DECREF(value.as_ptr())),
+                DuplicateKeys::Error => {
+                    ffi!(Py_DECREF(value.as_ptr()));
+                    let key_str = unicode_to_str(key).unwrap_or_default();
+                    res = Err(DeserializeError::invalid(Cow::Owned(format!(
+                        "Duplicate key \"{}\"",
+                        key_str
+                    ))));
+                }
+                _ => {
+                    ffi!(PyList_SetItem(list, self.rows as isize, value.as_ptr()));
+                }
+            }
+        } else {
+            pad(list, self.rows);
+            ffi!(PyList_Append(list, value.as_ptr()));
+            ffi!(Py_DECREF(value.as_ptr()));
+        }
+        ffi!(Py_DECREF(key));
+        res
+    }
+
+    /// End the current row.
+    pub fn end_row(&mut self) {
+        self.rows += 1;
+    }
+
+    /// Return the `dict`, with `None` for each row that ended without a key.
+    pub fn finish(mut self) -> NonNull<pyo3_ffi::PyObject> {
+        let dict = std::mem::replace(&mut self.dict, std::ptr::null_mut());
+        let mut pos: pyo3_ffi::Py_ssize_t = 0;
+        let mut key: *mut pyo3_ffi::PyObject = std::ptr::null_mut();
+        let mut list: *mut pyo3_ffi::PyObject = std::ptr::null_mut();
+        while ffi!(PyDict_Next(dict, &mut pos, &mut key, &mut list)) != 0 {
+            pad(list, self.rows);
+        }
+        nonnull!(dict)
+    }
+}
+
+impl Drop for Columns {
+    fn drop(&mut self) {
+        if !self.dict.is_null() {
+            ffi!(Py_DECREF(self.dict));
+        }
+    }
+}
+
+/// Append `None` to `list` until it has `len` items.
+fn pad(list: *mut pyo3_ffi::PyObject, len: usize) {
+    for _ in (ffi!(PyList_GET_SIZE(list)) as usize)..len {
+        ffi!(PyList_Append(list, NONE));
+    }
+}
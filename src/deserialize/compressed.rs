@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(feature = "compression")]
+use crate::deserialize::deserializer::ReadLimit;
+#[cfg(feature = "compression")]
+use crate::deserialize::file::{ChunkedReader, READ_CHUNK_SIZE};
+#[cfg(feature = "compression")]
+use crate::deserialize::json::deserialize_json_reader;
+#[cfg(feature = "compression")]
+use crate::deserialize::schema::check_schema;
+#[cfg(feature = "compression")]
+use crate::deserialize::utf8::BufferInput;
+use crate::deserialize::{DeserializeError, LoadsArgs};
+#[cfg(feature = "compression")]
+use crate::typeref::*;
+use std::borrow::Cow;
+#[cfg(feature = "compression")]
+use std::io::{BufRead, BufReader, Read};
+use std::ptr::NonNull;
+
+/// The format of compressed input.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+#[cfg(feature = "compression")]
+impl Compression {
+    /// The format of input starting with `magic`, if it is known.
+    fn detect(magic: &[u8]) -> Option<Compression> {
+        if magic.starts_with(b"\x1f\x8b") {
+            Some(Compression::Gzip)
+        } else if magic.starts_with(b"\x28\xb5\x2f\xfd") {
+            Some(Compression::Zstd)
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+        }
+    }
+}
+
+/// Deserialize the compressed JSON of a bytes-like object, file object, or
+/// file descriptor while decompressing it, so that neither it nor its
+/// decompressed contents are held in memory in full. If `compression` is
+/// `None`, the format is detected from the start of the input. An error of
+/// `None` means an exception was raised reading it or by a callable in
+/// `args`.
+#[cfg(feature = "compression")]
+pub fn loads_compressed(
+    ptr: *mut pyo3_ffi::PyObject,
+    compression: Option<Compression>,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if unsafe { ob_type!(ptr) != INT_TYPE } && ffi!(PyObject_CheckBuffer(ptr)) == 1 {
+        // the buffer is exported, so it cannot be resized by a hook
+        let input = BufferInput::new(ptr)?;
+        return deserialize_compressed(input.as_bytes(), compression, args);
+    }
+    let mut reader = ChunkedReader::new(ptr)?;
+    let res = deserialize_compressed(
+        BufReader::with_capacity(READ_CHUNK_SIZE, &mut reader),
+        compression,
+        args,
+    );
+    // an error reading the file is raised in place of the error it caused
+    res.map_err(|err| reader.take_error().unwrap_or(err))
+}
+
+/// `loads_compressed()` of orjson built without the `compression` feature,
+/// which cannot decompress its input.
+#[cfg(not(feature = "compression"))]
+pub fn loads_compressed(
+    _ptr: *mut pyo3_ffi::PyObject,
+    _compression: Option<Compression>,
+    _args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    Err(Some(DeserializeError::invalid(Cow::Borrowed(
+        "loads_compressed() requires orjson built with the compression feature",
+    ))))
+}
+
+#[cfg(feature = "compression")]
+fn deserialize_compressed<R: BufRead>(
+    mut input: R,
+    compression: Option<Compression>,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let compression = match compression {
+        Some(compression) => compression,
+        None => {
+            let magic = input.fill_buf().map_err(read_error)?;
+            Compression::detect(magic).ok_or_else(|| {
+                DeserializeError::invalid(Cow::Borrowed(
+                    "Input is not compressed with gzip or zstd",
+                ))
+            })?
+        }
+    };
+    match compression {
+        Compression::Gzip => deserialize_decoder(
+            flate2::bufread::MultiGzDecoder::new(input),
+            compression,
+            args,
+        ),
+        Compression::Zstd => {
+            let decoder = zstd::stream::read::Decoder::with_buffer(input).map_err(read_error)?;
+            deserialize_decoder(decoder, compression, args)
+        }
+    }
+}
+
+/// Deserialize the output of `decoder`, which is read in chunks, as JSON.
+#[cfg(feature = "compression")]
+fn deserialize_decoder<D: Read>(
+    decoder: D,
+    compression: Compression,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let mut reader = DecompressedReader {
+        decoder: decoder,
+        compression: compression,
+        error: None,
+        limit: ReadLimit::new(args.max_bytes),
+    };
+    match deserialize_json_reader(BufReader::with_capacity(READ_CHUNK_SIZE, &mut reader), args) {
+        Ok(obj) => check_schema(obj, args, None),
+        Err(err) => match reader.error.take() {
+            Some(read_err) if err.is_io => Err(Some(read_err)),
+            _ if !ffi!(PyErr_Occurred()).is_null() => Err(None),
+            _ => Err(Some(err.error)),
+        },
+    }
+}
+
+/// The error of a failed read of the compressed input. A file's own error is
+/// kept by its `ChunkedReader` and raised instead.
+#[cfg(feature = "compression")]
+#[cold]
+fn read_error(_err: std::io::Error) -> Option<DeserializeError<'static>> {
+    Some(DeserializeError::invalid(Cow::Borrowed(
+        "Input file could not be read",
+    )))
+}
+
+/// Reader of the decompressed input for the parser. An error decompressing
+/// it is kept to be raised in place of the parser's error.
+#[cfg(feature = "compression")]
+struct DecompressedReader<D: Read> {
+    decoder: D,
+    compression: Compression,
+    error: Option<DeserializeError<'static>>,
+    // bytes given to the parser, which fails if there are more than
+    // `max_bytes`
+    limit: ReadLimit,
+}
+
+#[cfg(feature = "compression")]
+impl<D: Read> Read for DecompressedReader<D> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.decoder.read(buf).map_err(|err| {
+            self.error = Some(DeserializeError::invalid(Cow::Owned(format!(
+                "Input is not valid {}: {}",
+                self.compression.name(),
+                err
+            ))));
+            err
+        })?;
+        if let Err(err) = self.limit.add(&buf[..read]) {
+            self.error = Some(err);
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "Input exceeds max_bytes",
+            ));
+        }
+        Ok(read)
+    }
+}
@@ -0,0 +1,251 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::pyobject::parse_none;
+use crate::typeref::*;
+use pyo3_ffi::PyDateTimeAPI;
+use std::ptr::NonNull;
+
+/// A date parsed from RFC 3339.
+pub struct Date {
+    pub year: i32,
+    pub month: i32,
+    pub day: i32,
+}
+
+/// A time parsed from RFC 3339, with its offset from UTC in seconds if it
+/// has one.
+pub struct Time {
+    pub hour: i32,
+    pub minute: i32,
+    pub second: i32,
+    pub microsecond: i32,
+    pub offset: Option<i32>,
+}
+
+/// A date and time parsed from RFC 3339.
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+/// The value of the ASCII digits `bytes`, if they are all digits.
+fn digits(bytes: &[u8]) -> Option<i32> {
+    bytes.iter().try_fold(0, |acc, &c| {
+        if c.is_ascii_digit() {
+            Some(acc * 10 + (c - b'0') as i32)
+        } else {
+            None
+        }
+    })
+}
+
+fn days_in_month(year: i32, month: i32) -> i32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Parse an RFC 3339 `full-date`, e.g., `2020-01-01`, of a year from 1, as
+/// Python supports.
+fn parse_date(bytes: &[u8]) -> Option<Date> {
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+    let year = digits(&bytes[0..4])?;
+    let month = digits(&bytes[5..7])?;
+    let day = digits(&bytes[8..10])?;
+    if year == 0 || !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(Date {
+        year: year,
+        month: month,
+        day: day,
+    })
+}
+
+/// Parse an RFC 3339 `full-time`, e.g., `12:30:00.5+01:00`, or a
+/// `partial-time` without an offset. Fractional seconds beyond microseconds
+/// are truncated and a leap second is refused.
+fn parse_time(bytes: &[u8]) -> Option<Time> {
+    if bytes.len() < 8 || bytes[2] != b':' || bytes[5] != b':' {
+        return None;
+    }
+    let hour = digits(&bytes[0..2])?;
+    let minute = digits(&bytes[3..5])?;
+    let second = digits(&bytes[6..8])?;
+    if hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    let mut rest = &bytes[8..];
+    let mut microsecond = 0;
+    if let [b'.', fraction @ ..] = rest {
+        let len = fraction.iter().take_while(|c| c.is_ascii_digit()).count();
+        if len == 0 {
+            return None;
+        }
+        // padded with zeros to, or truncated at, six digits
+        microsecond = fraction[..len]
+            .iter()
+            .chain(std::iter::repeat(&b'0'))
+            .take(6)
+            .fold(0, |acc, &c| acc * 10 + (c - b'0') as i32);
+        rest = &fraction[len..];
+    }
+    let offset = match rest {
+        [] => None,
+        [b'Z' | b'z'] => Some(0),
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let hours = digits(&[*h1, *h2])?;
+            let minutes = digits(&[*m1, *m2])?;
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = hours * 3600 + minutes * 60;
+            if *sign == b'-' {
+                Some(-offset)
+            } else {
+                Some(offset)
+            }
+        }
+        _ => return None,
+    };
+    Some(Time {
+        hour: hour,
+        minute: minute,
+        second: second,
+        microsecond: microsecond,
+        offset: offset,
+    })
+}
+
+/// Parse a date and time, separated by `T`, of which the time may not have
+/// an offset.
+fn parse_date_time(bytes: &[u8]) -> Option<DateTime> {
+    if bytes.len() < 19 || !matches!(bytes[10], b'T' | b't') {
+        return None;
+    }
+    Some(DateTime {
+        date: parse_date(&bytes[..10])?,
+        time: parse_time(&bytes[11..])?,
+    })
+}
+
+/// Parse an RFC 3339 `date-time`, e.g., `2020-01-01T12:30:00.5+01:00`, which
+/// must have an offset.
+pub fn parse_datetime(val: &str) -> Option<DateTime> {
+    parse_date_time(val.as_bytes()).filter(|val| val.time.offset.is_some())
+}
+
+/// A `datetime.timezone` of `offset` seconds from UTC. `None` means an
+/// exception was raised.
+fn new_timezone(offset: i32) -> Option<NonNull<pyo3_ffi::PyObject>> {
+    unsafe {
+        let api = *PyDateTimeAPI();
+        if offset == 0 {
+            ffi!(Py_INCREF(api.TimeZone_UTC));
+            return NonNull::new(api.TimeZone_UTC);
+        }
+        let delta = (api.Delta_FromDelta)(0, offset, 0, 1, api.DeltaType);
+        if delta.is_null() {
+            return None;
+        }
+        let tz = (api.TimeZone_FromTimeZone)(delta, std::ptr::null_mut());
+        ffi!(Py_DECREF(delta));
+        NonNull::new(tz)
+    }
+}
+
+/// The `tzinfo` of a time with `offset`, or `None` if it has none.
+fn new_tzinfo(offset: Option<i32>) -> Option<NonNull<pyo3_ffi::PyObject>> {
+    match offset {
+        Some(offset) => new_timezone(offset),
+        None => Some(parse_none()),
+    }
+}
+
+/// A `datetime.datetime` of `val`, which is timezone-aware if it has an
+/// offset. `None` means an exception was raised.
+pub fn new_datetime(val: &DateTime) -> Option<NonNull<pyo3_ffi::PyObject>> {
+    let tz = new_tzinfo(val.time.offset)?;
+    let obj = unsafe {
+        ((*PyDateTimeAPI()).DateTime_FromDateAndTime)(
+            val.date.year,
+            val.date.month,
+            val.date.day,
+            val.time.hour,
+            val.time.minute,
+            val.time.second,
+            val.time.microsecond,
+            tz.as_ptr(),
+            DATETIME_TYPE,
+        )
+    };
+    ffi!(Py_DECREF(tz.as_ptr()));
+    NonNull::new(obj)
+}
+
+fn new_date(val: &Date) -> Option<NonNull<pyo3_ffi::PyObject>> {
+    let obj =
+        unsafe { ((*PyDateTimeAPI()).Date_FromDate)(val.year, val.month, val.day, DATE_TYPE) };
+    NonNull::new(obj)
+}
+
+fn new_time(val: &Time) -> Option<NonNull<pyo3_ffi::PyObject>> {
+    let tz = new_tzinfo(val.offset)?;
+    let obj = unsafe {
+        ((*PyDateTimeAPI()).Time_FromTime)(
+            val.hour,
+            val.minute,
+            val.second,
+            val.microsecond,
+            tz.as_ptr(),
+            TIME_TYPE,
+        )
+    };
+    ffi!(Py_DECREF(tz.as_ptr()));
+    NonNull::new(obj)
+}
+
+/// Deserialize `val` to a `datetime.datetime`, `datetime.date`, or
+/// `datetime.time` if it is an RFC 3339 `date-time`, `full-date`, or
+/// `full-time`. A date and time or time without an offset, as orjson
+/// serializes a naive `datetime.datetime` or `datetime.time`, is not one.
+/// Return `None` if `val` is not one, or else the object, which is NULL if an
+/// exception was raised.
+pub fn parse_datetime_like(val: &str) -> Option<*mut pyo3_ffi::PyObject> {
+    let bytes = val.as_bytes();
+    if bytes.len() < 8 || !bytes[0].is_ascii_digit() {
+        return None;
+    }
+    let obj = if bytes[2] == b':' {
+        new_time(&parse_time(bytes).filter(|val| val.offset.is_some())?)
+    } else if bytes.len() == 10 {
+        new_date(&parse_date(bytes)?)
+    } else {
+        new_datetime(&parse_datetime(val)?)
+    };
+    Some(obj.map_or(std::ptr::null_mut(), NonNull::as_ptr))
+}
+
+/// A `datetime.datetime` in UTC of `timestamp`, seconds since the epoch,
+/// which is an `int` or `float`. `None` means an exception was raised, e.g.,
+/// for a timestamp out of range.
+pub fn new_datetime_from_timestamp(
+    timestamp: NonNull<pyo3_ffi::PyObject>,
+) -> Option<NonNull<pyo3_ffi::PyObject>> {
+    unsafe {
+        let api = *PyDateTimeAPI();
+        let args = ffi!(PyTuple_New(2));
+        ffi!(Py_INCREF(timestamp.as_ptr()));
+        ffi!(PyTuple_SET_ITEM(args, 0, timestamp.as_ptr()));
+        ffi!(Py_INCREF(api.TimeZone_UTC));
+        ffi!(PyTuple_SET_ITEM(args, 1, api.TimeZone_UTC));
+        let obj = (api.DateTime_FromTimestamp)(DATETIME_TYPE, args, std::ptr::null_mut());
+        ffi!(Py_DECREF(args));
+        NonNull::new(obj)
+    }
+}
@@ -0,0 +1,226 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::{deserialize, DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use std::borrow::Cow;
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr::{null_mut, NonNull};
+
+/// `orjson.Decoder`, the arguments of `loads()` other than the input, given
+/// once and used by each call of `decode()`.
+#[repr(C)]
+pub struct Decoder {
+    ob_base: pyo3_ffi::PyObject,
+    // the hooks are owned references
+    args: LoadsArgs,
+}
+
+unsafe extern "C" fn decoder_new(
+    subtype: *mut pyo3_ffi::PyTypeObject,
+    args: *mut pyo3_ffi::PyObject,
+    kwds: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    if pyo3_ffi::PyTuple_GET_SIZE(args) != 0 {
+        return crate::raise_loads_exception(DeserializeError::invalid(Cow::Borrowed(
+            "Decoder() takes no positional arguments",
+        )));
+    }
+    // these are of each input, so are given to `decode()`
+    let loads_args = match parse_kwargs(kwds, &[OFFSET, LENGTH], "Decoder") {
+        Ok(loads_args) => loads_args,
+        Err(err) => return err,
+    };
+    let obj = pyo3_ffi::PyType_GenericAlloc(subtype, 0);
+    if obj.is_null() {
+        return null_mut();
+    }
+    for func in hooks(&loads_args).iter().flatten() {
+        pyo3_ffi::Py_INCREF(func.as_ptr());
+    }
+    std::ptr::write(
+        std::ptr::addr_of_mut!((*(obj as *mut Decoder)).args),
+        loads_args,
+    );
+    obj
+}
+
+/// Parse `kwds`, the keyword arguments of `loads()` other than those in
+/// `excluded`, which may be null. An error is the result of raising
+/// `JSONDecodeError`, naming `func` for an argument not accepted.
+pub unsafe fn parse_kwargs(
+    kwds: *mut pyo3_ffi::PyObject,
+    excluded: &[*mut pyo3_ffi::PyObject],
+    func: &str,
+) -> Result<LoadsArgs, *mut pyo3_ffi::PyObject> {
+    let mut loads_args = LoadsArgs::default();
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            let known =
+                !excluded.contains(&arg) && crate::parse_loads_kwarg(arg, val, &mut loads_args)?;
+            if !known {
+                return Err(crate::raise_unexpected_kwarg(func, arg));
+            }
+        }
+        crate::check_loads_args(&mut loads_args)?;
+    }
+    Ok(loads_args)
+}
+
+/// The callables and schema of `args`, which an object holding `args` owns
+/// references to.
+pub fn hooks(args: &LoadsArgs) -> [Option<NonNull<pyo3_ffi::PyObject>>; 6] {
+    [
+        args.parse_float,
+        args.parse_int,
+        args.object_hook,
+        args.object_pairs_hook,
+        args.dict_class,
+        args.schema,
+    ]
+}
+
+/// Deserialize `data` with the arguments of `slf`, and `offset` and `length`
+/// if given in `kwargs`, a sequence of names and values.
+unsafe fn decode(
+    slf: *mut pyo3_ffi::PyObject,
+    data: *mut pyo3_ffi::PyObject,
+    kwargs: impl Iterator<Item = (*mut pyo3_ffi::PyObject, *mut pyo3_ffi::PyObject)>,
+) -> *mut pyo3_ffi::PyObject {
+    // per call, so that a call from a hook or another thread does not share
+    // the count of items or cache of keys
+    let mut args = (*(slf as *mut Decoder)).args.clone_options();
+    for (arg, val) in kwargs {
+        if arg == OFFSET {
+            match crate::parse_loads_limit(val, "Invalid offset") {
+                Ok(offset) => args.offset = offset,
+                Err(err) => return err,
+            }
+        } else if arg == LENGTH {
+            match crate::parse_loads_limit(val, "Invalid length") {
+                Ok(length) => args.length = length,
+                Err(err) => return err,
+            }
+        } else {
+            return crate::raise_unexpected_kwarg("decode", arg);
+        }
+    }
+    match deserialize(data, &args) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => crate::raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[cfg(Py_3_8)]
+unsafe extern "C" fn decoder_decode(
+    slf: *mut pyo3_ffi::PyObject,
+    args: *const *mut pyo3_ffi::PyObject,
+    nargs: pyo3_ffi::Py_ssize_t,
+    kwnames: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    let num_args = pyo3_ffi::PyVectorcall_NARGS(nargs as usize);
+    if unlikely!(num_args != 1) {
+        return crate::raise_loads_exception(DeserializeError::invalid(Cow::Borrowed(
+            "decode() takes exactly 1 positional argument",
+        )));
+    }
+    let num_kwargs = if kwnames.is_null() {
+        0
+    } else {
+        pyo3_ffi::Py_SIZE(kwnames)
+    };
+    let kwargs = (0..num_kwargs).map(|idx| {
+        (
+            pyo3_ffi::PyTuple_GET_ITEM(kwnames, idx),
+            *args.offset(num_args + idx),
+        )
+    });
+    decode(slf, *args, kwargs)
+}
+
+#[cfg(not(Py_3_8))]
+unsafe extern "C" fn decoder_decode(
+    slf: *mut pyo3_ffi::PyObject,
+    args: *mut pyo3_ffi::PyObject,
+    kwds: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    if unlikely!(pyo3_ffi::Py_SIZE(args) != 1) {
+        return crate::raise_loads_exception(DeserializeError::invalid(Cow::Borrowed(
+            "decode() takes exactly 1 positional argument",
+        )));
+    }
+    let data = pyo3_ffi::PyTuple_GET_ITEM(args, 0);
+    if kwds.is_null() {
+        decode(slf, data, std::iter::empty())
+    } else {
+        decode(slf, data, crate::ffi::PyDictIter::from_pyobject(kwds))
+    }
+}
+
+unsafe extern "C" fn decoder_dealloc(slf: *mut pyo3_ffi::PyObject) {
+    let args = std::ptr::addr_of_mut!((*(slf as *mut Decoder)).args);
+    for func in hooks(&*args).iter().flatten() {
+        pyo3_ffi::Py_DECREF(func.as_ptr());
+    }
+    std::ptr::drop_in_place(args);
+    let tp = ob_type!(slf);
+    ((*tp).tp_free.unwrap())(slf as *mut c_void);
+    pyo3_ffi::Py_DECREF(tp as *mut pyo3_ffi::PyObject);
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub unsafe fn create_decoder_type() -> *mut pyo3_ffi::PyTypeObject {
+    let decode_doc = "decode(obj, /, *, offset=None, length=None)\n--\n\nDeserialize JSON to Python objects with the arguments of the Decoder.\0";
+    let methods: Box<[pyo3_ffi::PyMethodDef; 2]> = Box::new([
+        #[cfg(Py_3_8)]
+        pyo3_ffi::PyMethodDef {
+            ml_name: "decode\0".as_ptr() as *const c_char,
+            ml_meth: pyo3_ffi::PyMethodDefPointer {
+                _PyCFunctionFastWithKeywords: decoder_decode,
+            },
+            ml_flags: pyo3_ffi::METH_FASTCALL | pyo3_ffi::METH_KEYWORDS,
+            ml_doc: decode_doc.as_ptr() as *const c_char,
+        },
+        #[cfg(not(Py_3_8))]
+        pyo3_ffi::PyMethodDef {
+            ml_name: "decode\0".as_ptr() as *const c_char,
+            ml_meth: pyo3_ffi::PyMethodDefPointer {
+                PyCFunctionWithKeywords: decoder_decode,
+            },
+            ml_flags: pyo3_ffi::METH_VARARGS | pyo3_ffi::METH_KEYWORDS,
+            ml_doc: decode_doc.as_ptr() as *const c_char,
+        },
+        std::mem::zeroed(),
+    ]);
+    let slots: Box<[pyo3_ffi::PyType_Slot; 5]> = Box::new([
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_new,
+            pfunc: decoder_new as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_methods,
+            pfunc: Box::into_raw(methods) as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_doc,
+            pfunc: "Decoder(*, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, surrogates=None, replace_invalid_utf8=False, allow_nan=False, strict=False, jsonc=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, numpy=False, raw_numbers=False, threads=None, schema=None)\n--\n\nDeserialize JSON to Python objects with arguments given once.\0".as_ptr() as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_dealloc,
+            pfunc: decoder_dealloc as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: 0,
+            pfunc: std::ptr::null_mut(),
+        },
+    ]);
+    let spec = Box::new(pyo3_ffi::PyType_Spec {
+        name: "orjson.Decoder\0".as_ptr() as *const c_char,
+        basicsize: std::mem::size_of::<Decoder>() as c_int,
+        itemsize: 0,
+        flags: pyo3_ffi::Py_TPFLAGS_DEFAULT as c_uint,
+        slots: Box::into_raw(slots) as *mut pyo3_ffi::PyType_Slot,
+    });
+    pyo3_ffi::PyType_FromSpec(Box::into_raw(spec)) as *mut pyo3_ffi::PyTypeObject
+}
@@ -1,19 +1,430 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use crate::deserialize::utf8::read_input_to_buf;
+use crate::deserialize::cache::LongKeyCache;
+use crate::deserialize::error::Location;
+use crate::deserialize::pyobject::new_array;
+use crate::deserialize::utf8::{
+    is_buffer_input, is_valid_utf8, read_input_bytes, read_input_to_buf, BufferInput,
+};
 use crate::deserialize::DeserializeError;
+use crate::error::INVALID_STR;
 use crate::typeref::*;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
 use std::ptr::NonNull;
 
+/// Nesting of containers allowed if `max_depth` is not given.
+pub const DEFAULT_MAX_DEPTH: u32 = 1024;
+
+/// Nesting of containers allowed by the deserializers that use the stack for
+/// each level, whatever `max_depth` is. This much fits in the 512KiB stack of
+/// a thread on macOS.
+pub const STACK_MAX_DEPTH: u32 = 1024;
+
+/// Arguments to `loads()` other than the input.
+#[derive(Default)]
+pub struct LoadsArgs {
+    pub parse_float: Option<NonNull<pyo3_ffi::PyObject>>,
+    pub parse_int: Option<NonNull<pyo3_ffi::PyObject>>,
+    pub object_hook: Option<NonNull<pyo3_ffi::PyObject>>,
+    pub object_pairs_hook: Option<NonNull<pyo3_ffi::PyObject>>,
+    pub dict_class: Option<NonNull<pyo3_ffi::PyObject>>,
+    pub duplicate_keys: DuplicateKeys,
+    pub surrogates: Surrogates,
+    pub replace_invalid_utf8: bool,
+    pub allow_nan: bool,
+    pub strict: bool,
+    pub jsonc: bool,
+    pub max_depth: Option<u32>,
+    pub max_items: Option<usize>,
+    pub max_string_len: Option<usize>,
+    pub max_string_bytes: Option<usize>,
+    pub max_bytes: Option<usize>,
+    pub tuples: bool,
+    pub immutable: bool,
+    pub datetimes: bool,
+    pub uuids: bool,
+    pub numpy: bool,
+    pub raw_numbers: bool,
+    pub offset: Option<usize>,
+    pub length: Option<usize>,
+    pub threads: Option<usize>,
+    pub schema: Option<NonNull<pyo3_ffi::PyObject>>,
+    // set by `loads_columns()` rather than given as a keyword argument
+    pub columns: bool,
+    // array elements and object members deserialized, to check `max_items`
+    items: Cell<usize>,
+    // bytes of the strings deserialized, to check `max_string_bytes`
+    string_bytes: Cell<usize>,
+    // keys deserialized that are too long for the process's cache
+    pub long_keys: RefCell<LongKeyCache>,
+}
+
+/// What to do with a key appearing more than once in an object.
+#[derive(Clone, Copy, PartialEq)]
+pub enum DuplicateKeys {
+    /// Keep the last value.
+    Last,
+    /// Keep the first value.
+    First,
+    /// Raise `JSONDecodeError`.
+    Error,
+    /// Collect the values into a `list`.
+    List,
+}
+
+impl Default for DuplicateKeys {
+    fn default() -> Self {
+        DuplicateKeys::Last
+    }
+}
+
+/// What to do with an escaped surrogate that is not part of a pair, e.g.,
+/// `"\ud800"`.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Surrogates {
+    /// Raise `JSONDecodeError`.
+    Reject,
+    /// Replace it with U+FFFD REPLACEMENT CHARACTER.
+    Replace,
+    /// Keep it in the `str`, as the `surrogatepass` error handler does.
+    Pass,
+}
+
+impl Default for Surrogates {
+    fn default() -> Self {
+        Surrogates::Reject
+    }
+}
+
+impl Surrogates {
+    /// How the serde backend reads a lone surrogate, which with
+    /// `Surrogates::Pass` is encoded as if it were a character.
+    pub fn lone_surrogates(self) -> serde_json::de::LoneSurrogates {
+        match self {
+            Surrogates::Reject => serde_json::de::LoneSurrogates::Error,
+            Surrogates::Replace => serde_json::de::LoneSurrogates::Replace,
+            Surrogates::Pass => serde_json::de::LoneSurrogates::Encode,
+        }
+    }
+}
+
+impl LoadsArgs {
+    /// Whether numbers must be read as written, to be given to a hook, kept
+    /// as `RawNumber`, or checked for loss of precision.
+    pub fn reads_raw_numbers(&self) -> bool {
+        self.parse_float.is_some() || self.parse_int.is_some() || self.raw_numbers || self.strict
+    }
+
+    /// The arguments in `self` without the state of a call using them.
+    pub fn clone_options(&self) -> LoadsArgs {
+        LoadsArgs {
+            parse_float: self.parse_float,
+            parse_int: self.parse_int,
+            object_hook: self.object_hook,
+            object_pairs_hook: self.object_pairs_hook,
+            dict_class: self.dict_class,
+            duplicate_keys: self.duplicate_keys,
+            surrogates: self.surrogates,
+            replace_invalid_utf8: self.replace_invalid_utf8,
+            allow_nan: self.allow_nan,
+            strict: self.strict,
+            jsonc: self.jsonc,
+            max_depth: self.max_depth,
+            max_items: self.max_items,
+            max_string_len: self.max_string_len,
+            max_string_bytes: self.max_string_bytes,
+            max_bytes: self.max_bytes,
+            tuples: self.tuples,
+            immutable: self.immutable,
+            datetimes: self.datetimes,
+            uuids: self.uuids,
+            numpy: self.numpy,
+            raw_numbers: self.raw_numbers,
+            offset: self.offset,
+            length: self.length,
+            threads: self.threads,
+            schema: self.schema,
+            columns: self.columns,
+            ..Default::default()
+        }
+    }
+
+    /// Nesting of containers allowed.
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth.unwrap_or(DEFAULT_MAX_DEPTH)
+    }
+
+    /// Nesting of containers allowed by a deserializer that recurses for
+    /// each, which is at most `STACK_MAX_DEPTH` so that input cannot exhaust
+    /// the stack.
+    pub fn stack_max_depth(&self) -> u32 {
+        std::cmp::min(self.max_depth(), STACK_MAX_DEPTH)
+    }
+
+    /// Count `len` more array elements or object members, failing if the
+    /// document has more than `max_items` in total.
+    #[inline(always)]
+    pub fn add_items(&self, len: usize) -> Result<(), DeserializeError<'static>> {
+        if let Some(max) = self.max_items {
+            let items = self.items.get().saturating_add(len);
+            if unlikely!(items > max) {
+                return Err(DeserializeError::invalid(Cow::Owned(format!(
+                    "Document exceeds max_items of {}",
+                    max
+                ))));
+            }
+            self.items.set(items);
+        }
+        Ok(())
+    }
+
+    /// How many more array elements or object members `add_items()` allows,
+    /// if `max_items` is given.
+    pub fn items_left(&self) -> Option<usize> {
+        self.max_items
+            .map(|max| max.saturating_sub(self.items.get()))
+    }
+
+    /// Fail if `value` has more than `max_string_len` code points, or if it
+    /// and the strings deserialized before it have more than
+    /// `max_string_bytes` bytes in total.
+    #[inline(always)]
+    pub fn check_string_len(&self, value: &str) -> Result<(), DeserializeError<'static>> {
+        if let Some(max) = self.max_string_len {
+            // a code point is at least one byte, so most strings are not counted
+            if unlikely!(value.len() > max && value.chars().count() > max) {
+                return Err(DeserializeError::invalid(Cow::Owned(format!(
+                    "String exceeds max_string_len of {} characters",
+                    max
+                ))));
+            }
+        }
+        if let Some(max) = self.max_string_bytes {
+            let string_bytes = self.string_bytes.get().saturating_add(value.len());
+            if unlikely!(string_bytes > max) {
+                return Err(DeserializeError::invalid(Cow::Owned(format!(
+                    "Document exceeds max_string_bytes of {} bytes",
+                    max
+                ))));
+            }
+            self.string_bytes.set(string_bytes);
+        }
+        Ok(())
+    }
+
+    /// Forget the items and string bytes counted, to deserialize the document
+    /// again from its start.
+    #[cfg(feature = "structural-index")]
+    pub fn reset_counts(&self) {
+        self.items.set(0);
+        self.string_bytes.set(0);
+    }
+
+    /// The part of `buffer` given by `offset` and `length`.
+    pub fn slice<'a>(&self, buffer: &'a [u8]) -> Result<&'a [u8], DeserializeError<'static>> {
+        let start = self.offset.unwrap_or(0);
+        let end = match self.length {
+            Some(len) => start.saturating_add(len),
+            None => buffer.len(),
+        };
+        if unlikely!(start > end || end > buffer.len()) {
+            return Err(DeserializeError::invalid(Cow::Borrowed(
+                "offset and length must be within the input",
+            )));
+        }
+        Ok(&buffer[start..end])
+    }
+
+    /// Fail if `input`, which is text, has more than `max_bytes`.
+    #[inline(always)]
+    pub fn check_bytes(&self, input: &[u8]) -> Result<(), DeserializeError<'static>> {
+        ReadLimit::new(self.max_bytes).add(input)
+    }
+
+    /// Fail if the input, of `len` bytes that are not text, e.g., CBOR, has
+    /// more than `max_bytes`.
+    #[inline(always)]
+    pub fn check_binary_bytes(&self, len: usize) -> Result<(), DeserializeError<'static>> {
+        match self.max_bytes {
+            Some(max) if unlikely!(len > max) => {
+                Err(DeserializeError::at_offset(max_bytes_message(max), max))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cold]
+fn max_bytes_message(max: usize) -> Cow<'static, str> {
+    Cow::Owned(format!("Input exceeds max_bytes of {} bytes", max))
+}
+
+/// The text read of an input given in parts, e.g., read from a file, which
+/// fails at the first byte past `max_bytes`.
+pub struct ReadLimit {
+    max_bytes: Option<usize>,
+    read_len: usize,
+    // where the text read ends
+    location: Location,
+}
+
+impl ReadLimit {
+    pub fn new(max_bytes: Option<usize>) -> Self {
+        ReadLimit::starting_at(max_bytes, Location::default())
+    }
+
+    /// A limit of text that starts at `location` of a larger input.
+    pub fn starting_at(max_bytes: Option<usize>, location: Location) -> Self {
+        ReadLimit {
+            max_bytes: max_bytes,
+            read_len: 0,
+            location: location,
+        }
+    }
+
+    /// Count `part`, the text read after what was counted before it.
+    #[inline(always)]
+    pub fn add(&mut self, part: &[u8]) -> Result<(), DeserializeError<'static>> {
+        if let Some(max) = self.max_bytes {
+            if unlikely!(part.len() > max - self.read_len) {
+                let mut end = self.location;
+                end.advance(&part[..max - self.read_len]);
+                return Err(DeserializeError::at_location(max_bytes_message(max), end));
+            }
+            self.read_len += part.len();
+            self.location.advance(part);
+        }
+        Ok(())
+    }
+}
+
+/// Deserialize `ptr`. An error of `None` means an exception was raised by a
+/// callable in `args`.
 pub fn deserialize(
     ptr: *mut pyo3_ffi::PyObject,
-) -> Result<NonNull<pyo3_ffi::PyObject>, DeserializeError<'static>> {
-    let buffer = read_input_to_buf(ptr)?;
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if unlikely!(args.offset.is_some() || args.length.is_some()) {
+        return deserialize_slice(ptr, args);
+    }
+    if unlikely!(is_buffer_input(ptr)) {
+        return deserialize_buffer(ptr, args);
+    }
+    // a bytearray, including one viewed by a memoryview, can be resized by
+    // another thread while the GIL is released
+    let obj_type_ptr = ob_type!(ptr);
+    let release_gil =
+        !is_type!(obj_type_ptr, BYTEARRAY_TYPE) && !is_type!(obj_type_ptr, MEMORYVIEW_TYPE);
+    match read_input_to_buf(ptr) {
+        Ok(buffer) => deserialize_utf8(buffer, args, release_gil),
+        // a `str` with surrogates is not replaced, as it is not bytes
+        Err(Some(err))
+            if args.replace_invalid_utf8
+                && err.message == INVALID_STR
+                && !is_type!(obj_type_ptr, STR_TYPE) =>
+        {
+            deserialize_lossy(read_input_bytes(ptr)?, args)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Deserialize the part of bytes-like `ptr` given by `offset` and `length`
+/// in place. Only that part must be valid UTF-8.
+#[cold]
+#[inline(never)]
+fn deserialize_slice(
+    ptr: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if is_type!(ob_type!(ptr), STR_TYPE) {
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+            "offset and length cannot be used with str",
+        ))));
+    }
+    let input = BufferInput::new(ptr)?;
+    deserialize_bytes(args.slice(input.as_bytes())?, args, true)
+}
 
-    if unlikely!(buffer.len() == 2) {
-        if buffer == b"[]" {
-            return Ok(nonnull!(ffi!(PyList_New(0))));
-        } else if buffer == b"{}" {
+/// Deserialize an object supporting the buffer protocol in place. The buffer
+/// is released before the error, if any, is raised, so it is copied.
+#[cold]
+#[inline(never)]
+fn deserialize_buffer(
+    ptr: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let input = BufferInput::new(ptr)?;
+    if unlikely!(args.replace_invalid_utf8) && !is_valid_utf8(input.as_bytes()) {
+        return deserialize_lossy(input.as_bytes(), args);
+    }
+    deserialize_utf8(input.as_utf8()?, args, true)
+        .map_err(|err| err.map(DeserializeError::into_owned))
+}
+
+/// Deserialize `buffer`, which need not outlive the result or error. If
+/// `release_gil`, no other thread has access to `buffer`.
+pub fn deserialize_bytes(
+    buffer: &[u8],
+    args: &LoadsArgs,
+    release_gil: bool,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    args.check_bytes(buffer)?;
+    if !is_valid_utf8(buffer) {
+        if args.replace_invalid_utf8 {
+            return deserialize_lossy(buffer, args);
+        }
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(INVALID_STR))));
+    }
+    let buffer: &'static [u8] =
+        unsafe { std::slice::from_raw_parts(buffer.as_ptr(), buffer.len()) };
+    deserialize_utf8(buffer, args, release_gil).map_err(|err| err.map(DeserializeError::into_owned))
+}
+
+/// Deserialize `buffer`, which is not valid UTF-8, with each invalid sequence
+/// of bytes replaced with U+FFFD REPLACEMENT CHARACTER. `max_bytes` is of
+/// `buffer` rather than of it replaced, which can be longer.
+#[cold]
+#[inline(never)]
+fn deserialize_lossy(
+    buffer: &[u8],
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    args.check_bytes(buffer)?;
+    let replaced = String::from_utf8_lossy(buffer);
+    let replaced: &'static [u8] =
+        unsafe { std::slice::from_raw_parts(replaced.as_ptr(), replaced.len()) };
+    parse_utf8(replaced, args, true).map_err(|err| err.map(DeserializeError::into_owned))
+}
+
+/// Deserialize `buffer`. If `release_gil`, `buffer` cannot be freed by another
+/// thread, so the GIL may be released while it is parsed.
+fn deserialize_utf8(
+    buffer: &'static [u8],
+    args: &LoadsArgs,
+    release_gil: bool,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    args.check_bytes(buffer)?;
+    parse_utf8(buffer, args, release_gil)
+}
+
+/// Deserialize `buffer`, which is valid UTF-8 and within `max_bytes`.
+fn parse_utf8(
+    buffer: &'static [u8],
+    args: &LoadsArgs,
+    release_gil: bool,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if unlikely!(buffer.len() == 2) && args.schema.is_none() && !args.columns {
+        if buffer == b"[]" && args.max_depth() > 0 {
+            return Ok(nonnull!(new_array(0, args)));
+        } else if buffer == b"{}"
+            && args.max_depth() > 0
+            && !args.immutable
+            && args.object_hook.is_none()
+            && args.object_pairs_hook.is_none()
+            && args.dict_class.is_none()
+        {
             return Ok(nonnull!(ffi!(PyDict_New())));
         } else if buffer == b"\"\"" {
             ffi!(Py_INCREF(EMPTY_UNICODE));
@@ -23,13 +434,24 @@ pub fn deserialize(
 
     let buffer_str = unsafe { std::str::from_utf8_unchecked(buffer) };
 
+    #[cfg(feature = "structural-index")]
+    {
+        use crate::deserialize::index::{can_index, deserialize_indexed};
+        if unlikely!(can_index(buffer.len(), args)) {
+            if let Some(ret) = deserialize_indexed(buffer_str, args, release_gil) {
+                return ret;
+            }
+        }
+    }
+
     #[cfg(feature = "yyjson")]
     {
-        crate::deserialize::yyjson::deserialize_yyjson(buffer_str)
+        crate::deserialize::yyjson::deserialize_yyjson(buffer_str, args, release_gil)
     }
 
     #[cfg(not(feature = "yyjson"))]
     {
-        crate::deserialize::json::deserialize_json(buffer_str)
+        let _ = release_gil;
+        crate::deserialize::json::deserialize_json(buffer_str, args)
     }
 }
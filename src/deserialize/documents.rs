@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::deserializer::deserialize_bytes;
+use crate::deserialize::iter::{new_iterator, InputReader, Scanner};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use std::ptr::NonNull;
+
+/// Create an iterator over the top-level documents of `ptr`, which follow
+/// one another with or without whitespace between them.
+pub fn iter_documents(
+    ptr: *mut pyo3_ffi::PyObject,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let scanner = DocumentScanner {
+        reader: InputReader::new(ptr)?,
+        document: Vec::new(),
+    };
+    new_iterator(unsafe { DOCUMENTS_ITERATOR_TYPE }, Box::new(scanner))
+}
+
+/// Finds the bytes of each of a sequence of documents so they can be
+/// deserialized one at a time.
+struct DocumentScanner {
+    reader: InputReader,
+    // the bytes of the current document, reused across documents
+    document: Vec<u8>,
+}
+
+impl DocumentScanner {
+    /// Read the bytes of the document starting at the next byte, which is not
+    /// whitespace, into `self.document`.
+    fn read_document(&mut self) -> Result<(), Option<DeserializeError<'static>>> {
+        self.document.clear();
+        let mut boundary = DocumentBoundary::default();
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                return Ok(());
+            }
+            let end = boundary.scan(buf);
+            let len = end.unwrap_or(buf.len());
+            self.document.extend_from_slice(&buf[..len]);
+            self.reader.consume(len);
+            if end.is_some() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Finds where a document ends in input read in parts. An array, object, or
+/// string ends where it is closed, and anything else, e.g., a number, before
+/// whitespace or the start of another document. Strings are skipped so that
+/// brackets in them do not end the document. Anything else invalid is left
+/// to be reported when the document is deserialized.
+#[derive(Default)]
+pub struct DocumentBoundary {
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    // whether the document is other than an array, object, or string
+    scalar: bool,
+}
+
+impl DocumentBoundary {
+    /// Scan `buf`, the next part of a document starting with a byte that is
+    /// not whitespace, and return the index at which the document ends, if
+    /// it does in `buf`. Once it has ended, `self` must not be used again.
+    pub fn scan(&mut self, buf: &[u8]) -> Option<usize> {
+        for (idx, &c) in buf.iter().enumerate() {
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if c == b'\\' {
+                    self.escaped = true;
+                } else if c == b'"' {
+                    self.in_string = false;
+                    if self.depth == 0 {
+                        return Some(idx + 1);
+                    }
+                }
+                continue;
+            }
+            match c {
+                b'"' | b'[' | b'{' | b']' | b'}' | b' ' | b'\t' | b'\n' | b'\r' if self.scalar => {
+                    return Some(idx);
+                }
+                b'"' => self.in_string = true,
+                b'[' | b'{' => self.depth += 1,
+                b']' | b'}' => {
+                    // a bracket not closing one is deserialized alone
+                    self.depth = self.depth.saturating_sub(1);
+                    if self.depth == 0 {
+                        return Some(idx + 1);
+                    }
+                }
+                _ if self.depth == 0 => self.scalar = true,
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+impl Scanner for DocumentScanner {
+    fn next(
+        &mut self,
+    ) -> Result<Option<NonNull<pyo3_ffi::PyObject>>, Option<DeserializeError<'static>>> {
+        if self.reader.skip_whitespace()?.is_none() {
+            return Ok(None);
+        }
+        let at = self.reader.location();
+        self.read_document()?;
+        // the iterator can be advanced by another thread if the GIL is released
+        deserialize_bytes(&self.document, &LoadsArgs::default(), false)
+            .map(Some)
+            .map_err(|err| err.map(|err| err.within(at)))
+    }
+}
@@ -2,14 +2,75 @@
 
 use std::borrow::Cow;
 
+// characters of the document on either side of the error in its snippet
+const SNIPPET_CONTEXT: usize = 32;
+
+// Messages of errors in the syntax of the input. These are yyjson's, which
+// `loads()` reports, so that every parser words an error the same way.
+pub const UNEXPECTED_CHARACTER: &str = "unexpected character";
+pub const UNEXPECTED_END: &str = "unexpected end of data";
+pub const UNEXPECTED_CONTENT: &str = "unexpected content after document";
+pub const INVALID_LITERAL: &str = "invalid literal";
+pub const INVALID_NUMBER: &str = "invalid number";
+pub const UNCLOSED_STRING: &str = "unclosed string";
+pub const CONTROL_CHARACTER: &str = "unexpected control character in string";
+pub const INVALID_ESCAPE: &str = "invalid escaped character in string";
+pub const INVALID_UNICODE_ESCAPE: &str = "invalid escaped unicode in string";
+pub const INVALID_UTF8: &str = "invalid utf-8 encoding in string";
+pub const RECURSION_LIMIT: &str = "recursion limit exceeded";
+pub const BYTE_ORDER_MARK: &str = "byte order mark (BOM) is not supported";
+pub const UNCLOSED_COMMENT: &str = "unclosed multiline comment";
+pub const NUMBER_INFINITY: &str = "number is infinity when parsed as double";
+pub const INVALID_HIGH_SURROGATE: &str = "invalid high surrogate in string";
+pub const INVALID_LOW_SURROGATE: &str = "invalid low surrogate in string";
+pub const NO_LOW_SURROGATE: &str = "no matched low surrogate in string";
+
+/// The position in an input of the next byte read, in characters as `pos`,
+/// `lineno`, and `colno` count them, for input that is read in parts.
+#[derive(Clone, Copy)]
+pub struct Location {
+    // characters before it
+    pub chars: usize,
+    // its line, starting at 1
+    pub line: usize,
+    // characters before it on its line
+    pub column: usize,
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Location {
+            chars: 0,
+            line: 1,
+            column: 0,
+        }
+    }
+}
+
+impl Location {
+    /// Move past `bytes`, which may start or end within a character.
+    pub fn advance(&mut self, bytes: &[u8]) {
+        self.chars += bytecount::num_chars(bytes);
+        match bytes.iter().rposition(|&c| c == b'\n') {
+            Some(idx) => {
+                self.line += bytecount::count(bytes, b'\n');
+                self.column = bytecount::num_chars(&bytes[idx + 1..]);
+            }
+            None => self.column += bytecount::num_chars(bytes),
+        }
+    }
+}
+
 pub struct DeserializeError<'a> {
     pub message: Cow<'a, str>,
-    #[cfg(not(feature = "yyjson"))]
-    pub line: usize, // start at 1
-    #[cfg(not(feature = "yyjson"))]
+    pub line: usize,   // start at 1
     pub column: usize, // start at 1
-    pub data: Option<&'a str>,
+    pub data: Option<Cow<'a, str>>,
     pub pos: i64,
+    // where the document is not kept, e.g., one read in chunks, the index of
+    // the character at which `data`, the text around the error if any,
+    // starts, or, for input that is not text, of the byte of the error
+    pub start: Option<i64>,
 }
 
 impl<'a> DeserializeError<'a> {
@@ -17,12 +78,120 @@ impl<'a> DeserializeError<'a> {
     pub fn invalid(message: Cow<'a, str>) -> Self {
         DeserializeError {
             message: message,
-            #[cfg(not(feature = "yyjson"))]
             line: 0,
-            #[cfg(not(feature = "yyjson"))]
             column: 0,
             data: None,
             pos: 0,
+            start: None,
+        }
+    }
+
+    /// An error at byte `pos` of input that is not text, e.g., MessagePack.
+    #[cold]
+    pub fn at_offset(message: Cow<'a, str>, pos: usize) -> Self {
+        DeserializeError {
+            message: message,
+            line: 0,
+            column: 0,
+            data: None,
+            pos: 0,
+            start: Some(pos as i64),
+        }
+    }
+
+    /// An error at `line` and `column` of a document that is not kept, e.g.,
+    /// one parsed as it is read, and at byte `pos` of `part`, the text of the
+    /// document around it, which starts at its character `start`.
+    #[cold]
+    pub fn in_part(
+        message: Cow<'a, str>,
+        line: usize,
+        column: usize,
+        part: Cow<'a, str>,
+        pos: usize,
+        start: usize,
+    ) -> Self {
+        DeserializeError {
+            message: message,
+            line: line,
+            column: column,
+            data: Some(part),
+            pos: pos as i64,
+            start: Some(start as i64),
+        }
+    }
+
+    /// An error at `at` in input that is not kept.
+    #[cold]
+    pub fn at_location(message: Cow<'a, str>, at: Location) -> Self {
+        DeserializeError::in_part(
+            message,
+            at.line,
+            at.column + 1,
+            Cow::Borrowed(""),
+            0,
+            at.chars,
+        )
+    }
+
+    /// Locate the error in a document, e.g., an element of an array, that
+    /// starts at `at` in a larger input, so that its position is in that
+    /// input rather than the document. An error without a position in the
+    /// document is at its start.
+    #[cold]
+    pub fn within(self, at: Location) -> DeserializeError<'static> {
+        let pos = self.pos().max(0) as usize;
+        let message = Cow::Owned(self.message.into_owned());
+        let data = match self.data {
+            Some(data) if self.start.is_none() => data.into_owned(),
+            // located in the document, which is not kept, e.g., past
+            // `max_bytes`
+            _ if self.start.is_some() && self.line > 0 => {
+                let end = Location {
+                    chars: at.chars + pos,
+                    line: at.line + self.line - 1,
+                    column: if self.line == 1 {
+                        at.column + self.column - 1
+                    } else {
+                        self.column - 1
+                    },
+                };
+                return DeserializeError::at_location(message, end);
+            }
+            _ => return DeserializeError::at_location(message, at),
+        };
+        let idx = data
+            .char_indices()
+            .nth(pos)
+            .map_or(data.len(), |(idx, _)| idx);
+        let mut end = at;
+        end.advance(&data.as_bytes()[..idx]);
+        DeserializeError::in_part(
+            message,
+            end.line,
+            end.column + 1,
+            Cow::Owned(data),
+            idx,
+            at.chars,
+        )
+    }
+
+    /// An error at byte `pos` of `data`, e.g., one found scanning it rather
+    /// than by a parser.
+    #[cold]
+    pub fn at_byte(message: Cow<'a, str>, pos: usize, data: &'a str) -> Self {
+        let before = &data.as_bytes()[..pos.min(data.len())];
+        let line_start = before
+            .iter()
+            .rposition(|&c| c == b'\n')
+            .map_or(0, |idx| idx + 1);
+        DeserializeError {
+            message: message,
+            line: bytecount::count(before, b'\n') + 1,
+            column: before.len() - line_start + 1,
+            data: Some(Cow::Borrowed(data)),
+            pos: before.len() as i64,
+            start: None,
         }
     }
 
@@ -33,8 +202,10 @@ impl<'a> DeserializeError<'a> {
             message,
             line,
             column,
-            data: Some(data),
-            pos: 0,
+            data: Some(Cow::Borrowed(data)),
+            // not known, so derived from `line` and `column`
+            pos: -1,
+            start: None,
         }
     }
 
@@ -43,31 +214,65 @@ impl<'a> DeserializeError<'a> {
     pub fn from_yyjson(message: Cow<'a, str>, pos: i64, data: &'a str) -> Self {
         DeserializeError {
             message: message,
-            data: Some(data),
+            line: 0,
+            column: 0,
+            data: Some(Cow::Borrowed(data)),
             pos: pos,
+            start: None,
+        }
+    }
+
+    /// Copy the message and the document so that the error outlives the
+    /// input, e.g., a buffer released before the error is raised.
+    #[cold]
+    pub fn into_owned(self) -> DeserializeError<'static> {
+        DeserializeError {
+            message: Cow::Owned(self.message.into_owned()),
+            line: self.line,
+            column: self.column,
+            data: self.data.map(|data| Cow::Owned(data.into_owned())),
+            pos: self.pos,
+            start: self.start,
         }
     }
 
     /// Return position of the error in the deserialized data
     #[cold]
-    #[cfg(feature = "yyjson")]
     pub fn pos(&self) -> i64 {
-        match self.data {
+        match self.start {
+            Some(start) => {
+                start
+                    + self.data.as_ref().map_or(0, |part| {
+                        bytecount::num_chars(&part.as_bytes()[..self.pos as usize]) as i64
+                    })
+            }
+            None => self.doc_pos(),
+        }
+    }
+
+    #[cold]
+    #[cfg(feature = "yyjson")]
+    fn doc_pos(&self) -> i64 {
+        match &self.data {
             Some(as_str) => bytecount::num_chars(&as_str.as_bytes()[0..self.pos as usize]) as i64,
             None => 0,
         }
     }
 
-    /// Return position of the error in the deserialized data
     #[cold]
     #[cfg(not(feature = "yyjson"))]
     #[cfg_attr(feature = "unstable-simd", optimize(size))]
-    pub fn pos(&self) -> i64 {
+    fn doc_pos(&self) -> i64 {
         if self.line == 0 || self.data.is_none() {
             return 1;
         }
+        // an error found scanning the document, e.g., by `at_byte()`
+        if self.pos >= 0 {
+            let data = self.data.as_ref().unwrap().as_bytes();
+            return bytecount::num_chars(&data[..self.pos as usize]) as i64;
+        }
 
-        let val = self.data.unwrap()
+        let val = self.data.as_ref().unwrap()
             .split('\n')
             // take only the relevant lines
             .take(self.line)
@@ -101,4 +306,32 @@ impl<'a> DeserializeError<'a> {
             + (self.line - 1);
         val as i64
     }
+
+    /// Return the text of the document around the character at `pos`, at
+    /// most `SNIPPET_CONTEXT` characters on either side of it within its line.
+    #[cold]
+    pub fn snippet(&self, pos: i64) -> &str {
+        let data: &str = match &self.data {
+            Some(data) => data,
+            None => return "",
+        };
+        let idx = data
+            .char_indices()
+            .nth((pos - self.start.unwrap_or(0)).max(0) as usize)
+            .map_or(data.len(), |(idx, _)| idx);
+        let start = data[..idx]
+            .char_indices()
+            .rev()
+            .take_while(|&(_, c)| c != '\n')
+            .take(SNIPPET_CONTEXT)
+            .last()
+            .map_or(idx, |(idx, _)| idx);
+        let end = data[idx..]
+            .char_indices()
+            .take_while(|&(_, c)| c != '\n' && c != '\r')
+            .take(SNIPPET_CONTEXT + 1)
+            .last()
+            .map_or(idx, |(offset, c)| idx + offset + c.len_utf8());
+        &data[start..end]
+    }
 }
@@ -0,0 +1,428 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::deserializer::{deserialize, deserialize_bytes, ReadLimit};
+use crate::deserialize::json::deserialize_json_reader;
+use crate::deserialize::schema::check_schema;
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::mem::ManuallyDrop;
+use std::os::raw::c_char;
+use std::ptr::NonNull;
+
+// Bytes read by the first call to `readinto()`. Once the buffer is full, it is
+// doubled, so a large file is read in few calls and copied at most once.
+pub const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Deserialize the contents of a file object or file descriptor from its
+/// current position to its end. An error of `None` means an exception was
+/// raised reading it or by a callable in `args`.
+pub fn deserialize_file(
+    ptr: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    // reading stops past `max_bytes`, which `deserialize_bytes()` then checks
+    let limit = args.max_bytes.unwrap_or(usize::MAX);
+    if unsafe { ob_type!(ptr) == INT_TYPE } {
+        let data = read_fd(ptr, limit)?;
+        return deserialize_bytes(&data, args, true);
+    }
+    let readinto = ffi!(PyObject_GetAttr(ptr, READINTO_METHOD_STR));
+    if !readinto.is_null() {
+        let data = read_into(readinto, limit);
+        ffi!(Py_DECREF(readinto));
+        return deserialize_bytes(&data?, args, true);
+    }
+    // e.g., a text file, whose contents are a `str`
+    ffi!(PyErr_Clear());
+    let contents = ffi!(PyObject_CallMethodObjArgs(
+        ptr,
+        READ_METHOD_STR,
+        std::ptr::null_mut::<pyo3_ffi::PyObject>()
+    ));
+    if contents.is_null() {
+        return Err(None);
+    }
+    let res = deserialize(contents, args).map_err(|err| err.map(DeserializeError::into_owned));
+    ffi!(Py_DECREF(contents));
+    res
+}
+
+/// Deserialize a file object or file descriptor while reading it, at most
+/// `chunk_size` bytes at a time, so that its contents are never held in
+/// memory in full. An error of `None` means an exception was raised reading it
+/// or by a callable in `args`.
+pub fn deserialize_file_chunked(
+    ptr: *mut pyo3_ffi::PyObject,
+    chunk_size: usize,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let mut reader = ChunkedReader::new(ptr)?;
+    reader.limit = ReadLimit::new(args.max_bytes);
+    match deserialize_json_reader(BufReader::with_capacity(chunk_size, &mut reader), args) {
+        Ok(obj) => check_schema(obj, args, None),
+        Err(err) => match reader.take_error() {
+            Some(read_err) if err.is_io => Err(read_err),
+            _ if !ffi!(PyErr_Occurred()).is_null() => Err(None),
+            _ => Err(Some(err.error)),
+        },
+    }
+}
+
+enum Source {
+    Fd(ManuallyDrop<File>),
+    ReadInto(*mut pyo3_ffi::PyObject),
+    Read(*mut pyo3_ffi::PyObject),
+}
+
+/// Reader of a file object or file descriptor for the parser. An error
+/// reading it is kept to be raised in place of the parser's error.
+pub struct ChunkedReader {
+    source: Source,
+    // contents returned by `read()` and not yet given to the parser
+    pending: Vec<u8>,
+    offset: usize,
+    error: Option<Option<DeserializeError<'static>>>,
+    // bytes given to the parser, which fails if there are more than
+    // `max_bytes`
+    pub limit: ReadLimit,
+}
+
+impl ChunkedReader {
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Result<Self, Option<DeserializeError<'static>>> {
+        let source = if unsafe { ob_type!(ptr) == INT_TYPE } {
+            Source::Fd(borrow_fd(ptr)?)
+        } else {
+            let readinto = ffi!(PyObject_GetAttr(ptr, READINTO_METHOD_STR));
+            if !readinto.is_null() {
+                Source::ReadInto(readinto)
+            } else {
+                ffi!(PyErr_Clear());
+                let read = ffi!(PyObject_GetAttr(ptr, READ_METHOD_STR));
+                if read.is_null() {
+                    return Err(None);
+                }
+                Source::Read(read)
+            }
+        };
+        Ok(ChunkedReader {
+            source: source,
+            pending: Vec::new(),
+            offset: 0,
+            error: None,
+            limit: ReadLimit::new(None),
+        })
+    }
+
+    /// Take the error that made a read fail.
+    pub fn take_error(&mut self) -> Option<Option<DeserializeError<'static>>> {
+        self.error.take()
+    }
+
+    fn read_fd(
+        file: &mut File,
+        buf: &mut [u8],
+    ) -> Result<usize, Option<DeserializeError<'static>>> {
+        loop {
+            let tstate = ffi!(PyEval_SaveThread());
+            let res = file.read(buf);
+            ffi!(PyEval_RestoreThread(tstate));
+            match res {
+                Ok(read) => return Ok(read),
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {
+                    if ffi!(PyErr_CheckSignals()) == -1 {
+                        return Err(None);
+                    }
+                }
+                Err(err) => {
+                    raise_os_error(err);
+                    return Err(None);
+                }
+            }
+        }
+    }
+
+    /// Copy the contents returned by `read()`, which for a text file are
+    /// characters rather than bytes, calling it again when they are used up.
+    fn read_contents(
+        &mut self,
+        read: *mut pyo3_ffi::PyObject,
+        buf: &mut [u8],
+    ) -> Result<usize, Option<DeserializeError<'static>>> {
+        if self.offset == self.pending.len() {
+            self.pending.clear();
+            self.offset = 0;
+            let size = ffi!(PyLong_FromSsize_t(buf.len() as isize));
+            let contents = ffi!(PyObject_CallFunctionObjArgs(
+                read,
+                size,
+                std::ptr::null_mut::<pyo3_ffi::PyObject>()
+            ));
+            ffi!(Py_DECREF(size));
+            if contents.is_null() {
+                return Err(None);
+            }
+            let res = self.append_contents(contents);
+            ffi!(Py_DECREF(contents));
+            res?;
+        }
+        let len = std::cmp::min(buf.len(), self.pending.len() - self.offset);
+        buf[..len].copy_from_slice(&self.pending[self.offset..self.offset + len]);
+        self.offset += len;
+        Ok(len)
+    }
+
+    fn append_contents(
+        &mut self,
+        contents: *mut pyo3_ffi::PyObject,
+    ) -> Result<(), Option<DeserializeError<'static>>> {
+        let obj_type_ptr = ob_type!(contents);
+        if is_type!(obj_type_ptr, STR_TYPE) {
+            match crate::unicode::unicode_to_str(contents) {
+                Some(as_str) => self.pending.extend_from_slice(as_str.as_bytes()),
+                None => {
+                    return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+                        crate::error::INVALID_STR,
+                    ))))
+                }
+            }
+        } else if is_type!(obj_type_ptr, BYTES_TYPE) {
+            self.pending.extend_from_slice(unsafe {
+                std::slice::from_raw_parts(
+                    pyo3_ffi::PyBytes_AsString(contents) as *const u8,
+                    pyo3_ffi::PyBytes_Size(contents) as usize,
+                )
+            });
+        } else if contents == unsafe { NONE } {
+            return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+                "Input file must be blocking",
+            ))));
+        } else {
+            return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+                "Input file read() must return bytes or str",
+            ))));
+        }
+        Ok(())
+    }
+}
+
+impl Read for ChunkedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let res = match &mut self.source {
+            Source::Fd(file) => ChunkedReader::read_fd(file, buf),
+            Source::ReadInto(readinto) => call_readinto(*readinto, buf.as_mut_ptr(), buf.len()),
+            Source::Read(read) => {
+                let read = *read;
+                self.read_contents(read, buf)
+            }
+        };
+        let res = res.and_then(|read| {
+            self.limit.add(&buf[..read])?;
+            Ok(read)
+        });
+        res.map_err(|err| {
+            self.error = Some(err);
+            std::io::Error::new(std::io::ErrorKind::Other, "Input file could not be read")
+        })
+    }
+}
+
+impl Drop for ChunkedReader {
+    fn drop(&mut self) {
+        match self.source {
+            Source::Fd(_) => {}
+            Source::ReadInto(method) | Source::Read(method) => ffi!(Py_DECREF(method)),
+        }
+    }
+}
+
+/// Read to the end of a binary file using its `readinto` method, or until
+/// more than `limit` bytes are read.
+fn read_into(
+    readinto: *mut pyo3_ffi::PyObject,
+    limit: usize,
+) -> Result<Vec<u8>, Option<DeserializeError<'static>>> {
+    let mut buf: Vec<u8> = Vec::new();
+    while buf.len() <= limit {
+        if buf.len() == buf.capacity() {
+            buf.reserve(std::cmp::max(READ_CHUNK_SIZE, buf.len()));
+        }
+        let spare = buf.capacity() - buf.len();
+        let read = call_readinto(readinto, unsafe { buf.as_mut_ptr().add(buf.len()) }, spare)?;
+        if read == 0 {
+            return Ok(buf);
+        }
+        unsafe { buf.set_len(buf.len() + read) };
+    }
+    Ok(buf)
+}
+
+/// Call `readinto()` with a view of the `len` bytes at `ptr` and return the
+/// number of bytes it read.
+fn call_readinto(
+    readinto: *mut pyo3_ffi::PyObject,
+    ptr: *mut u8,
+    len: usize,
+) -> Result<usize, Option<DeserializeError<'static>>> {
+    let view = ffi!(PyMemoryView_FromMemory(
+        ptr as *mut c_char,
+        len as isize,
+        pyo3_ffi::PyBUF_WRITE
+    ));
+    if view.is_null() {
+        return Err(None);
+    }
+    let res = ffi!(PyObject_CallFunctionObjArgs(
+        readinto,
+        view,
+        std::ptr::null_mut::<pyo3_ffi::PyObject>()
+    ));
+    if res.is_null() {
+        release_view(view);
+        return Err(None);
+    }
+    if !release_view(view) {
+        ffi!(Py_DECREF(res));
+        return Err(None);
+    }
+    if unlikely!(res == unsafe { NONE }) {
+        ffi!(Py_DECREF(res));
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+            "Input file must be blocking",
+        ))));
+    }
+    let read = ffi!(PyLong_AsSsize_t(res));
+    ffi!(Py_DECREF(res));
+    if read == -1 && !ffi!(PyErr_Occurred()).is_null() {
+        return Err(None);
+    }
+    if unlikely!(read < 0 || read as usize > len) {
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+            "Input file readinto() returned an invalid length",
+        ))));
+    }
+    Ok(read as usize)
+}
+
+/// Release and drop a view of the buffer given to `readinto()` so that the
+/// memory is not used through a reference kept to it, preserving an
+/// exception raised by `readinto()`. Return false if releasing it raised.
+fn release_view(view: *mut pyo3_ffi::PyObject) -> bool {
+    let mut ptype = std::ptr::null_mut();
+    let mut pvalue = std::ptr::null_mut();
+    let mut ptraceback = std::ptr::null_mut();
+    ffi!(PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback));
+    let released = ffi!(PyObject_CallMethodObjArgs(
+        view,
+        RELEASE_METHOD_STR,
+        std::ptr::null_mut::<pyo3_ffi::PyObject>()
+    ));
+    ffi!(Py_DECREF(view));
+    if !ptype.is_null() {
+        ffi!(Py_XDECREF(released));
+        ffi!(PyErr_Restore(ptype, pvalue, ptraceback));
+        return false;
+    }
+    if released.is_null() {
+        return false;
+    }
+    ffi!(Py_DECREF(released));
+    true
+}
+
+/// Read to the end of a file descriptor without holding the GIL, or until
+/// more than `limit` bytes are read.
+fn read_fd(
+    ptr: *mut pyo3_ffi::PyObject,
+    limit: usize,
+) -> Result<Vec<u8>, Option<DeserializeError<'static>>> {
+    let file = borrow_fd(ptr)?;
+    let mut buf: Vec<u8> = Vec::new();
+    let tstate = ffi!(PyEval_SaveThread());
+    let res = (&*file)
+        .take((limit as u64).saturating_add(1))
+        .read_to_end(&mut buf);
+    ffi!(PyEval_RestoreThread(tstate));
+    match res {
+        Ok(_) => Ok(buf),
+        Err(err) => {
+            raise_os_error(err);
+            Err(None)
+        }
+    }
+}
+
+/// Return a `File` for a file descriptor owned by the caller, which must not
+/// be dropped.
+#[cfg(unix)]
+fn borrow_fd(
+    ptr: *mut pyo3_ffi::PyObject,
+) -> Result<ManuallyDrop<File>, Option<DeserializeError<'static>>> {
+    use std::os::unix::io::FromRawFd;
+
+    let fd = ffi!(PyLong_AsLong(ptr));
+    if fd == -1 && !ffi!(PyErr_Occurred()).is_null() {
+        return Err(None);
+    }
+    if fd < 0 || fd > std::os::raw::c_int::MAX as std::os::raw::c_long {
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+            "Input file descriptor must be non-negative",
+        ))));
+    }
+    Ok(ManuallyDrop::new(unsafe {
+        File::from_raw_fd(fd as std::os::raw::c_int)
+    }))
+}
+
+#[cfg(not(unix))]
+fn borrow_fd(
+    _ptr: *mut pyo3_ffi::PyObject,
+) -> Result<ManuallyDrop<File>, Option<DeserializeError<'static>>> {
+    Err(Some(DeserializeError::invalid(Cow::Borrowed(
+        "Input file descriptors are not supported on this platform",
+    ))))
+}
+
+/// Raise `OSError`, or the subclass for its errno, for `err`.
+#[cold]
+pub fn raise_os_error(err: std::io::Error) {
+    let exc = new_os_error(err);
+    if !exc.is_null() {
+        unsafe {
+            pyo3_ffi::PyErr_SetObject(ob_type!(exc) as *mut pyo3_ffi::PyObject, exc);
+            pyo3_ffi::Py_DECREF(exc);
+        }
+    }
+}
+
+/// Make the `OSError`, or the subclass for its errno, for `err`, or return
+/// null with an exception set.
+#[cold]
+pub fn new_os_error(err: std::io::Error) -> *mut pyo3_ffi::PyObject {
+    let errno = err.raw_os_error().unwrap_or(0);
+    // `OSError` gives the errno itself
+    let suffix = format!(" (os error {})", errno);
+    let msg = err.to_string();
+    let msg = msg.strip_suffix(&suffix).unwrap_or(&msg);
+    unsafe {
+        let args = pyo3_ffi::PyTuple_New(2);
+        pyo3_ffi::PyTuple_SET_ITEM(
+            args,
+            0,
+            pyo3_ffi::PyLong_FromLong(errno as std::os::raw::c_long),
+        );
+        pyo3_ffi::PyTuple_SET_ITEM(
+            args,
+            1,
+            pyo3_ffi::PyUnicode_FromStringAndSize(
+                msg.as_ptr() as *const c_char,
+                msg.len() as isize,
+            ),
+        );
+        let exc = pyo3_ffi::PyObject_Call(pyo3_ffi::PyExc_OSError, args, std::ptr::null_mut());
+        pyo3_ffi::Py_DECREF(args);
+        exc
+    }
+}
@@ -0,0 +1,540 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::pyobject::*;
+use crate::deserialize::tokenize::is_number;
+use crate::deserialize::{DeserializeError, LoadsArgs, Surrogates};
+use std::borrow::Cow;
+use std::ptr::{null_mut, NonNull};
+
+/// Documents at least this long are deserialized using a structural index,
+/// as for shorter ones building it costs more than it saves.
+pub const INDEX_MIN_LEN: usize = 1024 * 1024;
+
+/// Whether a document of `len` bytes is deserialized using a structural index
+/// with `args`. Options that change the grammar or how numbers or pairs are
+/// read are left to the parser.
+pub fn can_index(len: usize, args: &LoadsArgs) -> bool {
+    len >= INDEX_MIN_LEN
+        && len <= u32::MAX as usize
+        && !args.reads_raw_numbers()
+        && !args.allow_nan
+        && !args.jsonc
+        && args.surrogates == Surrogates::Reject
+        && args.object_pairs_hook.is_none()
+        && !args.numpy
+        && args.schema.is_none()
+        && !args.columns
+        && args.threads.is_none()
+}
+
+/// Deserialize `data` in two stages: first find the offset of each
+/// structural character, quote, and literal in it, then make objects of them
+/// in order. `None` means `data` is invalid or exceeds a limit, so it is to
+/// be deserialized as usual for the error to be reported as it would be
+/// otherwise.
+#[inline(never)]
+pub fn deserialize_indexed(
+    data: &'static str,
+    args: &LoadsArgs,
+    release_gil: bool,
+) -> Option<Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>>> {
+    let index = if release_gil {
+        let tstate = ffi!(PyEval_SaveThread());
+        let index = structural_index(data.as_bytes());
+        ffi!(PyEval_RestoreThread(tstate));
+        index
+    } else {
+        structural_index(data.as_bytes())
+    }?;
+    let mut builder = Builder {
+        buf: data.as_bytes(),
+        index: &index,
+        next: 0,
+        args: args,
+        values: Vec::new(),
+        stack: Vec::new(),
+        scratch: String::new(),
+    };
+    match builder.build() {
+        Ok(obj) => Some(Ok(obj)),
+        Err(None) => Some(Err(None)),
+        Err(Some(_)) => {
+            args.reset_counts();
+            None
+        }
+    }
+}
+
+/// Bitmasks of the bytes of a block of 64 that are of each class.
+#[derive(Default)]
+struct Block {
+    backslash: u64,
+    quote: u64,
+    structural: u64,
+    whitespace: u64,
+    control: u64,
+}
+
+#[cfg(target_arch = "x86_64")]
+fn classify(chunk: &[u8; 64]) -> Block {
+    use std::arch::x86_64::*;
+    let mut block = Block::default();
+    // SSE2 is part of x86_64
+    unsafe {
+        let low = _mm_set1_epi8(0x1f);
+        for part in 0..4 {
+            let val = _mm_loadu_si128(chunk.as_ptr().add(part * 16) as *const __m128i);
+            let eq = |c: u8| _mm_cmpeq_epi8(val, _mm_set1_epi8(c as i8));
+            let mask = |m: __m128i| ((_mm_movemask_epi8(m) as u32) as u64) << (part * 16);
+            block.backslash |= mask(eq(b'\\'));
+            block.quote |= mask(eq(b'"'));
+            block.structural |= mask(_mm_or_si128(
+                _mm_or_si128(
+                    _mm_or_si128(eq(b'{'), eq(b'}')),
+                    _mm_or_si128(eq(b'['), eq(b']')),
+                ),
+                _mm_or_si128(eq(b':'), eq(b',')),
+            ));
+            block.whitespace |= mask(_mm_or_si128(
+                _mm_or_si128(eq(b' '), eq(b'\t')),
+                _mm_or_si128(eq(b'\n'), eq(b'\r')),
+            ));
+            block.control |= mask(_mm_cmpeq_epi8(_mm_max_epu8(val, low), low));
+        }
+    }
+    block
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn classify(chunk: &[u8; 64]) -> Block {
+    let mut block = Block::default();
+    for (idx, &c) in chunk.iter().enumerate() {
+        let bit = 1u64 << idx;
+        match c {
+            b'\\' => block.backslash |= bit,
+            b'"' => block.quote |= bit,
+            b'{' | b'}' | b'[' | b']' | b':' | b',' => block.structural |= bit,
+            b' ' => block.whitespace |= bit,
+            b'\t' | b'\n' | b'\r' => {
+                block.whitespace |= bit;
+                block.control |= bit;
+            }
+            0..=0x1f => block.control |= bit,
+            _ => {}
+        }
+    }
+    block
+}
+
+/// The bytes of a block escaped by a backslash, given the backslashes in it.
+/// `escape` is whether the block before ended with an unescaped backslash,
+/// and is set to whether this block does.
+#[inline(always)]
+fn escaped(backslash: u64, escape: &mut bool) -> u64 {
+    let mut escaped = *escape as u64;
+    *escape = false;
+    let mut bits = backslash;
+    while bits != 0 {
+        let idx = bits.trailing_zeros();
+        bits &= bits - 1;
+        if escaped & (1 << idx) != 0 {
+            continue;
+        }
+        if idx == 63 {
+            *escape = true;
+        } else {
+            escaped |= 1 << (idx + 1);
+        }
+    }
+    escaped
+}
+
+/// Each bit set if an odd number of the bits up to and including it are.
+#[inline(always)]
+fn prefix_xor(mut bits: u64) -> u64 {
+    bits ^= bits << 1;
+    bits ^= bits << 2;
+    bits ^= bits << 4;
+    bits ^= bits << 8;
+    bits ^= bits << 16;
+    bits ^= bits << 32;
+    bits
+}
+
+/// The offsets of `buf` of each structural character and quote not in a
+/// string and of the first byte of each literal, in order. `None` means a
+/// string is not closed or has a control character.
+fn structural_index(buf: &[u8]) -> Option<Vec<u32>> {
+    let mut index: Vec<u32> = Vec::with_capacity(buf.len() / 8);
+    // the block before ended with an unescaped backslash
+    let mut escape = false;
+    // all bits set if the block before ended in a string
+    let mut in_string: u64 = 0;
+    // the last byte of the block before is part of a literal
+    let mut in_literal: u64 = 0;
+    let mut last = [b' '; 64];
+    for (num, part) in buf.chunks(64).enumerate() {
+        let block = if part.len() == 64 {
+            classify(unsafe { &*(part.as_ptr() as *const [u8; 64]) })
+        } else {
+            last[..part.len()].copy_from_slice(part);
+            classify(&last)
+        };
+        let quote = block.quote & !escaped(block.backslash, &mut escape);
+        // the opening quote of a string and its contents but not its closing
+        // quote
+        let string = prefix_xor(quote) ^ in_string;
+        in_string = ((string as i64) >> 63) as u64;
+        if unlikely!(block.control & string != 0) {
+            return None;
+        }
+        let literal = !(block.structural | block.whitespace | quote | string);
+        let starts = literal & !((literal << 1) | in_literal);
+        in_literal = literal >> 63;
+        let mut bits = (block.structural & !string) | quote | starts;
+        let base = (num * 64) as u32;
+        while bits != 0 {
+            index.push(base + bits.trailing_zeros());
+            bits &= bits - 1;
+        }
+    }
+    if in_string != 0 {
+        return None;
+    }
+    Some(index)
+}
+
+/// The error of a document the index does not deserialize. It is reported
+/// by the parser instead, so its message is not seen.
+#[cold]
+fn invalid() -> Option<DeserializeError<'static>> {
+    Some(DeserializeError::invalid(Cow::Borrowed("Invalid JSON")))
+}
+
+/// A container being deserialized.
+enum Frame {
+    /// An array whose elements start at this index of `Builder::values`.
+    Array(usize),
+    Object {
+        dict: *mut pyo3_ffi::PyObject,
+        // the key of the member being deserialized, or null
+        key: *mut pyo3_ffi::PyObject,
+        hash: pyo3_ffi::Py_hash_t,
+        len: usize,
+        collected: Vec<*mut pyo3_ffi::PyObject>,
+    },
+}
+
+/// Makes the objects of a document from its structural index, nesting
+/// containers in `stack` rather than by recursion.
+struct Builder<'a> {
+    buf: &'static [u8],
+    index: &'a [u32],
+    // the position in `index` of the next offset to read
+    next: usize,
+    args: &'a LoadsArgs,
+    // the elements of the arrays in `stack`, in order
+    values: Vec<*mut pyo3_ffi::PyObject>,
+    stack: Vec<Frame>,
+    // a string with escapes, unescaped
+    scratch: String,
+}
+
+impl<'a> Drop for Builder<'a> {
+    fn drop(&mut self) {
+        for &val in self.values.iter() {
+            ffi!(Py_DECREF(val));
+        }
+        for frame in self.stack.iter() {
+            if let Frame::Object { dict, key, .. } = *frame {
+                ffi!(Py_DECREF(dict));
+                if !key.is_null() {
+                    ffi!(Py_DECREF(key));
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Builder<'a> {
+    /// The offset of the next structural character, quote, or literal.
+    #[inline(always)]
+    fn next(&mut self) -> Result<usize, Option<DeserializeError<'static>>> {
+        let pos = *self.index.get(self.next).ok_or_else(invalid)?;
+        self.next += 1;
+        Ok(pos as usize)
+    }
+
+    /// Read the next offset if it is of `c`.
+    #[inline(always)]
+    fn next_if(&mut self, c: u8) -> bool {
+        match self.index.get(self.next) {
+            Some(&pos) if self.buf[pos as usize] == c => {
+                self.next += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn build(&mut self) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let args = self.args;
+        'value: loop {
+            let pos = self.next()?;
+            let mut val = match self.buf[pos] {
+                b'[' => {
+                    self.push(Frame::Array(self.values.len()))?;
+                    if !self.next_if(b']') {
+                        continue 'value;
+                    }
+                    self.close()?
+                }
+                b'{' => {
+                    self.push(Frame::Object {
+                        dict: ffi!(PyDict_New()),
+                        key: null_mut(),
+                        hash: 0,
+                        len: 0,
+                        collected: Vec::new(),
+                    })?;
+                    if !self.next_if(b'}') {
+                        self.read_key()?;
+                        continue 'value;
+                    }
+                    self.close()?
+                }
+                b'"' => {
+                    let val = self.read_string(pos)?;
+                    args.check_string_len(val)?;
+                    parse_str(val, args)?
+                }
+                _ => self.read_literal(pos)?,
+            };
+            loop {
+                let is_array = match self.stack.last_mut() {
+                    None => {
+                        if self.next < self.index.len() {
+                            ffi!(Py_DECREF(val.as_ptr()));
+                            return Err(invalid());
+                        }
+                        return Ok(val);
+                    }
+                    Some(Frame::Array(_)) => {
+                        self.values.push(val.as_ptr());
+                        true
+                    }
+                    Some(Frame::Object {
+                        dict,
+                        key,
+                        hash,
+                        len,
+                        collected,
+                    }) => {
+                        let key = std::mem::replace(key, null_mut());
+                        *len += 1;
+                        set_object_item(*dict, key, *hash, val, args, collected)?;
+                        false
+                    }
+                };
+                let pos = self.next()?;
+                match self.buf[pos] {
+                    b',' => {
+                        if !is_array {
+                            self.read_key()?;
+                        }
+                        continue 'value;
+                    }
+                    b']' if is_array => val = self.close()?,
+                    b'}' if !is_array => val = self.close()?,
+                    _ => return Err(invalid()),
+                }
+            }
+        }
+    }
+
+    /// Start deserializing a container, failing if it is nested deeper than
+    /// `max_depth` allows.
+    #[inline(always)]
+    fn push(&mut self, frame: Frame) -> Result<(), Option<DeserializeError<'static>>> {
+        let too_deep = self.stack.len() as u32 >= self.args.max_depth();
+        // pushed regardless so that a dict is freed on error
+        self.stack.push(frame);
+        if unlikely!(too_deep) {
+            return Err(invalid());
+        }
+        Ok(())
+    }
+
+    /// Finish deserializing the innermost container.
+    fn close(&mut self) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let args = self.args;
+        match self.stack.last() {
+            Some(&Frame::Array(start)) => {
+                let len = self.values.len() - start;
+                args.add_items(len)?;
+                self.stack.pop();
+                let array = new_array(len, args);
+                for (idx, &item) in self.values[start..].iter().enumerate() {
+                    set_array_item(array, idx, item, args);
+                }
+                self.values.truncate(start);
+                Ok(nonnull!(array))
+            }
+            Some(&Frame::Object { len, .. }) => {
+                args.add_items(len)?;
+                match self.stack.pop() {
+                    Some(Frame::Object {
+                        dict, collected, ..
+                    }) => parse_object_hook(nonnull!(dict), &collected, args),
+                    _ => unreachable!(),
+                }
+            }
+            None => unreachable!(),
+        }
+    }
+
+    /// Read the key of the next member of the innermost object, and the colon
+    /// after it.
+    fn read_key(&mut self) -> Result<(), Option<DeserializeError<'static>>> {
+        let args = self.args;
+        let pos = self.next()?;
+        if self.buf[pos] != b'"' {
+            return Err(invalid());
+        }
+        let key_str = self.read_string(pos)?;
+        args.check_string_len(key_str)?;
+        let (pykey, pyhash) = get_unicode_key(key_str, args);
+        if let Some(Frame::Object { key, hash, .. }) = self.stack.last_mut() {
+            *key = pykey;
+            *hash = pyhash;
+        }
+        let pos = self.next()?;
+        if self.buf[pos] != b':' {
+            return Err(invalid());
+        }
+        Ok(())
+    }
+
+    /// Read the string opened by the quote at `open`, unescaped.
+    fn read_string(&mut self, open: usize) -> Result<&str, Option<DeserializeError<'static>>> {
+        // a quote in a string is escaped and is not in the index
+        let close = self.next()?;
+        let content = &self.buf[open + 1..close];
+        if !content.contains(&b'\\') {
+            return Ok(str_from_slice!(content.as_ptr(), content.len()));
+        }
+        unescape(content, &mut self.scratch).ok_or_else(invalid)?;
+        Ok(&self.scratch)
+    }
+
+    /// Read the literal starting at `start`.
+    fn read_literal(
+        &mut self,
+        start: usize,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let len = self.buf[start..]
+            .iter()
+            .position(|c| {
+                matches!(
+                    c,
+                    b' ' | b'\t' | b'\n' | b'\r' | b',' | b':' | b'[' | b']' | b'{' | b'}' | b'"'
+                )
+            })
+            .unwrap_or(self.buf.len() - start);
+        let literal = &self.buf[start..start + len];
+        match literal {
+            b"true" => Ok(parse_true()),
+            b"false" => Ok(parse_false()),
+            b"null" => Ok(parse_none()),
+            _ if is_number(literal) => read_number(literal, self.args),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Deserialize `literal`, a number, reading integers of up to 18 digits and
+/// finite floats directly and others as `loads()` does.
+#[inline(always)]
+fn read_number(
+    literal: &[u8],
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let val = str_from_slice!(literal.as_ptr(), literal.len());
+    if literal.iter().any(|&c| matches!(c, b'.' | b'e' | b'E')) {
+        return match val.parse::<f64>() {
+            Ok(val) if val.is_finite() => Ok(parse_f64(val)),
+            _ => Err(invalid()),
+        };
+    }
+    let (negative, digits) = match literal.split_first() {
+        Some((b'-', digits)) => (true, digits),
+        _ => (false, literal),
+    };
+    if digits.len() > 18 {
+        return parse_number_literal(val, args);
+    }
+    let num = digits
+        .iter()
+        .fold(0i64, |acc, &c| acc * 10 + (c - b'0') as i64);
+    Ok(parse_i64(if negative { -num } else { num }))
+}
+
+/// Unescape `content`, the contents of a string, into `out`. `None` means an
+/// escape is invalid or is of a lone surrogate.
+fn unescape(content: &[u8], out: &mut String) -> Option<()> {
+    out.clear();
+    let mut idx = 0;
+    while idx < content.len() {
+        if content[idx] != b'\\' {
+            let end = content[idx..]
+                .iter()
+                .position(|&c| c == b'\\')
+                .map_or(content.len(), |len| idx + len);
+            // split at backslashes, so it is valid UTF-8
+            out.push_str(str_from_slice!(content[idx..].as_ptr(), end - idx));
+            idx = end;
+            continue;
+        }
+        let escape = *content.get(idx + 1)?;
+        idx += 2;
+        match escape {
+            b'"' => out.push('"'),
+            b'\\' => out.push('\\'),
+            b'/' => out.push('/'),
+            b'b' => out.push('\x08'),
+            b'f' => out.push('\x0c'),
+            b'n' => out.push('\n'),
+            b'r' => out.push('\r'),
+            b't' => out.push('\t'),
+            b'u' => {
+                let high = hex4(content.get(idx..idx + 4)?)?;
+                idx += 4;
+                let code = match high {
+                    0xd800..=0xdbff => {
+                        if content.get(idx..idx + 2) != Some(b"\\u") {
+                            return None;
+                        }
+                        let low = hex4(content.get(idx + 2..idx + 6)?)?;
+                        if !(0xdc00..=0xdfff).contains(&low) {
+                            return None;
+                        }
+                        idx += 6;
+                        0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                    }
+                    0xdc00..=0xdfff => return None,
+                    _ => high,
+                };
+                out.push(char::from_u32(code)?);
+            }
+            _ => return None,
+        }
+    }
+    Some(())
+}
+
+/// The value of four hexadecimal digits.
+fn hex4(digits: &[u8]) -> Option<u32> {
+    digits
+        .iter()
+        .try_fold(0, |acc, &c| Some(acc * 16 + (c as char).to_digit(16)?))
+}
@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::error::Location;
+use crate::deserialize::file::{ChunkedReader, READ_CHUNK_SIZE};
+use crate::deserialize::utf8::BufferInput;
+use crate::deserialize::DeserializeError;
+use crate::typeref::*;
+use std::borrow::Cow;
+use std::io::{BufRead, BufReader};
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr::NonNull;
+
+/// Produces the values of an iterator returned by `orjson.iter_array()` or
+/// `orjson.loads_lines()`.
+pub trait Scanner {
+    /// Deserialize the next value, or return `None` after the last. An error
+    /// of `None` means an exception was raised reading the input.
+    fn next(
+        &mut self,
+    ) -> Result<Option<NonNull<pyo3_ffi::PyObject>>, Option<DeserializeError<'static>>>;
+
+    /// Raise `JSONDecodeError` for `err` returned by `next()`.
+    fn raise(&self, err: DeserializeError<'static>) -> *mut pyo3_ffi::PyObject {
+        crate::raise_loads_exception(err)
+    }
+}
+
+/// An iterator deserializing values as it is advanced.
+#[repr(C)]
+pub struct ScannerIterator {
+    ob_base: pyo3_ffi::PyObject,
+    // `None` once exhausted or after an error, which cannot be resumed from
+    scanner: Option<Box<dyn Scanner>>,
+}
+
+/// Create an iterator of type `tp`, one of the types created by
+/// `create_iterator_type()`, producing the values of `scanner`.
+pub fn new_iterator(
+    tp: *mut pyo3_ffi::PyTypeObject,
+    scanner: Box<dyn Scanner>,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    unsafe {
+        let obj = pyo3_ffi::PyType_GenericAlloc(tp, 0);
+        if obj.is_null() {
+            return Err(None);
+        }
+        std::ptr::write(
+            std::ptr::addr_of_mut!((*(obj as *mut ScannerIterator)).scanner),
+            Some(scanner),
+        );
+        Ok(NonNull::new_unchecked(obj))
+    }
+}
+
+unsafe extern "C" fn scanner_iterator_next(
+    slf: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    let slot = &mut (*(slf as *mut ScannerIterator)).scanner;
+    let res = match slot {
+        Some(scanner) => scanner.next(),
+        None => return std::ptr::null_mut(),
+    };
+    match res {
+        Ok(Some(val)) => val.as_ptr(),
+        Ok(None) => {
+            *slot = None;
+            std::ptr::null_mut()
+        }
+        Err(err) => {
+            let scanner = slot.take().unwrap();
+            match err {
+                Some(err) => scanner.raise(err),
+                None => std::ptr::null_mut(),
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn scanner_iterator_dealloc(slf: *mut pyo3_ffi::PyObject) {
+    std::ptr::drop_in_place(std::ptr::addr_of_mut!(
+        (*(slf as *mut ScannerIterator)).scanner
+    ));
+    let tp = ob_type!(slf);
+    ((*tp).tp_free.unwrap())(slf as *mut c_void);
+    pyo3_ffi::Py_DECREF(tp as *mut pyo3_ffi::PyObject);
+}
+
+/// Create an iterator type named `name`, which is nul-terminated.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub unsafe fn create_iterator_type(name: &'static str) -> *mut pyo3_ffi::PyTypeObject {
+    let slots: Box<[pyo3_ffi::PyType_Slot; 4]> = Box::new([
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_iter,
+            pfunc: pyo3_ffi::PyObject_SelfIter as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_iternext,
+            pfunc: scanner_iterator_next as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_dealloc,
+            pfunc: scanner_iterator_dealloc as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: 0,
+            pfunc: std::ptr::null_mut(),
+        },
+    ]);
+    let spec = Box::new(pyo3_ffi::PyType_Spec {
+        name: name.as_ptr() as *const c_char,
+        basicsize: std::mem::size_of::<ScannerIterator>() as c_int,
+        itemsize: 0,
+        flags: pyo3_ffi::Py_TPFLAGS_DEFAULT as c_uint,
+        slots: Box::into_raw(slots) as *mut pyo3_ffi::PyType_Slot,
+    });
+    pyo3_ffi::PyType_FromSpec(Box::into_raw(spec)) as *mut pyo3_ffi::PyTypeObject
+}
+
+enum Input {
+    // the `str` is kept alive for its UTF-8 contents
+    Str(*mut pyo3_ffi::PyObject, &'static [u8]),
+    // the number of bytes consumed is kept with the buffer
+    Buffer(BufferInput, usize),
+    File(BufReader<ChunkedReader>),
+}
+
+/// Input read incrementally by a `Scanner`: a `str`, an object supporting the
+/// buffer protocol, a file object, or a file descriptor.
+pub struct InputReader {
+    input: Input,
+    // the position of the first byte not consumed
+    location: Location,
+}
+
+impl InputReader {
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Result<Self, Option<DeserializeError<'static>>> {
+        let input = if is_type!(ob_type!(ptr), STR_TYPE) {
+            match crate::unicode::unicode_to_str(ptr) {
+                Some(as_str) => {
+                    ffi!(Py_INCREF(ptr));
+                    Input::Str(ptr, as_str.as_bytes())
+                }
+                None => {
+                    return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+                        crate::error::INVALID_STR,
+                    ))))
+                }
+            }
+        } else if ffi!(PyObject_CheckBuffer(ptr)) == 1 {
+            let buffer = BufferInput::new(ptr)?;
+            buffer.as_utf8()?;
+            Input::Buffer(buffer, 0)
+        } else {
+            Input::File(BufReader::with_capacity(
+                READ_CHUNK_SIZE,
+                ChunkedReader::new(ptr)?,
+            ))
+        };
+        Ok(InputReader {
+            input: input,
+            location: Location::default(),
+        })
+    }
+
+    /// Return the unconsumed input read so far, reading more if there is
+    /// none. It is empty at the end of the input. The bytes stay valid until
+    /// `fill_buf()` is called again.
+    pub fn fill_buf(&mut self) -> Result<&'static [u8], Option<DeserializeError<'static>>> {
+        let buf: &[u8] = match &mut self.input {
+            Input::Str(_, data) => data,
+            Input::Buffer(buffer, offset) => &buffer.as_bytes()[*offset..],
+            Input::File(reader) => {
+                if reader.fill_buf().is_err() {
+                    return Err(reader.get_mut().take_error().unwrap_or(None));
+                }
+                reader.buffer()
+            }
+        };
+        Ok(unsafe { std::slice::from_raw_parts(buf.as_ptr(), buf.len()) })
+    }
+
+    pub fn consume(&mut self, amt: usize) {
+        match &mut self.input {
+            Input::Str(_, data) => {
+                self.location.advance(&data[..amt]);
+                *data = &data[amt..];
+            }
+            Input::Buffer(buffer, offset) => {
+                self.location
+                    .advance(&buffer.as_bytes()[*offset..*offset + amt]);
+                *offset += amt;
+            }
+            Input::File(reader) => {
+                self.location.advance(&reader.buffer()[..amt]);
+                reader.consume(amt);
+            }
+        }
+    }
+
+    /// The position of the first byte of the input not consumed.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Skip whitespace and return the next byte, which is not consumed, or
+    /// `None` at the end of the input.
+    pub fn skip_whitespace(&mut self) -> Result<Option<u8>, Option<DeserializeError<'static>>> {
+        loop {
+            let buf = self.fill_buf()?;
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            match buf
+                .iter()
+                .position(|&c| !matches!(c, b' ' | b'\t' | b'\n' | b'\r'))
+            {
+                Some(idx) => {
+                    self.consume(idx);
+                    return Ok(Some(buf[idx]));
+                }
+                None => self.consume(buf.len()),
+            }
+        }
+    }
+}
+
+impl Drop for InputReader {
+    fn drop(&mut self) {
+        if let Input::Str(ptr, _) = self.input {
+            ffi!(Py_DECREF(ptr));
+        }
+    }
+}
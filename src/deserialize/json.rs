@@ -1,39 +1,387 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+#[cfg(not(feature = "yyjson"))]
+use crate::deserialize::columns::{not_records, Columns};
+use crate::deserialize::numpy::NumericArray;
 use crate::deserialize::pyobject::*;
-use crate::deserialize::DeserializeError;
-use crate::unicode::*;
+use crate::deserialize::utf8::LossyUtf8Reader;
+#[cfg(not(feature = "yyjson"))]
+use crate::deserialize::DuplicateKeys;
+use crate::deserialize::{DeserializeError, LoadsArgs};
 use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::de::ReadPosition;
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::fmt;
 use std::ptr::NonNull;
 
+#[cfg(not(feature = "yyjson"))]
 pub fn deserialize_json(
     data: &'static str,
-) -> Result<NonNull<pyo3_ffi::PyObject>, DeserializeError<'static>> {
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
     let mut deserializer = serde_json::Deserializer::from_str(data);
-    let seed = JsonValue {};
-    match seed.deserialize(&mut deserializer) {
+    if args.allow_nan {
+        deserializer.allow_nan();
+    }
+    if args.reads_raw_numbers() {
+        deserializer.raw_numbers();
+    }
+    if args.jsonc {
+        deserializer.allow_jsonc();
+    }
+    deserializer.set_lone_surrogates(args.surrogates.lone_surrogates());
+    deserializer.set_max_depth(args.stack_max_depth());
+    let res = if unlikely!(args.columns) {
+        JsonColumns { args: args }.deserialize(&mut deserializer)
+    } else {
+        JsonValue { args: args }.deserialize(&mut deserializer)
+    };
+    match res {
         Ok(obj) => {
             deserializer.end().map_err(|e| {
-                DeserializeError::from_json(Cow::Owned(e.to_string()), e.line(), e.column(), data)
+                DeserializeError::from_json(Cow::Owned(e.message()), e.line(), e.column(), data)
             })?;
-            Ok(obj)
+            crate::deserialize::schema::check_schema(obj, args, Some(data))
         }
-        Err(e) => Err(DeserializeError::from_json(
-            Cow::Owned(e.to_string()),
+        Err(_) if !ffi!(PyErr_Occurred()).is_null() => Err(None),
+        Err(e) => Err(Some(DeserializeError::from_json(
+            Cow::Owned(e.message()),
             e.line(),
             e.column(),
             data,
-        )),
+        ))),
+    }
+}
+
+/// Deserialize the value at the start of `data`, which may be followed by
+/// anything, and return it with the number of bytes of `data` it and the
+/// whitespace before it take.
+#[cfg(not(feature = "yyjson"))]
+pub fn deserialize_json_prefix(
+    data: &'static str,
+    args: &LoadsArgs,
+) -> Result<(NonNull<pyo3_ffi::PyObject>, usize), Option<DeserializeError<'static>>> {
+    let mut deserializer = serde_json::Deserializer::from_str(data);
+    if args.allow_nan {
+        deserializer.allow_nan();
+    }
+    if args.reads_raw_numbers() {
+        deserializer.raw_numbers();
+    }
+    if args.jsonc {
+        deserializer.allow_jsonc();
+    }
+    deserializer.set_lone_surrogates(args.surrogates.lone_surrogates());
+    deserializer.set_max_depth(args.stack_max_depth());
+    match (JsonValue { args: args }).deserialize(&mut deserializer) {
+        Ok(obj) => {
+            let end = deserializer.byte_offset();
+            crate::deserialize::schema::check_schema(obj, args, Some(data)).map(|obj| (obj, end))
+        }
+        Err(_) if !ffi!(PyErr_Occurred()).is_null() => Err(None),
+        Err(e) => Err(Some(DeserializeError::from_json(
+            Cow::Owned(e.message()),
+            e.line(),
+            e.column(),
+            data,
+        ))),
+    }
+}
+
+/// Check `data` as `deserialize_json()` would deserialize it, but without
+/// making any Python object.
+#[cfg(not(feature = "yyjson"))]
+pub fn check_json(data: &'static str, args: &LoadsArgs) -> Result<(), DeserializeError<'static>> {
+    let mut deserializer = serde_json::Deserializer::from_str(data);
+    if args.allow_nan {
+        deserializer.allow_nan();
+    }
+    if args.reads_raw_numbers() {
+        deserializer.raw_numbers();
+    }
+    if args.jsonc {
+        deserializer.allow_jsonc();
+    }
+    deserializer.set_lone_surrogates(args.surrogates.lone_surrogates());
+    deserializer.set_max_depth(args.stack_max_depth());
+    JsonCheck { args: args }
+        .deserialize(&mut deserializer)
+        .and_then(|_| deserializer.end())
+        .map_err(|e| {
+            DeserializeError::from_json(Cow::Owned(e.message()), e.line(), e.column(), data)
+        })
+}
+
+/// Deserialize a document read incrementally from `reader`, which is used by
+/// both backends because it does not need the whole input in memory.
+pub fn deserialize_json_reader<R: std::io::BufRead>(
+    reader: R,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, ReaderError> {
+    if args.replace_invalid_utf8 {
+        return read_json(LossyUtf8Reader::new(reader), args);
+    }
+    read_json(reader, args)
+}
+
+/// An error of `deserialize_json_reader()`.
+pub struct ReaderError {
+    /// Whether reading the input failed rather than parsing it.
+    pub is_io: bool,
+    pub error: DeserializeError<'static>,
+}
+
+fn read_json<R: std::io::Read>(
+    reader: R,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, ReaderError> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    if args.allow_nan {
+        deserializer.allow_nan();
+    }
+    if args.reads_raw_numbers() {
+        deserializer.raw_numbers();
+    }
+    if args.jsonc {
+        deserializer.allow_jsonc();
+    }
+    deserializer.set_lone_surrogates(args.surrogates.lone_surrogates());
+    deserializer.set_max_depth(args.stack_max_depth());
+    let res = JsonValue { args: args }
+        .deserialize(&mut deserializer)
+        .and_then(|obj| match deserializer.end() {
+            Ok(_) => Ok(obj),
+            Err(err) => {
+                ffi!(Py_DECREF(obj.as_ptr()));
+                Err(err)
+            }
+        });
+    res.map_err(|err| ReaderError {
+        is_io: err.is_io(),
+        error: locate(&err, deserializer.read_position()),
+    })
+}
+
+/// Locate `err`, which is on the current line of `read`, in a document that
+/// is not kept.
+#[cold]
+fn locate(err: &serde_json::Error, read: ReadPosition) -> DeserializeError<'static> {
+    let message = Cow::Owned(err.message());
+    if err.line() == 0 {
+        return DeserializeError::invalid(message);
+    }
+    // the column is of bytes
+    let tail_start = read.bytes - read.tail.len();
+    let offset = (read.line_start + err.column().saturating_sub(1)).clamp(tail_start, read.bytes);
+    let (before, after) = read.tail.split_at(offset - tail_start);
+    // the bytes kept may start within a character
+    let skip = before
+        .iter()
+        .take_while(|&&byte| byte & 0xc0 == 0x80)
+        .count();
+    let before = &before[skip..];
+    let start = read.chars - bytecount::num_chars(before) - bytecount::num_chars(after);
+    let pos = start + bytecount::num_chars(before);
+    let mut part = String::from_utf8_lossy(before).into_owned();
+    let part_pos = part.len();
+    part.push_str(&String::from_utf8_lossy(after));
+    DeserializeError::in_part(
+        message,
+        err.line(),
+        pos - read.line_start_chars + 1,
+        Cow::Owned(part),
+        part_pos,
+        start,
+    )
+}
+
+// The key of the map a number is given as with the arbitrary_precision
+// feature, which is enabled so that a number is read as written. An object
+// having only this key is indistinguishable from a number.
+const NUMBER_TOKEN: &str = "$serde_json::private::Number";
+
+/// Convert an error of calling a hook in `LoadsArgs`. The error for an
+/// exception raised by the hook is not reported, as the exception is raised
+/// instead.
+fn hook_error<E: de::Error>(err: Option<DeserializeError<'static>>) -> E {
+    match err {
+        Some(err) => E::custom(err.message),
+        None => E::custom("hook raised an exception"),
     }
 }
 
 #[derive(Clone, Copy)]
-struct JsonValue;
+struct JsonValue<'a> {
+    args: &'a LoadsArgs,
+}
+
+impl<'a> JsonValue<'a> {
+    /// Deserialize the rest of an object, of which `next` is the first key,
+    /// as the `list` of `(key, value)` tuples given to `object_pairs_hook`.
+    #[cold]
+    #[inline(never)]
+    fn visit_object_pairs<'de, A>(
+        self,
+        mut next: Option<beef::lean::Cow<'de, str>>,
+        mut map: A,
+        func: NonNull<pyo3_ffi::PyObject>,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut pairs: SmallVec<[*mut pyo3_ffi::PyObject; 8]> = SmallVec::new();
+        let res = loop {
+            let key = match next {
+                Some(key) => key,
+                None => break Ok(()),
+            };
+            if let Err(err) = self
+                .args
+                .add_items(1)
+                .and_then(|_| self.args.check_string_len(&key))
+            {
+                break Err(de::Error::custom(err.message));
+            }
+            let (pykey, _) = get_unicode_key(&key, self.args);
+            match map.next_value_seed(self) {
+                Ok(value) => pairs.push(parse_pair(pykey, value)),
+                Err(err) => {
+                    ffi!(Py_DECREF(pykey));
+                    break Err(err);
+                }
+            }
+            next = match map.next_key::<beef::lean::Cow<str>>() {
+                Ok(next) => next,
+                Err(err) => break Err(err),
+            };
+        };
+        if let Err(err) = res {
+            for &pair in pairs.iter() {
+                ffi!(Py_DECREF(pair));
+            }
+            return Err(err);
+        }
+        let list = ffi!(PyList_New(pairs.len() as isize));
+        for (i, &pair) in pairs.iter().enumerate() {
+            ffi!(PyList_SET_ITEM(list, i as isize, pair));
+        }
+        call_object_hook(func, nonnull!(list)).map_err(hook_error)
+    }
+
+    /// Deserialize the rest of an object, of which `next` is the first key.
+    fn visit_members<'de, A>(
+        self,
+        mut next: Option<beef::lean::Cow<'de, str>>,
+        mut map: A,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        if let Some(func) = self.args.object_pairs_hook {
+            return self.visit_object_pairs(next, map, func);
+        }
+        let dict_ptr = ffi!(PyDict_New());
+        let mut collected = Vec::new();
+        while let Some(key) = next {
+            if let Err(err) = self
+                .args
+                .add_items(1)
+                .and_then(|_| self.args.check_string_len(&key))
+            {
+                ffi!(Py_DECREF(dict_ptr));
+                return Err(de::Error::custom(err.message));
+            }
+            let (pykey, pyhash) = get_unicode_key(&key, self.args);
+            let value = match map.next_value_seed(self) {
+                Ok(value) => value,
+                Err(err) => {
+                    ffi!(Py_DECREF(pykey));
+                    ffi!(Py_DECREF(dict_ptr));
+                    return Err(err);
+                }
+            };
+            if let Err(err) =
+                set_object_item(dict_ptr, pykey, pyhash, value, self.args, &mut collected)
+            {
+                ffi!(Py_DECREF(dict_ptr));
+                return Err(de::Error::custom(err.message));
+            }
+            next = match map.next_key::<beef::lean::Cow<str>>() {
+                Ok(next) => next,
+                Err(err) => {
+                    ffi!(Py_DECREF(dict_ptr));
+                    return Err(err);
+                }
+            };
+        }
+        parse_object_hook(nonnull!(dict_ptr), &collected, self.args).map_err(hook_error)
+    }
+
+    /// Deserialize an array to a `numpy.ndarray` if its elements are numbers
+    /// that fit in one dtype, and to a `list` otherwise. The numbers are kept
+    /// as read until the end, so that a `list` has them as they are written.
+    #[cold]
+    #[inline(never)]
+    fn visit_numeric_seq<'de, A>(self, mut seq: A) -> Result<NonNull<pyo3_ffi::PyObject>, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut numbers: Vec<Number> = Vec::new();
+        let mut elements: Vec<*mut pyo3_ffi::PyObject> = Vec::new();
+        let res = loop {
+            match seq.next_element_seed(JsonElement { args: self.args }) {
+                Ok(Some(elem)) => {
+                    if let Err(err) = self.args.add_items(1) {
+                        if let Element::Object(obj) = elem {
+                            ffi!(Py_DECREF(obj.as_ptr()));
+                        }
+                        break Err(de::Error::custom(err.message));
+                    }
+                    match elem {
+                        Element::Number(number) if elements.is_empty() => numbers.push(number),
+                        Element::Number(number) => elements.push(number_to_object(number)),
+                        Element::Object(obj) => {
+                            elements.extend(numbers.drain(..).map(number_to_object));
+                            elements.push(obj.as_ptr());
+                        }
+                    }
+                }
+                Ok(None) => break Ok(()),
+                Err(err) => break Err(err),
+            }
+        };
+        if let Err(err) = res {
+            for &obj in elements.iter() {
+                ffi!(Py_DECREF(obj));
+            }
+            return Err(err);
+        }
+        if !numbers.is_empty() {
+            let mut array = NumericArray::with_capacity(numbers.len());
+            if numbers.iter().all(|&number| array.push(number)) {
+                return array.into_ndarray(self.args).map_err(hook_error);
+            }
+            elements.extend(numbers.drain(..).map(number_to_object));
+        }
+        let ptr = new_array(elements.len(), self.args);
+        for (i, &obj) in elements.iter().enumerate() {
+            set_array_item(ptr, i, obj, self.args);
+        }
+        Ok(nonnull!(ptr))
+    }
+}
+
+fn number_to_object(number: Number) -> *mut pyo3_ffi::PyObject {
+    match number {
+        Number::Int(val) => parse_i64(val),
+        Number::Uint(val) => parse_u64(val),
+        Number::Float(val) => parse_f64(val),
+    }
+    .as_ptr()
+}
 
-impl<'de> DeserializeSeed<'de> for JsonValue {
+impl<'de, 'a> DeserializeSeed<'de> for JsonValue<'a> {
     type Value = NonNull<pyo3_ffi::PyObject>;
 
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
@@ -44,7 +392,7 @@ impl<'de> DeserializeSeed<'de> for JsonValue {
     }
 }
 
-impl<'de> Visitor<'de> for JsonValue {
+impl<'de, 'a> Visitor<'de> for JsonValue<'a> {
     type Value = NonNull<pyo3_ffi::PyObject>;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -66,6 +414,9 @@ impl<'de> Visitor<'de> for JsonValue {
     where
         E: de::Error,
     {
+        if unlikely!(self.args.parse_int.is_some() || self.args.raw_numbers) {
+            return parse_number_literal(&value.to_string(), self.args).map_err(hook_error);
+        }
         Ok(parse_i64(value))
     }
 
@@ -73,6 +424,9 @@ impl<'de> Visitor<'de> for JsonValue {
     where
         E: de::Error,
     {
+        if unlikely!(self.args.parse_int.is_some() || self.args.raw_numbers) {
+            return parse_number_literal(&value.to_string(), self.args).map_err(hook_error);
+        }
         Ok(parse_u64(value))
     }
 
@@ -87,32 +441,57 @@ impl<'de> Visitor<'de> for JsonValue {
     where
         E: de::Error,
     {
-        Ok(nonnull!(unicode_from_str(value)))
+        self.visit_str(value)
     }
 
     fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
     where
         E: de::Error,
     {
-        Ok(nonnull!(unicode_from_str(value)))
+        self.args
+            .check_string_len(value)
+            .map_err(|err| E::custom(err.message))?;
+        parse_str(value, self.args).map_err(hook_error)
     }
 
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
     where
         A: SeqAccess<'de>,
     {
+        if unlikely!(self.args.numpy) {
+            return self.visit_numeric_seq(seq);
+        }
         match seq.next_element_seed(self) {
-            Ok(None) => Ok(nonnull!(ffi!(PyList_New(0)))),
+            Ok(None) => Ok(nonnull!(new_array(0, self.args))),
             Ok(Some(elem)) => {
                 let mut elements: SmallVec<[*mut pyo3_ffi::PyObject; 8]> =
                     SmallVec::with_capacity(8);
                 elements.push(elem.as_ptr());
-                while let Some(elem) = seq.next_element_seed(self)? {
-                    elements.push(elem.as_ptr());
+                let mut res = self.args.add_items(1);
+                while res.is_ok() {
+                    match seq.next_element_seed(self) {
+                        Ok(Some(elem)) => {
+                            elements.push(elem.as_ptr());
+                            res = self.args.add_items(1);
+                        }
+                        Ok(None) => break,
+                        Err(err) => {
+                            for &obj in elements.iter() {
+                                ffi!(Py_DECREF(obj));
+                            }
+                            return Err(err);
+                        }
+                    }
                 }
-                let ptr = ffi!(PyList_New(elements.len() as isize));
+                if let Err(err) = res {
+                    for &obj in elements.iter() {
+                        ffi!(Py_DECREF(obj));
+                    }
+                    return Err(de::Error::custom(err.message));
+                }
+                let ptr = new_array(elements.len(), self.args);
                 for (i, &obj) in elements.iter().enumerate() {
-                    ffi!(PyList_SET_ITEM(ptr, i as isize, obj));
+                    set_array_item(ptr, i, obj, self.args);
                 }
                 Ok(nonnull!(ptr))
             }
@@ -124,20 +503,327 @@ impl<'de> Visitor<'de> for JsonValue {
     where
         A: MapAccess<'de>,
     {
-        let dict_ptr = ffi!(PyDict_New());
-        while let Some(key) = map.next_key::<beef::lean::Cow<str>>()? {
-            let (pykey, pyhash) = get_unicode_key(&key);
-            let value = map.next_value_seed(self)?;
-            let _ = ffi!(_PyDict_SetItem_KnownHash(
-                dict_ptr,
-                pykey,
-                value.as_ptr(),
-                pyhash
-            ));
-            // counter Py_INCREF in insertdict
-            ffi!(Py_DECREF(pykey));
-            ffi!(Py_DECREF(value.as_ptr()));
-        }
-        Ok(nonnull!(dict_ptr))
+        let next = map.next_key::<beef::lean::Cow<str>>()?;
+        if unlikely!(next.as_deref() == Some(NUMBER_TOKEN)) {
+            let literal = map.next_value::<String>()?;
+            return parse_number_literal(&literal, self.args).map_err(hook_error);
+        }
+        self.visit_members(next, map)
+    }
+}
+
+/// An element of an array deserialized with `numpy`, kept as a number if it is
+/// one that fits in a machine type.
+enum Element {
+    Number(Number),
+    Object(NonNull<pyo3_ffi::PyObject>),
+}
+
+/// A value deserialized as `JsonValue` would, unless it is a number.
+#[derive(Clone, Copy)]
+struct JsonElement<'a> {
+    args: &'a LoadsArgs,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for JsonElement<'a> {
+    type Value = Element;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for JsonElement<'a> {
+    type Value = Element;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("JSON")
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Element::Object(parse_none()))
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Element::Object(parse_bool(value)))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Element::Number(Number::Int(value)))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Element::Number(Number::Uint(value)))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Element::Number(Number::Float(value)))
+    }
+
+    fn visit_borrowed_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visit_str(value)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        JsonValue { args: self.args }
+            .visit_str(value)
+            .map(Element::Object)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        JsonValue { args: self.args }
+            .visit_numeric_seq(seq)
+            .map(Element::Object)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let next = map.next_key::<beef::lean::Cow<str>>()?;
+        if unlikely!(next.as_deref() == Some(NUMBER_TOKEN)) {
+            let literal = map.next_value::<String>()?;
+            return match read_number_literal(&literal, self.args) {
+                Ok(Some(number)) => Ok(Element::Number(number)),
+                Ok(None) => parse_number_literal(&literal, self.args)
+                    .map(Element::Object)
+                    .map_err(hook_error),
+                Err(err) => Err(de::Error::custom(err.message)),
+            };
+        }
+        JsonValue { args: self.args }
+            .visit_members(next, map)
+            .map(Element::Object)
+    }
+}
+
+/// A value checked as `JsonValue` would deserialize it.
+#[cfg(not(feature = "yyjson"))]
+#[derive(Clone, Copy)]
+struct JsonCheck<'a> {
+    args: &'a LoadsArgs,
+}
+
+#[cfg(not(feature = "yyjson"))]
+impl<'de, 'a> DeserializeSeed<'de> for JsonCheck<'a> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+#[cfg(not(feature = "yyjson"))]
+impl<'de, 'a> Visitor<'de> for JsonCheck<'a> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("JSON")
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_bool<E>(self, _value: bool) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _value: i64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _value: u64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _value: f64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.args
+            .check_string_len(value)
+            .map_err(|err| E::custom(err.message))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        while seq.next_element_seed(self)?.is_some() {
+            self.args
+                .add_items(1)
+                .map_err(|err| de::Error::custom(err.message))?;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut next = map.next_key::<beef::lean::Cow<str>>()?;
+        if unlikely!(next.as_deref() == Some(NUMBER_TOKEN)) {
+            let literal = map.next_value::<String>()?;
+            return check_number_literal(&literal, self.args)
+                .map_err(|err| de::Error::custom(err.message));
+        }
+        let mut keys = std::collections::HashSet::new();
+        while let Some(key) = next {
+            self.args
+                .add_items(1)
+                .and_then(|_| self.args.check_string_len(&key))
+                .map_err(|err| de::Error::custom(err.message))?;
+            map.next_value_seed(self)?;
+            if self.args.duplicate_keys == DuplicateKeys::Error && !keys.insert(key.to_string()) {
+                return Err(de::Error::custom(format!("Duplicate key \"{}\"", key)));
+            }
+            next = map.next_key::<beef::lean::Cow<str>>()?;
+        }
+        Ok(())
+    }
+}
+
+/// A top-level array of objects deserialized to columns as `loads_columns()`
+/// does.
+#[cfg(not(feature = "yyjson"))]
+struct JsonColumns<'a> {
+    args: &'a LoadsArgs,
+}
+
+#[cfg(not(feature = "yyjson"))]
+impl<'de, 'a> DeserializeSeed<'de> for JsonColumns<'a> {
+    type Value = NonNull<pyo3_ffi::PyObject>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+#[cfg(not(feature = "yyjson"))]
+impl<'de, 'a> Visitor<'de> for JsonColumns<'a> {
+    type Value = NonNull<pyo3_ffi::PyObject>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of objects")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut columns = Columns::new();
+        while seq
+            .next_element_seed(JsonRow {
+                args: self.args,
+                columns: &mut columns,
+            })?
+            .is_some()
+        {
+            columns.end_row();
+            self.args
+                .add_items(1)
+                .map_err(|err| de::Error::custom(err.message))?;
+        }
+        Ok(columns.finish())
+    }
+
+    fn visit_map<A>(self, _map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        Err(de::Error::custom(not_records().message))
+    }
+}
+
+/// An object of the array given to `loads_columns()`, of which each member is
+/// set in `columns`.
+#[cfg(not(feature = "yyjson"))]
+struct JsonRow<'a, 'b> {
+    args: &'a LoadsArgs,
+    columns: &'b mut Columns,
+}
+
+#[cfg(not(feature = "yyjson"))]
+impl<'de, 'a, 'b> DeserializeSeed<'de> for JsonRow<'a, 'b> {
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+#[cfg(not(feature = "yyjson"))]
+impl<'de, 'a, 'b> Visitor<'de> for JsonRow<'a, 'b> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut next = map.next_key::<beef::lean::Cow<str>>()?;
+        if unlikely!(next.as_deref() == Some(NUMBER_TOKEN)) {
+            return Err(de::Error::custom(not_records().message));
+        }
+        while let Some(key) = next {
+            self.args
+                .add_items(1)
+                .and_then(|_| self.args.check_string_len(&key))
+                .map_err(|err| de::Error::custom(err.message))?;
+            let (pykey, pyhash) = get_unicode_key(&key, self.args);
+            let value = match map.next_value_seed(JsonValue { args: self.args }) {
+                Ok(value) => value,
+                Err(err) => {
+                    ffi!(Py_DECREF(pykey));
+                    return Err(err);
+                }
+            };
+            self.columns
+                .set(pykey, pyhash, value, self.args)
+                .map_err(|err| de::Error::custom(err.message))?;
+            next = map.next_key::<beef::lean::Cow<str>>()?;
+        }
+        Ok(())
     }
 }
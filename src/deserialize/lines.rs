@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::deserializer::deserialize_bytes;
+use crate::deserialize::iter::{new_iterator, InputReader, Scanner};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use std::ptr::NonNull;
+
+/// Create an iterator over the documents on each line of `ptr`.
+pub fn loads_lines(
+    ptr: *mut pyo3_ffi::PyObject,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let scanner = LineScanner {
+        reader: InputReader::new(ptr)?,
+        line: Vec::new(),
+    };
+    new_iterator(unsafe { LINES_ITERATOR_TYPE }, Box::new(scanner))
+}
+
+/// Deserializes newline-delimited JSON, skipping blank lines.
+struct LineScanner {
+    reader: InputReader,
+    // a line split across reads, reused across lines
+    line: Vec<u8>,
+}
+
+impl LineScanner {
+    /// Read the next line, without its `\n`, or return `None` at the end of
+    /// the input. The line is valid until this is called again.
+    fn read_line(&mut self) -> Result<Option<&'static [u8]>, Option<DeserializeError<'static>>> {
+        self.line.clear();
+        let mut partial = false;
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                if !partial {
+                    return Ok(None);
+                }
+                break;
+            }
+            match buf.iter().position(|&c| c == b'\n') {
+                Some(idx) => {
+                    self.reader.consume(idx + 1);
+                    if !partial {
+                        // the whole line was read at once, so it is not copied
+                        return Ok(Some(&buf[..idx]));
+                    }
+                    self.line.extend_from_slice(&buf[..idx]);
+                    break;
+                }
+                None => {
+                    self.line.extend_from_slice(buf);
+                    self.reader.consume(buf.len());
+                    partial = true;
+                }
+            }
+        }
+        Ok(Some(unsafe {
+            std::slice::from_raw_parts(self.line.as_ptr(), self.line.len())
+        }))
+    }
+}
+
+impl Scanner for LineScanner {
+    fn next(
+        &mut self,
+    ) -> Result<Option<NonNull<pyo3_ffi::PyObject>>, Option<DeserializeError<'static>>> {
+        loop {
+            let at = self.reader.location();
+            let line = match self.read_line()? {
+                Some(line) => line,
+                None => return Ok(None),
+            };
+            if line.iter().all(|&c| matches!(c, b' ' | b'\t' | b'\r')) {
+                continue;
+            }
+            // the iterator can be advanced by another thread if the GIL is
+            // released
+            return deserialize_bytes(line, &LoadsArgs::default(), false)
+                .map(Some)
+                .map_err(|err| err.map(|err| err.within(at)));
+        }
+    }
+}
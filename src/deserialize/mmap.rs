@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::deserializer::{deserialize, deserialize_bytes};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use std::os::raw::{c_char, c_int};
+use std::ptr::{null, null_mut, NonNull};
+
+/// Deserialize the file at `path`, a `str`, `bytes`, or `os.PathLike`,
+/// mapped into memory read-only rather than read, so that it is parsed in
+/// place as `mmap.mmap` input is, without being copied into a `bytes` object.
+/// An error of `None` means an exception was raised opening or mapping it or
+/// by a callable in `args`.
+pub fn load_mmap(
+    path: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    ffi!(Py_INCREF(path));
+    let mode = ffi!(PyUnicode_FromString("rb\0".as_ptr() as *const c_char));
+    let file = call_module_attr("io\0", "open\0", &[path, mode], null_mut());
+    if file.is_null() {
+        return Err(None);
+    }
+    let mapped = map_file(file);
+    // the mapping does not need the file to stay open
+    let closed = close_file(file);
+    let mapped = match mapped {
+        Ok(Some(mapped)) => mapped,
+        // an empty file cannot be mapped, and is no document
+        Ok(None) if closed => return deserialize_bytes(&[], args, false),
+        Ok(None) | Err(()) => return Err(None),
+    };
+    if !closed {
+        ffi!(Py_DECREF(mapped));
+        return Err(None);
+    }
+    let res = deserialize(mapped, args);
+    // the buffer of the mapping is no longer exported, so it is unmapped
+    ffi!(Py_DECREF(mapped));
+    res
+}
+
+/// Map the contents of `file`, an open binary file, read-only with
+/// `mmap.mmap`, or return `None` if it is empty. An error means an exception
+/// was raised.
+fn map_file(file: *mut pyo3_ffi::PyObject) -> Result<Option<*mut pyo3_ffi::PyObject>, ()> {
+    unsafe {
+        let end = pyo3_ffi::PyObject_CallMethod(
+            file,
+            "seek\0".as_ptr() as *const c_char,
+            "ii\0".as_ptr() as *const c_char,
+            0 as c_int,
+            2 as c_int,
+        );
+        if end.is_null() {
+            return Err(());
+        }
+        let len = pyo3_ffi::PyLong_AsSsize_t(end);
+        pyo3_ffi::Py_DECREF(end);
+        if len == -1 && !pyo3_ffi::PyErr_Occurred().is_null() {
+            return Err(());
+        }
+        if len == 0 {
+            return Ok(None);
+        }
+        let fileno = pyo3_ffi::PyObject_CallMethod(
+            file,
+            "fileno\0".as_ptr() as *const c_char,
+            null::<c_char>(),
+        );
+        if fileno.is_null() {
+            return Err(());
+        }
+        let module = pyo3_ffi::PyImport_ImportModule("mmap\0".as_ptr() as *const c_char);
+        if module.is_null() {
+            pyo3_ffi::Py_DECREF(fileno);
+            return Err(());
+        }
+        let access =
+            pyo3_ffi::PyObject_GetAttrString(module, "ACCESS_READ\0".as_ptr() as *const c_char);
+        pyo3_ffi::Py_DECREF(module);
+        if access.is_null() {
+            pyo3_ffi::Py_DECREF(fileno);
+            return Err(());
+        }
+        let kwargs = pyo3_ffi::PyDict_New();
+        pyo3_ffi::PyDict_SetItemString(kwargs, "access\0".as_ptr() as *const c_char, access);
+        pyo3_ffi::Py_DECREF(access);
+        // a length of 0 maps the whole file
+        let mapped = call_module_attr(
+            "mmap\0",
+            "mmap\0",
+            &[fileno, pyo3_ffi::PyLong_FromLong(0)],
+            kwargs,
+        );
+        pyo3_ffi::Py_DECREF(kwargs);
+        if mapped.is_null() {
+            return Err(());
+        }
+        Ok(Some(mapped))
+    }
+}
+
+/// Close `file` and release it, returning whether no exception was raised.
+/// An exception raised before is kept in place of one raised by `close()`.
+fn close_file(file: *mut pyo3_ffi::PyObject) -> bool {
+    unsafe {
+        let mut ptype = null_mut();
+        let mut pvalue = null_mut();
+        let mut ptraceback = null_mut();
+        let pending = !pyo3_ffi::PyErr_Occurred().is_null();
+        if pending {
+            pyo3_ffi::PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback);
+        }
+        let res = pyo3_ffi::PyObject_CallMethod(
+            file,
+            "close\0".as_ptr() as *const c_char,
+            null::<c_char>(),
+        );
+        pyo3_ffi::Py_DECREF(file);
+        if pending {
+            pyo3_ffi::Py_XDECREF(res);
+            pyo3_ffi::PyErr_Clear();
+            pyo3_ffi::PyErr_Restore(ptype, pvalue, ptraceback);
+            return false;
+        }
+        if res.is_null() {
+            return false;
+        }
+        pyo3_ffi::Py_DECREF(res);
+        true
+    }
+}
+
+/// Call `attr` of the module `module`, both nul-terminated, with the
+/// positional arguments `args`, which are consumed, and `kwargs`, which may be
+/// null.
+fn call_module_attr(
+    module: &str,
+    attr: &str,
+    args: &[*mut pyo3_ffi::PyObject],
+    kwargs: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    unsafe {
+        let tuple = pyo3_ffi::PyTuple_New(args.len() as isize);
+        for (idx, &arg) in args.iter().enumerate() {
+            pyo3_ffi::PyTuple_SET_ITEM(tuple, idx as isize, arg);
+        }
+        let module = pyo3_ffi::PyImport_ImportModule(module.as_ptr() as *const c_char);
+        if module.is_null() {
+            pyo3_ffi::Py_DECREF(tuple);
+            return null_mut();
+        }
+        let func = pyo3_ffi::PyObject_GetAttrString(module, attr.as_ptr() as *const c_char);
+        pyo3_ffi::Py_DECREF(module);
+        if func.is_null() {
+            pyo3_ffi::Py_DECREF(tuple);
+            return null_mut();
+        }
+        let res = pyo3_ffi::PyObject_Call(func, tuple, kwargs);
+        pyo3_ffi::Py_DECREF(func);
+        pyo3_ffi::Py_DECREF(tuple);
+        res
+    }
+}
@@ -1,18 +1,63 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+mod array;
 mod cache;
+mod cbor;
+mod columns;
+mod compressed;
+mod datetime;
+mod decoder;
 mod deserializer;
+mod documents;
 mod error;
+mod file;
+#[cfg(feature = "structural-index")]
+mod index;
+mod iter;
+mod json;
+mod lines;
+mod mmap;
+mod msgpack;
+mod numpy;
+#[cfg(feature = "yyjson")]
+mod parallel;
+mod parser;
+mod path;
+mod prefix;
 mod pyobject;
+mod raw_number;
+mod recover;
+mod schema;
+mod select;
+mod tokenize;
+mod typed;
 mod utf8;
-
-#[cfg(not(feature = "yyjson"))]
-mod json;
+mod valid;
 
 #[cfg(feature = "yyjson")]
 mod yyjson;
 
+pub use array::iter_array;
 pub use cache::KeyMap;
 pub use cache::KEY_MAP;
-pub use deserializer::deserialize;
+pub use cbor::loads_cbor;
+pub use columns::loads_columns;
+pub use compressed::{loads_compressed, Compression};
+pub use decoder::{create_decoder_type, hooks, parse_kwargs};
+pub use deserializer::{deserialize, deserialize_bytes, DuplicateKeys, LoadsArgs, Surrogates};
+pub use documents::iter_documents;
 pub use error::DeserializeError;
+pub use file::{deserialize_file, deserialize_file_chunked, new_os_error, raise_os_error};
+pub use iter::create_iterator_type;
+pub use lines::loads_lines;
+pub use mmap::load_mmap;
+pub use msgpack::unpackb;
+pub use parser::create_parser_type;
+pub use prefix::loads_prefix;
+pub use raw_number::{create_raw_number_type, raw_number_literal};
+pub use recover::find_errors;
+pub use schema::create_schema_type;
+pub use select::select;
+pub use tokenize::{tokenize, EVENT_NAMES};
+pub use typed::{loads_into, ClassTargetMap, CLASS_TARGETS};
+pub use valid::is_valid;
@@ -0,0 +1,387 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::error::*;
+use crate::deserialize::pyobject::*;
+use crate::deserialize::schema::check_schema;
+use crate::deserialize::utf8::{is_valid_utf8, BufferInput};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use std::borrow::Cow;
+use std::os::raw::c_char;
+use std::ptr::NonNull;
+
+/// Deserialize MessagePack `ptr`, an object supporting the buffer protocol,
+/// to the objects `loads()` makes with `args`. An error of `None` means an
+/// exception was raised by a callable in `args`.
+pub fn unpackb(
+    ptr: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if is_type!(ob_type!(ptr), STR_TYPE) || ffi!(PyObject_CheckBuffer(ptr)) == 0 {
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+            "Input must be bytes, bytearray, memoryview, or a buffer",
+        ))));
+    }
+    let input = BufferInput::new(ptr)?;
+    let data = args.slice(input.as_bytes())?;
+    args.check_binary_bytes(data.len())?;
+    let mut unpacker = Unpacker {
+        data: data,
+        pos: 0,
+        args: args,
+    };
+    let obj = unpacker.parse(args.stack_max_depth())?;
+    if unpacker.pos < data.len() {
+        ffi!(Py_DECREF(obj.as_ptr()));
+        return Err(unpacker.error(UNEXPECTED_CONTENT));
+    }
+    check_schema(obj, args, None)
+}
+
+struct Unpacker<'a> {
+    data: &'a [u8],
+    pos: usize,
+    args: &'a LoadsArgs,
+}
+
+impl<'a> Unpacker<'a> {
+    /// An error at the current position.
+    #[cold]
+    fn error(&self, message: &str) -> Option<DeserializeError<'static>> {
+        self.error_at(self.pos, message)
+    }
+
+    #[cold]
+    fn error_at(&self, pos: usize, message: &str) -> Option<DeserializeError<'static>> {
+        Some(DeserializeError::at_offset(
+            Cow::Owned(String::from(message)),
+            pos,
+        ))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Option<DeserializeError<'static>>> {
+        if unlikely!(self.data.len() - self.pos < len) {
+            return Err(self.error(UNEXPECTED_END));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Option<DeserializeError<'static>>> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Option<DeserializeError<'static>>> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Option<DeserializeError<'static>>> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Option<DeserializeError<'static>>> {
+        let bytes = self.take(8)?;
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(bytes);
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    /// Read the length of a container, which cannot have more elements than
+    /// there are bytes left, as each is at least one byte.
+    fn read_container_len(
+        &mut self,
+        marker: u8,
+        len: usize,
+    ) -> Result<usize, Option<DeserializeError<'static>>> {
+        let len = match marker {
+            0xdc | 0xde => self.read_u16()? as usize,
+            0xdd | 0xdf => self.read_u32()? as usize,
+            _ => len,
+        };
+        if unlikely!(len > self.data.len() - self.pos) {
+            return Err(self.error(UNEXPECTED_END));
+        }
+        Ok(len)
+    }
+
+    /// Read a string of `len` bytes.
+    fn read_str(&mut self, len: usize) -> Result<&'a str, Option<DeserializeError<'static>>> {
+        let start = self.pos;
+        let bytes = self.take(len)?;
+        if unlikely!(!is_valid_utf8(bytes)) {
+            return Err(self.error_at(start, INVALID_UTF8));
+        }
+        let val = str_from_slice!(bytes.as_ptr(), bytes.len());
+        self.args.check_string_len(val)?;
+        Ok(val)
+    }
+
+    /// Read the string of a map key, which must be a string as in JSON.
+    fn read_key(&mut self) -> Result<&'a str, Option<DeserializeError<'static>>> {
+        let start = self.pos;
+        let len = match self.read_u8()? {
+            marker @ 0xa0..=0xbf => (marker & 0x1f) as usize,
+            0xd9 => self.read_u8()? as usize,
+            0xda => self.read_u16()? as usize,
+            0xdb => self.read_u32()? as usize,
+            _ => return Err(self.error_at(start, "map key must be a string")),
+        };
+        self.read_str(len)
+    }
+
+    fn parse_int(
+        &self,
+        val: i64,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        if unlikely!(self.args.parse_int.is_some()) {
+            return parse_number_literal(itoa::Buffer::new().format(val), self.args);
+        }
+        Ok(parse_i64(val))
+    }
+
+    fn parse_uint(
+        &self,
+        val: u64,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        if unlikely!(self.args.parse_int.is_some()) {
+            return parse_number_literal(itoa::Buffer::new().format(val), self.args);
+        }
+        Ok(parse_u64(val))
+    }
+
+    /// Deserialize a float, given to `parse_float` as its shortest `str`.
+    /// `NaN` and infinity are refused unless `allow_nan`, as in JSON.
+    fn parse_float(
+        &self,
+        start: usize,
+        val: f64,
+        literal: &str,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        if unlikely!(!val.is_finite()) {
+            if !self.args.allow_nan {
+                return Err(self.error_at(start, "nan or inf number is not allowed"));
+            }
+            return Ok(parse_f64(val));
+        }
+        if unlikely!(self.args.parse_float.is_some()) {
+            return parse_number_literal(literal, self.args);
+        }
+        Ok(parse_f64(val))
+    }
+
+    fn parse_bin(
+        &mut self,
+        len: usize,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let bytes = self.take(len)?;
+        Ok(nonnull!(ffi!(PyBytes_FromStringAndSize(
+            bytes.as_ptr() as *const c_char,
+            len as isize
+        ))))
+    }
+
+    fn parse_array(
+        &mut self,
+        len: usize,
+        remaining_depth: u32,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        self.args.add_items(len)?;
+        let array = new_array(len, self.args);
+        for idx in 0..len {
+            match self.parse(remaining_depth - 1) {
+                Ok(each) => set_array_item(array, idx, each.as_ptr(), self.args),
+                Err(err) => {
+                    // items not set are NULL, which are skipped when it is freed
+                    ffi!(Py_DECREF(array));
+                    return Err(err);
+                }
+            }
+        }
+        Ok(nonnull!(array))
+    }
+
+    fn parse_map(
+        &mut self,
+        len: usize,
+        remaining_depth: u32,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        self.args.add_items(len)?;
+        if let Some(func) = self.args.object_pairs_hook {
+            return self.parse_map_pairs(len, func, remaining_depth);
+        }
+        let dict = ffi!(PyDict_New());
+        let mut collected = Vec::new();
+        for _ in 0..len {
+            let key = match self.read_key() {
+                Ok(key) => key,
+                Err(err) => {
+                    ffi!(Py_DECREF(dict));
+                    return Err(err);
+                }
+            };
+            let (pykey, pyhash) = get_unicode_key(key, self.args);
+            let pyval = match self.parse(remaining_depth - 1) {
+                Ok(pyval) => pyval,
+                Err(err) => {
+                    ffi!(Py_DECREF(pykey));
+                    ffi!(Py_DECREF(dict));
+                    return Err(err);
+                }
+            };
+            if let Err(err) = set_object_item(dict, pykey, pyhash, pyval, self.args, &mut collected)
+            {
+                ffi!(Py_DECREF(dict));
+                return Err(Some(err));
+            }
+        }
+        parse_object_hook(nonnull!(dict), &collected, self.args)
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn parse_map_pairs(
+        &mut self,
+        len: usize,
+        func: NonNull<pyo3_ffi::PyObject>,
+        remaining_depth: u32,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let list = ffi!(PyList_New(0));
+        for _ in 0..len {
+            let res = self.read_key().and_then(|key| {
+                let (pykey, _) = get_unicode_key(key, self.args);
+                match self.parse(remaining_depth - 1) {
+                    Ok(pyval) => Ok(parse_pair(pykey, pyval)),
+                    Err(err) => {
+                        ffi!(Py_DECREF(pykey));
+                        Err(err)
+                    }
+                }
+            });
+            match res {
+                Ok(pair) => {
+                    ffi!(PyList_Append(list, pair));
+                    ffi!(Py_DECREF(pair));
+                }
+                Err(err) => {
+                    ffi!(Py_DECREF(list));
+                    return Err(err);
+                }
+            }
+        }
+        call_object_hook(func, nonnull!(list))
+    }
+
+    /// Deserialize the next value, in which containers may be nested
+    /// `remaining_depth` levels deep.
+    fn parse(
+        &mut self,
+        remaining_depth: u32,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let start = self.pos;
+        let marker = self.read_u8()?;
+        if unlikely!(remaining_depth == 0 && matches!(marker, 0x80..=0x9f | 0xdc..=0xdf)) {
+            return Err(self.error_at(start, RECURSION_LIMIT));
+        }
+        match marker {
+            0x00..=0x7f => self.parse_int(marker as i64),
+            0x80..=0x8f => {
+                let len = self.read_container_len(marker, (marker & 0x0f) as usize)?;
+                self.parse_map(len, remaining_depth)
+            }
+            0x90..=0x9f => {
+                let len = self.read_container_len(marker, (marker & 0x0f) as usize)?;
+                self.parse_array(len, remaining_depth)
+            }
+            0xa0..=0xbf => {
+                let val = self.read_str((marker & 0x1f) as usize)?;
+                parse_str(val, self.args)
+            }
+            0xc0 => Ok(parse_none()),
+            0xc2 => Ok(parse_false()),
+            0xc3 => Ok(parse_true()),
+            0xc4 => {
+                let len = self.read_u8()? as usize;
+                self.parse_bin(len)
+            }
+            0xc5 => {
+                let len = self.read_u16()? as usize;
+                self.parse_bin(len)
+            }
+            0xc6 => {
+                let len = self.read_u32()? as usize;
+                self.parse_bin(len)
+            }
+            0xca => {
+                let val = f32::from_bits(self.read_u32()?);
+                self.parse_float(start, val as f64, ryu::Buffer::new().format(val))
+            }
+            0xcb => {
+                let val = f64::from_bits(self.read_u64()?);
+                self.parse_float(start, val, ryu::Buffer::new().format(val))
+            }
+            0xcc => {
+                let val = self.read_u8()?;
+                self.parse_int(val as i64)
+            }
+            0xcd => {
+                let val = self.read_u16()?;
+                self.parse_int(val as i64)
+            }
+            0xce => {
+                let val = self.read_u32()?;
+                self.parse_int(val as i64)
+            }
+            0xcf => {
+                let val = self.read_u64()?;
+                self.parse_uint(val)
+            }
+            0xd0 => {
+                let val = self.read_u8()? as i8;
+                self.parse_int(val as i64)
+            }
+            0xd1 => {
+                let val = self.read_u16()? as i16;
+                self.parse_int(val as i64)
+            }
+            0xd2 => {
+                let val = self.read_u32()? as i32;
+                self.parse_int(val as i64)
+            }
+            0xd3 => {
+                let val = self.read_u64()? as i64;
+                self.parse_int(val)
+            }
+            0xd9 => {
+                let len = self.read_u8()? as usize;
+                let val = self.read_str(len)?;
+                parse_str(val, self.args)
+            }
+            0xda => {
+                let len = self.read_u16()? as usize;
+                let val = self.read_str(len)?;
+                parse_str(val, self.args)
+            }
+            0xdb => {
+                let len = self.read_u32()? as usize;
+                let val = self.read_str(len)?;
+                parse_str(val, self.args)
+            }
+            0xdc..=0xdd => {
+                let len = self.read_container_len(marker, 0)?;
+                self.parse_array(len, remaining_depth)
+            }
+            0xde..=0xdf => {
+                let len = self.read_container_len(marker, 0)?;
+                self.parse_map(len, remaining_depth)
+            }
+            0xe0..=0xff => self.parse_int(marker as i8 as i64),
+            0xc7..=0xc9 | 0xd4..=0xd8 => {
+                Err(self.error_at(start, "MessagePack extension types are not supported"))
+            }
+            0xc1 => Err(self.error_at(start, "invalid MessagePack type 0xc1")),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::pyobject::Number;
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use std::borrow::Cow;
+use std::os::raw::c_char;
+use std::ptr::NonNull;
+
+// Magnitude up to which every integer is exactly a double.
+const MAX_EXACT_INT: u64 = 1 << 53;
+
+/// The elements of an array of numbers, collected to be deserialized to one
+/// `numpy.ndarray` rather than a `list` of `int` and `float` objects. The
+/// dtype is `int64` if every element is an integer that fits, `uint64` if
+/// every element is a non-negative integer that fits, and `float64` if an
+/// element is a float and every integer is exactly a double.
+pub enum NumericArray {
+    Int64(Vec<i64>),
+    Uint64(Vec<u64>),
+    Float64(Vec<f64>),
+}
+
+impl NumericArray {
+    pub fn with_capacity(len: usize) -> Self {
+        NumericArray::Int64(Vec::with_capacity(len))
+    }
+
+    /// Append `val`, changing the dtype if needed. `false` means no dtype
+    /// can hold it and the elements before it without loss, so the array is
+    /// to be deserialized to a `list`.
+    pub fn push(&mut self, val: Number) -> bool {
+        match (&mut *self, val) {
+            (NumericArray::Int64(vec), Number::Int(val)) => vec.push(val),
+            (NumericArray::Int64(vec), Number::Uint(val)) if val <= i64::MAX as u64 => {
+                vec.push(val as i64)
+            }
+            (NumericArray::Int64(vec), Number::Uint(val)) => {
+                if vec.iter().any(|&each| each < 0) {
+                    return false;
+                }
+                let mut widened: Vec<u64> = vec.iter().map(|&each| each as u64).collect();
+                widened.push(val);
+                *self = NumericArray::Uint64(widened);
+            }
+            (NumericArray::Uint64(vec), Number::Uint(val)) => vec.push(val),
+            (NumericArray::Uint64(vec), Number::Int(val)) if val >= 0 => vec.push(val as u64),
+            (NumericArray::Uint64(_), Number::Int(_)) => return false,
+            (NumericArray::Int64(vec), Number::Float(val)) => {
+                if !vec.iter().all(|&each| each.unsigned_abs() <= MAX_EXACT_INT) {
+                    return false;
+                }
+                let mut widened: Vec<f64> = vec.iter().map(|&each| each as f64).collect();
+                widened.push(val);
+                *self = NumericArray::Float64(widened);
+            }
+            (NumericArray::Uint64(vec), Number::Float(val)) => {
+                if !vec.iter().all(|&each| each <= MAX_EXACT_INT) {
+                    return false;
+                }
+                let mut widened: Vec<f64> = vec.iter().map(|&each| each as f64).collect();
+                widened.push(val);
+                *self = NumericArray::Float64(widened);
+            }
+            (NumericArray::Float64(vec), Number::Float(val)) => vec.push(val),
+            (NumericArray::Float64(vec), Number::Int(val)) => {
+                if val.unsigned_abs() > MAX_EXACT_INT {
+                    return false;
+                }
+                vec.push(val as f64)
+            }
+            (NumericArray::Float64(vec), Number::Uint(val)) => {
+                if val > MAX_EXACT_INT {
+                    return false;
+                }
+                vec.push(val as f64)
+            }
+        }
+        true
+    }
+
+    /// Make a one-dimensional `numpy.ndarray` of the elements, which is
+    /// read-only if `args.immutable`.
+    pub fn into_ndarray(
+        self,
+        args: &LoadsArgs,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let types = match unsafe { NUMPY_TYPES.as_ref() } {
+            Some(types) => types,
+            None => {
+                return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+                    "numpy is not installed",
+                ))))
+            }
+        };
+        let (dtype, len, data) = match &self {
+            NumericArray::Int64(vec) => (types.int64, vec.len(), vec.as_ptr() as *const u8),
+            NumericArray::Uint64(vec) => (types.uint64, vec.len(), vec.as_ptr() as *const u8),
+            NumericArray::Float64(vec) => (types.float64, vec.len(), vec.as_ptr() as *const u8),
+        };
+        let shape = ffi!(PyLong_FromSsize_t(len as isize));
+        let array = unsafe {
+            pyo3_ffi::PyObject_CallFunctionObjArgs(
+                types.array as *mut pyo3_ffi::PyObject,
+                shape,
+                dtype as *mut pyo3_ffi::PyObject,
+                std::ptr::null_mut::<pyo3_ffi::PyObject>(),
+            )
+        };
+        ffi!(Py_DECREF(shape));
+        if array.is_null() {
+            return Err(None);
+        }
+        // the array is new, so its buffer is contiguous and writable
+        let mut view = std::mem::MaybeUninit::<pyo3_ffi::Py_buffer>::uninit();
+        if ffi!(PyObject_GetBuffer(
+            array,
+            view.as_mut_ptr(),
+            pyo3_ffi::PyBUF_CONTIG
+        )) != 0
+        {
+            ffi!(Py_DECREF(array));
+            return Err(None);
+        }
+        let mut view = unsafe { view.assume_init() };
+        unsafe {
+            std::ptr::copy_nonoverlapping(data, view.buf as *mut u8, len * 8);
+            pyo3_ffi::PyBuffer_Release(&mut view);
+        }
+        if args.immutable {
+            let setflags = ffi!(PyObject_GetAttrString(
+                array,
+                "setflags\0".as_ptr() as *const c_char
+            ));
+            let res = if setflags.is_null() {
+                setflags
+            } else {
+                let res = unsafe {
+                    pyo3_ffi::PyObject_CallFunctionObjArgs(
+                        setflags,
+                        FALSE,
+                        std::ptr::null_mut::<pyo3_ffi::PyObject>(),
+                    )
+                };
+                ffi!(Py_DECREF(setflags));
+                res
+            };
+            if res.is_null() {
+                ffi!(Py_DECREF(array));
+                return Err(None);
+            }
+            ffi!(Py_DECREF(res));
+        }
+        Ok(nonnull!(array))
+    }
+}
@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::numpy::NumericArray;
+use crate::deserialize::pyobject::new_array;
+use crate::deserialize::schema::check_schema;
+use crate::deserialize::yyjson::{
+    collect_yy_numbers, nth_child, parse_yy_array_items, unsafe_yyjson_get_len,
+    yyjson_doc_get_root, DocText,
+};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::yyjson::*;
+use std::os::raw::c_char;
+use std::ptr::{null, null_mut, NonNull};
+
+/// Input of each thread parsing part of a top-level array is at least this
+/// long, so that threads are not started for less than they can save.
+pub const PARALLEL_MIN_PART_LEN: usize = 1024 * 1024;
+
+/// A document read by a thread other than the one deserializing it.
+struct Doc(*mut yyjson_doc);
+
+unsafe impl Send for Doc {}
+
+impl Drop for Doc {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { yyjson_doc_free(self.0) }
+        }
+    }
+}
+
+/// Deserialize `data`, a top-level array, by parsing parts of its elements
+/// in up to `args.threads` threads at once and then deserializing the parsed
+/// elements in order. `None` means it is to be deserialized as usual, as it
+/// is not an array long enough to be split or is invalid, so that an error is
+/// reported as it would be otherwise.
+#[cold]
+#[inline(never)]
+pub fn deserialize_parallel(
+    data: &'static str,
+    args: &LoadsArgs,
+    flags: yyjson_read_flag,
+    release_gil: bool,
+) -> Option<Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>>> {
+    let threads = args
+        .threads
+        .unwrap_or(1)
+        .min(data.len() / PARALLEL_MIN_PART_LEN);
+    // comments could hide the commas that separate elements
+    if threads < 2 || args.jsonc || args.max_depth() == 0 {
+        return None;
+    }
+    let tstate = if release_gil {
+        ffi!(PyEval_SaveThread())
+    } else {
+        null_mut()
+    };
+    let parts = split_array(data.as_bytes(), threads);
+    let docs = parts.as_ref().and_then(|parts| read_parts(parts, flags));
+    if release_gil {
+        ffi!(PyEval_RestoreThread(tstate));
+    }
+    let docs = docs?;
+    let parts = parts?;
+    Some(
+        deserialize_parts(data, &parts, &docs, args)
+            .and_then(|obj| check_schema(obj, args, Some(data))),
+    )
+}
+
+/// Make one array of the elements of the arrays in `docs`, read from `parts`
+/// of `data`.
+fn deserialize_parts(
+    data: &'static str,
+    parts: &[&'static [u8]],
+    docs: &[Doc],
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    // the byte of `data` at which each part starts, after the bracket or
+    // comma before it
+    let starts: Vec<usize> = parts
+        .iter()
+        .map(|part| part.as_ptr() as usize - data.as_ptr() as usize)
+        .collect();
+    let roots: Vec<*mut yyjson_val> = docs.iter().map(|doc| yyjson_doc_get_root(doc.0)).collect();
+    let len: usize = roots.iter().map(|&root| unsafe_yyjson_get_len(root)).sum();
+    let left = args.items_left().unwrap_or(0);
+    if let Err(err) = args.add_items(len) {
+        // at the first element past `max_items`, in the part it is in
+        let mut idx = left;
+        for (&root, &part_start) in roots.iter().zip(starts.iter()) {
+            let part_len = unsafe_yyjson_get_len(root);
+            if idx < part_len {
+                let text = DocText::elements(data, root, part_start);
+                return Err(Some(text.locate(err, nth_child(root, idx))));
+            }
+            idx -= part_len;
+        }
+        return Err(Some(DeserializeError::from_yyjson(
+            err.message,
+            starts[0] as i64 - 1,
+            data,
+        )));
+    }
+    if unlikely!(args.numpy) && len > 0 {
+        let mut numbers = NumericArray::with_capacity(len);
+        let mut numeric = true;
+        for &root in roots.iter() {
+            if !collect_yy_numbers(root, &mut numbers, args)? {
+                numeric = false;
+                break;
+            }
+        }
+        if numeric {
+            return numbers.into_ndarray(args);
+        }
+    }
+    let list = new_array(len, args);
+    let mut start = 0;
+    for (root, part_start) in roots.into_iter().zip(starts) {
+        let text = DocText::elements(data, root, part_start);
+        if let Err(err) = parse_yy_array_items(root, list, start, args, args.max_depth(), text) {
+            ffi!(Py_DECREF(list));
+            return Err(err);
+        }
+        start += unsafe_yyjson_get_len(root);
+    }
+    Ok(nonnull!(list))
+}
+
+/// Parse each of `parts`, the elements of an array as written, as an array,
+/// the last in the calling thread. `None` means a part is invalid or a thread
+/// could not be started.
+fn read_parts(parts: &[&'static [u8]], flags: yyjson_read_flag) -> Option<Vec<Doc>> {
+    let (last, rest) = parts.split_last()?;
+    let mut handles = Vec::with_capacity(rest.len());
+    for &part in rest {
+        let handle = std::thread::Builder::new()
+            .name("orjson-loads".to_string())
+            .spawn(move || read_part(part, flags));
+        match handle {
+            Ok(handle) => handles.push(handle),
+            Err(_) => break,
+        }
+    }
+    let last = read_part(last, flags);
+    let mut docs = Vec::with_capacity(parts.len());
+    let started = handles.len() == rest.len();
+    for handle in handles {
+        docs.push(handle.join().unwrap_or(Doc(null_mut())));
+    }
+    docs.push(last);
+    if !started || docs.iter().any(|doc| doc.0.is_null()) {
+        return None;
+    }
+    Some(docs)
+}
+
+/// Parse `part`, elements of an array as written, as an array.
+fn read_part(part: &[u8], flags: yyjson_read_flag) -> Doc {
+    let mut buffer: Vec<u8> = Vec::with_capacity(part.len() + 2);
+    buffer.push(b'[');
+    buffer.extend_from_slice(part);
+    buffer.push(b']');
+    let mut err = yyjson_read_err {
+        code: YYJSON_READ_SUCCESS,
+        msg: null(),
+        pos: 0,
+    };
+    Doc(unsafe {
+        yyjson_read_opts(
+            buffer.as_mut_ptr() as *mut c_char,
+            buffer.len(),
+            flags,
+            null_mut(),
+            &mut err,
+        )
+    })
+}
+
+/// Split the elements of the top-level array in `data` into `parts` of about
+/// the same length at the commas between elements. Strings are skipped so
+/// that brackets and commas in them are not counted. Anything else invalid is
+/// left to be reported when the parts are parsed. `None` means `data` is not
+/// an array that can be split, so it must be parsed whole.
+fn split_array(data: &'static [u8], parts: usize) -> Option<Vec<&'static [u8]>> {
+    let is_whitespace = |c: &u8| matches!(c, b' ' | b'\t' | b'\n' | b'\r');
+    let mut idx = data.iter().position(|c| !is_whitespace(c))?;
+    if data[idx] != b'[' {
+        return None;
+    }
+    idx += 1;
+    let step = (data.len() - idx) / parts;
+    let mut split: Vec<&'static [u8]> = Vec::with_capacity(parts);
+    let mut start = idx;
+    let mut depth: usize = 0;
+    let mut in_string = false;
+    let mut closed = false;
+    while idx < data.len() {
+        let c = data[idx];
+        if in_string {
+            if c == b'\\' {
+                idx += 1;
+            } else if c == b'"' {
+                in_string = false;
+            }
+        } else {
+            match c {
+                b'"' => in_string = true,
+                b'[' | b'{' => depth += 1,
+                b']' if depth == 0 => {
+                    split.push(&data[start..idx]);
+                    if !data[idx + 1..].iter().all(is_whitespace) {
+                        return None;
+                    }
+                    closed = true;
+                    break;
+                }
+                b']' | b'}' => depth = depth.checked_sub(1)?,
+                b',' if depth == 0 && idx - start >= step && split.len() + 1 < parts => {
+                    split.push(&data[start..idx]);
+                    start = idx + 1;
+                }
+                _ => {}
+            }
+        }
+        idx += 1;
+    }
+    // a part of only whitespace would be parsed as an empty array
+    if !closed || split.len() < 2 || split.iter().any(|part| part.iter().all(is_whitespace)) {
+        return None;
+    }
+    Some(split)
+}
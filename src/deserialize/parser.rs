@@ -0,0 +1,536 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::decoder::{hooks, parse_kwargs};
+use crate::deserialize::deserializer::{deserialize_bytes, ReadLimit};
+use crate::deserialize::error::*;
+use crate::deserialize::tokenize::{is_number, State};
+use crate::deserialize::utf8::{is_buffer_input, read_input_bytes, BufferInput};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use std::borrow::Cow;
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr::null_mut;
+
+/// `orjson.Parser`, which is fed input in parts, e.g., as it is received, and
+/// deserializes each document of it once the document has ended.
+#[repr(C)]
+pub struct Parser {
+    ob_base: pyo3_ffi::PyObject,
+    // the hooks are owned references
+    args: LoadsArgs,
+    // set while the input is deserialized, when a hook must not feed or close
+    // the parser
+    busy: bool,
+    // `None` once closed or after an error, which cannot be resumed from
+    state: Option<ParserState>,
+    // an error after documents that `feed()` returned, raised by the next
+    // call
+    error: Option<PendingError>,
+}
+
+/// An error after documents that `feed()` returned.
+enum PendingError {
+    Invalid(DeserializeError<'static>),
+    // the exception a hook raised, as `PyErr_Fetch()` takes it
+    Raised(
+        *mut pyo3_ffi::PyObject,
+        *mut pyo3_ffi::PyObject,
+        *mut pyo3_ffi::PyObject,
+    ),
+}
+
+impl PendingError {
+    /// Take the exception that is set, e.g., by a hook.
+    fn fetch() -> Self {
+        let mut ptype = null_mut();
+        let mut pvalue = null_mut();
+        let mut ptraceback = null_mut();
+        ffi!(PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback));
+        PendingError::Raised(ptype, pvalue, ptraceback)
+    }
+
+    /// Raise the error, returning null.
+    fn raise(self) -> *mut pyo3_ffi::PyObject {
+        match self {
+            PendingError::Invalid(err) => crate::raise_loads_exception(err),
+            PendingError::Raised(ptype, pvalue, ptraceback) => {
+                ffi!(PyErr_Restore(ptype, pvalue, ptraceback));
+                null_mut()
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ParserState {
+    // the input fed that has not been deserialized, starting with the
+    // document that has not ended, if any
+    buffer: Vec<u8>,
+    document: PartialDocument,
+    // the position in all of the input fed of the start of `buffer`
+    location: Location,
+}
+
+/// Finds where a document fed in parts ends, as `DocumentBoundary` does, and
+/// checks its syntax as it is fed, so that an error is raised by the call of
+/// `feed()` that supplied it rather than once the document has ended. Only
+/// what no more input could make valid is an error here, and what depends on
+/// the whole of a token, e.g., its UTF-8 or the code point of an escape, is
+/// left to deserializing the document.
+struct PartialDocument {
+    // the number of bytes of the document scanned, or 0 if it has not started
+    scanned: usize,
+    state: State,
+    // `[` or `{` for each container the next token is in
+    stack: Vec<u8>,
+    // within a string, and whether it is a key
+    string: Option<bool>,
+    // within an escape of a string, the bytes of it left to check, of which
+    // the hexadecimal digits of `\u` are the last 4, and its index
+    escape: u8,
+    escape_start: usize,
+    // the number or other literal being scanned, and its index
+    literal: Vec<u8>,
+    literal_start: usize,
+}
+
+impl Default for PartialDocument {
+    fn default() -> Self {
+        PartialDocument {
+            scanned: 0,
+            state: State::Value,
+            stack: Vec::new(),
+            string: None,
+            escape: 0,
+            escape_start: 0,
+            literal: Vec::new(),
+            literal_start: 0,
+        }
+    }
+}
+
+impl PartialDocument {
+    /// Scan `buf`, the next part of the document, which starts with a byte
+    /// that is not whitespace, and return the index at which the document
+    /// ends, if it does in `buf`, or else the index in the document of a
+    /// syntax error and its message. Once it has ended, `self` must not be
+    /// used again.
+    fn scan(
+        &mut self,
+        buf: &[u8],
+        args: &LoadsArgs,
+    ) -> Result<Option<usize>, (usize, &'static str)> {
+        for (idx, &c) in buf.iter().enumerate() {
+            let pos = self.scanned + idx;
+            if let Some(key) = self.string {
+                match c {
+                    _ if self.escape == 5 => match c {
+                        b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => self.escape = 0,
+                        b'u' => self.escape = 4,
+                        _ => return Err((pos, INVALID_ESCAPE)),
+                    },
+                    _ if self.escape > 0 => {
+                        if !c.is_ascii_hexdigit() {
+                            return Err((self.escape_start, INVALID_UNICODE_ESCAPE));
+                        }
+                        self.escape -= 1;
+                    }
+                    b'\\' => {
+                        self.escape = 5;
+                        self.escape_start = pos;
+                    }
+                    b'"' => {
+                        self.string = None;
+                        if key {
+                            self.state = State::Colon;
+                        } else if self.after_value() {
+                            return Ok(Some(idx + 1));
+                        }
+                    }
+                    0..=0x1f => return Err((pos, CONTROL_CHARACTER)),
+                    _ => {}
+                }
+                continue;
+            }
+            if !self.literal.is_empty() {
+                if c.is_ascii_alphanumeric() || matches!(c, b'+' | b'-' | b'.') {
+                    self.literal.push(c);
+                    continue;
+                }
+                self.check_literal(true, args.allow_nan)?;
+                self.literal.clear();
+                // the byte after a document that is a literal is not of it
+                if self.after_value() {
+                    return Ok(Some(idx));
+                }
+            }
+            let top = self.stack.last().copied();
+            match (self.state, c) {
+                (_, b' ' | b'\t' | b'\n' | b'\r') => {}
+                (State::Colon, b':') => self.state = State::Value,
+                (State::CommaOrEnd, b',') => {
+                    self.state = if top == Some(b'{') {
+                        State::Key
+                    } else {
+                        State::Value
+                    };
+                }
+                (State::CommaOrEnd | State::ValueOrEnd, b']') if top == Some(b'[') => {
+                    if self.end() {
+                        return Ok(Some(idx + 1));
+                    }
+                }
+                (State::CommaOrEnd | State::KeyOrEnd, b'}') if top == Some(b'{') => {
+                    if self.end() {
+                        return Ok(Some(idx + 1));
+                    }
+                }
+                (State::KeyOrEnd | State::Key, b'"') => self.string = Some(true),
+                (State::Value | State::ValueOrEnd, b'{' | b'[') => {
+                    if self.stack.len() == args.max_depth() as usize {
+                        return Err((pos, RECURSION_LIMIT));
+                    }
+                    self.stack.push(c);
+                    self.state = if c == b'{' {
+                        State::KeyOrEnd
+                    } else {
+                        State::ValueOrEnd
+                    };
+                }
+                (State::Value | State::ValueOrEnd, b'"') => self.string = Some(false),
+                (State::Value | State::ValueOrEnd, b'-' | b'0'..=b'9' | b't' | b'f' | b'n') => {
+                    self.literal.push(c);
+                    self.literal_start = pos;
+                }
+                (State::Value | State::ValueOrEnd, b'N' | b'I') if args.allow_nan => {
+                    self.literal.push(c);
+                    self.literal_start = pos;
+                }
+                (State::Value, 0xef) if pos == 0 => return Err((pos, BYTE_ORDER_MARK)),
+                _ => return Err((pos, UNEXPECTED_CHARACTER)),
+            }
+        }
+        if !self.literal.is_empty() {
+            self.check_literal(false, args.allow_nan)?;
+        }
+        self.scanned += buf.len();
+        Ok(None)
+    }
+
+    /// Check `self.literal`, which is the whole of the literal if `ended`, or
+    /// else the start of it.
+    fn check_literal(&self, ended: bool, allow_nan: bool) -> Result<(), (usize, &'static str)> {
+        let literal = &self.literal[..];
+        let words: &[&[u8]] = if allow_nan {
+            &[
+                b"true",
+                b"false",
+                b"null",
+                b"NaN",
+                b"Infinity",
+                b"-Infinity",
+            ]
+        } else {
+            &[b"true", b"false", b"null"]
+        };
+        let valid = if ended {
+            words.contains(&literal) || is_number(literal)
+        } else {
+            // a digit completes the start of any number
+            words.iter().any(|word| word.starts_with(literal))
+                || is_number(literal)
+                || is_number(&[literal, b"0"].concat())
+        };
+        match literal[0] {
+            _ if valid => Ok(()),
+            b'-' | b'0'..=b'9' => Err((self.literal_start, INVALID_NUMBER)),
+            _ => Err((self.literal_start, INVALID_LITERAL)),
+        }
+    }
+
+    /// Set the state after a value, or the end of a container, and return
+    /// whether it is the end of the document.
+    fn after_value(&mut self) -> bool {
+        if self.stack.is_empty() {
+            self.state = State::Done;
+            return true;
+        }
+        self.state = State::CommaOrEnd;
+        false
+    }
+
+    fn end(&mut self) -> bool {
+        self.stack.pop();
+        self.after_value()
+    }
+}
+
+impl ParserState {
+    /// Deserialize each document of `self.buffer` that has ended, appending
+    /// it to `list`, and remove them and the whitespace after them.
+    fn deserialize_ended(
+        &mut self,
+        args: &LoadsArgs,
+        list: *mut pyo3_ffi::PyObject,
+    ) -> Result<(), Option<DeserializeError<'static>>> {
+        let mut start = 0;
+        // the position of the byte of `self.buffer` at `advanced`
+        let mut at = self.location;
+        let mut advanced = 0;
+        loop {
+            if self.document.scanned == 0 {
+                match self.buffer[start..]
+                    .iter()
+                    .position(|&c| !matches!(c, b' ' | b'\t' | b'\n' | b'\r'))
+                {
+                    Some(idx) => start += idx,
+                    None => {
+                        start = self.buffer.len();
+                        break;
+                    }
+                }
+            }
+            let scanned = start + self.document.scanned;
+            match self.document.scan(&self.buffer[scanned..], args) {
+                Ok(Some(idx)) => {
+                    let end = scanned + idx;
+                    at.advance(&self.buffer[advanced..start]);
+                    advanced = start;
+                    // per document, so that `max_items` applies to each
+                    let val =
+                        deserialize_bytes(&self.buffer[start..end], &args.clone_options(), false)
+                            .map_err(|err| err.map(|err| err.within(at)))?;
+                    ffi!(PyList_Append(list, val.as_ptr()));
+                    ffi!(Py_DECREF(val.as_ptr()));
+                    start = end;
+                    self.document = PartialDocument::default();
+                }
+                Ok(None) => break,
+                Err((pos, message)) => {
+                    at.advance(&self.buffer[advanced..start + pos]);
+                    return Err(Some(DeserializeError::at_location(
+                        Cow::Borrowed(message),
+                        at,
+                    )));
+                }
+            }
+        }
+        at.advance(&self.buffer[advanced..start]);
+        self.location = at;
+        self.buffer.drain(..start);
+        // a document that has not ended cannot be smaller than it is so far
+        ReadLimit::starting_at(args.max_bytes, self.location).add(&self.buffer)?;
+        Ok(())
+    }
+}
+
+unsafe extern "C" fn parser_new(
+    subtype: *mut pyo3_ffi::PyTypeObject,
+    args: *mut pyo3_ffi::PyObject,
+    kwds: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    if pyo3_ffi::PyTuple_GET_SIZE(args) != 0 {
+        return crate::raise_loads_exception(DeserializeError::invalid(Cow::Borrowed(
+            "Parser() takes no positional arguments",
+        )));
+    }
+    // a comment could hide where a document ends
+    let loads_args = match parse_kwargs(kwds, &[OFFSET, LENGTH, JSONC], "Parser") {
+        Ok(loads_args) => loads_args,
+        Err(err) => return err,
+    };
+    let obj = pyo3_ffi::PyType_GenericAlloc(subtype, 0);
+    if obj.is_null() {
+        return null_mut();
+    }
+    for func in hooks(&loads_args).iter().flatten() {
+        pyo3_ffi::Py_INCREF(func.as_ptr());
+    }
+    let parser = obj as *mut Parser;
+    std::ptr::write(std::ptr::addr_of_mut!((*parser).args), loads_args);
+    std::ptr::write(std::ptr::addr_of_mut!((*parser).busy), false);
+    std::ptr::write(
+        std::ptr::addr_of_mut!((*parser).state),
+        Some(ParserState::default()),
+    );
+    std::ptr::write(std::ptr::addr_of_mut!((*parser).error), None);
+    obj
+}
+
+/// Take the state of `parser` to deserialize its input, or raise
+/// `JSONDecodeError` if it is closed or deserializing, or the error after the
+/// documents the last call returned.
+unsafe fn take_state(parser: *mut Parser) -> Result<ParserState, *mut pyo3_ffi::PyObject> {
+    if (*parser).busy {
+        return Err(crate::raise_loads_exception(DeserializeError::invalid(
+            Cow::Borrowed("Parser cannot be fed or closed by one of its own hooks"),
+        )));
+    }
+    if let Some(err) = (*parser).error.take() {
+        return Err(err.raise());
+    }
+    match (*parser).state.take() {
+        Some(state) => Ok(state),
+        None => Err(crate::raise_loads_exception(DeserializeError::invalid(
+            Cow::Borrowed("Parser is closed"),
+        ))),
+    }
+}
+
+/// Finish a call of `feed()` or `close()` that deserialized `res`, returning
+/// `list` or raising the error.
+unsafe fn finish(
+    list: *mut pyo3_ffi::PyObject,
+    res: Result<(), Option<DeserializeError<'static>>>,
+) -> *mut pyo3_ffi::PyObject {
+    match res {
+        Ok(()) => list,
+        Err(err) => {
+            pyo3_ffi::Py_DECREF(list);
+            match err {
+                Some(err) => crate::raise_loads_exception(err),
+                None => null_mut(),
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn parser_feed(
+    slf: *mut pyo3_ffi::PyObject,
+    data: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    let parser = slf as *mut Parser;
+    let mut state = match take_state(parser) {
+        Ok(state) => state,
+        Err(err) => return err,
+    };
+    let read = if is_buffer_input(data) {
+        BufferInput::new(data).map(|input| state.buffer.extend_from_slice(input.as_bytes()))
+    } else {
+        read_input_bytes(data).map(|input| state.buffer.extend_from_slice(input))
+    };
+    if let Err(err) = read {
+        // the input is rejected before any of it is fed
+        (*parser).state = Some(state);
+        return match err {
+            Some(err) => crate::raise_loads_exception(err),
+            None => null_mut(),
+        };
+    }
+    let list = pyo3_ffi::PyList_New(0);
+    (*parser).busy = true;
+    let res = state.deserialize_ended(&(*parser).args, list);
+    (*parser).busy = false;
+    match res {
+        Ok(()) => (*parser).state = Some(state),
+        // the documents before the invalid one, or the one a hook raised for,
+        // are returned, and it raises when the parser is next fed or closed
+        Err(err) if pyo3_ffi::PyList_GET_SIZE(list) != 0 => {
+            (*parser).error = Some(match err {
+                Some(err) => PendingError::Invalid(err),
+                None => PendingError::fetch(),
+            });
+            return list;
+        }
+        Err(_) => (),
+    }
+    finish(list, res)
+}
+
+unsafe extern "C" fn parser_close(
+    slf: *mut pyo3_ffi::PyObject,
+    _unused: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    let parser = slf as *mut Parser;
+    let state = match take_state(parser) {
+        Ok(state) => state,
+        Err(err) => return err,
+    };
+    let list = pyo3_ffi::PyList_New(0);
+    let mut res = Ok(());
+    // a document that has not ended, e.g., a number, ends with the input, or
+    // is incomplete and raises
+    if !state.buffer.is_empty() {
+        (*parser).busy = true;
+        res = deserialize_bytes(&state.buffer, &(*parser).args.clone_options(), false)
+            .map(|val| {
+                ffi!(PyList_Append(list, val.as_ptr()));
+                ffi!(Py_DECREF(val.as_ptr()));
+            })
+            .map_err(|err| err.map(|err| err.within(state.location)));
+        (*parser).busy = false;
+    }
+    finish(list, res)
+}
+
+unsafe extern "C" fn parser_dealloc(slf: *mut pyo3_ffi::PyObject) {
+    let parser = slf as *mut Parser;
+    for func in hooks(&(*parser).args).iter().flatten() {
+        pyo3_ffi::Py_DECREF(func.as_ptr());
+    }
+    std::ptr::drop_in_place(std::ptr::addr_of_mut!((*parser).args));
+    std::ptr::drop_in_place(std::ptr::addr_of_mut!((*parser).state));
+    if let Some(PendingError::Raised(ptype, pvalue, ptraceback)) = (*parser).error.take() {
+        pyo3_ffi::Py_XDECREF(ptype);
+        pyo3_ffi::Py_XDECREF(pvalue);
+        pyo3_ffi::Py_XDECREF(ptraceback);
+    }
+    std::ptr::drop_in_place(std::ptr::addr_of_mut!((*parser).error));
+    let tp = ob_type!(slf);
+    ((*tp).tp_free.unwrap())(slf as *mut c_void);
+    pyo3_ffi::Py_DECREF(tp as *mut pyo3_ffi::PyObject);
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub unsafe fn create_parser_type() -> *mut pyo3_ffi::PyTypeObject {
+    let methods: Box<[pyo3_ffi::PyMethodDef; 3]> = Box::new([
+        pyo3_ffi::PyMethodDef {
+            ml_name: "feed\0".as_ptr() as *const c_char,
+            ml_meth: pyo3_ffi::PyMethodDefPointer {
+                PyCFunction: parser_feed,
+            },
+            ml_flags: pyo3_ffi::METH_O,
+            ml_doc: "feed(data, /)\n--\n\nFeed the next part of the input and return a list of the documents that have ended.\0".as_ptr() as *const c_char,
+        },
+        pyo3_ffi::PyMethodDef {
+            ml_name: "close\0".as_ptr() as *const c_char,
+            ml_meth: pyo3_ffi::PyMethodDefPointer {
+                PyCFunction: parser_close,
+            },
+            ml_flags: pyo3_ffi::METH_NOARGS,
+            ml_doc: "close()\n--\n\nEnd the input and return a list of the document that ends with it, if any.\0".as_ptr() as *const c_char,
+        },
+        std::mem::zeroed(),
+    ]);
+    let slots: Box<[pyo3_ffi::PyType_Slot; 5]> = Box::new([
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_new,
+            pfunc: parser_new as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_methods,
+            pfunc: Box::into_raw(methods) as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_doc,
+            pfunc: "Parser(*, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, surrogates=None, replace_invalid_utf8=False, allow_nan=False, strict=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, numpy=False, raw_numbers=False, threads=None, schema=None)\n--\n\nDeserialize a sequence of JSON documents fed in parts, as each ends.\0".as_ptr() as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_dealloc,
+            pfunc: parser_dealloc as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: 0,
+            pfunc: std::ptr::null_mut(),
+        },
+    ]);
+    let spec = Box::new(pyo3_ffi::PyType_Spec {
+        name: "orjson.Parser\0".as_ptr() as *const c_char,
+        basicsize: std::mem::size_of::<Parser>() as c_int,
+        itemsize: 0,
+        flags: pyo3_ffi::Py_TPFLAGS_DEFAULT as c_uint,
+        slots: Box::into_raw(slots) as *mut pyo3_ffi::PyType_Slot,
+    });
+    pyo3_ffi::PyType_FromSpec(Box::into_raw(spec)) as *mut pyo3_ffi::PyTypeObject
+}
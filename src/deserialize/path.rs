@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::DeserializeError;
+use std::borrow::Cow;
+
+pub enum Segment {
+    Index(usize),
+    Key(String),
+}
+
+/// An error about a value in a document and its location. `path` is the
+/// location of the value, innermost first, as it is recorded returning from
+/// nested values.
+pub struct PathError {
+    pub message: String,
+    pub path: Vec<Segment>,
+}
+
+impl PathError {
+    #[cold]
+    pub fn new(message: String) -> Self {
+        PathError {
+            message: message,
+            path: Vec::new(),
+        }
+    }
+
+    #[cold]
+    pub fn at_index(mut self, idx: usize) -> Self {
+        self.path.push(Segment::Index(idx));
+        self
+    }
+
+    #[cold]
+    pub fn at_key(mut self, key: &str) -> Self {
+        self.path.push(Segment::Key(String::from(key)));
+        self
+    }
+
+    /// The message with the location of the value as a JSON pointer, e.g.,
+    /// `"/points/0/x"`, if it is not the document.
+    #[cold]
+    pub fn into_error(self) -> DeserializeError<'static> {
+        let mut message = self.message;
+        if !self.path.is_empty() {
+            message.push_str(" at \"");
+            for segment in self.path.iter().rev() {
+                message.push('/');
+                match segment {
+                    Segment::Index(idx) => message.push_str(&idx.to_string()),
+                    Segment::Key(key) => {
+                        message.push_str(&key.replace('~', "~0").replace('/', "~1"))
+                    }
+                }
+            }
+            message.push('"');
+        }
+        DeserializeError::invalid(Cow::Owned(message))
+    }
+
+    /// As `into_error()`, but at the start of the value in `data`, the valid
+    /// document it was deserialized from. Of an object's duplicate keys, the
+    /// value of the first is taken if `first_key` and of the last otherwise,
+    /// as deserializing keeps it.
+    #[cold]
+    pub fn into_error_in(self, data: &'static str, first_key: bool) -> DeserializeError<'static> {
+        let pos = value_at(data.as_bytes(), &self.path, first_key);
+        let err = self.into_error();
+        match pos {
+            Some(pos) => DeserializeError::at_byte(err.message, pos, data),
+            None => err,
+        }
+    }
+}
+
+/// The byte of `data`, a valid document, at which the value at `path`,
+/// innermost first, starts, found by scanning it rather than parsing it.
+fn value_at(data: &[u8], path: &[Segment], first_key: bool) -> Option<usize> {
+    let mut pos = skip_whitespace(data, 0);
+    for segment in path.iter().rev() {
+        let mut found = None;
+        match (data.get(pos)?, segment) {
+            (b'[', Segment::Index(idx)) => {
+                pos = skip_whitespace(data, pos + 1);
+                for _ in 0..*idx {
+                    pos = skip_whitespace(data, skip_value(data, pos)?);
+                    if data.get(pos) != Some(&b',') {
+                        return None;
+                    }
+                    pos = skip_whitespace(data, pos + 1);
+                }
+                if !matches!(data.get(pos), Some(b']') | None) {
+                    found = Some(pos);
+                }
+            }
+            (b'{', Segment::Key(key)) => {
+                pos += 1;
+                loop {
+                    pos = skip_whitespace(data, pos);
+                    if data.get(pos) != Some(&b'"') {
+                        break;
+                    }
+                    let end = skip_value(data, pos)?;
+                    let matches = key_equals(&data[pos..end], key);
+                    pos = skip_whitespace(data, end);
+                    pos = skip_whitespace(data, pos + 1);
+                    if matches {
+                        found = Some(pos);
+                        if first_key {
+                            break;
+                        }
+                    }
+                    pos = skip_whitespace(data, skip_value(data, pos)?);
+                    if data.get(pos) != Some(&b',') {
+                        break;
+                    }
+                    pos += 1;
+                }
+            }
+            _ => return None,
+        }
+        pos = found?;
+    }
+    Some(pos)
+}
+
+/// Whether the string `literal`, with its quotes, is `key`.
+fn key_equals(literal: &[u8], key: &str) -> bool {
+    if !literal.contains(&b'\\') {
+        return &literal[1..literal.len() - 1] == key.as_bytes();
+    }
+    serde_json::from_slice::<String>(literal).map_or(false, |literal| literal == key)
+}
+
+/// The byte of `data` from `pos` that is not whitespace or in a comment.
+fn skip_whitespace(data: &[u8], mut pos: usize) -> usize {
+    while let Some(&c) = data.get(pos) {
+        match c {
+            b' ' | b'\t' | b'\n' | b'\r' => pos += 1,
+            b'/' => pos = skip_comment(data, pos),
+            _ => break,
+        }
+    }
+    pos
+}
+
+/// The byte of `data` after the comment starting at `pos`.
+fn skip_comment(data: &[u8], pos: usize) -> usize {
+    let rest = &data[pos..];
+    let len = if rest.starts_with(b"/*") {
+        rest.windows(2)
+            .skip(2)
+            .position(|pair| pair == b"*/")
+            .map_or(rest.len(), |idx| idx + 4)
+    } else {
+        rest.iter().position(|&c| c == b'\n').unwrap_or(rest.len())
+    };
+    pos + len.max(1)
+}
+
+/// The byte of `data` after the value starting at `pos`. Brackets are
+/// matched and strings and comments skipped, as `data` is valid.
+fn skip_value(data: &[u8], mut pos: usize) -> Option<usize> {
+    let mut depth: usize = 0;
+    loop {
+        match *data.get(pos)? {
+            b'"' => {
+                pos += 1;
+                loop {
+                    match *data.get(pos)? {
+                        b'"' => break,
+                        b'\\' => pos += 2,
+                        _ => pos += 1,
+                    }
+                }
+            }
+            b'/' if depth > 0 => {
+                pos = skip_comment(data, pos);
+                continue;
+            }
+            b'[' | b'{' => depth += 1,
+            b']' | b'}' => depth -= 1,
+            _ if depth == 0 => {
+                while let Some(&c) = data.get(pos + 1) {
+                    if matches!(
+                        c,
+                        b',' | b']' | b'}' | b':' | b'/' | b' ' | b'\t' | b'\n' | b'\r'
+                    ) {
+                        break;
+                    }
+                    pos += 1;
+                }
+            }
+            _ => {}
+        }
+        pos += 1;
+        if depth == 0 {
+            return Some(pos);
+        }
+    }
+}
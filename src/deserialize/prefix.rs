@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::utf8::{read_input_to_buf, BufferInput};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use std::borrow::Cow;
+use std::ptr::NonNull;
+
+/// Deserialize the first value of `ptr`, which may be followed by anything,
+/// e.g., another document or binary data, and return a `tuple` of it and the
+/// index of `ptr` at which it ends: in characters for a `str` and in bytes
+/// otherwise, counted from the start of the input rather than `offset`.
+pub fn loads_prefix(
+    ptr: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let (obj, end) = if is_type!(ob_type!(ptr), STR_TYPE) {
+        if args.offset.is_some() || args.length.is_some() {
+            return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+                "offset and length cannot be used with str",
+            ))));
+        }
+        let buffer = read_input_to_buf(ptr)?;
+        let (obj, end) = deserialize_prefix(buffer, args)?;
+        (obj, bytecount::num_chars(&buffer[..end]))
+    } else {
+        let input = BufferInput::new(ptr)?;
+        let buffer = args.slice(input.as_bytes())?;
+        let (obj, end) = deserialize_prefix(buffer, args)
+            .map_err(|err| err.map(DeserializeError::into_owned))?;
+        (obj, args.offset.unwrap_or(0) + end)
+    };
+    let tuple = ffi!(PyTuple_New(2));
+    ffi!(PyTuple_SET_ITEM(tuple, 0, obj.as_ptr()));
+    ffi!(PyTuple_SET_ITEM(
+        tuple,
+        1,
+        ffi!(PyLong_FromUnsignedLongLong(end as u64))
+    ));
+    Ok(nonnull!(tuple))
+}
+
+/// Deserialize the value at the start of `buffer` and return it with the
+/// number of bytes it and the whitespace before it take. Only they must be
+/// valid UTF-8. `buffer` is of a `str` or an exported buffer, which another
+/// thread cannot resize, so the GIL is released while it is parsed.
+fn deserialize_prefix(
+    buffer: &'static [u8],
+    args: &LoadsArgs,
+) -> Result<(NonNull<pyo3_ffi::PyObject>, usize), Option<DeserializeError<'static>>> {
+    args.check_bytes(buffer)?;
+    // a value ends before anything that is not UTF-8, so what follows it is
+    // not parsed
+    let len = match std::str::from_utf8(buffer) {
+        Ok(_) => buffer.len(),
+        Err(err) => err.valid_up_to(),
+    };
+    let buffer_str = str_from_slice!(buffer.as_ptr(), len);
+
+    #[cfg(feature = "yyjson")]
+    let ret = crate::deserialize::yyjson::deserialize_yyjson_prefix(buffer_str, args, true);
+
+    #[cfg(not(feature = "yyjson"))]
+    let ret = crate::deserialize::json::deserialize_json_prefix(buffer_str, args);
+
+    match ret {
+        // the value did not end before what is not UTF-8
+        Err(Some(err))
+            if len < buffer.len()
+                && err.pos() == bytecount::num_chars(buffer_str.as_bytes()) as i64 =>
+        {
+            Err(Some(DeserializeError::at_byte(
+                Cow::Borrowed(crate::error::INVALID_STR),
+                len,
+                buffer_str,
+            )))
+        }
+        ret => ret,
+    }
+}
@@ -1,16 +1,39 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::deserialize::cache::*;
+use crate::deserialize::datetime::parse_datetime_like;
+use crate::deserialize::error::{INVALID_LITERAL, NUMBER_INFINITY};
+use crate::deserialize::raw_number::new_raw_number;
+use crate::deserialize::{DeserializeError, DuplicateKeys, LoadsArgs, Surrogates};
 use crate::typeref::*;
 use crate::unicode::*;
+use std::borrow::Cow;
 use std::ptr::NonNull;
 
-pub fn get_unicode_key(key_str: &str) -> (*mut pyo3_ffi::PyObject, pyo3_ffi::Py_hash_t) {
+/// Make a `str` of `val`, which has lone surrogates that are not valid UTF-8
+/// if they are kept with `Surrogates::Pass`.
+#[inline(always)]
+fn new_str(val: &str, args: &LoadsArgs) -> *mut pyo3_ffi::PyObject {
+    if unlikely!(args.surrogates == Surrogates::Pass) && has_surrogate(val.as_bytes()) {
+        unicode_from_wtf8(val.as_bytes())
+    } else {
+        unicode_from_str(val)
+    }
+}
+
+pub fn get_unicode_key(
+    key_str: &str,
+    args: &LoadsArgs,
+) -> (*mut pyo3_ffi::PyObject, pyo3_ffi::Py_hash_t) {
     let pykey: *mut pyo3_ffi::PyObject;
     let pyhash: pyo3_ffi::Py_hash_t;
-    if unlikely!(key_str.len() > 64) {
-        pykey = unicode_from_str(&key_str);
-        pyhash = hash_str(pykey);
+    if unlikely!(key_str.len() > KEY_MAP_MAX_LEN) {
+        pykey = args.long_keys.borrow_mut().get_or_insert(key_str, || {
+            let pyob = new_str(key_str, args);
+            hash_str(pyob);
+            pyob
+        });
+        pyhash = unsafe { (*pykey.cast::<PyASCIIObject>()).hash }
     } else {
         let hash = cache_hash(key_str.as_bytes());
         let map = unsafe {
@@ -21,7 +44,7 @@ pub fn get_unicode_key(key_str: &str) -> (*mut pyo3_ffi::PyObject, pyo3_ffi::Py_
         let entry = map.entry(&hash).or_insert_with(
             || hash,
             || {
-                let pyob = unicode_from_str(&key_str);
+                let pyob = new_str(key_str, args);
                 hash_str(pyob);
                 CachedKey::new(pyob)
             },
@@ -32,6 +55,56 @@ pub fn get_unicode_key(key_str: &str) -> (*mut pyo3_ffi::PyObject, pyo3_ffi::Py_
     (pykey, pyhash)
 }
 
+/// Deserialize the string `val`, which is not a key, to a `str`, or if
+/// `args.datetimes` and it is an RFC 3339 date, time, or date and time, to a
+/// `datetime.date`, `datetime.time`, or `datetime.datetime`, or if
+/// `args.uuids` and it is a UUID, to a `uuid.UUID`. An error of `None` means
+/// an exception was raised.
+#[inline(always)]
+pub fn parse_str(
+    val: &str,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if unlikely!(args.datetimes) {
+        if let Some(obj) = parse_datetime_like(val) {
+            return NonNull::new(obj).ok_or(None);
+        }
+    }
+    if unlikely!(args.uuids) {
+        if let Some(bytes) = uuid_from_str(val) {
+            return parse_uuid(&bytes);
+        }
+    }
+    Ok(nonnull!(new_str(val, args)))
+}
+
+/// Return a new `list`, or `tuple` if `args.tuples`, of `len` items to be set
+/// by `set_array_item()`.
+#[inline(always)]
+pub fn new_array(len: usize, args: &LoadsArgs) -> *mut pyo3_ffi::PyObject {
+    if unlikely!(args.tuples) {
+        ffi!(PyTuple_New(len as isize))
+    } else {
+        ffi!(PyList_New(len as isize))
+    }
+}
+
+/// Set item `idx` of an array returned by `new_array()` to `item`, which is
+/// consumed.
+#[inline(always)]
+pub fn set_array_item(
+    array: *mut pyo3_ffi::PyObject,
+    idx: usize,
+    item: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+) {
+    if unlikely!(args.tuples) {
+        ffi!(PyTuple_SET_ITEM(array, idx as isize, item))
+    } else {
+        ffi!(PyList_SET_ITEM(array, idx as isize, item))
+    }
+}
+
 #[allow(dead_code)]
 #[inline(always)]
 pub fn parse_bool(val: bool) -> NonNull<pyo3_ffi::PyObject> {
@@ -73,3 +146,386 @@ pub fn parse_none() -> NonNull<pyo3_ffi::PyObject> {
     ffi!(Py_INCREF(NONE));
     nonnull!(NONE)
 }
+
+/// A number of a document that fits in a machine type.
+#[derive(Clone, Copy)]
+pub enum Number {
+    Int(i64),
+    Uint(u64),
+    Float(f64),
+}
+
+/// Deserialize the number `literal`, as it is written in the document,
+/// calling `parse_float` or `parse_int` in `args` for its type or, for
+/// `raw_numbers`, keeping it as a `RawNumber`. If `strict` in `args`, a
+/// number that cannot be represented without loss of magnitude is an error.
+/// An error of `None` means an exception was raised by the hook.
+#[inline(never)]
+pub fn parse_number_literal(
+    literal: &str,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    // `NaN` and `Infinity` are not numbers of JSON and are deserialized to
+    // `float` as usual
+    if unlikely!(args.raw_numbers) && !is_special_literal(literal) {
+        return new_raw_number(literal);
+    }
+    if unlikely!(args.parse_float.is_some() || args.parse_int.is_some())
+        && !is_special_literal(literal)
+    {
+        let func = if is_float_literal(literal) {
+            args.parse_float
+        } else {
+            args.parse_int
+        };
+        if let Some(func) = func {
+            return call_parse_hook(func.as_ptr(), literal);
+        }
+    }
+    match read_number_literal(literal, args)? {
+        Some(Number::Int(val)) => Ok(parse_i64(val)),
+        Some(Number::Uint(val)) => Ok(parse_u64(val)),
+        Some(Number::Float(val)) => Ok(parse_f64(val)),
+        None => parse_big_int(literal),
+    }
+}
+
+/// Read the number `literal` as `parse_number_literal()` does without a
+/// hook. `None` means it is an integer out of the range of a 64-bit integer.
+pub fn read_number_literal(
+    literal: &str,
+    args: &LoadsArgs,
+) -> Result<Option<Number>, DeserializeError<'static>> {
+    // `NaN` and `Infinity` are read as raw numbers when allowed
+    if unlikely!(is_special_literal(literal)) {
+        return read_special_literal(literal).map(|val| Some(Number::Float(val)));
+    }
+    if !is_float_literal(literal) {
+        if let Ok(val) = literal.parse::<i64>() {
+            return Ok(Some(Number::Int(val)));
+        } else if let Ok(val) = literal.parse::<u64>() {
+            return Ok(Some(Number::Uint(val)));
+        } else if unlikely!(args.strict) {
+            return Err(DeserializeError::invalid(Cow::Borrowed(
+                "integer is out of range of a 64-bit integer",
+            )));
+        }
+        return Ok(None);
+    }
+    match literal.parse::<f64>() {
+        Ok(val) if unlikely!(args.strict && val == 0.0 && !is_zero_literal(literal)) => Err(
+            DeserializeError::invalid(Cow::Borrowed("number is zero when parsed as double")),
+        ),
+        Ok(val) if val.is_finite() || args.allow_nan => Ok(Some(Number::Float(val))),
+        _ => Err(DeserializeError::invalid(Cow::Borrowed(NUMBER_INFINITY))),
+    }
+}
+
+/// Whether `literal` is `NaN`, `Infinity`, or `-Infinity`, or another
+/// spelling of them that yyjson matches, e.g., `nan` or `-Inf`.
+#[inline(always)]
+fn is_special_literal(literal: &str) -> bool {
+    literal
+        .as_bytes()
+        .last()
+        .map_or(false, u8::is_ascii_alphabetic)
+}
+
+/// Read a literal for which `is_special_literal()` is true. yyjson matches
+/// them ignoring case, and `inf` and `-NaN` too, so only `NaN`, `Infinity`,
+/// and `-Infinity`, as the standard library writes them, are accepted.
+#[cold]
+fn read_special_literal(literal: &str) -> Result<f64, DeserializeError<'static>> {
+    match literal {
+        "NaN" => Ok(f64::NAN),
+        "Infinity" => Ok(f64::INFINITY),
+        "-Infinity" => Ok(f64::NEG_INFINITY),
+        _ => Err(DeserializeError::invalid(Cow::Borrowed(INVALID_LITERAL))),
+    }
+}
+
+#[inline(always)]
+fn is_float_literal(literal: &str) -> bool {
+    literal
+        .as_bytes()
+        .iter()
+        .any(|&c| matches!(c, b'.' | b'e' | b'E'))
+}
+
+/// The 128 bits of `val` if it is a UUID in the canonical form of 32
+/// hexadecimal digits in groups of 8, 4, 4, 4, and 12 separated by hyphens,
+/// e.g., `7202d115-7ff3-4c81-a7c1-2a1f067b1ece`, in either case.
+fn uuid_from_str(val: &str) -> Option<[u8; 16]> {
+    let bytes = val.as_bytes();
+    if bytes.len() != 36
+        || bytes[8] != b'-'
+        || bytes[13] != b'-'
+        || bytes[18] != b'-'
+        || bytes[23] != b'-'
+    {
+        return None;
+    }
+    let mut buf = [0u8; 16];
+    let mut digits = bytes
+        .iter()
+        .enumerate()
+        .filter(|&(idx, _)| !matches!(idx, 8 | 13 | 18 | 23))
+        .map(|(_, &c)| (c as char).to_digit(16));
+    for each in buf.iter_mut() {
+        let high = digits.next()??;
+        let low = digits.next()??;
+        *each = (high << 4 | low) as u8;
+    }
+    Some(buf)
+}
+
+/// Deserialize the 128 bits of a UUID, most significant first, to a
+/// `uuid.UUID`. An error of `None` means an exception was raised.
+pub fn parse_uuid(
+    bytes: &[u8; 16],
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let int = ffi!(_PyLong_FromByteArray(bytes.as_ptr(), 16, 0, 0));
+    if int.is_null() {
+        return Err(None);
+    }
+    // UUID(hex=None, bytes=None, bytes_le=None, fields=None, int=int)
+    let obj = unsafe {
+        pyo3_ffi::PyObject_CallFunctionObjArgs(
+            UUID_TYPE as *mut pyo3_ffi::PyObject,
+            NONE,
+            NONE,
+            NONE,
+            NONE,
+            int,
+            std::ptr::null_mut::<pyo3_ffi::PyObject>(),
+        )
+    };
+    ffi!(Py_DECREF(int));
+    NonNull::new(obj).ok_or(None)
+}
+
+/// Check the number `literal` as `parse_number_literal()` would deserialize
+/// it without a hook, but without making an object.
+pub fn check_number_literal(
+    literal: &str,
+    args: &LoadsArgs,
+) -> Result<(), DeserializeError<'static>> {
+    if is_special_literal(literal) {
+        return read_special_literal(literal).map(|_| ());
+    }
+    let is_float = literal
+        .as_bytes()
+        .iter()
+        .any(|&c| matches!(c, b'.' | b'e' | b'E'));
+    if !is_float {
+        if args.strict && literal.parse::<i64>().is_err() && literal.parse::<u64>().is_err() {
+            return Err(DeserializeError::invalid(Cow::Borrowed(
+                "integer is out of range of a 64-bit integer",
+            )));
+        }
+        return Ok(());
+    }
+    match literal.parse::<f64>() {
+        Ok(val) if args.strict && val == 0.0 && !is_zero_literal(literal) => Err(
+            DeserializeError::invalid(Cow::Borrowed("number is zero when parsed as double")),
+        ),
+        Ok(val) if val.is_finite() || args.allow_nan => Ok(()),
+        _ => Err(DeserializeError::invalid(Cow::Borrowed(NUMBER_INFINITY))),
+    }
+}
+
+/// Deserialize an integer `literal` out of the range of `i64` and `u64` to an
+/// `int`. It is an error for it to have more digits than
+/// `sys.get_int_max_str_digits()`.
+#[cold]
+#[inline(never)]
+fn parse_big_int(
+    literal: &str,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let digits = std::ffi::CString::new(literal).unwrap();
+    let res = ffi!(PyLong_FromString(digits.as_ptr(), std::ptr::null_mut(), 10));
+    match NonNull::new(res) {
+        Some(val) => Ok(val),
+        None if ffi!(PyErr_ExceptionMatches(pyo3_ffi::PyExc_ValueError)) != 0 => {
+            ffi!(PyErr_Clear());
+            Err(Some(DeserializeError::invalid(Cow::Borrowed(
+                "integer has more digits than sys.get_int_max_str_digits()",
+            ))))
+        }
+        None => Err(None),
+    }
+}
+
+/// Whether the digits of `literal` before any exponent are all zero.
+fn is_zero_literal(literal: &str) -> bool {
+    literal
+        .as_bytes()
+        .iter()
+        .take_while(|&&c| !matches!(c, b'e' | b'E'))
+        .all(|&c| !matches!(c, b'1'..=b'9'))
+}
+
+/// Return the result of calling `object_hook` in `args` with `dict`, or `dict`
+/// if there is none, after making it an instance of `args.dict_class` and
+/// read-only if `args.immutable`. `collected` is as for `set_object_item()`.
+/// An error of `None` means an exception was raised by the hook or class.
+pub fn parse_object_hook(
+    dict: NonNull<pyo3_ffi::PyObject>,
+    collected: &[*mut pyo3_ffi::PyObject],
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let obj = if unlikely!(args.immutable) {
+        freeze_object(dict, collected, args.dict_class)?
+    } else if let Some(cls) = args.dict_class {
+        call_object_hook(cls, dict)?
+    } else {
+        dict
+    };
+    match args.object_hook {
+        None => Ok(obj),
+        Some(func) => call_object_hook(func, obj),
+    }
+}
+
+/// Return a `types.MappingProxyType` of `dict`, consuming it, after replacing
+/// the lists in `collected` with tuples and making it an instance of
+/// `dict_class`, if given.
+#[cold]
+#[inline(never)]
+fn freeze_object(
+    dict: NonNull<pyo3_ffi::PyObject>,
+    collected: &[*mut pyo3_ffi::PyObject],
+    dict_class: Option<NonNull<pyo3_ffi::PyObject>>,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if !collected.is_empty() {
+        let mut pos: pyo3_ffi::Py_ssize_t = 0;
+        let mut key: *mut pyo3_ffi::PyObject = std::ptr::null_mut();
+        let mut value: *mut pyo3_ffi::PyObject = std::ptr::null_mut();
+        while ffi!(PyDict_Next(dict.as_ptr(), &mut pos, &mut key, &mut value)) != 0 {
+            if collected.contains(&value) {
+                // replacing the value of a key does not resize the dict
+                let tuple = ffi!(PyList_AsTuple(value));
+                ffi!(PyDict_SetItem(dict.as_ptr(), key, tuple));
+                ffi!(Py_DECREF(tuple));
+            }
+        }
+    }
+    let dict = match dict_class {
+        Some(cls) => call_object_hook(cls, dict)?,
+        None => dict,
+    };
+    let proxy = ffi!(PyDictProxy_New(dict.as_ptr()));
+    ffi!(Py_DECREF(dict.as_ptr()));
+    Ok(nonnull!(proxy))
+}
+
+/// Insert `key` and `value` into `dict`, consuming them, according to
+/// `duplicate_keys` in `args`. `collected` holds the lists created for
+/// `DuplicateKeys::List` so they are distinguished from `list` values.
+#[inline(always)]
+pub fn set_object_item(
+    dict: *mut pyo3_ffi::PyObject,
+    key: *mut pyo3_ffi::PyObject,
+    hash: pyo3_ffi::Py_hash_t,
+    value: NonNull<pyo3_ffi::PyObject>,
+    args: &LoadsArgs,
+    collected: &mut Vec<*mut pyo3_ffi::PyObject>,
+) -> Result<(), DeserializeError<'static>> {
+    if unlikely!(args.duplicate_keys != DuplicateKeys::Last) {
+        return set_duplicate_object_item(dict, key, value, args.duplicate_keys, collected);
+    }
+    let _ = ffi!(_PyDict_SetItem_KnownHash(dict, key, value.as_ptr(), hash));
+    // counter Py_INCREF in insertdict
+    ffi!(Py_DECREF(key));
+    ffi!(Py_DECREF(value.as_ptr()));
+    Ok(())
+}
+
+#[cold]
+#[inline(never)]
+fn set_duplicate_object_item(
+    dict: *mut pyo3_ffi::PyObject,
+    key: *mut pyo3_ffi::PyObject,
+    value: NonNull<pyo3_ffi::PyObject>,
+    duplicate_keys: DuplicateKeys,
+    collected: &mut Vec<*mut pyo3_ffi::PyObject>,
+) -> Result<(), DeserializeError<'static>> {
+    let existing = ffi!(PyDict_GetItemWithError(dict, key));
+    let mut res = Ok(());
+    if existing.is_null() {
+        ffi!(PyDict_SetItem(dict, key, value.as_ptr()));
+    } else {
+        match duplicate_keys {
+            DuplicateKeys::Last => {
+                ffi!(PyDict_SetItem(dict, key, value.as_ptr()));
+            }
+            DuplicateKeys::First => {}
+            DuplicateKeys::Error => {
+                let key_str = unicode_to_str(key).unwrap_or_default();
+                res = Err(DeserializeError::invalid(Cow::Owned(format!(
+                    "Duplicate key \"{}\"",
+                    key_str
+                ))));
+            }
+            DuplicateKeys::List => {
+                if collected.contains(&existing) {
+                    ffi!(PyList_Append(existing, value.as_ptr()));
+                } else {
+                    let list = ffi!(PyList_New(2));
+                    ffi!(Py_INCREF(existing));
+                    ffi!(PyList_SET_ITEM(list, 0, existing));
+                    ffi!(Py_INCREF(value.as_ptr()));
+                    ffi!(PyList_SET_ITEM(list, 1, value.as_ptr()));
+                    ffi!(PyDict_SetItem(dict, key, list));
+                    ffi!(Py_DECREF(list));
+                    collected.push(list);
+                }
+            }
+        }
+    }
+    ffi!(Py_DECREF(key));
+    ffi!(Py_DECREF(value.as_ptr()));
+    res
+}
+
+/// Create the `(key, value)` tuple given to `object_pairs_hook`, consuming
+/// `key` and `value`.
+pub fn parse_pair(
+    key: *mut pyo3_ffi::PyObject,
+    value: NonNull<pyo3_ffi::PyObject>,
+) -> *mut pyo3_ffi::PyObject {
+    let pair = ffi!(PyTuple_New(2));
+    ffi!(PyTuple_SET_ITEM(pair, 0, key));
+    ffi!(PyTuple_SET_ITEM(pair, 1, value.as_ptr()));
+    pair
+}
+
+/// Call `func`, a hook such as `object_hook`, with `obj`, consuming it. An
+/// error of `None` means an exception was raised by the hook.
+pub fn call_object_hook(
+    func: NonNull<pyo3_ffi::PyObject>,
+    obj: NonNull<pyo3_ffi::PyObject>,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let res = ffi!(PyObject_CallFunctionObjArgs(
+        func.as_ptr(),
+        obj.as_ptr(),
+        std::ptr::null_mut::<pyo3_ffi::PyObject>()
+    ));
+    ffi!(Py_DECREF(obj.as_ptr()));
+    NonNull::new(res).ok_or(None)
+}
+
+/// Call a hook such as `parse_float` with the `str` of `literal`.
+fn call_parse_hook(
+    func: *mut pyo3_ffi::PyObject,
+    literal: &str,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let arg = unicode_from_str(literal);
+    let res = ffi!(PyObject_CallFunctionObjArgs(
+        func,
+        arg,
+        std::ptr::null_mut::<pyo3_ffi::PyObject>()
+    ));
+    ffi!(Py_DECREF(arg));
+    NonNull::new(res).ok_or(None)
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::DeserializeError;
+use crate::typeref::*;
+use crate::unicode::*;
+use std::borrow::Cow;
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr::{null_mut, NonNull};
+
+/// A number as it is written in a document, deserialized with
+/// `raw_numbers=True` and serialized as the same literal.
+#[repr(C)]
+pub struct RawNumberObject {
+    ob_base: pyo3_ffi::PyObject,
+    // the `str` of the literal
+    literal: *mut pyo3_ffi::PyObject,
+}
+
+/// Make an `orjson.RawNumber` of `literal`, a number as it is written in the
+/// document.
+pub fn new_raw_number(
+    literal: &str,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let obj = ffi!(PyType_GenericAlloc(unsafe { RAW_NUMBER_TYPE }, 0));
+    if obj.is_null() {
+        return Err(None);
+    }
+    unsafe { (*(obj as *mut RawNumberObject)).literal = unicode_from_str(literal) };
+    Ok(nonnull!(obj))
+}
+
+/// The literal of `ptr`, which is an `orjson.RawNumber`.
+#[inline(always)]
+pub fn raw_number_literal(ptr: *mut pyo3_ffi::PyObject) -> &'static str {
+    unsafe { unicode_to_str((*(ptr as *mut RawNumberObject)).literal).unwrap_or_default() }
+}
+
+/// Whether `val` is a number in the grammar of JSON: an optional minus sign,
+/// an integer without leading zeros, an optional fraction, and an optional
+/// exponent.
+fn is_number_literal(val: &str) -> bool {
+    let bytes = val.as_bytes();
+    let mut idx = 0;
+    let digits = |idx: &mut usize| {
+        let start = *idx;
+        while bytes.get(*idx).map_or(false, u8::is_ascii_digit) {
+            *idx += 1;
+        }
+        *idx - start
+    };
+    if bytes.first() == Some(&b'-') {
+        idx += 1;
+    }
+    let int_start = idx;
+    match digits(&mut idx) {
+        0 => return false,
+        1 => (),
+        _ if bytes[int_start] == b'0' => return false,
+        _ => (),
+    }
+    if bytes.get(idx) == Some(&b'.') {
+        idx += 1;
+        if digits(&mut idx) == 0 {
+            return false;
+        }
+    }
+    if matches!(bytes.get(idx), Some(b'e') | Some(b'E')) {
+        idx += 1;
+        if matches!(bytes.get(idx), Some(b'+') | Some(b'-')) {
+            idx += 1;
+        }
+        if digits(&mut idx) == 0 {
+            return false;
+        }
+    }
+    idx == bytes.len()
+}
+
+unsafe extern "C" fn raw_number_new(
+    subtype: *mut pyo3_ffi::PyTypeObject,
+    args: *mut pyo3_ffi::PyObject,
+    kwds: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    if pyo3_ffi::PyTuple_GET_SIZE(args) != 1
+        || (!kwds.is_null() && pyo3_ffi::PyDict_Size(kwds) != 0)
+    {
+        return crate::raise_loads_exception(DeserializeError::invalid(Cow::Borrowed(
+            "RawNumber() takes exactly 1 positional argument",
+        )));
+    }
+    let literal = pyo3_ffi::PyTuple_GET_ITEM(args, 0);
+    if !is_type!(ob_type!(literal), STR_TYPE)
+        || !unicode_to_str(literal).map_or(false, is_number_literal)
+    {
+        return crate::raise_loads_exception(DeserializeError::invalid(Cow::Borrowed(
+            "RawNumber() argument must be a str of a JSON number",
+        )));
+    }
+    let obj = pyo3_ffi::PyType_GenericAlloc(subtype, 0);
+    if obj.is_null() {
+        return null_mut();
+    }
+    pyo3_ffi::Py_INCREF(literal);
+    (*(obj as *mut RawNumberObject)).literal = literal;
+    obj
+}
+
+unsafe extern "C" fn raw_number_dealloc(slf: *mut pyo3_ffi::PyObject) {
+    pyo3_ffi::Py_DECREF((*(slf as *mut RawNumberObject)).literal);
+    let tp = ob_type!(slf);
+    ((*tp).tp_free.unwrap())(slf as *mut c_void);
+    pyo3_ffi::Py_DECREF(tp as *mut pyo3_ffi::PyObject);
+}
+
+unsafe extern "C" fn raw_number_str(slf: *mut pyo3_ffi::PyObject) -> *mut pyo3_ffi::PyObject {
+    let literal = (*(slf as *mut RawNumberObject)).literal;
+    pyo3_ffi::Py_INCREF(literal);
+    literal
+}
+
+unsafe extern "C" fn raw_number_repr(slf: *mut pyo3_ffi::PyObject) -> *mut pyo3_ffi::PyObject {
+    unicode_from_str(&format!("RawNumber('{}')", raw_number_literal(slf)))
+}
+
+unsafe extern "C" fn raw_number_hash(slf: *mut pyo3_ffi::PyObject) -> pyo3_ffi::Py_hash_t {
+    pyo3_ffi::PyObject_Hash((*(slf as *mut RawNumberObject)).literal)
+}
+
+/// Two `RawNumber` are equal if their literals are, so `RawNumber("1.0")` is
+/// not equal to `RawNumber("1")` or to any `int` or `float`.
+unsafe extern "C" fn raw_number_richcompare(
+    slf: *mut pyo3_ffi::PyObject,
+    other: *mut pyo3_ffi::PyObject,
+    op: c_int,
+) -> *mut pyo3_ffi::PyObject {
+    if ob_type!(other) != RAW_NUMBER_TYPE || (op != pyo3_ffi::Py_EQ && op != pyo3_ffi::Py_NE) {
+        let res = pyo3_ffi::Py_NotImplemented();
+        pyo3_ffi::Py_INCREF(res);
+        return res;
+    }
+    let equal = raw_number_literal(slf) == raw_number_literal(other);
+    let res = if equal == (op == pyo3_ffi::Py_EQ) {
+        TRUE
+    } else {
+        FALSE
+    };
+    pyo3_ffi::Py_INCREF(res);
+    res
+}
+
+unsafe extern "C" fn raw_number_float(slf: *mut pyo3_ffi::PyObject) -> *mut pyo3_ffi::PyObject {
+    pyo3_ffi::PyFloat_FromString((*(slf as *mut RawNumberObject)).literal)
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub unsafe fn create_raw_number_type() -> *mut pyo3_ffi::PyTypeObject {
+    let slots: Box<[pyo3_ffi::PyType_Slot; 9]> = Box::new([
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_new,
+            pfunc: raw_number_new as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_doc,
+            pfunc: "RawNumber(literal, /)\n--\n\nA JSON number serialized exactly as the literal given.\0".as_ptr() as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_dealloc,
+            pfunc: raw_number_dealloc as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_str,
+            pfunc: raw_number_str as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_repr,
+            pfunc: raw_number_repr as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_hash,
+            pfunc: raw_number_hash as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_richcompare,
+            pfunc: raw_number_richcompare as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_nb_float,
+            pfunc: raw_number_float as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: 0,
+            pfunc: std::ptr::null_mut(),
+        },
+    ]);
+    let spec = Box::new(pyo3_ffi::PyType_Spec {
+        name: "orjson.RawNumber\0".as_ptr() as *const c_char,
+        basicsize: std::mem::size_of::<RawNumberObject>() as c_int,
+        itemsize: 0,
+        flags: pyo3_ffi::Py_TPFLAGS_DEFAULT as c_uint,
+        slots: Box::into_raw(slots) as *mut pyo3_ffi::PyType_Slot,
+    });
+    pyo3_ffi::PyType_FromSpec(Box::into_raw(spec)) as *mut pyo3_ffi::PyTypeObject
+}
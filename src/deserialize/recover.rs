@@ -0,0 +1,542 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::error::*;
+use crate::deserialize::tokenize::{is_number, State};
+use crate::deserialize::utf8::{is_buffer_input, read_input_bytes, BufferInput};
+use crate::deserialize::{DeserializeError, LoadsArgs, Surrogates};
+use crate::error::INVALID_STR;
+use crate::typeref::*;
+use std::borrow::Cow;
+use std::ptr::{null_mut, NonNull};
+
+/// Find the syntax errors of the document in `ptr`, continuing after each,
+/// and return a `list` of a `JSONDecodeError` for each, in order, of at most
+/// `max_errors`. Of `args`, only `allow_nan`, `jsonc`, `max_depth`, and
+/// `surrogates` are used. An error is for input of a type `loads()` does not accept.
+pub fn find_errors(
+    ptr: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+    max_errors: Option<usize>,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let is_str = is_type!(ob_type!(ptr), STR_TYPE);
+    let input = if is_buffer_input(ptr) {
+        Some(BufferInput::new(ptr)?)
+    } else {
+        None
+    };
+    let buffer = match &input {
+        Some(input) => input.as_bytes(),
+        None => match read_input_bytes(ptr) {
+            Ok(buffer) => buffer,
+            // encoding a `str` with surrogates raised, and it has no UTF-8
+            // to scan
+            Err(Some(err)) if is_str && err.message == INVALID_STR => {
+                return new_error_list(&[err], null_mut());
+            }
+            Err(err) => return Err(err),
+        },
+    };
+    let mut scanner = Recovery {
+        buf: buffer,
+        idx: 0,
+        state: State::Value,
+        stack: Vec::new(),
+        errors: Vec::new(),
+        trailing: false,
+        max_errors: max_errors.unwrap_or(usize::MAX),
+        max_depth: args.max_depth() as usize,
+        allow_nan: args.allow_nan,
+        jsonc: args.jsonc,
+        lone_surrogates: args.surrogates != Surrogates::Reject,
+    };
+    scanner.run();
+    if scanner.errors.is_empty() {
+        return Ok(nonnull!(ffi!(PyList_New(0))));
+    }
+    // the `doc` of each error, with each sequence that is not UTF-8 replaced
+    let data = String::from_utf8_lossy(buffer);
+    let lossy = matches!(data, Cow::Owned(_));
+    let doc = if is_str {
+        ptr
+    } else {
+        ffi!(PyUnicode_FromStringAndSize(
+            data.as_ptr() as *const std::os::raw::c_char,
+            data.len() as isize
+        ))
+    };
+    // the errors are in order, so their positions in `data` are found
+    // without decoding the input before each again
+    let mut decoded = (0, 0);
+    let errors = scanner
+        .errors
+        .iter()
+        .map(|&(pos, message)| {
+            let data_pos = if lossy {
+                decoded = (
+                    pos,
+                    decoded.1 + String::from_utf8_lossy(&buffer[decoded.0..pos]).len(),
+                );
+                decoded.1
+            } else {
+                pos
+            };
+            DeserializeError::at_byte(Cow::Borrowed(message), data_pos, &data)
+        })
+        .collect::<Vec<DeserializeError>>();
+    let res = new_error_list(&errors, doc);
+    if !is_str {
+        ffi!(Py_DECREF(doc));
+    }
+    res
+}
+
+/// A `list` of the `JSONDecodeError` of each of `errors`, sharing `doc` if it
+/// is not null.
+fn new_error_list(
+    errors: &[DeserializeError],
+    doc: *mut pyo3_ffi::PyObject,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let list = ffi!(PyList_New(errors.len() as isize));
+    for (idx, err) in errors.iter().enumerate() {
+        let exc = crate::new_loads_exception(err, doc);
+        if exc.is_null() {
+            ffi!(Py_DECREF(list));
+            return Err(None);
+        }
+        ffi!(PyList_SET_ITEM(list, idx as isize, exc));
+    }
+    Ok(nonnull!(list))
+}
+
+/// Whether `c` can start a value, including the invalid values the scanner
+/// skips as one.
+fn starts_value(c: u8) -> bool {
+    matches!(c, b'"' | b'\'' | b'[' | b'{' | b'-' | b'0'..=b'9') || c.is_ascii_alphabetic()
+}
+
+/// Scans a document for syntax errors, recovering from each as the person
+/// who wrote it most likely meant, e.g., by assuming a comma is missing or
+/// that a string ends with its line, so that the errors after it are found.
+struct Recovery<'a> {
+    buf: &'a [u8],
+    // bytes of `buf` scanned
+    idx: usize,
+    state: State,
+    // `[` or `{` for each container the next token is in
+    stack: Vec<u8>,
+    // the byte at which each error is and its message, in order
+    errors: Vec<(usize, &'static str)>,
+    // whether content after the document has been reported
+    trailing: bool,
+    max_errors: usize,
+    max_depth: usize,
+    allow_nan: bool,
+    jsonc: bool,
+    lone_surrogates: bool,
+}
+
+impl<'a> Recovery<'a> {
+    #[cold]
+    fn error(&mut self, pos: usize, message: &'static str) {
+        if self.errors.len() < self.max_errors {
+            self.errors.push((pos, message));
+        }
+    }
+
+    fn run(&mut self) {
+        if self.buf.starts_with(b"\xef\xbb\xbf") {
+            self.error(0, BYTE_ORDER_MARK);
+            self.idx = 3;
+        }
+        while self.errors.len() < self.max_errors {
+            let c = match self.skip_whitespace() {
+                Some(c) => c,
+                None => {
+                    if self.state != State::Done {
+                        self.error(self.idx, UNEXPECTED_END);
+                    }
+                    return;
+                }
+            };
+            match self.state {
+                State::Done => self.trailing(c),
+                State::Colon => self.colon(c),
+                State::CommaOrEnd => self.comma_or_end(c),
+                State::KeyOrEnd | State::Key => self.key(c),
+                State::Value | State::ValueOrEnd => self.value(c),
+            }
+        }
+    }
+
+    /// Skip whitespace, and comments if `jsonc`, and return the next byte,
+    /// which is not consumed, or `None` at the end of the input.
+    fn skip_whitespace(&mut self) -> Option<u8> {
+        loop {
+            let c = *self.buf.get(self.idx)?;
+            match c {
+                b' ' | b'\t' | b'\n' | b'\r' => self.idx += 1,
+                b'/' if self.jsonc => match self.buf.get(self.idx + 1) {
+                    Some(b'/') => {
+                        self.idx = self.buf[self.idx..]
+                            .iter()
+                            .position(|&c| c == b'\n')
+                            .map_or(self.buf.len(), |idx| self.idx + idx);
+                    }
+                    Some(b'*') => {
+                        match self.buf[self.idx + 2..]
+                            .windows(2)
+                            .position(|pair| pair == b"*/")
+                        {
+                            Some(idx) => self.idx += idx + 4,
+                            None => {
+                                self.error(self.idx, UNCLOSED_COMMENT);
+                                self.idx = self.buf.len();
+                            }
+                        }
+                    }
+                    _ => return Some(c),
+                },
+                _ => return Some(c),
+            }
+        }
+    }
+
+    /// Skip the invalid token starting at the next byte, which is at least
+    /// that byte and then anything up to whitespace or punctuation.
+    fn skip_token(&mut self) {
+        self.idx += 1;
+        while let Some(&c) = self.buf.get(self.idx) {
+            if matches!(
+                c,
+                b' ' | b'\t' | b'\n' | b'\r' | b',' | b':' | b'[' | b']' | b'{' | b'}' | b'"'
+            ) {
+                break;
+            }
+            self.idx += 1;
+        }
+    }
+
+    /// Skip the single-quoted string starting at the next byte, if it ends on
+    /// its line, or else the token it starts.
+    fn skip_single_quoted(&mut self) {
+        match self.buf[self.idx + 1..]
+            .iter()
+            .position(|&c| c == b'\'' || c == b'\n')
+        {
+            Some(idx) if self.buf[self.idx + 1 + idx] == b'\'' => self.idx += idx + 2,
+            _ => self.skip_token(),
+        }
+    }
+
+    /// The state after a value, or the end of a container.
+    fn after_value(&mut self) {
+        self.state = if self.stack.is_empty() {
+            State::Done
+        } else {
+            State::CommaOrEnd
+        };
+    }
+
+    fn end(&mut self) {
+        self.stack.pop();
+        self.idx += 1;
+        self.after_value();
+    }
+
+    /// End the container the bracket `c` closes. A bracket closing one that
+    /// encloses the current container ends the current one too.
+    fn close(&mut self, c: u8) {
+        let open = if c == b']' { b'[' } else { b'{' };
+        if self.stack.last() == Some(&open) {
+            self.end();
+            return;
+        }
+        match self.stack.iter().rposition(|&each| each == open) {
+            Some(depth) => {
+                self.error(self.idx, UNEXPECTED_CHARACTER);
+                self.stack.truncate(depth + 1);
+                self.end();
+            }
+            None => {
+                self.error(self.idx, UNEXPECTED_CHARACTER);
+                self.idx += 1;
+            }
+        }
+    }
+
+    /// Report the content after the document once, where it starts, and
+    /// scan each value in it as another document so that its errors are
+    /// found too, skipping anything else up to the next value.
+    fn trailing(&mut self, c: u8) {
+        if !self.trailing {
+            self.error(self.idx, UNEXPECTED_CONTENT);
+            self.trailing = true;
+        }
+        if starts_value(c) {
+            self.state = State::Value;
+        } else {
+            self.skip_token();
+        }
+    }
+
+    fn colon(&mut self, c: u8) {
+        if c == b':' {
+            self.idx += 1;
+            self.state = State::Value;
+            return;
+        }
+        self.error(self.idx, UNEXPECTED_CHARACTER);
+        if matches!(c, b',' | b']' | b'}') {
+            // the value is missing too
+            self.after_value();
+        } else {
+            if !starts_value(c) {
+                self.skip_token();
+            }
+            self.state = State::Value;
+        }
+    }
+
+    fn comma_or_end(&mut self, c: u8) {
+        let top = *self.stack.last().unwrap();
+        match c {
+            b',' => {
+                self.idx += 1;
+                self.state = if top == b'{' {
+                    State::Key
+                } else {
+                    State::Value
+                };
+            }
+            b']' | b'}' => self.close(c),
+            _ => {
+                self.error(self.idx, UNEXPECTED_CHARACTER);
+                if starts_value(c) {
+                    // the comma is missing
+                    self.state = if top == b'{' {
+                        State::Key
+                    } else {
+                        State::Value
+                    };
+                } else {
+                    self.skip_token();
+                }
+            }
+        }
+    }
+
+    fn key(&mut self, c: u8) {
+        match c {
+            b'"' => {
+                self.read_string();
+                self.state = State::Colon;
+            }
+            // a trailing comma is allowed with `jsonc`
+            b'}' if self.state == State::KeyOrEnd || self.jsonc => self.end(),
+            _ => {
+                self.error(self.idx, UNEXPECTED_CHARACTER);
+                match c {
+                    b']' | b'}' => self.after_value(),
+                    b',' => {
+                        self.idx += 1;
+                        self.state = State::Key;
+                    }
+                    b':' => {
+                        self.idx += 1;
+                        self.state = State::Value;
+                    }
+                    b'\'' => {
+                        self.skip_single_quoted();
+                        self.state = State::Colon;
+                    }
+                    _ => {
+                        self.skip_token();
+                        self.state = State::Colon;
+                    }
+                }
+            }
+        }
+    }
+
+    fn value(&mut self, c: u8) {
+        let top = self.stack.last().copied();
+        match c {
+            b'{' | b'[' => {
+                // reported once where the nesting first exceeds it, and then
+                // scanned as usual
+                if self.stack.len() == self.max_depth {
+                    self.error(self.idx, RECURSION_LIMIT);
+                }
+                self.stack.push(c);
+                self.idx += 1;
+                self.state = if c == b'{' {
+                    State::KeyOrEnd
+                } else {
+                    State::ValueOrEnd
+                };
+            }
+            b'"' => {
+                self.read_string();
+                self.after_value();
+            }
+            b'-' | b'0'..=b'9' | b'a'..=b'z' | b'A'..=b'Z' => {
+                self.read_literal();
+                self.after_value();
+            }
+            // a trailing comma is allowed with `jsonc`
+            b']' if top == Some(b'[') && (self.state == State::ValueOrEnd || self.jsonc) => {
+                self.end()
+            }
+            b']' | b'}' | b',' if top.is_some() => {
+                // the value is missing
+                self.error(self.idx, UNEXPECTED_CHARACTER);
+                self.after_value();
+            }
+            b'\'' => {
+                self.error(self.idx, UNEXPECTED_CHARACTER);
+                self.skip_single_quoted();
+                self.after_value();
+            }
+            _ => {
+                self.error(self.idx, UNEXPECTED_CHARACTER);
+                self.skip_token();
+                // anything before the document is skipped
+                if top.is_some() {
+                    self.after_value();
+                }
+            }
+        }
+    }
+
+    /// Read the number or `true`, `false`, or `null` starting at the next
+    /// byte.
+    fn read_literal(&mut self) {
+        let start = self.idx;
+        while let Some(&c) = self.buf.get(self.idx) {
+            if !(c.is_ascii_alphanumeric() || matches!(c, b'+' | b'-' | b'.')) {
+                break;
+            }
+            self.idx += 1;
+        }
+        let literal = &self.buf[start..self.idx];
+        let non_finite = self.allow_nan && matches!(literal, b"NaN" | b"Infinity" | b"-Infinity");
+        if non_finite || matches!(literal, b"true" | b"false" | b"null") {
+            return;
+        }
+        if !matches!(literal[0], b'-' | b'0'..=b'9') {
+            self.error(start, INVALID_LITERAL);
+        } else if !is_number(literal) {
+            self.error(start, INVALID_NUMBER);
+        } else if !self.allow_nan
+            && literal.iter().any(|&c| matches!(c, b'.' | b'e' | b'E'))
+            && std::str::from_utf8(literal)
+                .ok()
+                .and_then(|val| val.parse::<f64>().ok())
+                .map_or(false, f64::is_infinite)
+        {
+            self.error(start, NUMBER_INFINITY);
+        }
+    }
+
+    /// Read the string starting at the next byte. A line feed in it is taken
+    /// to be where it was meant to end.
+    fn read_string(&mut self) {
+        let mut idx = self.idx + 1;
+        loop {
+            let c = match self.buf.get(idx) {
+                Some(&c) => c,
+                None => {
+                    self.error(self.idx, UNCLOSED_STRING);
+                    self.idx = idx;
+                    return;
+                }
+            };
+            match c {
+                b'"' => {
+                    self.idx = idx + 1;
+                    return;
+                }
+                b'\\' => idx = self.read_escape(idx),
+                b'\n' => {
+                    self.error(idx, CONTROL_CHARACTER);
+                    self.idx = idx;
+                    return;
+                }
+                0..=0x1f => {
+                    self.error(idx, CONTROL_CHARACTER);
+                    idx += 1;
+                }
+                0x80..=0xff => idx = self.read_utf8(idx),
+                _ => idx += 1,
+            }
+        }
+    }
+
+    /// Check the escape at `idx` and return the index after it.
+    fn read_escape(&mut self, idx: usize) -> usize {
+        match self.buf.get(idx + 1) {
+            Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => idx + 2,
+            Some(b'u') => {
+                let code = match self.hex4(idx + 2) {
+                    Some(code) => code,
+                    None => {
+                        self.error(idx, INVALID_ESCAPE);
+                        return idx + 2;
+                    }
+                };
+                if (0xD800..0xDC00).contains(&code) {
+                    if self.buf.get(idx + 6..idx + 8) == Some(b"\\u")
+                        && matches!(self.hex4(idx + 8), Some(low) if (0xDC00..0xE000).contains(&low))
+                    {
+                        return idx + 12;
+                    }
+                    if !self.lone_surrogates {
+                        let message = if self.buf.get(idx + 6..idx + 8) == Some(b"\\u") {
+                            INVALID_LOW_SURROGATE
+                        } else {
+                            NO_LOW_SURROGATE
+                        };
+                        self.error(idx, message);
+                    }
+                } else if (0xDC00..0xE000).contains(&code) && !self.lone_surrogates {
+                    self.error(idx, INVALID_HIGH_SURROGATE);
+                }
+                idx + 6
+            }
+            // the end of the input is found by the string
+            None => idx + 1,
+            Some(_) => {
+                self.error(idx, INVALID_ESCAPE);
+                idx + 1
+            }
+        }
+    }
+
+    /// The four hexadecimal digits at `idx`, if they are.
+    fn hex4(&self, idx: usize) -> Option<u32> {
+        let digits = self.buf.get(idx..idx + 4)?;
+        digits.iter().try_fold(0, |acc, &c| {
+            char::from(c).to_digit(16).map(|digit| acc * 16 + digit)
+        })
+    }
+
+    /// Check the character starting with the byte at `idx`, which is not
+    /// ASCII, and return the index after it, or after the sequence that is
+    /// not UTF-8.
+    fn read_utf8(&mut self, idx: usize) -> usize {
+        let width = match self.buf[idx] {
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => 1,
+        };
+        let end = (idx + width).min(self.buf.len());
+        match std::str::from_utf8(&self.buf[idx..end]) {
+            Ok(_) => end,
+            Err(err) => {
+                self.error(idx, INVALID_UTF8);
+                idx + err.error_len().unwrap_or(end - idx).max(1)
+            }
+        }
+    }
+}
@@ -0,0 +1,903 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::path::PathError;
+use crate::deserialize::{DeserializeError, DuplicateKeys, LoadsArgs};
+use crate::ffi::PyDictIter;
+use crate::typeref::*;
+use crate::unicode::*;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr::{null_mut, NonNull};
+
+// Nesting of a schema given to `orjson.Schema`, which also bounds a `dict`
+// that contains itself.
+const COMPILE_DEPTH_LIMIT: u32 = 1024;
+
+// Schemas applied to one value without descending into it, which bounds a
+// `$ref` that refers to itself.
+const REF_DEPTH_LIMIT: u32 = 1024;
+
+const TYPE_NULL: u8 = 1;
+const TYPE_BOOLEAN: u8 = 1 << 1;
+const TYPE_INTEGER: u8 = 1 << 2;
+const TYPE_NUMBER: u8 = 1 << 3;
+const TYPE_STRING: u8 = 1 << 4;
+const TYPE_ARRAY: u8 = 1 << 5;
+const TYPE_OBJECT: u8 = 1 << 6;
+
+const TYPE_NAMES: [(u8, &str); 7] = [
+    (TYPE_NULL, "null"),
+    (TYPE_BOOLEAN, "boolean"),
+    (TYPE_INTEGER, "integer"),
+    (TYPE_NUMBER, "number"),
+    (TYPE_STRING, "string"),
+    (TYPE_ARRAY, "array"),
+    (TYPE_OBJECT, "object"),
+];
+
+// Assertions that would change whether a document is valid, so a schema
+// using them is refused rather than validated partially. Other keywords not
+// listed here or implemented, such as `title` and `format`, are annotations.
+const UNSUPPORTED_KEYWORDS: [&str; 17] = [
+    "$dynamicRef",
+    "$recursiveRef",
+    "contains",
+    "dependencies",
+    "dependentRequired",
+    "dependentSchemas",
+    "else",
+    "if",
+    "maxContains",
+    "minContains",
+    "pattern",
+    "patternProperties",
+    "propertyNames",
+    "then",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    "uniqueItems",
+];
+
+/// A value of `enum` or `const`.
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/// The type of a value being validated, with its value if it is a scalar.
+pub enum Kind {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(&'static str),
+    Array,
+    Object,
+    /// A Python object that is not made by deserializing JSON.
+    Other,
+}
+
+impl Kind {
+    fn name(&self) -> &'static str {
+        match self {
+            Kind::Null => "null",
+            Kind::Bool(_) => "boolean",
+            Kind::Number(_) => "number",
+            Kind::Str(_) => "string",
+            Kind::Array => "array",
+            Kind::Object => "object",
+            Kind::Other => "unsupported value",
+        }
+    }
+}
+
+/// A value of the document being validated, either parsed by yyjson or
+/// already deserialized to Python objects.
+pub trait Instance: Copy {
+    fn kind(self) -> Kind;
+    /// The number of elements of an array or members of an object.
+    fn len(self) -> usize;
+    /// Call `f` with each element of an array in order.
+    fn each_item(
+        self,
+        f: &mut dyn FnMut(usize, Self) -> Result<(), PathError>,
+    ) -> Result<(), PathError>;
+    /// Call `f` with each member of an object.
+    fn each_member(
+        self,
+        f: &mut dyn FnMut(&str, Self) -> Result<(), PathError>,
+    ) -> Result<(), PathError>;
+}
+
+/// A compiled schema or subschema.
+#[derive(Default)]
+struct Node {
+    // `true` or `false` as a schema.
+    boolean: Option<bool>,
+    // bits of `TYPE_*`, or 0 if any type is allowed
+    types: u8,
+    // values of `enum`, or of `const` as one value
+    values: Option<(&'static str, Vec<Json>)>,
+    // index of the target of `$ref` in `CompiledSchema::refs`
+    reference: Option<usize>,
+    properties: Vec<(String, Node)>,
+    required: Vec<String>,
+    additional_properties: Option<Box<Node>>,
+    prefix_items: Vec<Node>,
+    items: Option<Box<Node>>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    min_properties: Option<usize>,
+    max_properties: Option<usize>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    exclusive_minimum: Option<f64>,
+    exclusive_maximum: Option<f64>,
+    multiple_of: Option<f64>,
+    all_of: Vec<Node>,
+    any_of: Vec<Node>,
+    one_of: Vec<Node>,
+    not: Option<Box<Node>>,
+}
+
+/// A JSON Schema compiled by `orjson.Schema`, holding no Python objects.
+pub struct CompiledSchema {
+    root: Node,
+    // targets of `$ref`, in the order first referred to
+    refs: Vec<Node>,
+}
+
+/// `orjson.Schema`, a JSON Schema compiled once to validate documents as they
+/// are deserialized.
+#[repr(C)]
+pub struct SchemaObject {
+    ob_base: pyo3_ffi::PyObject,
+    schema: *mut CompiledSchema,
+}
+
+struct Compiler {
+    // the schema as given, to resolve `$ref`
+    document: *mut pyo3_ffi::PyObject,
+    refs: Vec<Option<Node>>,
+    ref_ids: HashMap<String, usize>,
+}
+
+fn is_type(obj: *mut pyo3_ffi::PyObject, ob_type: *mut pyo3_ffi::PyTypeObject) -> bool {
+    ob_type!(obj) == ob_type
+}
+
+fn non_negative_int(obj: *mut pyo3_ffi::PyObject, keyword: &str) -> Result<usize, String> {
+    if unsafe { is_type(obj, INT_TYPE) } {
+        let val = ffi!(PyLong_AsSsize_t(obj));
+        if val >= 0 {
+            return Ok(val as usize);
+        }
+        ffi!(PyErr_Clear());
+    }
+    Err(format!("{} must be a non-negative integer", keyword))
+}
+
+fn number(obj: *mut pyo3_ffi::PyObject, keyword: &str) -> Result<f64, String> {
+    unsafe {
+        if is_type(obj, FLOAT_TYPE) {
+            Ok(pyo3_ffi::PyFloat_AS_DOUBLE(obj))
+        } else if is_type(obj, INT_TYPE) {
+            let val = pyo3_ffi::PyLong_AsDouble(obj);
+            if val == -1.0 && !pyo3_ffi::PyErr_Occurred().is_null() {
+                pyo3_ffi::PyErr_Clear();
+                return Err(format!("{} is too large", keyword));
+            }
+            Ok(val)
+        } else {
+            Err(format!("{} must be a number", keyword))
+        }
+    }
+}
+
+fn list_items(
+    obj: *mut pyo3_ffi::PyObject,
+    keyword: &str,
+) -> Result<Vec<*mut pyo3_ffi::PyObject>, String> {
+    if unsafe { !is_type(obj, LIST_TYPE) } {
+        return Err(format!("{} must be an array", keyword));
+    }
+    Ok((0..ffi!(Py_SIZE(obj)))
+        .map(|idx| ffi!(PyList_GET_ITEM(obj, idx)))
+        .collect())
+}
+
+fn string(obj: *mut pyo3_ffi::PyObject, keyword: &str) -> Result<&'static str, String> {
+    if unsafe { is_type(obj, STR_TYPE) } {
+        if let Some(val) = unicode_to_str(obj) {
+            return Ok(val);
+        }
+    }
+    Err(format!("{} must be a string", keyword))
+}
+
+fn json_of(obj: *mut pyo3_ffi::PyObject, depth: u32) -> Result<Json, String> {
+    if depth > COMPILE_DEPTH_LIMIT {
+        return Err(String::from("nested too deeply"));
+    }
+    unsafe {
+        if obj == NONE {
+            Ok(Json::Null)
+        } else if is_type(obj, BOOL_TYPE) {
+            Ok(Json::Bool(obj == TRUE))
+        } else if is_type(obj, INT_TYPE) || is_type(obj, FLOAT_TYPE) {
+            number(obj, "enum and const").map(Json::Number)
+        } else if is_type(obj, STR_TYPE) {
+            string(obj, "enum and const").map(|val| Json::Str(String::from(val)))
+        } else if is_type(obj, LIST_TYPE) {
+            let mut values = Vec::new();
+            for each in list_items(obj, "enum and const")? {
+                values.push(json_of(each, depth + 1)?);
+            }
+            Ok(Json::Array(values))
+        } else if is_type(obj, DICT_TYPE) {
+            let mut members = Vec::new();
+            for (key, each) in PyDictIter::from_pyobject(obj) {
+                let key = string(key, "keys of enum and const")?;
+                members.push((String::from(key), json_of(each, depth + 1)?));
+            }
+            Ok(Json::Object(members))
+        } else {
+            Err(String::from("enum and const must be JSON values"))
+        }
+    }
+}
+
+impl Compiler {
+    #[cold]
+    fn compile(&mut self, obj: *mut pyo3_ffi::PyObject, depth: u32) -> Result<Node, String> {
+        if depth > COMPILE_DEPTH_LIMIT {
+            return Err(String::from("nested too deeply"));
+        }
+        let mut node = Node::default();
+        unsafe {
+            if is_type(obj, BOOL_TYPE) {
+                node.boolean = Some(obj == TRUE);
+                return Ok(node);
+            }
+            if !is_type(obj, DICT_TYPE) {
+                return Err(String::from("a schema must be an object or boolean"));
+            }
+        }
+        let mut additional_items = None;
+        for (key, val) in PyDictIter::from_pyobject(obj) {
+            let keyword = string(key, "keys")?;
+            match keyword {
+                "type" => node.types = types(val)?,
+                "enum" => {
+                    let mut values = Vec::new();
+                    for each in list_items(val, keyword)? {
+                        values.push(json_of(each, depth + 1)?);
+                    }
+                    node.values = Some(("Value is not one of enum", values));
+                }
+                "const" => {
+                    node.values = Some(("Value is not const", vec![json_of(val, depth + 1)?]))
+                }
+                "$ref" => node.reference = Some(self.reference(string(val, keyword)?)?),
+                "properties" => {
+                    if unsafe { !is_type(val, DICT_TYPE) } {
+                        return Err(String::from("properties must be an object"));
+                    }
+                    for (name, each) in PyDictIter::from_pyobject(val) {
+                        let name = string(name, "keys of properties")?;
+                        node.properties
+                            .push((String::from(name), self.compile(each, depth + 1)?));
+                    }
+                }
+                "required" => {
+                    for each in list_items(val, keyword)? {
+                        node.required.push(String::from(string(each, "required")?));
+                    }
+                }
+                "additionalProperties" => {
+                    node.additional_properties = Some(Box::new(self.compile(val, depth + 1)?))
+                }
+                "items" => {
+                    if unsafe { is_type(val, LIST_TYPE) } {
+                        for each in list_items(val, keyword)? {
+                            node.prefix_items.push(self.compile(each, depth + 1)?);
+                        }
+                    } else {
+                        node.items = Some(Box::new(self.compile(val, depth + 1)?));
+                    }
+                }
+                "prefixItems" => {
+                    for each in list_items(val, keyword)? {
+                        node.prefix_items.push(self.compile(each, depth + 1)?);
+                    }
+                }
+                "additionalItems" => additional_items = Some(self.compile(val, depth + 1)?),
+                "minItems" => node.min_items = Some(non_negative_int(val, keyword)?),
+                "maxItems" => node.max_items = Some(non_negative_int(val, keyword)?),
+                "minProperties" => node.min_properties = Some(non_negative_int(val, keyword)?),
+                "maxProperties" => node.max_properties = Some(non_negative_int(val, keyword)?),
+                "minLength" => node.min_length = Some(non_negative_int(val, keyword)?),
+                "maxLength" => node.max_length = Some(non_negative_int(val, keyword)?),
+                "minimum" => node.minimum = Some(number(val, keyword)?),
+                "maximum" => node.maximum = Some(number(val, keyword)?),
+                "exclusiveMinimum" => node.exclusive_minimum = Some(number(val, keyword)?),
+                "exclusiveMaximum" => node.exclusive_maximum = Some(number(val, keyword)?),
+                "multipleOf" => {
+                    let val = number(val, keyword)?;
+                    if val <= 0.0 {
+                        return Err(String::from("multipleOf must be greater than 0"));
+                    }
+                    node.multiple_of = Some(val);
+                }
+                "allOf" | "anyOf" | "oneOf" => {
+                    let mut nodes = Vec::new();
+                    for each in list_items(val, keyword)? {
+                        nodes.push(self.compile(each, depth + 1)?);
+                    }
+                    if nodes.is_empty() {
+                        return Err(format!("{} must not be empty", keyword));
+                    }
+                    match keyword {
+                        "allOf" => node.all_of = nodes,
+                        "anyOf" => node.any_of = nodes,
+                        _ => node.one_of = nodes,
+                    }
+                }
+                "not" => node.not = Some(Box::new(self.compile(val, depth + 1)?)),
+                _ if UNSUPPORTED_KEYWORDS.contains(&keyword) => {
+                    return Err(format!("{} is not supported", keyword));
+                }
+                _ => {}
+            }
+        }
+        // `additionalItems` applies past the schemas of `items` as an array
+        if node.items.is_none() && !node.prefix_items.is_empty() {
+            node.items = additional_items.map(Box::new);
+        }
+        Ok(node)
+    }
+
+    /// Index of the target of `$ref` `uri`, compiling it when first referred
+    /// to. Only references within the schema, i.e., a JSON pointer in the
+    /// fragment, such as `#/$defs/point`, are supported.
+    #[cold]
+    fn reference(&mut self, uri: &str) -> Result<usize, String> {
+        if let Some(&idx) = self.ref_ids.get(uri) {
+            return Ok(idx);
+        }
+        let pointer = uri
+            .strip_prefix('#')
+            .ok_or_else(|| format!("$ref {:?} is not within the schema", uri))?;
+        let mut target = self.document;
+        for token in pointer.split('/').skip(1) {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            target = unsafe {
+                if is_type(target, DICT_TYPE) {
+                    let key = unicode_from_str(&token);
+                    let each = pyo3_ffi::PyDict_GetItem(target, key);
+                    pyo3_ffi::Py_DECREF(key);
+                    each
+                } else if is_type(target, LIST_TYPE) {
+                    match token.parse::<isize>() {
+                        Ok(idx) if idx >= 0 && idx < pyo3_ffi::Py_SIZE(target) => {
+                            pyo3_ffi::PyList_GET_ITEM(target, idx)
+                        }
+                        _ => null_mut(),
+                    }
+                } else {
+                    null_mut()
+                }
+            };
+            if target.is_null() {
+                return Err(format!("$ref {:?} is not found", uri));
+            }
+        }
+        // reserved before compiling so that a reference to itself resolves
+        let idx = self.refs.len();
+        self.refs.push(None);
+        self.ref_ids.insert(String::from(uri), idx);
+        self.refs[idx] = Some(self.compile(target, 0)?);
+        Ok(idx)
+    }
+}
+
+fn types(val: *mut pyo3_ffi::PyObject) -> Result<u8, String> {
+    let names = if unsafe { is_type(val, LIST_TYPE) } {
+        list_items(val, "type")?
+    } else {
+        vec![val]
+    };
+    let mut bits = 0;
+    for each in names {
+        let name = string(each, "type")?;
+        match TYPE_NAMES.iter().find(|(_, each)| *each == name) {
+            Some((bit, _)) => bits |= bit,
+            None => return Err(format!("type {:?} is not a JSON type", name)),
+        }
+    }
+    Ok(bits)
+}
+
+fn type_names(bits: u8) -> String {
+    TYPE_NAMES
+        .iter()
+        .filter(|(bit, _)| bits & bit != 0)
+        .map(|(_, name)| *name)
+        .collect::<Vec<&str>>()
+        .join(" or ")
+}
+
+fn is_integer(val: f64) -> bool {
+    val.is_finite() && val.fract() == 0.0
+}
+
+fn matches_type(bits: u8, kind: &Kind) -> bool {
+    let bit = match kind {
+        Kind::Null => TYPE_NULL,
+        Kind::Bool(_) => TYPE_BOOLEAN,
+        Kind::Number(val) => {
+            if is_integer(*val) {
+                TYPE_NUMBER | TYPE_INTEGER
+            } else {
+                TYPE_NUMBER
+            }
+        }
+        Kind::Str(_) => TYPE_STRING,
+        Kind::Array => TYPE_ARRAY,
+        Kind::Object => TYPE_OBJECT,
+        Kind::Other => 0,
+    };
+    bits & bit != 0
+}
+
+fn equals<I: Instance>(json: &Json, inst: I) -> bool {
+    match (json, inst.kind()) {
+        (Json::Null, Kind::Null) => true,
+        (Json::Bool(lhs), Kind::Bool(rhs)) => *lhs == rhs,
+        (Json::Number(lhs), Kind::Number(rhs)) => *lhs == rhs,
+        (Json::Str(lhs), Kind::Str(rhs)) => lhs == rhs,
+        (Json::Array(values), Kind::Array) => {
+            values.len() == inst.len()
+                && inst
+                    .each_item(&mut |idx, each| {
+                        if equals(&values[idx], each) {
+                            Ok(())
+                        } else {
+                            Err(PathError::new(String::new()))
+                        }
+                    })
+                    .is_ok()
+        }
+        (Json::Object(members), Kind::Object) => {
+            members.len() == inst.len()
+                && inst
+                    .each_member(
+                        &mut |key, each| match members.iter().find(|(name, _)| name == key) {
+                            Some((_, value)) if equals(value, each) => Ok(()),
+                            _ => Err(PathError::new(String::new())),
+                        },
+                    )
+                    .is_ok()
+        }
+        _ => false,
+    }
+}
+
+impl CompiledSchema {
+    /// Compile `obj`, a JSON Schema as a `dict` or `bool`.
+    #[cold]
+    fn new(obj: *mut pyo3_ffi::PyObject) -> Result<Self, String> {
+        let mut compiler = Compiler {
+            document: obj,
+            refs: Vec::new(),
+            ref_ids: HashMap::new(),
+        };
+        let root = compiler.compile(obj, 0)?;
+        Ok(CompiledSchema {
+            root: root,
+            refs: compiler
+                .refs
+                .into_iter()
+                .map(|each| each.unwrap_or_default())
+                .collect(),
+        })
+    }
+
+    /// Validate `inst`, deserialized with `args`, failing with the first
+    /// violation found, which is located in `data` if it is the text of the
+    /// document. Values nested more than `args.max_depth()` containers deep
+    /// are not validated, as deserializing them fails.
+    pub fn validate<I: Instance>(
+        &self,
+        inst: I,
+        args: &LoadsArgs,
+        data: Option<&'static str>,
+    ) -> Result<(), DeserializeError<'static>> {
+        self.check(&self.root, inst, 0, args.max_depth())
+            .map_err(|err| match data {
+                Some(data) => err.into_error_in(data, args.duplicate_keys == DuplicateKeys::First),
+                None => err.into_error(),
+            })
+    }
+
+    fn check<I: Instance>(
+        &self,
+        node: &Node,
+        inst: I,
+        depth: u32,
+        remaining_depth: u32,
+    ) -> Result<(), PathError> {
+        if let Some(val) = node.boolean {
+            if val {
+                return Ok(());
+            }
+            return Err(PathError::new(String::from("Value is not allowed")));
+        }
+        if let Some(idx) = node.reference {
+            if unlikely!(depth >= REF_DEPTH_LIMIT) {
+                return Err(PathError::new(String::from(
+                    "$ref recursion limit exceeded",
+                )));
+            }
+            self.check(&self.refs[idx], inst, depth + 1, remaining_depth)?;
+        }
+        let kind = inst.kind();
+        if node.types != 0 && !matches_type(node.types, &kind) {
+            return Err(PathError::new(format!(
+                "Expected {}, got {}",
+                type_names(node.types),
+                kind.name()
+            )));
+        }
+        if let Some((message, values)) = &node.values {
+            if !values.iter().any(|each| equals(each, inst)) {
+                return Err(PathError::new(String::from(*message)));
+            }
+        }
+        match kind {
+            Kind::Number(val) => check_number(node, val)?,
+            Kind::Str(val) if node.min_length.is_some() || node.max_length.is_some() => {
+                check_len(
+                    val.chars().count(),
+                    node.min_length,
+                    node.max_length,
+                    "string",
+                    "characters",
+                )?;
+            }
+            Kind::Array if remaining_depth > 0 => self.check_array(node, inst, remaining_depth)?,
+            Kind::Object if remaining_depth > 0 => {
+                self.check_object(node, inst, remaining_depth)?
+            }
+            _ => {}
+        }
+        for each in node.all_of.iter() {
+            self.check(each, inst, depth + 1, remaining_depth)?;
+        }
+        if !node.any_of.is_empty()
+            && !node
+                .any_of
+                .iter()
+                .any(|each| self.check(each, inst, depth + 1, remaining_depth).is_ok())
+        {
+            return Err(PathError::new(String::from(
+                "Value does not match any schema of anyOf",
+            )));
+        }
+        if !node.one_of.is_empty() {
+            let matched = node
+                .one_of
+                .iter()
+                .filter(|each| self.check(each, inst, depth + 1, remaining_depth).is_ok())
+                .count();
+            if matched != 1 {
+                return Err(PathError::new(format!(
+                    "Value matches {} schemas of oneOf, not 1",
+                    matched
+                )));
+            }
+        }
+        if let Some(each) = &node.not {
+            if self.check(each, inst, depth + 1, remaining_depth).is_ok() {
+                return Err(PathError::new(String::from(
+                    "Value matches the schema of not",
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_array<I: Instance>(
+        &self,
+        node: &Node,
+        inst: I,
+        remaining_depth: u32,
+    ) -> Result<(), PathError> {
+        check_len(inst.len(), node.min_items, node.max_items, "array", "items")?;
+        if node.prefix_items.is_empty() && node.items.is_none() {
+            return Ok(());
+        }
+        inst.each_item(&mut |idx, each| {
+            let item_node = match node.prefix_items.get(idx) {
+                Some(item_node) => item_node,
+                None => match &node.items {
+                    Some(item_node) => item_node,
+                    None => return Ok(()),
+                },
+            };
+            self.check(item_node, each, 0, remaining_depth - 1)
+                .map_err(|err| err.at_index(idx))
+        })
+    }
+
+    fn check_object<I: Instance>(
+        &self,
+        node: &Node,
+        inst: I,
+        remaining_depth: u32,
+    ) -> Result<(), PathError> {
+        check_len(
+            inst.len(),
+            node.min_properties,
+            node.max_properties,
+            "object",
+            "properties",
+        )?;
+        let mut found = vec![false; node.required.len()];
+        inst.each_member(&mut |key, each| {
+            if let Some(idx) = node.required.iter().position(|name| name == key) {
+                found[idx] = true;
+            }
+            let member_node = match node.properties.iter().find(|(name, _)| name == key) {
+                Some((_, member_node)) => member_node,
+                None => match &node.additional_properties {
+                    Some(member_node) if member_node.boolean == Some(false) => {
+                        return Err(PathError::new(format!(
+                            "Property \"{}\" is not allowed",
+                            key
+                        )));
+                    }
+                    Some(member_node) => member_node,
+                    None => return Ok(()),
+                },
+            };
+            self.check(member_node, each, 0, remaining_depth - 1)
+                .map_err(|err| err.at_key(key))
+        })?;
+        if let Some(idx) = found.iter().position(|each| !each) {
+            return Err(PathError::new(format!(
+                "Missing required property \"{}\"",
+                node.required[idx]
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn check_len(
+    len: usize,
+    min: Option<usize>,
+    max: Option<usize>,
+    kind: &str,
+    unit: &str,
+) -> Result<(), PathError> {
+    match (min, max) {
+        (Some(min), _) if len < min => Err(PathError::new(format!(
+            "Expected {} of at least {} {}, got {}",
+            kind, min, unit, len
+        ))),
+        (_, Some(max)) if len > max => Err(PathError::new(format!(
+            "Expected {} of at most {} {}, got {}",
+            kind, max, unit, len
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn check_number(node: &Node, val: f64) -> Result<(), PathError> {
+    let bounds = [
+        (
+            node.minimum,
+            "at least",
+            val >= node.minimum.unwrap_or(f64::NAN),
+        ),
+        (
+            node.maximum,
+            "at most",
+            val <= node.maximum.unwrap_or(f64::NAN),
+        ),
+        (
+            node.exclusive_minimum,
+            "greater than",
+            val > node.exclusive_minimum.unwrap_or(f64::NAN),
+        ),
+        (
+            node.exclusive_maximum,
+            "less than",
+            val < node.exclusive_maximum.unwrap_or(f64::NAN),
+        ),
+    ];
+    for (bound, relation, ok) in bounds.iter() {
+        if let Some(bound) = bound {
+            if !ok {
+                return Err(PathError::new(format!(
+                    "Expected a number {} {}, got {}",
+                    relation, bound, val
+                )));
+            }
+        }
+    }
+    if let Some(divisor) = node.multiple_of {
+        if !is_integer(val / divisor) {
+            return Err(PathError::new(format!(
+                "Expected a multiple of {}, got {}",
+                divisor, val
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// A value deserialized to a Python object.
+#[derive(Clone, Copy)]
+struct PyInstance(*mut pyo3_ffi::PyObject);
+
+impl Instance for PyInstance {
+    fn kind(self) -> Kind {
+        let obj = self.0;
+        unsafe {
+            let ob_type = ob_type!(obj);
+            if ob_type == STR_TYPE {
+                Kind::Str(unicode_to_str(obj).unwrap_or_default())
+            } else if ob_type == INT_TYPE {
+                let val = pyo3_ffi::PyLong_AsDouble(obj);
+                if val == -1.0 && !pyo3_ffi::PyErr_Occurred().is_null() {
+                    pyo3_ffi::PyErr_Clear();
+                    // beyond the range of `f64`, so only its sign is kept
+                    let mut overflow: c_int = 0;
+                    pyo3_ffi::PyLong_AsLongLongAndOverflow(obj, &mut overflow);
+                    return Kind::Number(overflow as f64 * f64::INFINITY);
+                }
+                Kind::Number(val)
+            } else if ob_type == FLOAT_TYPE {
+                Kind::Number(pyo3_ffi::PyFloat_AS_DOUBLE(obj))
+            } else if ob_type == BOOL_TYPE {
+                Kind::Bool(obj == TRUE)
+            } else if obj == NONE {
+                Kind::Null
+            } else if ob_type == LIST_TYPE || ob_type == TUPLE_TYPE {
+                Kind::Array
+            } else if ob_type == DICT_TYPE {
+                Kind::Object
+            } else {
+                Kind::Other
+            }
+        }
+    }
+
+    fn len(self) -> usize {
+        ffi!(Py_SIZE(self.0)) as usize
+    }
+
+    fn each_item(
+        self,
+        f: &mut dyn FnMut(usize, Self) -> Result<(), PathError>,
+    ) -> Result<(), PathError> {
+        let is_list = unsafe { ob_type!(self.0) == LIST_TYPE };
+        for idx in 0..ffi!(Py_SIZE(self.0)) {
+            let each = if is_list {
+                ffi!(PyList_GET_ITEM(self.0, idx))
+            } else {
+                ffi!(PyTuple_GET_ITEM(self.0, idx))
+            };
+            f(idx as usize, PyInstance(each))?;
+        }
+        Ok(())
+    }
+
+    fn each_member(
+        self,
+        f: &mut dyn FnMut(&str, Self) -> Result<(), PathError>,
+    ) -> Result<(), PathError> {
+        for (key, each) in PyDictIter::from_pyobject(self.0) {
+            f(unicode_to_str(key).unwrap_or_default(), PyInstance(each))?;
+        }
+        Ok(())
+    }
+}
+
+/// The schema of `args`, if any.
+#[inline(always)]
+pub fn schema_of(args: &LoadsArgs) -> Option<&CompiledSchema> {
+    args.schema
+        .map(|obj| unsafe { &*(*(obj.as_ptr() as *mut SchemaObject)).schema })
+}
+
+/// Validate `obj`, deserialized with `args` from `data` if it is text that is
+/// kept, against their schema, if any, consuming it if it is invalid.
+pub fn check_schema(
+    obj: NonNull<pyo3_ffi::PyObject>,
+    args: &LoadsArgs,
+    data: Option<&'static str>,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if let Some(schema) = schema_of(args) {
+        if let Err(err) = schema.validate(PyInstance(obj.as_ptr()), args, data) {
+            ffi!(Py_DECREF(obj.as_ptr()));
+            return Err(Some(err));
+        }
+    }
+    Ok(obj)
+}
+
+unsafe extern "C" fn schema_new(
+    subtype: *mut pyo3_ffi::PyTypeObject,
+    args: *mut pyo3_ffi::PyObject,
+    kwds: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    if pyo3_ffi::PyTuple_GET_SIZE(args) != 1
+        || (!kwds.is_null() && pyo3_ffi::PyDict_Size(kwds) != 0)
+    {
+        return crate::raise_loads_exception(DeserializeError::invalid(Cow::Borrowed(
+            "Schema() takes exactly 1 positional argument",
+        )));
+    }
+    let schema = match CompiledSchema::new(pyo3_ffi::PyTuple_GET_ITEM(args, 0)) {
+        Ok(schema) => schema,
+        Err(msg) => {
+            return crate::raise_loads_exception(DeserializeError::invalid(Cow::Owned(format!(
+                "Invalid schema: {}",
+                msg
+            ))))
+        }
+    };
+    let obj = pyo3_ffi::PyType_GenericAlloc(subtype, 0);
+    if obj.is_null() {
+        return null_mut();
+    }
+    (*(obj as *mut SchemaObject)).schema = Box::into_raw(Box::new(schema));
+    obj
+}
+
+unsafe extern "C" fn schema_dealloc(slf: *mut pyo3_ffi::PyObject) {
+    drop(Box::from_raw((*(slf as *mut SchemaObject)).schema));
+    let tp = ob_type!(slf);
+    ((*tp).tp_free.unwrap())(slf as *mut c_void);
+    pyo3_ffi::Py_DECREF(tp as *mut pyo3_ffi::PyObject);
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub unsafe fn create_schema_type() -> *mut pyo3_ffi::PyTypeObject {
+    let slots: Box<[pyo3_ffi::PyType_Slot; 4]> = Box::new([
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_new,
+            pfunc: schema_new as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_doc,
+            pfunc: "Schema(schema, /)\n--\n\nJSON Schema compiled to validate documents as they are deserialized.\0".as_ptr() as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_dealloc,
+            pfunc: schema_dealloc as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: 0,
+            pfunc: std::ptr::null_mut(),
+        },
+    ]);
+    let spec = Box::new(pyo3_ffi::PyType_Spec {
+        name: "orjson.Schema\0".as_ptr() as *const c_char,
+        basicsize: std::mem::size_of::<SchemaObject>() as c_int,
+        itemsize: 0,
+        flags: pyo3_ffi::Py_TPFLAGS_DEFAULT as c_uint,
+        slots: Box::into_raw(slots) as *mut pyo3_ffi::PyType_Slot,
+    });
+    pyo3_ffi::PyType_FromSpec(Box::into_raw(spec)) as *mut pyo3_ffi::PyTypeObject
+}
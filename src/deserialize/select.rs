@@ -0,0 +1,496 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::deserializer::{deserialize_bytes, DEFAULT_MAX_DEPTH};
+use crate::deserialize::error::*;
+use crate::deserialize::path::Segment;
+use crate::deserialize::utf8::{is_buffer_input, read_input_to_buf, BufferInput};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::unicode::*;
+use std::borrow::Cow;
+use std::ptr::NonNull;
+
+/// What a step of a query selects of the children of a value.
+enum Selector {
+    /// `.name` or `['name']`
+    Key(String),
+    /// `[0]`
+    Index(usize),
+    /// `[start:end]`
+    Slice(usize, Option<usize>),
+    /// `.*` or `[*]`
+    Wildcard,
+}
+
+impl Selector {
+    fn matches_key(&self, key: &str) -> bool {
+        match self {
+            Selector::Key(name) => name == key,
+            Selector::Wildcard => true,
+            _ => false,
+        }
+    }
+
+    fn matches_index(&self, idx: usize) -> bool {
+        match self {
+            Selector::Index(val) => *val == idx,
+            Selector::Slice(start, end) => idx >= *start && end.map_or(true, |end| idx < end),
+            Selector::Wildcard => true,
+            Selector::Key(_) => false,
+        }
+    }
+}
+
+/// A step of a query, applied to the children of a value or, if
+/// `descendant`, to all of its descendants, as `..`.
+struct Step {
+    descendant: bool,
+    selector: Selector,
+}
+
+/// Parse a JSONPath query such as `$.items[*].price` into its steps.
+#[cold]
+fn parse_query(query: &str) -> Result<Vec<Step>, String> {
+    let mut rest = query
+        .strip_prefix('$')
+        .ok_or_else(|| String::from("must start with $"))?;
+    let mut steps = Vec::new();
+    while !rest.is_empty() {
+        let descendant = rest.starts_with("..");
+        if descendant {
+            rest = &rest[2..];
+        } else if rest.starts_with('.') {
+            rest = &rest[1..];
+        } else if !rest.starts_with('[') {
+            return Err(format!("unexpected {:?}", rest));
+        }
+        let selector;
+        if let Some(bracketed) = rest.strip_prefix('[') {
+            let (each, after) = parse_bracket(bracketed)?;
+            selector = each;
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('*') {
+            selector = Selector::Wildcard;
+            rest = after;
+        } else {
+            let end = rest.find(|c| c == '.' || c == '[').unwrap_or(rest.len());
+            if end == 0 {
+                return Err(String::from("expected a name after ."));
+            }
+            selector = Selector::Key(String::from(&rest[..end]));
+            rest = &rest[end..];
+        }
+        steps.push(Step {
+            descendant: descendant,
+            selector: selector,
+        });
+    }
+    Ok(steps)
+}
+
+/// Parse the selector in `rest`, which follows `[`, and return it with what
+/// follows its `]`.
+#[cold]
+fn parse_bracket(rest: &str) -> Result<(Selector, &str), String> {
+    let rest = rest.trim_start();
+    let (selector, rest) = if let Some(after) = rest.strip_prefix('*') {
+        (Selector::Wildcard, after)
+    } else if rest.starts_with('\'') || rest.starts_with('"') {
+        let quote = rest.as_bytes()[0] as char;
+        let mut name = String::new();
+        let mut chars = rest[1..].char_indices();
+        let mut end = None;
+        while let Some((idx, c)) = chars.next() {
+            if c == quote {
+                end = Some(idx + 2);
+                break;
+            } else if c == '\\' {
+                match chars.next() {
+                    Some((_, escaped)) if escaped == quote || escaped == '\\' => name.push(escaped),
+                    _ => return Err(String::from("invalid escape in name")),
+                }
+            } else {
+                name.push(c);
+            }
+        }
+        match end {
+            Some(end) => (Selector::Key(name), &rest[end..]),
+            None => return Err(String::from("unterminated name")),
+        }
+    } else if rest.starts_with('-') {
+        return Err(String::from("negative indexes are not supported"));
+    } else {
+        let (start, after) = parse_index(rest)?;
+        match after.trim_start().strip_prefix(':') {
+            Some(after) => {
+                let after = after.trim_start();
+                if after.starts_with('-') {
+                    return Err(String::from("negative indexes are not supported"));
+                }
+                let (end, after) = parse_index(after)?;
+                (Selector::Slice(start.unwrap_or(0), end), after)
+            }
+            None => match start {
+                Some(idx) => (Selector::Index(idx), after),
+                None => return Err(String::from("expected an index, name, or *")),
+            },
+        }
+    };
+    match rest.trim_start().strip_prefix(']') {
+        Some(after) => Ok((selector, after)),
+        None => Err(String::from("expected ]")),
+    }
+}
+
+/// Parse the digits, if any, at the start of `rest`.
+#[cold]
+fn parse_index(rest: &str) -> Result<(Option<usize>, &str), String> {
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    if end == 0 {
+        return Ok((None, rest));
+    }
+    match rest[..end].parse::<usize>() {
+        Ok(idx) => Ok((Some(idx), &rest[end..])),
+        Err(_) => Err(String::from("index is too large")),
+    }
+}
+
+/// Scans a document for the values a query selects, skipping over the rest
+/// without deserializing it.
+struct Selection<'a> {
+    data: &'a [u8],
+    pos: usize,
+    steps: Vec<Step>,
+    paths: bool,
+    // the location of the value being scanned, if `paths`
+    path: Vec<Segment>,
+    args: LoadsArgs,
+    results: *mut pyo3_ffi::PyObject,
+}
+
+impl<'a> Selection<'a> {
+    #[cold]
+    fn error(&self, message: &'static str) -> Option<DeserializeError<'static>> {
+        let data = str_from_slice!(self.data.as_ptr(), self.data.len());
+        Some(DeserializeError::at_byte(Cow::Borrowed(message), self.pos, data).into_owned())
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) -> Option<u8> {
+        while let Some(c) = self.peek() {
+            if !matches!(c, b' ' | b'\t' | b'\n' | b'\r') {
+                return Some(c);
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    fn expect(
+        &mut self,
+        c: u8,
+        message: &'static str,
+    ) -> Result<(), Option<DeserializeError<'static>>> {
+        if self.skip_whitespace() != Some(c) {
+            return Err(self.error(message));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Skip the string starting at `self.pos` and return whether it has an
+    /// escape.
+    fn skip_string(&mut self) -> Result<bool, Option<DeserializeError<'static>>> {
+        let mut escaped = false;
+        let mut idx = self.pos + 1;
+        while idx < self.data.len() {
+            match self.data[idx] {
+                b'"' => {
+                    self.pos = idx + 1;
+                    return Ok(escaped);
+                }
+                b'\\' => {
+                    escaped = true;
+                    idx += 2;
+                }
+                _ => idx += 1,
+            }
+        }
+        Err(self.error(UNCLOSED_STRING))
+    }
+
+    /// Skip the value starting at the next byte that is not whitespace.
+    /// Brackets are matched and strings skipped, but the rest is not
+    /// checked, as it is not deserialized.
+    fn skip_value(&mut self) -> Result<(), Option<DeserializeError<'static>>> {
+        match self.skip_whitespace() {
+            None => Err(self.error(UNEXPECTED_END)),
+            Some(b'"') => self.skip_string().map(|_| ()),
+            Some(b'[') | Some(b'{') => {
+                let mut depth: usize = 0;
+                while let Some(c) = self.peek() {
+                    match c {
+                        b'"' => {
+                            self.skip_string()?;
+                            continue;
+                        }
+                        b'[' | b'{' => depth += 1,
+                        b']' | b'}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                self.pos += 1;
+                                return Ok(());
+                            }
+                        }
+                        _ => {}
+                    }
+                    self.pos += 1;
+                }
+                Err(self.error(UNEXPECTED_END))
+            }
+            Some(b']') | Some(b'}') | Some(b',') | Some(b':') => {
+                Err(self.error(UNEXPECTED_CHARACTER))
+            }
+            Some(_) => {
+                while let Some(c) = self.peek() {
+                    if matches!(c, b',' | b']' | b'}' | b':' | b' ' | b'\t' | b'\n' | b'\r') {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// The steps of `states` to continue with for a child of the value
+    /// being scanned, given by whether a selector `matches` it.
+    fn child_states(&self, states: &[usize], matches: impl Fn(&Selector) -> bool) -> Vec<usize> {
+        let mut next: Vec<usize> = Vec::new();
+        for &state in states.iter().filter(|&&state| state < self.steps.len()) {
+            let step = &self.steps[state];
+            if step.descendant && !next.contains(&state) {
+                next.push(state);
+            }
+            if matches(&step.selector) && !next.contains(&(state + 1)) {
+                next.push(state + 1);
+            }
+        }
+        next
+    }
+
+    /// Append the value from `start` to `self.pos`, or its path if
+    /// `self.paths`, to the results.
+    fn emit(&mut self, start: usize) -> Result<(), Option<DeserializeError<'static>>> {
+        let obj = if self.paths {
+            let path = normalized_path(&self.path);
+            unicode_from_str(&path)
+        } else {
+            deserialize_bytes(&self.data[start..self.pos], &self.args, false)?.as_ptr()
+        };
+        ffi!(PyList_Append(self.results, obj));
+        ffi!(Py_DECREF(obj));
+        Ok(())
+    }
+
+    /// Scan the value at the next byte that is not whitespace, of which the
+    /// steps in `states` have been matched. A state equal to the number of
+    /// steps means the value is selected.
+    fn scan(
+        &mut self,
+        states: &[usize],
+        depth: u32,
+    ) -> Result<(), Option<DeserializeError<'static>>> {
+        let first = self.skip_whitespace();
+        let start = self.pos;
+        if states.contains(&self.steps.len()) {
+            self.skip_value()?;
+            self.emit(start)?;
+            if states.len() == 1 {
+                return Ok(());
+            }
+            // its descendants may be selected too, after it
+            self.pos = start;
+        }
+        match first {
+            Some(b'{') => self.scan_object(states, depth),
+            Some(b'[') => self.scan_array(states, depth),
+            _ => self.skip_value(),
+        }
+    }
+
+    fn scan_object(
+        &mut self,
+        states: &[usize],
+        depth: u32,
+    ) -> Result<(), Option<DeserializeError<'static>>> {
+        if unlikely!(depth >= DEFAULT_MAX_DEPTH) {
+            return Err(self.error(RECURSION_LIMIT));
+        }
+        self.pos += 1;
+        if self.skip_whitespace() == Some(b'}') {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            if self.skip_whitespace() != Some(b'"') {
+                return Err(self.error(UNEXPECTED_CHARACTER));
+            }
+            let key_start = self.pos;
+            let escaped = self.skip_string()?;
+            let key: Cow<str> = if escaped {
+                match serde_json::from_slice::<String>(&self.data[key_start..self.pos]) {
+                    Ok(key) => Cow::Owned(key),
+                    Err(_) => return Err(self.error(INVALID_ESCAPE)),
+                }
+            } else {
+                Cow::Borrowed(str_from_slice!(
+                    self.data[key_start + 1..].as_ptr(),
+                    self.pos - key_start - 2
+                ))
+            };
+            self.expect(b':', UNEXPECTED_CHARACTER)?;
+            let next = self.child_states(states, |selector| selector.matches_key(&key));
+            if next.is_empty() {
+                self.skip_value()?;
+            } else {
+                if self.paths {
+                    self.path.push(Segment::Key(key.into_owned()));
+                }
+                self.scan(&next, depth + 1)?;
+                if self.paths {
+                    self.path.pop();
+                }
+            }
+            match self.skip_whitespace() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.error(UNEXPECTED_CHARACTER)),
+            }
+        }
+    }
+
+    fn scan_array(
+        &mut self,
+        states: &[usize],
+        depth: u32,
+    ) -> Result<(), Option<DeserializeError<'static>>> {
+        if unlikely!(depth >= DEFAULT_MAX_DEPTH) {
+            return Err(self.error(RECURSION_LIMIT));
+        }
+        self.pos += 1;
+        if self.skip_whitespace() == Some(b']') {
+            self.pos += 1;
+            return Ok(());
+        }
+        let mut idx: usize = 0;
+        loop {
+            let next = self.child_states(states, |selector| selector.matches_index(idx));
+            if next.is_empty() {
+                self.skip_value()?;
+            } else {
+                if self.paths {
+                    self.path.push(Segment::Index(idx));
+                }
+                self.scan(&next, depth + 1)?;
+                if self.paths {
+                    self.path.pop();
+                }
+            }
+            match self.skip_whitespace() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.error(UNEXPECTED_CHARACTER)),
+            }
+            idx += 1;
+        }
+    }
+}
+
+/// The normalized path of a location, e.g., `$['items'][0]['price']`.
+fn normalized_path(path: &[Segment]) -> String {
+    let mut buf = String::from("$");
+    for segment in path.iter() {
+        match segment {
+            Segment::Index(idx) => {
+                buf.push('[');
+                buf.push_str(&idx.to_string());
+                buf.push(']');
+            }
+            Segment::Key(key) => {
+                buf.push_str("['");
+                for c in key.chars() {
+                    match c {
+                        '\'' => buf.push_str("\\'"),
+                        '\\' => buf.push_str("\\\\"),
+                        '\u{8}' => buf.push_str("\\b"),
+                        '\u{c}' => buf.push_str("\\f"),
+                        '\n' => buf.push_str("\\n"),
+                        '\r' => buf.push_str("\\r"),
+                        '\t' => buf.push_str("\\t"),
+                        c if (c as u32) < 0x20 => {
+                            buf.push_str(&format!("\\u{:04x}", c as u32));
+                        }
+                        c => buf.push(c),
+                    }
+                }
+                buf.push_str("']");
+            }
+        }
+    }
+    buf
+}
+
+/// Return a `list` of the values in `ptr` selected by JSONPath `query`, or
+/// of their normalized paths if `paths`.
+pub fn select(
+    ptr: *mut pyo3_ffi::PyObject,
+    query: &str,
+    paths: bool,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let steps = parse_query(query).map_err(|msg| {
+        Some(DeserializeError::invalid(Cow::Owned(format!(
+            "Invalid path: {}",
+            msg
+        ))))
+    })?;
+    let input;
+    let data = if is_buffer_input(ptr) {
+        input = BufferInput::new(ptr)?;
+        input.as_utf8()?
+    } else {
+        read_input_to_buf(ptr)?
+    };
+    let mut selection = Selection {
+        data: data,
+        pos: 0,
+        steps: steps,
+        paths: paths,
+        path: Vec::new(),
+        args: LoadsArgs::default(),
+        results: ffi!(PyList_New(0)),
+    };
+    let res = selection.scan(&[0], 0).and_then(|_| {
+        if selection.skip_whitespace().is_some() {
+            return Err(selection.error(UNEXPECTED_CONTENT));
+        }
+        Ok(())
+    });
+    match res {
+        Ok(()) => Ok(nonnull!(selection.results)),
+        Err(err) => {
+            ffi!(Py_DECREF(selection.results));
+            Err(err)
+        }
+    }
+}
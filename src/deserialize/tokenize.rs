@@ -0,0 +1,398 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::deserializer::{deserialize_bytes, DEFAULT_MAX_DEPTH};
+use crate::deserialize::error::*;
+use crate::deserialize::iter::{new_iterator, InputReader, Scanner};
+use crate::deserialize::pyobject::{parse_false, parse_none, parse_true};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::typeref::*;
+use crate::unicode::*;
+use std::borrow::Cow;
+use std::ptr::NonNull;
+
+/// The names of the events of `orjson.tokenize()`, nul-terminated, in the
+/// order of `Event`.
+pub const EVENT_NAMES: [&str; 9] = [
+    "start_object\0",
+    "end_object\0",
+    "start_array\0",
+    "end_array\0",
+    "key\0",
+    "string\0",
+    "number\0",
+    "boolean\0",
+    "null\0",
+];
+
+#[derive(Clone, Copy)]
+enum Event {
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+    Key,
+    String,
+    Number,
+    Boolean,
+    Null,
+}
+
+/// Create an iterator over the tokens of the document in `ptr`.
+pub fn tokenize(
+    ptr: *mut pyo3_ffi::PyObject,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let tokenizer = Tokenizer {
+        reader: InputReader::new(ptr)?,
+        state: State::Value,
+        stack: Vec::new(),
+        offset: 0,
+        token: Vec::new(),
+    };
+    new_iterator(unsafe { TOKEN_ITERATOR_TYPE }, Box::new(tokenizer))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum State {
+    // the document, or after `:`, or after `,` in an array
+    Value,
+    // after `[`
+    ValueOrEnd,
+    // after `{`
+    KeyOrEnd,
+    // after `,` in an object
+    Key,
+    // after a key
+    Colon,
+    // after a value in an array or object
+    CommaOrEnd,
+    // after the document
+    Done,
+}
+
+/// Reads the tokens of a document in order, checking its syntax as it goes,
+/// without deserializing containers.
+struct Tokenizer {
+    reader: InputReader,
+    state: State,
+    // `[` or `{` for each container the next token is in
+    stack: Vec<u8>,
+    // bytes of the input consumed
+    offset: usize,
+    // the bytes of the current string or literal, reused across tokens
+    token: Vec<u8>,
+}
+
+/// Whether `literal` is a number as RFC 8259 defines it.
+pub fn is_number(literal: &[u8]) -> bool {
+    let mut idx = 0;
+    let digits = |idx: &mut usize| {
+        let start = *idx;
+        while *idx < literal.len() && literal[*idx].is_ascii_digit() {
+            *idx += 1;
+        }
+        *idx - start
+    };
+    if literal.first() == Some(&b'-') {
+        idx += 1;
+    }
+    let int_start = idx;
+    let int_len = digits(&mut idx);
+    if int_len == 0 || (int_len > 1 && literal[int_start] == b'0') {
+        return false;
+    }
+    if literal.get(idx) == Some(&b'.') {
+        idx += 1;
+        if digits(&mut idx) == 0 {
+            return false;
+        }
+    }
+    if matches!(literal.get(idx), Some(b'e') | Some(b'E')) {
+        idx += 1;
+        if matches!(literal.get(idx), Some(b'+') | Some(b'-')) {
+            idx += 1;
+        }
+        if digits(&mut idx) == 0 {
+            return false;
+        }
+    }
+    idx == literal.len()
+}
+
+impl Tokenizer {
+    #[cold]
+    fn error(&self, message: &str) -> Option<DeserializeError<'static>> {
+        self.error_at(self.reader.location(), message)
+    }
+
+    /// An error at `at`, e.g., the start of a token.
+    #[cold]
+    fn error_at(&self, at: Location, message: &str) -> Option<DeserializeError<'static>> {
+        Some(DeserializeError::at_location(
+            Cow::Owned(String::from(message)),
+            at,
+        ))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.reader.consume(amt);
+        self.offset += amt;
+    }
+
+    /// Skip whitespace and return the next byte, which is not consumed, or
+    /// `None` at the end of the input.
+    fn skip_whitespace(&mut self) -> Result<Option<u8>, Option<DeserializeError<'static>>> {
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            let end = buf
+                .iter()
+                .position(|&c| !matches!(c, b' ' | b'\t' | b'\n' | b'\r'))
+                .unwrap_or(buf.len());
+            self.consume(end);
+            if end < buf.len() {
+                return Ok(Some(buf[end]));
+            }
+        }
+    }
+
+    /// Read the string starting at the next byte into `self.token`, with
+    /// its quotes, and return whether it has an escape.
+    fn read_string(&mut self) -> Result<bool, Option<DeserializeError<'static>>> {
+        self.token.clear();
+        let mut escaped = false;
+        let mut in_escape = false;
+        let mut first = true;
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                return Err(self.error(UNCLOSED_STRING));
+            }
+            let start = if first { 1 } else { 0 };
+            let mut end: Option<usize> = None;
+            for (idx, &c) in buf.iter().enumerate().skip(start) {
+                if in_escape {
+                    in_escape = false;
+                } else if c == b'\\' {
+                    in_escape = true;
+                    escaped = true;
+                } else if c == b'"' {
+                    end = Some(idx + 1);
+                    break;
+                } else if c < 0x20 {
+                    self.consume(idx);
+                    return Err(self.error(CONTROL_CHARACTER));
+                }
+            }
+            first = false;
+            let len = end.unwrap_or(buf.len());
+            self.token.extend_from_slice(&buf[..len]);
+            self.consume(len);
+            if end.is_some() {
+                return Ok(escaped);
+            }
+        }
+    }
+
+    /// The string in `self.token`, read by `read_string()`.
+    fn string(
+        &self,
+        escaped: bool,
+    ) -> Result<*mut pyo3_ffi::PyObject, Option<DeserializeError<'static>>> {
+        if escaped {
+            match serde_json::from_slice::<String>(&self.token) {
+                Ok(val) => Ok(unicode_from_str(&val)),
+                Err(_) => Err(self.error(INVALID_ESCAPE)),
+            }
+        } else {
+            match std::str::from_utf8(&self.token[1..self.token.len() - 1]) {
+                Ok(val) => Ok(unicode_from_str(val)),
+                Err(_) => Err(self.error(crate::error::INVALID_STR)),
+            }
+        }
+    }
+
+    /// Read the number or `true`, `false`, or `null` starting at the next
+    /// byte into `self.token`.
+    fn read_literal(&mut self) -> Result<(), Option<DeserializeError<'static>>> {
+        self.token.clear();
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                return Ok(());
+            }
+            let end = buf
+                .iter()
+                .position(|&c| !(c.is_ascii_alphanumeric() || matches!(c, b'+' | b'-' | b'.')));
+            let len = end.unwrap_or(buf.len());
+            self.token.extend_from_slice(&buf[..len]);
+            self.consume(len);
+            if end.is_some() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// The state after a value, or the end of a container.
+    fn after_value(&mut self) {
+        self.state = if self.stack.is_empty() {
+            State::Done
+        } else {
+            State::CommaOrEnd
+        };
+    }
+
+    fn start(&mut self, c: u8) -> Result<(), Option<DeserializeError<'static>>> {
+        if unlikely!(self.stack.len() >= DEFAULT_MAX_DEPTH as usize) {
+            return Err(self.error(RECURSION_LIMIT));
+        }
+        self.stack.push(c);
+        self.consume(1);
+        self.state = if c == b'{' {
+            State::KeyOrEnd
+        } else {
+            State::ValueOrEnd
+        };
+        Ok(())
+    }
+
+    fn end(&mut self) {
+        self.stack.pop();
+        self.consume(1);
+        self.after_value();
+    }
+
+    /// Read the value starting with `c`.
+    fn read_value(
+        &mut self,
+        c: u8,
+    ) -> Result<(Event, *mut pyo3_ffi::PyObject), Option<DeserializeError<'static>>> {
+        let (event, val) = match c {
+            b'{' => {
+                self.start(c)?;
+                return Ok((Event::StartObject, parse_none().as_ptr()));
+            }
+            b'[' => {
+                self.start(c)?;
+                return Ok((Event::StartArray, parse_none().as_ptr()));
+            }
+            b'"' => {
+                let escaped = self.read_string()?;
+                (Event::String, self.string(escaped)?)
+            }
+            b'-' | b'0'..=b'9' => {
+                let at = self.reader.location();
+                self.read_literal()?;
+                if !is_number(&self.token) {
+                    return Err(self.error_at(at, INVALID_NUMBER));
+                }
+                // the iterator can be advanced by another thread if the GIL
+                // is released
+                let val = deserialize_bytes(&self.token, &LoadsArgs::default(), false)?;
+                (Event::Number, val.as_ptr())
+            }
+            b't' | b'f' | b'n' => {
+                let at = self.reader.location();
+                self.read_literal()?;
+                match &self.token[..] {
+                    b"true" => (Event::Boolean, parse_true().as_ptr()),
+                    b"false" => (Event::Boolean, parse_false().as_ptr()),
+                    b"null" => (Event::Null, parse_none().as_ptr()),
+                    _ => return Err(self.error_at(at, INVALID_LITERAL)),
+                }
+            }
+            _ => return Err(self.error(UNEXPECTED_CHARACTER)),
+        };
+        self.after_value();
+        Ok((event, val))
+    }
+
+    fn next_token(
+        &mut self,
+    ) -> Result<Option<(Event, *mut pyo3_ffi::PyObject, usize)>, Option<DeserializeError<'static>>>
+    {
+        loop {
+            let c = match self.skip_whitespace()? {
+                Some(c) => c,
+                None if self.state == State::Done => return Ok(None),
+                None => return Err(self.error(UNEXPECTED_END)),
+            };
+            let offset = self.offset;
+            match self.state {
+                State::Done => return Err(self.error(UNEXPECTED_CONTENT)),
+                State::Colon => {
+                    if c != b':' {
+                        return Err(self.error(UNEXPECTED_CHARACTER));
+                    }
+                    self.consume(1);
+                    self.state = State::Value;
+                }
+                State::CommaOrEnd => {
+                    let top = *self.stack.last().unwrap();
+                    match c {
+                        b',' => {
+                            self.consume(1);
+                            self.state = if top == b'{' {
+                                State::Key
+                            } else {
+                                State::Value
+                            };
+                        }
+                        b']' if top == b'[' => {
+                            self.end();
+                            return Ok(Some((Event::EndArray, parse_none().as_ptr(), offset)));
+                        }
+                        b'}' if top == b'{' => {
+                            self.end();
+                            return Ok(Some((Event::EndObject, parse_none().as_ptr(), offset)));
+                        }
+                        _ if top == b'[' => return Err(self.error(UNEXPECTED_CHARACTER)),
+                        _ => return Err(self.error(UNEXPECTED_CHARACTER)),
+                    }
+                }
+                State::KeyOrEnd if c == b'}' => {
+                    self.end();
+                    return Ok(Some((Event::EndObject, parse_none().as_ptr(), offset)));
+                }
+                State::KeyOrEnd | State::Key => {
+                    if c != b'"' {
+                        return Err(self.error(UNEXPECTED_CHARACTER));
+                    }
+                    let escaped = self.read_string()?;
+                    let key = self.string(escaped)?;
+                    self.state = State::Colon;
+                    return Ok(Some((Event::Key, key, offset)));
+                }
+                State::ValueOrEnd if c == b']' => {
+                    self.end();
+                    return Ok(Some((Event::EndArray, parse_none().as_ptr(), offset)));
+                }
+                State::Value | State::ValueOrEnd => {
+                    let (event, val) = self.read_value(c)?;
+                    return Ok(Some((event, val, offset)));
+                }
+            }
+        }
+    }
+}
+
+impl Scanner for Tokenizer {
+    fn next(
+        &mut self,
+    ) -> Result<Option<NonNull<pyo3_ffi::PyObject>>, Option<DeserializeError<'static>>> {
+        let (event, val, offset) = match self.next_token()? {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+        unsafe {
+            let name = TOKEN_EVENTS[event as usize];
+            pyo3_ffi::Py_INCREF(name);
+            let tuple = pyo3_ffi::PyTuple_New(3);
+            pyo3_ffi::PyTuple_SET_ITEM(tuple, 0, name);
+            pyo3_ffi::PyTuple_SET_ITEM(tuple, 1, val);
+            pyo3_ffi::PyTuple_SET_ITEM(tuple, 2, pyo3_ffi::PyLong_FromSize_t(offset));
+            Ok(Some(nonnull!(tuple)))
+        }
+    }
+}
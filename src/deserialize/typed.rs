@@ -0,0 +1,474 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::path::PathError;
+use crate::deserialize::{deserialize, DeserializeError, LoadsArgs};
+use crate::ffi::PyDictIter;
+use crate::serialize::{field_options, option_value};
+use crate::typeref::*;
+use crate::unicode::*;
+use associative_cache::replacement::RoundRobinReplacement;
+use associative_cache::*;
+use once_cell::unsync::OnceCell;
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::ptr::NonNull;
+use std::rc::Rc;
+
+/// What `loads_into()` deserializes a value into, from its type hint.
+enum Target {
+    /// Any value, as by `loads()`, for `typing.Any` and hints not supported.
+    Any,
+    Str,
+    Int,
+    /// A `float`, or an `int` converted to one.
+    Float,
+    Bool,
+    None,
+    Optional(Box<Target>),
+    List(Box<Target>),
+    /// A `dict` with `str` keys and values of the target.
+    Dict(Box<Target>),
+    /// An instance of a dataclass, held as a strong reference, from a `dict`.
+    Dataclass(*mut pyo3_ffi::PyObject),
+}
+
+impl Drop for Target {
+    fn drop(&mut self) {
+        if let Target::Dataclass(cls) = self {
+            ffi!(Py_DECREF(*cls));
+        }
+    }
+}
+
+impl Target {
+    /// Name of the target in an error message.
+    #[cold]
+    fn name(&self) -> Cow<'static, str> {
+        match self {
+            Target::Any => Cow::Borrowed("any value"),
+            Target::Str => Cow::Borrowed("str"),
+            Target::Int => Cow::Borrowed("int"),
+            Target::Float => Cow::Borrowed("float"),
+            Target::Bool => Cow::Borrowed("bool"),
+            Target::None => Cow::Borrowed("None"),
+            Target::Optional(each) => Cow::Owned(format!("{} or None", each.name())),
+            Target::List(_) => Cow::Borrowed("list"),
+            Target::Dict(_) => Cow::Borrowed("dict"),
+            Target::Dataclass(cls) => Cow::Owned(type_name(*cls as *mut pyo3_ffi::PyTypeObject)),
+        }
+    }
+}
+
+struct TargetField {
+    // Strong reference to the attribute name, passed as a keyword argument.
+    attr: *mut pyo3_ffi::PyObject,
+    // Strong reference to the key in the document, the attribute name or its
+    // `"alias"`.
+    key: *mut pyo3_ffi::PyObject,
+    target: Target,
+    required: bool,
+}
+
+/// The fields of a dataclass that `loads_into()` passes to it, computed once
+/// per class from `__dataclass_fields__` and its type hints.
+pub struct ClassTarget {
+    // Strong reference to the class's `__dataclass_fields__`, compared against
+    // the class on lookup to detect a type object at a reused address.
+    dataclass_fields: *mut pyo3_ffi::PyObject,
+    fields: Vec<TargetField>,
+}
+
+impl Drop for ClassTarget {
+    fn drop(&mut self) {
+        for field in self.fields.iter() {
+            ffi!(Py_DECREF(field.attr));
+            ffi!(Py_DECREF(field.key));
+        }
+        ffi!(Py_DECREF(self.dataclass_fields));
+    }
+}
+
+pub type ClassTargetMap =
+    AssociativeCache<usize, Rc<ClassTarget>, Capacity64, HashDirectMapped, RoundRobinReplacement>;
+
+pub static mut CLASS_TARGETS: OnceCell<ClassTargetMap> = OnceCell::new();
+
+/// A value that does not match `target`.
+#[cold]
+fn mismatch(target: &Target, val: *mut pyo3_ffi::PyObject) -> PathError {
+    PathError::new(format!(
+        "Expected {}, got {}",
+        target.name(),
+        type_name(ob_type!(val))
+    ))
+}
+
+fn type_name(ob_type: *mut pyo3_ffi::PyTypeObject) -> String {
+    unsafe { CStr::from_ptr((*ob_type).tp_name) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn is_dataclass_type(obj: *mut pyo3_ffi::PyObject) -> bool {
+    ffi!(PyType_Check(obj)) == 1
+        && ffi!(PyDict_Contains(
+            (*(obj as *mut pyo3_ffi::PyTypeObject)).tp_dict,
+            DATACLASS_FIELDS_STR
+        )) == 1
+}
+
+/// New reference to `obj.name` or null, without an exception, if absent.
+fn attr(obj: *mut pyo3_ffi::PyObject, name: &str) -> *mut pyo3_ffi::PyObject {
+    let ptr = ffi!(PyObject_GetAttrString(obj, name.as_ptr() as *const c_char));
+    if ptr.is_null() {
+        ffi!(PyErr_Clear());
+    }
+    ptr
+}
+
+/// The target of the type hint `hint` or `None` if it is not supported.
+fn target(hint: *mut pyo3_ffi::PyObject) -> Option<Target> {
+    unsafe {
+        if hint == STR_TYPE as *mut pyo3_ffi::PyObject {
+            Some(Target::Str)
+        } else if hint == INT_TYPE as *mut pyo3_ffi::PyObject {
+            Some(Target::Int)
+        } else if hint == FLOAT_TYPE as *mut pyo3_ffi::PyObject {
+            Some(Target::Float)
+        } else if hint == BOOL_TYPE as *mut pyo3_ffi::PyObject {
+            Some(Target::Bool)
+        } else if hint == NONE_TYPE as *mut pyo3_ffi::PyObject || hint == NONE {
+            Some(Target::None)
+        } else if hint == LIST_TYPE as *mut pyo3_ffi::PyObject {
+            Some(Target::List(Box::new(Target::Any)))
+        } else if hint == DICT_TYPE as *mut pyo3_ffi::PyObject {
+            Some(Target::Dict(Box::new(Target::Any)))
+        } else if hint == TYPING_TYPES.any {
+            Some(Target::Any)
+        } else if is_dataclass_type(hint) {
+            pyo3_ffi::Py_INCREF(hint);
+            Some(Target::Dataclass(hint))
+        } else {
+            generic_target(hint)
+        }
+    }
+}
+
+/// The target of a subscripted type hint such as `List[int]`, `dict[str, int]`,
+/// or `Optional[int]`.
+#[cold]
+fn generic_target(hint: *mut pyo3_ffi::PyObject) -> Option<Target> {
+    let args = attr(hint, "__args__\0");
+    if args.is_null() {
+        return None;
+    }
+    let origin = attr(hint, "__origin__\0");
+    let res = unsafe { generic_target_of(hint, origin, args) };
+    ffi!(Py_XDECREF(origin));
+    ffi!(Py_DECREF(args));
+    res
+}
+
+unsafe fn generic_target_of(
+    hint: *mut pyo3_ffi::PyObject,
+    origin: *mut pyo3_ffi::PyObject,
+    args: *mut pyo3_ffi::PyObject,
+) -> Option<Target> {
+    if pyo3_ffi::PyTuple_Check(args) == 0 {
+        return None;
+    }
+    let arg = |idx| pyo3_ffi::PyTuple_GET_ITEM(args, idx);
+    let num_args = pyo3_ffi::PyTuple_GET_SIZE(args);
+    if origin == LIST_TYPE as *mut pyo3_ffi::PyObject && num_args == 1 {
+        Some(Target::List(Box::new(
+            target(arg(0)).unwrap_or(Target::Any),
+        )))
+    } else if origin == DICT_TYPE as *mut pyo3_ffi::PyObject
+        && num_args == 2
+        && arg(0) == STR_TYPE as *mut pyo3_ffi::PyObject
+    {
+        Some(Target::Dict(Box::new(
+            target(arg(1)).unwrap_or(Target::Any),
+        )))
+    } else if (origin == TYPING_TYPES.union
+        || ob_type!(hint) as *mut pyo3_ffi::PyObject == TYPING_TYPES.union_type)
+        && num_args == 2
+    {
+        let none = NONE_TYPE as *mut pyo3_ffi::PyObject;
+        let other = if arg(0) == none {
+            arg(1)
+        } else if arg(1) == none {
+            arg(0)
+        } else {
+            return None;
+        };
+        target(other).map(|each| Target::Optional(Box::new(each)))
+    } else {
+        None
+    }
+}
+
+/// Return the cached target of the dataclass `cls`. The entry is reference
+/// counted so that a nested dataclass that evicts it does not invalidate it
+/// for the caller.
+fn class_target(cls: *mut pyo3_ffi::PyObject) -> Result<Rc<ClassTarget>, Option<PathError>> {
+    let ob_type = cls as *mut pyo3_ffi::PyTypeObject;
+    let dataclass_fields = ffi!(PyDict_GetItem((*ob_type).tp_dict, DATACLASS_FIELDS_STR));
+    let cache = unsafe { CLASS_TARGETS.get_mut().unwrap_or_else(|| unreachable!()) };
+    if let Some(each) = cache.get(&(ob_type as usize)) {
+        if each.dataclass_fields == dataclass_fields {
+            return Ok(Rc::clone(each));
+        }
+    }
+    let each = Rc::new(ClassTarget::new(cls, dataclass_fields)?);
+    let cache = unsafe { CLASS_TARGETS.get_mut().unwrap_or_else(|| unreachable!()) };
+    cache.insert(ob_type as usize, Rc::clone(&each));
+    Ok(each)
+}
+
+impl ClassTarget {
+    #[cold]
+    #[inline(never)]
+    fn new(
+        cls: *mut pyo3_ffi::PyObject,
+        dataclass_fields: *mut pyo3_ffi::PyObject,
+    ) -> Result<Self, Option<PathError>> {
+        // resolves hints written as strings, e.g., with `from __future__
+        // import annotations`, and raises if they cannot be
+        let hints = ffi!(PyObject_CallFunctionObjArgs(
+            TYPING_TYPES.get_type_hints,
+            cls,
+            std::ptr::null_mut::<pyo3_ffi::PyObject>()
+        ));
+        if hints.is_null() {
+            return Err(None);
+        }
+        ffi!(Py_INCREF(dataclass_fields));
+        let mut class_target = ClassTarget {
+            dataclass_fields: dataclass_fields,
+            fields: Vec::new(),
+        };
+        let res = class_target.add_fields(hints);
+        ffi!(Py_DECREF(hints));
+        res.map(|_| class_target)
+    }
+
+    fn add_fields(&mut self, hints: *mut pyo3_ffi::PyObject) -> Result<(), Option<PathError>> {
+        for (attr_name, field) in PyDictIter::from_pyobject(self.dataclass_fields) {
+            let field_type = ffi!(PyObject_GetAttr(field, FIELD_TYPE_STR));
+            ffi!(Py_XDECREF(field_type));
+            if unsafe { field_type != FIELD_TYPE.as_ptr() } {
+                ffi!(PyErr_Clear());
+                continue;
+            }
+            let init = attr(field, "init\0");
+            ffi!(Py_XDECREF(init));
+            if init == unsafe { FALSE } {
+                continue;
+            }
+            let mut key = attr_name;
+            let options = field_options(field);
+            if !options.is_null() {
+                let alias = option_value(options, "alias\0");
+                ffi!(Py_DECREF(options));
+                if !alias.is_null() {
+                    ffi!(Py_DECREF(alias));
+                    if unlikely!(unsafe { ob_type!(alias) != STR_TYPE }) {
+                        return Err(Some(PathError::new(String::from(
+                            "dataclass alias must be str",
+                        ))));
+                    }
+                    key = alias;
+                }
+            }
+            let hint = ffi!(PyDict_GetItem(hints, attr_name));
+            let target = if hint.is_null() {
+                Target::Any
+            } else {
+                target(hint).unwrap_or(Target::Any)
+            };
+            ffi!(Py_INCREF(attr_name));
+            ffi!(Py_INCREF(key));
+            self.fields.push(TargetField {
+                attr: attr_name,
+                key: key,
+                target: target,
+                required: has_no_default(field),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn has_no_default(field: *mut pyo3_ffi::PyObject) -> bool {
+    let missing = unsafe { TYPING_TYPES.missing };
+    ["default\0", "default_factory\0"].iter().all(|name| {
+        let value = attr(field, name);
+        ffi!(Py_XDECREF(value));
+        value == missing
+    })
+}
+
+/// Deserialize `data` into an instance of `hint`, a dataclass or a supported
+/// type hint such as `List[Point]`, by checking the objects `loads()` makes
+/// against it and calling each dataclass with its fields.
+pub fn loads_into(
+    hint: *mut pyo3_ffi::PyObject,
+    data: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let target = match target(hint) {
+        Some(Target::Any) if hint != unsafe { TYPING_TYPES.any } => None,
+        each => each,
+    };
+    if target.is_none() {
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+            "loads_into() argument must be a dataclass or a supported type hint",
+        ))));
+    }
+    let obj = deserialize(data, args)?;
+    let res = convert(obj.as_ptr(), &target.unwrap());
+    ffi!(Py_DECREF(obj.as_ptr()));
+    res.map_err(|err| err.map(PathError::into_error))
+}
+
+/// New reference to `val` as `target`.
+fn convert(
+    val: *mut pyo3_ffi::PyObject,
+    target: &Target,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<PathError>> {
+    unsafe {
+        let ob_type = ob_type!(val);
+        let matches = match target {
+            Target::Any => true,
+            Target::Str => ob_type == STR_TYPE,
+            Target::Int => ob_type == INT_TYPE,
+            Target::Float => {
+                if ob_type == INT_TYPE {
+                    return NonNull::new(pyo3_ffi::PyNumber_Float(val)).ok_or(None);
+                }
+                ob_type == FLOAT_TYPE
+            }
+            Target::Bool => ob_type == BOOL_TYPE,
+            Target::None => val == NONE,
+            Target::Optional(each) => {
+                if val == NONE {
+                    true
+                } else {
+                    return convert(val, each).map_err(|err| match err {
+                        Some(inner) if inner.path.is_empty() => Some(mismatch(target, val)),
+                        err => err,
+                    });
+                }
+            }
+            Target::List(each) => {
+                if ob_type != LIST_TYPE {
+                    false
+                } else if let Target::Any = **each {
+                    true
+                } else {
+                    return convert_list(val, each);
+                }
+            }
+            Target::Dict(each) => {
+                if ob_type != DICT_TYPE {
+                    false
+                } else if let Target::Any = **each {
+                    true
+                } else {
+                    return convert_dict(val, each);
+                }
+            }
+            Target::Dataclass(cls) => {
+                if ob_type != DICT_TYPE {
+                    false
+                } else {
+                    return convert_dataclass(val, *cls);
+                }
+            }
+        };
+        if unlikely!(!matches) {
+            return Err(Some(mismatch(target, val)));
+        }
+        pyo3_ffi::Py_INCREF(val);
+        Ok(NonNull::new_unchecked(val))
+    }
+}
+
+fn convert_list(
+    val: *mut pyo3_ffi::PyObject,
+    target: &Target,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<PathError>> {
+    let len = ffi!(Py_SIZE(val));
+    let list = ffi!(PyList_New(len));
+    for idx in 0..len {
+        match convert(ffi!(PyList_GET_ITEM(val, idx)), target) {
+            Ok(each) => ffi!(PyList_SET_ITEM(list, idx, each.as_ptr())),
+            Err(err) => {
+                ffi!(Py_DECREF(list));
+                return Err(err.map(|each| each.at_index(idx as usize)));
+            }
+        }
+    }
+    Ok(nonnull!(list))
+}
+
+fn convert_dict(
+    val: *mut pyo3_ffi::PyObject,
+    target: &Target,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<PathError>> {
+    let dict = ffi!(PyDict_New());
+    for (key, each) in PyDictIter::from_pyobject(val) {
+        match convert(each, target) {
+            Ok(each) => {
+                ffi!(PyDict_SetItem(dict, key, each.as_ptr()));
+                ffi!(Py_DECREF(each.as_ptr()));
+            }
+            Err(err) => {
+                ffi!(Py_DECREF(dict));
+                return Err(err.map(|each| each.at_key(unicode_to_str(key).unwrap_or_default())));
+            }
+        }
+    }
+    Ok(nonnull!(dict))
+}
+
+fn convert_dataclass(
+    val: *mut pyo3_ffi::PyObject,
+    cls: *mut pyo3_ffi::PyObject,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<PathError>> {
+    let class_target = class_target(cls)?;
+    let kwargs = ffi!(PyDict_New());
+    for field in class_target.fields.iter() {
+        let each = ffi!(PyDict_GetItem(val, field.key));
+        if each.is_null() {
+            if unlikely!(field.required) {
+                ffi!(Py_DECREF(kwargs));
+                return Err(Some(PathError::new(format!(
+                    "Missing field \"{}\" of {}",
+                    unicode_to_str(field.key).unwrap_or_default(),
+                    type_name(cls as *mut pyo3_ffi::PyTypeObject)
+                ))));
+            }
+            continue;
+        }
+        match convert(each, &field.target) {
+            Ok(each) => {
+                ffi!(PyDict_SetItem(kwargs, field.attr, each.as_ptr()));
+                ffi!(Py_DECREF(each.as_ptr()));
+            }
+            Err(err) => {
+                ffi!(Py_DECREF(kwargs));
+                return Err(
+                    err.map(|each| each.at_key(unicode_to_str(field.key).unwrap_or_default()))
+                );
+            }
+        }
+    }
+    let empty = ffi!(PyTuple_New(0));
+    let obj = ffi!(PyObject_Call(cls, empty, kwargs));
+    ffi!(Py_DECREF(empty));
+    ffi!(Py_DECREF(kwargs));
+    NonNull::new(obj).ok_or(None)
+}
@@ -6,30 +6,21 @@ use crate::ffi::*;
 use crate::typeref::*;
 use crate::unicode::*;
 use std::borrow::Cow;
+use std::io::{BufRead, Read};
 use std::os::raw::c_char;
 
-#[cfg(target_arch = "x86_64")]
-fn is_valid_utf8(buf: &[u8]) -> bool {
-    if std::is_x86_feature_detected!("sse4.2") {
-        simdutf8::basic::from_utf8(buf).is_ok()
-    } else {
-        encoding_rs::Encoding::utf8_valid_up_to(buf) == buf.len()
-    }
-}
-
-#[cfg(target_arch = "aarch64")]
-fn is_valid_utf8(buf: &[u8]) -> bool {
+/// Whether `buf` is valid UTF-8. The whole input is validated up front by
+/// `simdutf8`, which selects the fastest implementation the CPU supports
+/// when called, as are strings read in chunks by `serde_json`.
+pub fn is_valid_utf8(buf: &[u8]) -> bool {
     simdutf8::basic::from_utf8(buf).is_ok()
 }
 
-#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
-fn is_valid_utf8(buf: &[u8]) -> bool {
-    std::str::from_utf8(buf).is_ok()
-}
-
-pub fn read_input_to_buf(
+/// The contents of `ptr`, which are valid UTF-8 if it is a `str` but are not
+/// validated otherwise.
+pub fn read_input_bytes(
     ptr: *mut pyo3_ffi::PyObject,
-) -> Result<&'static [u8], DeserializeError<'static>> {
+) -> Result<&'static [u8], Option<DeserializeError<'static>>> {
     let obj_type_ptr = ob_type!(ptr);
     let buffer: &[u8];
     if is_type!(obj_type_ptr, BYTES_TYPE) {
@@ -39,29 +30,26 @@ pub fn read_input_to_buf(
                 PyBytes_GET_SIZE(ptr) as usize,
             )
         };
-        if !is_valid_utf8(buffer) {
-            return Err(DeserializeError::invalid(Cow::Borrowed(INVALID_STR)));
-        }
     } else if is_type!(obj_type_ptr, STR_TYPE) {
         let uni = unicode_to_str(ptr);
         if unlikely!(uni.is_none()) {
-            return Err(DeserializeError::invalid(Cow::Borrowed(INVALID_STR)));
+            return Err(Some(DeserializeError::invalid(Cow::Borrowed(INVALID_STR))));
         }
         let as_str = uni.unwrap();
         buffer = unsafe { std::slice::from_raw_parts(as_str.as_ptr(), as_str.len()) };
     } else if unlikely!(is_type!(obj_type_ptr, MEMORYVIEW_TYPE)) {
         let membuf = unsafe { PyMemoryView_GET_BUFFER(ptr) };
         if unsafe { pyo3_ffi::PyBuffer_IsContiguous(membuf, b'C' as c_char) == 0 } {
-            return Err(DeserializeError::invalid(Cow::Borrowed(
+            return Err(Some(DeserializeError::invalid(Cow::Borrowed(
                 "Input type memoryview must be a C contiguous buffer",
-            )));
+            ))));
+        }
+        if unsafe { (*membuf).itemsize != 1 } {
+            return Err(raise_not_bytes(unsafe { (*membuf).format }));
         }
         buffer = unsafe {
             std::slice::from_raw_parts((*membuf).buf as *const u8, (*membuf).len as usize)
         };
-        if !is_valid_utf8(buffer) {
-            return Err(DeserializeError::invalid(Cow::Borrowed(INVALID_STR)));
-        }
     } else if unlikely!(is_type!(obj_type_ptr, BYTEARRAY_TYPE)) {
         buffer = unsafe {
             std::slice::from_raw_parts(
@@ -69,13 +57,195 @@ pub fn read_input_to_buf(
                 ffi!(PyByteArray_Size(ptr)) as usize,
             )
         };
+    } else {
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+            "Input must be bytes, bytearray, memoryview, str, or a buffer",
+        ))));
+    }
+    Ok(buffer)
+}
+
+/// The contents of `ptr` if they are valid UTF-8.
+pub fn read_input_to_buf(
+    ptr: *mut pyo3_ffi::PyObject,
+) -> Result<&'static [u8], Option<DeserializeError<'static>>> {
+    let buffer = read_input_bytes(ptr)?;
+    if !is_type!(ob_type!(ptr), STR_TYPE) && !is_valid_utf8(buffer) {
+        return Err(Some(DeserializeError::invalid(Cow::Borrowed(INVALID_STR))));
+    }
+    Ok(buffer)
+}
+
+/// Raise `TypeError` for a buffer of items other than bytes, e.g., of
+/// `array.array("i")`, which would otherwise be read as the bytes of its
+/// memory. `format` is the `struct` format of its items, if given.
+#[cold]
+fn raise_not_bytes(format: *const c_char) -> Option<DeserializeError<'static>> {
+    let format = if format.is_null() {
+        Cow::Borrowed("B")
+    } else {
+        unsafe { std::ffi::CStr::from_ptr(format) }.to_string_lossy()
+    };
+    let msg = format!(
+        "Input buffer must be of bytes, not of format '{}'\0",
+        format
+    );
+    ffi!(PyErr_SetString(
+        pyo3_ffi::PyExc_TypeError,
+        msg.as_ptr() as *const c_char
+    ));
+    None
+}
+
+/// Whether `ptr` is input other than the types `read_input_to_buf()` reads
+/// that can be read using the buffer protocol.
+#[inline(always)]
+pub fn is_buffer_input(ptr: *mut pyo3_ffi::PyObject) -> bool {
+    let obj_type_ptr = ob_type!(ptr);
+    !is_type!(obj_type_ptr, BYTES_TYPE)
+        && !is_type!(obj_type_ptr, STR_TYPE)
+        && !is_type!(obj_type_ptr, MEMORYVIEW_TYPE)
+        && !is_type!(obj_type_ptr, BYTEARRAY_TYPE)
+        && ffi!(PyObject_CheckBuffer(ptr)) == 1
+}
+
+/// A C contiguous buffer exported by an object supporting the buffer
+/// protocol, such as `mmap.mmap` or `array.array`, read without copying and
+/// released when dropped.
+pub struct BufferInput {
+    view: pyo3_ffi::Py_buffer,
+}
+
+impl BufferInput {
+    #[cold]
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Result<Self, Option<DeserializeError<'static>>> {
+        let mut view = std::mem::MaybeUninit::<pyo3_ffi::Py_buffer>::uninit();
+        if ffi!(PyObject_GetBuffer(
+            ptr,
+            view.as_mut_ptr(),
+            pyo3_ffi::PyBUF_CONTIG_RO | pyo3_ffi::PyBUF_FORMAT
+        )) != 0
+        {
+            ffi!(PyErr_Clear());
+            return Err(Some(DeserializeError::invalid(Cow::Borrowed(
+                "Input must be a C contiguous buffer",
+            ))));
+        }
+        let input = BufferInput {
+            view: unsafe { view.assume_init() },
+        };
+        if input.view.itemsize != 1 {
+            return Err(raise_not_bytes(input.view.format));
+        }
+        Ok(input)
+    }
+
+    /// The contents of the buffer. They must not be used after `self` is
+    /// dropped.
+    pub fn as_bytes(&self) -> &'static [u8] {
+        if self.view.len == 0 {
+            &[]
+        } else {
+            unsafe {
+                std::slice::from_raw_parts(self.view.buf as *const u8, self.view.len as usize)
+            }
+        }
+    }
+
+    /// The contents of the buffer if they are valid UTF-8. They must not be
+    /// used after `self` is dropped.
+    pub fn as_utf8(&self) -> Result<&'static [u8], DeserializeError<'static>> {
+        let buffer = self.as_bytes();
         if !is_valid_utf8(buffer) {
             return Err(DeserializeError::invalid(Cow::Borrowed(INVALID_STR)));
         }
-    } else {
-        return Err(DeserializeError::invalid(Cow::Borrowed(
-            "Input must be bytes, bytearray, memoryview, or str",
-        )));
+        Ok(buffer)
+    }
+}
+
+impl Drop for BufferInput {
+    fn drop(&mut self) {
+        ffi!(PyBuffer_Release(&mut self.view));
+    }
+}
+
+/// Reader of `inner` with each sequence of bytes that is not valid UTF-8
+/// replaced with U+FFFD REPLACEMENT CHARACTER, as `String::from_utf8_lossy()`
+/// replaces them, for input that is not held in memory in full.
+pub struct LossyUtf8Reader<R: BufRead> {
+    inner: R,
+    // bytes at the start of the buffer of `inner` known to be valid
+    valid: usize,
+    // bytes to return before reading `inner` again, i.e., a replacement or a
+    // character split between reads of `inner`
+    pending: Vec<u8>,
+}
+
+impl<R: BufRead> LossyUtf8Reader<R> {
+    pub fn new(inner: R) -> Self {
+        LossyUtf8Reader {
+            inner: inner,
+            valid: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Read the rest of the sequence starting with `seq`, which is incomplete
+    /// at the end of the buffer of `inner`, into `self.pending`, replacing it
+    /// if it is not valid.
+    #[cold]
+    fn read_split(&mut self, mut seq: Vec<u8>) -> std::io::Result<()> {
+        while let Some(&next) = self.inner.fill_buf()?.first() {
+            seq.push(next);
+            self.inner.consume(1);
+            match std::str::from_utf8(&seq) {
+                Ok(_) => {
+                    self.pending = seq;
+                    return Ok(());
+                }
+                Err(err) if err.error_len().is_some() => break,
+                Err(_) => {}
+            }
+        }
+        self.pending = String::from_utf8_lossy(&seq).into_owned().into_bytes();
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Read for LossyUtf8Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() && self.valid == 0 {
+            let data = self.inner.fill_buf()?;
+            if data.is_empty() {
+                return Ok(0);
+            }
+            match std::str::from_utf8(data) {
+                Ok(_) => self.valid = data.len(),
+                Err(err) if err.valid_up_to() > 0 => self.valid = err.valid_up_to(),
+                Err(err) => match err.error_len() {
+                    Some(len) => {
+                        self.inner.consume(len);
+                        self.pending = "\u{FFFD}".as_bytes().to_vec();
+                    }
+                    None => {
+                        let seq = data.to_vec();
+                        self.inner.consume(seq.len());
+                        self.read_split(seq)?;
+                    }
+                },
+            }
+        }
+        if !self.pending.is_empty() {
+            let len = buf.len().min(self.pending.len());
+            buf[..len].copy_from_slice(&self.pending[..len]);
+            self.pending.drain(..len);
+            return Ok(len);
+        }
+        let data = self.inner.fill_buf()?;
+        let len = buf.len().min(self.valid);
+        buf[..len].copy_from_slice(&data[..len]);
+        self.inner.consume(len);
+        self.valid -= len;
+        Ok(len)
     }
-    Ok(buffer)
 }
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::pyobject::{parse_false, parse_none, parse_true};
+use crate::deserialize::utf8::{is_buffer_input, read_input_to_buf, BufferInput};
+use crate::deserialize::{DeserializeError, LoadsArgs};
+use crate::error::INVALID_STR;
+use crate::typeref::*;
+use std::ptr::NonNull;
+
+/// Whether `ptr` is a document `loads()` would deserialize with `args`,
+/// checked without making any Python object. If `position`, return `None`
+/// if it is, or else the `pos` of the `JSONDecodeError` that `loads()` would
+/// raise. An error is for input of a type `loads()` does not accept.
+pub fn is_valid(
+    ptr: *mut pyo3_ffi::PyObject,
+    args: &LoadsArgs,
+    position: bool,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let res = if unlikely!(is_buffer_input(ptr)) {
+        let input = BufferInput::new(ptr)?;
+        input
+            .as_utf8()
+            .and_then(|buffer| check(buffer, args, true))
+            .map_err(|err| err.pos())
+    } else {
+        let obj_type_ptr = ob_type!(ptr);
+        let release_gil =
+            !is_type!(obj_type_ptr, BYTEARRAY_TYPE) && !is_type!(obj_type_ptr, MEMORYVIEW_TYPE);
+        match read_input_to_buf(ptr) {
+            Ok(buffer) => check(buffer, args, release_gil).map_err(|err| err.pos()),
+            Err(Some(err)) if err.message == INVALID_STR => {
+                // encoding a `str` with surrogates raised
+                ffi!(PyErr_Clear());
+                Err(err.pos())
+            }
+            Err(err) => return Err(err),
+        }
+    };
+    Ok(match (res, position) {
+        (Ok(()), false) => parse_true(),
+        (Err(_), false) => parse_false(),
+        (Ok(()), true) => parse_none(),
+        (Err(pos), true) => nonnull!(ffi!(PyLong_FromLongLong(pos))),
+    })
+}
+
+/// Check UTF-8 `buffer`. If `release_gil`, no other thread has access to it.
+fn check(
+    buffer: &'static [u8],
+    args: &LoadsArgs,
+    release_gil: bool,
+) -> Result<(), DeserializeError<'static>> {
+    args.check_bytes(buffer)?;
+    let buffer_str = unsafe { std::str::from_utf8_unchecked(buffer) };
+
+    #[cfg(feature = "yyjson")]
+    {
+        crate::deserialize::yyjson::check_yyjson(buffer_str, args, release_gil)
+    }
+
+    #[cfg(not(feature = "yyjson"))]
+    {
+        let _ = release_gil;
+        crate::deserialize::json::check_json(buffer_str, args)
+    }
+}
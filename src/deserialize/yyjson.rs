@@ -1,11 +1,17 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+use crate::deserialize::columns::{not_records, Columns};
+use crate::deserialize::error::RECURSION_LIMIT;
+use crate::deserialize::numpy::NumericArray;
+use crate::deserialize::path::PathError;
 use crate::deserialize::pyobject::*;
-use crate::deserialize::DeserializeError;
+use crate::deserialize::schema::{schema_of, Instance, Kind};
+use crate::deserialize::{DeserializeError, DuplicateKeys, LoadsArgs, Surrogates};
 use crate::typeref::*;
-use crate::unicode::*;
 use crate::yyjson::*;
+use smallvec::SmallVec;
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::os::raw::c_char;
 use std::ptr::{null, null_mut, NonNull};
 
@@ -23,15 +29,16 @@ const TAG_FALSE: u8 = 0b00000011;
 const TAG_INT64: u8 = 0b00001100;
 const TAG_NULL: u8 = 0b00000010;
 const TAG_OBJECT: u8 = 0b00000111;
+const TAG_RAW: u8 = 0b00000001;
 const TAG_STRING: u8 = 0b00000101;
 const TAG_TRUE: u8 = 0b00001011;
 const TAG_UINT64: u8 = 0b00000100;
 
-fn yyjson_doc_get_root(doc: *mut yyjson_doc) -> *mut yyjson_val {
+pub fn yyjson_doc_get_root(doc: *mut yyjson_doc) -> *mut yyjson_val {
     unsafe { (*doc).root }
 }
 
-fn unsafe_yyjson_get_len(val: *mut yyjson_val) -> usize {
+pub fn unsafe_yyjson_get_len(val: *mut yyjson_val) -> usize {
     unsafe { ((*val).tag >> YYJSON_TAG_BIT) as usize }
 }
 
@@ -79,9 +86,35 @@ fn yyjson_obj_iter_next(iter: &mut yyjson_obj_iter) -> *mut yyjson_val {
     }
 }
 
+/// The flags to read a document with given `args`.
+pub fn read_flags(args: &LoadsArgs) -> yyjson_read_flag {
+    let mut flags = YYJSON_READ_NOFLAG;
+    if args.reads_raw_numbers() {
+        flags |= YYJSON_READ_NUMBER_AS_RAW;
+    } else {
+        flags |= YYJSON_READ_BIGNUM_AS_RAW;
+    }
+    if args.allow_nan {
+        flags |= YYJSON_READ_ALLOW_INF_AND_NAN;
+    }
+    if args.jsonc {
+        flags |= YYJSON_READ_ALLOW_COMMENTS | YYJSON_READ_ALLOW_TRAILING_COMMAS;
+    }
+    match args.surrogates {
+        Surrogates::Reject => {}
+        Surrogates::Replace => flags |= YYJSON_READ_REPLACE_LONE_SURROGATES,
+        Surrogates::Pass => flags |= YYJSON_READ_ALLOW_LONE_SURROGATES,
+    }
+    flags
+}
+
+/// Deserialize `data`, parsing it without holding the GIL if `release_gil`
+/// and it is too large for the buffer shared by calls holding the GIL.
 pub fn deserialize_yyjson(
     data: &'static str,
-) -> Result<NonNull<pyo3_ffi::PyObject>, DeserializeError<'static>> {
+    args: &LoadsArgs,
+    release_gil: bool,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
     unsafe {
         let allocator: *mut yyjson_alc;
         if yyjson_read_max_memory_usage(data.len()) < YYJSON_BUFFER_SIZE {
@@ -94,23 +127,220 @@ pub fn deserialize_yyjson(
             msg: null(),
             pos: 0,
         };
-        let doc: *mut yyjson_doc = yyjson_read_opts(
-            data.as_ptr() as *mut c_char,
-            data.len(),
-            YYJSON_READ_NOFLAG,
-            allocator,
-            &mut err,
-        );
+        let flags = read_flags(args);
+        if unlikely!(args.threads.is_some()) && allocator.is_null() {
+            if let Some(ret) =
+                crate::deserialize::parallel::deserialize_parallel(data, args, flags, release_gil)
+            {
+                return ret;
+            }
+        }
+        let mut read = || {
+            yyjson_read_opts(
+                data.as_ptr() as *mut c_char,
+                data.len(),
+                flags,
+                allocator,
+                &mut err,
+            )
+        };
+        let doc: *mut yyjson_doc;
+        if release_gil && allocator.is_null() {
+            // the document is read into memory not used by other calls, and
+            // Python objects are made from it once the GIL is held again
+            let tstate = pyo3_ffi::PyEval_SaveThread();
+            doc = read();
+            pyo3_ffi::PyEval_RestoreThread(tstate);
+        } else {
+            doc = read();
+        }
         if unlikely!(doc.is_null()) {
             let msg: Cow<str> = std::ffi::CStr::from_ptr(err.msg).to_string_lossy();
-            Err(DeserializeError::from_yyjson(msg, err.pos as i64, data))
+            Err(Some(DeserializeError::from_yyjson(
+                msg,
+                err.pos as i64,
+                data,
+            )))
         } else {
             let root = yyjson_doc_get_root(doc);
-            let ret = parse_node(root);
+            if let Some(schema) = schema_of(args) {
+                // before any Python object is made for the document
+                if let Err(err) = schema.validate(YyjsonInstance(root), args, Some(data)) {
+                    yyjson_doc_free(doc);
+                    return Err(Some(err));
+                }
+            }
+            let text = DocText::new(data, root);
+            let ret = if unlikely!(args.columns) {
+                parse_yy_columns(root, args, text)
+            } else {
+                parse_node(root, args, args.max_depth(), text)
+            };
             yyjson_doc_free(doc);
-            Ok(ret)
+            ret
+        }
+    }
+}
+
+/// Deserialize the value at the start of `data`, which may be followed by
+/// anything, and return it with the number of bytes of `data` it and the
+/// whitespace before it take.
+pub fn deserialize_yyjson_prefix(
+    data: &'static str,
+    args: &LoadsArgs,
+    release_gil: bool,
+) -> Result<(NonNull<pyo3_ffi::PyObject>, usize), Option<DeserializeError<'static>>> {
+    unsafe {
+        let mut err = yyjson_read_err {
+            code: YYJSON_READ_SUCCESS,
+            msg: null(),
+            pos: 0,
+        };
+        // the memory read uses is not bounded by the length of `data`, so it
+        // is allocated rather than from the pool
+        let mut read = || {
+            yyjson_read_opts(
+                data.as_ptr() as *mut c_char,
+                data.len(),
+                read_flags(args) | YYJSON_READ_STOP_WHEN_DONE,
+                null_mut(),
+                &mut err,
+            )
+        };
+        let doc = if release_gil {
+            let tstate = pyo3_ffi::PyEval_SaveThread();
+            let doc = read();
+            pyo3_ffi::PyEval_RestoreThread(tstate);
+            doc
+        } else {
+            read()
+        };
+        if unlikely!(doc.is_null()) {
+            let msg: Cow<str> = std::ffi::CStr::from_ptr(err.msg).to_string_lossy();
+            return Err(Some(DeserializeError::from_yyjson(
+                msg,
+                err.pos as i64,
+                data,
+            )));
+        }
+        let root = yyjson_doc_get_root(doc);
+        if let Some(schema) = schema_of(args) {
+            if let Err(err) = schema.validate(YyjsonInstance(root), args, Some(data)) {
+                yyjson_doc_free(doc);
+                return Err(Some(err));
+            }
+        }
+        let ret = parse_node(root, args, args.max_depth(), DocText::new(data, root));
+        let end = (*doc).dat_read;
+        yyjson_doc_free(doc);
+        ret.map(|obj| (obj, end))
+    }
+}
+
+/// The text of a document read by yyjson, to locate an error in one of its
+/// values, as yyjson does not keep where each starts.
+#[derive(Clone, Copy)]
+pub struct DocText {
+    data: &'static str,
+    // the value from which values are counted, and the byte of `data` at
+    // which it starts
+    first: *mut yyjson_val,
+    start: usize,
+}
+
+impl DocText {
+    /// The text of a document that is all of `data`, of which `root` is the
+    /// root.
+    pub fn new(data: &'static str, root: *mut yyjson_val) -> Self {
+        DocText {
+            data: data,
+            first: root,
+            start: 0,
         }
     }
+
+    /// The text of the elements of array `root`, of which the first starts
+    /// at byte `start` of `data`, e.g., a part of an array read by itself.
+    pub fn elements(data: &'static str, root: *mut yyjson_val, start: usize) -> Self {
+        DocText {
+            data: data,
+            first: unsafe { root.add(1) },
+            start: start,
+        }
+    }
+
+    /// Locate `err`, an error without a position, e.g., a limit exceeded, at
+    /// the start of `val`, the value it is in. An error with a position is
+    /// returned as it is.
+    #[cold]
+    #[inline(never)]
+    pub fn locate(
+        self,
+        err: DeserializeError<'static>,
+        val: *mut yyjson_val,
+    ) -> DeserializeError<'static> {
+        if err.data.is_some() || err.start.is_some() {
+            return err;
+        }
+        // values are laid out in the order they are written, each key
+        // before its value
+        let idx = unsafe { val.offset_from(self.first) } as usize;
+        let pos = value_start(self.data.as_bytes(), self.start, idx);
+        DeserializeError::from_yyjson(err.message, pos as i64, self.data)
+    }
+}
+
+/// The byte of `data` at which the value `idx` values after the one at `pos`
+/// starts, counting keys, and the values in a container after it. `data` is
+/// valid, so only where each value starts is looked for.
+fn value_start(data: &[u8], mut pos: usize, idx: usize) -> usize {
+    let is_end = |c: u8| {
+        matches!(
+            c,
+            b' ' | b'\t' | b'\n' | b'\r' | b',' | b':' | b']' | b'}' | b'/'
+        )
+    };
+    let mut count = 0;
+    while pos < data.len() {
+        match data[pos] {
+            b' ' | b'\t' | b'\n' | b'\r' | b',' | b':' | b']' | b'}' => pos += 1,
+            // a comment, if `jsonc`
+            b'/' if data.get(pos + 1) == Some(&b'/') => {
+                pos += data[pos..]
+                    .iter()
+                    .position(|&c| c == b'\n')
+                    .unwrap_or(data.len() - pos);
+            }
+            b'/' => {
+                pos += data[pos + 2..]
+                    .windows(2)
+                    .position(|pair| pair == b"*/")
+                    .map_or(data.len() - pos, |end| end + 4);
+            }
+            c => {
+                if count == idx {
+                    return pos;
+                }
+                count += 1;
+                pos += 1;
+                match c {
+                    b'[' | b'{' => {}
+                    b'"' => {
+                        while pos < data.len() && data[pos] != b'"' {
+                            pos += if data[pos] == b'\\' { 2 } else { 1 };
+                        }
+                        pos += 1;
+                    }
+                    _ => {
+                        while pos < data.len() && !is_end(data[pos]) {
+                            pos += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    data.len()
 }
 
 enum ElementType {
@@ -123,6 +353,7 @@ enum ElementType {
     False,
     Array,
     Object,
+    Raw,
 }
 
 impl ElementType {
@@ -137,83 +368,610 @@ impl ElementType {
             TAG_FALSE => Self::False,
             TAG_ARRAY => Self::Array,
             TAG_OBJECT => Self::Object,
+            TAG_RAW => Self::Raw,
             _ => unsafe { std::hint::unreachable_unchecked() },
         }
     }
 }
 
-fn parse_yy_string(elem: *mut yyjson_val) -> NonNull<pyo3_ffi::PyObject> {
-    nonnull!(unicode_from_str(str_from_slice!(
-        (*elem).uni.str_ as *const u8,
-        unsafe_yyjson_get_len(elem)
-    )))
+fn parse_yy_string(
+    elem: *mut yyjson_val,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let val = str_from_slice!((*elem).uni.str_ as *const u8, unsafe_yyjson_get_len(elem));
+    args.check_string_len(val)?;
+    parse_str(val, args)
+}
+
+/// Deserialize the elements of array `elem` into `list` from index `start`.
+/// On error, the elements set remain in `list`.
+#[inline(always)]
+pub fn parse_yy_array_items(
+    elem: *mut yyjson_val,
+    list: *mut pyo3_ffi::PyObject,
+    start: usize,
+    args: &LoadsArgs,
+    remaining_depth: u32,
+    text: DocText,
+) -> Result<(), Option<DeserializeError<'static>>> {
+    let len = unsafe_yyjson_get_len(elem);
+    if len == 0 {
+        return Ok(());
+    }
+    let mut iter: yyjson_arr_iter = yyjson_arr_iter {
+        idx: 0,
+        max: len,
+        cur: unsafe_yyjson_get_first(elem),
+    };
+    for idx in 0..=len - 1 {
+        let val = yyjson_arr_iter_next(&mut iter);
+        let each = parse_node(val, args, remaining_depth - 1, text)?;
+        set_array_item(list, start + idx, each.as_ptr(), args);
+    }
+    Ok(())
+}
+
+/// Append the elements of array `elem` to `numbers`. `false` means an element
+/// is not a number or does not fit with the others, so `elem` is to be
+/// deserialized to a `list`.
+pub fn collect_yy_numbers(
+    elem: *mut yyjson_val,
+    numbers: &mut NumericArray,
+    args: &LoadsArgs,
+) -> Result<bool, Option<DeserializeError<'static>>> {
+    let len = unsafe_yyjson_get_len(elem);
+    let mut iter: yyjson_arr_iter = yyjson_arr_iter {
+        idx: 0,
+        max: len,
+        cur: unsafe_yyjson_get_first(elem),
+    };
+    for _ in 0..len {
+        let val = yyjson_arr_iter_next(&mut iter);
+        let number = match ElementType::from_tag(val) {
+            ElementType::Uint64 => Number::Uint(unsafe { (*val).uni.u64_ }),
+            ElementType::Int64 => Number::Int(unsafe { (*val).uni.i64_ }),
+            ElementType::Double => Number::Float(unsafe { (*val).uni.f64_ }),
+            ElementType::Raw => {
+                let literal =
+                    str_from_slice!((*val).uni.str_ as *const u8, unsafe_yyjson_get_len(val));
+                match read_number_literal(literal, args)? {
+                    Some(number) => number,
+                    None => return Ok(false),
+                }
+            }
+            _ => return Ok(false),
+        };
+        if !numbers.push(number) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
 }
 
+/// Deserialize `root`, an array of objects, to columns as `loads_columns()`
+/// does.
+#[cold]
 #[inline(never)]
-fn parse_yy_array(elem: *mut yyjson_val) -> NonNull<pyo3_ffi::PyObject> {
+fn parse_yy_columns(
+    root: *mut yyjson_val,
+    args: &LoadsArgs,
+    text: DocText,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    if !matches!(ElementType::from_tag(root), ElementType::Array) {
+        return Err(Some(not_records()));
+    }
+    let mut columns = Columns::new();
     unsafe {
-        let len = unsafe_yyjson_get_len(elem);
-        let list = ffi!(PyList_New(len as isize));
-        if len == 0 {
-            return nonnull!(list);
+        let len = unsafe_yyjson_get_len(root);
+        // the array and its objects are nested as they would be deserialized
+        if args.max_depth() < if len == 0 { 1 } else { 2 } {
+            let err = DeserializeError::invalid(Cow::Borrowed(RECURSION_LIMIT));
+            return Err(Some(text.locate(err, root)));
         }
-        let mut iter: yyjson_arr_iter = yyjson_arr_iter {
+        add_container_items(root, len, args, text)?;
+        let mut iter = yyjson_arr_iter {
             idx: 0,
             max: len,
+            cur: unsafe_yyjson_get_first(root),
+        };
+        for _ in 0..len {
+            let row = yyjson_arr_iter_next(&mut iter);
+            if !matches!(ElementType::from_tag(row), ElementType::Object) {
+                return Err(Some(not_records()));
+            }
+            let members = unsafe_yyjson_get_len(row);
+            add_container_items(row, members, args, text)?;
+            let mut members_iter = yyjson_obj_iter {
+                idx: 0,
+                max: members,
+                cur: unsafe_yyjson_get_first(row),
+                obj: row,
+            };
+            for _ in 0..members {
+                let key = yyjson_obj_iter_next(&mut members_iter);
+                let val = yyjson_obj_iter_get_val(key);
+                let key_str =
+                    str_from_slice!((*key).uni.str_ as *const u8, unsafe_yyjson_get_len(key));
+                args.check_string_len(key_str)
+                    .map_err(|err| text.locate(err, key))?;
+                let (pykey, pyhash) = get_unicode_key(key_str, args);
+                let pyval = match parse_node(val, args, args.max_depth() - 2, text) {
+                    Ok(pyval) => pyval,
+                    Err(err) => {
+                        ffi!(Py_DECREF(pykey));
+                        return Err(err);
+                    }
+                };
+                columns
+                    .set(pykey, pyhash, pyval, args)
+                    .map_err(|err| text.locate(err, key))?;
+            }
+            columns.end_row();
+        }
+    }
+    Ok(columns.finish())
+}
+
+/// What a container being deserialized by `parse_node()` is made into.
+enum FrameKind {
+    /// A `list`, or a `tuple` if `args.tuples`.
+    Array,
+    /// A `dict`, and the lists made in it for `DuplicateKeys::List`.
+    Object(Vec<*mut pyo3_ffi::PyObject>),
+    /// The `list` of `(key, value)` tuples given to `object_pairs_hook`.
+    Pairs(NonNull<pyo3_ffi::PyObject>),
+}
+
+/// An array or object being deserialized by `parse_node()`, of which the
+/// elements or members before `idx` are set in `obj`.
+struct Frame {
+    kind: FrameKind,
+    // owned until the container ends, and freed with it on error
+    obj: *mut pyo3_ffi::PyObject,
+    // the container, at which an error ending it is located
+    elem: *mut yyjson_val,
+    // the next element, or key of the next member
+    cur: *mut yyjson_val,
+    // the element, or key of the member, read by `next()`
+    member: *mut yyjson_val,
+    idx: usize,
+    len: usize,
+    // the key of the member whose value is being deserialized, owned
+    key: *mut pyo3_ffi::PyObject,
+    hash: pyo3_ffi::Py_hash_t,
+}
+
+impl Drop for Frame {
+    fn drop(&mut self) {
+        if !self.key.is_null() {
+            ffi!(Py_DECREF(self.key));
+        }
+        if !self.obj.is_null() {
+            ffi!(Py_DECREF(self.obj));
+        }
+    }
+}
+
+impl Frame {
+    /// Start deserializing `elem`, a container of `len` elements or members,
+    /// into `obj`.
+    fn new(
+        kind: FrameKind,
+        obj: *mut pyo3_ffi::PyObject,
+        elem: *mut yyjson_val,
+        len: usize,
+    ) -> Self {
+        Frame {
+            kind: kind,
+            obj: obj,
+            elem: elem,
             cur: unsafe_yyjson_get_first(elem),
+            member: null_mut(),
+            idx: 0,
+            len: len,
+            key: null_mut(),
+            hash: 0,
+        }
+    }
+
+    /// Return the next element, or the value of the next member after
+    /// reading its key.
+    #[inline(always)]
+    fn next(&mut self, args: &LoadsArgs) -> Result<*mut yyjson_val, DeserializeError<'static>> {
+        self.member = self.cur;
+        let elem = if let FrameKind::Array = self.kind {
+            self.cur
+        } else {
+            let key = self.cur;
+            let key_str = str_from_slice!((*key).uni.str_ as *const u8, unsafe_yyjson_get_len(key));
+            args.check_string_len(key_str)?;
+            let (pykey, pyhash) = get_unicode_key(key_str, args);
+            self.key = pykey;
+            self.hash = pyhash;
+            unsafe { key.add(1) }
         };
-        for idx in 0..=len - 1 {
-            let val = yyjson_arr_iter_next(&mut iter);
-            let each = parse_node(val);
-            ffi!(PyList_SET_ITEM(list, idx as isize, each.as_ptr()));
+        self.cur = unsafe_yyjson_get_next(elem);
+        Ok(elem)
+    }
+
+    /// Set the element or member value read by `next()` to `val`, consuming
+    /// it.
+    #[inline(always)]
+    fn set(
+        &mut self,
+        val: NonNull<pyo3_ffi::PyObject>,
+        args: &LoadsArgs,
+    ) -> Result<(), DeserializeError<'static>> {
+        let idx = self.idx;
+        self.idx += 1;
+        let key = std::mem::replace(&mut self.key, null_mut());
+        match self.kind {
+            FrameKind::Array => {
+                set_array_item(self.obj, idx, val.as_ptr(), args);
+                Ok(())
+            }
+            FrameKind::Object(ref mut collected) => {
+                set_object_item(self.obj, key, self.hash, val, args, collected)
+            }
+            FrameKind::Pairs(_) => {
+                ffi!(PyList_SET_ITEM(
+                    self.obj,
+                    idx as isize,
+                    parse_pair(key, val)
+                ));
+                Ok(())
+            }
+        }
+    }
+
+    /// Return the object made of the container once all of it is set.
+    fn end(
+        mut self,
+        args: &LoadsArgs,
+    ) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+        let obj = nonnull!(std::mem::replace(&mut self.obj, null_mut()));
+        match self.kind {
+            FrameKind::Array => Ok(obj),
+            FrameKind::Object(ref collected) => parse_object_hook(obj, collected, args),
+            FrameKind::Pairs(func) => call_object_hook(func, obj),
         }
-        nonnull!(list)
     }
 }
 
-#[inline(never)]
-fn parse_yy_object(elem: *mut yyjson_val) -> NonNull<pyo3_ffi::PyObject> {
+/// Count the `len` elements or members of `elem`, an array or object, as
+/// `LoadsArgs::add_items()` does, locating an error at the first of them
+/// past `max_items`.
+#[inline(always)]
+fn add_container_items(
+    elem: *mut yyjson_val,
+    len: usize,
+    args: &LoadsArgs,
+    text: DocText,
+) -> Result<(), DeserializeError<'static>> {
+    let left = args.items_left();
+    args.add_items(len)
+        .map_err(|err| text.locate(err, nth_child(elem, left.unwrap_or(0))))
+}
+
+/// The element of array `elem` or key of object `elem` at `idx`.
+#[cold]
+pub fn nth_child(elem: *mut yyjson_val, idx: usize) -> *mut yyjson_val {
+    let object = matches!(ElementType::from_tag(elem), ElementType::Object);
+    let mut child = unsafe_yyjson_get_first(elem);
+    for _ in 0..idx {
+        if object {
+            child = unsafe { child.add(1) };
+        }
+        child = unsafe_yyjson_get_next(child);
+    }
+    child
+}
+
+/// Start deserializing `elem`, an array or object, by pushing a frame for it
+/// onto `stack`, or return what it is deserialized to if that needs none.
+#[inline(always)]
+fn start_container(
+    elem: *mut yyjson_val,
+    tag: ElementType,
+    args: &LoadsArgs,
+    stack: &mut SmallVec<[Frame; 16]>,
+    text: DocText,
+) -> Result<Option<NonNull<pyo3_ffi::PyObject>>, Option<DeserializeError<'static>>> {
+    let len = unsafe_yyjson_get_len(elem);
+    add_container_items(elem, len, args, text)?;
+    let frame = if let ElementType::Array = tag {
+        if unlikely!(args.numpy) && len > 0 {
+            let mut numbers = NumericArray::with_capacity(len);
+            if collect_yy_numbers(elem, &mut numbers, args)? {
+                return numbers.into_ndarray(args).map(Some);
+            }
+        }
+        Frame::new(FrameKind::Array, new_array(len, args), elem, len)
+    } else if let Some(func) = args.object_pairs_hook {
+        Frame::new(
+            FrameKind::Pairs(func),
+            ffi!(PyList_New(len as isize)),
+            elem,
+            len,
+        )
+    } else {
+        Frame::new(
+            FrameKind::Object(Vec::new()),
+            ffi!(_PyDict_NewPresized(len as isize)),
+            elem,
+            len,
+        )
+    };
+    stack.push(frame);
+    Ok(None)
+}
+
+/// Deserialize a scalar `elem` of type `tag`.
+#[inline(always)]
+fn parse_scalar(
+    elem: *mut yyjson_val,
+    tag: ElementType,
+    args: &LoadsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    match tag {
+        ElementType::String => parse_yy_string(elem, args),
+        ElementType::Uint64 => Ok(parse_u64(unsafe { (*elem).uni.u64_ })),
+        ElementType::Int64 => Ok(parse_i64(unsafe { (*elem).uni.i64_ })),
+        ElementType::Double => Ok(parse_f64(unsafe { (*elem).uni.f64_ })),
+        ElementType::Null => Ok(parse_none()),
+        ElementType::True => Ok(parse_true()),
+        ElementType::False => Ok(parse_false()),
+        ElementType::Raw => parse_number_literal(
+            str_from_slice!((*elem).uni.str_ as *const u8, unsafe_yyjson_get_len(elem)),
+            args,
+        ),
+        ElementType::Array | ElementType::Object => unsafe { std::hint::unreachable_unchecked() },
+    }
+}
+
+/// Deserialize `elem`, in which containers may be nested `remaining_depth`
+/// levels deep. The containers being deserialized are kept on a stack rather
+/// than deserialized recursively, so that how deeply they may be nested is
+/// not limited by the size of the thread's stack.
+pub fn parse_node(
+    elem: *mut yyjson_val,
+    args: &LoadsArgs,
+    remaining_depth: u32,
+    text: DocText,
+) -> Result<NonNull<pyo3_ffi::PyObject>, Option<DeserializeError<'static>>> {
+    let at = |val: *mut yyjson_val| {
+        move |err: Option<DeserializeError<'static>>| err.map(|err| text.locate(err, val))
+    };
+    // on error, the containers on the stack are freed as it is dropped
+    let mut stack: SmallVec<[Frame; 16]> = SmallVec::new();
+    let mut elem = elem;
+    loop {
+        let tag = ElementType::from_tag(elem);
+        let mut val = if let ElementType::Array | ElementType::Object = tag {
+            if unlikely!(stack.len() as u32 >= remaining_depth) {
+                let err = DeserializeError::invalid(Cow::Borrowed(RECURSION_LIMIT));
+                return Err(Some(text.locate(err, elem)));
+            }
+            start_container(elem, tag, args, &mut stack, text).map_err(at(elem))?
+        } else {
+            Some(parse_scalar(elem, tag, args).map_err(at(elem))?)
+        };
+        // set the value in its container, and each container that has ended
+        // in the one it is in, until one has an element or member left
+        loop {
+            let frame = match stack.last_mut() {
+                Some(frame) => frame,
+                None => return Ok(val.unwrap()),
+            };
+            if let Some(val) = val.take() {
+                frame
+                    .set(val, args)
+                    .map_err(|err| text.locate(err, frame.member))?;
+            }
+            if frame.idx < frame.len {
+                elem = frame
+                    .next(args)
+                    .map_err(|err| text.locate(err, frame.member))?;
+                break;
+            }
+            let frame = stack.pop().unwrap();
+            let container = frame.elem;
+            val = Some(frame.end(args).map_err(at(container))?);
+        }
+    }
+}
+
+/// Check `data` as `deserialize_yyjson()` would deserialize it, but without
+/// making any Python object, so that the GIL is not needed once it is read.
+pub fn check_yyjson(
+    data: &'static str,
+    args: &LoadsArgs,
+    release_gil: bool,
+) -> Result<(), DeserializeError<'static>> {
     unsafe {
-        let len = unsafe_yyjson_get_len(elem);
-        if len == 0 {
-            return nonnull!(ffi!(PyDict_New()));
+        let allocator = if yyjson_read_max_memory_usage(data.len()) < YYJSON_BUFFER_SIZE {
+            std::ptr::addr_of_mut!(*YYJSON_ALLOC)
+        } else {
+            null_mut()
+        };
+        let mut err = yyjson_read_err {
+            code: YYJSON_READ_SUCCESS,
+            msg: null(),
+            pos: 0,
+        };
+        let mut check = || {
+            let doc = yyjson_read_opts(
+                data.as_ptr() as *mut c_char,
+                data.len(),
+                read_flags(args),
+                allocator,
+                &mut err,
+            );
+            if doc.is_null() {
+                let msg: Cow<str> = std::ffi::CStr::from_ptr(err.msg).to_string_lossy();
+                return Err(DeserializeError::from_yyjson(msg, err.pos as i64, data));
+            }
+            let root = yyjson_doc_get_root(doc);
+            let ret = check_node(root, args, args.max_depth(), DocText::new(data, root));
+            yyjson_doc_free(doc);
+            ret
+        };
+        if release_gil && allocator.is_null() {
+            let tstate = pyo3_ffi::PyEval_SaveThread();
+            let ret = check();
+            pyo3_ffi::PyEval_RestoreThread(tstate);
+            ret
+        } else {
+            check()
         }
-        let dict = ffi!(_PyDict_NewPresized(len as isize));
+    }
+}
+
+/// An array or object being checked by `check_node()`.
+struct CheckFrame {
+    // the next element, or key of the next member
+    cur: *mut yyjson_val,
+    left: usize,
+    object: bool,
+    // the keys of the object, if duplicate keys are errors
+    keys: HashSet<&'static str>,
+}
+
+/// Check `elem` as `parse_node()` would deserialize it, keeping the
+/// containers being checked on a stack as it does.
+fn check_node(
+    elem: *mut yyjson_val,
+    args: &LoadsArgs,
+    remaining_depth: u32,
+    text: DocText,
+) -> Result<(), DeserializeError<'static>> {
+    let mut stack: SmallVec<[CheckFrame; 16]> = SmallVec::new();
+    let mut elem = elem;
+    loop {
+        let checked = match ElementType::from_tag(elem) {
+            tag @ (ElementType::Array | ElementType::Object) => {
+                if unlikely!(stack.len() as u32 >= remaining_depth) {
+                    let err = DeserializeError::invalid(Cow::Borrowed(RECURSION_LIMIT));
+                    return Err(text.locate(err, elem));
+                }
+                let len = unsafe_yyjson_get_len(elem);
+                add_container_items(elem, len, args, text)?;
+                stack.push(CheckFrame {
+                    cur: unsafe_yyjson_get_first(elem),
+                    left: len,
+                    object: matches!(tag, ElementType::Object),
+                    keys: HashSet::new(),
+                });
+                Ok(())
+            }
+            ElementType::String => args.check_string_len(str_from_slice!(
+                (*elem).uni.str_ as *const u8,
+                unsafe_yyjson_get_len(elem)
+            )),
+            ElementType::Raw => check_number_literal(
+                str_from_slice!((*elem).uni.str_ as *const u8, unsafe_yyjson_get_len(elem)),
+                args,
+            ),
+            _ => Ok(()),
+        };
+        checked.map_err(|err| text.locate(err, elem))?;
+        loop {
+            let frame = match stack.last_mut() {
+                Some(frame) => frame,
+                None => return Ok(()),
+            };
+            if frame.left == 0 {
+                stack.pop();
+                continue;
+            }
+            frame.left -= 1;
+            if frame.object {
+                let key = frame.cur;
+                let key_str =
+                    str_from_slice!((*key).uni.str_ as *const u8, unsafe_yyjson_get_len(key));
+                args.check_string_len(key_str)
+                    .map_err(|err| text.locate(err, key))?;
+                if args.duplicate_keys == DuplicateKeys::Error && !frame.keys.insert(key_str) {
+                    let err = DeserializeError::invalid(Cow::Owned(format!(
+                        "Duplicate key \"{}\"",
+                        key_str
+                    )));
+                    return Err(text.locate(err, key));
+                }
+                elem = unsafe { key.add(1) };
+            } else {
+                elem = frame.cur;
+            }
+            frame.cur = unsafe_yyjson_get_next(elem);
+            break;
+        }
+    }
+}
+
+/// A value of a document read by yyjson, to validate against a schema.
+#[derive(Clone, Copy)]
+pub struct YyjsonInstance(pub *mut yyjson_val);
+
+impl Instance for YyjsonInstance {
+    fn kind(self) -> Kind {
+        let elem = self.0;
+        match ElementType::from_tag(elem) {
+            ElementType::String => Kind::Str(str_from_slice!(
+                (*elem).uni.str_ as *const u8,
+                unsafe_yyjson_get_len(elem)
+            )),
+            ElementType::Uint64 => Kind::Number(unsafe { (*elem).uni.u64_ } as f64),
+            ElementType::Int64 => Kind::Number(unsafe { (*elem).uni.i64_ } as f64),
+            ElementType::Double => Kind::Number(unsafe { (*elem).uni.f64_ }),
+            ElementType::Null => Kind::Null,
+            ElementType::True => Kind::Bool(true),
+            ElementType::False => Kind::Bool(false),
+            ElementType::Array => Kind::Array,
+            ElementType::Object => Kind::Object,
+            ElementType::Raw => Kind::Number(
+                str_from_slice!((*elem).uni.str_ as *const u8, unsafe_yyjson_get_len(elem))
+                    .parse::<f64>()
+                    .unwrap_or(f64::NAN),
+            ),
+        }
+    }
+
+    fn len(self) -> usize {
+        unsafe_yyjson_get_len(self.0)
+    }
+
+    fn each_item(
+        self,
+        f: &mut dyn FnMut(usize, Self) -> Result<(), PathError>,
+    ) -> Result<(), PathError> {
+        let len = unsafe_yyjson_get_len(self.0);
+        let mut iter = yyjson_arr_iter {
+            idx: 0,
+            max: len,
+            cur: unsafe_yyjson_get_first(self.0),
+        };
+        for idx in 0..len {
+            f(idx, YyjsonInstance(yyjson_arr_iter_next(&mut iter)))?;
+        }
+        Ok(())
+    }
+
+    fn each_member(
+        self,
+        f: &mut dyn FnMut(&str, Self) -> Result<(), PathError>,
+    ) -> Result<(), PathError> {
+        let len = unsafe_yyjson_get_len(self.0);
         let mut iter = yyjson_obj_iter {
             idx: 0,
             max: len,
-            cur: unsafe_yyjson_get_first(elem),
-            obj: elem,
+            cur: unsafe_yyjson_get_first(self.0),
+            obj: self.0,
         };
-        for _ in 0..=len - 1 {
+        for _ in 0..len {
             let key = yyjson_obj_iter_next(&mut iter);
-            let val = yyjson_obj_iter_get_val(key);
             let key_str = str_from_slice!((*key).uni.str_ as *const u8, unsafe_yyjson_get_len(key));
-            let (pykey, pyhash) = get_unicode_key(key_str);
-            let pyval = parse_node(val);
-            let _ = ffi!(_PyDict_SetItem_KnownHash(
-                dict,
-                pykey,
-                pyval.as_ptr(),
-                pyhash
-            ));
-            ffi!(Py_DECREF(pykey));
-            ffi!(Py_DECREF(pyval.as_ptr()));
-        }
-        nonnull!(dict)
-    }
-}
-
-pub fn parse_node(elem: *mut yyjson_val) -> NonNull<pyo3_ffi::PyObject> {
-    match ElementType::from_tag(elem) {
-        ElementType::String => parse_yy_string(elem),
-        ElementType::Uint64 => parse_u64(unsafe { (*elem).uni.u64_ }),
-        ElementType::Int64 => parse_i64(unsafe { (*elem).uni.i64_ }),
-        ElementType::Double => parse_f64(unsafe { (*elem).uni.f64_ }),
-        ElementType::Null => parse_none(),
-        ElementType::True => parse_true(),
-        ElementType::False => parse_false(),
-        ElementType::Array => parse_yy_array(elem),
-        ElementType::Object => parse_yy_object(elem),
+            f(key_str, YyjsonInstance(yyjson_obj_iter_get_val(key)))?;
+        }
+        Ok(())
     }
 }
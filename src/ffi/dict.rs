@@ -6,6 +6,31 @@ use pyo3_ffi::{PyObject, Py_hash_t, Py_ssize_t};
 #[cfg(feature = "pydictiter")]
 use std::os::raw::{c_char, c_void};
 
+// dictobject.h, the fields laid out alike in every supported version
+#[repr(C)]
+struct PyDictObjectHead {
+    ob_base: pyo3_ffi::PyObject,
+    ma_used: pyo3_ffi::Py_ssize_t,
+    ma_version_tag: u64,
+    ma_keys: *mut std::os::raw::c_void,
+    ma_values: *mut std::os::raw::c_void,
+}
+
+/// The keys table of a split-table `dict`, i.e., one sharing its keys with
+/// other instances of a class, or null if the table is combined.
+#[allow(non_snake_case)]
+#[inline(always)]
+pub fn PyDict_SharedKeys(op: *mut pyo3_ffi::PyObject) -> *mut std::os::raw::c_void {
+    unsafe {
+        let dict = op as *mut PyDictObjectHead;
+        if (*dict).ma_values.is_null() {
+            std::ptr::null_mut()
+        } else {
+            (*dict).ma_keys
+        }
+    }
+}
+
 // dictobject.h
 #[cfg(feature = "pydictiter")]
 #[repr(C)]
@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use pyo3_ffi::*;
+
+// PYLONG_BITS_IN_DIGIT as CPython configures it by default. typeref checks
+// that the running interpreter uses it before enabling PyLong_AsSmallValue().
+pub const PYLONG_SHIFT: u32 = 30;
+
+#[repr(C)]
+pub struct PyLongObject {
+    pub ob_base: PyVarObject,
+    pub ob_digit: [u32; 2],
+}
+
+/// The value of an `int` of at most two digits, read directly from its
+/// digits, or `None` if it has more digits. `ob_size` is the number of digits
+/// negated for negative values.
+#[cfg(not(Py_3_12))]
+#[allow(non_snake_case)]
+#[inline(always)]
+pub unsafe fn PyLong_AsSmallValue(op: *mut PyObject) -> Option<i64> {
+    let size = (*op.cast::<PyVarObject>()).ob_size;
+    let digits = &(*op.cast::<PyLongObject>()).ob_digit;
+    match size {
+        0 => Some(0),
+        1 => Some(digits[0] as i64),
+        -1 => Some(-(digits[0] as i64)),
+        2 => Some(((digits[1] as i64) << PYLONG_SHIFT) | digits[0] as i64),
+        -2 => Some(-(((digits[1] as i64) << PYLONG_SHIFT) | digits[0] as i64)),
+        _ => None,
+    }
+}
+
+// Python 3.12 stores the sign and number of digits in lv_tag instead.
+#[cfg(Py_3_12)]
+#[allow(non_snake_case)]
+#[inline(always)]
+pub unsafe fn PyLong_AsSmallValue(_op: *mut PyObject) -> Option<i64> {
+    None
+}
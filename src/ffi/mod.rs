@@ -3,9 +3,11 @@
 mod buffer;
 mod bytes;
 mod dict;
+mod long;
 mod pytype;
 
 pub use buffer::*;
 pub use bytes::*;
 pub use dict::*;
+pub use long::*;
 pub use pytype::*;
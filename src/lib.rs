@@ -12,17 +12,33 @@
 #[macro_use]
 mod util;
 
+mod aio;
 mod deserialize;
 mod error;
 mod ffi;
 mod opt;
 mod serialize;
+mod stdlib;
 mod typeref;
 mod unicode;
 
 #[cfg(feature = "yyjson")]
 mod yyjson;
 
+#[cfg(feature = "library")]
+pub mod api;
+
+#[cfg(all(feature = "mimalloc", feature = "jemalloc"))]
+compile_error!("Features mimalloc and jemalloc are mutually exclusive.");
+
+#[cfg(feature = "mimalloc")]
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+#[cfg(feature = "jemalloc")]
+#[global_allocator]
+static GLOBAL: jemalloc::Jemalloc = jemalloc::Jemalloc;
+
 use pyo3_ffi::*;
 use std::borrow::Cow;
 use std::os::raw::c_char;
@@ -71,7 +87,7 @@ pub unsafe extern "C" fn orjson_init_exec(mptr: *mut PyObject) -> c_int {
     }
     {
         let dumps_doc =
-            "dumps(obj, /, default=None, option=None)\n--\n\nSerialize Python objects to JSON.\0";
+            "dumps(obj, /, default=None, option=None, *, max_size=None, stats=None, float_precision=None, max_depth=None, redact_keys=None, include_keys=None, max_str_len=None, transform=None, separators=None, **options)\n--\n\nSerialize Python objects to JSON.\0";
 
         let wrapped_dumps: PyMethodDef;
 
@@ -107,14 +123,34 @@ pub unsafe extern "C" fn orjson_init_exec(mptr: *mut PyObject) -> c_int {
     }
 
     {
-        let loads_doc = "loads(obj, /)\n--\n\nDeserialize JSON to Python objects.\0";
+        let loads_doc =
+            "loads(obj, /, *, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, surrogates=None, replace_invalid_utf8=False, allow_nan=False, strict=False, jsonc=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, numpy=False, raw_numbers=False, offset=None, length=None, threads=None, schema=None)\n--\n\nDeserialize JSON to Python objects.\0";
+
+        let wrapped_loads: PyMethodDef;
+
+        #[cfg(Py_3_8)]
+        {
+            wrapped_loads = PyMethodDef {
+                ml_name: "loads\0".as_ptr() as *const c_char,
+                ml_meth: PyMethodDefPointer {
+                    _PyCFunctionFastWithKeywords: loads,
+                },
+                ml_flags: pyo3_ffi::METH_FASTCALL | METH_KEYWORDS,
+                ml_doc: loads_doc.as_ptr() as *const c_char,
+            };
+        }
+        #[cfg(not(Py_3_8))]
+        {
+            wrapped_loads = PyMethodDef {
+                ml_name: "loads\0".as_ptr() as *const c_char,
+                ml_meth: PyMethodDefPointer {
+                    PyCFunctionWithKeywords: loads,
+                },
+                ml_flags: METH_VARARGS | METH_KEYWORDS,
+                ml_doc: loads_doc.as_ptr() as *const c_char,
+            };
+        }
 
-        let wrapped_loads = PyMethodDef {
-            ml_name: "loads\0".as_ptr() as *const c_char,
-            ml_meth: PyMethodDefPointer { PyCFunction: loads },
-            ml_flags: METH_O,
-            ml_doc: loads_doc.as_ptr() as *const c_char,
-        };
         let func = PyCFunction_NewEx(
             Box::into_raw(Box::new(wrapped_loads)),
             null_mut(),
@@ -123,10 +159,442 @@ pub unsafe extern "C" fn orjson_init_exec(mptr: *mut PyObject) -> c_int {
         add!(mptr, "loads\0", func);
     }
 
+    {
+        let load_doc = "load(fp, /, *, chunk_size=None, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, surrogates=None, replace_invalid_utf8=False, allow_nan=False, strict=False, jsonc=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, numpy=False, raw_numbers=False, threads=None, schema=None)\n--\n\nDeserialize JSON read from a file object or file descriptor to Python objects.\0";
+
+        let wrapped_load = PyMethodDef {
+            ml_name: "load\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: load,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: load_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_load)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "load\0", func);
+    }
+
+    {
+        let load_mmap_doc = "load_mmap(path, /, *, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, surrogates=None, replace_invalid_utf8=False, allow_nan=False, strict=False, jsonc=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, numpy=False, raw_numbers=False, offset=None, length=None, threads=None, schema=None)\n--\n\nDeserialize JSON of the file at path, parsed in place by mapping it into memory.\0";
+
+        let wrapped_load_mmap = PyMethodDef {
+            ml_name: "load_mmap\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: load_mmap,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: load_mmap_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_load_mmap)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "load_mmap\0", func);
+    }
+
+    {
+        let aload_doc = "aload(path, /, *, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, surrogates=None, replace_invalid_utf8=False, allow_nan=False, strict=False, jsonc=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, numpy=False, raw_numbers=False, threads=None, schema=None)\n--\n\nReturn an asyncio.Future of deserializing JSON of the file at path, read on a thread of the event loop's default executor.\0";
+
+        let wrapped_aload = PyMethodDef {
+            ml_name: "aload\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: aload,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: aload_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_aload)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "aload\0", func);
+    }
+
+    {
+        let loads_into_doc = "loads_into(type, obj, /, *, duplicate_keys=None, surrogates=None, replace_invalid_utf8=False, allow_nan=False, strict=False, jsonc=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, offset=None, length=None, threads=None, schema=None)\n--\n\nDeserialize JSON to an instance of a dataclass or a type hint of one.\0";
+
+        let wrapped_loads_into = PyMethodDef {
+            ml_name: "loads_into\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: loads_into,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: loads_into_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_loads_into)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "loads_into\0", func);
+    }
+
+    {
+        let select_doc = "select(data, path, /, *, paths=False)\n--\n\nReturn the values in JSON selected by a JSONPath query, deserializing only those.\0";
+
+        let wrapped_select = PyMethodDef {
+            ml_name: "select\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: select_values,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: select_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_select)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "select\0", func);
+    }
+
+    {
+        let loads_columns_doc = "loads_columns(obj, /, *, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, surrogates=None, replace_invalid_utf8=False, allow_nan=False, strict=False, jsonc=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, raw_numbers=False, offset=None, length=None)\n--\n\nDeserialize a JSON array of objects to a dict of a list of the values of each key.\0";
+
+        let wrapped_loads_columns = PyMethodDef {
+            ml_name: "loads_columns\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: loads_columns,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: loads_columns_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_loads_columns)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "loads_columns\0", func);
+    }
+
+    {
+        let loads_prefix_doc = "loads_prefix(data, /, *, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, surrogates=None, allow_nan=False, strict=False, jsonc=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, numpy=False, raw_numbers=False, offset=None, length=None, schema=None)\n--\n\nDeserialize the JSON value at the start of data, returning it and the index at which it ends.\0";
+
+        let wrapped_loads_prefix = PyMethodDef {
+            ml_name: "loads_prefix\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: loads_prefix,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: loads_prefix_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_loads_prefix)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "loads_prefix\0", func);
+    }
+
+    {
+        let unpackb_doc = "unpackb(data, /, *, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, allow_nan=False, strict=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, offset=None, length=None, schema=None)\n--\n\nDeserialize MessagePack to Python objects as loads() deserializes JSON.\0";
+
+        let wrapped_unpackb = PyMethodDef {
+            ml_name: "unpackb\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: unpackb,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: unpackb_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_unpackb)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "unpackb\0", func);
+    }
+
+    {
+        let loads_cbor_doc = "loads_cbor(data, /, *, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, allow_nan=False, strict=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, offset=None, length=None, schema=None)\n--\n\nDeserialize CBOR to Python objects as loads() deserializes JSON.\0";
+
+        let wrapped_loads_cbor = PyMethodDef {
+            ml_name: "loads_cbor\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: loads_cbor,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: loads_cbor_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_loads_cbor)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "loads_cbor\0", func);
+    }
+
+    {
+        let loads_compressed_doc = "loads_compressed(data_or_fp, /, *, compression=None, parse_float=None, parse_int=None, object_hook=None, object_pairs_hook=None, dict_class=None, duplicate_keys=None, surrogates=None, replace_invalid_utf8=False, allow_nan=False, strict=False, jsonc=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, tuples=False, immutable=False, datetimes=False, uuids=False, numpy=False, raw_numbers=False, schema=None)\n--\n\nDeserialize gzip or zstd compressed JSON to Python objects while decompressing it.\0";
+
+        let wrapped_loads_compressed = PyMethodDef {
+            ml_name: "loads_compressed\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: loads_compressed,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: loads_compressed_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_loads_compressed)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "loads_compressed\0", func);
+    }
+
+    {
+        let is_valid_doc = "is_valid(data, /, *, duplicate_keys=None, surrogates=None, allow_nan=False, strict=False, jsonc=False, max_depth=None, max_items=None, max_string_len=None, max_string_bytes=None, max_bytes=None, position=False)\n--\n\nReturn whether data is valid JSON, checked without deserializing it.\0";
+
+        let wrapped_is_valid = PyMethodDef {
+            ml_name: "is_valid\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: is_valid,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: is_valid_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_is_valid)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "is_valid\0", func);
+    }
+
+    {
+        let find_errors_doc = "find_errors(data, /, *, surrogates=None, allow_nan=False, jsonc=False, max_depth=None, max_errors=None)\n--\n\nReturn a list of a JSONDecodeError for each syntax error of data, continuing after each.\0";
+
+        let wrapped_find_errors = PyMethodDef {
+            ml_name: "find_errors\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: find_errors,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: find_errors_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_find_errors)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "find_errors\0", func);
+    }
+
+    {
+        let iter_array_doc = "iter_array(data_or_fp, /)\n--\n\nIterate over the elements of a JSON array, deserializing each as it is reached.\0";
+
+        let wrapped_iter_array = PyMethodDef {
+            ml_name: "iter_array\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: iter_array,
+            },
+            ml_flags: METH_O,
+            ml_doc: iter_array_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_iter_array)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "iter_array\0", func);
+    }
+
+    {
+        let tokenize_doc = "tokenize(data_or_fp, /)\n--\n\nIterate over the tokens of a JSON document as (event, value, offset) tuples.\0";
+
+        let wrapped_tokenize = PyMethodDef {
+            ml_name: "tokenize\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: tokenize,
+            },
+            ml_flags: METH_O,
+            ml_doc: tokenize_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_tokenize)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "tokenize\0", func);
+    }
+
+    {
+        let loads_lines_doc = "loads_lines(data_or_fp, /)\n--\n\nIterate over the JSON documents on each line of newline-delimited JSON, deserializing each as it is reached.\0";
+
+        let wrapped_loads_lines = PyMethodDef {
+            ml_name: "loads_lines\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: loads_lines,
+            },
+            ml_flags: METH_O,
+            ml_doc: loads_lines_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_loads_lines)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "loads_lines\0", func);
+    }
+
+    {
+        let iter_documents_doc = "iter_documents(data_or_fp, /)\n--\n\nIterate over the JSON documents that follow one another in data_or_fp, deserializing each as it is reached.\0";
+
+        let wrapped_iter_documents = PyMethodDef {
+            ml_name: "iter_documents\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: iter_documents,
+            },
+            ml_flags: METH_O,
+            ml_doc: iter_documents_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_iter_documents)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "iter_documents\0", func);
+    }
+
+    {
+        let compile_doc = "compile(cls, /, default=None, option=None, **options)\n--\n\nCompile a dataclass or TypedDict type into a callable serializing its instances to JSON.\0";
+
+        let wrapped_compile = PyMethodDef {
+            ml_name: "compile\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: compile,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: compile_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_compile)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "compile\0", func);
+    }
+
+    {
+        let adump_doc = "adump(obj, path, /, default=None, option=None, **options)\n--\n\nReturn an asyncio.Future of serializing Python objects to JSON written to the file at path on a thread of the event loop's default executor.\0";
+
+        let wrapped_adump = PyMethodDef {
+            ml_name: "adump\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: adump,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: adump_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_adump)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "adump\0", func);
+    }
+
+    {
+        let dump_fd_doc = "dump_fd(obj, fd, /, default=None, option=None, *, lines=False, **options)\n--\n\nSerialize Python objects to JSON written directly to a file descriptor, returning the number of bytes written.\0";
+
+        let wrapped_dump_fd = PyMethodDef {
+            ml_name: "dump_fd\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: dump_fd,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: dump_fd_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_dump_fd)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "dump_fd\0", func);
+    }
+
+    {
+        let dumps_into_doc = "dumps_into(obj, buffer, /, default=None, option=None, *, offset=0, **options)\n--\n\nSerialize Python objects to JSON written into a writable buffer at offset, returning the number of bytes written.\0";
+
+        let wrapped_dumps_into = PyMethodDef {
+            ml_name: "dumps_into\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunctionWithKeywords: dumps_into,
+            },
+            ml_flags: METH_VARARGS | METH_KEYWORDS,
+            ml_doc: dumps_into_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_dumps_into)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "dumps_into\0", func);
+    }
+
+    {
+        let set_default_options_doc = "set_default_options(option, /)\n--\n\nSet the options every call to dumps() uses in addition to its own.\0";
+
+        let wrapped_set_default_options = PyMethodDef {
+            ml_name: "set_default_options\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: set_default_options,
+            },
+            ml_flags: METH_O,
+            ml_doc: set_default_options_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_set_default_options)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "set_default_options\0", func);
+    }
+
+    {
+        let get_default_options_doc =
+            "get_default_options()\n--\n\nReturn the options set by set_default_options().\0";
+
+        let wrapped_get_default_options = PyMethodDef {
+            ml_name: "get_default_options\0".as_ptr() as *const c_char,
+            ml_meth: PyMethodDefPointer {
+                PyCFunction: get_default_options,
+            },
+            ml_flags: METH_NOARGS,
+            ml_doc: get_default_options_doc.as_ptr() as *const c_char,
+        };
+        let func = PyCFunction_NewEx(
+            Box::into_raw(Box::new(wrapped_get_default_options)),
+            null_mut(),
+            PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char),
+        );
+        add!(mptr, "get_default_options\0", func);
+    }
+
     opt!(mptr, "OPT_APPEND_NEWLINE\0", opt::APPEND_NEWLINE);
+    opt!(mptr, "OPT_BYTES_KEYS\0", opt::BYTES_KEYS);
+    opt!(mptr, "OPT_DEFAULT_PATH\0", opt::DEFAULT_PATH);
+    opt!(mptr, "OPT_FALLBACK_REPR\0", opt::FALLBACK_REPR);
+    opt!(mptr, "OPT_FALLBACK_STR\0", opt::FALLBACK_STR);
+    opt!(mptr, "OPT_FALLBACK_VARS\0", opt::FALLBACK_VARS);
     opt!(mptr, "OPT_INDENT_2\0", opt::INDENT_2);
+    opt!(mptr, "OPT_JS\0", opt::JS);
     opt!(mptr, "OPT_NAIVE_UTC\0", opt::NAIVE_UTC);
     opt!(mptr, "OPT_NON_STR_KEYS\0", opt::NON_STR_KEYS);
+    opt!(mptr, "OPT_NORMALIZE_FLOAT\0", opt::NORMALIZE_FLOAT);
+    opt!(
+        mptr,
+        "OPT_NORMALIZE_NEGATIVE_ZERO\0",
+        opt::NORMALIZE_NEGATIVE_ZERO
+    );
+    opt!(mptr, "OPT_NUMBERS_AS_STRINGS\0", opt::NUMBERS_AS_STRINGS);
     opt!(mptr, "OPT_OMIT_MICROSECONDS\0", opt::OMIT_MICROSECONDS);
     opt!(
         mptr,
@@ -144,37 +612,112 @@ pub unsafe extern "C" fn orjson_init_exec(mptr: *mut PyObject) -> c_int {
         opt::PASSTHROUGH_SUBCLASS
     );
     opt!(mptr, "OPT_SERIALIZE_DATACLASS\0", opt::SERIALIZE_DATACLASS);
+    opt!(
+        mptr,
+        "OPT_SERIALIZE_GEO_INTERFACE\0",
+        opt::SERIALIZE_GEO_INTERFACE
+    );
+    opt!(mptr, "OPT_SERIALIZE_MAPPING\0", opt::SERIALIZE_MAPPING);
+    opt!(mptr, "OPT_SERIALIZE_MULTIDICT\0", opt::SERIALIZE_MULTIDICT);
     opt!(mptr, "OPT_SERIALIZE_NUMPY\0", opt::SERIALIZE_NUMPY);
+    opt!(mptr, "OPT_SERIALIZE_PAIRS\0", opt::SERIALIZE_PAIRS);
+    opt!(mptr, "OPT_SERIALIZE_SEQUENCE\0", opt::SERIALIZE_SEQUENCE);
     opt!(mptr, "OPT_SERIALIZE_UUID\0", opt::SERIALIZE_UUID);
     opt!(mptr, "OPT_SORT_KEYS\0", opt::SORT_KEYS);
     opt!(mptr, "OPT_STRICT_INTEGER\0", opt::STRICT_INTEGER);
     opt!(mptr, "OPT_UTC_Z\0", opt::UTC_Z);
 
+    add!(mptr, "Options\0", create_options_flag());
+
+    add!(
+        mptr,
+        "AsyncWriter\0",
+        typeref::ASYNC_WRITER_TYPE as *mut PyObject
+    );
+    add!(mptr, "Decoder\0", typeref::DECODER_TYPE as *mut PyObject);
     add!(mptr, "JSONDecodeError\0", typeref::JsonDecodeError);
     add!(mptr, "JSONEncodeError\0", typeref::JsonEncodeError);
+    add!(mptr, "JSONEncodeSizeError\0", typeref::JsonEncodeSizeError);
+    add!(mptr, "Parser\0", typeref::PARSER_TYPE as *mut PyObject);
+    add!(
+        mptr,
+        "RawNumber\0",
+        typeref::RAW_NUMBER_TYPE as *mut PyObject
+    );
+    add!(mptr, "Schema\0", typeref::SCHEMA_TYPE as *mut PyObject);
+
+    let stdlib = crate::stdlib::create_stdlib_module();
+    if stdlib.is_null() {
+        return -1;
+    }
+    add!(mptr, "stdlib\0", stdlib);
 
     // maturin>=0.11.0 creates a python package that imports *, hiding dunder by default
-    let all: [&str; 20] = [
+    let all: [&str; 64] = [
         "__all__\0",
         "__version__\0",
+        "adump\0",
+        "aload\0",
+        "AsyncWriter\0",
+        "compile\0",
+        "Decoder\0",
+        "dump_fd\0",
         "dumps\0",
+        "dumps_into\0",
+        "find_errors\0",
+        "get_default_options\0",
+        "is_valid\0",
+        "iter_array\0",
+        "iter_documents\0",
         "JSONDecodeError\0",
         "JSONEncodeError\0",
+        "JSONEncodeSizeError\0",
+        "load\0",
+        "load_mmap\0",
         "loads\0",
+        "loads_cbor\0",
+        "loads_columns\0",
+        "loads_compressed\0",
+        "loads_into\0",
+        "loads_lines\0",
+        "loads_prefix\0",
         "OPT_APPEND_NEWLINE\0",
+        "OPT_BYTES_KEYS\0",
+        "OPT_DEFAULT_PATH\0",
+        "OPT_FALLBACK_REPR\0",
+        "OPT_FALLBACK_STR\0",
+        "OPT_FALLBACK_VARS\0",
         "OPT_INDENT_2\0",
+        "OPT_JS\0",
         "OPT_NAIVE_UTC\0",
         "OPT_NON_STR_KEYS\0",
+        "OPT_NORMALIZE_FLOAT\0",
+        "OPT_NORMALIZE_NEGATIVE_ZERO\0",
+        "OPT_NUMBERS_AS_STRINGS\0",
         "OPT_OMIT_MICROSECONDS\0",
         "OPT_PASSTHROUGH_DATACLASS\0",
         "OPT_PASSTHROUGH_DATETIME\0",
         "OPT_PASSTHROUGH_SUBCLASS\0",
         "OPT_SERIALIZE_DATACLASS\0",
+        "OPT_SERIALIZE_GEO_INTERFACE\0",
+        "OPT_SERIALIZE_MAPPING\0",
+        "OPT_SERIALIZE_MULTIDICT\0",
         "OPT_SERIALIZE_NUMPY\0",
+        "OPT_SERIALIZE_PAIRS\0",
+        "OPT_SERIALIZE_SEQUENCE\0",
         "OPT_SERIALIZE_UUID\0",
         "OPT_SORT_KEYS\0",
         "OPT_STRICT_INTEGER\0",
         "OPT_UTC_Z\0",
+        "Options\0",
+        "Parser\0",
+        "RawNumber\0",
+        "Schema\0",
+        "select\0",
+        "set_default_options\0",
+        "stdlib\0",
+        "tokenize\0",
+        "unpackb\0",
     ];
 
     let pyall = PyTuple_New(all.len() as isize);
@@ -190,6 +733,45 @@ pub unsafe extern "C" fn orjson_init_exec(mptr: *mut PyObject) -> c_int {
     0
 }
 
+/// Create `orjson.Options`, an `enum.IntFlag` of the options.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe fn create_options_flag() -> *mut PyObject {
+    let module = PyImport_ImportModule("enum\0".as_ptr() as *const c_char);
+    let int_flag = PyObject_GetAttrString(module, "IntFlag\0".as_ptr() as *const c_char);
+    Py_DECREF(module);
+
+    let members = PyList_New(0);
+    for (name, opt) in opt::OPTION_NAMES.iter() {
+        let member = PyTuple_New(2);
+        PyTuple_SET_ITEM(
+            member,
+            0,
+            PyUnicode_FromStringAndSize(name.as_ptr() as *const c_char, name.len() as isize - 1),
+        );
+        PyTuple_SET_ITEM(member, 1, PyLong_FromLong(*opt as std::os::raw::c_long));
+        PyList_Append(members, member);
+        Py_DECREF(member);
+    }
+    let args = PyTuple_New(2);
+    PyTuple_SET_ITEM(
+        args,
+        0,
+        PyUnicode_InternFromString("Options\0".as_ptr() as *const c_char),
+    );
+    PyTuple_SET_ITEM(args, 1, members);
+    let kwargs = PyDict_New();
+    let module_name = PyUnicode_InternFromString("orjson\0".as_ptr() as *const c_char);
+    PyDict_SetItemString(kwargs, "module\0".as_ptr() as *const c_char, module_name);
+    Py_DECREF(module_name);
+
+    let options = PyObject_Call(int_flag, args, kwargs);
+    Py_DECREF(kwargs);
+    Py_DECREF(args);
+    Py_DECREF(int_flag);
+    options
+}
+
 #[allow(non_snake_case)]
 #[no_mangle]
 #[cold]
@@ -226,31 +808,125 @@ pub unsafe extern "C" fn PyInit_orjson() -> *mut PyModuleDef {
 #[inline(never)]
 #[cfg_attr(feature = "optimize", optimize(size))]
 fn raise_loads_exception(err: deserialize::DeserializeError) -> *mut PyObject {
+    let exc = new_loads_exception(&err, null_mut());
+    if !exc.is_null() {
+        unsafe {
+            PyErr_SetObject(typeref::JsonDecodeError, exc);
+            Py_DECREF(exc);
+        }
+    }
+    null_mut()
+}
+
+/// Create the `JSONDecodeError` of `err` without raising it. Its `doc` is
+/// `doc` if not null, which must be the document of `err` as a `str`, so that
+/// the errors of one document can share it. Return null if creating it raised.
+#[cold]
+#[inline(never)]
+#[cfg_attr(feature = "optimize", optimize(size))]
+fn new_loads_exception(err: &deserialize::DeserializeError, doc: *mut PyObject) -> *mut PyObject {
     let pos = err.pos();
-    let msg = err.message;
-    let doc;
-    match err.data {
-        Some(as_str) => {
-            doc = unsafe {
+    let msg = &err.message;
+    let doc = if !doc.is_null() {
+        ffi!(Py_INCREF(doc));
+        doc
+    } else {
+        // only the text around the error is kept if `start` is given
+        match err.data.as_ref().filter(|_| err.start.is_none()) {
+            Some(as_str) => unsafe {
                 PyUnicode_FromStringAndSize(as_str.as_ptr() as *const c_char, as_str.len() as isize)
+            },
+            None => {
+                ffi!(Py_INCREF(crate::typeref::EMPTY_UNICODE));
+                unsafe { crate::typeref::EMPTY_UNICODE }
             }
         }
-        None => {
-            ffi!(Py_INCREF(crate::typeref::EMPTY_UNICODE));
-            doc = unsafe { crate::typeref::EMPTY_UNICODE }
-        }
-    }
+    };
     unsafe {
-        let err_msg =
-            PyUnicode_FromStringAndSize(msg.as_ptr() as *const c_char, msg.len() as isize);
+        // a key in the message has a lone surrogate if it was kept with
+        // `surrogates="surrogatepass"`
+        let err_msg = PyUnicode_DecodeUTF8(
+            msg.as_ptr() as *const c_char,
+            msg.len() as isize,
+            "surrogatepass\0".as_ptr() as *const c_char,
+        );
         let args = PyTuple_New(3);
-        let pos = PyLong_FromLongLong(pos);
         PyTuple_SET_ITEM(args, 0, err_msg);
         PyTuple_SET_ITEM(args, 1, doc);
-        PyTuple_SET_ITEM(args, 2, pos);
-        PyErr_SetObject(typeref::JsonDecodeError, args);
+        PyTuple_SET_ITEM(args, 2, PyLong_FromLongLong(pos));
+        // an exception already raised, e.g., encoding a `str`, is replaced
+        PyErr_Clear();
+        let exc = PyObject_Call(typeref::JsonDecodeError, args, null_mut());
         Py_DECREF(args);
-    };
+        if exc.is_null() {
+            return null_mut();
+        }
+        let snippet = err.snippet(pos);
+        set_error_attr(
+            exc,
+            "snippet\0",
+            PyUnicode_FromStringAndSize(snippet.as_ptr() as *const c_char, snippet.len() as isize),
+        );
+        // the document is not kept, so `lineno` and `colno`, and the
+        // location in the message, are not derived from `pos`
+        if err.start.is_some() && err.line > 0 {
+            set_error_attr(exc, "lineno\0", PyLong_FromSize_t(err.line));
+            set_error_attr(exc, "colno\0", PyLong_FromSize_t(err.column));
+            let located = format!(
+                "{}: line {} column {} (char {})",
+                msg, err.line, err.column, pos
+            );
+            let exc_args = PyTuple_New(1);
+            PyTuple_SET_ITEM(
+                exc_args,
+                0,
+                PyUnicode_DecodeUTF8(
+                    located.as_ptr() as *const c_char,
+                    located.len() as isize,
+                    "surrogatepass\0".as_ptr() as *const c_char,
+                ),
+            );
+            set_error_attr(exc, "args\0", exc_args);
+        }
+        exc
+    }
+}
+
+/// Set the attribute `name`, which must be nul-terminated, of an exception
+/// to `val`, which is consumed.
+#[cold]
+unsafe fn set_error_attr(exc: *mut PyObject, name: &str, val: *mut PyObject) {
+    PyObject_SetAttrString(exc, name.as_ptr() as *const c_char, val);
+    Py_DECREF(val);
+}
+
+/// Raise the `JSONDecodeError` of `func`, e.g., `"loads"`, given the keyword
+/// argument `arg`, which it does not take.
+#[cold]
+#[inline(never)]
+#[cfg_attr(feature = "optimize", optimize(size))]
+fn raise_unexpected_kwarg(func: &str, arg: *mut PyObject) -> *mut PyObject {
+    let msg = format!(
+        "{}() got an unexpected keyword argument: '{}'",
+        func,
+        crate::unicode::unicode_to_str(arg).unwrap_or_default()
+    );
+    let err = deserialize::DeserializeError::invalid(Cow::Borrowed(&msg));
+    let exc = new_loads_exception(&err, null_mut());
+    if !exc.is_null() {
+        unsafe {
+            // not an error in the document, so the message has no location
+            let exc_args = PyTuple_New(1);
+            PyTuple_SET_ITEM(
+                exc_args,
+                0,
+                PyUnicode_FromStringAndSize(msg.as_ptr() as *const c_char, msg.len() as isize),
+            );
+            set_error_attr(exc, "args\0", exc_args);
+            PyErr_SetObject(typeref::JsonDecodeError, exc);
+            Py_DECREF(exc);
+        }
+    }
     null_mut()
 }
 
@@ -258,152 +934,1660 @@ fn raise_loads_exception(err: deserialize::DeserializeError) -> *mut PyObject {
 #[inline(never)]
 #[cfg_attr(feature = "optimize", optimize(size))]
 fn raise_dumps_exception(msg: Cow<str>) -> *mut PyObject {
+    raise_dumps_exception_type(unsafe { typeref::JsonEncodeError }, msg)
+}
+
+#[cold]
+#[inline(never)]
+#[cfg_attr(feature = "optimize", optimize(size))]
+fn raise_dumps_exception_type(exc: *mut PyObject, msg: Cow<str>) -> *mut PyObject {
     unsafe {
+        // an exception raised serializing the object, e.g., by `default` or
+        // `repr()`, is the cause
+        let mut ptype = null_mut();
+        let mut pvalue = null_mut();
+        let mut ptraceback = null_mut();
+        PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback);
+        if !ptype.is_null() {
+            PyErr_NormalizeException(&mut ptype, &mut pvalue, &mut ptraceback);
+            if !ptraceback.is_null() {
+                PyException_SetTraceback(pvalue, ptraceback);
+                Py_DECREF(ptraceback);
+            }
+            Py_DECREF(ptype);
+        }
         let err_msg =
             PyUnicode_FromStringAndSize(msg.as_ptr() as *const c_char, msg.len() as isize);
-        PyErr_SetObject(typeref::JsonEncodeError, err_msg);
+        PyErr_SetObject(exc, err_msg);
         Py_DECREF(err_msg);
+        if !pvalue.is_null() {
+            let mut etype = null_mut();
+            let mut evalue = null_mut();
+            let mut etraceback = null_mut();
+            PyErr_Fetch(&mut etype, &mut evalue, &mut etraceback);
+            PyErr_NormalizeException(&mut etype, &mut evalue, &mut etraceback);
+            PyException_SetCause(evalue, pvalue);
+            PyErr_Restore(etype, evalue, etraceback);
+        }
     };
     null_mut()
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn loads(_self: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
-    match crate::deserialize::deserialize(obj) {
-        Ok(val) => val.as_ptr(),
-        Err(err) => raise_loads_exception(err),
+#[cold]
+#[inline(never)]
+#[cfg_attr(feature = "optimize", optimize(size))]
+fn raise_encode_error(err: serialize::EncodeError) -> *mut PyObject {
+    match err {
+        serialize::EncodeError::Message(_) => raise_dumps_exception(Cow::Owned(err.to_string())),
+        serialize::EncodeError::MaxSize(_) => raise_dumps_exception_type(
+            unsafe { typeref::JsonEncodeSizeError },
+            Cow::Owned(err.to_string()),
+        ),
+        serialize::EncodeError::Raised => null_mut(),
     }
 }
 
-#[cfg(Py_3_8)]
-#[no_mangle]
-pub unsafe extern "C" fn dumps(
-    _self: *mut PyObject,
-    args: *const *mut PyObject,
-    nargs: Py_ssize_t,
-    kwnames: *mut PyObject,
-) -> *mut PyObject {
-    let mut default: Option<NonNull<PyObject>> = None;
-    let mut optsptr: Option<NonNull<PyObject>> = None;
+#[inline(never)]
+unsafe fn parse_max_size(ptr: *mut PyObject) -> Result<Option<usize>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        return Ok(None);
+    }
+    if (*ptr).ob_type != typeref::INT_TYPE {
+        return Err(raise_dumps_exception(Cow::Borrowed("Invalid max_size")));
+    }
+    let val = PyLong_AsLongLong(ptr);
+    if val < 0 {
+        PyErr_Clear();
+        return Err(raise_dumps_exception(Cow::Borrowed("Invalid max_size")));
+    }
+    Ok(Some(val as usize))
+}
 
-    let num_args = PyVectorcall_NARGS(nargs as usize);
-    if unlikely!(num_args == 0) {
-        return raise_dumps_exception(Cow::Borrowed(
-            "dumps() missing 1 required positional argument: 'obj'",
+#[inline(never)]
+unsafe fn parse_max_depth(ptr: *mut PyObject) -> Result<Option<u32>, *mut PyObject> {
+    max_depth_value(ptr).ok_or_else(|| raise_dumps_exception(Cow::Borrowed("Invalid max_depth")))
+}
+
+#[inline(never)]
+unsafe fn parse_loads_max_depth(ptr: *mut PyObject) -> Result<Option<u32>, *mut PyObject> {
+    max_depth_value(ptr).ok_or_else(|| {
+        raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "Invalid max_depth",
+        )))
+    })
+}
+
+/// Return `max_depth`, an `int` or `None`, or `None` if it is invalid.
+unsafe fn max_depth_value(ptr: *mut PyObject) -> Option<Option<u32>> {
+    if ptr == typeref::NONE {
+        return Some(None);
+    }
+    if (*ptr).ob_type != typeref::INT_TYPE {
+        return None;
+    }
+    let val = PyLong_AsLongLong(ptr);
+    if !(0..=u32::MAX as i64).contains(&val) {
+        PyErr_Clear();
+        return None;
+    }
+    Some(Some(val as u32))
+}
+
+#[inline(never)]
+unsafe fn parse_max_str_len(ptr: *mut PyObject) -> Result<Option<usize>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        return Ok(None);
+    }
+    if (*ptr).ob_type != typeref::INT_TYPE {
+        return Err(raise_dumps_exception(Cow::Borrowed("Invalid max_str_len")));
+    }
+    let val = PyLong_AsLongLong(ptr);
+    if val < 0 {
+        PyErr_Clear();
+        return Err(raise_dumps_exception(Cow::Borrowed("Invalid max_str_len")));
+    }
+    Ok(Some(val as usize))
+}
+
+#[inline(never)]
+unsafe fn parse_transform(ptr: *mut PyObject) -> Result<Option<NonNull<PyObject>>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        Ok(None)
+    } else if PyCallable_Check(ptr) == 0 {
+        Err(raise_dumps_exception(Cow::Borrowed("Invalid transform")))
+    } else {
+        Ok(Some(NonNull::new_unchecked(ptr)))
+    }
+}
+
+#[inline(never)]
+unsafe fn parse_loads_hook(
+    ptr: *mut PyObject,
+    msg: &'static str,
+) -> Result<Option<NonNull<PyObject>>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        Ok(None)
+    } else if PyCallable_Check(ptr) == 0 {
+        Err(raise_loads_exception(
+            deserialize::DeserializeError::invalid(Cow::Borrowed(msg)),
+        ))
+    } else {
+        Ok(Some(NonNull::new_unchecked(ptr)))
+    }
+}
+
+#[inline(never)]
+unsafe fn parse_dict_class(ptr: *mut PyObject) -> Result<Option<NonNull<PyObject>>, *mut PyObject> {
+    if ptr == typeref::NONE || ptr == typeref::DICT_TYPE as *mut PyObject {
+        Ok(None)
+    } else if PyType_Check(ptr) == 0
+        || PyType_IsSubtype(ptr as *mut PyTypeObject, typeref::DICT_TYPE) == 0
+    {
+        Err(raise_loads_exception(
+            deserialize::DeserializeError::invalid(Cow::Borrowed(
+                "dict_class must be a subclass of dict",
+            )),
+        ))
+    } else {
+        Ok(Some(NonNull::new_unchecked(ptr)))
+    }
+}
+
+#[inline(never)]
+unsafe fn parse_loads_schema(
+    ptr: *mut PyObject,
+) -> Result<Option<NonNull<PyObject>>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        Ok(None)
+    } else if ob_type!(ptr) != typeref::SCHEMA_TYPE {
+        Err(raise_loads_exception(
+            deserialize::DeserializeError::invalid(Cow::Borrowed(
+                "schema must be an orjson.Schema",
+            )),
+        ))
+    } else {
+        Ok(Some(NonNull::new_unchecked(ptr)))
+    }
+}
+
+#[inline(never)]
+unsafe fn parse_loads_limit(
+    ptr: *mut PyObject,
+    msg: &'static str,
+) -> Result<Option<usize>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        return Ok(None);
+    }
+    if (*ptr).ob_type != typeref::INT_TYPE {
+        return Err(raise_loads_exception(
+            deserialize::DeserializeError::invalid(Cow::Borrowed(msg)),
         ));
     }
-    if num_args & 2 == 2 {
-        default = Some(NonNull::new_unchecked(*args.offset(1)));
+    let val = PyLong_AsLongLong(ptr);
+    if val < 0 {
+        PyErr_Clear();
+        return Err(raise_loads_exception(
+            deserialize::DeserializeError::invalid(Cow::Borrowed(msg)),
+        ));
     }
-    if num_args & 3 == 3 {
-        optsptr = Some(NonNull::new_unchecked(*args.offset(2)));
+    Ok(Some(val as usize))
+}
+
+#[inline(never)]
+unsafe fn parse_loads_flag(ptr: *mut PyObject, msg: &'static str) -> Result<bool, *mut PyObject> {
+    if ptr == typeref::TRUE {
+        Ok(true)
+    } else if ptr == typeref::FALSE {
+        Ok(false)
+    } else {
+        Err(raise_loads_exception(
+            deserialize::DeserializeError::invalid(Cow::Borrowed(msg)),
+        ))
     }
-    if !kwnames.is_null() {
-        for i in 0..=Py_SIZE(kwnames).saturating_sub(1) {
-            let arg = PyTuple_GET_ITEM(kwnames, i as Py_ssize_t);
-            if arg == typeref::DEFAULT {
-                if unlikely!(num_args & 2 == 2) {
-                    return raise_dumps_exception(Cow::Borrowed(
-                        "dumps() got multiple values for argument: 'default'",
-                    ));
-                }
-                default = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
-            } else if arg == typeref::OPTION {
-                if unlikely!(num_args & 3 == 3) {
-                    return raise_dumps_exception(Cow::Borrowed(
-                        "dumps() got multiple values for argument: 'option'",
-                    ));
-                }
-                optsptr = Some(NonNull::new_unchecked(*args.offset(num_args + i)));
-            } else {
-                return raise_dumps_exception(Cow::Borrowed(
-                    "dumps() got an unexpected keyword argument",
-                ));
+}
+
+#[inline(never)]
+unsafe fn parse_duplicate_keys(
+    ptr: *mut PyObject,
+) -> Result<crate::deserialize::DuplicateKeys, *mut PyObject> {
+    use crate::deserialize::DuplicateKeys;
+    if ptr == typeref::NONE {
+        return Ok(DuplicateKeys::Last);
+    }
+    let policy = if (*ptr).ob_type == typeref::STR_TYPE {
+        match crate::unicode::unicode_to_str(ptr) {
+            Some("last") => Some(DuplicateKeys::Last),
+            Some("first") => Some(DuplicateKeys::First),
+            Some("error") => Some(DuplicateKeys::Error),
+            Some("list") => Some(DuplicateKeys::List),
+            _ => None,
+        }
+    } else {
+        None
+    };
+    match policy {
+        Some(policy) => Ok(policy),
+        None => {
+            PyErr_Clear();
+            Err(raise_loads_exception(
+                deserialize::DeserializeError::invalid(Cow::Borrowed(
+                    "duplicate_keys must be \"last\", \"first\", \"error\", or \"list\"",
+                )),
+            ))
+        }
+    }
+}
+
+#[inline(never)]
+unsafe fn parse_surrogates(
+    ptr: *mut PyObject,
+) -> Result<crate::deserialize::Surrogates, *mut PyObject> {
+    use crate::deserialize::Surrogates;
+    if ptr == typeref::NONE {
+        return Ok(Surrogates::Reject);
+    }
+    let policy = if (*ptr).ob_type == typeref::STR_TYPE {
+        match crate::unicode::unicode_to_str(ptr) {
+            Some("reject") => Some(Surrogates::Reject),
+            Some("replace") => Some(Surrogates::Replace),
+            Some("surrogatepass") => Some(Surrogates::Pass),
+            _ => None,
+        }
+    } else {
+        None
+    };
+    match policy {
+        Some(policy) => Ok(policy),
+        None => {
+            PyErr_Clear();
+            Err(raise_loads_exception(
+                deserialize::DeserializeError::invalid(Cow::Borrowed(
+                    "surrogates must be \"reject\", \"replace\", or \"surrogatepass\"",
+                )),
+            ))
+        }
+    }
+}
+
+unsafe fn parse_compression(
+    ptr: *mut PyObject,
+) -> Result<Option<crate::deserialize::Compression>, *mut PyObject> {
+    use crate::deserialize::Compression;
+    if ptr == typeref::NONE {
+        return Ok(None);
+    }
+    let compression = if (*ptr).ob_type == typeref::STR_TYPE {
+        match crate::unicode::unicode_to_str(ptr) {
+            Some("gzip") => Some(Compression::Gzip),
+            Some("zstd") => Some(Compression::Zstd),
+            _ => None,
+        }
+    } else {
+        None
+    };
+    match compression {
+        Some(compression) => Ok(Some(compression)),
+        None => {
+            PyErr_Clear();
+            Err(raise_loads_exception(
+                deserialize::DeserializeError::invalid(Cow::Borrowed(
+                    "compression must be \"gzip\", \"zstd\", or None",
+                )),
+            ))
+        }
+    }
+}
+
+/// Check keyword arguments of `loads()` and `load()` that interact once all
+/// are recorded.
+#[inline(never)]
+unsafe fn check_loads_args(args: &mut crate::deserialize::LoadsArgs) -> Result<(), *mut PyObject> {
+    if args.strict {
+        if args.allow_nan {
+            return Err(raise_loads_exception(
+                deserialize::DeserializeError::invalid(Cow::Borrowed(
+                    "strict and allow_nan cannot both be enabled",
+                )),
+            ));
+        }
+        if args.jsonc {
+            return Err(raise_loads_exception(
+                deserialize::DeserializeError::invalid(Cow::Borrowed(
+                    "strict and jsonc cannot both be enabled",
+                )),
+            ));
+        }
+        if args.duplicate_keys == crate::deserialize::DuplicateKeys::Last {
+            args.duplicate_keys = crate::deserialize::DuplicateKeys::Error;
+        }
+    }
+    if args.immutable {
+        args.tuples = true;
+    }
+    if args.dict_class.is_some() && args.object_pairs_hook.is_some() {
+        return Err(raise_loads_exception(
+            deserialize::DeserializeError::invalid(Cow::Borrowed(
+                "dict_class cannot be combined with object_pairs_hook",
+            )),
+        ));
+    }
+    if args.schema.is_some()
+        && (args.parse_float.is_some()
+            || args.parse_int.is_some()
+            || args.object_hook.is_some()
+            || args.object_pairs_hook.is_some()
+            || args.dict_class.is_some()
+            || args.tuples
+            || args.datetimes
+            || args.uuids
+            || args.numpy
+            || args.raw_numbers
+            || args.duplicate_keys == crate::deserialize::DuplicateKeys::List)
+    {
+        return Err(raise_loads_exception(
+            deserialize::DeserializeError::invalid(Cow::Borrowed(
+                "schema cannot be combined with hooks, dict_class, tuples, immutable, datetimes, uuids, numpy, raw_numbers or duplicate_keys=\"list\"",
+            )),
+        ));
+    }
+    if args.numpy {
+        if args.parse_float.is_some() || args.parse_int.is_some() {
+            return Err(raise_loads_exception(
+                deserialize::DeserializeError::invalid(Cow::Borrowed(
+                    "numpy cannot be combined with parse_float or parse_int",
+                )),
+            ));
+        }
+        if typeref::NUMPY_TYPES.is_none() {
+            return Err(raise_loads_exception(
+                deserialize::DeserializeError::invalid(Cow::Borrowed("numpy is not installed")),
+            ));
+        }
+    }
+    if args.raw_numbers && (args.parse_float.is_some() || args.parse_int.is_some() || args.numpy) {
+        return Err(raise_loads_exception(
+            deserialize::DeserializeError::invalid(Cow::Borrowed(
+                "raw_numbers cannot be combined with parse_float, parse_int or numpy",
+            )),
+        ));
+    }
+    Ok(())
+}
+
+/// Record `arg=val` if `arg` is a keyword argument of `loads()` and `load()`.
+/// Return whether it is.
+#[inline(never)]
+unsafe fn parse_loads_kwarg(
+    arg: *mut PyObject,
+    val: *mut PyObject,
+    args: &mut crate::deserialize::LoadsArgs,
+) -> Result<bool, *mut PyObject> {
+    if arg == typeref::PARSE_FLOAT {
+        args.parse_float = parse_loads_hook(val, "Invalid parse_float")?;
+    } else if arg == typeref::PARSE_INT {
+        args.parse_int = parse_loads_hook(val, "Invalid parse_int")?;
+    } else if arg == typeref::OBJECT_HOOK {
+        args.object_hook = parse_loads_hook(val, "Invalid object_hook")?;
+    } else if arg == typeref::OBJECT_PAIRS_HOOK {
+        args.object_pairs_hook = parse_loads_hook(val, "Invalid object_pairs_hook")?;
+    } else if arg == typeref::DICT_CLASS {
+        args.dict_class = parse_dict_class(val)?;
+    } else if arg == typeref::DUPLICATE_KEYS {
+        args.duplicate_keys = parse_duplicate_keys(val)?;
+    } else if arg == typeref::SURROGATES {
+        args.surrogates = parse_surrogates(val)?;
+    } else if arg == typeref::REPLACE_INVALID_UTF8 {
+        args.replace_invalid_utf8 = parse_loads_flag(val, "Invalid replace_invalid_utf8")?;
+    } else if arg == typeref::ALLOW_NAN {
+        args.allow_nan = parse_loads_flag(val, "Invalid allow_nan")?;
+    } else if arg == typeref::STRICT {
+        args.strict = parse_loads_flag(val, "Invalid strict")?;
+    } else if arg == typeref::JSONC {
+        args.jsonc = parse_loads_flag(val, "Invalid jsonc")?;
+    } else if arg == typeref::MAX_DEPTH {
+        args.max_depth = parse_loads_max_depth(val)?;
+    } else if arg == typeref::MAX_ITEMS {
+        args.max_items = parse_loads_limit(val, "Invalid max_items")?;
+    } else if arg == typeref::MAX_STRING_LEN {
+        args.max_string_len = parse_loads_limit(val, "Invalid max_string_len")?;
+    } else if arg == typeref::MAX_STRING_BYTES {
+        args.max_string_bytes = parse_loads_limit(val, "Invalid max_string_bytes")?;
+    } else if arg == typeref::MAX_BYTES {
+        args.max_bytes = parse_loads_limit(val, "Invalid max_bytes")?;
+    } else if arg == typeref::TUPLES {
+        args.tuples = parse_loads_flag(val, "Invalid tuples")?;
+    } else if arg == typeref::IMMUTABLE {
+        args.immutable = parse_loads_flag(val, "Invalid immutable")?;
+    } else if arg == typeref::DATETIMES {
+        args.datetimes = parse_loads_flag(val, "Invalid datetimes")?;
+    } else if arg == typeref::UUIDS {
+        args.uuids = parse_loads_flag(val, "Invalid uuids")?;
+    } else if arg == typeref::NUMPY {
+        args.numpy = parse_loads_flag(val, "Invalid numpy")?;
+    } else if arg == typeref::RAW_NUMBERS {
+        args.raw_numbers = parse_loads_flag(val, "Invalid raw_numbers")?;
+    } else if arg == typeref::OFFSET {
+        args.offset = parse_loads_limit(val, "Invalid offset")?;
+    } else if arg == typeref::LENGTH {
+        args.length = parse_loads_limit(val, "Invalid length")?;
+    } else if arg == typeref::SCHEMA {
+        args.schema = parse_loads_schema(val)?;
+    } else if arg == typeref::THREADS {
+        args.threads = parse_loads_limit(val, "threads must be a positive int")?;
+        if args.threads == Some(0) {
+            return Err(raise_loads_exception(
+                deserialize::DeserializeError::invalid(Cow::Borrowed(
+                    "threads must be a positive int",
+                )),
+            ));
+        }
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+#[inline(never)]
+unsafe fn parse_redact_keys(
+    ptr: *mut PyObject,
+) -> Result<Option<crate::serialize::RedactKeys>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        return Ok(None);
+    }
+    match crate::serialize::RedactKeys::new(ptr) {
+        Some(val) => Ok(Some(val)),
+        None => Err(raise_dumps_exception(Cow::Borrowed("Invalid redact_keys"))),
+    }
+}
+
+#[inline(never)]
+unsafe fn parse_include_keys(
+    ptr: *mut PyObject,
+) -> Result<Option<crate::serialize::IncludeKeys>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        return Ok(None);
+    }
+    match crate::serialize::IncludeKeys::new(ptr) {
+        Some(val) => Ok(Some(val)),
+        None => Err(raise_dumps_exception(Cow::Borrowed("Invalid include_keys"))),
+    }
+}
+
+#[inline(never)]
+unsafe fn parse_separators(
+    ptr: *mut PyObject,
+) -> Result<Option<crate::serialize::Separators>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        return Ok(None);
+    }
+    match crate::serialize::Separators::new(ptr) {
+        Some(val) => Ok(Some(val)),
+        None => Err(raise_dumps_exception(Cow::Borrowed("Invalid separators"))),
+    }
+}
+
+#[inline(never)]
+unsafe fn parse_stats(ptr: *mut PyObject) -> Result<Option<NonNull<PyObject>>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        Ok(None)
+    } else if (*ptr).ob_type != typeref::DICT_TYPE {
+        Err(raise_dumps_exception(Cow::Borrowed("Invalid stats")))
+    } else {
+        Ok(Some(NonNull::new_unchecked(ptr)))
+    }
+}
+
+#[inline(never)]
+unsafe fn parse_float_precision(ptr: *mut PyObject) -> Result<Option<u8>, *mut PyObject> {
+    if ptr == typeref::NONE {
+        return Ok(None);
+    }
+    if (*ptr).ob_type != typeref::INT_TYPE {
+        return Err(raise_dumps_exception(Cow::Borrowed(
+            "Invalid float_precision",
+        )));
+    }
+    let val = PyLong_AsLongLong(ptr);
+    if !(0..=15).contains(&val) {
+        PyErr_Clear();
+        return Err(raise_dumps_exception(Cow::Borrowed(
+            "Invalid float_precision",
+        )));
+    }
+    Ok(Some(val as u8))
+}
+
+/// Parse `option`, an `int` or `None`, into option bits. It accepts `int`
+/// subclasses other than `bool`, e.g., `orjson.Options`.
+#[inline(never)]
+unsafe fn parse_option(ptr: *mut PyObject) -> Result<i32, *mut PyObject> {
+    if ptr == typeref::NONE {
+        return Ok(0);
+    }
+    let ob_type = (*ptr).ob_type;
+    if ob_type != typeref::INT_TYPE && (PyLong_Check(ptr) == 0 || ob_type == typeref::BOOL_TYPE) {
+        return Err(raise_dumps_exception(Cow::Borrowed("Invalid opts")));
+    }
+    let val = PyLong_AsLongLong(ptr);
+    if val < 0 {
+        PyErr_Clear();
+        return Err(raise_dumps_exception(Cow::Borrowed("Invalid opts")));
+    }
+    let unknown = val & !(opt::MAX_OPT as i64);
+    if unlikely!(unknown != 0) {
+        return Err(raise_dumps_exception(Cow::Owned(format!(
+            "Invalid opts: unknown bits {:#x}",
+            unknown
+        ))));
+    }
+    Ok(val as i32)
+}
+
+/// Options set and cleared by keyword arguments such as `sort_keys=True`.
+#[derive(Default)]
+struct OptionKwargs {
+    set: opt::Opt,
+    clear: opt::Opt,
+}
+
+impl OptionKwargs {
+    fn apply(&self, optsbits: i32) -> i32 {
+        (optsbits | self.set as i32) & !(self.clear as i32)
+    }
+}
+
+/// The arguments after `obj` of `dumps()` and the functions that serialize
+/// as it does.
+struct DumpsCall {
+    default: Option<NonNull<PyObject>>,
+    opts: opt::Opt,
+    args: crate::serialize::DumpsArgs,
+}
+
+/// Parse the arguments of `func`, which serializes as `dumps()` does, after
+/// its required positional arguments: `default` and `option`, given as the
+/// rest of the positional arguments in `positional` or by keyword, and the
+/// keyword arguments `separators`, `float_precision`, `max_size`, and the
+/// options in `kwargs`. `extra` records any other keyword argument of `func`
+/// and returns whether it is one.
+#[inline(never)]
+unsafe fn parse_dumps_kwargs<I, F>(
+    func: &str,
+    positional: &[*mut PyObject],
+    kwargs: I,
+    mut extra: F,
+) -> Result<DumpsCall, *mut PyObject>
+where
+    I: Iterator<Item = (*mut PyObject, *mut PyObject)>,
+    F: FnMut(
+        *mut PyObject,
+        *mut PyObject,
+        &mut crate::serialize::DumpsArgs,
+    ) -> Result<bool, *mut PyObject>,
+{
+    let mut default = positional.first().copied();
+    let mut optsptr = positional.get(1).copied();
+    let mut option_kwargs = OptionKwargs::default();
+    let mut args = crate::serialize::DumpsArgs::default();
+    for (arg, val) in kwargs {
+        if arg == typeref::DEFAULT {
+            if unlikely!(!positional.is_empty()) {
+                return Err(raise_dumps_exception(Cow::Owned(format!(
+                    "{}() got multiple values for argument: 'default'",
+                    func
+                ))));
+            }
+            default = Some(val);
+        } else if arg == typeref::OPTION {
+            if unlikely!(positional.len() == 2) {
+                return Err(raise_dumps_exception(Cow::Owned(format!(
+                    "{}() got multiple values for argument: 'option'",
+                    func
+                ))));
             }
+            optsptr = Some(val);
+        } else if arg == typeref::SEPARATORS {
+            args.separators = parse_separators(val)?;
+        } else if arg == typeref::FLOAT_PRECISION {
+            args.float_precision = parse_float_precision(val)?;
+        } else if arg == typeref::MAX_SIZE {
+            args.max_size = parse_max_size(val)?;
+        } else if !extra(arg, val, &mut args)? && !parse_option_kwarg(arg, val, &mut option_kwargs)?
+        {
+            return Err(raise_dumps_exception(Cow::Owned(format!(
+                "{}() got an unexpected keyword argument",
+                func
+            ))));
         }
     }
 
-    let mut optsbits: i32 = 0;
-    if let Some(opts) = optsptr {
-        if (*opts.as_ptr()).ob_type != typeref::INT_TYPE {
-            return raise_dumps_exception(Cow::Borrowed("Invalid opts"));
+    // the default options apply only if `option` is not given
+    let optsbits = match optsptr {
+        Some(opts) if opts != typeref::NONE => parse_option(opts)?,
+        _ => DEFAULT_OPTIONS as i32,
+    };
+    Ok(DumpsCall {
+        default: default
+            .filter(|&func| func != typeref::NONE)
+            .map(|func| NonNull::new_unchecked(func)),
+        opts: option_kwargs.apply(optsbits) as opt::Opt,
+        args: args,
+    })
+}
+
+/// The items of the tuple of positional arguments `ptr`.
+unsafe fn tuple_items<'a>(ptr: *mut PyObject) -> &'a [*mut PyObject] {
+    std::slice::from_raw_parts(
+        (*(ptr as *mut PyTupleObject)).ob_item.as_ptr(),
+        Py_SIZE(ptr) as usize,
+    )
+}
+
+/// The items of the `dict` of keyword arguments `ptr`, which is null if
+/// there are none.
+fn dict_items(ptr: *mut PyObject) -> impl Iterator<Item = (*mut PyObject, *mut PyObject)> {
+    (!ptr.is_null())
+        .then(|| crate::ffi::PyDictIter::from_pyobject(ptr))
+        .into_iter()
+        .flatten()
+}
+
+/// Record `arg=val` if `arg` is a keyword argument only of `dumps()`. Return
+/// whether it is.
+#[inline(never)]
+unsafe fn parse_dumps_only_kwarg(
+    arg: *mut PyObject,
+    val: *mut PyObject,
+    args: &mut crate::serialize::DumpsArgs,
+) -> Result<bool, *mut PyObject> {
+    if arg == typeref::STATS {
+        args.stats = parse_stats(val)?;
+    } else if arg == typeref::MAX_DEPTH {
+        args.max_depth = parse_max_depth(val)?;
+    } else if arg == typeref::REDACT_KEYS {
+        args.redact_keys = parse_redact_keys(val)?;
+    } else if arg == typeref::INCLUDE_KEYS {
+        args.include_keys = parse_include_keys(val)?;
+    } else if arg == typeref::MAX_STR_LEN {
+        args.max_str_len = parse_max_str_len(val)?;
+    } else if arg == typeref::TRANSFORM {
+        args.transform = parse_transform(val)?;
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Record `arg=val` if `arg` is an option keyword argument. Return whether
+/// it is.
+#[inline(never)]
+unsafe fn parse_option_kwarg(
+    arg: *mut PyObject,
+    val: *mut PyObject,
+    kwargs: &mut OptionKwargs,
+) -> Result<bool, *mut PyObject> {
+    // Keyword names are usually the interned strings; names built at runtime,
+    // e.g., passed as `**options`, are compared by value.
+    let arg_as_str = crate::unicode::unicode_to_str(arg);
+    for (i, &(name, opt)) in opt::OPTION_KWARGS.iter().enumerate() {
+        if arg != typeref::OPTION_KWARG_NAMES[i] && arg_as_str != Some(&name[..name.len() - 1]) {
+            continue;
         }
-        optsbits = PyLong_AsLong(optsptr.unwrap().as_ptr()) as i32;
-        if !(0..=opt::MAX_OPT).contains(&optsbits) {
-            return raise_dumps_exception(Cow::Borrowed("Invalid opts"));
+        let enabled = if opt == opt::INDENT_2 {
+            if val == typeref::NONE {
+                Some(false)
+            } else if (*val).ob_type == typeref::INT_TYPE && PyLong_AsLong(val) == 2 {
+                Some(true)
+            } else {
+                None
+            }
+        } else if val == typeref::TRUE {
+            Some(true)
+        } else if val == typeref::FALSE {
+            Some(false)
+        } else {
+            None
+        };
+        match enabled {
+            Some(true) => {
+                kwargs.set |= opt;
+                kwargs.clear &= !opt;
+            }
+            Some(false) => {
+                kwargs.clear |= opt;
+                kwargs.set &= !opt;
+            }
+            None => {
+                PyErr_Clear();
+                return Err(raise_dumps_exception(Cow::Owned(format!(
+                    "Invalid {}",
+                    &name[..name.len() - 1]
+                ))));
+            }
         }
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn compile(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    let num_args = Py_SIZE(args);
+    if unlikely!(num_args == 0) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "compile() missing 1 required positional argument: 'cls'",
+        ));
+    }
+    if unlikely!(num_args > 3) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "compile() takes at most 3 positional arguments",
+        ));
     }
+    let call = match parse_dumps_kwargs(
+        "compile",
+        &tuple_items(args)[1..],
+        dict_items(kwds),
+        |arg, val, args| {
+            if arg == typeref::MAX_DEPTH {
+                args.max_depth = parse_max_depth(val)?;
+                return Ok(true);
+            }
+            // the plan does not apply them, so they are refused rather than
+            // ignored
+            if arg == typeref::STATS
+                || arg == typeref::REDACT_KEYS
+                || arg == typeref::INCLUDE_KEYS
+                || arg == typeref::MAX_STR_LEN
+                || arg == typeref::TRANSFORM
+            {
+                return Err(raise_dumps_exception(Cow::Owned(format!(
+                    "compile() does not support the keyword argument: '{}'",
+                    crate::unicode::unicode_to_str(arg).unwrap_or_default()
+                ))));
+            }
+            Ok(false)
+        },
+    ) {
+        Ok(call) => call,
+        Err(err) => return err,
+    };
 
-    match crate::serialize::serialize(*args, default, optsbits as opt::Opt) {
+    match crate::serialize::compile(
+        PyTuple_GET_ITEM(args, 0),
+        call.default,
+        call.opts,
+        call.args,
+    ) {
         Ok(val) => val.as_ptr(),
-        Err(err) => raise_dumps_exception(Cow::Borrowed(&err)),
+        Err(err) => raise_encode_error(err),
     }
 }
 
-#[cfg(not(Py_3_8))]
 #[no_mangle]
-pub unsafe extern "C" fn dumps(
+pub unsafe extern "C" fn adump(
     _self: *mut PyObject,
     args: *mut PyObject,
     kwds: *mut PyObject,
 ) -> *mut PyObject {
-    let mut default: Option<NonNull<PyObject>> = None;
-    let mut optsptr: Option<NonNull<PyObject>> = None;
+    let num_args = Py_SIZE(args);
+    if unlikely!(num_args < 2) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "adump() missing required positional arguments: 'obj' and 'path'",
+        ));
+    }
+    if unlikely!(num_args > 4) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "adump() takes at most 4 positional arguments",
+        ));
+    }
+    let call = match parse_dumps_kwargs(
+        "adump",
+        &tuple_items(args)[2..],
+        dict_items(kwds),
+        |_, _, _| Ok(false),
+    ) {
+        Ok(call) => call,
+        Err(err) => return err,
+    };
 
-    let obj = PyTuple_GET_ITEM(args, 0);
+    crate::aio::adump(
+        PyTuple_GET_ITEM(args, 0),
+        PyTuple_GET_ITEM(args, 1),
+        call.default,
+        call.opts,
+        call.args,
+    )
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dump_fd(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    let mut lines = false;
 
     let num_args = Py_SIZE(args);
-    if unlikely!(num_args == 0) {
+    if unlikely!(num_args < 2) {
         return raise_dumps_exception(Cow::Borrowed(
-            "dumps() missing 1 required positional argument: 'obj'",
+            "dump_fd() missing required positional arguments: 'obj' and 'fd'",
         ));
     }
-    if num_args & 2 == 2 {
-        default = Some(NonNull::new_unchecked(PyTuple_GET_ITEM(args, 1)));
+    if unlikely!(num_args > 4) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "dump_fd() takes at most 4 positional arguments",
+        ));
     }
-    if num_args & 3 == 3 {
-        optsptr = Some(NonNull::new_unchecked(PyTuple_GET_ITEM(args, 2)));
+    let call = match parse_dumps_kwargs(
+        "dump_fd",
+        &tuple_items(args)[2..],
+        dict_items(kwds),
+        |arg, val, _| {
+            if arg != typeref::LINES {
+                return Ok(false);
+            }
+            if val == typeref::TRUE {
+                lines = true;
+            } else if val != typeref::FALSE {
+                return Err(raise_dumps_exception(Cow::Borrowed("Invalid lines")));
+            }
+            Ok(true)
+        },
+    ) {
+        Ok(call) => call,
+        Err(err) => return err,
+    };
+
+    match crate::serialize::dump_fd(
+        PyTuple_GET_ITEM(args, 0),
+        PyTuple_GET_ITEM(args, 1),
+        call.default,
+        call.opts,
+        &call.args,
+        lines,
+    ) {
+        Ok(written) => PyLong_FromSize_t(written),
+        Err(Some(err)) => raise_encode_error(err),
+        Err(None) => null_mut(),
     }
+}
 
+#[no_mangle]
+pub unsafe extern "C" fn dumps_into(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    let mut offset: usize = 0;
+
+    let num_args = Py_SIZE(args);
+    if unlikely!(num_args < 2) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "dumps_into() missing required positional arguments: 'obj' and 'buffer'",
+        ));
+    }
+    if unlikely!(num_args > 4) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "dumps_into() takes at most 4 positional arguments",
+        ));
+    }
+    let call = match parse_dumps_kwargs(
+        "dumps_into",
+        &tuple_items(args)[2..],
+        dict_items(kwds),
+        |arg, val, _| {
+            if arg != typeref::OFFSET {
+                return Ok(false);
+            }
+            if (*val).ob_type != typeref::INT_TYPE {
+                return Err(raise_dumps_exception(Cow::Borrowed("Invalid offset")));
+            }
+            let val = PyLong_AsLongLong(val);
+            if val < 0 {
+                PyErr_Clear();
+                return Err(raise_dumps_exception(Cow::Borrowed("Invalid offset")));
+            }
+            offset = val as usize;
+            Ok(true)
+        },
+    ) {
+        Ok(call) => call,
+        Err(err) => return err,
+    };
+
+    let max_size = call.args.max_size;
+    match crate::serialize::dumps_into(
+        PyTuple_GET_ITEM(args, 0),
+        PyTuple_GET_ITEM(args, 1),
+        offset,
+        call.default,
+        call.opts,
+        call.args,
+    ) {
+        Ok(written) => PyLong_FromSize_t(written),
+        // the output does not fit in the buffer rather than `max_size`
+        Err(Some(serialize::EncodeError::MaxSize(limit))) if Some(limit) != max_size => {
+            raise_dumps_exception_type(
+                typeref::JsonEncodeSizeError,
+                Cow::Owned(format!(
+                    "Output exceeds the {} bytes of the buffer after offset",
+                    limit
+                )),
+            )
+        }
+        Err(Some(err)) => raise_encode_error(err),
+        Err(None) => null_mut(),
+    }
+}
+
+// Options set by set_default_options(). The GIL serializes access.
+static mut DEFAULT_OPTIONS: opt::Opt = 0;
+
+#[no_mangle]
+pub unsafe extern "C" fn set_default_options(
+    _self: *mut PyObject,
+    obj: *mut PyObject,
+) -> *mut PyObject {
+    match parse_option(obj) {
+        Ok(val) => DEFAULT_OPTIONS = val as opt::Opt,
+        Err(err) => return err,
+    }
+    Py_INCREF(typeref::NONE);
+    typeref::NONE
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn get_default_options(
+    _self: *mut PyObject,
+    _args: *mut PyObject,
+) -> *mut PyObject {
+    PyLong_FromLong(DEFAULT_OPTIONS as std::os::raw::c_long)
+}
+
+#[cfg(Py_3_8)]
+#[no_mangle]
+pub unsafe extern "C" fn loads(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    let num_args = PyVectorcall_NARGS(nargs as usize);
+    if unlikely!(num_args != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "loads() takes exactly 1 positional argument",
+        )));
+    }
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if unlikely!(!kwnames.is_null()) {
+        for i in 0..Py_SIZE(kwnames) {
+            let arg = PyTuple_GET_ITEM(kwnames, i);
+            match parse_loads_kwarg(arg, *args.offset(num_args + i), &mut loads_args) {
+                Ok(true) => {}
+                Ok(false) => return raise_unexpected_kwarg("loads", arg),
+                Err(err) => return err,
+            }
+        }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
+        }
+    }
+    match crate::deserialize::deserialize(*args, &loads_args) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[cfg(not(Py_3_8))]
+#[no_mangle]
+pub unsafe extern "C" fn loads(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "loads() takes exactly 1 positional argument",
+        )));
+    }
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if unlikely!(!kwds.is_null()) {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            match parse_loads_kwarg(arg, val, &mut loads_args) {
+                Ok(true) => {}
+                Ok(false) => return raise_unexpected_kwarg("loads", arg),
+                Err(err) => return err,
+            }
+        }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
+        }
+    }
+    match crate::deserialize::deserialize(PyTuple_GET_ITEM(args, 0), &loads_args) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn load(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "load() takes exactly 1 positional argument",
+        )));
+    }
+    let mut chunk_size: Option<usize> = None;
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
     if !kwds.is_null() {
         for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
-            if arg == typeref::DEFAULT {
-                if unlikely!(num_args & 2 == 2) {
-                    return raise_dumps_exception(Cow::Borrowed(
-                        "dumps() got multiple values for argument: 'default'",
-                    ));
+            if arg == typeref::CHUNK_SIZE {
+                if val == typeref::NONE {
+                    continue;
                 }
-                default = Some(NonNull::new_unchecked(val));
-            } else if arg == typeref::OPTION {
-                if unlikely!(num_args & 3 == 3) {
-                    return raise_dumps_exception(Cow::Borrowed(
-                        "dumps() got multiple values for argument: 'option'",
+                let size = PyLong_AsSsize_t(val);
+                if unlikely!(size <= 0) {
+                    PyErr_Clear();
+                    return raise_loads_exception(deserialize::DeserializeError::invalid(
+                        Cow::Borrowed("chunk_size must be a positive int"),
                     ));
                 }
-                optsptr = Some(NonNull::new_unchecked(val));
-            } else if arg.is_null() {
-                break;
+                chunk_size = Some(size as usize);
+            } else if arg == typeref::OFFSET || arg == typeref::LENGTH {
+                return raise_unexpected_kwarg("load", arg);
+            } else {
+                match parse_loads_kwarg(arg, val, &mut loads_args) {
+                    Ok(true) => {}
+                    Ok(false) => return raise_unexpected_kwarg("load", arg),
+                    Err(err) => return err,
+                }
+            }
+        }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
+        }
+    }
+    let obj = PyTuple_GET_ITEM(args, 0);
+    let res = match chunk_size {
+        Some(size) => crate::deserialize::deserialize_file_chunked(obj, size, &loads_args),
+        None => crate::deserialize::deserialize_file(obj, &loads_args),
+    };
+    match res {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn load_mmap(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "load_mmap() takes exactly 1 positional argument",
+        )));
+    }
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            match parse_loads_kwarg(arg, val, &mut loads_args) {
+                Ok(true) => {}
+                Ok(false) => return raise_unexpected_kwarg("load_mmap", arg),
+                Err(err) => return err,
+            }
+        }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
+        }
+    }
+    match crate::deserialize::load_mmap(PyTuple_GET_ITEM(args, 0), &loads_args) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn aload(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "aload() takes exactly 1 positional argument",
+        )));
+    }
+    let loads_args = match crate::deserialize::parse_kwargs(
+        kwds,
+        &[typeref::OFFSET, typeref::LENGTH],
+        "aload",
+    ) {
+        Ok(loads_args) => loads_args,
+        Err(err) => return err,
+    };
+    crate::aio::aload(PyTuple_GET_ITEM(args, 0), loads_args)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn loads_into(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 2) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "loads_into() takes exactly 2 positional arguments",
+        )));
+    }
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            // objects are made by the type given rather than these
+            let known = arg != typeref::PARSE_FLOAT
+                && arg != typeref::PARSE_INT
+                && arg != typeref::OBJECT_HOOK
+                && arg != typeref::OBJECT_PAIRS_HOOK
+                && arg != typeref::DICT_CLASS
+                && arg != typeref::TUPLES
+                && arg != typeref::IMMUTABLE
+                && arg != typeref::DATETIMES
+                && arg != typeref::UUIDS
+                && arg != typeref::NUMPY
+                && arg != typeref::RAW_NUMBERS
+                && match parse_loads_kwarg(arg, val, &mut loads_args) {
+                    Ok(known) => known,
+                    Err(err) => return err,
+                };
+            if !known {
+                return raise_unexpected_kwarg("loads_into", arg);
+            }
+        }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
+        }
+    }
+    match crate::deserialize::loads_into(
+        PyTuple_GET_ITEM(args, 0),
+        PyTuple_GET_ITEM(args, 1),
+        &loads_args,
+    ) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+// not `select`, as it would replace the C library's `select()`
+#[no_mangle]
+pub unsafe extern "C" fn select_values(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 2) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "select() takes exactly 2 positional arguments",
+        )));
+    }
+    let mut paths = false;
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            if arg == typeref::PATHS {
+                paths = match parse_loads_flag(val, "Invalid paths") {
+                    Ok(paths) => paths,
+                    Err(err) => return err,
+                };
             } else {
-                return raise_dumps_exception(Cow::Borrowed(
-                    "dumps() got an unexpected keyword argument",
-                ));
+                return raise_unexpected_kwarg("select", arg);
+            }
+        }
+    }
+    let query = PyTuple_GET_ITEM(args, 1);
+    if !is_type!(ob_type!(query), typeref::STR_TYPE) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "select() path must be a str",
+        )));
+    }
+    let query = match crate::unicode::unicode_to_str(query) {
+        Some(query) => query,
+        None => {
+            return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+                crate::error::INVALID_STR,
+            )))
+        }
+    };
+    match crate::deserialize::select(PyTuple_GET_ITEM(args, 0), query, paths) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn loads_columns(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "loads_columns() takes exactly 1 positional argument",
+        )));
+    }
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            // the document is not deserialized as a whole, so it cannot be
+            // split into parts, made an array, or validated
+            let known = arg != typeref::THREADS
+                && arg != typeref::NUMPY
+                && arg != typeref::SCHEMA
+                && match parse_loads_kwarg(arg, val, &mut loads_args) {
+                    Ok(known) => known,
+                    Err(err) => return err,
+                };
+            if !known {
+                return raise_unexpected_kwarg("loads_columns", arg);
+            }
+        }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
+        }
+    }
+    match crate::deserialize::loads_columns(PyTuple_GET_ITEM(args, 0), &mut loads_args) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn loads_prefix(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "loads_prefix() takes exactly 1 positional argument",
+        )));
+    }
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            // the value is parsed in one piece, and the index at which it
+            // ends is of the input rather than of it with invalid UTF-8
+            // replaced
+            let known = arg != typeref::THREADS
+                && arg != typeref::REPLACE_INVALID_UTF8
+                && match parse_loads_kwarg(arg, val, &mut loads_args) {
+                    Ok(known) => known,
+                    Err(err) => return err,
+                };
+            if !known {
+                return raise_unexpected_kwarg("loads_prefix", arg);
             }
         }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
+        }
+    }
+    match crate::deserialize::loads_prefix(PyTuple_GET_ITEM(args, 0), &loads_args) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
     }
+}
 
-    let mut optsbits: i32 = 0;
-    if let Some(opts) = optsptr {
-        if (*opts.as_ptr()).ob_type != typeref::INT_TYPE {
-            return raise_dumps_exception(Cow::Borrowed("Invalid opts"));
+#[no_mangle]
+pub unsafe extern "C" fn unpackb(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "unpackb() takes exactly 1 positional argument",
+        )));
+    }
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            // comments, numbers as written, escaped surrogates, and invalid
+            // UTF-8 are of text, the input is parsed in one piece, and arrays
+            // are not deserialized to numpy arrays
+            let known = arg != typeref::JSONC
+                && arg != typeref::THREADS
+                && arg != typeref::NUMPY
+                && arg != typeref::RAW_NUMBERS
+                && arg != typeref::SURROGATES
+                && arg != typeref::REPLACE_INVALID_UTF8
+                && match parse_loads_kwarg(arg, val, &mut loads_args) {
+                    Ok(known) => known,
+                    Err(err) => return err,
+                };
+            if !known {
+                return raise_unexpected_kwarg("unpackb", arg);
+            }
+        }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
         }
-        optsbits = PyLong_AsLong(optsptr.unwrap().as_ptr()) as i32;
-        if optsbits < 0 || optsbits > opt::MAX_OPT {
-            return raise_dumps_exception(Cow::Borrowed("Invalid opts"));
+    }
+    match crate::deserialize::unpackb(PyTuple_GET_ITEM(args, 0), &loads_args) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn loads_cbor(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "loads_cbor() takes exactly 1 positional argument",
+        )));
+    }
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            // comments, numbers as written, escaped surrogates, and invalid
+            // UTF-8 are of text, the input is parsed in one piece, and arrays
+            // are not deserialized to numpy arrays
+            let known = arg != typeref::JSONC
+                && arg != typeref::THREADS
+                && arg != typeref::NUMPY
+                && arg != typeref::RAW_NUMBERS
+                && arg != typeref::SURROGATES
+                && arg != typeref::REPLACE_INVALID_UTF8
+                && match parse_loads_kwarg(arg, val, &mut loads_args) {
+                    Ok(known) => known,
+                    Err(err) => return err,
+                };
+            if !known {
+                return raise_unexpected_kwarg("loads_cbor", arg);
+            }
+        }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
+        }
+    }
+    match crate::deserialize::loads_cbor(PyTuple_GET_ITEM(args, 0), &loads_args) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn loads_compressed(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "loads_compressed() takes exactly 1 positional argument",
+        )));
+    }
+    let mut compression: Option<crate::deserialize::Compression> = None;
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            if arg == typeref::COMPRESSION {
+                compression = match parse_compression(val) {
+                    Ok(compression) => compression,
+                    Err(err) => return err,
+                };
+                continue;
+            }
+            // the input is decompressed and parsed as it is read
+            let known = arg != typeref::OFFSET
+                && arg != typeref::LENGTH
+                && arg != typeref::THREADS
+                && match parse_loads_kwarg(arg, val, &mut loads_args) {
+                    Ok(known) => known,
+                    Err(err) => return err,
+                };
+            if !known {
+                return raise_unexpected_kwarg("loads_compressed", arg);
+            }
+        }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
+        }
+    }
+    match crate::deserialize::loads_compressed(PyTuple_GET_ITEM(args, 0), compression, &loads_args)
+    {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn is_valid(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "is_valid() takes exactly 1 positional argument",
+        )));
+    }
+    let mut position = false;
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            if arg == typeref::POSITION {
+                position = match parse_loads_flag(val, "Invalid position") {
+                    Ok(position) => position,
+                    Err(err) => return err,
+                };
+                continue;
+            }
+            // only arguments deciding whether a document is accepted
+            let known = (arg == typeref::DUPLICATE_KEYS
+                || arg == typeref::SURROGATES
+                || arg == typeref::ALLOW_NAN
+                || arg == typeref::STRICT
+                || arg == typeref::JSONC
+                || arg == typeref::MAX_DEPTH
+                || arg == typeref::MAX_ITEMS
+                || arg == typeref::MAX_STRING_LEN
+                || arg == typeref::MAX_STRING_BYTES
+                || arg == typeref::MAX_BYTES)
+                && match parse_loads_kwarg(arg, val, &mut loads_args) {
+                    Ok(known) => known,
+                    Err(err) => return err,
+                };
+            if !known {
+                return raise_unexpected_kwarg("is_valid", arg);
+            }
+        }
+        if let Err(err) = check_loads_args(&mut loads_args) {
+            return err;
+        }
+    }
+    match crate::deserialize::is_valid(PyTuple_GET_ITEM(args, 0), &loads_args, position) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn find_errors(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if unlikely!(Py_SIZE(args) != 1) {
+        return raise_loads_exception(deserialize::DeserializeError::invalid(Cow::Borrowed(
+            "find_errors() takes exactly 1 positional argument",
+        )));
+    }
+    let mut max_errors = None;
+    let mut loads_args = crate::deserialize::LoadsArgs::default();
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            if arg == typeref::MAX_ERRORS {
+                max_errors = match parse_loads_limit(val, "Invalid max_errors") {
+                    Ok(max_errors) => max_errors,
+                    Err(err) => return err,
+                };
+                continue;
+            }
+            // only arguments changing the syntax that is accepted
+            let known = (arg == typeref::SURROGATES
+                || arg == typeref::ALLOW_NAN
+                || arg == typeref::JSONC
+                || arg == typeref::MAX_DEPTH)
+                && match parse_loads_kwarg(arg, val, &mut loads_args) {
+                    Ok(known) => known,
+                    Err(err) => return err,
+                };
+            if !known {
+                return raise_unexpected_kwarg("find_errors", arg);
+            }
         }
     }
+    match crate::deserialize::find_errors(PyTuple_GET_ITEM(args, 0), &loads_args, max_errors) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn iter_array(_self: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
+    match crate::deserialize::iter_array(obj) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn tokenize(_self: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
+    match crate::deserialize::tokenize(obj) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn loads_lines(_self: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
+    match crate::deserialize::loads_lines(obj) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn iter_documents(_self: *mut PyObject, obj: *mut PyObject) -> *mut PyObject {
+    match crate::deserialize::iter_documents(obj) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(err)) => raise_loads_exception(err),
+        Err(None) => null_mut(),
+    }
+}
+
+#[cfg(Py_3_8)]
+#[no_mangle]
+pub unsafe extern "C" fn dumps(
+    _self: *mut PyObject,
+    args: *const *mut PyObject,
+    nargs: Py_ssize_t,
+    kwnames: *mut PyObject,
+) -> *mut PyObject {
+    let num_args = PyVectorcall_NARGS(nargs as usize);
+    if unlikely!(num_args == 0) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "dumps() missing 1 required positional argument: 'obj'",
+        ));
+    }
+    if unlikely!(num_args > 3) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "dumps() takes at most 3 positional arguments",
+        ));
+    }
+    let num_kwargs = if kwnames.is_null() {
+        0
+    } else {
+        Py_SIZE(kwnames)
+    };
+    let call = match parse_dumps_kwargs(
+        "dumps",
+        std::slice::from_raw_parts(args.offset(1), num_args as usize - 1),
+        (0..num_kwargs).map(|i| (PyTuple_GET_ITEM(kwnames, i), *args.offset(num_args + i))),
+        |arg, val, args| parse_dumps_only_kwarg(arg, val, args),
+    ) {
+        Ok(call) => call,
+        Err(err) => return err,
+    };
+
+    match crate::serialize::serialize(*args, call.default, call.opts, call.args) {
+        Ok(val) => val.as_ptr(),
+        Err(err) => raise_encode_error(err),
+    }
+}
+
+#[cfg(not(Py_3_8))]
+#[no_mangle]
+pub unsafe extern "C" fn dumps(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    let num_args = Py_SIZE(args);
+    if unlikely!(num_args == 0) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "dumps() missing 1 required positional argument: 'obj'",
+        ));
+    }
+    if unlikely!(num_args > 3) {
+        return raise_dumps_exception(Cow::Borrowed(
+            "dumps() takes at most 3 positional arguments",
+        ));
+    }
+    let call = match parse_dumps_kwargs(
+        "dumps",
+        &tuple_items(args)[1..],
+        dict_items(kwds),
+        |arg, val, args| parse_dumps_only_kwarg(arg, val, args),
+    ) {
+        Ok(call) => call,
+        Err(err) => return err,
+    };
 
-    match crate::serialize::serialize(obj, default, optsbits as opt::Opt) {
+    match crate::serialize::serialize(
+        PyTuple_GET_ITEM(args, 0),
+        call.default,
+        call.opts,
+        call.args,
+    ) {
         Ok(val) => val.as_ptr(),
-        Err(err) => raise_dumps_exception(Cow::Owned(err)),
+        Err(err) => raise_encode_error(err),
     }
 }
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-pub type Opt = u16;
+pub type Opt = u32;
 
 pub const INDENT_2: Opt = 1;
 pub const NAIVE_UTC: Opt = 1 << 1;
@@ -14,6 +14,20 @@ pub const PASSTHROUGH_SUBCLASS: Opt = 1 << 8;
 pub const PASSTHROUGH_DATETIME: Opt = 1 << 9;
 pub const APPEND_NEWLINE: Opt = 1 << 10;
 pub const PASSTHROUGH_DATACLASS: Opt = 1 << 11;
+pub const SERIALIZE_GEO_INTERFACE: Opt = 1 << 12;
+pub const BYTES_KEYS: Opt = 1 << 13;
+pub const JS: Opt = 1 << 14;
+pub const NUMBERS_AS_STRINGS: Opt = 1 << 15;
+pub const NORMALIZE_FLOAT: Opt = 1 << 16;
+pub const NORMALIZE_NEGATIVE_ZERO: Opt = 1 << 17;
+pub const FALLBACK_REPR: Opt = 1 << 18;
+pub const FALLBACK_STR: Opt = 1 << 19;
+pub const FALLBACK_VARS: Opt = 1 << 20;
+pub const SERIALIZE_MAPPING: Opt = 1 << 21;
+pub const SERIALIZE_SEQUENCE: Opt = 1 << 22;
+pub const SERIALIZE_MULTIDICT: Opt = 1 << 23;
+pub const SERIALIZE_PAIRS: Opt = 1 << 24;
+pub const DEFAULT_PATH: Opt = 1 << 25;
 
 // deprecated
 pub const SERIALIZE_DATACLASS: Opt = 0;
@@ -21,19 +35,104 @@ pub const SERIALIZE_UUID: Opt = 0;
 
 pub const SORT_OR_NON_STR_KEYS: Opt = SORT_KEYS | NON_STR_KEYS;
 
+pub const NON_STR_OR_BYTES_KEYS: Opt = NON_STR_KEYS | BYTES_KEYS;
+
+pub const UTC_Z_OR_JS: Opt = UTC_Z | JS;
+
+pub const STRICT_INTEGER_OR_JS: Opt = STRICT_INTEGER | JS;
+
+pub const MAPPING_OR_MULTIDICT: Opt = SERIALIZE_MAPPING | SERIALIZE_MULTIDICT;
+
+pub const ANY_FALLBACK: Opt = FALLBACK_REPR | FALLBACK_STR | FALLBACK_VARS;
+
 pub const NOT_PASSTHROUGH: Opt =
     !(PASSTHROUGH_DATETIME | PASSTHROUGH_DATACLASS | PASSTHROUGH_SUBCLASS);
 
+/// Members of `orjson.Options`, named as the `OPT_` constants.
+pub const OPTION_NAMES: [(&str, Opt); 26] = [
+    ("APPEND_NEWLINE\0", APPEND_NEWLINE),
+    ("BYTES_KEYS\0", BYTES_KEYS),
+    ("DEFAULT_PATH\0", DEFAULT_PATH),
+    ("FALLBACK_REPR\0", FALLBACK_REPR),
+    ("FALLBACK_STR\0", FALLBACK_STR),
+    ("FALLBACK_VARS\0", FALLBACK_VARS),
+    ("INDENT_2\0", INDENT_2),
+    ("JS\0", JS),
+    ("NAIVE_UTC\0", NAIVE_UTC),
+    ("NON_STR_KEYS\0", NON_STR_KEYS),
+    ("NORMALIZE_FLOAT\0", NORMALIZE_FLOAT),
+    ("NORMALIZE_NEGATIVE_ZERO\0", NORMALIZE_NEGATIVE_ZERO),
+    ("NUMBERS_AS_STRINGS\0", NUMBERS_AS_STRINGS),
+    ("OMIT_MICROSECONDS\0", OMIT_MICROSECONDS),
+    ("PASSTHROUGH_DATACLASS\0", PASSTHROUGH_DATACLASS),
+    ("PASSTHROUGH_DATETIME\0", PASSTHROUGH_DATETIME),
+    ("PASSTHROUGH_SUBCLASS\0", PASSTHROUGH_SUBCLASS),
+    ("SERIALIZE_GEO_INTERFACE\0", SERIALIZE_GEO_INTERFACE),
+    ("SERIALIZE_MAPPING\0", SERIALIZE_MAPPING),
+    ("SERIALIZE_MULTIDICT\0", SERIALIZE_MULTIDICT),
+    ("SERIALIZE_NUMPY\0", SERIALIZE_NUMPY),
+    ("SERIALIZE_PAIRS\0", SERIALIZE_PAIRS),
+    ("SERIALIZE_SEQUENCE\0", SERIALIZE_SEQUENCE),
+    ("SORT_KEYS\0", SORT_KEYS),
+    ("STRICT_INTEGER\0", STRICT_INTEGER),
+    ("UTC_Z\0", UTC_Z),
+];
+
+/// Keyword arguments of `dumps()` equivalent to an option, e.g.,
+/// `sort_keys=True` for `OPT_SORT_KEYS`.
+pub const OPTION_KWARGS: [(&str, Opt); 26] = [
+    ("append_newline\0", APPEND_NEWLINE),
+    ("bytes_keys\0", BYTES_KEYS),
+    ("default_path\0", DEFAULT_PATH),
+    ("fallback_repr\0", FALLBACK_REPR),
+    ("fallback_str\0", FALLBACK_STR),
+    ("fallback_vars\0", FALLBACK_VARS),
+    ("indent\0", INDENT_2),
+    ("js\0", JS),
+    ("naive_utc\0", NAIVE_UTC),
+    ("non_str_keys\0", NON_STR_KEYS),
+    ("normalize_float\0", NORMALIZE_FLOAT),
+    ("normalize_negative_zero\0", NORMALIZE_NEGATIVE_ZERO),
+    ("numbers_as_strings\0", NUMBERS_AS_STRINGS),
+    ("omit_microseconds\0", OMIT_MICROSECONDS),
+    ("passthrough_dataclass\0", PASSTHROUGH_DATACLASS),
+    ("passthrough_datetime\0", PASSTHROUGH_DATETIME),
+    ("passthrough_subclass\0", PASSTHROUGH_SUBCLASS),
+    ("serialize_geo_interface\0", SERIALIZE_GEO_INTERFACE),
+    ("serialize_mapping\0", SERIALIZE_MAPPING),
+    ("serialize_multidict\0", SERIALIZE_MULTIDICT),
+    ("serialize_numpy\0", SERIALIZE_NUMPY),
+    ("serialize_pairs\0", SERIALIZE_PAIRS),
+    ("serialize_sequence\0", SERIALIZE_SEQUENCE),
+    ("sort_keys\0", SORT_KEYS),
+    ("strict_integer\0", STRICT_INTEGER),
+    ("utc_z\0", UTC_Z),
+];
+
 pub const MAX_OPT: i32 = (APPEND_NEWLINE
+    | BYTES_KEYS
+    | DEFAULT_PATH
+    | FALLBACK_REPR
+    | FALLBACK_STR
+    | FALLBACK_VARS
     | INDENT_2
+    | JS
     | NAIVE_UTC
     | NON_STR_KEYS
+    | NORMALIZE_FLOAT
+    | NORMALIZE_NEGATIVE_ZERO
+    | NUMBERS_AS_STRINGS
     | OMIT_MICROSECONDS
     | PASSTHROUGH_DATETIME
     | PASSTHROUGH_DATACLASS
     | PASSTHROUGH_SUBCLASS
     | SERIALIZE_DATACLASS
+    | SERIALIZE_GEO_INTERFACE
+    | SERIALIZE_MAPPING
+    | SERIALIZE_MULTIDICT
     | SERIALIZE_NUMPY
+    | SERIALIZE_PAIRS
+    | SERIALIZE_SEQUENCE
     | SERIALIZE_UUID
     | SORT_KEYS
     | STRICT_INTEGER
@@ -0,0 +1,747 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::opt::*;
+use crate::serialize::encode::*;
+use crate::serialize::error::*;
+use crate::serialize::serializer::*;
+use crate::serialize::writer::BytesWriter;
+use crate::typeref::*;
+use crate::unicode::unicode_to_str;
+use std::borrow::Cow;
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr::{null_mut, NonNull};
+
+/// Output is written once at least this many bytes of it are serialized if
+/// `chunk_size` is not given.
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// `orjson.AsyncWriter`, which serializes to an asynchronous stream in
+/// chunks, awaiting each being written before serializing the next.
+#[repr(C)]
+pub struct AsyncWriter {
+    ob_base: pyo3_ffi::PyObject,
+    // strong references; `write` is the callable returning an awaitable or,
+    // with `drain`, the bound methods of a stream such as
+    // `asyncio.StreamWriter`
+    write: *mut pyo3_ffi::PyObject,
+    drain: *mut pyo3_ffi::PyObject,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    opts: Opt,
+    chunk_size: usize,
+}
+
+/// The awaitable returned by `AsyncWriter.dump()`, which serializes the
+/// next chunk each time the awaitable of writing the last is done.
+#[repr(C)]
+pub struct AsyncDump {
+    ob_base: pyo3_ffi::PyObject,
+    // strong reference keeping `write`, `drain`, and `default` alive
+    writer: *mut AsyncWriter,
+    // strong reference to the iterator of the awaitable writing the last
+    // chunk, or null
+    awaiting: *mut pyo3_ffi::PyObject,
+    // set while serializing, when `default` must not advance the awaitable
+    busy: bool,
+    // `None` once done or after an error, which cannot be resumed from
+    state: Option<Box<DumpState>>,
+}
+
+/// The part of the object given to `dump()` that is left to serialize.
+/// The containers are strong references.
+enum Entries {
+    // serialized in one chunk as it is not a container streamed
+    Whole(*mut pyo3_ffi::PyObject),
+    // the length is read each time as the list may be modified while a
+    // chunk is written
+    List(*mut pyo3_ffi::PyObject, isize),
+    Tuple(*mut pyo3_ffi::PyObject, isize),
+    // a list of the (key, value) tuples of a dict when `dump()` is called
+    Items(*mut pyo3_ffi::PyObject, isize),
+    Iterator(*mut pyo3_ffi::PyObject, bool),
+    Done,
+}
+
+struct DumpState {
+    enc: Encoder,
+    entries: Entries,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    opts: Opt,
+    chunk_size: usize,
+}
+
+impl Drop for DumpState {
+    fn drop(&mut self) {
+        match self.entries {
+            Entries::Whole(ptr)
+            | Entries::List(ptr, _)
+            | Entries::Tuple(ptr, _)
+            | Entries::Items(ptr, _)
+            | Entries::Iterator(ptr, _) => ffi!(Py_DECREF(ptr)),
+            Entries::Done => {}
+        }
+        self.enc.discard_output();
+    }
+}
+
+impl DumpState {
+    fn new(
+        ptr: *mut pyo3_ffi::PyObject,
+        default: Option<NonNull<pyo3_ffi::PyObject>>,
+        opts: Opt,
+        chunk_size: usize,
+    ) -> Result<Self, ()> {
+        let ob_type = ob_type!(ptr);
+        let entries = if ob_type == unsafe { LIST_TYPE } {
+            ffi!(Py_INCREF(ptr));
+            Entries::List(ptr, 0)
+        } else if ob_type == unsafe { TUPLE_TYPE } {
+            ffi!(Py_INCREF(ptr));
+            Entries::Tuple(ptr, 0)
+        } else if ob_type == unsafe { DICT_TYPE } && opts & (SORT_KEYS | NON_STR_KEYS) == 0 {
+            let items = ffi!(PyDict_Items(ptr));
+            if items.is_null() {
+                return Err(());
+            }
+            Entries::Items(items, 0)
+        } else if ob_type != unsafe { DICT_TYPE } && ffi!(PyIter_Check(ptr)) != 0 {
+            ffi!(Py_INCREF(ptr));
+            Entries::Iterator(ptr, true)
+        } else {
+            ffi!(Py_INCREF(ptr));
+            Entries::Whole(ptr)
+        };
+        Ok(DumpState {
+            enc: Encoder::new(BytesWriter::default(), opts, DumpsArgs::default()),
+            entries: entries,
+            default: default,
+            opts: opts,
+            chunk_size: chunk_size,
+        })
+    }
+
+    /// Write the start of the container on the first call and the separator
+    /// and key before the next value, returning a strong reference to it or,
+    /// after the last, end the container.
+    fn next_value(&mut self) -> Result<Option<*mut pyo3_ffi::PyObject>, Option<EncodeError>> {
+        let enc = &mut self.enc;
+        match &mut self.entries {
+            Entries::Whole(_) | Entries::Done => unreachable!(),
+            Entries::List(ptr, idx) | Entries::Tuple(ptr, idx) => {
+                if *idx == 0 {
+                    enc.begin_array()?;
+                }
+                if *idx >= ffi!(Py_SIZE(*ptr)) {
+                    enc.end_array()?;
+                    return Ok(None);
+                }
+                enc.begin_array_value(*idx == 0)?;
+                let value = if ob_type!(*ptr) == unsafe { LIST_TYPE } {
+                    ffi!(PyList_GET_ITEM(*ptr, *idx))
+                } else {
+                    ffi!(PyTuple_GET_ITEM(*ptr, *idx))
+                };
+                *idx += 1;
+                ffi!(Py_INCREF(value));
+                Ok(Some(value))
+            }
+            Entries::Items(items, idx) => {
+                if *idx == 0 {
+                    enc.begin_object()?;
+                }
+                if *idx == ffi!(Py_SIZE(*items)) {
+                    enc.end_object()?;
+                    return Ok(None);
+                }
+                let item = ffi!(PyList_GET_ITEM(*items, *idx));
+                let key = ffi!(PyTuple_GET_ITEM(item, 0));
+                if unlikely!(ob_type!(key) != unsafe { STR_TYPE }) {
+                    return Err(Some(EncodeError::from(SerializeError::KeyMustBeStr)));
+                }
+                match unicode_to_str(key) {
+                    Some(key_as_str) => enc.write_key(key_as_str, *idx == 0)?,
+                    None => return Err(Some(EncodeError::from(SerializeError::InvalidStr))),
+                }
+                *idx += 1;
+                let value = ffi!(PyTuple_GET_ITEM(item, 1));
+                ffi!(Py_INCREF(value));
+                Ok(Some(value))
+            }
+            Entries::Iterator(ptr, first) => {
+                if *first {
+                    enc.begin_array()?;
+                }
+                let value = ffi!(PyIter_Next(*ptr));
+                if value.is_null() {
+                    if !ffi!(PyErr_Occurred()).is_null() {
+                        return Err(None);
+                    }
+                    enc.end_array()?;
+                    return Ok(None);
+                }
+                enc.begin_array_value(*first)?;
+                *first = false;
+                Ok(Some(value))
+            }
+        }
+    }
+
+    /// Serialize the next chunk of at least `chunk_size` bytes, unless it is
+    /// the last, or return `None` once all of the output has been returned.
+    /// An error of `None` means an exception was raised by an iterator.
+    fn next_chunk(&mut self) -> Result<Option<NonNull<pyo3_ffi::PyObject>>, Option<EncodeError>> {
+        if let Entries::Done = self.entries {
+            return Ok(None);
+        }
+        if let Entries::Whole(ptr) = self.entries {
+            PyObjectSerializer::new(ptr, self.opts, 0, 0, self.default).encode(&mut self.enc)?;
+            return self.finish().map(Some);
+        }
+        loop {
+            match self.next_value()? {
+                Some(value) => {
+                    let res = PyObjectSerializer::new(value, self.opts, 0, 1, self.default)
+                        .encode(&mut self.enc);
+                    ffi!(Py_DECREF(value));
+                    res?;
+                }
+                None => return self.finish().map(Some),
+            }
+            if self.enc.position() >= self.chunk_size {
+                return Ok(Some(self.enc.take_output()));
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Result<NonNull<pyo3_ffi::PyObject>, Option<EncodeError>> {
+        if self.opts & APPEND_NEWLINE != 0 {
+            self.enc.write_raw(b"\n")?;
+        }
+        match std::mem::replace(&mut self.entries, Entries::Done) {
+            Entries::Whole(ptr)
+            | Entries::List(ptr, _)
+            | Entries::Tuple(ptr, _)
+            | Entries::Items(ptr, _)
+            | Entries::Iterator(ptr, _) => ffi!(Py_DECREF(ptr)),
+            Entries::Done => {}
+        }
+        Ok(self.enc.take_output())
+    }
+}
+
+unsafe extern "C" fn async_writer_new(
+    subtype: *mut pyo3_ffi::PyTypeObject,
+    args: *mut pyo3_ffi::PyObject,
+    kwds: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    let mut default: Option<NonNull<pyo3_ffi::PyObject>> = None;
+    let mut optsptr: Option<NonNull<pyo3_ffi::PyObject>> = None;
+    let mut option_kwargs = crate::OptionKwargs::default();
+    let mut chunk_size = DEFAULT_CHUNK_SIZE;
+
+    let num_args = pyo3_ffi::Py_SIZE(args);
+    if unlikely!(num_args == 0) {
+        return crate::raise_dumps_exception(Cow::Borrowed(
+            "AsyncWriter() missing 1 required positional argument: 'write'",
+        ));
+    }
+    if unlikely!(num_args > 3) {
+        return crate::raise_dumps_exception(Cow::Borrowed(
+            "AsyncWriter() takes at most 3 positional arguments",
+        ));
+    }
+    if num_args >= 2 {
+        default = Some(NonNull::new_unchecked(pyo3_ffi::PyTuple_GET_ITEM(args, 1)));
+    }
+    if num_args == 3 {
+        optsptr = Some(NonNull::new_unchecked(pyo3_ffi::PyTuple_GET_ITEM(args, 2)));
+    }
+    if !kwds.is_null() {
+        for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+            if arg == DEFAULT {
+                if unlikely!(num_args >= 2) {
+                    return crate::raise_dumps_exception(Cow::Borrowed(
+                        "AsyncWriter() got multiple values for argument: 'default'",
+                    ));
+                }
+                default = Some(NonNull::new_unchecked(val));
+            } else if arg == OPTION {
+                if unlikely!(num_args == 3) {
+                    return crate::raise_dumps_exception(Cow::Borrowed(
+                        "AsyncWriter() got multiple values for argument: 'option'",
+                    ));
+                }
+                optsptr = Some(NonNull::new_unchecked(val));
+            } else if arg == CHUNK_SIZE {
+                if val == NONE {
+                    continue;
+                }
+                let size = pyo3_ffi::PyLong_AsSsize_t(val);
+                if unlikely!(size <= 0) {
+                    pyo3_ffi::PyErr_Clear();
+                    return crate::raise_dumps_exception(Cow::Borrowed(
+                        "chunk_size must be a positive int",
+                    ));
+                }
+                chunk_size = size as usize;
+            } else {
+                match crate::parse_option_kwarg(arg, val, &mut option_kwargs) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        return crate::raise_dumps_exception(Cow::Borrowed(
+                            "AsyncWriter() got an unexpected keyword argument",
+                        ))
+                    }
+                    Err(err) => return err,
+                }
+            }
+        }
+    }
+    if default.map_or(false, |func| func.as_ptr() == NONE) {
+        default = None;
+    }
+
+    // the default options apply only if `option` is not given
+    let mut optsbits = crate::DEFAULT_OPTIONS as i32;
+    if let Some(opts) = optsptr.filter(|opts| opts.as_ptr() != NONE) {
+        match crate::parse_option(opts.as_ptr()) {
+            Ok(val) => optsbits = val,
+            Err(err) => return err,
+        }
+    }
+    optsbits = option_kwargs.apply(optsbits);
+
+    // a stream is written to and then drained, while anything else must be
+    // a callable returning an awaitable
+    let target = pyo3_ffi::PyTuple_GET_ITEM(args, 0);
+    let (write, drain) = if pyo3_ffi::PyCallable_Check(target) == 1 {
+        pyo3_ffi::Py_INCREF(target);
+        (target, null_mut())
+    } else {
+        let write = pyo3_ffi::PyObject_GetAttrString(target, "write\0".as_ptr() as *const c_char);
+        let drain = pyo3_ffi::PyObject_GetAttrString(target, "drain\0".as_ptr() as *const c_char);
+        if write.is_null() || drain.is_null() {
+            pyo3_ffi::Py_XDECREF(write);
+            pyo3_ffi::Py_XDECREF(drain);
+            pyo3_ffi::PyErr_Clear();
+            return crate::raise_dumps_exception(Cow::Borrowed(
+                "AsyncWriter() argument must be a callable returning an awaitable or a stream with write() and drain()",
+            ));
+        }
+        (write, drain)
+    };
+
+    let obj = pyo3_ffi::PyType_GenericAlloc(subtype, 0);
+    if obj.is_null() {
+        pyo3_ffi::Py_DECREF(write);
+        pyo3_ffi::Py_XDECREF(drain);
+        return null_mut();
+    }
+    if let Some(func) = default {
+        pyo3_ffi::Py_INCREF(func.as_ptr());
+    }
+    let writer = obj as *mut AsyncWriter;
+    (*writer).write = write;
+    (*writer).drain = drain;
+    (*writer).default = default;
+    (*writer).opts = optsbits as Opt;
+    (*writer).chunk_size = chunk_size;
+    obj
+}
+
+unsafe extern "C" fn async_writer_dump(
+    slf: *mut pyo3_ffi::PyObject,
+    obj: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    let writer = slf as *mut AsyncWriter;
+    let state = match DumpState::new(obj, (*writer).default, (*writer).opts, (*writer).chunk_size) {
+        Ok(state) => state,
+        Err(()) => return null_mut(),
+    };
+    let ptr = pyo3_ffi::PyType_GenericAlloc(ASYNC_DUMP_TYPE, 0);
+    if ptr.is_null() {
+        return null_mut();
+    }
+    pyo3_ffi::Py_INCREF(slf);
+    let dump = ptr as *mut AsyncDump;
+    (*dump).writer = writer;
+    (*dump).awaiting = null_mut();
+    (*dump).busy = false;
+    std::ptr::write(std::ptr::addr_of_mut!((*dump).state), Some(Box::new(state)));
+    ptr
+}
+
+unsafe extern "C" fn async_writer_dealloc(slf: *mut pyo3_ffi::PyObject) {
+    let writer = slf as *mut AsyncWriter;
+    pyo3_ffi::Py_DECREF((*writer).write);
+    pyo3_ffi::Py_XDECREF((*writer).drain);
+    if let Some(func) = (*writer).default {
+        pyo3_ffi::Py_DECREF(func.as_ptr());
+    }
+    let tp = ob_type!(slf);
+    ((*tp).tp_free.unwrap())(slf as *mut c_void);
+    pyo3_ffi::Py_DECREF(tp as *mut pyo3_ffi::PyObject);
+}
+
+/// Write `chunk`, consuming it, and return a strong reference to the
+/// iterator of the awaitable doing so, or null if an exception was raised.
+unsafe fn write_chunk(
+    writer: *mut AsyncWriter,
+    chunk: NonNull<pyo3_ffi::PyObject>,
+) -> *mut pyo3_ffi::PyObject {
+    let mut awaitable = pyo3_ffi::PyObject_CallFunctionObjArgs(
+        (*writer).write,
+        chunk.as_ptr(),
+        null_mut::<pyo3_ffi::PyObject>(),
+    );
+    pyo3_ffi::Py_DECREF(chunk.as_ptr());
+    if awaitable.is_null() {
+        return null_mut();
+    }
+    if !(*writer).drain.is_null() {
+        pyo3_ffi::Py_DECREF(awaitable);
+        awaitable = pyo3_ffi::PyObject_CallObject((*writer).drain, null_mut());
+        if awaitable.is_null() {
+            return null_mut();
+        }
+    }
+    let ob_type = ob_type!(awaitable);
+    let am_await = if (*ob_type).tp_as_async.is_null() {
+        None
+    } else {
+        (*(*ob_type).tp_as_async).am_await
+    };
+    let iter = match am_await {
+        Some(func) => func(awaitable),
+        None => {
+            pyo3_ffi::PyErr_Format(
+                pyo3_ffi::PyExc_TypeError,
+                "object %.100s can't be used in 'await' expression\0".as_ptr() as *const c_char,
+                (*ob_type).tp_name,
+            );
+            null_mut()
+        }
+    };
+    pyo3_ffi::Py_DECREF(awaitable);
+    iter
+}
+
+/// End `dump`, which cannot be resumed.
+unsafe fn finish_dump(dump: *mut AsyncDump) {
+    (*dump).state = None;
+    pyo3_ffi::Py_XDECREF((*dump).awaiting);
+    (*dump).awaiting = null_mut();
+}
+
+/// Raise `ValueError` for `default` advancing the awaitable serializing
+/// the value it was called for.
+#[cold]
+unsafe fn raise_running() -> *mut pyo3_ffi::PyObject {
+    pyo3_ffi::PyErr_SetString(
+        pyo3_ffi::PyExc_ValueError,
+        "AsyncWriter.dump() is already running\0".as_ptr() as *const c_char,
+    );
+    null_mut()
+}
+
+/// Clear `StopIteration` ending the awaitable being awaited, returning
+/// false if another exception was raised.
+unsafe fn clear_stop_iteration() -> bool {
+    if pyo3_ffi::PyErr_Occurred().is_null() {
+        return true;
+    }
+    if pyo3_ffi::PyErr_ExceptionMatches(pyo3_ffi::PyExc_StopIteration) == 1 {
+        pyo3_ffi::PyErr_Clear();
+        return true;
+    }
+    false
+}
+
+/// Advance `dump`, returning the value yielded by the awaitable of writing
+/// a chunk, or null with no exception set once all are written. `res` is the
+/// result of resuming the awaitable being awaited, if it was.
+unsafe fn resume_dump(
+    dump: *mut AsyncDump,
+    mut res: Option<*mut pyo3_ffi::PyObject>,
+) -> *mut pyo3_ffi::PyObject {
+    if (*dump).busy {
+        return raise_running();
+    }
+    loop {
+        if !(*dump).awaiting.is_null() {
+            let val = match res.take() {
+                Some(val) => val,
+                None => pyo3_ffi::PyIter_Next((*dump).awaiting),
+            };
+            if !val.is_null() {
+                return val;
+            }
+            if !clear_stop_iteration() {
+                finish_dump(dump);
+                return null_mut();
+            }
+            pyo3_ffi::Py_DECREF((*dump).awaiting);
+            (*dump).awaiting = null_mut();
+        }
+        let state = match (*dump).state.as_mut() {
+            Some(state) => state,
+            None => return null_mut(),
+        };
+        (*dump).busy = true;
+        let chunk = state.next_chunk();
+        (*dump).busy = false;
+        match chunk {
+            Ok(Some(chunk)) => {
+                let iter = write_chunk((*dump).writer, chunk);
+                if iter.is_null() {
+                    finish_dump(dump);
+                    return null_mut();
+                }
+                (*dump).awaiting = iter;
+            }
+            Ok(None) => {
+                finish_dump(dump);
+                return null_mut();
+            }
+            Err(err) => {
+                finish_dump(dump);
+                return match err {
+                    Some(err) => crate::raise_encode_error(err),
+                    None => null_mut(),
+                };
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn async_dump_await(slf: *mut pyo3_ffi::PyObject) -> *mut pyo3_ffi::PyObject {
+    pyo3_ffi::Py_INCREF(slf);
+    slf
+}
+
+unsafe extern "C" fn async_dump_next(slf: *mut pyo3_ffi::PyObject) -> *mut pyo3_ffi::PyObject {
+    resume_dump(slf as *mut AsyncDump, None)
+}
+
+/// Return `val` from a method resuming the awaitable, raising
+/// `StopIteration` if it is done.
+unsafe fn stop_if_done(val: *mut pyo3_ffi::PyObject) -> *mut pyo3_ffi::PyObject {
+    if val.is_null() && pyo3_ffi::PyErr_Occurred().is_null() {
+        pyo3_ffi::PyErr_SetNone(pyo3_ffi::PyExc_StopIteration);
+    }
+    val
+}
+
+unsafe extern "C" fn async_dump_send(
+    slf: *mut pyo3_ffi::PyObject,
+    value: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    let dump = slf as *mut AsyncDump;
+    if (*dump).busy {
+        return raise_running();
+    }
+    if value == NONE || (*dump).awaiting.is_null() {
+        return stop_if_done(resume_dump(dump, None));
+    }
+    let res = pyo3_ffi::PyObject_CallMethod(
+        (*dump).awaiting,
+        "send\0".as_ptr() as *const c_char,
+        "O\0".as_ptr() as *const c_char,
+        value,
+    );
+    stop_if_done(resume_dump(dump, Some(res)))
+}
+
+unsafe extern "C" fn async_dump_throw(
+    slf: *mut pyo3_ffi::PyObject,
+    args: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    let dump = slf as *mut AsyncDump;
+    if (*dump).busy {
+        return raise_running();
+    }
+    // the exception is raised in the awaitable being awaited, which may
+    // handle it, or else where the dump is awaited
+    if !(*dump).awaiting.is_null() {
+        let throw =
+            pyo3_ffi::PyObject_GetAttrString((*dump).awaiting, "throw\0".as_ptr() as *const c_char);
+        if !throw.is_null() {
+            let res = pyo3_ffi::PyObject_CallObject(throw, args);
+            pyo3_ffi::Py_DECREF(throw);
+            return stop_if_done(resume_dump(dump, Some(res)));
+        }
+        pyo3_ffi::PyErr_Clear();
+    }
+    let mut typ: *mut pyo3_ffi::PyObject = null_mut();
+    let mut val: *mut pyo3_ffi::PyObject = null_mut();
+    let mut tb: *mut pyo3_ffi::PyObject = null_mut();
+    if pyo3_ffi::PyArg_UnpackTuple(
+        args,
+        "throw\0".as_ptr() as *const c_char,
+        1,
+        3,
+        &mut typ,
+        &mut val,
+        &mut tb,
+    ) == 0
+    {
+        return null_mut();
+    }
+    finish_dump(dump);
+    if pyo3_ffi::PyExceptionInstance_Check(typ) != 0 {
+        pyo3_ffi::PyErr_SetObject(ob_type!(typ) as *mut pyo3_ffi::PyObject, typ);
+    } else {
+        pyo3_ffi::PyErr_SetObject(typ, if val.is_null() { NONE } else { val });
+    }
+    null_mut()
+}
+
+unsafe extern "C" fn async_dump_close(
+    slf: *mut pyo3_ffi::PyObject,
+    _unused: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    let dump = slf as *mut AsyncDump;
+    if (*dump).busy {
+        return raise_running();
+    }
+    if !(*dump).awaiting.is_null() {
+        let res = pyo3_ffi::PyObject_CallMethod(
+            (*dump).awaiting,
+            "close\0".as_ptr() as *const c_char,
+            null_mut(),
+        );
+        if res.is_null() {
+            if pyo3_ffi::PyErr_ExceptionMatches(pyo3_ffi::PyExc_AttributeError) == 0 {
+                finish_dump(dump);
+                return null_mut();
+            }
+            pyo3_ffi::PyErr_Clear();
+        } else {
+            pyo3_ffi::Py_DECREF(res);
+        }
+    }
+    finish_dump(dump);
+    pyo3_ffi::Py_INCREF(NONE);
+    NONE
+}
+
+unsafe extern "C" fn async_dump_dealloc(slf: *mut pyo3_ffi::PyObject) {
+    let dump = slf as *mut AsyncDump;
+    pyo3_ffi::Py_XDECREF((*dump).awaiting);
+    std::ptr::drop_in_place(std::ptr::addr_of_mut!((*dump).state));
+    pyo3_ffi::Py_DECREF((*dump).writer as *mut pyo3_ffi::PyObject);
+    let tp = ob_type!(slf);
+    ((*tp).tp_free.unwrap())(slf as *mut c_void);
+    pyo3_ffi::Py_DECREF(tp as *mut pyo3_ffi::PyObject);
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub unsafe fn create_async_writer_type() -> *mut pyo3_ffi::PyTypeObject {
+    let methods: Box<[pyo3_ffi::PyMethodDef; 2]> = Box::new([
+        pyo3_ffi::PyMethodDef {
+            ml_name: "dump\0".as_ptr() as *const c_char,
+            ml_meth: pyo3_ffi::PyMethodDefPointer {
+                PyCFunction: async_writer_dump,
+            },
+            ml_flags: pyo3_ffi::METH_O,
+            ml_doc: "dump(obj, /)\n--\n\nReturn an awaitable serializing obj and writing it in chunks.\0".as_ptr() as *const c_char,
+        },
+        std::mem::zeroed(),
+    ]);
+    let slots: Box<[pyo3_ffi::PyType_Slot; 5]> = Box::new([
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_new,
+            pfunc: async_writer_new as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_methods,
+            pfunc: Box::into_raw(methods) as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_doc,
+            pfunc: "AsyncWriter(write, /, default=None, option=None, *, chunk_size=65536)\n--\n\nSerialize to an asynchronous stream, awaiting each chunk being written before serializing the next.\0".as_ptr() as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_dealloc,
+            pfunc: async_writer_dealloc as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: 0,
+            pfunc: std::ptr::null_mut(),
+        },
+    ]);
+    let spec = Box::new(pyo3_ffi::PyType_Spec {
+        name: "orjson.AsyncWriter\0".as_ptr() as *const c_char,
+        basicsize: std::mem::size_of::<AsyncWriter>() as c_int,
+        itemsize: 0,
+        flags: pyo3_ffi::Py_TPFLAGS_DEFAULT as c_uint,
+        slots: Box::into_raw(slots) as *mut pyo3_ffi::PyType_Slot,
+    });
+    pyo3_ffi::PyType_FromSpec(Box::into_raw(spec)) as *mut pyo3_ffi::PyTypeObject
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub unsafe fn create_async_dump_type() -> *mut pyo3_ffi::PyTypeObject {
+    let methods: Box<[pyo3_ffi::PyMethodDef; 4]> = Box::new([
+        pyo3_ffi::PyMethodDef {
+            ml_name: "send\0".as_ptr() as *const c_char,
+            ml_meth: pyo3_ffi::PyMethodDefPointer {
+                PyCFunction: async_dump_send,
+            },
+            ml_flags: pyo3_ffi::METH_O,
+            ml_doc: std::ptr::null(),
+        },
+        pyo3_ffi::PyMethodDef {
+            ml_name: "throw\0".as_ptr() as *const c_char,
+            ml_meth: pyo3_ffi::PyMethodDefPointer {
+                PyCFunction: async_dump_throw,
+            },
+            ml_flags: pyo3_ffi::METH_VARARGS,
+            ml_doc: std::ptr::null(),
+        },
+        pyo3_ffi::PyMethodDef {
+            ml_name: "close\0".as_ptr() as *const c_char,
+            ml_meth: pyo3_ffi::PyMethodDefPointer {
+                PyCFunction: async_dump_close,
+            },
+            ml_flags: pyo3_ffi::METH_NOARGS,
+            ml_doc: std::ptr::null(),
+        },
+        std::mem::zeroed(),
+    ]);
+    let slots: Box<[pyo3_ffi::PyType_Slot; 6]> = Box::new([
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_am_await,
+            pfunc: async_dump_await as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_iter,
+            pfunc: async_dump_await as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_iternext,
+            pfunc: async_dump_next as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_methods,
+            pfunc: Box::into_raw(methods) as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_dealloc,
+            pfunc: async_dump_dealloc as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: 0,
+            pfunc: std::ptr::null_mut(),
+        },
+    ]);
+    let spec = Box::new(pyo3_ffi::PyType_Spec {
+        name: "orjson.AsyncDump\0".as_ptr() as *const c_char,
+        basicsize: std::mem::size_of::<AsyncDump>() as c_int,
+        itemsize: 0,
+        flags: pyo3_ffi::Py_TPFLAGS_DEFAULT as c_uint,
+        slots: Box::into_raw(slots) as *mut pyo3_ffi::PyType_Slot,
+    });
+    pyo3_ffi::PyType_FromSpec(Box::into_raw(spec)) as *mut pyo3_ffi::PyTypeObject
+}
@@ -0,0 +1,535 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::opt::*;
+use crate::serialize::dataclass_meta::{dataclass_meta, DataclassMeta};
+use crate::serialize::encode::*;
+use crate::serialize::error::*;
+use crate::serialize::int::*;
+use crate::serialize::serializer::*;
+use crate::serialize::str::*;
+use crate::typeref::*;
+use crate::unicode::*;
+
+use crate::ffi::PyDictIter;
+use smallvec::SmallVec;
+
+use std::os::raw::{c_char, c_int, c_uint, c_void};
+use std::ptr::NonNull;
+use std::rc::Rc;
+
+// Nested dataclass and TypedDict annotations are compiled up to this depth and
+// serialized generically below it, which also bounds recursive definitions.
+const PLAN_DEPTH_LIMIT: u8 = 8;
+
+/// The type a field is annotated with. A value of exactly that type skips
+/// type dispatch, an instance of a subclass of it is serialized as by
+/// `dumps()`, and any other value is an error.
+enum FieldKind {
+    Any,
+    Str,
+    Int,
+    Float,
+    Bool,
+    Plan(Box<Plan>),
+}
+
+struct PlanField {
+    // Strong reference to the attribute name or dict key.
+    attr: *mut pyo3_ffi::PyObject,
+    // Serialized key, borrowed from `attr` or a dataclass alias in `meta`.
+    key: &'static str,
+    kind: FieldKind,
+}
+
+enum PlanType {
+    Dataclass {
+        slots: bool,
+        // Keeps the aliases used as keys alive.
+        _meta: Rc<DataclassMeta>,
+    },
+    TypedDict,
+}
+
+/// Serialization plan of a dataclass or TypedDict built once by
+/// `orjson.compile()`.
+pub struct Plan {
+    // Strong reference to the compiled class.
+    cls: *mut pyo3_ffi::PyTypeObject,
+    plan_type: PlanType,
+    fields: Vec<PlanField>,
+}
+
+impl Drop for Plan {
+    fn drop(&mut self) {
+        for field in self.fields.iter() {
+            ffi!(Py_DECREF(field.attr));
+        }
+        ffi!(Py_DECREF(self.cls as *mut pyo3_ffi::PyObject));
+    }
+}
+
+fn is_dataclass_type(cls: *mut pyo3_ffi::PyTypeObject) -> bool {
+    ffi!(PyDict_Contains((*cls).tp_dict, DATACLASS_FIELDS_STR)) == 1
+}
+
+fn is_typeddict_type(cls: *mut pyo3_ffi::PyTypeObject) -> bool {
+    if unsafe { (*cls).tp_flags & pyo3_ffi::Py_TPFLAGS_DICT_SUBCLASS == 0 } {
+        return false;
+    }
+    let ret = ffi!(PyObject_HasAttr(cls as *mut pyo3_ffi::PyObject, TOTAL_STR)) == 1;
+    ffi!(PyErr_Clear());
+    ret
+}
+
+fn field_kind(annotation: *mut pyo3_ffi::PyObject, depth: u8) -> FieldKind {
+    unsafe {
+        if annotation == STR_TYPE as *mut pyo3_ffi::PyObject {
+            FieldKind::Str
+        } else if annotation == INT_TYPE as *mut pyo3_ffi::PyObject {
+            FieldKind::Int
+        } else if annotation == FLOAT_TYPE as *mut pyo3_ffi::PyObject {
+            FieldKind::Float
+        } else if annotation == BOOL_TYPE as *mut pyo3_ffi::PyObject {
+            FieldKind::Bool
+        } else if depth < PLAN_DEPTH_LIMIT && pyo3_ffi::PyType_Check(annotation) == 1 {
+            match Plan::new(annotation as *mut pyo3_ffi::PyTypeObject, depth + 1) {
+                Ok(Some(plan)) => FieldKind::Plan(Box::new(plan)),
+                _ => FieldKind::Any,
+            }
+        } else {
+            FieldKind::Any
+        }
+    }
+}
+
+impl FieldKind {
+    /// Whether `ptr`, which is not of exactly the annotated type, is an
+    /// instance of it. An `int` is accepted as a `float`, as type checkers
+    /// accept it.
+    #[cold]
+    #[inline(never)]
+    fn accepts(&self, ptr: *mut pyo3_ffi::PyObject) -> bool {
+        unsafe {
+            match self {
+                FieldKind::Any => true,
+                FieldKind::Str => pyo3_ffi::PyUnicode_Check(ptr) == 1,
+                FieldKind::Int => pyo3_ffi::PyLong_Check(ptr) == 1,
+                FieldKind::Float => {
+                    pyo3_ffi::PyFloat_Check(ptr) == 1 || pyo3_ffi::PyLong_Check(ptr) == 1
+                }
+                FieldKind::Bool => false,
+                FieldKind::Plan(plan) => match plan.plan_type {
+                    PlanType::Dataclass { .. } => {
+                        pyo3_ffi::PyType_IsSubtype(ob_type!(ptr), plan.cls) == 1
+                    }
+                    PlanType::TypedDict => pyo3_ffi::PyDict_Check(ptr) == 1,
+                },
+            }
+        }
+    }
+
+    /// The name of the annotated type.
+    #[cold]
+    fn name(&self) -> &'static str {
+        match self {
+            FieldKind::Any => "any",
+            FieldKind::Str => "str",
+            FieldKind::Int => "int",
+            FieldKind::Float => "float",
+            FieldKind::Bool => "bool",
+            FieldKind::Plan(plan) => unsafe {
+                std::ffi::CStr::from_ptr((*plan.cls).tp_name)
+                    .to_str()
+                    .unwrap_or("dataclass")
+            },
+        }
+    }
+}
+
+impl Plan {
+    /// Build the plan of `cls` or return `None` if it is neither a dataclass
+    /// nor a TypedDict.
+    fn new(cls: *mut pyo3_ffi::PyTypeObject, depth: u8) -> Result<Option<Self>, SerializeError> {
+        if is_dataclass_type(cls) {
+            Plan::new_dataclass(cls, depth).map(Some)
+        } else if is_typeddict_type(cls) {
+            Plan::new_typeddict(cls, depth).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[cold]
+    fn new_dataclass(cls: *mut pyo3_ffi::PyTypeObject, depth: u8) -> Result<Self, SerializeError> {
+        let meta = dataclass_meta(cls)?;
+        let dataclass_fields = ffi!(PyDict_GetItem((*cls).tp_dict, DATACLASS_FIELDS_STR));
+        ffi!(Py_INCREF(cls as *mut pyo3_ffi::PyObject));
+        let mut plan = Plan {
+            cls: cls,
+            plan_type: PlanType::Dataclass {
+                slots: ffi!(PyDict_Contains((*cls).tp_dict, SLOTS_STR)) == 1,
+                _meta: Rc::clone(&meta),
+            },
+            fields: Vec::with_capacity(meta.fields.len()),
+        };
+        for &(attr, key) in meta.fields.iter() {
+            let field = ffi!(PyDict_GetItem(dataclass_fields, attr));
+            let annotation = ffi!(PyObject_GetAttr(field, TYPE_STR));
+            let kind = if annotation.is_null() {
+                ffi!(PyErr_Clear());
+                FieldKind::Any
+            } else {
+                let kind = field_kind(annotation, depth);
+                ffi!(Py_DECREF(annotation));
+                kind
+            };
+            ffi!(Py_INCREF(attr));
+            plan.fields.push(PlanField {
+                attr: attr,
+                key: key,
+                kind: kind,
+            });
+        }
+        Ok(plan)
+    }
+
+    #[cold]
+    fn new_typeddict(cls: *mut pyo3_ffi::PyTypeObject, depth: u8) -> Result<Self, SerializeError> {
+        ffi!(Py_INCREF(cls as *mut pyo3_ffi::PyObject));
+        let mut plan = Plan {
+            cls: cls,
+            plan_type: PlanType::TypedDict,
+            fields: Vec::new(),
+        };
+        let annotations = ffi!(PyObject_GetAttr(
+            cls as *mut pyo3_ffi::PyObject,
+            ANNOTATIONS_STR
+        ));
+        if annotations.is_null() {
+            ffi!(PyErr_Clear());
+            return Ok(plan);
+        }
+        let res = (|| {
+            if unsafe { ob_type!(annotations) != DICT_TYPE } {
+                return Ok(());
+            }
+            for (attr, annotation) in PyDictIter::from_pyobject(annotations) {
+                if unlikely!(unsafe { ob_type!(attr) != STR_TYPE }) {
+                    return Err(SerializeError::KeyMustBeStr);
+                }
+                let key = unicode_to_str(attr);
+                if unlikely!(key.is_none()) {
+                    return Err(SerializeError::InvalidStr);
+                }
+                ffi!(Py_INCREF(attr));
+                plan.fields.push(PlanField {
+                    attr: attr,
+                    key: key.unwrap(),
+                    kind: field_kind(annotation, depth),
+                });
+            }
+            Ok(())
+        })();
+        ffi!(Py_DECREF(annotations));
+        res.map(|_| plan)
+    }
+
+    /// The error of the `dict` `ptr` having a key that is not a field of the
+    /// TypedDict.
+    #[cold]
+    #[inline(never)]
+    fn undeclared_key(&self, ptr: *mut pyo3_ffi::PyObject) -> EncodeError {
+        for (key, _) in PyDictIter::from_pyobject(ptr) {
+            if unsafe { ob_type!(key) != STR_TYPE } {
+                return EncodeError::from(SerializeError::KeyMustBeStr);
+            }
+            match unicode_to_str(key) {
+                Some(key) if !self.fields.iter().any(|field| field.key == key) => {
+                    return EncodeError::from(SerializeError::TypedDictUndeclaredKey(key));
+                }
+                Some(_) => (),
+                None => return EncodeError::from(SerializeError::InvalidStr),
+            }
+        }
+        unreachable!()
+    }
+
+    /// Whether the plan applies to `ptr` when serializing with `opts`.
+    #[inline(always)]
+    fn matches(&self, ptr: *mut pyo3_ffi::PyObject, opts: Opt) -> bool {
+        match self.plan_type {
+            PlanType::Dataclass { .. } => {
+                opts & PASSTHROUGH_DATACLASS == 0 && ob_type!(ptr) == self.cls
+            }
+            PlanType::TypedDict => {
+                opts & SORT_OR_NON_STR_KEYS == 0 && unsafe { ob_type!(ptr) == DICT_TYPE }
+            }
+        }
+    }
+}
+
+struct PlanValueSerializer<'a> {
+    ptr: *mut pyo3_ffi::PyObject,
+    key: &'static str,
+    kind: &'a FieldKind,
+    opts: Opt,
+    default_calls: u8,
+    recursion: u32,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+}
+
+impl<'a> Encode for PlanValueSerializer<'a> {
+    #[inline(always)]
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        let ob_type = ob_type!(self.ptr);
+        match self.kind {
+            FieldKind::Str if unsafe { ob_type == STR_TYPE } => {
+                StrSerializer::new(self.ptr).encode(enc)
+            }
+            FieldKind::Int
+                if unsafe { ob_type == INT_TYPE } && self.opts & STRICT_INTEGER_OR_JS == 0 =>
+            {
+                IntSerializer::new(self.ptr).encode(enc)
+            }
+            FieldKind::Float if unsafe { ob_type == FLOAT_TYPE } => {
+                enc.write_f64(ffi!(PyFloat_AS_DOUBLE(self.ptr)))
+            }
+            FieldKind::Bool if unsafe { ob_type == BOOL_TYPE } => {
+                enc.write_bool(unsafe { self.ptr == TRUE })
+            }
+            FieldKind::Plan(plan) if plan.matches(self.ptr, self.opts) => PlanSerializer::new(
+                self.ptr,
+                plan,
+                self.opts,
+                self.default_calls,
+                self.recursion,
+                self.default,
+            )
+            .encode(enc),
+            kind if kind.accepts(self.ptr) => PyObjectSerializer::new(
+                self.ptr,
+                self.opts,
+                self.default_calls,
+                self.recursion,
+                self.default,
+            )
+            .encode(enc),
+            kind => err!(SerializeError::FieldTypeMismatch(
+                self.key,
+                nonnull!(self.ptr),
+                kind.name()
+            )),
+        }
+    }
+}
+
+pub struct PlanSerializer<'a> {
+    ptr: *mut pyo3_ffi::PyObject,
+    plan: &'a Plan,
+    opts: Opt,
+    default_calls: u8,
+    recursion: u32,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+}
+
+impl<'a> PlanSerializer<'a> {
+    pub fn new(
+        ptr: *mut pyo3_ffi::PyObject,
+        plan: &'a Plan,
+        opts: Opt,
+        default_calls: u8,
+        recursion: u32,
+        default: Option<NonNull<pyo3_ffi::PyObject>>,
+    ) -> Self {
+        PlanSerializer {
+            ptr: ptr,
+            plan: plan,
+            opts: opts,
+            default_calls: default_calls,
+            recursion: recursion,
+            default: default,
+        }
+    }
+}
+
+impl<'a> Encode for PlanSerializer<'a> {
+    #[inline(never)]
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        if unlikely!(self.recursion >= enc.max_depth()) {
+            err!(SerializeError::RecursionLimit)
+        }
+        let mut values: SmallVec<[*mut pyo3_ffi::PyObject; 16]> =
+            SmallVec::with_capacity(self.plan.fields.len());
+        match self.plan.plan_type {
+            PlanType::Dataclass { slots, .. } => {
+                let mut dict = std::ptr::null_mut();
+                if !slots {
+                    dict = ffi!(PyObject_GetAttr(self.ptr, DICT_STR));
+                    if dict.is_null() {
+                        ffi!(PyErr_Clear());
+                    } else {
+                        ffi!(Py_DECREF(dict));
+                    }
+                }
+                for field in self.plan.fields.iter() {
+                    let mut value = std::ptr::null_mut();
+                    if !dict.is_null() {
+                        value = ffi!(PyDict_GetItem(dict, field.attr));
+                    }
+                    if value.is_null() {
+                        value = ffi!(PyObject_GetAttr(self.ptr, field.attr));
+                        if value.is_null() {
+                            ffi!(PyErr_Clear());
+                        } else {
+                            ffi!(Py_DECREF(value));
+                        }
+                    }
+                    values.push(value);
+                }
+            }
+            PlanType::TypedDict => {
+                let mut found = 0;
+                for field in self.plan.fields.iter() {
+                    let value = ffi!(PyDict_GetItem(self.ptr, field.attr));
+                    found += !value.is_null() as isize;
+                    values.push(value);
+                }
+                if unlikely!(found != ffi!(Py_SIZE(self.ptr))) {
+                    return Err(self.plan.undeclared_key(self.ptr));
+                }
+            }
+        }
+        enc.begin_object()?;
+        let mut first = true;
+        for (field, &value) in self.plan.fields.iter().zip(values.iter()) {
+            if value.is_null() {
+                continue;
+            }
+            enc.write_key(field.key, first)?;
+            first = false;
+            PlanValueSerializer {
+                ptr: value,
+                key: field.key,
+                kind: &field.kind,
+                opts: self.opts,
+                default_calls: self.default_calls,
+                recursion: self.recursion + 1,
+                default: self.default,
+            }
+            .encode(enc)?;
+        }
+        enc.end_object()
+    }
+}
+
+/// The callable returned by `orjson.compile()`.
+#[repr(C)]
+pub struct CompiledSerializer {
+    ob_base: pyo3_ffi::PyObject,
+    plan: *mut Plan,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    opts: Opt,
+    args: *mut DumpsArgs,
+}
+
+/// Compile `cls` into a new `CompiledSerializer`.
+pub fn compile(
+    cls: *mut pyo3_ffi::PyObject,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    opts: Opt,
+    args: DumpsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, EncodeError> {
+    let plan = if ffi!(PyType_Check(cls)) == 1 {
+        Plan::new(cls as *mut pyo3_ffi::PyTypeObject, 0).map_err(EncodeError::from)?
+    } else {
+        None
+    };
+    if plan.is_none() {
+        return Err(EncodeError::Message(String::from(
+            "compile() argument must be a dataclass or TypedDict type",
+        )));
+    }
+    unsafe {
+        let ptr = pyo3_ffi::PyType_GenericAlloc(COMPILED_SERIALIZER_TYPE, 0);
+        let obj = ptr as *mut CompiledSerializer;
+        (*obj).plan = Box::into_raw(Box::new(plan.unwrap()));
+        if let Some(func) = default {
+            pyo3_ffi::Py_INCREF(func.as_ptr());
+        }
+        (*obj).default = default;
+        (*obj).opts = opts;
+        (*obj).args = Box::into_raw(Box::new(args));
+        Ok(NonNull::new_unchecked(ptr))
+    }
+}
+
+unsafe extern "C" fn compiled_serializer_call(
+    slf: *mut pyo3_ffi::PyObject,
+    args: *mut pyo3_ffi::PyObject,
+    kwds: *mut pyo3_ffi::PyObject,
+) -> *mut pyo3_ffi::PyObject {
+    if unlikely!(
+        pyo3_ffi::PyTuple_GET_SIZE(args) != 1 || (!kwds.is_null() && pyo3_ffi::Py_SIZE(kwds) != 0)
+    ) {
+        return crate::raise_dumps_exception(std::borrow::Cow::Borrowed(
+            "CompiledSerializer() takes exactly 1 positional argument",
+        ));
+    }
+    let compiled = slf as *mut CompiledSerializer;
+    let obj = pyo3_ffi::PyTuple_GET_ITEM(args, 0);
+    let plan = &*(*compiled).plan;
+    let opts = (*compiled).opts;
+    let args = (*(*compiled).args).clone_options();
+    let res = if plan.matches(obj, opts) {
+        to_bytes(
+            &PlanSerializer::new(obj, plan, opts, 0, 0, (*compiled).default),
+            opts,
+            args,
+        )
+    } else {
+        serialize(obj, (*compiled).default, opts, args)
+    };
+    match res {
+        Ok(val) => val.as_ptr(),
+        Err(err) => crate::raise_encode_error(err),
+    }
+}
+
+unsafe extern "C" fn compiled_serializer_dealloc(slf: *mut pyo3_ffi::PyObject) {
+    let compiled = slf as *mut CompiledSerializer;
+    drop(Box::from_raw((*compiled).plan));
+    drop(Box::from_raw((*compiled).args));
+    if let Some(func) = (*compiled).default {
+        pyo3_ffi::Py_DECREF(func.as_ptr());
+    }
+    let tp = ob_type!(slf);
+    ((*tp).tp_free.unwrap())(slf as *mut c_void);
+    pyo3_ffi::Py_DECREF(tp as *mut pyo3_ffi::PyObject);
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub unsafe fn create_compiled_serializer_type() -> *mut pyo3_ffi::PyTypeObject {
+    let slots: Box<[pyo3_ffi::PyType_Slot; 3]> = Box::new([
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_call,
+            pfunc: compiled_serializer_call as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: pyo3_ffi::Py_tp_dealloc,
+            pfunc: compiled_serializer_dealloc as *mut c_void,
+        },
+        pyo3_ffi::PyType_Slot {
+            slot: 0,
+            pfunc: std::ptr::null_mut(),
+        },
+    ]);
+    let spec = Box::new(pyo3_ffi::PyType_Spec {
+        name: "orjson.CompiledSerializer\0".as_ptr() as *const c_char,
+        basicsize: std::mem::size_of::<CompiledSerializer>() as c_int,
+        itemsize: 0,
+        flags: pyo3_ffi::Py_TPFLAGS_DEFAULT as c_uint,
+        slots: Box::into_raw(slots) as *mut pyo3_ffi::PyType_Slot,
+    });
+    pyo3_ffi::PyType_FromSpec(Box::into_raw(spec)) as *mut pyo3_ffi::PyTypeObject
+}
@@ -1,146 +1,112 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use crate::opt::*;
-use crate::serialize::error::*;
-use crate::serialize::serializer::*;
-use crate::typeref::*;
-use crate::unicode::*;
+use crate::serialize::dataclass_meta::DataclassMeta;
+use crate::serialize::encode::*;
+use crate::serialize::error::EncodeError;
 
-use crate::ffi::PyDictIter;
-use serde::ser::{Serialize, SerializeMap, Serializer};
-
-use std::ptr::NonNull;
+use std::rc::Rc;
 
+/// The fields of a dataclass instance being written from its `__dict__`,
+/// each value returned to `PyObjectSerializer` to encode.
 pub struct DataclassFastSerializer {
     ptr: *mut pyo3_ffi::PyObject,
-    opts: Opt,
-    default_calls: u8,
-    recursion: u8,
-    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    dict: *mut pyo3_ffi::PyObject,
+    meta: Rc<DataclassMeta>,
+    idx: usize,
+    first: bool,
 }
 
 impl DataclassFastSerializer {
     pub fn new(
         ptr: *mut pyo3_ffi::PyObject,
-        opts: Opt,
-        default_calls: u8,
-        recursion: u8,
-        default: Option<NonNull<pyo3_ffi::PyObject>>,
+        dict: *mut pyo3_ffi::PyObject,
+        meta: Rc<DataclassMeta>,
     ) -> Self {
         DataclassFastSerializer {
             ptr: ptr,
-            opts: opts,
-            default_calls: default_calls,
-            recursion: recursion,
-            default: default,
+            dict: dict,
+            meta: meta,
+            idx: 0,
+            first: true,
         }
     }
-}
 
-impl Serialize for DataclassFastSerializer {
+    /// Write the separator and key before the next value and return it or,
+    /// after the last field, end the object.
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let len = ffi!(Py_SIZE(self.ptr));
-        if unlikely!(len == 0) {
-            return serializer.serialize_map(Some(0)).unwrap().end();
-        }
-        let mut map = serializer.serialize_map(None).unwrap();
-        for (key, value) in PyDictIter::from_pyobject(self.ptr) {
-            if unlikely!(unsafe { ob_type!(key) != STR_TYPE }) {
-                err!(SerializeError::KeyMustBeStr)
-            }
-            let data = unicode_to_str(key);
-            if unlikely!(data.is_none()) {
-                err!(SerializeError::InvalidStr)
-            }
-            let key_as_str = data.unwrap();
-            if unlikely!(key_as_str.as_bytes()[0] == b'_') {
+    pub fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        while self.idx < self.meta.fields.len() {
+            let (attr, key_as_str) = self.meta.fields[self.idx];
+            self.idx += 1;
+            if unlikely!(enc.is_including()) && !enc.includes_key(key_as_str) {
                 continue;
             }
-            let pyvalue = PyObjectSerializer::new(
-                value,
-                self.opts,
-                self.default_calls,
-                self.recursion + 1,
-                self.default,
-            );
-            map.serialize_key(key_as_str).unwrap();
-            map.serialize_value(&pyvalue)?;
+            // A field not in the instance __dict__, e.g., one left to a class
+            // attribute by a custom __init__, is looked up as an attribute.
+            let mut value = ffi!(PyDict_GetItem(self.dict, attr));
+            if unlikely!(value.is_null()) {
+                value = ffi!(PyObject_GetAttr(self.ptr, attr));
+                if value.is_null() {
+                    ffi!(PyErr_Clear());
+                    continue;
+                }
+                ffi!(Py_DECREF(value));
+            }
+            enc.write_key(key_as_str, self.first)?;
+            self.first = false;
+            return Ok(Some(value));
         }
-        map.end()
+        enc.end_object()?;
+        Ok(None)
     }
 }
 
+/// The fields of a dataclass instance without `__dict__` or with
+/// `__slots__` being written from its attributes.
 pub struct DataclassFallbackSerializer {
     ptr: *mut pyo3_ffi::PyObject,
-    opts: Opt,
-    default_calls: u8,
-    recursion: u8,
-    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    meta: Rc<DataclassMeta>,
+    idx: usize,
+    first: bool,
 }
 
 impl DataclassFallbackSerializer {
-    pub fn new(
-        ptr: *mut pyo3_ffi::PyObject,
-        opts: Opt,
-        default_calls: u8,
-        recursion: u8,
-        default: Option<NonNull<pyo3_ffi::PyObject>>,
-    ) -> Self {
+    pub fn new(ptr: *mut pyo3_ffi::PyObject, meta: Rc<DataclassMeta>) -> Self {
         DataclassFallbackSerializer {
             ptr: ptr,
-            opts: opts,
-            default_calls: default_calls,
-            recursion: recursion,
-            default: default,
+            meta: meta,
+            idx: 0,
+            first: true,
         }
     }
-}
 
-impl Serialize for DataclassFallbackSerializer {
+    /// Write the separator and key before the next value and return it or,
+    /// after the last field, end the object.
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let fields = ffi!(PyObject_GetAttr(self.ptr, DATACLASS_FIELDS_STR));
-        ffi!(Py_DECREF(fields));
-        let len = ffi!(Py_SIZE(fields)) as usize;
-        if unlikely!(len == 0) {
-            return serializer.serialize_map(Some(0)).unwrap().end();
-        }
-        let mut map = serializer.serialize_map(None).unwrap();
-        for (attr, field) in PyDictIter::from_pyobject(fields) {
-            let field_type = ffi!(PyObject_GetAttr(field, FIELD_TYPE_STR));
-            ffi!(Py_DECREF(field_type));
-            if unsafe { field_type != FIELD_TYPE.as_ptr() } {
+    pub fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        while self.idx < self.meta.fields.len() {
+            let (attr, key_as_str) = self.meta.fields[self.idx];
+            self.idx += 1;
+            if unlikely!(enc.is_including()) && !enc.includes_key(key_as_str) {
                 continue;
             }
-            let data = unicode_to_str(attr);
-            if unlikely!(data.is_none()) {
-                err!(SerializeError::InvalidStr);
-            }
-            let key_as_str = data.unwrap();
-            if key_as_str.as_bytes()[0] == b'_' {
+            let value = ffi!(PyObject_GetAttr(self.ptr, attr));
+            if unlikely!(value.is_null()) {
+                ffi!(PyErr_Clear());
                 continue;
             }
-
-            let value = ffi!(PyObject_GetAttr(self.ptr, attr));
             ffi!(Py_DECREF(value));
-            let pyvalue = PyObjectSerializer::new(
-                value,
-                self.opts,
-                self.default_calls,
-                self.recursion + 1,
-                self.default,
-            );
-
-            map.serialize_key(key_as_str).unwrap();
-            map.serialize_value(&pyvalue)?
+            enc.write_key(key_as_str, self.first)?;
+            self.first = false;
+            return Ok(Some(value));
         }
-        map.end()
+        enc.end_object()?;
+        Ok(None)
     }
 }
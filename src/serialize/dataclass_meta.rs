@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::ffi::PyDictIter;
+use crate::serialize::error::SerializeError;
+use crate::typeref::*;
+use crate::unicode::*;
+use associative_cache::replacement::RoundRobinReplacement;
+use associative_cache::*;
+use once_cell::unsync::OnceCell;
+use smallvec::SmallVec;
+use std::os::raw::c_char;
+use std::rc::Rc;
+
+/// The fields of a dataclass to serialize and the key of each, computed once
+/// per class from `__dataclass_fields__` and
+/// `dataclasses.field(metadata={"orjson": {...}})`.
+pub struct DataclassMeta {
+    // Strong reference to the class's `__dataclass_fields__`. It keeps the
+    // attribute names in `fields` alive, and comparing it against the class on
+    // lookup detects a type object at a reused address.
+    dataclass_fields: *mut pyo3_ffi::PyObject,
+    // Strong references to the `"alias"` strings used as keys in `fields`.
+    aliases: SmallVec<[*mut pyo3_ffi::PyObject; 2]>,
+    /// Attribute name and serialized key of each field, in definition order.
+    pub fields: SmallVec<[(*mut pyo3_ffi::PyObject, &'static str); 8]>,
+}
+
+impl Drop for DataclassMeta {
+    fn drop(&mut self) {
+        for each in self.aliases.iter() {
+            ffi!(Py_DECREF(*each));
+        }
+        ffi!(Py_DECREF(self.dataclass_fields));
+    }
+}
+
+pub type DataclassMetaMap =
+    AssociativeCache<usize, Rc<DataclassMeta>, Capacity64, HashDirectMapped, RoundRobinReplacement>;
+
+pub static mut DATACLASS_META: OnceCell<DataclassMetaMap> = OnceCell::new();
+
+/// Return the cached fields of the dataclass `ob_type`. The entry is
+/// reference counted so that serializing a nested dataclass that evicts it
+/// does not invalidate it for the caller.
+#[inline]
+pub fn dataclass_meta(
+    ob_type: *mut pyo3_ffi::PyTypeObject,
+) -> Result<Rc<DataclassMeta>, SerializeError> {
+    let dataclass_fields = ffi!(PyDict_GetItem((*ob_type).tp_dict, DATACLASS_FIELDS_STR));
+    let cache = unsafe { DATACLASS_META.get_mut().unwrap_or_else(|| unreachable!()) };
+    if let Some(meta) = cache.get(&(ob_type as usize)) {
+        if meta.dataclass_fields == dataclass_fields {
+            return Ok(Rc::clone(meta));
+        }
+    }
+    let meta = Rc::new(DataclassMeta::new(dataclass_fields)?);
+    cache.insert(ob_type as usize, Rc::clone(&meta));
+    Ok(meta)
+}
+
+impl DataclassMeta {
+    #[cold]
+    #[inline(never)]
+    fn new(dataclass_fields: *mut pyo3_ffi::PyObject) -> Result<Self, SerializeError> {
+        ffi!(Py_INCREF(dataclass_fields));
+        let mut meta = DataclassMeta {
+            dataclass_fields: dataclass_fields,
+            aliases: SmallVec::new(),
+            fields: SmallVec::new(),
+        };
+        for (attr, field) in PyDictIter::from_pyobject(dataclass_fields) {
+            let field_type = ffi!(PyObject_GetAttr(field, FIELD_TYPE_STR));
+            ffi!(Py_XDECREF(field_type));
+            if unsafe { field_type != FIELD_TYPE.as_ptr() } {
+                ffi!(PyErr_Clear());
+                continue;
+            }
+            let data = unicode_to_str(attr);
+            if unlikely!(data.is_none()) {
+                return Err(SerializeError::InvalidStr);
+            }
+            let mut key_as_str = data.unwrap();
+            if key_as_str.as_bytes()[0] == b'_' {
+                continue;
+            }
+            let options = field_options(field);
+            if !options.is_null() {
+                let exclude = option_is_true(options, "exclude\0");
+                let alias = option_value(options, "alias\0");
+                ffi!(Py_DECREF(options));
+                if !alias.is_null() {
+                    let alias_as_str = if unsafe { ob_type!(alias) == STR_TYPE } {
+                        unicode_to_str(alias)
+                    } else {
+                        None
+                    };
+                    meta.aliases.push(alias);
+                    if unlikely!(alias_as_str.is_none()) {
+                        return Err(SerializeError::DataclassAliasMustBeStr);
+                    }
+                    key_as_str = alias_as_str.unwrap();
+                }
+                if exclude {
+                    continue;
+                }
+            }
+            // an alias may be the name or alias of another field
+            if unlikely!(meta.fields.iter().any(|(_, key)| *key == key_as_str)) {
+                return Err(SerializeError::DataclassDuplicateKey(
+                    key_as_str.to_string(),
+                ));
+            }
+            meta.fields.push((attr, key_as_str));
+        }
+        Ok(meta)
+    }
+}
+
+/// New reference to `field.metadata["orjson"]` or null if absent.
+pub fn field_options(field: *mut pyo3_ffi::PyObject) -> *mut pyo3_ffi::PyObject {
+    let metadata = ffi!(PyObject_GetAttr(field, METADATA_STR));
+    if unlikely!(metadata.is_null()) {
+        ffi!(PyErr_Clear());
+        return std::ptr::null_mut();
+    }
+    let options = if ffi!(PyObject_Size(metadata)) > 0 {
+        option_value(metadata, "orjson\0")
+    } else {
+        std::ptr::null_mut()
+    };
+    ffi!(Py_DECREF(metadata));
+    ffi!(PyErr_Clear());
+    options
+}
+
+/// New reference to `options[key]` or null if absent.
+pub fn option_value(options: *mut pyo3_ffi::PyObject, key: &str) -> *mut pyo3_ffi::PyObject {
+    let value = ffi!(PyMapping_GetItemString(
+        options,
+        key.as_ptr() as *const c_char
+    ));
+    if value.is_null() {
+        ffi!(PyErr_Clear());
+    }
+    value
+}
+
+fn option_is_true(options: *mut pyo3_ffi::PyObject, key: &str) -> bool {
+    let value = option_value(options, key);
+    if value.is_null() {
+        return false;
+    }
+    let ret = ffi!(PyObject_IsTrue(value)) == 1;
+    ffi!(Py_DECREF(value));
+    ffi!(PyErr_Clear());
+    ret
+}
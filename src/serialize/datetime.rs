@@ -2,9 +2,9 @@
 
 use crate::opt::*;
 use crate::serialize::datetimelike::{DateTimeBuffer, DateTimeError, DateTimeLike, Offset};
+use crate::serialize::encode::*;
 use crate::serialize::error::*;
 use crate::typeref::*;
-use serde::ser::{Serialize, Serializer};
 
 macro_rules! write_double_digit {
     ($buf:ident, $value:ident) => {
@@ -15,6 +15,15 @@ macro_rules! write_double_digit {
     };
 }
 
+macro_rules! write_millisecond {
+    ($buf:ident, $millisecond:ident) => {
+        let mut buf = itoa::Buffer::new();
+        let formatted = buf.format($millisecond);
+        $buf.extend_from_slice(&[b'.', b'0', b'0', b'0'][..(4 - formatted.len())]);
+        $buf.extend_from_slice(formatted.as_bytes());
+    };
+}
+
 macro_rules! write_microsecond {
     ($buf:ident, $microsecond:ident) => {
         if $microsecond != 0 {
@@ -60,15 +69,12 @@ impl Date {
         }
     }
 }
-impl Serialize for Date {
+impl Encode for Date {
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         let mut buf = DateTimeBuffer::new();
         self.write_buf(&mut buf);
-        serializer.serialize_str(str_from_slice!(buf.as_ptr(), buf.len()))
+        enc.write_str(str_from_slice!(buf.as_ptr(), buf.len()))
     }
 }
 
@@ -100,7 +106,12 @@ impl Time {
         buf.push(b':');
         let second = ffi!(PyDateTime_TIME_GET_SECOND(self.ptr)) as u8;
         write_double_digit!(buf, second);
-        if self.opts & OMIT_MICROSECONDS == 0 {
+        if unlikely!(self.opts & JS != 0) {
+            if self.opts & OMIT_MICROSECONDS == 0 {
+                let millisecond = ffi!(PyDateTime_TIME_GET_MICROSECOND(self.ptr)) as u32 / 1_000;
+                write_millisecond!(buf, millisecond);
+            }
+        } else if self.opts & OMIT_MICROSECONDS == 0 {
             let microsecond = ffi!(PyDateTime_TIME_GET_MICROSECOND(self.ptr)) as u32;
             write_microsecond!(buf, microsecond);
         }
@@ -108,17 +119,14 @@ impl Time {
     }
 }
 
-impl Serialize for Time {
+impl Encode for Time {
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         let mut buf = DateTimeBuffer::new();
         if self.write_buf(&mut buf).is_err() {
             err!(SerializeError::DatetimeLibraryUnsupported)
         };
-        serializer.serialize_str(str_from_slice!(buf.as_ptr(), buf.len()))
+        enc.write_str(str_from_slice!(buf.as_ptr(), buf.len()))
     }
 }
 
@@ -232,16 +240,13 @@ impl DateTimeLike for DateTime {
     }
 }
 
-impl Serialize for DateTime {
+impl Encode for DateTime {
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         let mut buf = DateTimeBuffer::new();
         if self.write_buf(&mut buf, self.opts).is_err() {
             err!(SerializeError::DatetimeLibraryUnsupported)
         }
-        serializer.serialize_str(str_from_slice!(buf.as_ptr(), buf.len()))
+        enc.write_str(str_from_slice!(buf.as_ptr(), buf.len()))
     }
 }
@@ -115,7 +115,12 @@ pub trait DateTimeLike {
         write_double_digit!(buf, self.minute());
         buf.push(b':');
         write_double_digit!(buf, self.second());
-        if opts & OMIT_MICROSECONDS == 0 {
+        if unlikely!(opts & JS != 0) {
+            if opts & OMIT_MICROSECONDS == 0 {
+                buf.push(b'.');
+                write_triple_digit!(buf, self.millisecond());
+            }
+        } else if opts & OMIT_MICROSECONDS == 0 {
             let microsecond = self.microsecond();
             if microsecond != 0 {
                 buf.push(b'.');
@@ -135,7 +140,7 @@ pub trait DateTimeLike {
             let offset = self.offset()?;
             let mut offset_second = offset.second;
             if offset_second == 0 {
-                if opts & UTC_Z != 0 {
+                if opts & UTC_Z_OR_JS != 0 {
                     buf.push(b'Z');
                 } else {
                     buf.extend_from_slice(&[b'+', b'0', b'0', b':', b'0', b'0']);
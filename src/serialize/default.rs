@@ -1,18 +1,21 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::opt::*;
+use crate::serialize::encode::*;
 use crate::serialize::error::*;
 use crate::serialize::serializer::*;
+use crate::serialize::stats::SerializeStats;
+use crate::serialize::str::StrSerializer;
+use crate::typeref::*;
 
-use serde::ser::{Serialize, Serializer};
-
+use std::os::raw::c_char;
 use std::ptr::NonNull;
 
 pub struct DefaultSerializer {
     ptr: *mut pyo3_ffi::PyObject,
     opts: Opt,
     default_calls: u8,
-    recursion: u8,
+    recursion: u32,
     default: Option<NonNull<pyo3_ffi::PyObject>>,
 }
 
@@ -21,7 +24,7 @@ impl DefaultSerializer {
         ptr: *mut pyo3_ffi::PyObject,
         opts: Opt,
         default_calls: u8,
-        recursion: u8,
+        recursion: u32,
         default: Option<NonNull<pyo3_ffi::PyObject>>,
     ) -> Self {
         DefaultSerializer {
@@ -34,23 +37,93 @@ impl DefaultSerializer {
     }
 }
 
-impl Serialize for DefaultSerializer {
+/// The callable in a `dict` given as `default` for the type of `ptr` or, if
+/// there is none, the nearest base class of it in its MRO.
+#[cold]
+#[inline(never)]
+fn lookup_converter(
+    converters: *mut pyo3_ffi::PyObject,
+    ptr: *mut pyo3_ffi::PyObject,
+) -> Option<NonNull<pyo3_ffi::PyObject>> {
+    let ob_type = ob_type!(ptr);
+    let callable = ffi!(PyDict_GetItem(
+        converters,
+        ob_type as *mut pyo3_ffi::PyObject
+    ));
+    if !callable.is_null() {
+        return Some(nonnull!(callable));
+    }
+    let mro = unsafe { (*ob_type).tp_mro };
+    if mro.is_null() {
+        return None;
+    }
+    // the first entry is the type itself
+    for idx in 1..ffi!(Py_SIZE(mro)) {
+        let base = ffi!(PyTuple_GET_ITEM(mro, idx));
+        let callable = ffi!(PyDict_GetItem(converters, base));
+        if !callable.is_null() {
+            return Some(nonnull!(callable));
+        }
+    }
+    None
+}
+
+/// Call `default` with `ptr` and the JSON pointer of its location for
+/// `OPT_DEFAULT_PATH`.
+#[cold]
+#[inline(never)]
+fn call_with_path(
+    callable: NonNull<pyo3_ffi::PyObject>,
+    ptr: *mut pyo3_ffi::PyObject,
+    pointer: &str,
+) -> *mut pyo3_ffi::PyObject {
+    let path = ffi!(PyUnicode_FromStringAndSize(
+        pointer.as_ptr() as *const c_char,
+        pointer.len() as isize
+    ));
+    if unlikely!(path.is_null()) {
+        return path;
+    }
+    let res = ffi!(PyObject_CallFunctionObjArgs(
+        callable.as_ptr(),
+        ptr,
+        path,
+        std::ptr::null_mut::<pyo3_ffi::PyObject>()
+    ));
+    ffi!(Py_DECREF(path));
+    res
+}
+
+impl Encode for DefaultSerializer {
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         match self.default {
-            Some(callable) => {
+            Some(default) => {
                 if unlikely!(self.default_calls == RECURSION_LIMIT) {
                     err!(SerializeError::DefaultRecursionLimit)
                 }
-                let default_obj = ffi!(PyObject_CallFunctionObjArgs(
-                    callable.as_ptr(),
-                    self.ptr,
-                    std::ptr::null_mut() as *mut pyo3_ffi::PyObject
-                ));
+                let callable = if unlikely!(unsafe { ob_type!(default.as_ptr()) == DICT_TYPE }) {
+                    match lookup_converter(default.as_ptr(), self.ptr) {
+                        Some(callable) => callable,
+                        None => return self.encode_fallback(enc),
+                    }
+                } else {
+                    default
+                };
+                SerializeStats::record_default_call();
+                let default_obj = match enc.path_pointer() {
+                    None => ffi!(PyObject_CallFunctionObjArgs(
+                        callable.as_ptr(),
+                        self.ptr,
+                        std::ptr::null_mut() as *mut pyo3_ffi::PyObject
+                    )),
+                    Some(pointer) => call_with_path(callable, self.ptr, &pointer),
+                };
                 if unlikely!(default_obj.is_null()) {
+                    if self.opts & ANY_FALLBACK != 0 {
+                        ffi!(PyErr_Clear());
+                        return self.encode_fallback(enc);
+                    }
                     err!(SerializeError::UnsupportedType(nonnull!(self.ptr)))
                 } else {
                     let res = PyObjectSerializer::new(
@@ -60,12 +133,55 @@ impl Serialize for DefaultSerializer {
                         self.recursion,
                         self.default,
                     )
-                    .serialize(serializer);
+                    .encode(enc);
                     ffi!(Py_DECREF(default_obj));
                     res
                 }
             }
-            None => err!(SerializeError::UnsupportedType(nonnull!(self.ptr))),
+            None => self.encode_fallback(enc),
+        }
+    }
+}
+
+impl DefaultSerializer {
+    /// Write an object that `default` is not given for or raised on as its
+    /// `__dict__` for `OPT_FALLBACK_VARS` if it is a `dict`, or else as its
+    /// `str()` for `OPT_FALLBACK_STR` or its `repr()` for `OPT_FALLBACK_REPR`.
+    #[cold]
+    fn encode_fallback(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        if self.opts & FALLBACK_VARS != 0 {
+            let dict = ffi!(PyObject_GetAttr(self.ptr, DICT_STR));
+            if !dict.is_null() && unsafe { ob_type!(dict) == DICT_TYPE } {
+                if unlikely!(self.default_calls == RECURSION_LIMIT) {
+                    ffi!(Py_DECREF(dict));
+                    err!(SerializeError::DefaultRecursionLimit)
+                }
+                let res = PyObjectSerializer::new(
+                    dict,
+                    self.opts,
+                    self.default_calls + 1,
+                    self.recursion,
+                    self.default,
+                )
+                .encode(enc);
+                ffi!(Py_DECREF(dict));
+                return res;
+            }
+            ffi!(Py_XDECREF(dict));
+            ffi!(PyErr_Clear());
+        }
+        let string = if self.opts & FALLBACK_STR != 0 {
+            ffi!(PyObject_Str(self.ptr))
+        } else if self.opts & FALLBACK_REPR != 0 {
+            ffi!(PyObject_Repr(self.ptr))
+        } else {
+            err!(SerializeError::UnsupportedType(nonnull!(self.ptr)))
+        };
+        if unlikely!(string.is_null()) {
+            err!(SerializeError::UnsupportedType(nonnull!(self.ptr)))
         }
+        let res = StrSerializer::new(string).encode(enc);
+        ffi!(Py_DECREF(string));
+        res
     }
 }
@@ -1,111 +1,129 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use crate::ffi::PyDictIter;
+use crate::deserialize::raw_number_literal;
+use crate::ffi::{PyBytes_AS_STRING, PyBytes_GET_SIZE, PyDictIter, PyDict_SharedKeys};
 use crate::opt::*;
 use crate::serialize::datetime::*;
 use crate::serialize::datetimelike::*;
+use crate::serialize::encode::*;
 use crate::serialize::error::*;
-use crate::serialize::serializer::pyobject_to_obtype;
 use crate::serialize::serializer::*;
+use crate::serialize::shared_keys::{shared_keys, SharedKeys};
 use crate::serialize::uuid::*;
 use crate::typeref::*;
 use crate::unicode::*;
 use compact_str::CompactString;
-use serde::ser::{Serialize, SerializeMap, Serializer};
 use smallvec::SmallVec;
-use std::ptr::NonNull;
+use std::cell::RefCell;
+use std::rc::Rc;
 
+/// The entries of a `dict` with `str` keys being written in iteration order,
+/// each value returned to `PyObjectSerializer` to encode.
 pub struct Dict {
-    ptr: *mut pyo3_ffi::PyObject,
-    opts: Opt,
-    default_calls: u8,
-    recursion: u8,
-    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    iter: PyDictIter,
+    idx: usize,
+    first: bool,
+    shared: Option<Rc<RefCell<SharedKeys>>>,
 }
 
 impl Dict {
-    pub fn new(
-        ptr: *mut pyo3_ffi::PyObject,
-        opts: Opt,
-        default_calls: u8,
-        recursion: u8,
-        default: Option<NonNull<pyo3_ffi::PyObject>>,
-    ) -> Self {
+    pub fn new(ptr: *mut pyo3_ffi::PyObject, opts: Opt) -> Self {
+        let dk = PyDict_SharedKeys(ptr);
         Dict {
-            ptr: ptr,
-            opts: opts,
-            default_calls: default_calls,
-            recursion: recursion,
-            default: default,
+            iter: PyDictIter::from_pyobject(ptr),
+            idx: 0,
+            first: true,
+            // keys are escaped differently for OPT_JS than they are cached
+            shared: if dk.is_null() || opts & JS != 0 {
+                None
+            } else {
+                Some(shared_keys(dk))
+            },
         }
     }
-}
 
-impl Serialize for Dict {
+    /// Write a key of a split-table dict from, or else to, the keys
+    /// serialized for its keys table.
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut map = serializer.serialize_map(None).unwrap();
-        for (key, value) in PyDictIter::from_pyobject(self.ptr) {
+    fn write_shared_key(
+        &self,
+        shared: &RefCell<SharedKeys>,
+        key: *mut pyo3_ffi::PyObject,
+        enc: &mut Encoder,
+    ) -> Result<(), EncodeError> {
+        if let Some(val) = shared.borrow().get(self.idx, key) {
+            return enc.write_raw(val);
+        }
+        let key_data = unicode_data(key);
+        if unlikely!(key_data.is_none()) {
+            err!(SerializeError::InvalidStr)
+        }
+        let start = enc.position();
+        enc.write_unicode(key_data.unwrap())?;
+        shared
+            .borrow_mut()
+            .record(self.idx, key, enc.written_since(start));
+        Ok(())
+    }
+
+    /// Write the separator and key before the next value and return it or,
+    /// after the last entry, end the object.
+    #[inline(always)]
+    pub fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        while let Some((key, value)) = self.iter.next() {
             if unlikely!(unsafe { ob_type!(key) != STR_TYPE }) {
                 err!(SerializeError::KeyMustBeStr)
             }
-            let key_as_str = unicode_to_str(key);
-            if unlikely!(key_as_str.is_none()) {
-                err!(SerializeError::InvalidStr)
+            if unlikely!(enc.is_including()) && !Self::includes_key(key, enc)? {
+                self.idx += 1;
+                continue;
+            }
+            enc.begin_key(self.first)?;
+            if let Some(shared) = &self.shared {
+                self.write_shared_key(shared, key, enc)?;
+            } else {
+                let key_data = unicode_data(key);
+                if unlikely!(key_data.is_none()) {
+                    err!(SerializeError::InvalidStr)
+                }
+                enc.write_unicode(key_data.unwrap())?;
             }
-            let pyvalue = PyObjectSerializer::new(
-                value,
-                self.opts,
-                self.default_calls,
-                self.recursion + 1,
-                self.default,
-            );
-            map.serialize_key(key_as_str.unwrap()).unwrap();
-            map.serialize_value(&pyvalue)?;
+            enc.end_key()?;
+            enc.check_redacted(key);
+            enc.set_path_key(key)?;
+            self.idx += 1;
+            self.first = false;
+            return Ok(Some(value));
+        }
+        enc.end_object()?;
+        Ok(None)
+    }
+
+    #[inline(never)]
+    fn includes_key(key: *mut pyo3_ffi::PyObject, enc: &mut Encoder) -> Result<bool, EncodeError> {
+        match unicode_to_str(key) {
+            Some(key_as_str) => Ok(enc.includes_key(key_as_str)),
+            None => err!(SerializeError::InvalidStr),
         }
-        map.end()
     }
 }
 
+/// The entries of a `dict` with `str` keys being written sorted by key.
 pub struct DictSortedKey {
-    ptr: *mut pyo3_ffi::PyObject,
-    opts: Opt,
-    default_calls: u8,
-    recursion: u8,
-    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    items: SmallVec<[(&'static str, *mut pyo3_ffi::PyObject); 8]>,
+    idx: usize,
+    first: bool,
 }
 
 impl DictSortedKey {
-    pub fn new(
-        ptr: *mut pyo3_ffi::PyObject,
-        opts: Opt,
-        default_calls: u8,
-        recursion: u8,
-        default: Option<NonNull<pyo3_ffi::PyObject>>,
-    ) -> Self {
-        DictSortedKey {
-            ptr: ptr,
-            opts: opts,
-            default_calls: default_calls,
-            recursion: recursion,
-            default: default,
-        }
-    }
-}
-
-impl Serialize for DictSortedKey {
-    #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let len = ffi!(Py_SIZE(self.ptr)) as usize;
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Result<Self, EncodeError> {
+        let len = ffi!(Py_SIZE(ptr)) as usize;
         let mut items: SmallVec<[(&str, *mut pyo3_ffi::PyObject); 8]> =
             SmallVec::with_capacity(len);
-        for (key, value) in PyDictIter::from_pyobject(self.ptr) {
+        for (key, value) in PyDictIter::from_pyobject(ptr) {
             if unlikely!(unsafe { ob_type!(key) != STR_TYPE }) {
                 err!(SerializeError::KeyMustBeStr)
             }
@@ -118,49 +136,121 @@ impl Serialize for DictSortedKey {
 
         items.sort_unstable_by(|a, b| a.0.cmp(b.0));
 
-        let mut map = serializer.serialize_map(None).unwrap();
-        for (key, val) in items.iter() {
-            let pyvalue = PyObjectSerializer::new(
-                *val,
-                self.opts,
-                self.default_calls,
-                self.recursion + 1,
-                self.default,
-            );
-            map.serialize_key(key).unwrap();
-            map.serialize_value(&pyvalue)?;
+        Ok(DictSortedKey {
+            items: items,
+            idx: 0,
+            first: true,
+        })
+    }
+
+    /// Write the separator and key before the next value and return it or,
+    /// after the last entry, end the object.
+    #[inline(always)]
+    pub fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        while self.idx < self.items.len() {
+            let (key, value) = self.items[self.idx];
+            self.idx += 1;
+            if unlikely!(enc.is_including()) && !enc.includes_key(key) {
+                continue;
+            }
+            enc.write_key(key, self.first)?;
+            self.first = false;
+            return Ok(Some(value));
         }
-        map.end()
+        enc.end_object()?;
+        Ok(None)
     }
 }
 
+/// The entries of a `dict` being written with keys that are not `str`
+/// converted to strings, sorted if `OPT_SORT_KEYS` is given.
 pub struct DictNonStrKey {
-    ptr: *mut pyo3_ffi::PyObject,
-    opts: Opt,
-    default_calls: u8,
-    recursion: u8,
-    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    items: SmallVec<[(CompactString, *mut pyo3_ffi::PyObject); 8]>,
+    idx: usize,
+    first: bool,
 }
 
 impl DictNonStrKey {
-    pub fn new(
-        ptr: *mut pyo3_ffi::PyObject,
+    pub fn new(ptr: *mut pyo3_ffi::PyObject, opts: Opt) -> Result<Self, EncodeError> {
+        let len = ffi!(Py_SIZE(ptr)) as usize;
+        let mut items: SmallVec<[(CompactString, *mut pyo3_ffi::PyObject); 8]> =
+            SmallVec::with_capacity(len);
+        let opts = opts & NOT_PASSTHROUGH;
+        for (key, value) in PyDictIter::from_pyobject(ptr) {
+            items.push((Self::key_to_string(key, opts)?, value));
+        }
+
+        if opts & SORT_KEYS != 0 {
+            items.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        }
+
+        Ok(DictNonStrKey {
+            items: items,
+            idx: 0,
+            first: true,
+        })
+    }
+
+    /// Write the separator and key before the next value and return it or,
+    /// after the last entry, end the object.
+    #[inline(always)]
+    pub fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        while self.idx < self.items.len() {
+            let (key, value) = &self.items[self.idx];
+            let key = str_from_slice!(key.as_ptr(), key.len());
+            let value = *value;
+            self.idx += 1;
+            if unlikely!(enc.is_including()) && !enc.includes_key(key) {
+                continue;
+            }
+            enc.write_key(key, self.first)?;
+            self.first = false;
+            return Ok(Some(value));
+        }
+        enc.end_object()?;
+        Ok(None)
+    }
+
+    /// The string `key` is written as, converting a key that is not `str` if
+    /// `opts`, without the passthrough options, allow.
+    pub fn key_to_string(
+        key: *mut pyo3_ffi::PyObject,
         opts: Opt,
-        default_calls: u8,
-        recursion: u8,
-        default: Option<NonNull<pyo3_ffi::PyObject>>,
-    ) -> Self {
-        DictNonStrKey {
-            ptr: ptr,
-            opts: opts,
-            default_calls: default_calls,
-            recursion: recursion,
-            default: default,
+    ) -> Result<CompactString, EncodeError> {
+        if is_type!(ob_type!(key), STR_TYPE) {
+            let uni = unicode_to_str(key);
+            if unlikely!(uni.is_none()) {
+                err!(SerializeError::InvalidStr)
+            }
+            Ok(CompactString::from(uni.unwrap()))
+        } else if opts & BYTES_KEYS != 0 && is_type!(ob_type!(key), BYTES_TYPE) {
+            let buffer = unsafe {
+                std::slice::from_raw_parts(
+                    PyBytes_AS_STRING(key) as *const u8,
+                    PyBytes_GET_SIZE(key) as usize,
+                )
+            };
+            match std::str::from_utf8(buffer) {
+                Ok(key_as_str) => Ok(CompactString::from(key_as_str)),
+                Err(_) => err!(SerializeError::InvalidBytesKey),
+            }
+        } else if unlikely!(opts & NON_STR_KEYS == 0) {
+            err!(SerializeError::KeyMustBeStr)
+        } else {
+            match Self::pyobject_to_string(key, opts) {
+                Ok(key_as_str) => Ok(key_as_str),
+                Err(err) => err!(err),
+            }
         }
     }
 
     fn pyobject_to_string(
-        &self,
         key: *mut pyo3_ffi::PyObject,
         opts: crate::opt::Opt,
     ) -> Result<CompactString, SerializeError> {
@@ -180,6 +270,7 @@ impl DictNonStrKey {
                     ffi!(PyErr_Clear());
                     let uval = ffi!(PyLong_AsUnsignedLongLong(key));
                     if unlikely!(uval == u64::MAX && !ffi!(PyErr_Occurred()).is_null()) {
+                        ffi!(PyErr_Clear());
                         return Err(SerializeError::DictIntegerKey64Bit);
                     }
                     Ok(CompactString::from(itoa::Buffer::new().format(uval)))
@@ -191,6 +282,8 @@ impl DictNonStrKey {
                 let val = ffi!(PyFloat_AS_DOUBLE(key));
                 if !val.is_finite() {
                     Ok(CompactString::from("null"))
+                } else if opts & NORMALIZE_NEGATIVE_ZERO != 0 && val == 0.0 {
+                    Ok(CompactString::from("0.0"))
                 } else {
                     Ok(CompactString::from(ryu::Buffer::new().format_finite(val)))
                 }
@@ -225,10 +318,11 @@ impl DictNonStrKey {
                 let key_as_str = str_from_slice!(buf.as_ptr(), buf.len());
                 Ok(CompactString::from(key_as_str))
             }
+            ObType::RawNumber => Ok(CompactString::from(raw_number_literal(key))),
             ObType::Enum => {
                 let value = ffi!(PyObject_GetAttr(key, VALUE_STR));
                 ffi!(Py_DECREF(value));
-                self.pyobject_to_string(value, opts)
+                Self::pyobject_to_string(value, opts)
             }
             ObType::Str => {
                 // because of ObType::Enum
@@ -253,53 +347,12 @@ impl DictNonStrKey {
             | ObType::Dict
             | ObType::List
             | ObType::Dataclass
+            | ObType::GeoInterface
+            | ObType::Mapping
+            | ObType::MultiDict
+            | ObType::Sequence
+            | ObType::Pairs
             | ObType::Unknown => Err(SerializeError::DictKeyInvalidType),
         }
     }
 }
-
-impl Serialize for DictNonStrKey {
-    #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let len = ffi!(Py_SIZE(self.ptr)) as usize;
-        let mut items: SmallVec<[(CompactString, *mut pyo3_ffi::PyObject); 8]> =
-            SmallVec::with_capacity(len);
-        let opts = self.opts & NOT_PASSTHROUGH;
-        for (key, value) in PyDictIter::from_pyobject(self.ptr) {
-            if is_type!(ob_type!(key), STR_TYPE) {
-                let uni = unicode_to_str(key);
-                if unlikely!(uni.is_none()) {
-                    err!(SerializeError::InvalidStr)
-                }
-                items.push((CompactString::from(uni.unwrap()), value));
-            } else {
-                match self.pyobject_to_string(key, opts) {
-                    Ok(key_as_str) => items.push((key_as_str, value)),
-                    Err(err) => err!(err),
-                }
-            }
-        }
-
-        if opts & SORT_KEYS != 0 {
-            items.sort_unstable_by(|a, b| a.0.cmp(&b.0));
-        }
-
-        let mut map = serializer.serialize_map(None).unwrap();
-        for (key, val) in items.iter() {
-            let pyvalue = PyObjectSerializer::new(
-                *val,
-                self.opts,
-                self.default_calls,
-                self.recursion + 1,
-                self.default,
-            );
-            let key_as_str = str_from_slice!(key.as_ptr(), key.len());
-            map.serialize_key(key_as_str).unwrap();
-            map.serialize_value(&pyvalue)?;
-        }
-        map.end()
-    }
-}
@@ -0,0 +1,920 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::opt::*;
+use crate::serialize::error::{EncodeError, SerializeError};
+use crate::serialize::include::IncludeKeys;
+use crate::serialize::path::ValuePath;
+use crate::serialize::redact::RedactKeys;
+use crate::serialize::separators::Separators;
+use crate::serialize::serializer::{DumpsArgs, DEFAULT_MAX_DEPTH, RECURSION_LIMIT};
+use crate::serialize::writer::BytesWriter;
+use crate::unicode::{unicode_to_str, UnicodeData};
+use std::fmt::Write;
+use std::ptr::NonNull;
+
+// Code points of UCS1, UCS2, and UCS4 strings are encoded in chunks of this
+// many so that reserving the longest possible output, six bytes for each, is
+// bounded.
+const UCS_CHUNK_LENGTH: usize = 4096;
+
+// Times a `PyObjectSerializer` may be nested in another, i.e., serializing a
+// value returned by `default` or `__geo_interface__` or of an enum. This
+// bounds the stack used where `max_depth` does not. It is one more than the
+// calls to `default` allowed so that exceeding those is reported as such.
+const NESTED_LIMIT: u16 = RECURSION_LIMIT as u16 + 1;
+
+// Floats of at least this magnitude have no fractional digits to round.
+const FLOAT_INTEGRAL_MIN: f64 = 4503599627370496.0; // 2**52
+
+// Longest output of `normalize_float()`: a sign and either 21 integral digits
+// and ".0" or "0.", five zeros, and 17 significant digits.
+const FLOAT_NORMALIZED_LEN: usize = 32;
+
+// Written after a string value truncated by `max_str_len`, closing it.
+const TRUNCATED_STR_END: &[u8] = b"...\"";
+
+#[cold]
+#[inline(never)]
+fn call_transform(
+    callable: NonNull<pyo3_ffi::PyObject>,
+    ptr: *mut pyo3_ffi::PyObject,
+) -> Result<*mut pyo3_ffi::PyObject, EncodeError> {
+    let value = ffi!(PyObject_CallFunctionObjArgs(
+        callable.as_ptr(),
+        ptr,
+        std::ptr::null_mut::<pyo3_ffi::PyObject>()
+    ));
+    if unlikely!(value.is_null()) {
+        err!(SerializeError::TransformFailed)
+    }
+    Ok(value)
+}
+
+/// A value that writes itself as JSON to an `Encoder`.
+pub trait Encode {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError>;
+}
+
+/// Writes JSON tokens directly to the output buffer, either compact or, for
+/// `OPT_INDENT_2`, indented by two spaces per level. With `float_precision`,
+/// floats are rounded to that many decimal places before being written. For
+/// `OPT_JS`, U+2028 and U+2029 in strings are escaped. For
+/// `OPT_NUMBERS_AS_STRINGS`, finite numbers are written as strings. For
+/// `OPT_NORMALIZE_FLOAT`, floats are written in a fixed notation. For
+/// `OPT_NORMALIZE_NEGATIVE_ZERO`, negative zero is written as zero. With
+/// `max_str_len`, longer string values are truncated. With `transform`, each
+/// value is replaced by what `transform()` returns for it. With
+/// `separators`, those are written instead of "," and ":". With
+/// `repr_floats`, floats are written as `repr()` writes them and NaN and
+/// infinity are an error rather than `null`.
+///
+/// Nesting of containers beyond `max_depth` is an error.
+///
+/// With `redact_keys`, writing a key that is redacted makes `take_redacted()`
+/// return true before the value is written. With `include_keys`, containers
+/// track the keys included of the objects in them and an entry is written
+/// only if `includes_key()` is true of its key. For `OPT_DEFAULT_PATH`,
+/// containers track the index or key of the value being written so that
+/// `path_pointer()` can locate it.
+///
+/// Containers are written as `begin_array()`, then `begin_array_value()`
+/// before each element, then `end_array()`, and likewise for objects using
+/// `write_key()` before each value.
+pub struct Encoder {
+    writer: BytesWriter,
+    pretty: bool,
+    escape_line_separators: bool,
+    quote_numbers: bool,
+    normalize_float: bool,
+    positive_zero: bool,
+    float_precision: Option<u8>,
+    max_depth: u32,
+    redact_keys: Option<RedactKeys>,
+    redact_value: bool,
+    include_keys: Option<IncludeKeys>,
+    // The node of `include_keys` applying to each container being written
+    // and to the next value, `None` if all keys are written.
+    include_stack: Vec<Option<usize>>,
+    include_next: Option<usize>,
+    max_str_len: Option<usize>,
+    transform: Option<NonNull<pyo3_ffi::PyObject>>,
+    separators: Option<Separators>,
+    repr_floats: bool,
+    path: Option<ValuePath>,
+    nested: u16,
+    depth: usize,
+    has_value: bool,
+}
+
+impl Encoder {
+    pub fn new(writer: BytesWriter, opts: Opt, args: DumpsArgs) -> Self {
+        Encoder {
+            writer: writer,
+            pretty: opts & INDENT_2 != 0,
+            escape_line_separators: opts & JS != 0,
+            quote_numbers: opts & NUMBERS_AS_STRINGS != 0,
+            normalize_float: opts & NORMALIZE_FLOAT != 0,
+            positive_zero: opts & NORMALIZE_NEGATIVE_ZERO != 0,
+            float_precision: args.float_precision,
+            max_depth: args.max_depth.unwrap_or(DEFAULT_MAX_DEPTH),
+            redact_keys: args.redact_keys,
+            redact_value: false,
+            include_keys: args.include_keys,
+            include_stack: Vec::new(),
+            include_next: Some(0),
+            max_str_len: args.max_str_len,
+            transform: args.transform,
+            separators: args.separators,
+            repr_floats: args.repr_floats,
+            path: if opts & DEFAULT_PATH != 0 {
+                Some(ValuePath::default())
+            } else {
+                None
+            },
+            nested: 0,
+            depth: 0,
+            has_value: false,
+        }
+    }
+
+    /// Length of the output so far.
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.writer.len()
+    }
+
+    /// The output written after `position()` returned `start`.
+    #[inline(always)]
+    pub fn written_since(&self, start: usize) -> &[u8] {
+        self.writer.written_since(start)
+    }
+
+    #[inline(always)]
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth
+    }
+
+    /// A strong reference to the value to write instead of `ptr`, or null if
+    /// `transform` is not given.
+    #[inline(always)]
+    pub fn transform(
+        &self,
+        ptr: *mut pyo3_ffi::PyObject,
+    ) -> Result<*mut pyo3_ffi::PyObject, EncodeError> {
+        match self.transform {
+            None => Ok(std::ptr::null_mut()),
+            Some(callable) => call_transform(callable, ptr),
+        }
+    }
+
+    /// Whether the value after the key just written is to be replaced by
+    /// `REDACTED`, resetting it.
+    #[inline(always)]
+    pub fn take_redacted(&mut self) -> bool {
+        std::mem::replace(&mut self.redact_value, false)
+    }
+
+    /// Mark the value after the `str` object `key`, just written, as
+    /// redacted if it is.
+    #[inline(always)]
+    pub fn check_redacted(&mut self, key: *mut pyo3_ffi::PyObject) {
+        if let Some(redact) = &self.redact_keys {
+            self.redact_value = redact.contains(key);
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_including(&self) -> bool {
+        self.include_keys.is_some()
+    }
+
+    /// Whether the entry of `key` in the object being written is written
+    /// with `include_keys`. If so, its value is written with the keys
+    /// included under it.
+    #[inline(never)]
+    pub fn includes_key(&mut self, key: &str) -> bool {
+        let node = match self.include_stack.last() {
+            Some(&Some(node)) => node,
+            _ => {
+                self.include_next = None;
+                return true;
+            }
+        };
+        match self.include_keys.as_ref().unwrap().get(node, key) {
+            Some(child) => {
+                self.include_next = child;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The JSON pointer of the value being written for `OPT_DEFAULT_PATH`.
+    pub fn path_pointer(&self) -> Option<String> {
+        self.path.as_ref().map(|path| path.to_pointer())
+    }
+
+    /// Record the `str` object `key`, just written, as the key of the value
+    /// being written for `OPT_DEFAULT_PATH`.
+    #[inline(always)]
+    pub fn set_path_key(&mut self, key: *mut pyo3_ffi::PyObject) -> Result<(), EncodeError> {
+        if let Some(path) = self.path.as_mut() {
+            match unicode_to_str(key) {
+                Some(key_as_str) => path.set_key(key_as_str),
+                None => err!(SerializeError::InvalidStr),
+            }
+        }
+        Ok(())
+    }
+
+    /// Count a `PyObjectSerializer` starting, returning false if too many
+    /// are already in progress.
+    #[inline(always)]
+    pub fn enter_nested(&mut self) -> bool {
+        if unlikely!(self.nested == NESTED_LIMIT) {
+            return false;
+        }
+        self.nested += 1;
+        true
+    }
+
+    #[inline(always)]
+    pub fn leave_nested(&mut self) {
+        self.nested -= 1;
+    }
+
+    pub fn into_inner(self) -> BytesWriter {
+        self.writer
+    }
+
+    /// The output so far as a `bytes` object, continuing with none.
+    pub fn take_output(&mut self) -> NonNull<pyo3_ffi::PyObject> {
+        self.writer.take()
+    }
+
+    /// Free the output, e.g., when it is abandoned.
+    pub fn discard_output(&mut self) {
+        self.writer.discard()
+    }
+
+    #[inline(always)]
+    pub fn write_null(&mut self) -> Result<(), EncodeError> {
+        self.writer.write_bytes(b"null")
+    }
+
+    #[inline(always)]
+    pub fn write_bool(&mut self, val: bool) -> Result<(), EncodeError> {
+        if val {
+            self.writer.write_bytes(b"true")
+        } else {
+            self.writer.write_bytes(b"false")
+        }
+    }
+
+    #[inline(always)]
+    pub fn write_int<T: itoa::Integer>(&mut self, val: T) -> Result<(), EncodeError> {
+        self.write_number(itoa::Buffer::new().format(val).as_bytes())
+    }
+
+    /// Write a float as its shortest round-trip representation, or `null` if
+    /// it is NaN or infinite and not `repr_floats`.
+    #[inline(always)]
+    pub fn write_f64(&mut self, val: f64) -> Result<(), EncodeError> {
+        if unlikely!(!val.is_finite()) {
+            self.write_non_finite()
+        } else if unlikely!(self.positive_zero && val == 0.0) {
+            self.write_float("0.0")
+        } else if unlikely!(self.float_precision.is_some()) {
+            self.write_rounded_f64(val)
+        } else {
+            self.write_float(ryu::Buffer::new().format_finite(val))
+        }
+    }
+
+    #[inline(always)]
+    pub fn write_f32(&mut self, val: f32) -> Result<(), EncodeError> {
+        if unlikely!(!val.is_finite()) {
+            self.write_non_finite()
+        } else if unlikely!(self.positive_zero && val == 0.0) {
+            self.write_float("0.0")
+        } else if unlikely!(self.float_precision.is_some()) {
+            self.write_rounded_f64(val as f64)
+        } else {
+            self.write_float(ryu::Buffer::new().format_finite(val))
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn write_non_finite(&mut self) -> Result<(), EncodeError> {
+        if self.repr_floats {
+            err!(SerializeError::FloatNotFinite)
+        }
+        self.write_null()
+    }
+
+    /// Write the float nearest to `val` rounded half to even to
+    /// `float_precision` decimal places, as `round()` does.
+    #[cold]
+    #[inline(never)]
+    fn write_rounded_f64(&mut self, val: f64) -> Result<(), EncodeError> {
+        let precision = self.float_precision.unwrap_or(0) as usize;
+        let mut rounded = val;
+        if val.abs() < FLOAT_INTEGRAL_MIN {
+            // at most 16 integral digits, sign, point, and 15 fractional digits
+            let mut buf = arrayvec::ArrayString::<40>::new();
+            write!(buf, "{:.*}", precision, val).unwrap();
+            rounded = buf.parse::<f64>().unwrap();
+            if self.positive_zero && rounded == 0.0 {
+                rounded = 0.0;
+            }
+        }
+        self.write_float(ryu::Buffer::new().format_finite(rounded))
+    }
+
+    /// Write the shortest round-trip representation of a float as formatted
+    /// by `ryu`, rewritten by `normalize_float()` for `OPT_NORMALIZE_FLOAT`
+    /// or `repr_floats`.
+    #[inline(always)]
+    fn write_float(&mut self, val: &str) -> Result<(), EncodeError> {
+        if unlikely!(self.normalize_float) {
+            self.write_normalized_float(val, FloatNotation::ECMAScript)
+        } else if unlikely!(self.repr_floats) {
+            self.write_normalized_float(val, FloatNotation::Repr)
+        } else {
+            self.write_number(val.as_bytes())
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn write_normalized_float(
+        &mut self,
+        val: &str,
+        notation: FloatNotation,
+    ) -> Result<(), EncodeError> {
+        let mut buf = arrayvec::ArrayVec::<u8, FLOAT_NORMALIZED_LEN>::new();
+        normalize_float(val.as_bytes(), &mut buf, notation);
+        self.write_number(&buf)
+    }
+
+    /// Write a formatted number, quoted for `OPT_NUMBERS_AS_STRINGS`.
+    #[inline(always)]
+    fn write_number(&mut self, val: &[u8]) -> Result<(), EncodeError> {
+        if unlikely!(self.quote_numbers) {
+            self.writer.write_quoted(val)
+        } else {
+            self.writer.write_bytes(val)
+        }
+    }
+
+    /// Write a number exactly as it was written in a document, quoted for
+    /// `OPT_NUMBERS_AS_STRINGS`.
+    #[inline(always)]
+    pub fn write_number_literal(&mut self, val: &str) -> Result<(), EncodeError> {
+        self.write_number(val.as_bytes())
+    }
+
+    /// Write bytes that are already valid JSON.
+    #[inline(always)]
+    pub fn write_raw(&mut self, val: &[u8]) -> Result<(), EncodeError> {
+        self.writer.write_bytes(val)
+    }
+
+    #[inline(always)]
+    pub fn write_str(&mut self, val: &str) -> Result<(), EncodeError> {
+        if needs_escape(val.as_bytes())
+            || (unlikely!(self.escape_line_separators) && has_line_separator(val.as_bytes()))
+        {
+            self.write_escaped_str(val)
+        } else {
+            self.writer.write_quoted(val.as_bytes())
+        }
+    }
+
+    /// Write the data of an ASCII `str`, copying it in one go if nothing
+    /// needs escaping.
+    #[inline(always)]
+    fn write_ascii(&mut self, val: &[u8]) -> Result<(), EncodeError> {
+        if !needs_escape(val) {
+            self.writer.write_quoted(val)
+        } else {
+            self.write_ucs(val)
+        }
+    }
+
+    /// Write a `str` from its CPython representation, escaping and encoding
+    /// UCS1, UCS2, and UCS4 as UTF-8 in one pass.
+    #[inline(always)]
+    pub fn write_unicode(&mut self, data: UnicodeData) -> Result<(), EncodeError> {
+        match data {
+            UnicodeData::Ascii(val) => self.write_ascii(val),
+            UnicodeData::Utf8(val) => self.write_str(val),
+            UnicodeData::Ucs1(val) => self.write_ucs(val),
+            UnicodeData::Ucs2(val) => self.write_ucs(val),
+            UnicodeData::Ucs4(val) => self.write_ucs(val),
+        }
+    }
+
+    /// Write a `str` that is a value rather than a key, truncating it to
+    /// `max_str_len` code points followed by an ellipsis if it is longer.
+    #[inline(always)]
+    pub fn write_str_value(&mut self, data: UnicodeData) -> Result<(), EncodeError> {
+        match self.max_str_len {
+            Some(len) => self.write_truncated_unicode(data, len),
+            None => self.write_unicode(data),
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn write_truncated_unicode(
+        &mut self,
+        data: UnicodeData,
+        len: usize,
+    ) -> Result<(), EncodeError> {
+        let truncated = match data {
+            UnicodeData::Ascii(val) if val.len() > len => UnicodeData::Ascii(&val[..len]),
+            UnicodeData::Ucs1(val) if val.len() > len => UnicodeData::Ucs1(&val[..len]),
+            UnicodeData::Ucs2(val) if val.len() > len => UnicodeData::Ucs2(&val[..len]),
+            UnicodeData::Ucs4(val) if val.len() > len => UnicodeData::Ucs4(&val[..len]),
+            UnicodeData::Utf8(val) => match val.char_indices().nth(len) {
+                Some((idx, _)) => UnicodeData::Utf8(&val[..idx]),
+                None => return self.write_str(val),
+            },
+            _ => return self.write_unicode(data),
+        };
+        self.writer.write_byte(b'"')?;
+        match truncated {
+            UnicodeData::Ascii(val) | UnicodeData::Ucs1(val) => self.write_ucs_contents(val)?,
+            UnicodeData::Ucs2(val) => self.write_ucs_contents(val)?,
+            UnicodeData::Ucs4(val) => self.write_ucs_contents(val)?,
+            UnicodeData::Utf8(val) => self.write_escaped_contents(val)?,
+        }
+        self.writer.write_bytes(TRUNCATED_STR_END)
+    }
+
+    #[inline(never)]
+    fn write_ucs<T: Copy + Into<u32>>(&mut self, val: &[T]) -> Result<(), EncodeError> {
+        self.writer.write_byte(b'"')?;
+        self.write_ucs_contents(val)?;
+        self.writer.write_byte(b'"')
+    }
+
+    /// Write the code points of a `str` escaped and encoded as UTF-8, without
+    /// the quotes.
+    #[inline(always)]
+    fn write_ucs_contents<T: Copy + Into<u32>>(&mut self, val: &[T]) -> Result<(), EncodeError> {
+        let escape_line_separators = self.escape_line_separators;
+        for chunk in val.chunks(UCS_CHUNK_LENGTH) {
+            self.writer.reserve_upper_bound(chunk.len() * 6);
+            let start = self.writer.cursor();
+            let mut ptr = start;
+            for &each in chunk {
+                let cp: u32 = each.into();
+                unsafe {
+                    if cp < 0x80 {
+                        let escape = ESCAPE[cp as usize];
+                        if escape == 0 {
+                            *ptr = cp as u8;
+                            ptr = ptr.add(1);
+                        } else if escape == UU {
+                            std::ptr::copy_nonoverlapping(
+                                [
+                                    b'\\',
+                                    b'u',
+                                    b'0',
+                                    b'0',
+                                    HEX_DIGITS[(cp >> 4) as usize],
+                                    HEX_DIGITS[(cp & 0xF) as usize],
+                                ]
+                                .as_ptr(),
+                                ptr,
+                                6,
+                            );
+                            ptr = ptr.add(6);
+                        } else {
+                            *ptr = b'\\';
+                            *ptr.add(1) = escape;
+                            ptr = ptr.add(2);
+                        }
+                    } else if cp < 0x800 {
+                        *ptr = (0xC0 | (cp >> 6)) as u8;
+                        *ptr.add(1) = (0x80 | (cp & 0x3F)) as u8;
+                        ptr = ptr.add(2);
+                    } else if cp < 0x10000 {
+                        if unlikely!((0xD800..0xE000).contains(&cp)) {
+                            return Err(EncodeError::from(SerializeError::InvalidStr));
+                        }
+                        if unlikely!(escape_line_separators && (cp == 0x2028 || cp == 0x2029)) {
+                            std::ptr::copy_nonoverlapping(
+                                [
+                                    b'\\',
+                                    b'u',
+                                    b'2',
+                                    b'0',
+                                    b'2',
+                                    HEX_DIGITS[(cp & 0xF) as usize],
+                                ]
+                                .as_ptr(),
+                                ptr,
+                                6,
+                            );
+                            ptr = ptr.add(6);
+                            continue;
+                        }
+                        *ptr = (0xE0 | (cp >> 12)) as u8;
+                        *ptr.add(1) = (0x80 | ((cp >> 6) & 0x3F)) as u8;
+                        *ptr.add(2) = (0x80 | (cp & 0x3F)) as u8;
+                        ptr = ptr.add(3);
+                    } else {
+                        *ptr = (0xF0 | (cp >> 18)) as u8;
+                        *ptr.add(1) = (0x80 | ((cp >> 12) & 0x3F)) as u8;
+                        *ptr.add(2) = (0x80 | ((cp >> 6) & 0x3F)) as u8;
+                        *ptr.add(3) = (0x80 | (cp & 0x3F)) as u8;
+                        ptr = ptr.add(4);
+                    }
+                }
+            }
+            self.writer
+                .advance(unsafe { ptr.offset_from(start) } as usize)?;
+        }
+        Ok(())
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn write_escaped_str(&mut self, val: &str) -> Result<(), EncodeError> {
+        self.writer.write_byte(b'"')?;
+        self.write_escaped_contents(val)?;
+        self.writer.write_byte(b'"')
+    }
+
+    fn write_escaped_contents(&mut self, val: &str) -> Result<(), EncodeError> {
+        let bytes = val.as_bytes();
+        let mut start = 0;
+        for (idx, &byte) in bytes.iter().enumerate() {
+            let escape = ESCAPE[byte as usize];
+            if escape == 0 {
+                if unlikely!(byte == 0xE2)
+                    && self.escape_line_separators
+                    && is_line_separator(&bytes[idx..])
+                {
+                    if start < idx {
+                        self.writer.write_bytes(&bytes[start..idx])?;
+                    }
+                    self.writer.write_bytes(&[
+                        b'\\',
+                        b'u',
+                        b'2',
+                        b'0',
+                        b'2',
+                        HEX_DIGITS[(bytes[idx + 2] - 0xA0) as usize],
+                    ])?;
+                    start = idx + 3;
+                }
+                continue;
+            }
+            if start < idx {
+                self.writer.write_bytes(&bytes[start..idx])?;
+            }
+            if escape == UU {
+                self.writer.write_bytes(&[
+                    b'\\',
+                    b'u',
+                    b'0',
+                    b'0',
+                    HEX_DIGITS[(byte >> 4) as usize],
+                    HEX_DIGITS[(byte & 0xF) as usize],
+                ])?;
+            } else {
+                self.writer.write_bytes(&[b'\\', escape])?;
+            }
+            start = idx + 1;
+        }
+        if start != bytes.len() {
+            self.writer.write_bytes(&bytes[start..])?;
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn begin_array(&mut self) -> Result<(), EncodeError> {
+        if unlikely!(self.include_keys.is_some()) {
+            self.include_stack.push(self.include_next);
+        }
+        if let Some(path) = self.path.as_mut() {
+            path.enter_array();
+        }
+        self.depth += 1;
+        self.has_value = false;
+        self.writer.write_byte(b'[')
+    }
+
+    #[inline(always)]
+    pub fn begin_array_value(&mut self, first: bool) -> Result<(), EncodeError> {
+        if unlikely!(self.include_keys.is_some()) {
+            // elements are written with the keys included of the array
+            self.include_next = *self.include_stack.last().unwrap();
+        }
+        if let Some(path) = self.path.as_mut() {
+            path.next_index(first);
+        }
+        if unlikely!(self.pretty) {
+            self.begin_pretty_value(first)
+        } else if !first {
+            self.write_item_separator()
+        } else {
+            Ok(())
+        }
+    }
+
+    #[inline(always)]
+    pub fn end_array(&mut self) -> Result<(), EncodeError> {
+        if unlikely!(self.include_keys.is_some()) {
+            self.include_stack.pop();
+        }
+        if let Some(path) = self.path.as_mut() {
+            path.leave();
+        }
+        self.depth -= 1;
+        if unlikely!(self.pretty) {
+            self.end_pretty_container()?;
+        }
+        self.has_value = true;
+        self.writer.write_byte(b']')
+    }
+
+    #[inline(always)]
+    pub fn begin_object(&mut self) -> Result<(), EncodeError> {
+        if unlikely!(self.include_keys.is_some()) {
+            self.include_stack.push(self.include_next);
+        }
+        if let Some(path) = self.path.as_mut() {
+            path.enter_object();
+        }
+        self.depth += 1;
+        self.has_value = false;
+        self.writer.write_byte(b'{')
+    }
+
+    /// Write the separator before an entry and the entry's key and colon.
+    #[inline(always)]
+    pub fn write_key(&mut self, key: &str, first: bool) -> Result<(), EncodeError> {
+        if let Some(redact) = &self.redact_keys {
+            self.redact_value = redact.contains_str(key);
+        }
+        if let Some(path) = self.path.as_mut() {
+            path.set_key(key);
+        }
+        self.begin_key(first)?;
+        self.write_str(key)?;
+        self.end_key()
+    }
+
+    /// Write the separator before an entry whose key is then written as a
+    /// string and followed by `end_key()`.
+    #[inline(always)]
+    pub fn begin_key(&mut self, first: bool) -> Result<(), EncodeError> {
+        if unlikely!(self.pretty) {
+            self.begin_pretty_value(first)
+        } else if !first {
+            self.write_item_separator()
+        } else {
+            Ok(())
+        }
+    }
+
+    #[inline(always)]
+    pub fn end_key(&mut self) -> Result<(), EncodeError> {
+        if let Some(separators) = &self.separators {
+            self.writer.write_bytes(separators.key())
+        } else if unlikely!(self.pretty) {
+            self.writer.write_bytes(b": ")
+        } else {
+            self.writer.write_byte(b':')
+        }
+    }
+
+    #[inline(always)]
+    fn write_item_separator(&mut self) -> Result<(), EncodeError> {
+        if let Some(separators) = &self.separators {
+            self.writer.write_bytes(separators.item())
+        } else {
+            self.writer.write_byte(b',')
+        }
+    }
+
+    #[inline(always)]
+    pub fn end_object(&mut self) -> Result<(), EncodeError> {
+        if unlikely!(self.include_keys.is_some()) {
+            self.include_stack.pop();
+        }
+        if let Some(path) = self.path.as_mut() {
+            path.leave();
+        }
+        self.depth -= 1;
+        if unlikely!(self.pretty) {
+            self.end_pretty_container()?;
+        }
+        self.has_value = true;
+        self.writer.write_byte(b'}')
+    }
+
+    #[inline(never)]
+    fn begin_pretty_value(&mut self, first: bool) -> Result<(), EncodeError> {
+        self.has_value = true;
+        if !first {
+            self.write_item_separator()?;
+        }
+        self.writer.write_byte(b'\n')?;
+        self.writer.write_indent(self.depth * 2)
+    }
+
+    #[inline(never)]
+    fn end_pretty_container(&mut self) -> Result<(), EncodeError> {
+        if self.has_value {
+            self.writer.write_byte(b'\n')?;
+            self.writer.write_indent(self.depth * 2)?;
+        }
+        Ok(())
+    }
+}
+
+static HEX_DIGITS: [u8; 16] = *b"0123456789abcdef";
+
+const BB: u8 = b'b'; // \x08
+const TT: u8 = b't'; // \x09
+const NN: u8 = b'n'; // \x0A
+const FF: u8 = b'f'; // \x0C
+const RR: u8 = b'r'; // \x0D
+const QU: u8 = b'"'; // \x22
+const BS: u8 = b'\\'; // \x5C
+const UU: u8 = b'u'; // \x00...\x1F except the ones above
+const __: u8 = 0;
+
+// Masks of `needs_escape()` selecting the low and high bit of each byte.
+const SWAR_ONES: u64 = 0x0101_0101_0101_0101;
+const SWAR_HIGH: u64 = 0x8080_8080_8080_8080;
+
+/// Whether any byte of `val` is a control character, quote, or backslash.
+/// Checks eight bytes at a time, which is exact for any byte because a borrow
+/// into the next byte only follows a byte that matched.
+#[inline(always)]
+fn needs_escape(val: &[u8]) -> bool {
+    let mut chunks = val.chunks_exact(8);
+    for chunk in &mut chunks {
+        let word = u64::from_le_bytes(chunk.try_into().unwrap());
+        let quote = word ^ (SWAR_ONES * b'"' as u64);
+        let backslash = word ^ (SWAR_ONES * b'\\' as u64);
+        let found = (word.wrapping_sub(SWAR_ONES * 0x20) & !word)
+            | (quote.wrapping_sub(SWAR_ONES) & !quote)
+            | (backslash.wrapping_sub(SWAR_ONES) & !backslash);
+        if found & SWAR_HIGH != 0 {
+            return true;
+        }
+    }
+    chunks.remainder().iter().any(|&b| ESCAPE[b as usize] != 0)
+}
+
+/// Notation in which `normalize_float()` rewrites a float.
+#[derive(Clone, Copy, PartialEq)]
+enum FloatNotation {
+    /// As ECMAScript's `Number.prototype.toString()`: positional for
+    /// magnitudes in [1e-6, 1e21), else with an exponent, e.g., "1e+21" or
+    /// "1.5e-7".
+    ECMAScript,
+    /// As Python's `float.__repr__()`: positional for magnitudes in
+    /// [1e-4, 1e16), else with an exponent of at least two digits, e.g.,
+    /// "1e+16" or "1.5e-05".
+    Repr,
+}
+
+/// Rewrite a float formatted by `ryu`, e.g., "1e16" or "0.0001", in
+/// `notation` with the same digits: positional or as one digit, an optional
+/// fraction, and a signed exponent. Integral values keep a ".0" fraction so
+/// that they are read back as floats.
+fn normalize_float(
+    val: &[u8],
+    buf: &mut arrayvec::ArrayVec<u8, FLOAT_NORMALIZED_LEN>,
+    notation: FloatNotation,
+) {
+    let (negative, val) = match val.split_first() {
+        Some((b'-', rest)) => (true, rest),
+        _ => (false, val),
+    };
+    let (mantissa, exponent) = match val.iter().position(|&b| b == b'e') {
+        Some(idx) => (
+            &val[..idx],
+            std::str::from_utf8(&val[idx + 1..])
+                .unwrap()
+                .parse::<i32>()
+                .unwrap(),
+        ),
+        None => (val, 0),
+    };
+    // significant digits and the position of the decimal point relative to
+    // the first of them
+    let mut digits = arrayvec::ArrayVec::<u8, 24>::new();
+    let mut point: i32 = 0;
+    let mut seen_point = false;
+    for &b in mantissa {
+        if b == b'.' {
+            seen_point = true;
+        } else if b == b'0' && digits.is_empty() {
+            if seen_point {
+                point -= 1;
+            }
+        } else {
+            digits.push(b);
+            if !seen_point {
+                point += 1;
+            }
+        }
+    }
+    while digits.last() == Some(&b'0') {
+        digits.pop();
+    }
+    if negative {
+        buf.push(b'-');
+    }
+    if digits.is_empty() {
+        buf.try_extend_from_slice(b"0.0").unwrap();
+        return;
+    }
+    let k = digits.len() as i32;
+    let n = point + exponent;
+    // bounds of `n` for which the notation is positional
+    let (min_n, max_n) = match notation {
+        FloatNotation::ECMAScript => (-6, 21),
+        FloatNotation::Repr => (-4, 16),
+    };
+    if k <= n && n <= max_n {
+        buf.try_extend_from_slice(&digits).unwrap();
+        for _ in 0..(n - k) {
+            buf.push(b'0');
+        }
+        buf.try_extend_from_slice(b".0").unwrap();
+    } else if 0 < n && n <= max_n {
+        buf.try_extend_from_slice(&digits[..n as usize]).unwrap();
+        buf.push(b'.');
+        buf.try_extend_from_slice(&digits[n as usize..]).unwrap();
+    } else if min_n < n && n <= 0 {
+        buf.try_extend_from_slice(b"0.").unwrap();
+        for _ in 0..-n {
+            buf.push(b'0');
+        }
+        buf.try_extend_from_slice(&digits).unwrap();
+    } else {
+        buf.push(digits[0]);
+        if k > 1 {
+            buf.push(b'.');
+            buf.try_extend_from_slice(&digits[1..]).unwrap();
+        }
+        buf.push(b'e');
+        buf.push(if n > 0 { b'+' } else { b'-' });
+        if notation == FloatNotation::Repr && (n - 1).abs() < 10 {
+            buf.push(b'0');
+        }
+        buf.try_extend_from_slice(itoa::Buffer::new().format((n - 1).abs()).as_bytes())
+            .unwrap();
+    }
+}
+
+/// Whether `val` begins with U+2028 or U+2029 encoded as UTF-8.
+#[inline(always)]
+fn is_line_separator(val: &[u8]) -> bool {
+    val.len() >= 3 && val[0] == 0xE2 && val[1] == 0x80 && (val[2] == 0xA8 || val[2] == 0xA9)
+}
+
+/// Whether `val` contains U+2028 or U+2029, which are valid in JSON strings
+/// but end a line in JavaScript before ES2019.
+#[cold]
+#[inline(never)]
+fn has_line_separator(val: &[u8]) -> bool {
+    val.iter()
+        .enumerate()
+        .any(|(idx, &b)| b == 0xE2 && is_line_separator(&val[idx..]))
+}
+
+// Lookup table of escape sequences. A value of b'x' at index i means that byte
+// i is escaped as "\x" in JSON. A value of 0 means that byte i is not escaped.
+static ESCAPE: [u8; 256] = [
+    //   1   2   3   4   5   6   7   8   9   A   B   C   D   E   F
+    UU, UU, UU, UU, UU, UU, UU, UU, BB, TT, NN, UU, FF, RR, UU, UU, // 0
+    UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, UU, // 1
+    __, __, QU, __, __, __, __, __, __, __, __, __, __, __, __, __, // 2
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 3
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 4
+    __, __, __, __, __, __, __, __, __, __, __, __, BS, __, __, __, // 5
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 6
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 7
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 8
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // 9
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // A
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // B
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // C
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // D
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // E
+    __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, __, // F
+];
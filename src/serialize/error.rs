@@ -4,20 +4,63 @@ use crate::error::INVALID_STR;
 use std::ffi::CStr;
 use std::ptr::NonNull;
 
+/// Error returned by `serialize()`. Each variant maps to a different
+/// exception type.
+pub enum EncodeError {
+    Message(String),
+    MaxSize(usize),
+    // An exception raised by Python code called while serializing, e.g.,
+    // `keys()` of a mapping, which is propagated as it is.
+    Raised,
+}
+
+impl std::fmt::Display for EncodeError {
+    #[cold]
+    #[cfg_attr(feature = "optimize", optimize(size))]
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EncodeError::Message(msg) => write!(f, "{}", msg),
+            EncodeError::MaxSize(limit) => {
+                write!(f, "Output exceeds max_size of {} bytes", limit)
+            }
+            EncodeError::Raised => write!(f, "An exception was raised"),
+        }
+    }
+}
+
+impl From<SerializeError> for EncodeError {
+    // The message is formatted immediately because it may refer to an object
+    // that does not outlive serialization, e.g., one returned by default.
+    #[cold]
+    #[inline(never)]
+    fn from(err: SerializeError) -> Self {
+        EncodeError::Message(err.to_string())
+    }
+}
+
 pub enum SerializeError {
+    DataclassAliasMustBeStr,
+    DataclassDuplicateKey(String),
     DatetimeLibraryUnsupported,
     DefaultRecursionLimit,
+    FloatNotFinite,
     Integer53Bits,
     Integer64Bits,
     InvalidStr,
+    InvalidBytesKey,
     KeyMustBeStr,
     RecursionLimit,
     TimeHasTzinfo,
     DictIntegerKey64Bit,
     DictKeyInvalidType,
+    // the key of the field, its value, and the name of its annotation
+    FieldTypeMismatch(&'static str, NonNull<pyo3_ffi::PyObject>, &'static str),
     NumpyMalformed,
     NumpyNotCContiguous,
     NumpyUnsupportedDatatype,
+    PairMustBeTuple,
+    TransformFailed,
+    TypedDictUndeclaredKey(&'static str),
     UnsupportedType(NonNull<pyo3_ffi::PyObject>),
 }
 
@@ -26,13 +69,21 @@ impl std::fmt::Display for SerializeError {
     #[cfg_attr(feature = "optimize", optimize(size))]
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
+            SerializeError::DataclassAliasMustBeStr => {
+                write!(f, "Dataclass field alias must be str")
+            }
+            SerializeError::DataclassDuplicateKey(ref key) => {
+                write!(f, "Dataclass has more than one field with key: {}", key)
+            }
             SerializeError::DatetimeLibraryUnsupported => write!(f, "datetime's timezone library is not supported: use datetime.timezone.utc, pendulum, pytz, or dateutil"),
             SerializeError::DefaultRecursionLimit => {
                 write!(f, "default serializer exceeds recursion limit")
             }
+            SerializeError::FloatNotFinite => write!(f, "Float must be finite"),
             SerializeError::Integer53Bits => write!(f, "Integer exceeds 53-bit range"),
             SerializeError::Integer64Bits => write!(f, "Integer exceeds 64-bit range"),
             SerializeError::InvalidStr => write!(f, "{}", INVALID_STR),
+            SerializeError::InvalidBytesKey => write!(f, "Dict bytes key must be valid UTF-8"),
             SerializeError::KeyMustBeStr => write!(f, "Dict key must be str"),
             SerializeError::RecursionLimit => write!(f, "Recursion limit reached"),
             SerializeError::TimeHasTzinfo => write!(f, "datetime.time must not have tzinfo set"),
@@ -42,6 +93,14 @@ impl std::fmt::Display for SerializeError {
             SerializeError::DictKeyInvalidType => {
                 write!(f, "Dict key must a type serializable with OPT_NON_STR_KEYS")
             }
+            SerializeError::FieldTypeMismatch(key, ptr, expected) => {
+                let name = unsafe { CStr::from_ptr((*ob_type!(ptr.as_ptr())).tp_name).to_string_lossy() };
+                write!(
+                    f,
+                    "Field {} must be {}, not {}",
+                    key, expected, name
+                )
+            }
             SerializeError::NumpyMalformed => write!(f, "numpy array is malformed"),
             SerializeError::NumpyNotCContiguous => write!(
                 f,
@@ -50,6 +109,13 @@ impl std::fmt::Display for SerializeError {
             SerializeError::NumpyUnsupportedDatatype => {
                 write!(f, "unsupported datatype in numpy array")
             }
+            SerializeError::PairMustBeTuple => {
+                write!(f, "Pair must be a tuple of a key and a value")
+            }
+            SerializeError::TransformFailed => write!(f, "transform raised an exception"),
+            SerializeError::TypedDictUndeclaredKey(key) => {
+                write!(f, "Dict key is not declared on the TypedDict: {}", key)
+            }
             SerializeError::UnsupportedType(ptr) => {
                 let name = unsafe { CStr::from_ptr((*ob_type!(ptr.as_ptr())).tp_name).to_string_lossy() };
                 write!(f, "Type is not JSON serializable: {}", name)
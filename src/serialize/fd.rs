@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::deserialize::{new_os_error, raise_os_error};
+use crate::ffi::{PyBytes_AS_STRING, PyBytes_GET_SIZE};
+use crate::opt::*;
+use crate::serialize::error::*;
+use crate::serialize::serializer::*;
+use std::ptr::NonNull;
+
+/// Lines are written once at least this many bytes of them are serialized.
+const LINES_FLUSH_SIZE: usize = 64 * 1024;
+
+/// The most buffers given to one call of `writev()`, which is `IOV_MAX` on
+/// Linux and macOS.
+const MAX_BUFFERS: usize = 1024;
+
+/// Serialize `obj` and write it to the file descriptor `fd`, or, if `lines`,
+/// each item of the iterable `obj` followed by a newline, and return the
+/// number of bytes written. An error of `None` means an exception was
+/// raised, e.g., writing or by the iterator. The lines of the items before
+/// one that fails to serialize are written. `args.max_size` limits the
+/// output, or each line if `lines`.
+pub fn dump_fd(
+    obj: *mut pyo3_ffi::PyObject,
+    fd: *mut pyo3_ffi::PyObject,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    opts: Opt,
+    args: &DumpsArgs,
+    lines: bool,
+) -> Result<usize, Option<EncodeError>> {
+    let mut out = FdWriter::new(fd)?;
+    if !lines {
+        let bytes = serialize(obj, default, opts, args.clone_options())?;
+        out.push(bytes);
+        return out.flush().map(|_| out.written);
+    }
+    let iter = ffi!(PyObject_GetIter(obj));
+    if iter.is_null() {
+        return Err(None);
+    }
+    let res = out.write_lines(iter, default, opts | APPEND_NEWLINE, args);
+    ffi!(Py_DECREF(iter));
+    res.map(|_| out.written)
+}
+
+/// Serialized output not yet written to a file descriptor.
+struct FdWriter {
+    file: std::mem::ManuallyDrop<std::fs::File>,
+    // strong references to `bytes` objects
+    pending: Vec<NonNull<pyo3_ffi::PyObject>>,
+    pending_len: usize,
+    written: usize,
+}
+
+impl Drop for FdWriter {
+    fn drop(&mut self) {
+        for bytes in self.pending.drain(..) {
+            ffi!(Py_DECREF(bytes.as_ptr()));
+        }
+    }
+}
+
+impl FdWriter {
+    /// Borrow the file descriptor of `fd`, an `int` or an object with a
+    /// `fileno()` method, which must outlive the writer.
+    #[cfg(unix)]
+    fn new(fd: *mut pyo3_ffi::PyObject) -> Result<Self, Option<EncodeError>> {
+        use std::os::unix::io::FromRawFd;
+
+        let fd = ffi!(PyObject_AsFileDescriptor(fd));
+        if fd == -1 {
+            return Err(None);
+        }
+        Ok(FdWriter {
+            file: std::mem::ManuallyDrop::new(unsafe { std::fs::File::from_raw_fd(fd) }),
+            pending: Vec::new(),
+            pending_len: 0,
+            written: 0,
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn new(_fd: *mut pyo3_ffi::PyObject) -> Result<Self, Option<EncodeError>> {
+        Err(Some(EncodeError::Message(String::from(
+            "Output file descriptors are not supported on this platform",
+        ))))
+    }
+
+    fn push(&mut self, bytes: NonNull<pyo3_ffi::PyObject>) {
+        self.pending_len += unsafe { PyBytes_GET_SIZE(bytes.as_ptr()) } as usize;
+        self.pending.push(bytes);
+    }
+
+    fn write_lines(
+        &mut self,
+        iter: *mut pyo3_ffi::PyObject,
+        default: Option<NonNull<pyo3_ffi::PyObject>>,
+        opts: Opt,
+        args: &DumpsArgs,
+    ) -> Result<(), Option<EncodeError>> {
+        loop {
+            let item = ffi!(PyIter_Next(iter));
+            if item.is_null() {
+                if ffi!(PyErr_Occurred()).is_null() {
+                    return self.flush();
+                }
+                // the exception is raised once the lines before it are
+                // written, unless writing raises another
+                let mut ptype = std::ptr::null_mut();
+                let mut pvalue = std::ptr::null_mut();
+                let mut ptraceback = std::ptr::null_mut();
+                ffi!(PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback));
+                if self.flush().is_ok() {
+                    ffi!(PyErr_Restore(ptype, pvalue, ptraceback));
+                } else {
+                    unsafe {
+                        pyo3_ffi::Py_XDECREF(ptype);
+                        pyo3_ffi::Py_XDECREF(pvalue);
+                        pyo3_ffi::Py_XDECREF(ptraceback);
+                    }
+                }
+                return Err(None);
+            }
+            let res = serialize(item, default, opts, args.clone_options());
+            ffi!(Py_DECREF(item));
+            match res {
+                Ok(bytes) => self.push(bytes),
+                Err(err) => {
+                    self.flush()?;
+                    return Err(Some(err));
+                }
+            }
+            if self.pending.len() == MAX_BUFFERS || self.pending_len >= LINES_FLUSH_SIZE {
+                self.flush()?;
+            }
+        }
+    }
+
+    /// Write the pending output with the GIL released, retrying partial
+    /// writes and those interrupted by a signal whose handler does not raise.
+    fn flush(&mut self) -> Result<(), Option<EncodeError>> {
+        use std::io::{IoSlice, Write};
+
+        let buffers: Vec<&[u8]> = self
+            .pending
+            .iter()
+            .map(|bytes| unsafe {
+                std::slice::from_raw_parts(
+                    PyBytes_AS_STRING(bytes.as_ptr()) as *const u8,
+                    PyBytes_GET_SIZE(bytes.as_ptr()) as usize,
+                )
+            })
+            .collect();
+        let mut idx = 0;
+        let mut offset = 0;
+        let mut res = Ok(());
+        while idx < buffers.len() {
+            let end = std::cmp::min(idx + MAX_BUFFERS, buffers.len());
+            let mut slices: Vec<IoSlice> = Vec::with_capacity(end - idx);
+            slices.push(IoSlice::new(&buffers[idx][offset..]));
+            slices.extend(buffers[idx + 1..end].iter().map(|buf| IoSlice::new(buf)));
+            let tstate = ffi!(PyEval_SaveThread());
+            let written = self.file.write_vectored(&slices);
+            ffi!(PyEval_RestoreThread(tstate));
+            match written {
+                Ok(0) => {
+                    raise_os_error(std::io::Error::from(std::io::ErrorKind::WriteZero));
+                    res = Err(None);
+                    break;
+                }
+                Ok(mut len) => {
+                    self.written += len;
+                    while idx < buffers.len() && len >= buffers[idx].len() - offset {
+                        len -= buffers[idx].len() - offset;
+                        idx += 1;
+                        offset = 0;
+                    }
+                    offset += len;
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {
+                    if ffi!(PyErr_CheckSignals()) == -1 {
+                        res = Err(None);
+                        break;
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                    raise_blocking_error(err, self.written);
+                    res = Err(None);
+                    break;
+                }
+                Err(err) => {
+                    raise_os_error(err);
+                    res = Err(None);
+                    break;
+                }
+            }
+        }
+        for bytes in self.pending.drain(..) {
+            ffi!(Py_DECREF(bytes.as_ptr()));
+        }
+        self.pending_len = 0;
+        res
+    }
+}
+
+/// Raise `BlockingIOError` for `err`, with the `written` bytes written before
+/// the file descriptor was full as its `characters_written`, as a write to a
+/// non-blocking file does.
+#[cold]
+fn raise_blocking_error(err: std::io::Error, written: usize) {
+    let exc = new_os_error(err);
+    if exc.is_null() {
+        return;
+    }
+    unsafe {
+        let count = pyo3_ffi::PyLong_FromSize_t(written);
+        let res = pyo3_ffi::PyObject_SetAttrString(
+            exc,
+            "characters_written\0".as_ptr() as *const std::os::raw::c_char,
+            count,
+        );
+        pyo3_ffi::Py_DECREF(count);
+        if res == 0 {
+            pyo3_ffi::PyErr_SetObject(ob_type!(exc) as *mut pyo3_ffi::PyObject, exc);
+        }
+        pyo3_ffi::Py_DECREF(exc);
+    }
+}
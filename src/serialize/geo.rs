@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::opt::*;
+use crate::serialize::encode::*;
+use crate::serialize::error::*;
+use crate::serialize::serializer::*;
+use crate::typeref::*;
+use std::ptr::NonNull;
+
+pub struct GeoInterfaceSerializer {
+    ptr: *mut pyo3_ffi::PyObject,
+    opts: Opt,
+    default_calls: u8,
+    recursion: u32,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+}
+
+impl GeoInterfaceSerializer {
+    pub fn new(
+        ptr: *mut pyo3_ffi::PyObject,
+        opts: Opt,
+        default_calls: u8,
+        recursion: u32,
+        default: Option<NonNull<pyo3_ffi::PyObject>>,
+    ) -> Self {
+        GeoInterfaceSerializer {
+            ptr: ptr,
+            opts: opts,
+            default_calls: default_calls,
+            recursion: recursion,
+            default: default,
+        }
+    }
+}
+
+impl Encode for GeoInterfaceSerializer {
+    #[inline(never)]
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        // __geo_interface__ is typically a property building a new mapping.
+        let value = ffi!(PyObject_GetAttr(self.ptr, GEO_INTERFACE_STR));
+        if unlikely!(value.is_null()) {
+            err!(SerializeError::UnsupportedType(nonnull!(self.ptr)))
+        }
+        let res = PyObjectSerializer::new(
+            value,
+            self.opts,
+            self.default_calls,
+            self.recursion + 1,
+            self.default,
+        )
+        .encode(enc);
+        ffi!(Py_DECREF(value));
+        res
+    }
+}
@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::typeref::*;
+use crate::unicode::unicode_to_str;
+
+/// Keys given to `dumps(include_keys=...)`, as a tree of the keys of each
+/// object to write. A key is either a top-level key or a dotted path such as
+/// `"a.b"`, which writes only `"b"` of the object under `"a"`.
+pub struct IncludeKeys {
+    // The keys of each node, the first being the top level, and the node
+    // applying to the value of each or `None` if it is written whole.
+    nodes: Vec<Vec<(String, Option<usize>)>>,
+}
+
+impl IncludeKeys {
+    /// Collect the paths from an iterable of `str` other than a `str` itself,
+    /// or return `None` if `ptr` is not one or a path has an empty key.
+    #[cold]
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Option<Self> {
+        if unsafe { ob_type!(ptr) == STR_TYPE } {
+            return None;
+        }
+        let iter = ffi!(PyObject_GetIter(ptr));
+        if iter.is_null() {
+            ffi!(PyErr_Clear());
+            return None;
+        }
+        let mut include = IncludeKeys {
+            nodes: vec![Vec::new()],
+        };
+        let mut valid = true;
+        loop {
+            let each = ffi!(PyIter_Next(iter));
+            if each.is_null() {
+                break;
+            }
+            let path = if unsafe { ob_type!(each) == STR_TYPE } {
+                unicode_to_str(each)
+            } else {
+                None
+            };
+            match path {
+                Some(path) if !path.split('.').any(|key| key.is_empty()) => {
+                    include.insert(path);
+                    ffi!(Py_DECREF(each));
+                }
+                _ => {
+                    ffi!(Py_DECREF(each));
+                    valid = false;
+                    break;
+                }
+            }
+        }
+        ffi!(Py_DECREF(iter));
+        if !valid || !ffi!(PyErr_Occurred()).is_null() {
+            ffi!(PyErr_Clear());
+            return None;
+        }
+        Some(include)
+    }
+
+    fn insert(&mut self, path: &str) {
+        let mut node = 0;
+        let mut keys = path.split('.').peekable();
+        while let Some(key) = keys.next() {
+            let last = keys.peek().is_none();
+            match self.nodes[node].iter().position(|(each, _)| each == key) {
+                Some(pos) => match self.nodes[node][pos].1 {
+                    // a shorter path already writes the value whole
+                    None => return,
+                    Some(_) if last => {
+                        self.nodes[node][pos].1 = None;
+                        return;
+                    }
+                    Some(child) => node = child,
+                },
+                None if last => {
+                    self.nodes[node].push((key.to_string(), None));
+                    return;
+                }
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Vec::new());
+                    self.nodes[node].push((key.to_string(), Some(child)));
+                    node = child;
+                }
+            }
+        }
+    }
+
+    /// If `key` is written in an object to which `node` applies, return the
+    /// node applying to its value.
+    #[inline]
+    pub fn get(&self, node: usize, key: &str) -> Option<Option<usize>> {
+        self.nodes[node]
+            .iter()
+            .find(|(each, _)| each == key)
+            .map(|(_, child)| *child)
+    }
+}
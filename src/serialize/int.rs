@@ -1,7 +1,9 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+use crate::ffi::PyLong_AsSmallValue;
+use crate::serialize::encode::*;
 use crate::serialize::error::*;
-use serde::ser::{Serialize, Serializer};
+use crate::typeref::PYLONG_SMALL_VALUE;
 
 // https://tools.ietf.org/html/rfc7159#section-6
 // "[-(2**53)+1, (2**53)-1]"
@@ -19,21 +21,23 @@ impl IntSerializer {
     }
 }
 
-impl Serialize for IntSerializer {
+impl Encode for IntSerializer {
     #[inline]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        if unsafe { PYLONG_SMALL_VALUE } {
+            if let Some(val) = unsafe { PyLong_AsSmallValue(self.ptr) } {
+                return enc.write_int(val);
+            }
+        }
         let val = ffi!(PyLong_AsLongLong(self.ptr));
         if val == -1 {
             if unlikely!(!ffi!(PyErr_Occurred()).is_null()) {
-                UIntSerializer::new(self.ptr).serialize(serializer)
+                UIntSerializer::new(self.ptr).encode(enc)
             } else {
-                serializer.serialize_i64(val)
+                enc.write_int(val)
             }
         } else {
-            serializer.serialize_i64(val)
+            enc.write_int(val)
         }
     }
 }
@@ -49,22 +53,20 @@ impl UIntSerializer {
     }
 }
 
-impl Serialize for UIntSerializer {
+impl Encode for UIntSerializer {
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         ffi!(PyErr_Clear());
         let val = ffi!(PyLong_AsUnsignedLongLong(self.ptr));
         if unlikely!(val == u64::MAX) {
             if ffi!(PyErr_Occurred()).is_null() {
-                serializer.serialize_u64(val)
+                enc.write_int(val)
             } else {
+                ffi!(PyErr_Clear());
                 err!(SerializeError::Integer64Bits)
             }
         } else {
-            serializer.serialize_u64(val)
+            enc.write_int(val)
         }
     }
 }
@@ -80,24 +82,22 @@ impl Int53Serializer {
     }
 }
 
-impl Serialize for Int53Serializer {
+impl Encode for Int53Serializer {
     #[cold]
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         let val = ffi!(PyLong_AsLongLong(self.ptr));
         if unlikely!(val == -1) {
             if ffi!(PyErr_Occurred()).is_null() {
-                serializer.serialize_i64(val)
+                enc.write_int(val)
             } else {
+                ffi!(PyErr_Clear());
                 err!(SerializeError::Integer53Bits)
             }
         } else if !(STRICT_INT_MIN..=STRICT_INT_MAX).contains(&val) {
             err!(SerializeError::Integer53Bits)
         } else {
-            serializer.serialize_i64(val)
+            enc.write_int(val)
         }
     }
 }
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::opt::*;
+use crate::serialize::encode::*;
+use crate::serialize::error::*;
+use crate::serialize::serializer::*;
+use crate::serialize::writer::BytesWriter;
+use std::ptr::NonNull;
+
+/// Serialize `obj` into the writable buffer of `buffer` starting at `offset`
+/// and return the number of bytes written. An error of `None` means an
+/// exception was raised, e.g., because `buffer` is not writable. Output that
+/// does not fit is `EncodeError::MaxSize` of the space after `offset`, or of
+/// `args.max_size` if it is less, and the buffer is then left unmodified.
+pub fn dumps_into(
+    obj: *mut pyo3_ffi::PyObject,
+    buffer: *mut pyo3_ffi::PyObject,
+    offset: usize,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    opts: Opt,
+    args: DumpsArgs,
+) -> Result<usize, Option<EncodeError>> {
+    // the buffer is held while serializing, so that `default` cannot resize
+    // it, e.g., a `bytearray`
+    let mut view = std::mem::MaybeUninit::<pyo3_ffi::Py_buffer>::uninit();
+    if ffi!(PyObject_GetBuffer(
+        buffer,
+        view.as_mut_ptr(),
+        pyo3_ffi::PyBUF_CONTIG
+    )) != 0
+    {
+        return Err(None);
+    }
+    let mut view = unsafe { view.assume_init() };
+    let res = write_into(obj, &view, offset, default, opts, args);
+    unsafe { pyo3_ffi::PyBuffer_Release(&mut view) };
+    res
+}
+
+fn write_into(
+    obj: *mut pyo3_ffi::PyObject,
+    view: &pyo3_ffi::Py_buffer,
+    offset: usize,
+    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    opts: Opt,
+    args: DumpsArgs,
+) -> Result<usize, Option<EncodeError>> {
+    let len = view.len as usize;
+    if unlikely!(offset > len) {
+        return Err(Some(EncodeError::Message(String::from(
+            "offset exceeds the length of the buffer",
+        ))));
+    }
+    let limit = std::cmp::min(len - offset, args.max_size.unwrap_or(usize::MAX));
+    let mut enc = Encoder::new(BytesWriter::with_max_size(limit), opts, args);
+    let mut res = PyObjectSerializer::new(obj, opts, 0, 0, default).encode(&mut enc);
+    if res.is_ok() && opts & APPEND_NEWLINE != 0 {
+        res = enc.write_raw(b"\n");
+    }
+    let mut writer = enc.into_inner();
+    let written = match res {
+        Ok(_) => {
+            let output = writer.written_since(0);
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    output.as_ptr(),
+                    (view.buf as *mut u8).add(offset),
+                    output.len(),
+                );
+            }
+            Ok(output.len())
+        }
+        Err(err) => Err(Some(err)),
+    };
+    writer.discard();
+    written
+}
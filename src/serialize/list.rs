@@ -1,63 +1,39 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use crate::opt::*;
-use crate::serialize::serializer::*;
-
-use serde::ser::{Serialize, SerializeSeq, Serializer};
-use std::ptr::NonNull;
+use crate::serialize::encode::*;
+use crate::serialize::error::EncodeError;
 
+/// The elements of a `list` being written, each returned to
+/// `PyObjectSerializer` to encode.
 pub struct ListSerializer {
     ptr: *mut pyo3_ffi::PyObject,
-    opts: Opt,
-    default_calls: u8,
-    recursion: u8,
-    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    idx: usize,
 }
 
 impl ListSerializer {
-    pub fn new(
-        ptr: *mut pyo3_ffi::PyObject,
-        opts: Opt,
-        default_calls: u8,
-        recursion: u8,
-        default: Option<NonNull<pyo3_ffi::PyObject>>,
-    ) -> Self {
-        ListSerializer {
-            ptr: ptr,
-            opts: opts,
-            default_calls: default_calls,
-            recursion: recursion,
-            default: default,
-        }
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Self {
+        ListSerializer { ptr: ptr, idx: 0 }
     }
-}
 
-impl Serialize for ListSerializer {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        if ffi!(Py_SIZE(self.ptr)) == 0 {
-            serializer.serialize_seq(Some(0)).unwrap().end()
-        } else {
-            let mut seq = serializer.serialize_seq(None).unwrap();
-            let slice: &[*mut pyo3_ffi::PyObject] = unsafe {
-                std::slice::from_raw_parts(
-                    (*(self.ptr as *mut pyo3_ffi::PyListObject)).ob_item,
-                    ffi!(Py_SIZE(self.ptr)) as usize,
-                )
-            };
-            for &each in slice {
-                let value = PyObjectSerializer::new(
-                    each,
-                    self.opts,
-                    self.default_calls,
-                    self.recursion + 1,
-                    self.default,
-                );
-                seq.serialize_element(&value)?;
-            }
-            seq.end()
+    /// Write the separator before the next element and return it or, after
+    /// the last element, end the array.
+    #[inline(always)]
+    pub fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        // the length is read each time as `default` may modify the list
+        if self.idx >= ffi!(Py_SIZE(self.ptr)) as usize {
+            enc.end_array()?;
+            return Ok(None);
         }
+        enc.begin_array_value(self.idx == 0)?;
+        let value = unsafe {
+            *(*(self.ptr as *mut pyo3_ffi::PyListObject))
+                .ob_item
+                .add(self.idx)
+        };
+        self.idx += 1;
+        Ok(Some(value))
     }
 }
@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::opt::*;
+use crate::serialize::dict::DictNonStrKey;
+use crate::serialize::encode::*;
+use crate::serialize::error::*;
+use crate::typeref::{GETALL_STR, MAPPING_TYPE, TUPLE_TYPE};
+use compact_str::CompactString;
+use smallvec::SmallVec;
+use std::collections::HashSet;
+
+/// Whether `obj` is an instance of `collections.abc.Mapping`, including by
+/// registration.
+pub fn is_mapping(obj: *mut pyo3_ffi::PyObject) -> bool {
+    match ffi!(PyObject_IsInstance(obj, unsafe { MAPPING_TYPE.as_ptr() })) {
+        1 => true,
+        0 => false,
+        _ => {
+            ffi!(PyErr_Clear());
+            false
+        }
+    }
+}
+
+/// Whether `obj` is a `collections.abc.Mapping` that may map a key to
+/// several values, as `multidict.MultiDict` does, by having `getall()`.
+pub fn is_multidict(obj: *mut pyo3_ffi::PyObject) -> bool {
+    ffi!(PyObject_HasAttr(obj, unsafe { GETALL_STR })) == 1 && is_mapping(obj)
+}
+
+/// The entries of a `collections.abc.Mapping` other than a `dict` being
+/// written for `OPT_SERIALIZE_MAPPING` or `OPT_SERIALIZE_MULTIDICT`. Entries
+/// are read before any is written and keys are converted as those of a
+/// `dict` are.
+pub struct MappingSerializer {
+    // `__getitem__()` may return a new object, so each value is a strong
+    // reference.
+    items: SmallVec<[(CompactString, *mut pyo3_ffi::PyObject); 8]>,
+    idx: usize,
+    first: bool,
+}
+
+impl Drop for MappingSerializer {
+    fn drop(&mut self) {
+        for (_, value) in self.items.iter() {
+            ffi!(Py_DECREF(*value));
+        }
+    }
+}
+
+impl MappingSerializer {
+    /// Read the entries using `keys()` and `__getitem__()`.
+    #[cold]
+    #[inline(never)]
+    pub fn new(ptr: *mut pyo3_ffi::PyObject, opts: Opt) -> Result<Self, EncodeError> {
+        let keys = ffi!(PyMapping_Keys(ptr));
+        if unlikely!(keys.is_null()) {
+            return Err(EncodeError::Raised);
+        }
+        let len = ffi!(Py_SIZE(keys)) as usize;
+        let mut mapping = MappingSerializer {
+            items: SmallVec::with_capacity(len),
+            idx: 0,
+            first: true,
+        };
+        let opts = opts & NOT_PASSTHROUGH;
+        for i in 0..len {
+            let key = ffi!(PyList_GET_ITEM(keys, i as isize));
+            let value = ffi!(PyObject_GetItem(ptr, key));
+            if unlikely!(value.is_null()) {
+                ffi!(Py_DECREF(keys));
+                return Err(EncodeError::Raised);
+            }
+            if let Err(err) = mapping.push(key, value, opts) {
+                ffi!(Py_DECREF(keys));
+                return Err(err);
+            }
+        }
+        ffi!(Py_DECREF(keys));
+        mapping.sort(opts);
+        Ok(mapping)
+    }
+
+    /// Read the entries of a multidict using `items()`, keeping each value
+    /// of a repeated key for `OPT_SERIALIZE_MULTIDICT` or otherwise only the
+    /// first, which is the one `__getitem__()` returns.
+    #[cold]
+    #[inline(never)]
+    pub fn with_items(ptr: *mut pyo3_ffi::PyObject, opts: Opt) -> Result<Self, EncodeError> {
+        let items = ffi!(PyMapping_Items(ptr));
+        if unlikely!(items.is_null()) {
+            return Err(EncodeError::Raised);
+        }
+        let len = ffi!(Py_SIZE(items)) as usize;
+        let mut mapping = MappingSerializer {
+            items: SmallVec::with_capacity(len),
+            idx: 0,
+            first: true,
+        };
+        let repeated = opts & SERIALIZE_MULTIDICT != 0;
+        let mut seen: HashSet<CompactString> = HashSet::new();
+        let opts = opts & NOT_PASSTHROUGH;
+        for i in 0..len {
+            let pair = ffi!(PyList_GET_ITEM(items, i as isize));
+            if unlikely!(unsafe { ob_type!(pair) != TUPLE_TYPE } || ffi!(Py_SIZE(pair)) != 2) {
+                ffi!(Py_DECREF(items));
+                err!(SerializeError::UnsupportedType(nonnull!(ptr)))
+            }
+            let value = ffi!(PyTuple_GET_ITEM(pair, 1));
+            ffi!(Py_INCREF(value));
+            if let Err(err) = mapping.push(ffi!(PyTuple_GET_ITEM(pair, 0)), value, opts) {
+                ffi!(Py_DECREF(items));
+                return Err(err);
+            }
+            if !repeated && !seen.insert(mapping.items.last().unwrap().0.clone()) {
+                let (_, value) = mapping.items.pop().unwrap();
+                ffi!(Py_DECREF(value));
+            }
+        }
+        ffi!(Py_DECREF(items));
+        mapping.sort(opts);
+        Ok(mapping)
+    }
+
+    /// Read the `(key, value)` tuples of an iterable for `OPT_SERIALIZE_PAIRS`
+    /// with `OPT_SORT_KEYS`, keeping each value of a repeated key.
+    #[cold]
+    #[inline(never)]
+    pub fn with_pairs(ptr: *mut pyo3_ffi::PyObject, opts: Opt) -> Result<Self, EncodeError> {
+        let iter = ffi!(PyObject_GetIter(ptr));
+        if unlikely!(iter.is_null()) {
+            return Err(EncodeError::Raised);
+        }
+        let mut mapping = MappingSerializer {
+            items: SmallVec::new(),
+            idx: 0,
+            first: true,
+        };
+        let opts = opts & NOT_PASSTHROUGH;
+        loop {
+            let pair = ffi!(PyIter_Next(iter));
+            if pair.is_null() {
+                ffi!(Py_DECREF(iter));
+                if unlikely!(!ffi!(PyErr_Occurred()).is_null()) {
+                    return Err(EncodeError::Raised);
+                }
+                break;
+            }
+            if unlikely!(unsafe { ob_type!(pair) != TUPLE_TYPE } || ffi!(Py_SIZE(pair)) != 2) {
+                ffi!(Py_DECREF(pair));
+                ffi!(Py_DECREF(iter));
+                err!(SerializeError::PairMustBeTuple)
+            }
+            let value = ffi!(PyTuple_GET_ITEM(pair, 1));
+            ffi!(Py_INCREF(value));
+            let res = mapping.push(ffi!(PyTuple_GET_ITEM(pair, 0)), value, opts);
+            ffi!(Py_DECREF(pair));
+            if let Err(err) = res {
+                ffi!(Py_DECREF(iter));
+                return Err(err);
+            }
+        }
+        mapping.sort(opts);
+        Ok(mapping)
+    }
+
+    /// Add the entry of `key` and the strong reference `value`, which is
+    /// released if `key` cannot be converted.
+    fn push(
+        &mut self,
+        key: *mut pyo3_ffi::PyObject,
+        value: *mut pyo3_ffi::PyObject,
+        opts: Opt,
+    ) -> Result<(), EncodeError> {
+        match DictNonStrKey::key_to_string(key, opts) {
+            Ok(key_as_str) => {
+                self.items.push((key_as_str, value));
+                Ok(())
+            }
+            Err(err) => {
+                ffi!(Py_DECREF(value));
+                Err(err)
+            }
+        }
+    }
+
+    fn sort(&mut self, opts: Opt) {
+        // stable so that the values of a repeated key keep their order
+        if opts & SORT_KEYS != 0 {
+            self.items.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+    }
+
+    /// Write the separator and key before the next value and return it or,
+    /// after the last entry, end the object.
+    #[inline(never)]
+    pub fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        while self.idx < self.items.len() {
+            let (key, value) = &self.items[self.idx];
+            let key = str_from_slice!(key.as_ptr(), key.len());
+            let value = *value;
+            self.idx += 1;
+            if unlikely!(enc.is_including()) && !enc.includes_key(key) {
+                continue;
+            }
+            enc.write_key(key, self.first)?;
+            self.first = false;
+            return Ok(Some(value));
+        }
+        enc.end_object()?;
+        Ok(None)
+    }
+}
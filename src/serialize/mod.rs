@@ -1,20 +1,48 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+mod async_writer;
+mod compile;
 mod dataclass;
+mod dataclass_meta;
 mod datetime;
 #[macro_use]
 mod datetimelike;
 mod default;
 mod dict;
+mod encode;
 mod error;
+mod fd;
+mod geo;
+mod include;
 mod int;
+mod into;
 mod list;
+mod mapping;
 mod numpy;
+mod obtype_cache;
+mod pairs;
+mod path;
 mod pyenum;
+mod redact;
+mod separators;
+mod sequence;
 mod serializer;
+mod shared_keys;
+mod stats;
 mod str;
 mod tuple;
 mod uuid;
 mod writer;
 
-pub use serializer::serialize;
+pub use async_writer::{create_async_dump_type, create_async_writer_type};
+pub use compile::{compile, create_compiled_serializer_type};
+pub use dataclass_meta::{field_options, option_value, DataclassMetaMap, DATACLASS_META};
+pub use error::EncodeError;
+pub use fd::dump_fd;
+pub use include::IncludeKeys;
+pub use into::dumps_into;
+pub use obtype_cache::{ObTypeMap, OBTYPE_CACHE};
+pub use redact::RedactKeys;
+pub use separators::Separators;
+pub use serializer::{serialize, DumpsArgs};
+pub use shared_keys::{SharedKeysMap, SHARED_KEYS};
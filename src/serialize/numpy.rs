@@ -1,11 +1,11 @@
 use crate::opt::*;
 use crate::serialize::datetimelike::{DateTimeBuffer, DateTimeError, DateTimeLike, Offset};
 use crate::serialize::default::*;
+use crate::serialize::encode::*;
 use crate::serialize::error::*;
 use crate::typeref::{ARRAY_STRUCT_STR, DESCR_STR, DTYPE_STR, NUMPY_TYPES};
 use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
 use pyo3_ffi::*;
-use serde::ser::{self, Serialize, SerializeSeq, Serializer};
 use std::convert::TryInto;
 use std::fmt;
 use std::ops::DerefMut;
@@ -16,7 +16,7 @@ pub struct NumpySerializer {
     ptr: *mut pyo3_ffi::PyObject,
     opts: Opt,
     default_calls: u8,
-    recursion: u8,
+    recursion: u32,
     default: Option<NonNull<pyo3_ffi::PyObject>>,
 }
 
@@ -25,7 +25,7 @@ impl NumpySerializer {
         ptr: *mut pyo3_ffi::PyObject,
         opts: Opt,
         default_calls: u8,
-        recursion: u8,
+        recursion: u32,
         default: Option<NonNull<pyo3_ffi::PyObject>>,
     ) -> Self {
         NumpySerializer {
@@ -38,15 +38,12 @@ impl NumpySerializer {
     }
 }
 
-impl<'p> Serialize for NumpySerializer {
+impl<'p> Encode for NumpySerializer {
     #[cold]
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         match NumpyArray::new(self.ptr, self.opts) {
-            Ok(val) => val.serialize(serializer),
+            Ok(val) => val.encode(enc),
             Err(PyArrayError::Malformed) => err!(SerializeError::NumpyMalformed),
             Err(PyArrayError::NotContiguous) | Err(PyArrayError::UnsupportedDataType)
                 if self.default.is_some() =>
@@ -58,7 +55,7 @@ impl<'p> Serialize for NumpySerializer {
                     self.recursion,
                     self.default,
                 )
-                .serialize(serializer)
+                .encode(enc)
             }
             Err(PyArrayError::NotContiguous) => {
                 err!(SerializeError::NumpyNotCContiguous)
@@ -290,65 +287,63 @@ impl Drop for NumpyArray {
     }
 }
 
-impl Serialize for NumpyArray {
+impl Encode for NumpyArray {
     #[cold]
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         if unlikely!(!(self.depth >= self.dimensions() || self.shape()[self.depth] != 0)) {
-            serializer.serialize_seq(Some(0)).unwrap().end()
+            enc.write_raw(b"[]")
         } else if !self.children.is_empty() {
-            let mut seq = serializer.serialize_seq(None).unwrap();
-            for child in &self.children {
-                seq.serialize_element(child).unwrap();
+            enc.begin_array()?;
+            for (idx, child) in self.children.iter().enumerate() {
+                enc.begin_array_value(idx == 0)?;
+                child.encode(enc)?;
             }
-            seq.end()
+            enc.end_array()
         } else {
             match self.kind {
                 ItemType::F64 => {
                     NumpyF64Array::new(slice!(self.data() as *const f64, self.num_items()))
-                        .serialize(serializer)
+                        .encode(enc)
                 }
                 ItemType::F32 => {
                     NumpyF32Array::new(slice!(self.data() as *const f32, self.num_items()))
-                        .serialize(serializer)
+                        .encode(enc)
                 }
                 ItemType::U64 => {
                     NumpyU64Array::new(slice!(self.data() as *const u64, self.num_items()))
-                        .serialize(serializer)
+                        .encode(enc)
                 }
                 ItemType::U32 => {
                     NumpyU32Array::new(slice!(self.data() as *const u32, self.num_items()))
-                        .serialize(serializer)
+                        .encode(enc)
                 }
                 ItemType::U8 => {
                     NumpyU8Array::new(slice!(self.data() as *const u8, self.num_items()))
-                        .serialize(serializer)
+                        .encode(enc)
                 }
                 ItemType::I64 => {
                     NumpyI64Array::new(slice!(self.data() as *const i64, self.num_items()))
-                        .serialize(serializer)
+                        .encode(enc)
                 }
                 ItemType::I32 => {
                     NumpyI32Array::new(slice!(self.data() as *const i32, self.num_items()))
-                        .serialize(serializer)
+                        .encode(enc)
                 }
                 ItemType::I8 => {
                     NumpyI8Array::new(slice!(self.data() as *const i8, self.num_items()))
-                        .serialize(serializer)
+                        .encode(enc)
                 }
                 ItemType::BOOL => {
                     NumpyBoolArray::new(slice!(self.data() as *const u8, self.num_items()))
-                        .serialize(serializer)
+                        .encode(enc)
                 }
                 ItemType::DATETIME64(unit) => NumpyDatetime64Array::new(
                     slice!(self.data() as *const i64, self.num_items()),
                     unit,
                     self.opts,
                 )
-                .serialize(serializer),
+                .encode(enc),
             }
         }
     }
@@ -365,16 +360,14 @@ impl<'a> NumpyF64Array<'a> {
     }
 }
 
-impl<'a> Serialize for NumpyF64Array<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            seq.serialize_element(&DataTypeF64 { obj: each }).unwrap();
+impl<'a> Encode for NumpyF64Array<'a> {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.begin_array()?;
+        for (idx, &each) in self.data.iter().enumerate() {
+            enc.begin_array_value(idx == 0)?;
+            DataTypeF64 { obj: each }.encode(enc)?;
         }
-        seq.end()
+        enc.end_array()
     }
 }
 
@@ -383,12 +376,9 @@ pub struct DataTypeF64 {
     obj: f64,
 }
 
-impl Serialize for DataTypeF64 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_f64(self.obj)
+impl Encode for DataTypeF64 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_f64(self.obj)
     }
 }
 
@@ -403,16 +393,14 @@ impl<'a> NumpyF32Array<'a> {
     }
 }
 
-impl<'a> Serialize for NumpyF32Array<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            seq.serialize_element(&DataTypeF32 { obj: each }).unwrap();
+impl<'a> Encode for NumpyF32Array<'a> {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.begin_array()?;
+        for (idx, &each) in self.data.iter().enumerate() {
+            enc.begin_array_value(idx == 0)?;
+            DataTypeF32 { obj: each }.encode(enc)?;
         }
-        seq.end()
+        enc.end_array()
     }
 }
 
@@ -421,12 +409,9 @@ struct DataTypeF32 {
     obj: f32,
 }
 
-impl Serialize for DataTypeF32 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_f32(self.obj)
+impl Encode for DataTypeF32 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_f32(self.obj)
     }
 }
 
@@ -441,16 +426,14 @@ impl<'a> NumpyU64Array<'a> {
     }
 }
 
-impl<'a> Serialize for NumpyU64Array<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            seq.serialize_element(&DataTypeU64 { obj: each }).unwrap();
+impl<'a> Encode for NumpyU64Array<'a> {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.begin_array()?;
+        for (idx, &each) in self.data.iter().enumerate() {
+            enc.begin_array_value(idx == 0)?;
+            DataTypeU64 { obj: each }.encode(enc)?;
         }
-        seq.end()
+        enc.end_array()
     }
 }
 
@@ -459,12 +442,9 @@ pub struct DataTypeU64 {
     obj: u64,
 }
 
-impl Serialize for DataTypeU64 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u64(self.obj)
+impl Encode for DataTypeU64 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.obj)
     }
 }
 
@@ -479,16 +459,14 @@ impl<'a> NumpyU32Array<'a> {
     }
 }
 
-impl<'a> Serialize for NumpyU32Array<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            seq.serialize_element(&DataTypeU32 { obj: each }).unwrap();
+impl<'a> Encode for NumpyU32Array<'a> {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.begin_array()?;
+        for (idx, &each) in self.data.iter().enumerate() {
+            enc.begin_array_value(idx == 0)?;
+            DataTypeU32 { obj: each }.encode(enc)?;
         }
-        seq.end()
+        enc.end_array()
     }
 }
 
@@ -497,12 +475,9 @@ pub struct DataTypeU32 {
     obj: u32,
 }
 
-impl Serialize for DataTypeU32 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u32(self.obj)
+impl Encode for DataTypeU32 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.obj)
     }
 }
 
@@ -517,16 +492,14 @@ impl<'a> NumpyI64Array<'a> {
     }
 }
 
-impl<'a> Serialize for NumpyI64Array<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            seq.serialize_element(&DataTypeI64 { obj: each }).unwrap();
+impl<'a> Encode for NumpyI64Array<'a> {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.begin_array()?;
+        for (idx, &each) in self.data.iter().enumerate() {
+            enc.begin_array_value(idx == 0)?;
+            DataTypeI64 { obj: each }.encode(enc)?;
         }
-        seq.end()
+        enc.end_array()
     }
 }
 
@@ -535,12 +508,9 @@ pub struct DataTypeI64 {
     obj: i64,
 }
 
-impl Serialize for DataTypeI64 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_i64(self.obj)
+impl Encode for DataTypeI64 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.obj)
     }
 }
 
@@ -555,16 +525,14 @@ impl<'a> NumpyI32Array<'a> {
     }
 }
 
-impl<'a> Serialize for NumpyI32Array<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            seq.serialize_element(&DataTypeI32 { obj: each }).unwrap();
+impl<'a> Encode for NumpyI32Array<'a> {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.begin_array()?;
+        for (idx, &each) in self.data.iter().enumerate() {
+            enc.begin_array_value(idx == 0)?;
+            DataTypeI32 { obj: each }.encode(enc)?;
         }
-        seq.end()
+        enc.end_array()
     }
 }
 
@@ -573,12 +541,9 @@ pub struct DataTypeI32 {
     obj: i32,
 }
 
-impl Serialize for DataTypeI32 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_i32(self.obj)
+impl Encode for DataTypeI32 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.obj)
     }
 }
 
@@ -593,16 +558,14 @@ impl<'a> NumpyI8Array<'a> {
     }
 }
 
-impl<'a> Serialize for NumpyI8Array<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            seq.serialize_element(&DataTypeI8 { obj: each }).unwrap();
+impl<'a> Encode for NumpyI8Array<'a> {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.begin_array()?;
+        for (idx, &each) in self.data.iter().enumerate() {
+            enc.begin_array_value(idx == 0)?;
+            DataTypeI8 { obj: each }.encode(enc)?;
         }
-        seq.end()
+        enc.end_array()
     }
 }
 
@@ -611,12 +574,9 @@ pub struct DataTypeI8 {
     obj: i8,
 }
 
-impl Serialize for DataTypeI8 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_i8(self.obj)
+impl Encode for DataTypeI8 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.obj)
     }
 }
 
@@ -631,16 +591,14 @@ impl<'a> NumpyU8Array<'a> {
     }
 }
 
-impl<'a> Serialize for NumpyU8Array<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            seq.serialize_element(&DataTypeU8 { obj: each }).unwrap();
+impl<'a> Encode for NumpyU8Array<'a> {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.begin_array()?;
+        for (idx, &each) in self.data.iter().enumerate() {
+            enc.begin_array_value(idx == 0)?;
+            DataTypeU8 { obj: each }.encode(enc)?;
         }
-        seq.end()
+        enc.end_array()
     }
 }
 
@@ -649,12 +607,9 @@ pub struct DataTypeU8 {
     obj: u8,
 }
 
-impl Serialize for DataTypeU8 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u8(self.obj)
+impl Encode for DataTypeU8 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.obj)
     }
 }
 
@@ -669,16 +624,14 @@ impl<'a> NumpyBoolArray<'a> {
     }
 }
 
-impl<'a> Serialize for NumpyBoolArray<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            seq.serialize_element(&DataTypeBool { obj: each }).unwrap();
+impl<'a> Encode for NumpyBoolArray<'a> {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.begin_array()?;
+        for (idx, &each) in self.data.iter().enumerate() {
+            enc.begin_array_value(idx == 0)?;
+            DataTypeBool { obj: each }.encode(enc)?;
         }
-        seq.end()
+        enc.end_array()
     }
 }
 
@@ -687,12 +640,9 @@ pub struct DataTypeBool {
     obj: u8,
 }
 
-impl Serialize for DataTypeBool {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_bool(self.obj == 1)
+impl Encode for DataTypeBool {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_bool(self.obj == 1)
     }
 }
 
@@ -707,41 +657,36 @@ impl NumpyScalar {
     }
 }
 
-impl Serialize for NumpyScalar {
+impl Encode for NumpyScalar {
     #[cold]
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         unsafe {
             let ob_type = ob_type!(self.ptr);
             let scalar_types = NUMPY_TYPES.deref_mut().as_ref().unwrap();
             if ob_type == scalar_types.float64 {
-                (*(self.ptr as *mut NumpyFloat64)).serialize(serializer)
+                (*(self.ptr as *mut NumpyFloat64)).encode(enc)
             } else if ob_type == scalar_types.float32 {
-                (*(self.ptr as *mut NumpyFloat32)).serialize(serializer)
+                (*(self.ptr as *mut NumpyFloat32)).encode(enc)
             } else if ob_type == scalar_types.int64 {
-                (*(self.ptr as *mut NumpyInt64)).serialize(serializer)
+                (*(self.ptr as *mut NumpyInt64)).encode(enc)
             } else if ob_type == scalar_types.int32 {
-                (*(self.ptr as *mut NumpyInt32)).serialize(serializer)
+                (*(self.ptr as *mut NumpyInt32)).encode(enc)
             } else if ob_type == scalar_types.int8 {
-                (*(self.ptr as *mut NumpyInt8)).serialize(serializer)
+                (*(self.ptr as *mut NumpyInt8)).encode(enc)
             } else if ob_type == scalar_types.uint64 {
-                (*(self.ptr as *mut NumpyUint64)).serialize(serializer)
+                (*(self.ptr as *mut NumpyUint64)).encode(enc)
             } else if ob_type == scalar_types.uint32 {
-                (*(self.ptr as *mut NumpyUint32)).serialize(serializer)
+                (*(self.ptr as *mut NumpyUint32)).encode(enc)
             } else if ob_type == scalar_types.uint8 {
-                (*(self.ptr as *mut NumpyUint8)).serialize(serializer)
+                (*(self.ptr as *mut NumpyUint8)).encode(enc)
             } else if ob_type == scalar_types.bool_ {
-                (*(self.ptr as *mut NumpyBool)).serialize(serializer)
+                (*(self.ptr as *mut NumpyBool)).encode(enc)
             } else if ob_type == scalar_types.datetime64 {
                 let unit = NumpyDatetimeUnit::from_pyobject(self.ptr);
                 let obj = &*(self.ptr as *mut NumpyDatetime64);
-                let dt = unit
-                    .datetime(obj.value, self.opts)
-                    .map_err(NumpyDateTimeError::into_serde_err)?;
-                dt.serialize(serializer)
+                let dt = unit.datetime(obj.value, self.opts)?;
+                dt.encode(enc)
             } else {
                 unreachable!()
             }
@@ -756,12 +701,9 @@ pub struct NumpyInt8 {
     value: i8,
 }
 
-impl Serialize for NumpyInt8 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_i8(self.value)
+impl Encode for NumpyInt8 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.value)
     }
 }
 
@@ -772,12 +714,9 @@ pub struct NumpyInt32 {
     value: i32,
 }
 
-impl Serialize for NumpyInt32 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_i32(self.value)
+impl Encode for NumpyInt32 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.value)
     }
 }
 
@@ -788,12 +727,9 @@ pub struct NumpyInt64 {
     value: i64,
 }
 
-impl Serialize for NumpyInt64 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_i64(self.value)
+impl Encode for NumpyInt64 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.value)
     }
 }
 
@@ -804,12 +740,9 @@ pub struct NumpyUint8 {
     value: u8,
 }
 
-impl Serialize for NumpyUint8 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u8(self.value)
+impl Encode for NumpyUint8 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.value)
     }
 }
 
@@ -820,12 +753,9 @@ pub struct NumpyUint32 {
     value: u32,
 }
 
-impl Serialize for NumpyUint32 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u32(self.value)
+impl Encode for NumpyUint32 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.value)
     }
 }
 
@@ -836,12 +766,9 @@ pub struct NumpyUint64 {
     value: u64,
 }
 
-impl Serialize for NumpyUint64 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_u64(self.value)
+impl Encode for NumpyUint64 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_int(self.value)
     }
 }
 
@@ -852,12 +779,9 @@ pub struct NumpyFloat32 {
     value: f32,
 }
 
-impl Serialize for NumpyFloat32 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_f32(self.value)
+impl Encode for NumpyFloat32 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_f32(self.value)
     }
 }
 
@@ -868,12 +792,9 @@ pub struct NumpyFloat64 {
     value: f64,
 }
 
-impl Serialize for NumpyFloat64 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_f64(self.value)
+impl Encode for NumpyFloat64 {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_f64(self.value)
     }
 }
 
@@ -884,12 +805,9 @@ pub struct NumpyBool {
     value: bool,
 }
 
-impl Serialize for NumpyBool {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_bool(self.value)
+impl Encode for NumpyBool {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.write_bool(self.value)
     }
 }
 
@@ -947,17 +865,19 @@ enum NumpyDateTimeError {
     Unrepresentable { unit: NumpyDatetimeUnit, val: i64 },
 }
 
-impl NumpyDateTimeError {
+impl From<NumpyDateTimeError> for EncodeError {
     #[cold]
     #[cfg_attr(feature = "optimize", optimize(size))]
-    fn into_serde_err<T: ser::Error>(self) -> T {
-        let err = match self {
-            Self::UnsupportedUnit(unit) => format!("unsupported numpy.datetime64 unit: {}", unit),
-            Self::Unrepresentable { unit, val } => {
+    fn from(err: NumpyDateTimeError) -> Self {
+        let msg = match err {
+            NumpyDateTimeError::UnsupportedUnit(unit) => {
+                format!("unsupported numpy.datetime64 unit: {}", unit)
+            }
+            NumpyDateTimeError::Unrepresentable { unit, val } => {
                 format!("unrepresentable numpy.datetime64: {} {}", val, unit)
             }
         };
-        ser::Error::custom(err)
+        EncodeError::Message(msg)
     }
 }
 
@@ -1067,20 +987,15 @@ impl<'a> NumpyDatetime64Array<'a> {
     }
 }
 
-impl<'a> Serialize for NumpyDatetime64Array<'a> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut seq = serializer.serialize_seq(None).unwrap();
-        for &each in self.data.iter() {
-            let dt = self
-                .unit
-                .datetime(each, self.opts)
-                .map_err(NumpyDateTimeError::into_serde_err)?;
-            seq.serialize_element(&dt).unwrap();
+impl<'a> Encode for NumpyDatetime64Array<'a> {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        enc.begin_array()?;
+        for (idx, &each) in self.data.iter().enumerate() {
+            let dt = self.unit.datetime(each, self.opts)?;
+            enc.begin_array_value(idx == 0)?;
+            dt.encode(enc)?;
         }
-        seq.end()
+        enc.end_array()
     }
 }
 
@@ -1134,13 +1049,10 @@ impl DateTimeLike for NumpyDatetime64Repr {
     }
 }
 
-impl Serialize for NumpyDatetime64Repr {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+impl Encode for NumpyDatetime64Repr {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         let mut buf = DateTimeBuffer::new();
         let _ = self.write_buf(&mut buf, self.opts);
-        serializer.collect_str(str_from_slice!(buf.as_ptr(), buf.len()))
+        enc.write_str(str_from_slice!(buf.as_ptr(), buf.len()))
     }
 }
@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::opt::*;
+use crate::serialize::serializer::ObType;
+use associative_cache::replacement::RoundRobinReplacement;
+use associative_cache::*;
+use once_cell::unsync::OnceCell;
+
+/// Options that change how `pyobject_to_obtype_uncached()` classifies a type.
+const OBTYPE_OPTS: Opt = PASSTHROUGH_DATACLASS
+    | PASSTHROUGH_DATETIME
+    | PASSTHROUGH_SUBCLASS
+    | SERIALIZE_GEO_INTERFACE
+    | SERIALIZE_MAPPING
+    | SERIALIZE_MULTIDICT
+    | SERIALIZE_SEQUENCE
+    | SERIALIZE_NUMPY
+    | SERIALIZE_PAIRS;
+
+#[derive(Copy, Clone)]
+pub struct ObTypeEntry {
+    // `tp_version_tag` is unique across types and changes when a type is
+    // modified, so it invalidates entries for a type object at a reused
+    // address or a class that has since become a dataclass.
+    version_tag: u32,
+    opts: Opt,
+    ob_type: ObType,
+}
+
+pub type ObTypeMap =
+    AssociativeCache<usize, ObTypeEntry, Capacity64, HashDirectMapped, RoundRobinReplacement>;
+
+pub static mut OBTYPE_CACHE: OnceCell<ObTypeMap> = OnceCell::new();
+
+/// Version tag of `ob_type` if it is valid.
+#[inline(always)]
+fn version_tag(ob_type: *mut pyo3_ffi::PyTypeObject) -> Option<u32> {
+    unsafe {
+        if (*ob_type).tp_flags & pyo3_ffi::Py_TPFLAGS_VALID_VERSION_TAG == 0 {
+            None
+        } else {
+            Some((*ob_type).tp_version_tag)
+        }
+    }
+}
+
+/// The cached classification of `ob_type` under `opts`, if any.
+#[inline(always)]
+pub fn obtype_cache_get(ob_type: *mut pyo3_ffi::PyTypeObject, opts: Opt) -> Option<ObType> {
+    let version_tag = version_tag(ob_type)?;
+    let cache = unsafe { OBTYPE_CACHE.get_mut().unwrap_or_else(|| unreachable!()) };
+    match cache.get(&(ob_type as usize)) {
+        Some(entry) if entry.version_tag == version_tag && entry.opts == opts & OBTYPE_OPTS => {
+            Some(entry.ob_type)
+        }
+        _ => None,
+    }
+}
+
+#[inline(never)]
+pub fn obtype_cache_insert(ob_type: *mut pyo3_ffi::PyTypeObject, opts: Opt, val: ObType) {
+    if let Some(version_tag) = version_tag(ob_type) {
+        let cache = unsafe { OBTYPE_CACHE.get_mut().unwrap_or_else(|| unreachable!()) };
+        cache.insert(
+            ob_type as usize,
+            ObTypeEntry {
+                version_tag: version_tag,
+                opts: opts & OBTYPE_OPTS,
+                ob_type: val,
+            },
+        );
+    }
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::opt::*;
+use crate::serialize::dict::DictNonStrKey;
+use crate::serialize::encode::*;
+use crate::serialize::error::*;
+use crate::typeref::*;
+
+/// Whether `obj` is a `dict.items()` view or an iterator, which are written
+/// as objects of their pairs for `OPT_SERIALIZE_PAIRS`.
+pub fn is_pairs(obj: *mut pyo3_ffi::PyObject) -> bool {
+    unsafe { ob_type!(obj) == DICT_ITEMS_TYPE || pyo3_ffi::PyIter_Check(obj) != 0 }
+}
+
+/// The `(key, value)` tuples of an iterable being written as the entries of
+/// an object, in order and including repeated keys, as they are iterated.
+/// With `OPT_SORT_KEYS`, `MappingSerializer::with_pairs()` is used instead.
+pub struct PairsSerializer {
+    iter: *mut pyo3_ffi::PyObject,
+    // Strong reference to the tuple of the value last returned.
+    pair: *mut pyo3_ffi::PyObject,
+    opts: Opt,
+    first: bool,
+}
+
+impl Drop for PairsSerializer {
+    fn drop(&mut self) {
+        ffi!(Py_XDECREF(self.pair));
+        ffi!(Py_DECREF(self.iter));
+    }
+}
+
+impl PairsSerializer {
+    #[cold]
+    #[inline(never)]
+    pub fn new(ptr: *mut pyo3_ffi::PyObject, opts: Opt) -> Result<Self, EncodeError> {
+        let iter = ffi!(PyObject_GetIter(ptr));
+        if unlikely!(iter.is_null()) {
+            return Err(EncodeError::Raised);
+        }
+        Ok(PairsSerializer {
+            iter: iter,
+            pair: std::ptr::null_mut(),
+            opts: opts & NOT_PASSTHROUGH,
+            first: true,
+        })
+    }
+
+    /// Write the separator and key before the next value and return it or,
+    /// after the last pair, end the object.
+    #[inline(never)]
+    pub fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        loop {
+            ffi!(Py_XDECREF(self.pair));
+            self.pair = ffi!(PyIter_Next(self.iter));
+            let pair = self.pair;
+            if pair.is_null() {
+                if unlikely!(!ffi!(PyErr_Occurred()).is_null()) {
+                    return Err(EncodeError::Raised);
+                }
+                enc.end_object()?;
+                return Ok(None);
+            }
+            if unlikely!(unsafe { ob_type!(pair) != TUPLE_TYPE } || ffi!(Py_SIZE(pair)) != 2) {
+                err!(SerializeError::PairMustBeTuple)
+            }
+            let key = DictNonStrKey::key_to_string(ffi!(PyTuple_GET_ITEM(pair, 0)), self.opts)?;
+            let key = key.as_str();
+            if unlikely!(enc.is_including()) && !enc.includes_key(key) {
+                continue;
+            }
+            enc.write_key(key, self.first)?;
+            self.first = false;
+            return Ok(Some(ffi!(PyTuple_GET_ITEM(pair, 1))));
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use compact_str::CompactString;
+
+enum Segment {
+    Index(usize),
+    Key(CompactString),
+}
+
+/// The location of the value being written, for `OPT_DEFAULT_PATH`. It has
+/// a segment for each array or object being written, which is the index or
+/// key of the value last begun in it.
+#[derive(Default)]
+pub struct ValuePath {
+    segments: Vec<Segment>,
+}
+
+impl ValuePath {
+    #[inline]
+    pub fn enter_array(&mut self) {
+        self.segments.push(Segment::Index(0));
+    }
+
+    #[inline]
+    pub fn enter_object(&mut self) {
+        self.segments.push(Segment::Key(CompactString::default()));
+    }
+
+    #[inline]
+    pub fn leave(&mut self) {
+        self.segments.pop();
+    }
+
+    /// Advance to the next element of the array being written.
+    #[inline]
+    pub fn next_index(&mut self, first: bool) {
+        if let Some(Segment::Index(idx)) = self.segments.last_mut() {
+            if !first {
+                *idx += 1;
+            }
+        }
+    }
+
+    #[inline]
+    pub fn set_key(&mut self, key: &str) {
+        if let Some(Segment::Key(each)) = self.segments.last_mut() {
+            *each = CompactString::new(key);
+        }
+    }
+
+    /// The location as a JSON pointer (RFC 6901), e.g., `"/a/0"`, or `""` at
+    /// the top level.
+    #[cold]
+    pub fn to_pointer(&self) -> String {
+        let mut pointer = String::new();
+        for segment in self.segments.iter() {
+            pointer.push('/');
+            match segment {
+                Segment::Index(idx) => {
+                    pointer.push_str(itoa::Buffer::new().format(*idx));
+                }
+                Segment::Key(key) => {
+                    for ch in key.chars() {
+                        match ch {
+                            '~' => pointer.push_str("~0"),
+                            '/' => pointer.push_str("~1"),
+                            _ => pointer.push(ch),
+                        }
+                    }
+                }
+            }
+        }
+        pointer
+    }
+}
@@ -1,16 +1,17 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::opt::*;
+use crate::serialize::encode::*;
+use crate::serialize::error::EncodeError;
 use crate::serialize::serializer::*;
 use crate::typeref::*;
-use serde::ser::{Serialize, Serializer};
 use std::ptr::NonNull;
 
 pub struct EnumSerializer {
     ptr: *mut pyo3_ffi::PyObject,
     opts: Opt,
     default_calls: u8,
-    recursion: u8,
+    recursion: u32,
     default: Option<NonNull<pyo3_ffi::PyObject>>,
 }
 
@@ -19,7 +20,7 @@ impl EnumSerializer {
         ptr: *mut pyo3_ffi::PyObject,
         opts: Opt,
         default_calls: u8,
-        recursion: u8,
+        recursion: u32,
         default: Option<NonNull<pyo3_ffi::PyObject>>,
     ) -> Self {
         EnumSerializer {
@@ -32,12 +33,9 @@ impl EnumSerializer {
     }
 }
 
-impl<'p> Serialize for EnumSerializer {
+impl<'p> Encode for EnumSerializer {
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         let value = ffi!(PyObject_GetAttr(self.ptr, VALUE_STR));
         ffi!(Py_DECREF(value));
         PyObjectSerializer::new(
@@ -47,6 +45,6 @@ impl<'p> Serialize for EnumSerializer {
             self.recursion,
             self.default,
         )
-        .serialize(serializer)
+        .encode(enc)
     }
 }
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::typeref::*;
+use crate::unicode::unicode_to_str;
+
+/// The JSON written instead of the value of a redacted key.
+pub const REDACTED: &[u8] = b"\"[REDACTED]\"";
+
+/// Keys given to `dumps(redact_keys=...)` whose values are written as
+/// `REDACTED`.
+pub struct RedactKeys {
+    // Strong reference to a frozenset of the keys. It keeps the strings in
+    // `keys` alive and is used to look up keys that are `str` objects.
+    set: *mut pyo3_ffi::PyObject,
+    keys: Vec<&'static str>,
+}
+
+impl Drop for RedactKeys {
+    fn drop(&mut self) {
+        ffi!(Py_DECREF(self.set));
+    }
+}
+
+impl RedactKeys {
+    /// Collect the keys from an iterable of `str` other than a `str` itself,
+    /// or return `None` if `ptr` is not one.
+    #[cold]
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Option<Self> {
+        if unsafe { ob_type!(ptr) == STR_TYPE } {
+            return None;
+        }
+        let set = ffi!(PyFrozenSet_New(ptr));
+        if set.is_null() {
+            ffi!(PyErr_Clear());
+            return None;
+        }
+        let mut redact = RedactKeys {
+            set: set,
+            keys: Vec::with_capacity(ffi!(Py_SIZE(set)) as usize),
+        };
+        let iter = ffi!(PyObject_GetIter(set));
+        loop {
+            let each = ffi!(PyIter_Next(iter));
+            if each.is_null() {
+                break;
+            }
+            // the frozenset holds a reference to each key
+            ffi!(Py_DECREF(each));
+            let key = if unsafe { ob_type!(each) == STR_TYPE } {
+                unicode_to_str(each)
+            } else {
+                None
+            };
+            match key {
+                Some(key) => redact.keys.push(key),
+                None => {
+                    ffi!(Py_DECREF(iter));
+                    ffi!(PyErr_Clear());
+                    return None;
+                }
+            }
+        }
+        ffi!(Py_DECREF(iter));
+        Some(redact)
+    }
+
+    /// Whether the `str` object `key` is redacted.
+    #[inline]
+    pub fn contains(&self, key: *mut pyo3_ffi::PyObject) -> bool {
+        ffi!(PySet_Contains(self.set, key)) == 1
+    }
+
+    #[inline]
+    pub fn contains_str(&self, key: &str) -> bool {
+        self.keys.contains(&key)
+    }
+}
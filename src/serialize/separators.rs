@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::typeref::*;
+use crate::unicode::unicode_to_str;
+
+/// Separators given to `dumps(separators=...)`, written between the entries
+/// of containers and between a key and its value instead of "," and ":".
+#[derive(Clone)]
+pub struct Separators {
+    item: Vec<u8>,
+    key: Vec<u8>,
+}
+
+/// Whether `val` is `punct` surrounded by any JSON whitespace.
+fn is_separator(val: &str, punct: u8) -> bool {
+    let mut found = false;
+    for &b in val.as_bytes() {
+        match b {
+            b' ' | b'\t' | b'\n' | b'\r' => {}
+            _ if b == punct && !found => found = true,
+            _ => return false,
+        }
+    }
+    found
+}
+
+impl Separators {
+    /// Get the item and key separators from a `tuple` or `list` of two `str`,
+    /// or return `None` if `ptr` is not one or a separator is not "," or ":"
+    /// respectively surrounded by whitespace, so that the output is JSON.
+    #[cold]
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Option<Self> {
+        let (item, key) = unsafe {
+            if ob_type!(ptr) == TUPLE_TYPE && pyo3_ffi::PyTuple_GET_SIZE(ptr) == 2 {
+                (
+                    pyo3_ffi::PyTuple_GET_ITEM(ptr, 0),
+                    pyo3_ffi::PyTuple_GET_ITEM(ptr, 1),
+                )
+            } else if ob_type!(ptr) == LIST_TYPE && pyo3_ffi::PyList_GET_SIZE(ptr) == 2 {
+                (
+                    pyo3_ffi::PyList_GET_ITEM(ptr, 0),
+                    pyo3_ffi::PyList_GET_ITEM(ptr, 1),
+                )
+            } else {
+                return None;
+            }
+        };
+        if unsafe { ob_type!(item) != STR_TYPE || ob_type!(key) != STR_TYPE } {
+            return None;
+        }
+        let item = unicode_to_str(item)?;
+        let key = unicode_to_str(key)?;
+        if !is_separator(item, b',') || !is_separator(key, b':') {
+            return None;
+        }
+        Some(Separators::from_strs(item, key))
+    }
+
+    pub fn from_strs(item: &str, key: &str) -> Self {
+        Separators {
+            item: item.as_bytes().to_vec(),
+            key: key.as_bytes().to_vec(),
+        }
+    }
+
+    /// The separator written between the entries of a container.
+    #[inline(always)]
+    pub fn item(&self) -> &[u8] {
+        &self.item
+    }
+
+    /// The separator written between a key and its value.
+    #[inline(always)]
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
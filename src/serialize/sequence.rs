@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::serialize::encode::*;
+use crate::serialize::error::*;
+use crate::typeref::*;
+
+/// Whether `obj` is an instance of `collections.abc.Sequence`, including by
+/// registration, other than a `bytes`, `bytearray`, `memoryview`, `str`, or
+/// `collections.UserString`. The elements of a string are strings, so it is
+/// not an array.
+pub fn is_sequence(obj: *mut pyo3_ffi::PyObject) -> bool {
+    unsafe {
+        let ob_type = ob_type!(obj);
+        if ob_type == BYTES_TYPE || ob_type == BYTEARRAY_TYPE || ob_type == MEMORYVIEW_TYPE {
+            return false;
+        }
+        if pyo3_ffi::PyUnicode_Check(obj) != 0
+            || pyo3_ffi::PyType_IsSubtype(ob_type, *USER_STRING_TYPE) != 0
+        {
+            return false;
+        }
+    }
+    match ffi!(PyObject_IsInstance(obj, unsafe { SEQUENCE_TYPE.as_ptr() })) {
+        1 => true,
+        0 => false,
+        _ => {
+            ffi!(PyErr_Clear());
+            false
+        }
+    }
+}
+
+/// The elements of a `collections.abc.Sequence` other than a `list` or
+/// `tuple` being written for `OPT_SERIALIZE_SEQUENCE`, each read using
+/// `__getitem__()` and returned to `PyObjectSerializer` to encode.
+pub struct SequenceSerializer {
+    ptr: *mut pyo3_ffi::PyObject,
+    len: isize,
+    idx: isize,
+    // Strong reference to the element last returned, which `__getitem__()`
+    // may have created.
+    value: *mut pyo3_ffi::PyObject,
+}
+
+impl Drop for SequenceSerializer {
+    fn drop(&mut self) {
+        ffi!(Py_XDECREF(self.value));
+    }
+}
+
+impl SequenceSerializer {
+    #[cold]
+    #[inline(never)]
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Result<Self, EncodeError> {
+        let len = ffi!(PySequence_Size(ptr));
+        if unlikely!(len < 0) {
+            return Err(EncodeError::Raised);
+        }
+        Ok(SequenceSerializer {
+            ptr: ptr,
+            len: len,
+            idx: 0,
+            value: std::ptr::null_mut(),
+        })
+    }
+
+    /// Write the separator before the next element and return it or, after
+    /// the last element, end the array.
+    #[inline(never)]
+    pub fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        ffi!(Py_XDECREF(self.value));
+        self.value = std::ptr::null_mut();
+        if self.idx == self.len {
+            enc.end_array()?;
+            return Ok(None);
+        }
+        let value = ffi!(PySequence_GetItem(self.ptr, self.idx));
+        if unlikely!(value.is_null()) {
+            return Err(EncodeError::Raised);
+        }
+        self.value = value;
+        enc.begin_array_value(self.idx == 0)?;
+        self.idx += 1;
+        Ok(Some(value))
+    }
+}
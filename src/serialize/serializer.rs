@@ -1,49 +1,111 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+use crate::deserialize::raw_number_literal;
 use crate::ffi::PyTypeObject;
 use crate::opt::*;
 use crate::serialize::dataclass::*;
+use crate::serialize::dataclass_meta::dataclass_meta;
 use crate::serialize::datetime::*;
 use crate::serialize::default::*;
 use crate::serialize::dict::*;
+use crate::serialize::encode::*;
 use crate::serialize::error::*;
+use crate::serialize::geo::GeoInterfaceSerializer;
+use crate::serialize::include::IncludeKeys;
 use crate::serialize::int::*;
 use crate::serialize::list::*;
+use crate::serialize::mapping::{is_mapping, is_multidict, MappingSerializer};
 use crate::serialize::numpy::*;
+use crate::serialize::obtype_cache::{obtype_cache_get, obtype_cache_insert};
+use crate::serialize::pairs::{is_pairs, PairsSerializer};
 use crate::serialize::pyenum::EnumSerializer;
+use crate::serialize::redact::{RedactKeys, REDACTED};
+use crate::serialize::separators::Separators;
+use crate::serialize::sequence::{is_sequence, SequenceSerializer};
+use crate::serialize::stats::SerializeStats;
 use crate::serialize::str::*;
 use crate::serialize::tuple::*;
 use crate::serialize::uuid::*;
 use crate::serialize::writer::*;
 use crate::typeref::*;
-use serde::ser::{Serialize, SerializeMap, Serializer};
-use std::io::Write;
 use std::ptr::NonNull;
 
 pub const RECURSION_LIMIT: u8 = 255;
 
+/// Nesting of containers allowed if `max_depth` is not given.
+pub const DEFAULT_MAX_DEPTH: u32 = 1024;
+
+/// Keyword arguments of `dumps()` other than `default` and the options.
+#[derive(Default)]
+pub struct DumpsArgs {
+    pub max_size: Option<usize>,
+    pub stats: Option<NonNull<pyo3_ffi::PyObject>>,
+    pub float_precision: Option<u8>,
+    pub max_depth: Option<u32>,
+    pub redact_keys: Option<RedactKeys>,
+    pub include_keys: Option<IncludeKeys>,
+    pub max_str_len: Option<usize>,
+    pub transform: Option<NonNull<pyo3_ffi::PyObject>>,
+    pub separators: Option<Separators>,
+    // set by `orjson.stdlib` rather than given as a keyword argument
+    pub repr_floats: bool,
+}
+
+impl DumpsArgs {
+    /// The arguments in `self` that do not refer to Python objects and so can
+    /// be kept for later calls.
+    pub fn clone_options(&self) -> DumpsArgs {
+        DumpsArgs {
+            max_size: self.max_size,
+            float_precision: self.float_precision,
+            max_depth: self.max_depth,
+            separators: self.separators.clone(),
+            ..Default::default()
+        }
+    }
+}
+
 pub fn serialize(
     ptr: *mut pyo3_ffi::PyObject,
     default: Option<NonNull<pyo3_ffi::PyObject>>,
     opts: Opt,
-) -> Result<NonNull<pyo3_ffi::PyObject>, String> {
-    let mut buf = BytesWriter::default();
+    args: DumpsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, EncodeError> {
     let obj = PyObjectSerializer::new(ptr, opts, 0, 0, default);
-    let res = if opts & INDENT_2 != INDENT_2 {
-        serde_json::to_writer(&mut buf, &obj)
-    } else {
-        serde_json::to_writer_pretty(&mut buf, &obj)
+    to_bytes(&obj, opts, args)
+}
+
+/// Serialize `obj` to a new `bytes` object as `dumps()` does.
+pub fn to_bytes<T: Encode>(
+    obj: &T,
+    opts: Opt,
+    args: DumpsArgs,
+) -> Result<NonNull<pyo3_ffi::PyObject>, EncodeError> {
+    let writer = match args.max_size {
+        Some(limit) => BytesWriter::with_max_size(limit),
+        None => BytesWriter::default(),
     };
+    let stats = args.stats;
+    let mut enc = Encoder::new(writer, opts, args);
+    let mut collected = SerializeStats::new();
+    let prev_stats = SerializeStats::install(match stats {
+        Some(_) => &mut collected as *mut SerializeStats,
+        None => std::ptr::null_mut(),
+    });
+    let mut res = obj.encode(&mut enc);
+    if res.is_ok() && opts & APPEND_NEWLINE != 0 {
+        res = enc.write_raw(b"\n");
+    }
+    SerializeStats::restore(prev_stats);
+    let mut buf = enc.into_inner();
+    if let Some(dict) = stats {
+        collected.write_to_dict(dict.as_ptr(), buf.len());
+    }
     match res {
-        Ok(_) => {
-            if opts & APPEND_NEWLINE != 0 {
-                let _ = buf.write(b"\n");
-            }
-            Ok(buf.finish())
-        }
+        Ok(_) => Ok(buf.finish()),
         Err(err) => {
-            ffi!(_Py_Dealloc(buf.finish().as_ptr()));
-            Err(err.to_string())
+            buf.discard();
+            Err(err)
         }
     }
 }
@@ -63,11 +125,17 @@ pub enum ObType {
     Time,
     Tuple,
     Uuid,
+    RawNumber,
     Dataclass,
     NumpyScalar,
     NumpyArray,
     Enum,
     StrSubclass,
+    GeoInterface,
+    Mapping,
+    MultiDict,
+    Sequence,
+    Pairs,
     Unknown,
 }
 
@@ -102,10 +170,34 @@ macro_rules! is_subclass {
     };
 }
 
+/// Classify `obj` by its type's cached classification or compute and cache it.
+#[inline(never)]
+pub fn pyobject_to_obtype_unlikely(obj: *mut pyo3_ffi::PyObject, opts: Opt) -> ObType {
+    let ob_type = ob_type!(obj);
+    if let Some(val) = obtype_cache_get(ob_type, opts) {
+        return val;
+    }
+    let val = pyobject_to_obtype_uncached(obj, opts);
+    match val {
+        // `__geo_interface__` may be set on the instance
+        ObType::GeoInterface | ObType::Unknown if opts & SERIALIZE_GEO_INTERFACE != 0 => (),
+        // `Mapping.register()` and `Sequence.register()` do not change the
+        // version tag of the class registered
+        ObType::Mapping
+        | ObType::MultiDict
+        | ObType::Sequence
+        | ObType::Pairs
+        | ObType::Unknown
+            if opts & (MAPPING_OR_MULTIDICT | SERIALIZE_SEQUENCE | SERIALIZE_PAIRS) != 0 => {}
+        _ => obtype_cache_insert(ob_type, opts, val),
+    }
+    val
+}
+
 #[cold]
 #[cfg_attr(feature = "optimize", optimize(size))]
 #[inline(never)]
-pub fn pyobject_to_obtype_unlikely(obj: *mut pyo3_ffi::PyObject, opts: Opt) -> ObType {
+fn pyobject_to_obtype_uncached(obj: *mut pyo3_ffi::PyObject, opts: Opt) -> ObType {
     unsafe {
         let ob_type = ob_type!(obj);
         if ob_type == DATE_TYPE && opts & PASSTHROUGH_DATETIME == 0 {
@@ -116,6 +208,8 @@ pub fn pyobject_to_obtype_unlikely(obj: *mut pyo3_ffi::PyObject, opts: Opt) -> O
             ObType::Tuple
         } else if ob_type == UUID_TYPE {
             ObType::Uuid
+        } else if ob_type == RAW_NUMBER_TYPE {
+            ObType::RawNumber
         } else if (*(ob_type as *mut PyTypeObject)).ob_type == ENUM_TYPE {
             ObType::Enum
         } else if opts & PASSTHROUGH_SUBCLASS == 0
@@ -134,6 +228,14 @@ pub fn pyobject_to_obtype_unlikely(obj: *mut pyo3_ffi::PyObject, opts: Opt) -> O
             && is_subclass!(ob_type, Py_TPFLAGS_DICT_SUBCLASS)
         {
             ObType::Dict
+        } else if opts & (PASSTHROUGH_SUBCLASS | PASSTHROUGH_DATETIME) == 0
+            && ffi!(PyDateTime_Check(obj)) == 1
+        {
+            ObType::Datetime
+        } else if opts & (PASSTHROUGH_SUBCLASS | PASSTHROUGH_DATETIME) == 0
+            && ffi!(PyDate_Check(obj)) == 1
+        {
+            ObType::Date
         } else if opts & PASSTHROUGH_DATACLASS == 0
             && ffi!(PyDict_Contains((*ob_type).tp_dict, DATACLASS_FIELDS_STR)) == 1
         {
@@ -142,6 +244,18 @@ pub fn pyobject_to_obtype_unlikely(obj: *mut pyo3_ffi::PyObject, opts: Opt) -> O
             ObType::NumpyScalar
         } else if opts & SERIALIZE_NUMPY != 0 && is_numpy_array(ob_type) {
             ObType::NumpyArray
+        } else if opts & SERIALIZE_GEO_INTERFACE != 0
+            && ffi!(PyObject_HasAttr(obj, GEO_INTERFACE_STR)) == 1
+        {
+            ObType::GeoInterface
+        } else if opts & MAPPING_OR_MULTIDICT != 0 && is_multidict(obj) {
+            ObType::MultiDict
+        } else if opts & SERIALIZE_MAPPING != 0 && is_mapping(obj) {
+            ObType::Mapping
+        } else if opts & SERIALIZE_SEQUENCE != 0 && is_sequence(obj) {
+            ObType::Sequence
+        } else if opts & SERIALIZE_PAIRS != 0 && is_pairs(obj) {
+            ObType::Pairs
         } else {
             ObType::Unknown
         }
@@ -152,7 +266,7 @@ pub struct PyObjectSerializer {
     ptr: *mut pyo3_ffi::PyObject,
     opts: Opt,
     default_calls: u8,
-    recursion: u8,
+    recursion: u32,
     default: Option<NonNull<pyo3_ffi::PyObject>>,
 }
 
@@ -161,7 +275,7 @@ impl PyObjectSerializer {
         ptr: *mut pyo3_ffi::PyObject,
         opts: Opt,
         default_calls: u8,
-        recursion: u8,
+        recursion: u32,
         default: Option<NonNull<pyo3_ffi::PyObject>>,
     ) -> Self {
         PyObjectSerializer {
@@ -174,139 +288,284 @@ impl PyObjectSerializer {
     }
 }
 
-impl Serialize for PyObjectSerializer {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        match pyobject_to_obtype(self.ptr, self.opts) {
-            ObType::Str => StrSerializer::new(self.ptr).serialize(serializer),
-            ObType::StrSubclass => StrSubclassSerializer::new(self.ptr).serialize(serializer),
+/// A container being written by `PyObjectSerializer`. Values are returned to
+/// it to encode instead of encoded by the container so that nesting uses the
+/// heap rather than the stack.
+enum Frame {
+    List(ListSerializer),
+    Tuple(TupleSerializer),
+    Dict(Dict),
+    DictSortedKey(DictSortedKey),
+    DictNonStrKey(DictNonStrKey),
+    DataclassFast(DataclassFastSerializer),
+    DataclassFallback(DataclassFallbackSerializer),
+    Mapping(MappingSerializer),
+    Sequence(SequenceSerializer),
+    Pairs(PairsSerializer),
+}
+
+impl Frame {
+    #[inline(always)]
+    fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        match self {
+            Frame::List(val) => val.next_value(enc),
+            Frame::Tuple(val) => val.next_value(enc),
+            Frame::Dict(val) => val.next_value(enc),
+            Frame::DictSortedKey(val) => val.next_value(enc),
+            Frame::DictNonStrKey(val) => val.next_value(enc),
+            Frame::DataclassFast(val) => val.next_value(enc),
+            Frame::DataclassFallback(val) => val.next_value(enc),
+            Frame::Mapping(val) => val.next_value(enc),
+            Frame::Sequence(val) => val.next_value(enc),
+            Frame::Pairs(val) => val.next_value(enc),
+        }
+    }
+}
+
+/// A strong reference released when dropped, or null.
+struct OwnedRef(*mut pyo3_ffi::PyObject);
+
+impl Drop for OwnedRef {
+    fn drop(&mut self) {
+        ffi!(Py_XDECREF(self.0));
+    }
+}
+
+impl Encode for PyObjectSerializer {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        // `default`, enums, and `__geo_interface__` encode their value with a
+        // new `PyObjectSerializer`, which is the only use of the stack that
+        // grows with the input.
+        if unlikely!(!enc.enter_nested()) {
+            err!(SerializeError::RecursionLimit)
+        }
+        // each container is kept with the value returned by `transform` that
+        // it is, if any
+        let mut stack: Vec<(Frame, OwnedRef)> = Vec::new();
+        let mut ptr = self.ptr;
+        loop {
+            let recursion = self.recursion + stack.len() as u32;
+            let transformed = OwnedRef(enc.transform(ptr)?);
+            if unlikely!(!transformed.0.is_null()) {
+                ptr = transformed.0;
+            }
+            let ob_type = pyobject_to_obtype(ptr, self.opts);
+            SerializeStats::record_obtype(ob_type, recursion);
+            match ob_type {
+                ObType::Enum | ObType::NumpyArray | ObType::GeoInterface | ObType::Unknown => {
+                    self.encode_nested(ptr, ob_type, recursion, enc)?
+                }
+                _ => {
+                    if let Some(frame) = self.encode_value(ptr, ob_type, recursion, enc)? {
+                        stack.push((frame, transformed));
+                    }
+                }
+            }
+            loop {
+                match stack.last_mut() {
+                    None => {
+                        enc.leave_nested();
+                        return Ok(());
+                    }
+                    Some((frame, _)) => match frame.next_value(enc)? {
+                        Some(value) => {
+                            if unlikely!(enc.take_redacted()) {
+                                enc.write_raw(REDACTED)?;
+                                continue;
+                            }
+                            ptr = value;
+                            break;
+                        }
+                        None => {
+                            stack.pop();
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+impl PyObjectSerializer {
+    /// Encode `ptr`, of a type encoded using a new `PyObjectSerializer`.
+    #[inline(never)]
+    fn encode_nested(
+        &self,
+        ptr: *mut pyo3_ffi::PyObject,
+        ob_type: ObType,
+        recursion: u32,
+        enc: &mut Encoder,
+    ) -> Result<(), EncodeError> {
+        match ob_type {
+            ObType::Enum => {
+                EnumSerializer::new(ptr, self.opts, self.default_calls, recursion, self.default)
+                    .encode(enc)
+            }
+            ObType::NumpyArray => {
+                NumpySerializer::new(ptr, self.opts, self.default_calls, recursion, self.default)
+                    .encode(enc)
+            }
+            ObType::GeoInterface => GeoInterfaceSerializer::new(
+                ptr,
+                self.opts,
+                self.default_calls,
+                recursion,
+                self.default,
+            )
+            .encode(enc),
+            _ => {
+                DefaultSerializer::new(ptr, self.opts, self.default_calls, recursion, self.default)
+                    .encode(enc)
+            }
+        }
+    }
+
+    /// Encode `ptr` or, if it is a non-empty container, write its start and
+    /// return it to have its values encoded. This is not inlined so that the
+    /// stack it uses, which is large in a debug build, is not used for each
+    /// call to `default`.
+    #[inline(never)]
+    fn encode_value(
+        &self,
+        ptr: *mut pyo3_ffi::PyObject,
+        ob_type: ObType,
+        recursion: u32,
+        enc: &mut Encoder,
+    ) -> Result<Option<Frame>, EncodeError> {
+        match ob_type {
+            ObType::Str => StrSerializer::new(ptr).encode(enc)?,
+            ObType::StrSubclass => StrSubclassSerializer::new(ptr).encode(enc)?,
             ObType::Int => {
-                if unlikely!(self.opts & STRICT_INTEGER != 0) {
-                    Int53Serializer::new(self.ptr).serialize(serializer)
+                if unlikely!(self.opts & STRICT_INTEGER_OR_JS != 0) {
+                    Int53Serializer::new(ptr).encode(enc)?
                 } else {
-                    IntSerializer::new(self.ptr).serialize(serializer)
+                    IntSerializer::new(ptr).encode(enc)?
                 }
             }
-            ObType::None => serializer.serialize_unit(),
-            ObType::Float => serializer.serialize_f64(ffi!(PyFloat_AS_DOUBLE(self.ptr))),
-            ObType::Bool => serializer.serialize_bool(unsafe { self.ptr == TRUE }),
-            ObType::Datetime => DateTime::new(self.ptr, self.opts).serialize(serializer),
-            ObType::Date => Date::new(self.ptr).serialize(serializer),
-            ObType::Time => Time::new(self.ptr, self.opts).serialize(serializer),
-            ObType::Uuid => UUID::new(self.ptr).serialize(serializer),
+            ObType::None => enc.write_null()?,
+            ObType::Float => enc.write_f64(ffi!(PyFloat_AS_DOUBLE(ptr)))?,
+            ObType::Bool => enc.write_bool(unsafe { ptr == TRUE })?,
+            ObType::Datetime => DateTime::new(ptr, self.opts).encode(enc)?,
+            ObType::Date => Date::new(ptr).encode(enc)?,
+            ObType::Time => Time::new(ptr, self.opts).encode(enc)?,
+            ObType::Uuid => UUID::new(ptr).encode(enc)?,
+            ObType::RawNumber => enc.write_number_literal(raw_number_literal(ptr))?,
             ObType::Dict => {
-                if unlikely!(self.recursion == RECURSION_LIMIT) {
+                if unlikely!(recursion >= enc.max_depth()) {
                     err!(SerializeError::RecursionLimit)
                 }
-                if ffi!(Py_SIZE(self.ptr)) == 0 {
-                    serializer.serialize_map(Some(0)).unwrap().end()
-                } else if self.opts & SORT_OR_NON_STR_KEYS == 0 {
-                    Dict::new(
-                        self.ptr,
-                        self.opts,
-                        self.default_calls,
-                        self.recursion,
-                        self.default,
-                    )
-                    .serialize(serializer)
-                } else if self.opts & NON_STR_KEYS != 0 {
-                    DictNonStrKey::new(
-                        self.ptr,
-                        self.opts,
-                        self.default_calls,
-                        self.recursion,
-                        self.default,
-                    )
-                    .serialize(serializer)
+                if ffi!(Py_SIZE(ptr)) == 0 {
+                    enc.write_raw(b"{}")?
                 } else {
-                    DictSortedKey::new(
-                        self.ptr,
-                        self.opts,
-                        self.default_calls,
-                        self.recursion,
-                        self.default,
-                    )
-                    .serialize(serializer)
+                    let frame = if self.opts & (SORT_OR_NON_STR_KEYS | BYTES_KEYS) == 0 {
+                        Frame::Dict(Dict::new(ptr, self.opts))
+                    } else if self.opts & NON_STR_OR_BYTES_KEYS != 0 {
+                        Frame::DictNonStrKey(DictNonStrKey::new(ptr, self.opts)?)
+                    } else {
+                        Frame::DictSortedKey(DictSortedKey::new(ptr)?)
+                    };
+                    enc.begin_object()?;
+                    return Ok(Some(frame));
                 }
             }
             ObType::List => {
-                if unlikely!(self.recursion == RECURSION_LIMIT) {
+                if unlikely!(recursion >= enc.max_depth()) {
                     err!(SerializeError::RecursionLimit)
                 }
-                ListSerializer::new(
-                    self.ptr,
-                    self.opts,
-                    self.default_calls,
-                    self.recursion,
-                    self.default,
-                )
-                .serialize(serializer)
+                if ffi!(Py_SIZE(ptr)) == 0 {
+                    enc.write_raw(b"[]")?
+                } else {
+                    enc.begin_array()?;
+                    return Ok(Some(Frame::List(ListSerializer::new(ptr))));
+                }
+            }
+            ObType::Tuple => {
+                if unlikely!(recursion >= enc.max_depth()) {
+                    err!(SerializeError::RecursionLimit)
+                }
+                if ffi!(Py_SIZE(ptr)) == 0 {
+                    enc.write_raw(b"[]")?
+                } else {
+                    enc.begin_array()?;
+                    return Ok(Some(Frame::Tuple(TupleSerializer::new(ptr))));
+                }
             }
-            ObType::Tuple => TupleSerializer::new(
-                self.ptr,
-                self.opts,
-                self.default_calls,
-                self.recursion,
-                self.default,
-            )
-            .serialize(serializer),
             ObType::Dataclass => {
-                if unlikely!(self.recursion == RECURSION_LIMIT) {
+                if unlikely!(recursion >= enc.max_depth()) {
                     err!(SerializeError::RecursionLimit)
                 }
-                let dict = ffi!(PyObject_GetAttr(self.ptr, DICT_STR));
-                let ob_type = ob_type!(self.ptr);
-                if unlikely!(
+                let ob_type = ob_type!(ptr);
+                let meta = match dataclass_meta(ob_type) {
+                    Ok(meta) => meta,
+                    Err(err) => err!(err),
+                };
+                let dict = ffi!(PyObject_GetAttr(ptr, DICT_STR));
+                if unlikely!(meta.fields.is_empty()) {
+                    ffi!(Py_XDECREF(dict));
+                    ffi!(PyErr_Clear());
+                    enc.write_raw(b"{}")?
+                } else if unlikely!(
                     dict.is_null() || ffi!(PyDict_Contains((*ob_type).tp_dict, SLOTS_STR)) == 1
                 ) {
+                    ffi!(Py_XDECREF(dict));
                     ffi!(PyErr_Clear());
-                    DataclassFallbackSerializer::new(
-                        self.ptr,
-                        self.opts,
-                        self.default_calls,
-                        self.recursion,
-                        self.default,
-                    )
-                    .serialize(serializer)
+                    enc.begin_object()?;
+                    return Ok(Some(Frame::DataclassFallback(
+                        DataclassFallbackSerializer::new(ptr, meta),
+                    )));
                 } else {
                     ffi!(Py_DECREF(dict));
-                    DataclassFastSerializer::new(
-                        dict,
-                        self.opts,
-                        self.default_calls,
-                        self.recursion,
-                        self.default,
-                    )
-                    .serialize(serializer)
+                    enc.begin_object()?;
+                    return Ok(Some(Frame::DataclassFast(DataclassFastSerializer::new(
+                        ptr, dict, meta,
+                    ))));
                 }
             }
-            ObType::Enum => EnumSerializer::new(
-                self.ptr,
-                self.opts,
-                self.default_calls,
-                self.recursion,
-                self.default,
-            )
-            .serialize(serializer),
-            ObType::NumpyArray => NumpySerializer::new(
-                self.ptr,
-                self.opts,
-                self.default_calls,
-                self.recursion,
-                self.default,
-            )
-            .serialize(serializer),
-            ObType::NumpyScalar => NumpyScalar::new(self.ptr, self.opts).serialize(serializer),
-            ObType::Unknown => DefaultSerializer::new(
-                self.ptr,
-                self.opts,
-                self.default_calls,
-                self.recursion,
-                self.default,
-            )
-            .serialize(serializer),
+            ObType::NumpyScalar => NumpyScalar::new(ptr, self.opts).encode(enc)?,
+            ObType::Mapping => {
+                if unlikely!(recursion >= enc.max_depth()) {
+                    err!(SerializeError::RecursionLimit)
+                }
+                let frame = MappingSerializer::new(ptr, self.opts)?;
+                enc.begin_object()?;
+                return Ok(Some(Frame::Mapping(frame)));
+            }
+            ObType::MultiDict => {
+                if unlikely!(recursion >= enc.max_depth()) {
+                    err!(SerializeError::RecursionLimit)
+                }
+                let frame = MappingSerializer::with_items(ptr, self.opts)?;
+                enc.begin_object()?;
+                return Ok(Some(Frame::Mapping(frame)));
+            }
+            ObType::Sequence => {
+                if unlikely!(recursion >= enc.max_depth()) {
+                    err!(SerializeError::RecursionLimit)
+                }
+                let frame = SequenceSerializer::new(ptr)?;
+                enc.begin_array()?;
+                return Ok(Some(Frame::Sequence(frame)));
+            }
+            ObType::Pairs => {
+                if unlikely!(recursion >= enc.max_depth()) {
+                    err!(SerializeError::RecursionLimit)
+                }
+                if self.opts & SORT_KEYS != 0 {
+                    let frame = MappingSerializer::with_pairs(ptr, self.opts)?;
+                    enc.begin_object()?;
+                    return Ok(Some(Frame::Mapping(frame)));
+                }
+                let frame = PairsSerializer::new(ptr, self.opts)?;
+                enc.begin_object()?;
+                return Ok(Some(Frame::Pairs(frame)));
+            }
+            ObType::Enum | ObType::NumpyArray | ObType::GeoInterface | ObType::Unknown => {
+                unreachable!()
+            }
         }
+        Ok(None)
     }
 }
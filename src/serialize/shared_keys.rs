@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use associative_cache::replacement::RoundRobinReplacement;
+use associative_cache::*;
+use once_cell::unsync::OnceCell;
+use smallvec::SmallVec;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The keys of split-table dicts, e.g., the `__dict__` of instances of a
+/// class, as serialized, so that dicts sharing a keys table copy each key's
+/// output instead of escaping it again.
+pub struct SharedKeys {
+    // Strong reference to each key. Comparing a dict's key against it by
+    // pointer guarantees the same string even if the keys table was freed and
+    // its address reused.
+    keys: SmallVec<[(*mut pyo3_ffi::PyObject, usize); 8]>,
+    // The quoted and escaped keys, each ending at the offset stored with it.
+    bytes: Vec<u8>,
+}
+
+impl Drop for SharedKeys {
+    fn drop(&mut self) {
+        for &(key, _) in self.keys.iter() {
+            ffi!(Py_DECREF(key));
+        }
+    }
+}
+
+impl SharedKeys {
+    fn new() -> Self {
+        SharedKeys {
+            keys: SmallVec::new(),
+            bytes: Vec::new(),
+        }
+    }
+
+    /// The serialized `key` if it is the `idx`th key recorded.
+    #[inline(always)]
+    pub fn get(&self, idx: usize, key: *mut pyo3_ffi::PyObject) -> Option<&[u8]> {
+        match self.keys.get(idx) {
+            Some(&(each, end)) if each == key => {
+                let start = if idx == 0 { 0 } else { self.keys[idx - 1].1 };
+                Some(&self.bytes[start..end])
+            }
+            _ => None,
+        }
+    }
+
+    /// Record the serialized `key` as the `idx`th key if it is the next. A
+    /// different first key, e.g., of a new keys table at the address of a
+    /// freed one, replaces those recorded.
+    #[inline(never)]
+    pub fn record(&mut self, idx: usize, key: *mut pyo3_ffi::PyObject, val: &[u8]) {
+        if idx == 0 && !self.keys.is_empty() {
+            for &(each, _) in self.keys.iter() {
+                ffi!(Py_DECREF(each));
+            }
+            self.keys.clear();
+            self.bytes.clear();
+        }
+        if idx == self.keys.len() {
+            ffi!(Py_INCREF(key));
+            self.bytes.extend_from_slice(val);
+            self.keys.push((key, self.bytes.len()));
+        }
+    }
+}
+
+pub type SharedKeysMap = AssociativeCache<
+    usize,
+    Rc<RefCell<SharedKeys>>,
+    Capacity64,
+    HashDirectMapped,
+    RoundRobinReplacement,
+>;
+
+pub static mut SHARED_KEYS: OnceCell<SharedKeysMap> = OnceCell::new();
+
+/// The serialized keys of the keys table `dk`. The entry is reference
+/// counted so that serializing a nested dict that evicts it does not
+/// invalidate it for the caller.
+#[inline(never)]
+pub fn shared_keys(dk: *mut std::os::raw::c_void) -> Rc<RefCell<SharedKeys>> {
+    let cache = unsafe { SHARED_KEYS.get_mut().unwrap_or_else(|| unreachable!()) };
+    if let Some(entry) = cache.get(&(dk as usize)) {
+        return Rc::clone(entry);
+    }
+    let entry = Rc::new(RefCell::new(SharedKeys::new()));
+    cache.insert(dk as usize, Rc::clone(&entry));
+    entry
+}
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use crate::serialize::serializer::ObType;
+use std::os::raw::c_char;
+use std::ptr::null_mut;
+
+const NUM_OBTYPES: usize = ObType::Unknown as usize + 1;
+
+const OBTYPE_NAMES: [&str; NUM_OBTYPES] = [
+    "str\0",
+    "int\0",
+    "bool\0",
+    "none\0",
+    "float\0",
+    "list\0",
+    "dict\0",
+    "datetime\0",
+    "date\0",
+    "time\0",
+    "tuple\0",
+    "uuid\0",
+    "raw_number\0",
+    "dataclass\0",
+    "numpy_scalar\0",
+    "numpy_array\0",
+    "enum\0",
+    "str_subclass\0",
+    "geo_interface\0",
+    "mapping\0",
+    "multidict\0",
+    "sequence\0",
+    "pairs\0",
+    "unknown\0",
+];
+
+pub struct SerializeStats {
+    max_depth: u32,
+    default_calls: usize,
+    types: [usize; NUM_OBTYPES],
+}
+
+// Stats of the innermost serialize() call requesting them. The GIL is held
+// for the duration of serialize() and a call made from within `default`
+// saves and restores the outer pointer.
+static mut STATS: *mut SerializeStats = null_mut();
+
+impl SerializeStats {
+    pub fn new() -> Self {
+        SerializeStats {
+            max_depth: 0,
+            default_calls: 0,
+            types: [0; NUM_OBTYPES],
+        }
+    }
+
+    /// Make `stats` the target of recording until the returned previous
+    /// value is passed to `restore()`.
+    pub fn install(stats: *mut SerializeStats) -> *mut SerializeStats {
+        unsafe {
+            let prev = STATS;
+            STATS = stats;
+            prev
+        }
+    }
+
+    pub fn restore(prev: *mut SerializeStats) {
+        unsafe { STATS = prev }
+    }
+
+    #[inline(always)]
+    pub fn record_obtype(ob_type: ObType, recursion: u32) {
+        unsafe {
+            if unlikely!(!STATS.is_null()) {
+                (*STATS).types[ob_type as usize] += 1;
+                if recursion > (*STATS).max_depth {
+                    (*STATS).max_depth = recursion;
+                }
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn record_default_call() {
+        unsafe {
+            if unlikely!(!STATS.is_null()) {
+                (*STATS).default_calls += 1;
+            }
+        }
+    }
+
+    /// Write the collected values into the `dict` given as `stats=`.
+    #[cold]
+    #[cfg_attr(feature = "optimize", optimize(size))]
+    pub fn write_to_dict(&self, dict: *mut pyo3_ffi::PyObject, bytes_written: usize) {
+        unsafe {
+            // an exception raised by `default` is still pending on error
+            let mut ptype = null_mut();
+            let mut pvalue = null_mut();
+            let mut ptraceback = null_mut();
+            pyo3_ffi::PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback);
+            set_usize(dict, "bytes\0", bytes_written);
+            set_usize(dict, "max_depth\0", self.max_depth as usize);
+            set_usize(dict, "default_calls\0", self.default_calls);
+            let types = pyo3_ffi::PyDict_New();
+            for (name, &count) in OBTYPE_NAMES.iter().zip(self.types.iter()) {
+                if count != 0 {
+                    set_usize(types, name, count);
+                }
+            }
+            pyo3_ffi::PyDict_SetItemString(dict, "types\0".as_ptr() as *const c_char, types);
+            pyo3_ffi::Py_DECREF(types);
+            pyo3_ffi::PyErr_Restore(ptype, pvalue, ptraceback);
+        }
+    }
+}
+
+unsafe fn set_usize(dict: *mut pyo3_ffi::PyObject, key: &str, val: usize) {
+    let pyval = pyo3_ffi::PyLong_FromSize_t(val);
+    pyo3_ffi::PyDict_SetItemString(dict, key.as_ptr() as *const c_char, pyval);
+    pyo3_ffi::Py_DECREF(pyval);
+}
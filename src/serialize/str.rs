@@ -1,10 +1,9 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+use crate::serialize::encode::*;
 use crate::serialize::error::*;
 use crate::unicode::*;
 
-use serde::ser::{Serialize, Serializer};
-
 #[repr(transparent)]
 pub struct StrSerializer {
     ptr: *mut pyo3_ffi::PyObject,
@@ -16,16 +15,13 @@ impl StrSerializer {
     }
 }
 
-impl Serialize for StrSerializer {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let uni = unicode_to_str(self.ptr);
+impl Encode for StrSerializer {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
+        let uni = unicode_data(self.ptr);
         if unlikely!(uni.is_none()) {
             err!(SerializeError::InvalidStr)
         }
-        serializer.serialize_str(uni.unwrap())
+        enc.write_str_value(uni.unwrap())
     }
 }
 
@@ -40,16 +36,13 @@ impl StrSubclassSerializer {
     }
 }
 
-impl Serialize for StrSubclassSerializer {
+impl Encode for StrSubclassSerializer {
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         let uni = unicode_to_str_via_ffi(self.ptr);
         if unlikely!(uni.is_none()) {
             err!(SerializeError::InvalidStr)
         }
-        serializer.serialize_str(uni.unwrap())
+        enc.write_str_value(UnicodeData::Utf8(uni.unwrap()))
     }
 }
@@ -1,58 +1,34 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use crate::opt::*;
-use crate::serialize::serializer::*;
-
-use serde::ser::{Serialize, SerializeSeq, Serializer};
-use std::ptr::NonNull;
+use crate::serialize::encode::*;
+use crate::serialize::error::EncodeError;
 
+/// The elements of a `tuple` being written, each returned to
+/// `PyObjectSerializer` to encode.
 pub struct TupleSerializer {
     ptr: *mut pyo3_ffi::PyObject,
-    opts: Opt,
-    default_calls: u8,
-    recursion: u8,
-    default: Option<NonNull<pyo3_ffi::PyObject>>,
+    idx: isize,
 }
 
 impl TupleSerializer {
-    pub fn new(
-        ptr: *mut pyo3_ffi::PyObject,
-        opts: Opt,
-        default_calls: u8,
-        recursion: u8,
-        default: Option<NonNull<pyo3_ffi::PyObject>>,
-    ) -> Self {
-        TupleSerializer {
-            ptr: ptr,
-            opts: opts,
-            default_calls: default_calls,
-            recursion: recursion,
-            default: default,
-        }
+    pub fn new(ptr: *mut pyo3_ffi::PyObject) -> Self {
+        TupleSerializer { ptr: ptr, idx: 0 }
     }
-}
 
-impl Serialize for TupleSerializer {
-    #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        if ffi!(Py_SIZE(self.ptr)) == 0 {
-            serializer.serialize_seq(Some(0)).unwrap().end()
-        } else {
-            let mut seq = serializer.serialize_seq(None).unwrap();
-            for i in 0..=ffi!(Py_SIZE(self.ptr)).saturating_sub(1) {
-                let elem = nonnull!(ffi!(PyTuple_GET_ITEM(self.ptr, i as isize)));
-                seq.serialize_element(&PyObjectSerializer::new(
-                    elem.as_ptr(),
-                    self.opts,
-                    self.default_calls,
-                    self.recursion + 1,
-                    self.default,
-                ))?
-            }
-            seq.end()
+    /// Write the separator before the next element and return it or, after
+    /// the last element, end the array.
+    #[inline(always)]
+    pub fn next_value(
+        &mut self,
+        enc: &mut Encoder,
+    ) -> Result<Option<*mut pyo3_ffi::PyObject>, EncodeError> {
+        if self.idx == ffi!(Py_SIZE(self.ptr)) {
+            enc.end_array()?;
+            return Ok(None);
         }
+        enc.begin_array_value(self.idx == 0)?;
+        let value = ffi!(PyTuple_GET_ITEM(self.ptr, self.idx));
+        self.idx += 1;
+        Ok(Some(value))
     }
 }
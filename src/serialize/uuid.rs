@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+use crate::serialize::encode::*;
+use crate::serialize::error::EncodeError;
 use crate::typeref::*;
-use serde::ser::{Serialize, Serializer};
 use std::io::Write;
 use std::os::raw::c_uchar;
 
@@ -49,14 +50,11 @@ impl UUID {
         buf.try_extend_from_slice(&hexadecimal[20..32]).unwrap();
     }
 }
-impl Serialize for UUID {
+impl Encode for UUID {
     #[inline(never)]
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
+    fn encode(&self, enc: &mut Encoder) -> Result<(), EncodeError> {
         let mut buf = arrayvec::ArrayVec::<u8, 36>::new();
         self.write_buf(&mut buf);
-        serializer.serialize_str(str_from_slice!(buf.as_ptr(), buf.len()))
+        enc.write_str(str_from_slice!(buf.as_ptr(), buf.len()))
     }
 }
@@ -1,33 +1,68 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use crate::ffi::PyBytesObject;
+use crate::serialize::error::EncodeError;
 use pyo3_ffi::*;
-use serde_json::WriteExt;
+use std::mem::MaybeUninit;
 use std::os::raw::c_char;
 use std::ptr::NonNull;
 
 const BUFFER_LENGTH: usize = 1024;
 
+// Output up to this length is written to a buffer on the stack and copied to
+// an exactly sized bytes object, avoiding growing and then shrinking one.
+const STACK_BUFFER_LENGTH: usize = 1024;
+
 pub struct BytesWriter {
     cap: usize,
     len: usize,
+    max_size: usize,
+    // null until the output no longer fits in `stack`
     bytes: *mut PyBytesObject,
+    stack: MaybeUninit<[u8; STACK_BUFFER_LENGTH]>,
 }
 
 impl BytesWriter {
     pub fn default() -> Self {
+        BytesWriter::with_max_size(usize::MAX)
+    }
+
+    pub fn with_max_size(max_size: usize) -> Self {
         BytesWriter {
-            cap: BUFFER_LENGTH,
+            // a small max_size only lowers the point at which grow() is
+            // called
+            cap: std::cmp::min(STACK_BUFFER_LENGTH, max_size),
             len: 0,
-            bytes: unsafe {
-                PyBytes_FromStringAndSize(std::ptr::null_mut(), BUFFER_LENGTH as isize)
-                    as *mut PyBytesObject
-            },
+            max_size: max_size,
+            bytes: std::ptr::null_mut(),
+            stack: MaybeUninit::uninit(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The output written after `start`.
+    pub fn written_since(&self, start: usize) -> &[u8] {
+        unsafe {
+            let ptr = if self.bytes.is_null() {
+                self.stack.as_ptr() as *const u8
+            } else {
+                std::ptr::addr_of!((*self.bytes).ob_sval) as *const u8
+            };
+            std::slice::from_raw_parts(ptr.add(start), self.len - start)
         }
     }
 
     pub fn finish(&mut self) -> NonNull<PyObject> {
         unsafe {
+            if self.bytes.is_null() {
+                return NonNull::new_unchecked(PyBytes_FromStringAndSize(
+                    self.stack.as_ptr() as *const c_char,
+                    self.len as isize,
+                ));
+            }
             std::ptr::write(self.buffer_ptr(), 0);
             (*self.bytes.cast::<PyVarObject>()).ob_size = self.len as Py_ssize_t;
             self.resize(self.len);
@@ -35,27 +70,66 @@ impl BytesWriter {
         }
     }
 
-    fn buffer_ptr(&self) -> *mut u8 {
+    /// Return the output as `finish()` does and continue with none, e.g., to
+    /// write the output in parts.
+    pub fn take(&mut self) -> NonNull<PyObject> {
+        let bytes = self.finish();
+        self.cap = std::cmp::min(STACK_BUFFER_LENGTH, self.max_size);
+        self.len = 0;
+        self.bytes = std::ptr::null_mut();
+        bytes
+    }
+
+    /// Free the output, e.g., on error.
+    pub fn discard(&mut self) {
+        if !self.bytes.is_null() {
+            ffi!(Py_DECREF(self.bytes as *mut PyObject));
+            self.bytes = std::ptr::null_mut();
+        }
+        self.len = 0;
+    }
+
+    fn buffer_ptr(&mut self) -> *mut u8 {
         unsafe {
-            std::mem::transmute::<*mut [c_char; 1], *mut u8>(std::ptr::addr_of_mut!(
-                (*self.bytes).ob_sval
-            ))
-            .add(self.len)
+            if self.bytes.is_null() {
+                (self.stack.as_mut_ptr() as *mut u8).add(self.len)
+            } else {
+                std::mem::transmute::<*mut [c_char; 1], *mut u8>(std::ptr::addr_of_mut!(
+                    (*self.bytes).ob_sval
+                ))
+                .add(self.len)
+            }
         }
     }
 
+    /// Set the capacity to `len`, moving the output from the stack to a bytes
+    /// object if it is not already in one.
     pub fn resize(&mut self, len: usize) {
         self.cap = len;
         unsafe {
-            _PyBytes_Resize(
-                std::ptr::addr_of_mut!(self.bytes) as *mut *mut PyBytesObject as *mut *mut PyObject,
-                len as isize,
-            );
+            if self.bytes.is_null() {
+                self.bytes = PyBytes_FromStringAndSize(std::ptr::null_mut(), len as isize)
+                    as *mut PyBytesObject;
+                std::ptr::copy_nonoverlapping(
+                    self.stack.as_ptr() as *const u8,
+                    std::ptr::addr_of_mut!((*self.bytes).ob_sval) as *mut u8,
+                    self.len,
+                );
+            } else {
+                _PyBytes_Resize(
+                    std::ptr::addr_of_mut!(self.bytes) as *mut *mut PyBytesObject
+                        as *mut *mut PyObject,
+                    len as isize,
+                );
+            }
         }
     }
 
     #[inline(never)]
-    fn grow(&mut self, len: usize) {
+    fn grow(&mut self, len: usize) -> Result<(), EncodeError> {
+        if unlikely!(len > self.max_size) {
+            return Err(EncodeError::MaxSize(self.max_size));
+        }
         let mut cap = self.cap;
         while len >= cap {
             if len < 262144 {
@@ -64,60 +138,100 @@ impl BytesWriter {
                 cap *= 2;
             }
         }
+        self.resize(std::cmp::min(cap, self.max_size));
+        Ok(())
+    }
+
+    /// Grow the allocation to fit `len` bytes without applying `max_size`.
+    #[inline(never)]
+    fn grow_unlimited(&mut self, len: usize) {
+        let mut cap = std::cmp::max(self.cap, BUFFER_LENGTH);
+        while len >= cap {
+            cap *= 2;
+        }
         self.resize(cap);
     }
-}
 
-impl std::io::Write for BytesWriter {
-    fn write(&mut self, buf: &[u8]) -> Result<usize, std::io::Error> {
-        let _ = self.write_all(buf);
-        Ok(buf.len())
+    /// Ensure space for an upper bound of `additional` bytes to be written
+    /// through `cursor()` and committed with `advance()`. `max_size` is
+    /// applied by `advance()` to the bytes actually written.
+    #[inline(always)]
+    pub fn reserve_upper_bound(&mut self, additional: usize) {
+        let end_length = self.len + additional;
+        if unlikely!(end_length > self.cap) {
+            self.grow_unlimited(end_length);
+        }
+    }
+
+    /// Pointer to the end of the written output.
+    #[inline(always)]
+    pub fn cursor(&mut self) -> *mut u8 {
+        self.buffer_ptr()
+    }
+
+    /// Commit `written` bytes written through `cursor()`.
+    #[inline(always)]
+    pub fn advance(&mut self, written: usize) -> Result<(), EncodeError> {
+        self.len += written;
+        if unlikely!(self.len > self.max_size) {
+            return Err(EncodeError::MaxSize(self.max_size));
+        }
+        // the allocation may now exceed max_size, so lower cap for reserve()
+        // to keep applying it
+        self.cap = std::cmp::min(self.cap, self.max_size);
+        Ok(())
     }
 
-    fn write_all(&mut self, buf: &[u8]) -> Result<(), std::io::Error> {
-        let to_write = buf.len();
-        let end_length = self.len + to_write;
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) -> Result<(), EncodeError> {
+        let end_length = self.len + additional;
         if unlikely!(end_length > self.cap) {
-            self.grow(end_length);
+            self.grow(end_length)?;
         }
-        unsafe {
-            std::ptr::copy_nonoverlapping(buf.as_ptr() as *const u8, self.buffer_ptr(), to_write);
-        };
-        self.len = end_length;
         Ok(())
     }
 
-    fn flush(&mut self) -> Result<(), std::io::Error> {
+    #[inline(always)]
+    pub fn write_byte(&mut self, val: u8) -> Result<(), EncodeError> {
+        self.reserve(1)?;
+        unsafe { std::ptr::write(self.buffer_ptr(), val) };
+        self.len += 1;
         Ok(())
     }
-}
 
-impl WriteExt for &mut BytesWriter {
-    fn write_str(&mut self, val: &str) -> Result<(), std::io::Error> {
+    #[inline(always)]
+    pub fn write_bytes(&mut self, val: &[u8]) -> Result<(), EncodeError> {
         let to_write = val.len();
-        let end_length = self.len + to_write + 2;
-        if unlikely!(end_length > self.cap) {
-            self.grow(end_length);
-        }
+        self.reserve(to_write)?;
+        unsafe {
+            std::ptr::copy_nonoverlapping(val.as_ptr(), self.buffer_ptr(), to_write);
+        };
+        self.len += to_write;
+        Ok(())
+    }
+
+    /// Write `val` surrounded by double quotes. It must not need escaping.
+    #[inline(always)]
+    pub fn write_quoted(&mut self, val: &[u8]) -> Result<(), EncodeError> {
+        let to_write = val.len();
+        self.reserve(to_write + 2)?;
         unsafe {
             let ptr = self.buffer_ptr();
             std::ptr::write(ptr, b'"');
-            std::ptr::copy_nonoverlapping(val.as_ptr() as *const u8, ptr.add(1), to_write);
+            std::ptr::copy_nonoverlapping(val.as_ptr(), ptr.add(1), to_write);
             std::ptr::write(ptr.add(to_write + 1), b'"');
         };
-        self.len = end_length;
+        self.len += to_write + 2;
         Ok(())
     }
 
-    fn write_indent(&mut self, len: usize) -> Result<(), std::io::Error> {
-        let end_length = self.len + len;
-        if unlikely!(end_length > self.cap) {
-            self.grow(end_length);
-        }
+    #[inline(always)]
+    pub fn write_indent(&mut self, len: usize) -> Result<(), EncodeError> {
+        self.reserve(len)?;
         unsafe {
             std::ptr::write_bytes(self.buffer_ptr(), b' ', len);
         };
-        self.len = end_length;
+        self.len += len;
         Ok(())
     }
 }
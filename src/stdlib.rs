@@ -0,0 +1,432 @@
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+//! The module `orjson.stdlib`, whose functions have the signatures of those
+//! of the standard library's `json`. A call is done by orjson if it can give
+//! the same result, or else by `json` with the same arguments, so that an
+//! application can `import orjson.stdlib as json`. This is also the case
+//! for input orjson refuses, e.g., a `dict` with `int` keys or `NaN`, so
+//! that the result or exception is that of `json`.
+
+use crate::deserialize::LoadsArgs;
+use crate::opt::*;
+use crate::serialize::{DumpsArgs, Separators};
+use crate::unicode::{unicode_from_str, unicode_to_str};
+use pyo3_ffi::*;
+use std::fmt::Write;
+use std::os::raw::c_char;
+use std::ptr::{null_mut, NonNull};
+
+/// Call the function `name` of `json` with the arguments given to ours.
+#[cold]
+#[inline(never)]
+unsafe fn call_json(name: &str, args: *mut PyObject, kwds: *mut PyObject) -> *mut PyObject {
+    let module = PyImport_ImportModule("json\0".as_ptr() as *const c_char);
+    if module.is_null() {
+        return null_mut();
+    }
+    let func = PyObject_GetAttrString(module, name.as_ptr() as *const c_char);
+    Py_DECREF(module);
+    if func.is_null() {
+        return null_mut();
+    }
+    let res = PyObject_Call(func, args, kwds);
+    Py_DECREF(func);
+    res
+}
+
+/// The truth value of `val`, or `None` if evaluating it raised.
+unsafe fn truth(val: *mut PyObject) -> Option<bool> {
+    match PyObject_IsTrue(val) {
+        1 => Some(true),
+        0 => Some(false),
+        _ => {
+            PyErr_Clear();
+            None
+        }
+    }
+}
+
+/// The arguments of `dumps()` with which orjson serializes as `json.dumps()`
+/// does.
+struct StdlibDumpsArgs {
+    default: Option<NonNull<PyObject>>,
+    opts: Opt,
+    separators: Separators,
+    ensure_ascii: bool,
+}
+
+/// Parse the keyword arguments of `dumps()`, returning `None` if orjson does
+/// not serialize with them as `json.dumps()` does. `datetime` objects and
+/// dataclasses are passed to `default`, as `json` does.
+unsafe fn parse_dumps_kwds(kwds: *mut PyObject) -> Option<StdlibDumpsArgs> {
+    let mut default: Option<NonNull<PyObject>> = None;
+    let mut opts = PASSTHROUGH_DATACLASS | PASSTHROUGH_DATETIME;
+    let mut separators: Option<Separators> = None;
+    let mut ensure_ascii = true;
+    if kwds.is_null() {
+        return Some(StdlibDumpsArgs {
+            default: default,
+            opts: opts,
+            separators: Separators::from_strs(", ", ": "),
+            ensure_ascii: ensure_ascii,
+        });
+    }
+    for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+        match unicode_to_str(arg)? {
+            "skipkeys" => {
+                if truth(val)? {
+                    return None;
+                }
+            }
+            "ensure_ascii" => ensure_ascii = truth(val)?,
+            // nesting is limited regardless
+            "check_circular" => {}
+            // NaN and infinity are refused and so serialized by `json`
+            "allow_nan" => {}
+            "cls" => {
+                if val != crate::typeref::NONE {
+                    return None;
+                }
+            }
+            "indent" => {
+                if val != crate::typeref::NONE {
+                    if PyLong_CheckExact(val) == 0 || PyLong_AsLong(val) != 2 {
+                        PyErr_Clear();
+                        return None;
+                    }
+                    opts |= INDENT_2;
+                }
+            }
+            "separators" => {
+                if val != crate::typeref::NONE {
+                    separators = Some(Separators::new(val)?);
+                }
+            }
+            "default" => {
+                if val != crate::typeref::NONE {
+                    if PyCallable_Check(val) == 0 {
+                        return None;
+                    }
+                    default = Some(NonNull::new_unchecked(val));
+                }
+            }
+            "sort_keys" => {
+                if truth(val)? {
+                    opts |= SORT_KEYS;
+                }
+            }
+            _ => return None,
+        }
+    }
+    // `json` separates items by "," rather than ", " if indenting
+    let separators = separators.unwrap_or_else(|| {
+        if opts & INDENT_2 != 0 {
+            Separators::from_strs(",", ": ")
+        } else {
+            Separators::from_strs(", ", ": ")
+        }
+    });
+    Some(StdlibDumpsArgs {
+        default: default,
+        opts: opts,
+        separators: separators,
+        ensure_ascii: ensure_ascii,
+    })
+}
+
+/// `buf` with each character that is not ASCII, or is DEL, escaped as
+/// `json.dumps()` escapes it with `ensure_ascii`. Such characters can only
+/// be in strings.
+#[cold]
+#[inline(never)]
+fn escape_non_ascii(buf: &str) -> String {
+    let mut res = String::with_capacity(buf.len() * 2);
+    let mut units = [0u16; 2];
+    for each in buf.chars() {
+        if each.is_ascii() && each != '\x7f' {
+            res.push(each);
+        } else {
+            for unit in each.encode_utf16(&mut units) {
+                write!(res, "\\u{:04x}", unit).unwrap();
+            }
+        }
+    }
+    res
+}
+
+/// Serialize `args[0]` to a `str` as `json.dumps(*args, **kwds)` does.
+unsafe fn stdlib_dumps_impl(args: *mut PyObject, kwds: *mut PyObject) -> *mut PyObject {
+    if Py_SIZE(args) != 1 {
+        return call_json("dumps\0", args, kwds);
+    }
+    let parsed = match parse_dumps_kwds(kwds) {
+        Some(parsed) => parsed,
+        None => return call_json("dumps\0", args, kwds),
+    };
+    let dumps_args = DumpsArgs {
+        separators: Some(parsed.separators),
+        repr_floats: true,
+        ..Default::default()
+    };
+    let obj = PyTuple_GET_ITEM(args, 0);
+    let bytes = match crate::serialize::serialize(obj, parsed.default, parsed.opts, dumps_args) {
+        Ok(val) => val.as_ptr(),
+        Err(_) => {
+            PyErr_Clear();
+            return call_json("dumps\0", args, kwds);
+        }
+    };
+    let buf = str_from_slice!(
+        crate::ffi::PyBytes_AS_STRING(bytes) as *const u8,
+        crate::ffi::PyBytes_GET_SIZE(bytes)
+    );
+    let res = if parsed.ensure_ascii && buf.bytes().any(|each| each >= 0x7f) {
+        unicode_from_str(&escape_non_ascii(buf))
+    } else {
+        unicode_from_str(buf)
+    };
+    Py_DECREF(bytes);
+    res
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn stdlib_dumps(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    stdlib_dumps_impl(args, kwds)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn stdlib_dump(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if Py_SIZE(args) != 2 {
+        return call_json("dump\0", args, kwds);
+    }
+    let obj_args = PyTuple_GetSlice(args, 0, 1);
+    let contents = stdlib_dumps_impl(obj_args, kwds);
+    Py_DECREF(obj_args);
+    if contents.is_null() {
+        return null_mut();
+    }
+    let write = PyObject_GetAttrString(
+        PyTuple_GET_ITEM(args, 1),
+        "write\0".as_ptr() as *const c_char,
+    );
+    if write.is_null() {
+        Py_DECREF(contents);
+        return null_mut();
+    }
+    let res = PyObject_CallFunctionObjArgs(write, contents, null_mut::<PyObject>());
+    Py_DECREF(write);
+    Py_DECREF(contents);
+    if res.is_null() {
+        return null_mut();
+    }
+    Py_DECREF(res);
+    Py_INCREF(crate::typeref::NONE);
+    crate::typeref::NONE
+}
+
+/// Parse the keyword arguments of `loads()`, returning `None` if orjson does
+/// not deserialize with them as `json.loads()` does. `NaN` and infinity are
+/// allowed, as `json` allows them.
+unsafe fn parse_loads_kwds(kwds: *mut PyObject) -> Option<LoadsArgs> {
+    let mut res = LoadsArgs::default();
+    res.allow_nan = true;
+    if kwds.is_null() {
+        return Some(res);
+    }
+    for (arg, val) in crate::ffi::PyDictIter::from_pyobject(kwds) {
+        let hook = if val == crate::typeref::NONE {
+            None
+        } else if PyCallable_Check(val) != 0 {
+            Some(NonNull::new_unchecked(val))
+        } else {
+            return None;
+        };
+        match unicode_to_str(arg)? {
+            "object_hook" => res.object_hook = hook,
+            "parse_float" => res.parse_float = hook,
+            "parse_int" => res.parse_int = hook,
+            "object_pairs_hook" => res.object_pairs_hook = hook,
+            "cls" | "parse_constant" => {
+                if hook.is_some() {
+                    return None;
+                }
+            }
+            // control characters in strings are refused regardless
+            "strict" => {
+                if !truth(val)? {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(res)
+}
+
+/// Deserialize `args[0]` as `json.loads(*args, **kwds)` does.
+unsafe fn stdlib_loads_impl(args: *mut PyObject, kwds: *mut PyObject) -> *mut PyObject {
+    if Py_SIZE(args) != 1 {
+        return call_json("loads\0", args, kwds);
+    }
+    let obj = PyTuple_GET_ITEM(args, 0);
+    if PyUnicode_Check(obj) == 0 && PyBytes_Check(obj) == 0 && PyByteArray_Check(obj) == 0 {
+        return call_json("loads\0", args, kwds);
+    }
+    let loads_args = match parse_loads_kwds(kwds) {
+        Some(loads_args) => loads_args,
+        None => return call_json("loads\0", args, kwds),
+    };
+    match crate::deserialize::deserialize(obj, &loads_args) {
+        Ok(val) => val.as_ptr(),
+        Err(Some(_)) => call_json("loads\0", args, kwds),
+        Err(None) => null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn stdlib_loads(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    stdlib_loads_impl(args, kwds)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn stdlib_load(
+    _self: *mut PyObject,
+    args: *mut PyObject,
+    kwds: *mut PyObject,
+) -> *mut PyObject {
+    if Py_SIZE(args) != 1 {
+        return call_json("load\0", args, kwds);
+    }
+    let contents = PyObject_CallMethodObjArgs(
+        PyTuple_GET_ITEM(args, 0),
+        crate::typeref::READ_METHOD_STR,
+        null_mut::<PyObject>(),
+    );
+    if contents.is_null() {
+        return null_mut();
+    }
+    let contents_args = PyTuple_Pack(1, contents);
+    Py_DECREF(contents);
+    let res = stdlib_loads_impl(contents_args, kwds);
+    Py_DECREF(contents_args);
+    res
+}
+
+/// Set the attribute `name` of `module` to `obj`, consuming the reference.
+unsafe fn add(module: *mut PyObject, name: &str, obj: *mut PyObject) {
+    PyObject_SetAttrString(module, name.as_ptr() as *const c_char, obj);
+    Py_XDECREF(obj);
+}
+
+/// Add the function `name` of `orjson.stdlib` to `module`.
+unsafe fn add_function(
+    module: *mut PyObject,
+    name: &'static str,
+    doc: &'static str,
+    func: PyCFunctionWithKeywords,
+) {
+    let def = PyMethodDef {
+        ml_name: name.as_ptr() as *const c_char,
+        ml_meth: PyMethodDefPointer {
+            PyCFunctionWithKeywords: func,
+        },
+        ml_flags: METH_VARARGS | METH_KEYWORDS,
+        ml_doc: doc.as_ptr() as *const c_char,
+    };
+    let module_name = PyModule_GetNameObject(module);
+    let func = PyCFunction_NewEx(Box::into_raw(Box::new(def)), null_mut(), module_name);
+    Py_DECREF(module_name);
+    add(module, name, func);
+}
+
+/// Create the module `orjson.stdlib` and add it to `sys.modules` so that it
+/// can be imported however `orjson` is packaged.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+pub unsafe fn create_stdlib_module() -> *mut PyObject {
+    let name = PyUnicode_InternFromString("orjson.stdlib\0".as_ptr() as *const c_char);
+    let module = PyModule_NewObject(name);
+    if module.is_null() {
+        Py_DECREF(name);
+        return null_mut();
+    }
+    PyModule_SetDocString(
+        module,
+        "Serialize and deserialize JSON with the signatures of the json module, falling back to it where orjson differs.\0".as_ptr() as *const c_char,
+    );
+    add_function(
+        module,
+        "dumps\0",
+        "dumps(obj, *, skipkeys=False, ensure_ascii=True, check_circular=True, allow_nan=True, cls=None, indent=None, separators=None, default=None, sort_keys=False, **kw)\n--\n\nSerialize obj to a JSON formatted str as json.dumps() does.\0",
+        stdlib_dumps,
+    );
+    add_function(
+        module,
+        "dump\0",
+        "dump(obj, fp, *, skipkeys=False, ensure_ascii=True, check_circular=True, allow_nan=True, cls=None, indent=None, separators=None, default=None, sort_keys=False, **kw)\n--\n\nSerialize obj as a JSON formatted stream to fp as json.dump() does.\0",
+        stdlib_dump,
+    );
+    add_function(
+        module,
+        "loads\0",
+        "loads(s, *, cls=None, object_hook=None, parse_float=None, parse_int=None, parse_constant=None, object_pairs_hook=None, **kw)\n--\n\nDeserialize s, a str, bytes, or bytearray, as json.loads() does.\0",
+        stdlib_loads,
+    );
+    add_function(
+        module,
+        "load\0",
+        "load(fp, *, cls=None, object_hook=None, parse_float=None, parse_int=None, parse_constant=None, object_pairs_hook=None, **kw)\n--\n\nDeserialize fp.read() as json.load() does.\0",
+        stdlib_load,
+    );
+    let json = PyImport_ImportModule("json\0".as_ptr() as *const c_char);
+    if json.is_null() {
+        Py_DECREF(name);
+        Py_DECREF(module);
+        return null_mut();
+    }
+    for attr in ["JSONDecodeError\0", "JSONDecoder\0", "JSONEncoder\0"] {
+        add(
+            module,
+            attr,
+            PyObject_GetAttrString(json, attr.as_ptr() as *const c_char),
+        );
+    }
+    Py_DECREF(json);
+    let all = [
+        "dump\0",
+        "dumps\0",
+        "load\0",
+        "loads\0",
+        "JSONDecoder\0",
+        "JSONDecodeError\0",
+        "JSONEncoder\0",
+    ];
+    let pyall = PyTuple_New(all.len() as isize);
+    for (i, obj) in all.iter().enumerate() {
+        PyTuple_SET_ITEM(
+            pyall,
+            i as isize,
+            PyUnicode_InternFromString(obj.as_ptr() as *const c_char),
+        )
+    }
+    add(module, "__all__\0", pyall);
+    // a module executed again leaves the first imported
+    let modules = PyImport_GetModuleDict();
+    if PyDict_Contains(modules, name) == 0 {
+        PyDict_SetItem(modules, name, module);
+    }
+    Py_DECREF(name);
+    module
+}
@@ -21,8 +21,63 @@ pub struct NumpyTypes {
     pub datetime64: *mut PyTypeObject,
 }
 
+/// What `loads_into()` uses to read the type hints of a dataclass.
+pub struct TypingTypes {
+    pub get_type_hints: *mut PyObject,
+    pub any: *mut PyObject,
+    pub union: *mut PyObject,
+    // `types.UnionType`, the type of `X | Y`, or null before Python 3.10.
+    pub union_type: *mut PyObject,
+    pub missing: *mut PyObject,
+}
+
 pub static mut DEFAULT: *mut PyObject = 0 as *mut PyObject;
 pub static mut OPTION: *mut PyObject = 0 as *mut PyObject;
+pub static mut MAX_SIZE: *mut PyObject = 0 as *mut PyObject;
+pub static mut MAX_DEPTH: *mut PyObject = 0 as *mut PyObject;
+pub static mut STATS: *mut PyObject = 0 as *mut PyObject;
+pub static mut FLOAT_PRECISION: *mut PyObject = 0 as *mut PyObject;
+pub static mut REDACT_KEYS: *mut PyObject = 0 as *mut PyObject;
+pub static mut INCLUDE_KEYS: *mut PyObject = 0 as *mut PyObject;
+pub static mut MAX_STR_LEN: *mut PyObject = 0 as *mut PyObject;
+pub static mut TRANSFORM: *mut PyObject = 0 as *mut PyObject;
+pub static mut SEPARATORS: *mut PyObject = 0 as *mut PyObject;
+pub static mut CHUNK_SIZE: *mut PyObject = 0 as *mut PyObject;
+pub static mut LINES: *mut PyObject = 0 as *mut PyObject;
+pub static mut COMPRESSION: *mut PyObject = 0 as *mut PyObject;
+pub static mut PARSE_FLOAT: *mut PyObject = 0 as *mut PyObject;
+pub static mut PARSE_INT: *mut PyObject = 0 as *mut PyObject;
+pub static mut OBJECT_HOOK: *mut PyObject = 0 as *mut PyObject;
+pub static mut OBJECT_PAIRS_HOOK: *mut PyObject = 0 as *mut PyObject;
+pub static mut DICT_CLASS: *mut PyObject = 0 as *mut PyObject;
+pub static mut DUPLICATE_KEYS: *mut PyObject = 0 as *mut PyObject;
+pub static mut SURROGATES: *mut PyObject = 0 as *mut PyObject;
+pub static mut REPLACE_INVALID_UTF8: *mut PyObject = 0 as *mut PyObject;
+pub static mut ALLOW_NAN: *mut PyObject = 0 as *mut PyObject;
+pub static mut STRICT: *mut PyObject = 0 as *mut PyObject;
+pub static mut JSONC: *mut PyObject = 0 as *mut PyObject;
+pub static mut MAX_ITEMS: *mut PyObject = 0 as *mut PyObject;
+pub static mut MAX_STRING_LEN: *mut PyObject = 0 as *mut PyObject;
+pub static mut MAX_STRING_BYTES: *mut PyObject = 0 as *mut PyObject;
+pub static mut MAX_BYTES: *mut PyObject = 0 as *mut PyObject;
+pub static mut TUPLES: *mut PyObject = 0 as *mut PyObject;
+pub static mut IMMUTABLE: *mut PyObject = 0 as *mut PyObject;
+pub static mut DATETIMES: *mut PyObject = 0 as *mut PyObject;
+pub static mut UUIDS: *mut PyObject = 0 as *mut PyObject;
+pub static mut NUMPY: *mut PyObject = 0 as *mut PyObject;
+pub static mut RAW_NUMBERS: *mut PyObject = 0 as *mut PyObject;
+pub static mut OFFSET: *mut PyObject = 0 as *mut PyObject;
+pub static mut LENGTH: *mut PyObject = 0 as *mut PyObject;
+pub static mut THREADS: *mut PyObject = 0 as *mut PyObject;
+pub static mut SCHEMA: *mut PyObject = 0 as *mut PyObject;
+pub static mut POSITION: *mut PyObject = 0 as *mut PyObject;
+pub static mut MAX_ERRORS: *mut PyObject = 0 as *mut PyObject;
+pub static mut PATHS: *mut PyObject = 0 as *mut PyObject;
+pub static mut OPTION_KWARG_NAMES: [*mut PyObject; crate::opt::OPTION_KWARGS.len()] =
+    [0 as *mut PyObject; crate::opt::OPTION_KWARGS.len()];
+
+// Whether ints can be read with crate::ffi::PyLong_AsSmallValue().
+pub static mut PYLONG_SMALL_VALUE: bool = false;
 
 pub static mut NONE: *mut PyObject = 0 as *mut PyObject;
 pub static mut TRUE: *mut PyObject = 0 as *mut PyObject;
@@ -44,27 +99,58 @@ pub static mut TIME_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
 pub static mut TUPLE_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
 pub static mut UUID_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
 pub static mut ENUM_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut DICT_ITEMS_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut COMPILED_SERIALIZER_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut ASYNC_WRITER_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut ASYNC_DUMP_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut FILE_TASK_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut ARRAY_ITERATOR_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut LINES_ITERATOR_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut DOCUMENTS_ITERATOR_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut TOKEN_ITERATOR_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut DECODER_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut PARSER_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut SCHEMA_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
+pub static mut RAW_NUMBER_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
 
 #[cfg(Py_3_9)]
 pub static mut ZONEINFO_TYPE: *mut PyTypeObject = 0 as *mut PyTypeObject;
 
 pub static mut NUMPY_TYPES: Lazy<Option<NumpyTypes>> = Lazy::new(|| unsafe { load_numpy_types() });
 pub static mut FIELD_TYPE: Lazy<NonNull<PyObject>> = Lazy::new(|| unsafe { look_up_field_type() });
+pub static mut TYPING_TYPES: Lazy<TypingTypes> = Lazy::new(|| unsafe { look_up_typing_types() });
+pub static mut MAPPING_TYPE: Lazy<NonNull<PyObject>> =
+    Lazy::new(|| unsafe { look_up_abc_type("Mapping\0") });
+pub static mut SEQUENCE_TYPE: Lazy<NonNull<PyObject>> =
+    Lazy::new(|| unsafe { look_up_abc_type("Sequence\0") });
+pub static mut USER_STRING_TYPE: Lazy<*mut PyTypeObject> = Lazy::new(|| unsafe {
+    look_up_module_attr("collections\0", "UserString\0") as *mut PyTypeObject
+});
 
 pub static mut INT_ATTR_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut UTCOFFSET_METHOD_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut NORMALIZE_METHOD_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut CONVERT_METHOD_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut EMPTY_UNICODE: *mut PyObject = 0 as *mut PyObject;
+pub static mut TOKEN_EVENTS: [*mut PyObject; 9] = [0 as *mut PyObject; 9];
 pub static mut DST_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut DICT_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut DATACLASS_FIELDS_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut SLOTS_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut FIELD_TYPE_STR: *mut PyObject = 0 as *mut PyObject;
+pub static mut GETALL_STR: *mut PyObject = 0 as *mut PyObject;
+pub static mut METADATA_STR: *mut PyObject = 0 as *mut PyObject;
+pub static mut TYPE_STR: *mut PyObject = 0 as *mut PyObject;
+pub static mut ANNOTATIONS_STR: *mut PyObject = 0 as *mut PyObject;
+pub static mut TOTAL_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut ARRAY_STRUCT_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut DTYPE_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut DESCR_STR: *mut PyObject = 0 as *mut PyObject;
 pub static mut VALUE_STR: *mut PyObject = 0 as *mut PyObject;
+pub static mut GEO_INTERFACE_STR: *mut PyObject = 0 as *mut PyObject;
+pub static mut READ_METHOD_STR: *mut PyObject = 0 as *mut PyObject;
+pub static mut READINTO_METHOD_STR: *mut PyObject = 0 as *mut PyObject;
+pub static mut RELEASE_METHOD_STR: *mut PyObject = 0 as *mut PyObject;
 
 pub static mut STR_HASH_FUNCTION: Option<hashfunc> = None;
 
@@ -102,6 +188,8 @@ pub static mut YYJSON_ALLOC: Lazy<crate::yyjson::yyjson_alc> = Lazy::new(|| unsa
 #[allow(non_upper_case_globals)]
 pub static mut JsonEncodeError: *mut PyObject = 0 as *mut PyObject;
 #[allow(non_upper_case_globals)]
+pub static mut JsonEncodeSizeError: *mut PyObject = 0 as *mut PyObject;
+#[allow(non_upper_case_globals)]
 pub static mut JsonDecodeError: *mut PyObject = 0 as *mut PyObject;
 
 static INIT: Once = Once::new();
@@ -113,6 +201,18 @@ pub fn init_typerefs() {
         assert!(crate::deserialize::KEY_MAP
             .set(crate::deserialize::KeyMap::default())
             .is_ok());
+        assert!(crate::serialize::DATACLASS_META
+            .set(crate::serialize::DataclassMetaMap::default())
+            .is_ok());
+        assert!(crate::deserialize::CLASS_TARGETS
+            .set(crate::deserialize::ClassTargetMap::default())
+            .is_ok());
+        assert!(crate::serialize::OBTYPE_CACHE
+            .set(crate::serialize::ObTypeMap::default())
+            .is_ok());
+        assert!(crate::serialize::SHARED_KEYS
+            .set(crate::serialize::SharedKeysMap::default())
+            .is_ok());
         PyDateTime_IMPORT();
         NONE = Py_None();
         TRUE = Py_True();
@@ -138,12 +238,27 @@ pub fn init_typerefs() {
         NONE_TYPE = (*NONE).ob_type;
         BOOL_TYPE = (*TRUE).ob_type;
         INT_TYPE = (*PyLong_FromLongLong(0)).ob_type;
+        PYLONG_SMALL_VALUE = has_pylong_digit_size();
         FLOAT_TYPE = (*PyFloat_FromDouble(0.0)).ob_type;
         DATETIME_TYPE = look_up_datetime_type();
         DATE_TYPE = look_up_date_type();
         TIME_TYPE = look_up_time_type();
         UUID_TYPE = look_up_uuid_type();
         ENUM_TYPE = look_up_enum_type();
+        DICT_ITEMS_TYPE = look_up_dict_items_type();
+        COMPILED_SERIALIZER_TYPE = crate::serialize::create_compiled_serializer_type();
+        ASYNC_WRITER_TYPE = crate::serialize::create_async_writer_type();
+        ASYNC_DUMP_TYPE = crate::serialize::create_async_dump_type();
+        FILE_TASK_TYPE = crate::aio::create_file_task_type();
+        ARRAY_ITERATOR_TYPE = crate::deserialize::create_iterator_type("orjson.ArrayIterator\0");
+        LINES_ITERATOR_TYPE = crate::deserialize::create_iterator_type("orjson.LinesIterator\0");
+        DOCUMENTS_ITERATOR_TYPE =
+            crate::deserialize::create_iterator_type("orjson.DocumentsIterator\0");
+        TOKEN_ITERATOR_TYPE = crate::deserialize::create_iterator_type("orjson.TokenIterator\0");
+        DECODER_TYPE = crate::deserialize::create_decoder_type();
+        PARSER_TYPE = crate::deserialize::create_parser_type();
+        SCHEMA_TYPE = crate::deserialize::create_schema_type();
+        RAW_NUMBER_TYPE = crate::deserialize::create_raw_number_type();
 
         #[cfg(Py_3_9)]
         {
@@ -160,19 +275,100 @@ pub fn init_typerefs() {
             PyUnicode_InternFromString("__dataclass_fields__\0".as_ptr() as *const c_char);
         SLOTS_STR = PyUnicode_InternFromString("__slots__\0".as_ptr() as *const c_char);
         FIELD_TYPE_STR = PyUnicode_InternFromString("_field_type\0".as_ptr() as *const c_char);
+        GETALL_STR = PyUnicode_InternFromString("getall\0".as_ptr() as *const c_char);
+        METADATA_STR = PyUnicode_InternFromString("metadata\0".as_ptr() as *const c_char);
+        TYPE_STR = PyUnicode_InternFromString("type\0".as_ptr() as *const c_char);
+        ANNOTATIONS_STR = PyUnicode_InternFromString("__annotations__\0".as_ptr() as *const c_char);
+        TOTAL_STR = PyUnicode_InternFromString("__total__\0".as_ptr() as *const c_char);
         ARRAY_STRUCT_STR =
             PyUnicode_InternFromString("__array_struct__\0".as_ptr() as *const c_char);
         DTYPE_STR = PyUnicode_InternFromString("dtype\0".as_ptr() as *const c_char);
         DESCR_STR = PyUnicode_InternFromString("descr\0".as_ptr() as *const c_char);
         VALUE_STR = PyUnicode_InternFromString("value\0".as_ptr() as *const c_char);
+        GEO_INTERFACE_STR =
+            PyUnicode_InternFromString("__geo_interface__\0".as_ptr() as *const c_char);
+        READ_METHOD_STR = PyUnicode_InternFromString("read\0".as_ptr() as *const c_char);
+        READINTO_METHOD_STR = PyUnicode_InternFromString("readinto\0".as_ptr() as *const c_char);
+        RELEASE_METHOD_STR = PyUnicode_InternFromString("release\0".as_ptr() as *const c_char);
         DEFAULT = PyUnicode_InternFromString("default\0".as_ptr() as *const c_char);
         OPTION = PyUnicode_InternFromString("option\0".as_ptr() as *const c_char);
+        MAX_SIZE = PyUnicode_InternFromString("max_size\0".as_ptr() as *const c_char);
+        MAX_DEPTH = PyUnicode_InternFromString("max_depth\0".as_ptr() as *const c_char);
+        STATS = PyUnicode_InternFromString("stats\0".as_ptr() as *const c_char);
+        FLOAT_PRECISION = PyUnicode_InternFromString("float_precision\0".as_ptr() as *const c_char);
+        REDACT_KEYS = PyUnicode_InternFromString("redact_keys\0".as_ptr() as *const c_char);
+        INCLUDE_KEYS = PyUnicode_InternFromString("include_keys\0".as_ptr() as *const c_char);
+        MAX_STR_LEN = PyUnicode_InternFromString("max_str_len\0".as_ptr() as *const c_char);
+        TRANSFORM = PyUnicode_InternFromString("transform\0".as_ptr() as *const c_char);
+        SEPARATORS = PyUnicode_InternFromString("separators\0".as_ptr() as *const c_char);
+        CHUNK_SIZE = PyUnicode_InternFromString("chunk_size\0".as_ptr() as *const c_char);
+        LINES = PyUnicode_InternFromString("lines\0".as_ptr() as *const c_char);
+        COMPRESSION = PyUnicode_InternFromString("compression\0".as_ptr() as *const c_char);
+        PARSE_FLOAT = PyUnicode_InternFromString("parse_float\0".as_ptr() as *const c_char);
+        PARSE_INT = PyUnicode_InternFromString("parse_int\0".as_ptr() as *const c_char);
+        OBJECT_HOOK = PyUnicode_InternFromString("object_hook\0".as_ptr() as *const c_char);
+        OBJECT_PAIRS_HOOK =
+            PyUnicode_InternFromString("object_pairs_hook\0".as_ptr() as *const c_char);
+        DICT_CLASS = PyUnicode_InternFromString("dict_class\0".as_ptr() as *const c_char);
+        DUPLICATE_KEYS = PyUnicode_InternFromString("duplicate_keys\0".as_ptr() as *const c_char);
+        SURROGATES = PyUnicode_InternFromString("surrogates\0".as_ptr() as *const c_char);
+        REPLACE_INVALID_UTF8 =
+            PyUnicode_InternFromString("replace_invalid_utf8\0".as_ptr() as *const c_char);
+        ALLOW_NAN = PyUnicode_InternFromString("allow_nan\0".as_ptr() as *const c_char);
+        STRICT = PyUnicode_InternFromString("strict\0".as_ptr() as *const c_char);
+        JSONC = PyUnicode_InternFromString("jsonc\0".as_ptr() as *const c_char);
+        MAX_ITEMS = PyUnicode_InternFromString("max_items\0".as_ptr() as *const c_char);
+        MAX_STRING_LEN = PyUnicode_InternFromString("max_string_len\0".as_ptr() as *const c_char);
+        MAX_STRING_BYTES =
+            PyUnicode_InternFromString("max_string_bytes\0".as_ptr() as *const c_char);
+        MAX_BYTES = PyUnicode_InternFromString("max_bytes\0".as_ptr() as *const c_char);
+        TUPLES = PyUnicode_InternFromString("tuples\0".as_ptr() as *const c_char);
+        IMMUTABLE = PyUnicode_InternFromString("immutable\0".as_ptr() as *const c_char);
+        DATETIMES = PyUnicode_InternFromString("datetimes\0".as_ptr() as *const c_char);
+        UUIDS = PyUnicode_InternFromString("uuids\0".as_ptr() as *const c_char);
+        NUMPY = PyUnicode_InternFromString("numpy\0".as_ptr() as *const c_char);
+        RAW_NUMBERS = PyUnicode_InternFromString("raw_numbers\0".as_ptr() as *const c_char);
+        OFFSET = PyUnicode_InternFromString("offset\0".as_ptr() as *const c_char);
+        LENGTH = PyUnicode_InternFromString("length\0".as_ptr() as *const c_char);
+        THREADS = PyUnicode_InternFromString("threads\0".as_ptr() as *const c_char);
+        SCHEMA = PyUnicode_InternFromString("schema\0".as_ptr() as *const c_char);
+        PATHS = PyUnicode_InternFromString("paths\0".as_ptr() as *const c_char);
+        POSITION = PyUnicode_InternFromString("position\0".as_ptr() as *const c_char);
+        MAX_ERRORS = PyUnicode_InternFromString("max_errors\0".as_ptr() as *const c_char);
+        for (i, name) in crate::deserialize::EVENT_NAMES.iter().enumerate() {
+            TOKEN_EVENTS[i] = PyUnicode_InternFromString(name.as_ptr() as *const c_char);
+        }
+        for (i, (name, _)) in crate::opt::OPTION_KWARGS.iter().enumerate() {
+            OPTION_KWARG_NAMES[i] = PyUnicode_InternFromString(name.as_ptr() as *const c_char);
+        }
         JsonEncodeError = pyo3_ffi::PyExc_TypeError;
         Py_INCREF(JsonEncodeError);
+        JsonEncodeSizeError = pyo3_ffi::PyErr_NewException(
+            "orjson.JSONEncodeSizeError\0".as_ptr() as *const c_char,
+            JsonEncodeError,
+            std::ptr::null_mut(),
+        );
+        Py_INCREF(JsonEncodeSizeError);
         JsonDecodeError = look_up_json_exc();
     });
 }
 
+/// Whether `int` digits are `crate::ffi::PYLONG_SHIFT` bits and laid out as
+/// `crate::ffi::PyLongObject` describes. CPython configured with
+/// `--enable-big-digits=15` uses 15-bit digits.
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe fn has_pylong_digit_size() -> bool {
+    let val = PyLong_FromLongLong((1 << crate::ffi::PYLONG_SHIFT) - 1);
+    let one_digit = Py_SIZE(val) == 1
+        && crate::ffi::PyLong_AsSmallValue(val) == Some((1 << crate::ffi::PYLONG_SHIFT) - 1);
+    Py_DECREF(val);
+    let val = PyLong_FromLongLong(1 << crate::ffi::PYLONG_SHIFT);
+    let two_digits = Py_SIZE(val) == 2;
+    Py_DECREF(val);
+    one_digit && two_digits
+}
+
 #[cold]
 #[cfg_attr(feature = "optimize", optimize(size))]
 unsafe fn look_up_json_exc() -> *mut PyObject {
@@ -240,6 +436,42 @@ unsafe fn look_up_field_type() -> NonNull<PyObject> {
     NonNull::new_unchecked(ptr as *mut PyObject)
 }
 
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe fn look_up_module_attr(module_name: &str, name: &str) -> *mut PyObject {
+    let module = PyImport_ImportModule(module_name.as_ptr() as *const c_char);
+    let ptr = PyObject_GetAttrString(module, name.as_ptr() as *const c_char);
+    Py_DECREF(module);
+    ptr
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe fn look_up_typing_types() -> TypingTypes {
+    let union_type = look_up_module_attr("types\0", "UnionType\0");
+    if union_type.is_null() {
+        PyErr_Clear();
+    }
+    TypingTypes {
+        get_type_hints: look_up_module_attr("typing\0", "get_type_hints\0"),
+        any: look_up_module_attr("typing\0", "Any\0"),
+        union: look_up_module_attr("typing\0", "Union\0"),
+        union_type: union_type,
+        missing: look_up_module_attr("dataclasses\0", "MISSING\0"),
+    }
+}
+
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe fn look_up_abc_type(name: &str) -> NonNull<PyObject> {
+    let module = PyImport_ImportModule("collections.abc\0".as_ptr() as *const c_char);
+    let module_dict = PyObject_GenericGetDict(module, std::ptr::null_mut());
+    let ptr = PyMapping_GetItemString(module_dict, name.as_ptr() as *const c_char);
+    Py_DECREF(module_dict);
+    Py_DECREF(module);
+    NonNull::new_unchecked(ptr)
+}
+
 #[cold]
 #[cfg_attr(feature = "optimize", optimize(size))]
 unsafe fn look_up_enum_type() -> *mut PyTypeObject {
@@ -302,6 +534,19 @@ unsafe fn look_up_time_type() -> *mut PyTypeObject {
     ptr
 }
 
+#[cold]
+#[cfg_attr(feature = "optimize", optimize(size))]
+unsafe fn look_up_dict_items_type() -> *mut PyTypeObject {
+    let dict = PyDict_New();
+    let method = PyObject_GetAttrString(dict, "items\0".as_ptr() as *const c_char);
+    let items = PyObject_CallObject(method, std::ptr::null_mut());
+    let ptr = (*items).ob_type;
+    Py_DECREF(items);
+    Py_DECREF(method);
+    Py_DECREF(dict);
+    ptr
+}
+
 #[cfg(Py_3_9)]
 #[cold]
 #[cfg_attr(feature = "optimize", optimize(size))]
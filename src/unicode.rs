@@ -3,7 +3,7 @@
 use crate::typeref::EMPTY_UNICODE;
 use crate::typeref::STR_HASH_FUNCTION;
 use pyo3_ffi::*;
-use std::os::raw::c_char;
+use std::os::raw::{c_char, c_void};
 
 // see unicodeobject.h for documentation
 // re: python3.12 changes, https://www.python.org/dev/peps/pep-0623/
@@ -40,6 +40,14 @@ const STATE_COMPACT: u32 = 0b00000000000000000000000000010000;
 
 const STATE_COMPACT_ASCII: u32 = STATE_COMPACT | STATE_ASCII;
 
+#[cfg(not(Py_3_12))]
+const STATE_KIND_SHIFT: u32 = 2;
+
+#[cfg(Py_3_12)]
+const STATE_KIND_SHIFT: u32 = 1;
+
+const STATE_KIND_MASK: u32 = 0b111;
+
 fn is_four_byte(buf: &str) -> bool {
     let mut ret = false;
     for &each in buf.as_bytes() {
@@ -115,6 +123,26 @@ pub fn unicode_from_str(buf: &str) -> *mut pyo3_ffi::PyObject {
     }
 }
 
+/// Whether `buf` has a surrogate encoded as UTF-8 would encode it if it were
+/// a character, which is not valid UTF-8.
+pub fn has_surrogate(buf: &[u8]) -> bool {
+    buf.windows(2)
+        .any(|each| each[0] == 0xED && each[1] >= 0xA0)
+}
+
+/// Make a `str` of `buf`, which may have surrogates as `has_surrogate()`
+/// finds them, as the `surrogatepass` error handler decodes them.
+#[cold]
+pub fn unicode_from_wtf8(buf: &[u8]) -> *mut pyo3_ffi::PyObject {
+    unsafe {
+        PyUnicode_DecodeUTF8(
+            buf.as_ptr() as *const c_char,
+            buf.len() as isize,
+            "surrogatepass\0".as_ptr() as *const c_char,
+        )
+    }
+}
+
 #[inline]
 pub fn hash_str(op: *mut PyObject) -> Py_hash_t {
     unsafe {
@@ -123,6 +151,56 @@ pub fn hash_str(op: *mut PyObject) -> Py_hash_t {
     }
 }
 
+/// The contents of a `str` in the representation CPython stores.
+pub enum UnicodeData {
+    Ascii(&'static [u8]),
+    /// The UTF-8 copy cached on the object or created for it.
+    Utf8(&'static str),
+    Ucs1(&'static [u8]),
+    Ucs2(&'static [u16]),
+    Ucs4(&'static [u32]),
+}
+
+/// Read a `str` without creating the UTF-8 copy that `unicode_to_str()` may
+/// cache on it. Strings that are not compact, i.e., subclasses and strings
+/// created by deprecated APIs, are converted to UTF-8.
+#[inline(always)]
+pub fn unicode_data(op: *mut PyObject) -> Option<UnicodeData> {
+    unsafe {
+        let state = (*op.cast::<PyASCIIObject>()).state;
+        if state & STATE_COMPACT_ASCII == STATE_COMPACT_ASCII {
+            let ptr = op.cast::<PyASCIIObject>().offset(1) as *const u8;
+            let len = (*op.cast::<PyASCIIObject>()).length as usize;
+            Some(UnicodeData::Ascii(std::slice::from_raw_parts(ptr, len)))
+        } else if state & STATE_COMPACT == STATE_COMPACT {
+            if !(*op.cast::<PyCompactUnicodeObject>()).utf8.is_null() {
+                let ptr = (*op.cast::<PyCompactUnicodeObject>()).utf8 as *const u8;
+                let len = (*op.cast::<PyCompactUnicodeObject>()).utf8_length as usize;
+                return Some(UnicodeData::Utf8(str_from_slice!(ptr, len)));
+            }
+            let data = op.cast::<PyCompactUnicodeObject>().offset(1) as *const c_void;
+            let len = (*op.cast::<PyASCIIObject>()).length as usize;
+            match (state >> STATE_KIND_SHIFT) & STATE_KIND_MASK {
+                1 => Some(UnicodeData::Ucs1(std::slice::from_raw_parts(
+                    data as *const u8,
+                    len,
+                ))),
+                2 => Some(UnicodeData::Ucs2(std::slice::from_raw_parts(
+                    data as *const u16,
+                    len,
+                ))),
+                4 => Some(UnicodeData::Ucs4(std::slice::from_raw_parts(
+                    data as *const u32,
+                    len,
+                ))),
+                _ => unicode_to_str_via_ffi(op).map(UnicodeData::Utf8),
+            }
+        } else {
+            unicode_to_str_via_ffi(op).map(UnicodeData::Utf8)
+        }
+    }
+}
+
 #[inline(never)]
 pub fn unicode_to_str_via_ffi(op: *mut PyObject) -> Option<&'static str> {
     let mut str_size: pyo3_ffi::Py_ssize_t = 0;
@@ -27,6 +27,14 @@ extern "C" {
 }
 pub type yyjson_read_flag = u32;
 pub const YYJSON_READ_NOFLAG: yyjson_read_flag = 0;
+pub const YYJSON_READ_STOP_WHEN_DONE: yyjson_read_flag = 1 << 1;
+pub const YYJSON_READ_ALLOW_TRAILING_COMMAS: yyjson_read_flag = 1 << 2;
+pub const YYJSON_READ_ALLOW_COMMENTS: yyjson_read_flag = 1 << 3;
+pub const YYJSON_READ_ALLOW_INF_AND_NAN: yyjson_read_flag = 1 << 4;
+pub const YYJSON_READ_NUMBER_AS_RAW: yyjson_read_flag = 1 << 5;
+pub const YYJSON_READ_BIGNUM_AS_RAW: yyjson_read_flag = 1 << 7;
+pub const YYJSON_READ_REPLACE_LONE_SURROGATES: yyjson_read_flag = 1 << 8;
+pub const YYJSON_READ_ALLOW_LONE_SURROGATES: yyjson_read_flag = 1 << 9;
 pub type yyjson_read_code = u32;
 pub const YYJSON_READ_SUCCESS: yyjson_read_code = 0;
 #[repr(C)]
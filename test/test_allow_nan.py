@@ -0,0 +1,128 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import decimal
+import io
+import json
+import math
+
+import pytest
+
+import orjson
+
+
+class TestAllowNan:
+    def test_allow_nan_default(self):
+        """
+        loads() rejects NaN and Infinity by default
+        """
+        for val in ("NaN", "Infinity", "-Infinity", "[NaN]", "1e400"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val)
+
+    def test_allow_nan_false(self):
+        """
+        loads() allow_nan=False
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("NaN", allow_nan=False)
+
+    def test_allow_nan(self):
+        """
+        loads() allow_nan=True
+        """
+        res = orjson.loads("[NaN, Infinity, -Infinity]", allow_nan=True)
+        assert math.isnan(res[0])
+        assert res[1:] == [math.inf, -math.inf]
+        assert all(type(each) is float for each in res)
+
+    def test_allow_nan_stdlib(self):
+        """
+        loads() allow_nan=True deserializes output of json.dumps()
+        """
+        doc = json.dumps({"a": [float("inf"), 1.5, float("-inf")], "b": None})
+        assert orjson.loads(doc, allow_nan=True) == {
+            "a": [math.inf, 1.5, -math.inf],
+            "b": None,
+        }
+
+    def test_allow_nan_exact(self):
+        """
+        loads() allow_nan=True accepts only NaN, Infinity, and -Infinity
+        """
+        for val in ("nan", "NAN", "-NaN", "inf", "-Inf", "infinity", "-iNfInItY"):
+            with pytest.raises(orjson.JSONDecodeError) as exc:
+                orjson.loads(f"[1, {val}]", allow_nan=True)
+            assert exc.value.msg == "invalid literal"
+            assert exc.value.pos == 4
+            assert not orjson.is_valid(val, allow_nan=True)
+
+    def test_allow_nan_null(self):
+        """
+        loads() allow_nan=True does not change null
+        """
+        assert orjson.loads("[null, NaN]", allow_nan=True)[0] is None
+
+    def test_allow_nan_overflow(self):
+        """
+        loads() allow_nan=True deserializes too large numbers as infinite
+        """
+        assert orjson.loads("[1e400, -1e400]", allow_nan=True) == [
+            math.inf,
+            -math.inf,
+        ]
+
+    def test_allow_nan_invalid_literal(self):
+        """
+        loads() allow_nan=True rejects other literals
+        """
+        for val in ("Nan1", "Infinite", "-", "-Na", "undefined", "nul"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val, allow_nan=True)
+
+    def test_allow_nan_object(self):
+        """
+        loads() allow_nan=True in an object
+        """
+        assert orjson.loads('{"a": Infinity}', allow_nan=True) == {"a": math.inf}
+
+    def test_allow_nan_parse_float(self):
+        """
+        loads() allow_nan=True literals are not given to parse_float
+        """
+        res = orjson.loads(
+            "[0.5, Infinity, -Infinity]",
+            allow_nan=True,
+            parse_float=decimal.Decimal,
+        )
+        assert res == [decimal.Decimal("0.5"), math.inf, -math.inf]
+        assert type(res[1]) is float
+
+    def test_allow_nan_parse_int(self):
+        """
+        loads() allow_nan=True literals are not given to parse_int
+        """
+        res = orjson.loads("[1, NaN]", allow_nan=True, parse_int=str)
+        assert res[0] == "1"
+        assert math.isnan(res[1])
+
+    def test_allow_nan_invalid(self):
+        """
+        loads() allow_nan not a bool
+        """
+        for val in (1, None, "true"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads("[]", allow_nan=val)
+
+    def test_allow_nan_load(self):
+        """
+        load() allow_nan
+        """
+        assert orjson.load(io.BytesIO(b"[Infinity]"), allow_nan=True) == [math.inf]
+
+    def test_allow_nan_load_chunked(self):
+        """
+        load() allow_nan with chunk_size
+        """
+        assert orjson.load(
+            io.BytesIO(b"[-Infinity, Infinity]"), chunk_size=2, allow_nan=True
+        ) == [-math.inf, math.inf]
@@ -0,0 +1,154 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import asyncio
+import datetime
+import os
+import pathlib
+import tempfile
+import threading
+
+import pytest
+
+import orjson
+
+from .util import DOC
+
+
+class TestAload:
+    def test_aload(self):
+        """
+        aload() str, bytes, and os.PathLike paths
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "document.json")
+            with open(path, "wb") as fileobj:
+                fileobj.write(orjson.dumps(DOC))
+
+            async def main():
+                for each in (path, os.fsencode(path), pathlib.Path(path)):
+                    assert await orjson.aload(each) == DOC
+
+            asyncio.run(main())
+
+    def test_aload_kwargs(self):
+        """
+        aload() arguments of loads()
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "document.json")
+            with open(path, "wb") as fileobj:
+                fileobj.write(b'[1, {"a": 2}, "x"]')
+
+            async def main():
+                val = await orjson.aload(path, tuples=True, object_hook=len)
+                assert val == (1, 1, "x")
+                with pytest.raises(orjson.JSONDecodeError):
+                    await orjson.aload(path, max_bytes=10)
+                with pytest.raises(orjson.JSONDecodeError):
+                    orjson.aload(path, offset=1)
+
+            asyncio.run(main())
+
+    def test_aload_thread(self):
+        """
+        aload() deserializes on a thread other than the event loop's
+        """
+        threads = []
+
+        def hook(obj):
+            threads.append(threading.get_ident())
+            return obj
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "document.json")
+            with open(path, "wb") as fileobj:
+                fileobj.write(b'{"a": 1}')
+
+            async def main():
+                assert await orjson.aload(path, object_hook=hook) == {"a": 1}
+
+            asyncio.run(main())
+        assert threads and threads[0] != threading.get_ident()
+
+    def test_aload_error(self):
+        """
+        aload() of a file that does not exist or is invalid
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "document.json")
+
+            async def main():
+                with pytest.raises(FileNotFoundError):
+                    await orjson.aload(path)
+                with open(path, "wb") as fileobj:
+                    fileobj.write(b"[1,")
+                with pytest.raises(orjson.JSONDecodeError):
+                    await orjson.aload(path)
+                with pytest.raises(TypeError):
+                    orjson.aload(1.5)
+
+            asyncio.run(main())
+
+    def test_aload_no_loop(self):
+        """
+        aload() and adump() raise RuntimeError without a running event loop
+        """
+        with pytest.raises(RuntimeError):
+            orjson.aload("document.json")
+        with pytest.raises(RuntimeError):
+            orjson.adump([], "document.json")
+
+
+class TestAdump:
+    def test_adump(self):
+        """
+        adump() writes the output of dumps()
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "document.json")
+
+            async def main():
+                assert await orjson.adump(DOC, path) is None
+                with open(path, "rb") as fileobj:
+                    assert fileobj.read() == orjson.dumps(DOC)
+                # truncates the file
+                await orjson.adump([], pathlib.Path(path))
+                with open(path, "rb") as fileobj:
+                    assert fileobj.read() == b"[]"
+
+            asyncio.run(main())
+
+    def test_adump_options(self):
+        """
+        adump() with default and options
+        """
+        obj = {"b": datetime.date(2000, 1, 1), "a": object()}
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "document.json")
+
+            async def main():
+                await orjson.adump(obj, path, str, orjson.OPT_SORT_KEYS, indent=2)
+                with open(path, "rb") as fileobj:
+                    expected = orjson.dumps(obj, str, orjson.OPT_SORT_KEYS, indent=2)
+                    assert fileobj.read() == expected
+
+            asyncio.run(main())
+
+    def test_adump_error(self):
+        """
+        adump() of an unsupported type or to a directory that does not exist
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "document.json")
+
+            async def main():
+                with pytest.raises(orjson.JSONEncodeError):
+                    await orjson.adump(object(), path)
+                with pytest.raises(FileNotFoundError):
+                    await orjson.adump([], os.path.join(path, "document.json"))
+                with pytest.raises(orjson.JSONEncodeSizeError):
+                    await orjson.adump([1, 2], path, max_size=4)
+                with pytest.raises(orjson.JSONEncodeError):
+                    orjson.adump([], path, lines=True)
+
+            asyncio.run(main())
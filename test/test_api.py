@@ -95,7 +95,7 @@ class TestApi:
         dumps() option out of range high
         """
         with pytest.raises(orjson.JSONEncodeError):
-            orjson.dumps(True, option=1 << 12)
+            orjson.dumps(True, option=1 << 26)
 
     def test_opts_multiple(self):
         """
@@ -145,6 +145,13 @@ class TestApi:
         with pytest.raises(TypeError):
             orjson.dumps({}, None, orjson.OPT_NAIVE_UTC, option=orjson.OPT_NAIVE_UTC)
 
+    def test_too_many_args(self):
+        """
+        dumps() more than 3 positional arguments
+        """
+        with pytest.raises(TypeError):
+            orjson.dumps({}, None, None, None)
+
     def test_option_mixed(self):
         """
         dumps() option one arg, one kwarg
@@ -169,7 +176,7 @@ class TestApi:
         """
         assert (
             str(inspect.signature(orjson.dumps))
-            == "(obj, /, default=None, option=None)"
+            == "(obj, /, default=None, option=None, *, max_size=None, stats=None, float_precision=None, max_depth=None, redact_keys=None, include_keys=None, max_str_len=None, transform=None, separators=None, **options)"
         )
         inspect.signature(orjson.dumps).bind("str")
         inspect.signature(orjson.dumps).bind("str", default=default, option=1)
@@ -0,0 +1,223 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import asyncio
+import datetime
+
+import pytest
+
+import orjson
+
+from .util import DOC
+
+
+class Chunks:
+    """
+    A callable returning a coroutine writing each chunk to a list
+    """
+
+    def __init__(self):
+        self.chunks = []
+
+    async def __call__(self, chunk):
+        await asyncio.sleep(0)
+        self.chunks.append(chunk)
+
+
+class Stream:
+    """
+    A stream with write() and drain() as asyncio.StreamWriter
+    """
+
+    def __init__(self):
+        self.buffer = []
+        self.chunks = []
+
+    def write(self, data):
+        self.buffer.append(data)
+
+    async def drain(self):
+        await asyncio.sleep(0)
+        self.chunks.append(b"".join(self.buffer))
+        self.buffer.clear()
+
+
+def dump(obj, *args, **kwargs):
+    write = Chunks()
+    asyncio.run(orjson.AsyncWriter(write, *args, **kwargs).dump(obj))
+    return write.chunks
+
+
+class TestAsyncWriter:
+    def test_async_writer(self):
+        """
+        AsyncWriter.dump() writes the same output as dumps()
+        """
+        for obj in (
+            DOC,
+            list(DOC.values()),
+            tuple(DOC.values()),
+            [],
+            {},
+            (),
+            "a",
+            1,
+            None,
+        ):
+            assert b"".join(dump(obj)) == orjson.dumps(obj)
+
+    def test_async_writer_chunks(self):
+        """
+        AsyncWriter.dump() writes a chunk once chunk_size bytes are serialized
+        """
+        obj = ["a" * 10] * 10
+        chunks = dump(obj, chunk_size=30)
+        assert b"".join(chunks) == orjson.dumps(obj)
+        assert chunks[0] == b'["aaaaaaaaaa","aaaaaaaaaa","aaaaaaaaaa"'
+        assert len(chunks) == 4
+        assert len(dump(obj, chunk_size=1)) == 11
+        assert len(dump(obj)) == 1
+
+    def test_async_writer_options(self):
+        """
+        AsyncWriter.dump() with default and options
+        """
+        obj = {"b": [datetime.date(2000, 1, 1)], "a": {1: 2}}
+        for chunk_size in (1, 100):
+            for kwargs in (
+                {"option": orjson.OPT_INDENT_2 | orjson.OPT_NON_STR_KEYS},
+                {"option": orjson.OPT_APPEND_NEWLINE | orjson.OPT_NON_STR_KEYS},
+                {"option": orjson.OPT_SORT_KEYS | orjson.OPT_NON_STR_KEYS},
+                {"indent": 2, "non_str_keys": True},
+            ):
+                expected = orjson.dumps(obj, **kwargs)
+                chunks = dump(obj, chunk_size=chunk_size, **kwargs)
+                assert b"".join(chunks) == expected
+        obj = [object(), {"a": [object()]}]
+        assert b"".join(dump(obj, str, chunk_size=1)) == orjson.dumps(obj, str)
+
+    def test_async_writer_iterator(self):
+        """
+        AsyncWriter.dump() writes an iterator as an array
+        """
+        assert b"".join(dump(iter([]))) == b"[]"
+        chunks = dump((idx for idx in range(3)), chunk_size=1)
+        assert chunks == [b"[0", b",1", b",2", b"]"]
+
+    def test_async_writer_list_modified(self):
+        """
+        AsyncWriter.dump() of a list modified while a chunk is written
+        """
+        obj = [1, 2, 3]
+
+        async def write(chunk):
+            chunks.append(chunk)
+            obj.pop()
+
+        chunks = []
+        asyncio.run(orjson.AsyncWriter(write, chunk_size=1).dump(obj))
+        assert chunks == [b"[1", b",2", b"]"]
+
+    def test_async_writer_stream(self):
+        """
+        AsyncWriter of a stream drains it after each chunk
+        """
+        stream = Stream()
+        asyncio.run(orjson.AsyncWriter(stream, chunk_size=1).dump([1, 2]))
+        assert stream.chunks == [b"[1", b",2", b"]"]
+        assert stream.buffer == []
+
+    def test_async_writer_reuse(self):
+        """
+        AsyncWriter writes any number of objects
+        """
+        write = Chunks()
+        writer = orjson.AsyncWriter(write)
+
+        async def main():
+            await writer.dump([1])
+            await writer.dump({"a": 2})
+
+        asyncio.run(main())
+        assert write.chunks == [b"[1]", b'{"a":2}']
+
+    def test_async_writer_error(self):
+        """
+        AsyncWriter.dump() raises JSONEncodeError for what dumps() does
+        """
+        for obj in ([1, object()], {"a": object()}, {1: 2}, object()):
+            write = Chunks()
+            with pytest.raises(orjson.JSONEncodeError):
+                asyncio.run(orjson.AsyncWriter(write, chunk_size=1).dump(obj))
+            assert len(write.chunks) <= 1
+
+    def test_async_writer_iterator_error(self):
+        """
+        AsyncWriter.dump() propagates an exception raised by an iterator
+        """
+
+        def gen():
+            yield 1
+            raise ValueError("gen")
+
+        with pytest.raises(ValueError):
+            dump(gen())
+
+    def test_async_writer_write_error(self):
+        """
+        AsyncWriter.dump() propagates an exception raised writing a chunk
+        """
+
+        async def write(chunk):
+            raise ValueError("write")
+
+        with pytest.raises(ValueError):
+            asyncio.run(orjson.AsyncWriter(write).dump([1]))
+
+        with pytest.raises(TypeError):
+            asyncio.run(orjson.AsyncWriter(lambda chunk: None).dump([1]))
+
+    def test_async_writer_cancelled(self):
+        """
+        AsyncWriter.dump() is cancelled while a chunk is written
+        """
+        chunks = []
+
+        async def write(chunk):
+            chunks.append(chunk)
+            await asyncio.sleep(10)
+
+        async def main():
+            task = asyncio.ensure_future(
+                orjson.AsyncWriter(write, chunk_size=1).dump([1, 2])
+            )
+            await asyncio.sleep(0)
+            task.cancel()
+            with pytest.raises(asyncio.CancelledError):
+                await task
+
+        asyncio.run(main())
+        assert chunks == [b"[1"]
+
+    def test_async_writer_close(self):
+        """
+        AsyncWriter.dump() awaitable closed before it is done
+        """
+        dumping = orjson.AsyncWriter(Chunks()).dump([1])
+        assert dumping.close() is None
+        with pytest.raises(StopIteration):
+            dumping.send(None)
+
+    def test_async_writer_args(self):
+        """
+        AsyncWriter() invalid arguments
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.AsyncWriter()
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.AsyncWriter(object())
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.AsyncWriter(Chunks(), chunk_size=0)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.AsyncWriter(Chunks(), option=-1)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.AsyncWriter(Chunks(), max_size=1)
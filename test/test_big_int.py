@@ -0,0 +1,97 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import sys
+
+import pytest
+
+import orjson
+
+
+class TestBigInt:
+    def test_big_int(self):
+        """
+        loads() integers outside 64 bits are int
+        """
+        for val in (
+            18446744073709551616,
+            -9223372036854775809,
+            -18446744073709551615,
+            -18446744073709551616,
+            123456789012345678901234567890,
+            -(10**100),
+        ):
+            res = orjson.loads(str(val))
+            assert type(res) is int
+            assert res == val
+
+    def test_big_int_boundary(self):
+        """
+        loads() integers at the limits of 64 bits
+        """
+        assert orjson.loads(
+            "[18446744073709551615, 18446744073709551616, "
+            "-9223372036854775808, -9223372036854775809]"
+        ) == [
+            18446744073709551615,
+            18446744073709551616,
+            -9223372036854775808,
+            -9223372036854775809,
+        ]
+
+    def test_big_int_nested(self):
+        """
+        loads() integers outside 64 bits in arrays and objects
+        """
+        val = {"a": [10**30, 1, -(10**25)], "b": 10**20}
+        assert orjson.loads(
+            '{"a": [1000000000000000000000000000000, 1, '
+            '-10000000000000000000000000], "b": 100000000000000000000}'
+        ) == val
+
+    def test_big_int_float(self):
+        """
+        loads() large numbers with a fraction or exponent are float
+        """
+        doc = "[100000000000000000000.0, 1e20, 10000000000000000000e1]"
+        assert orjson.loads(doc) == [1e20, 1e20, 1e20]
+        assert all(type(each) is float for each in orjson.loads(doc))
+
+    def test_big_int_str(self):
+        """
+        loads() integers outside 64 bits from str and bytes
+        """
+        assert orjson.loads(str(10**40)) == 10**40
+        assert orjson.loads(str(10**40).encode()) == 10**40
+
+    def test_big_int_parse_int(self):
+        """
+        loads() parse_int is still given integers outside 64 bits
+        """
+        assert orjson.loads(str(10**40), parse_int=str) == str(10**40)
+
+    @pytest.mark.skipif(
+        not hasattr(sys, "get_int_max_str_digits"), reason="no int digit limit"
+    )
+    def test_big_int_max_str_digits(self):
+        """
+        loads() integers with too many digits raise JSONDecodeError at the
+        integer
+        """
+        digits = "1" * (sys.get_int_max_str_digits() + 1)
+        for doc, pos in ((digits, 0), (f'{{"a":\n -{digits}}}', 7)):
+            with pytest.raises(orjson.JSONDecodeError) as exc_info:
+                orjson.loads(doc)
+            assert exc_info.value.msg == (
+                "integer has more digits than sys.get_int_max_str_digits()"
+            )
+            assert exc_info.value.pos == pos
+        assert orjson.loads(digits[1:]) == int(digits[1:])
+
+    def test_big_int_load_chunked(self):
+        """
+        load() integers outside 64 bits with chunk_size
+        """
+        assert orjson.load(
+            io.BytesIO(b"[123456789012345678901234567890, 1]"), chunk_size=4
+        ) == [123456789012345678901234567890, 1]
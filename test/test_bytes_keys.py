@@ -0,0 +1,97 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import pytest
+
+import orjson
+
+
+class TestBytesKeys:
+    def test_bytes_keys(self):
+        """
+        dumps() OPT_BYTES_KEYS
+        """
+        assert (
+            orjson.dumps({b"a": 1, "b": 2, b"\xe5\xa5\xbd": 3}, option=orjson.OPT_BYTES_KEYS)
+            == '{"a":1,"b":2,"好":3}'.encode("utf-8")
+        )
+
+    def test_bytes_keys_disabled(self):
+        """
+        dumps() bytes keys without OPT_BYTES_KEYS
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps({b"a": 1})
+
+    def test_bytes_keys_nested(self):
+        """
+        dumps() OPT_BYTES_KEYS nested dict
+        """
+        assert (
+            orjson.dumps({b"a": [{b"b": {b"c": None}}]}, option=orjson.OPT_BYTES_KEYS)
+            == b'{"a":[{"b":{"c":null}}]}'
+        )
+
+    def test_bytes_keys_empty(self):
+        """
+        dumps() OPT_BYTES_KEYS empty key
+        """
+        assert orjson.dumps({b"": 1}, option=orjson.OPT_BYTES_KEYS) == b'{"":1}'
+
+    def test_bytes_keys_escape(self):
+        """
+        dumps() OPT_BYTES_KEYS escapes key
+        """
+        assert (
+            orjson.dumps({b'"\n': 1}, option=orjson.OPT_BYTES_KEYS) == b'{"\\"\\n":1}'
+        )
+
+    def test_bytes_keys_invalid_utf8(self):
+        """
+        dumps() OPT_BYTES_KEYS invalid UTF-8
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps({b"\xff": 1}, option=orjson.OPT_BYTES_KEYS)
+
+    def test_bytes_keys_other_type(self):
+        """
+        dumps() OPT_BYTES_KEYS does not allow other non-str keys
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps({1: 1}, option=orjson.OPT_BYTES_KEYS)
+
+    def test_bytes_keys_bytearray(self):
+        """
+        dumps() OPT_BYTES_KEYS does not allow bytearray or memoryview
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps({memoryview(b"a"): 1}, option=orjson.OPT_BYTES_KEYS)
+
+    def test_bytes_keys_non_str_keys(self):
+        """
+        dumps() OPT_BYTES_KEYS with OPT_NON_STR_KEYS
+        """
+        assert (
+            orjson.dumps(
+                {b"a": 1, 2: 2}, option=orjson.OPT_BYTES_KEYS | orjson.OPT_NON_STR_KEYS
+            )
+            == b'{"a":1,"2":2}'
+        )
+
+    def test_bytes_keys_non_str_keys_disabled(self):
+        """
+        dumps() OPT_NON_STR_KEYS does not allow bytes keys
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps({b"a": 1}, option=orjson.OPT_NON_STR_KEYS)
+
+    def test_bytes_keys_sort(self):
+        """
+        dumps() OPT_BYTES_KEYS with OPT_SORT_KEYS
+        """
+        assert (
+            orjson.dumps(
+                {b"b": 1, "a": 2, b"c": 3},
+                option=orjson.OPT_BYTES_KEYS | orjson.OPT_SORT_KEYS,
+            )
+            == b'{"a":2,"b":1,"c":3}'
+        )
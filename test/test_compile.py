@@ -0,0 +1,337 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+from typing import List, Optional
+
+import pytest
+
+import orjson
+
+try:
+    from typing import TypedDict
+except ImportError:
+    TypedDict = None
+
+
+@dataclasses.dataclass
+class Point:
+    x: int
+    y: float
+    label: str
+    visible: bool
+
+
+@dataclasses.dataclass
+class Shape:
+    name: str
+    origin: Point
+    points: List[Point]
+    parent: Optional["Shape"] = None
+
+
+@dataclasses.dataclass
+class Renamed:
+    first_name: str = dataclasses.field(metadata={"orjson": {"alias": "firstName"}})
+    secret: str = dataclasses.field(default="", metadata={"orjson": {"exclude": True}})
+
+
+class Custom:
+    pass
+
+
+class TestCompile:
+    def test_compile_dataclass(self):
+        """
+        compile() dataclass
+        """
+        serialize = orjson.compile(Point)
+        obj = Point(1, 2.5, "a", True)
+        assert serialize(obj) == orjson.dumps(obj)
+        assert serialize(obj) == b'{"x":1,"y":2.5,"label":"a","visible":true}'
+
+    def test_compile_dataclass_nested(self):
+        """
+        compile() dataclass with nested dataclass fields
+        """
+        serialize = orjson.compile(Shape)
+        obj = Shape("b", Point(0, 0.0, "o", False), [Point(1, 1.0, "p", True)])
+        obj = Shape("a", Point(0, 0.0, "o", False), [], obj)
+        assert serialize(obj) == orjson.dumps(obj)
+
+    def test_compile_dataclass_unexpected_type(self):
+        """
+        compile() raises for a value not of the annotated type
+        """
+        serialize = orjson.compile(Point)
+        for obj, msg in (
+            (Point("1", 2.5, "a", True), "Field x must be int, not str"),
+            (Point(1, None, "a", True), "Field y must be float, not NoneType"),
+            (Point(1, 2.5, 3, True), "Field label must be str, not int"),
+            (Point(1, 2.5, "a", 1), "Field visible must be bool, not int"),
+        ):
+            with pytest.raises(orjson.JSONEncodeError) as exc_info:
+                serialize(obj)
+            assert str(exc_info.value) == msg
+        serialize = orjson.compile(Shape)
+        with pytest.raises(orjson.JSONEncodeError) as exc_info:
+            serialize(Shape("a", {"x": 1}, []))  # type: ignore
+        assert str(exc_info.value) == "Field origin must be Point, not dict"
+
+    def test_compile_dataclass_subclass_type(self):
+        """
+        compile() serializes an instance of a subclass of the annotated type
+        as dumps(), and an int annotated as float
+        """
+
+        class Label(str):
+            pass
+
+        @dataclasses.dataclass
+        class SubPoint(Point):
+            z: int = 0
+
+        serialize = orjson.compile(Point)
+        obj = Point(True, 2, Label("a"), False)
+        assert serialize(obj) == b'{"x":true,"y":2,"label":"a","visible":false}'
+        serialize = orjson.compile(Shape)
+        obj = Shape("a", SubPoint(1, 2.5, "a", True, 3), [])
+        assert serialize(obj) == orjson.dumps(obj)
+
+    def test_compile_dataclass_metadata(self):
+        """
+        compile() dataclass honors metadata alias and exclude
+        """
+        serialize = orjson.compile(Renamed)
+        assert serialize(Renamed("a", "b")) == b'{"firstName":"a"}'
+
+    def test_compile_other_type(self):
+        """
+        compile() callable serializes other objects as dumps()
+        """
+        serialize = orjson.compile(Point)
+        assert serialize([1, {"a": None}]) == b'[1,{"a":null}]'
+
+    def test_compile_default(self):
+        """
+        compile() default
+        """
+        serialize = orjson.compile(Shape, default=lambda obj: "custom")
+        obj = Shape("a", Point(0, 0.0, "o", False), [Custom()])  # type: ignore
+        assert (
+            serialize(obj)
+            == b'{"name":"a","origin":{"x":0,"y":0.0,"label":"o","visible":false},"points":["custom"],"parent":null}'
+        )
+
+    def test_compile_unsupported(self):
+        """
+        compile() callable raises on unsupported types without default
+        """
+        serialize = orjson.compile(Shape)
+        obj = Shape("a", Point(0, 0.0, "o", False), [Custom()])  # type: ignore
+        with pytest.raises(orjson.JSONEncodeError):
+            serialize(obj)
+
+    def test_compile_option(self):
+        """
+        compile() option
+        """
+        serialize = orjson.compile(
+            Point, option=orjson.OPT_INDENT_2 | orjson.OPT_APPEND_NEWLINE
+        )
+        obj = Point(1, 2.5, "a", True)
+        assert serialize(obj) == orjson.dumps(
+            obj, option=orjson.OPT_INDENT_2 | orjson.OPT_APPEND_NEWLINE
+        )
+
+    def test_compile_kwargs(self):
+        """
+        compile() separators, float_precision, and max_size
+        """
+        obj = Point(1, 2.125, "a", True)
+        kwargs = {"separators": (", ", ": "), "float_precision": 2}
+        serialize = orjson.compile(Point, **kwargs)
+        assert serialize(obj) == orjson.dumps(obj, **kwargs)
+        assert serialize([obj]) == orjson.dumps([obj], **kwargs)
+        serialize = orjson.compile(Point, max_size=10)
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            serialize(obj)
+        assert serialize([]) == b"[]"
+        for arg in ("stats", "redact_keys", "include_keys", "max_str_len", "transform"):
+            with pytest.raises(orjson.JSONEncodeError) as exc_info:
+                orjson.compile(Point, **{arg: None})
+            assert str(exc_info.value) == (
+                f"compile() does not support the keyword argument: '{arg}'"
+            )
+
+    def test_compile_max_depth(self):
+        """
+        compile() max_depth applies to the compiled type and its fields
+        """
+        obj = Shape("a", Point(0, 0.0, "o", False), [Point(1, 1.0, "p", True)])
+        serialize = orjson.compile(Shape, max_depth=3)
+        assert serialize(obj) == orjson.dumps(obj, max_depth=3)
+        for max_depth in (0, 1, 2):
+            serialize = orjson.compile(Shape, max_depth=max_depth)
+            with pytest.raises(orjson.JSONEncodeError):
+                serialize(obj)
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps(obj, max_depth=max_depth)
+
+    def test_compile_option_strict_integer(self):
+        """
+        compile() OPT_STRICT_INTEGER applies to int fields
+        """
+        serialize = orjson.compile(Point, option=orjson.OPT_STRICT_INTEGER)
+        with pytest.raises(orjson.JSONEncodeError):
+            serialize(Point(9007199254740992, 0.0, "a", True))
+
+    def test_compile_option_passthrough_dataclass(self):
+        """
+        compile() OPT_PASSTHROUGH_DATACLASS passes instances to default
+        """
+        serialize = orjson.compile(
+            Point,
+            default=lambda obj: obj.label,
+            option=orjson.OPT_PASSTHROUGH_DATACLASS,
+        )
+        assert serialize(Point(1, 2.5, "a", True)) == b'"a"'
+
+    def test_compile_invalid_option(self):
+        """
+        compile() invalid option
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.compile(Point, option=1 << 30)
+
+    def test_compile_invalid_type(self):
+        """
+        compile() argument must be a dataclass or TypedDict type
+        """
+        for obj in (Custom, Point(1, 2.5, "a", True), dict, 1):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.compile(obj)
+
+    def test_compile_call_arguments(self):
+        """
+        compile() callable takes exactly one positional argument
+        """
+        serialize = orjson.compile(Point)
+        with pytest.raises(orjson.JSONEncodeError):
+            serialize()
+        with pytest.raises(orjson.JSONEncodeError):
+            serialize(1, 2)
+        with pytest.raises(orjson.JSONEncodeError):
+            serialize(obj=1)
+
+    def test_compile_recursion(self):
+        """
+        compile() callable raises on recursion limit
+        """
+        serialize = orjson.compile(Shape)
+        obj = Shape("a", Point(0, 0.0, "o", False), [])
+        obj.parent = obj
+        with pytest.raises(orjson.JSONEncodeError):
+            serialize(obj)
+
+
+@pytest.mark.skipif(TypedDict is None, reason="TypedDict unavailable")
+class TestCompileTypedDict:
+    def test_compile_typeddict(self):
+        """
+        compile() TypedDict
+        """
+
+        class Movie(TypedDict):
+            title: str
+            year: int
+
+        serialize = orjson.compile(Movie)
+        assert serialize({"title": "a", "year": 1}) == b'{"title":"a","year":1}'
+
+    def test_compile_typeddict_order(self):
+        """
+        compile() TypedDict serializes keys in declaration order
+        """
+
+        class Movie(TypedDict):
+            title: str
+            year: int
+
+        serialize = orjson.compile(Movie)
+        assert serialize({"year": 1, "title": "a"}) == b'{"title":"a","year":1}'
+
+    def test_compile_typeddict_missing(self):
+        """
+        compile() TypedDict omits missing keys
+        """
+
+        class Movie(TypedDict, total=False):
+            title: str
+            year: int
+
+        serialize = orjson.compile(Movie)
+        assert serialize({"year": 1}) == b'{"year":1}'
+
+    def test_compile_typeddict_extra(self):
+        """
+        compile() TypedDict raises for a key not declared on it
+        """
+
+        class Movie(TypedDict):
+            title: str
+
+        serialize = orjson.compile(Movie)
+        with pytest.raises(orjson.JSONEncodeError) as exc_info:
+            serialize({"a": 1, "title": "b"})
+        assert str(exc_info.value) == "Dict key is not declared on the TypedDict: a"
+
+    def test_compile_typeddict_unexpected_type(self):
+        """
+        compile() TypedDict raises for a value not of the annotated type
+        """
+
+        class Inner(TypedDict):
+            b: int
+
+        class Movie(TypedDict):
+            title: str
+            inner: Inner
+
+        serialize = orjson.compile(Movie)
+        with pytest.raises(orjson.JSONEncodeError) as exc_info:
+            serialize({"title": 1, "inner": {"b": 1}})
+        assert str(exc_info.value) == "Field title must be str, not int"
+        with pytest.raises(orjson.JSONEncodeError) as exc_info:
+            serialize({"title": "a", "inner": [1]})
+        assert str(exc_info.value) == "Field inner must be Inner, not list"
+
+    def test_compile_typeddict_nested(self):
+        """
+        compile() TypedDict with nested TypedDict and dataclass
+        """
+
+        class Inner(TypedDict):
+            b: int
+
+        class Outer(TypedDict):
+            a: Inner
+            point: Point
+
+        serialize = orjson.compile(Outer)
+        obj = {"point": Point(1, 2.5, "a", True), "a": {"b": 1}}
+        assert (
+            serialize(obj)
+            == b'{"a":{"b":1},"point":{"x":1,"y":2.5,"label":"a","visible":true}}'
+        )
+
+    def test_compile_typeddict_sort_keys(self):
+        """
+        compile() TypedDict OPT_SORT_KEYS
+        """
+
+        class Movie(TypedDict):
+            year: int
+            title: str
+
+        serialize = orjson.compile(Movie, option=orjson.OPT_SORT_KEYS)
+        assert serialize({"year": 1, "title": "a"}) == b'{"title":"a","year":1}'
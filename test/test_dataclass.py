@@ -1,6 +1,7 @@
 # SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 import abc
+import sys
 import uuid
 from dataclasses import InitVar, asdict, dataclass, field
 from enum import Enum
@@ -56,6 +57,20 @@ class Dataclass4:
     c: float = 1.1
 
 
+@dataclass
+class ExcludeDataclass:
+    name: str
+    password: str = field(metadata={"orjson": {"exclude": True}})
+    token: str = field(default="", metadata={"orjson": {"exclude": False}})
+
+
+@dataclass
+class AliasDataclass:
+    first_name: str = field(metadata={"orjson": {"alias": "firstName"}})
+    last_name: str = field(metadata={"orjson": {"alias": "lastName"}})
+    age: int = 0
+
+
 @dataclass
 class Datasubclass(Dataclass1):
     additional: bool
@@ -178,6 +193,123 @@ class TestDataclass:
         obj = Dataclass4("a", 1, 2.1)
         assert orjson.dumps(obj) == b'{"a":"a","b":1,"c":2.1}'
 
+    def test_dataclass_metadata_exclude(self):
+        """
+        dumps() dataclass omits fields with metadata exclude
+        """
+        obj = ExcludeDataclass("a", "secret", "b")
+        assert orjson.dumps(obj) == b'{"name":"a","token":"b"}'
+
+    @pytest.mark.skipif(sys.version_info < (3, 10), reason="dataclass slots")
+    def test_dataclass_metadata_exclude_slots(self):
+        """
+        dumps() dataclass with __slots__ omits fields with metadata exclude
+        """
+
+        @dataclass(slots=True)
+        class ExcludeSlotsDataclass:
+            name: str
+            password: str = field(metadata={"orjson": {"exclude": True}})
+
+        obj = ExcludeSlotsDataclass("a", "secret")
+        assert orjson.dumps(obj) == b'{"name":"a"}'
+
+    def test_dataclass_metadata_exclude_nested(self):
+        """
+        dumps() dataclass metadata exclude in nested and repeated instances
+        """
+        obj = [
+            ExcludeDataclass("a", "secret"),
+            Dataclass1("b", 1, None),
+            ExcludeDataclass("c", "secret"),
+        ]
+        assert (
+            orjson.dumps(obj)
+            == b'[{"name":"a","token":""},{"name":"b","number":1,"sub":null},{"name":"c","token":""}]'
+        )
+
+    def test_dataclass_metadata_exclude_sort(self):
+        """
+        dumps() dataclass metadata exclude with OPT_SORT_KEYS
+        """
+        obj = ExcludeDataclass("a", "secret", "b")
+        assert (
+            orjson.dumps(obj, option=orjson.OPT_SORT_KEYS)
+            == b'{"name":"a","token":"b"}'
+        )
+
+    def test_dataclass_metadata_alias(self):
+        """
+        dumps() dataclass uses metadata alias as key
+        """
+        obj = AliasDataclass("a", "b", 1)
+        assert orjson.dumps(obj) == b'{"firstName":"a","lastName":"b","age":1}'
+
+    @pytest.mark.skipif(sys.version_info < (3, 10), reason="dataclass slots")
+    def test_dataclass_metadata_alias_slots(self):
+        """
+        dumps() dataclass with __slots__ uses metadata alias as key
+        """
+
+        @dataclass(slots=True)
+        class AliasSlotsDataclass:
+            first_name: str = field(metadata={"orjson": {"alias": "firstName"}})
+
+        obj = AliasSlotsDataclass("a")
+        assert orjson.dumps(obj) == b'{"firstName":"a"}'
+
+    def test_dataclass_metadata_alias_exclude(self):
+        """
+        dumps() dataclass exclude takes precedence over alias
+        """
+
+        @dataclass
+        class AliasExcludeDataclass:
+            a: int = field(metadata={"orjson": {"alias": "b", "exclude": True}})
+            c: int = 0
+
+        assert orjson.dumps(AliasExcludeDataclass(1, 2)) == b'{"c":2}'
+
+    def test_dataclass_metadata_alias_invalid(self):
+        """
+        dumps() dataclass metadata alias must be str
+        """
+
+        @dataclass
+        class AliasInvalidDataclass:
+            a: int = field(metadata={"orjson": {"alias": 1}})
+
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(AliasInvalidDataclass(1))
+
+    def test_dataclass_metadata_alias_duplicate(self):
+        """
+        dumps() dataclass metadata alias must not be the key of another field
+        """
+
+        @dataclass
+        class AliasNameDataclass:
+            a: int = field(metadata={"orjson": {"alias": "b"}})
+            b: int = 0
+
+        @dataclass
+        class AliasAliasDataclass:
+            a: int = field(metadata={"orjson": {"alias": "c"}})
+            b: int = field(metadata={"orjson": {"alias": "c"}})
+
+        @dataclass
+        class AliasExcludedDataclass:
+            a: int = field(metadata={"orjson": {"alias": "b"}})
+            b: int = field(default=0, metadata={"orjson": {"exclude": True}})
+
+        for obj in (AliasNameDataclass(1, 2), AliasAliasDataclass(1, 2)):
+            for _ in range(2):
+                with pytest.raises(
+                    orjson.JSONEncodeError, match="more than one field with key"
+                ):
+                    orjson.dumps(obj)
+        assert orjson.dumps(AliasExcludedDataclass(1, 2)) == b'{"b":1}'
+
     def test_dataclass_classvar(self):
         """
         dumps() dataclass class variable
@@ -249,6 +381,39 @@ class TestDataclass:
         obj = InitDataclass("zxc", "vbn")
         assert orjson.dumps(obj) == b'{"ab":"zxc vbn"}'
 
+    def test_dataclass_non_field_attribute(self):
+        """
+        dumps() dataclass does not include instance attributes that are not fields
+        """
+
+        @dataclass
+        class NonFieldDataclass:
+            a: int
+
+            def __post_init__(self):
+                self.b = 2
+
+        obj = NonFieldDataclass(1)
+        assert obj.__dict__ == {"a": 1, "b": 2}
+        assert orjson.dumps(obj) == b'{"a":1}'
+
+    def test_dataclass_class_attribute_field(self):
+        """
+        dumps() dataclass field not in instance __dict__
+        """
+
+        @dataclass(init=False)
+        class ClassAttributeDataclass:
+            a: int = 1
+            b: int = 2
+
+            def __init__(self, b):
+                self.b = b
+
+        obj = ClassAttributeDataclass(3)
+        assert obj.__dict__ == {"b": 3}
+        assert orjson.dumps(obj) == b'{"a":1,"b":3}'
+
     def test_dataclass_option(self):
         """
         dumps() accepts deprecated OPT_SERIALIZE_DATACLASS
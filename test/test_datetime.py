@@ -783,3 +783,86 @@ class TestDateclassPassthrough:
             )
             == b'"Thu, 01 Jan 1970 00:00:00 GMT"'
         )
+
+
+class DatetimeSubclass(datetime.datetime):
+    def isoformat(self, *args, **kwargs):
+        raise NotImplementedError
+
+
+class DateSubclass(datetime.date):
+    pass
+
+
+class TestDatetimeSubclass:
+    def test_datetime_subclass(self):
+        """
+        datetime.datetime subclass
+        """
+        assert (
+            orjson.dumps([DatetimeSubclass(2000, 1, 1, 2, 3, 4, 123)])
+            == b'["2000-01-01T02:03:04.000123"]'
+        )
+
+    def test_datetime_subclass_tz(self):
+        """
+        datetime.datetime subclass with tzinfo
+        """
+        assert (
+            orjson.dumps(
+                DatetimeSubclass(2000, 1, 1, 2, 3, 4, tzinfo=datetime.timezone.utc),
+                option=orjson.OPT_UTC_Z,
+            )
+            == b'"2000-01-01T02:03:04Z"'
+        )
+
+    def test_date_subclass(self):
+        """
+        datetime.date subclass
+        """
+        assert orjson.dumps(DateSubclass(2000, 1, 2)) == b'"2000-01-02"'
+
+    def test_datetime_subclass_dict_key(self):
+        """
+        datetime.datetime subclass as dict key with OPT_NON_STR_KEYS
+        """
+        assert (
+            orjson.dumps(
+                {DatetimeSubclass(2000, 1, 1): 1}, option=orjson.OPT_NON_STR_KEYS
+            )
+            == b'{"2000-01-01T00:00:00":1}'
+        )
+
+    def test_datetime_subclass_passthrough_subclass(self):
+        """
+        datetime.datetime subclass OPT_PASSTHROUGH_SUBCLASS
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(
+                DatetimeSubclass(2000, 1, 1), option=orjson.OPT_PASSTHROUGH_SUBCLASS
+            )
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(DateSubclass(2000, 1, 1), option=orjson.OPT_PASSTHROUGH_SUBCLASS)
+
+    def test_datetime_subclass_passthrough_datetime(self):
+        """
+        datetime.datetime subclass OPT_PASSTHROUGH_DATETIME
+        """
+        assert (
+            orjson.dumps(
+                DatetimeSubclass(2000, 1, 1),
+                option=orjson.OPT_PASSTHROUGH_DATETIME,
+                default=lambda obj: "default",
+            )
+            == b'"default"'
+        )
+
+    @pytest.mark.skipif(pendulum is None, reason="pendulum install broken on win")
+    def test_datetime_pendulum_datetime(self):
+        """
+        pendulum.DateTime is serialized as datetime.datetime
+        """
+        assert (
+            orjson.dumps(pendulum.datetime(2018, 6, 1, 2, 3, 4, tz="UTC"))
+            == b'"2018-06-01T02:03:04+00:00"'
+        )
@@ -0,0 +1,150 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import datetime
+
+import pytest
+
+import orjson
+
+UTC = datetime.timezone.utc
+
+
+class TestDatetimes:
+    def test_datetimes_default(self):
+        """
+        loads() deserializes RFC 3339 strings to str by default
+        """
+        assert orjson.loads('["2020-01-01T00:00:00Z"]') == ["2020-01-01T00:00:00Z"]
+        assert orjson.loads('"2020-01-01"', datetimes=False) == "2020-01-01"
+
+    def test_datetimes_datetime(self):
+        """
+        loads() datetimes=True date and time
+        """
+        assert orjson.loads(
+            '"2020-01-01T12:30:45.123456+01:30"', datetimes=True
+        ) == datetime.datetime(
+            2020,
+            1,
+            1,
+            12,
+            30,
+            45,
+            123456,
+            tzinfo=datetime.timezone(datetime.timedelta(hours=1, minutes=30)),
+        )
+        assert orjson.loads('"2020-01-01t00:00:00z"', datetimes=True) == (
+            datetime.datetime(2020, 1, 1, tzinfo=UTC)
+        )
+        assert orjson.loads('"2020-01-01T00:00:00-05:00"', datetimes=True) == (
+            datetime.datetime(2020, 1, 1, 5, tzinfo=UTC)
+        )
+
+    def test_datetimes_naive(self):
+        """
+        loads() datetimes=True date and time or time without an offset is not
+        RFC 3339 and is str
+        """
+        for val in ("2020-01-01T12:30:00", "2020-01-01T12:30:00.5", "12:30:00"):
+            assert orjson.loads(orjson.dumps(val), datetimes=True) == val
+
+    def test_datetimes_date_time(self):
+        """
+        loads() datetimes=True date and time alone
+        """
+        assert orjson.loads('"2020-02-29"', datetimes=True) == datetime.date(
+            2020, 2, 29
+        )
+        assert orjson.loads('"12:30:00.5-01:00"', datetimes=True) == datetime.time(
+            12, 30, 0, 500000, tzinfo=datetime.timezone(-datetime.timedelta(hours=1))
+        )
+        assert orjson.loads('"12:30:00Z"', datetimes=True) == datetime.time(
+            12, 30, tzinfo=UTC
+        )
+
+    def test_datetimes_fraction(self):
+        """
+        loads() datetimes=True fractional seconds truncated to microseconds
+        """
+        assert orjson.loads('"2020-01-01T00:00:00.1234567899Z"', datetimes=True) == (
+            datetime.datetime(2020, 1, 1, 0, 0, 0, 123456, tzinfo=UTC)
+        )
+
+    def test_datetimes_roundtrip(self):
+        """
+        loads() datetimes=True is the inverse of dumps()
+        """
+        obj = [
+            datetime.datetime(2020, 1, 1, 12, 30, 45, 5, tzinfo=UTC),
+            datetime.date(9999, 12, 31),
+        ]
+        assert orjson.loads(orjson.dumps(obj), datetimes=True) == obj
+        naive = datetime.datetime(1, 1, 1)
+        assert orjson.loads(
+            orjson.dumps(naive, option=orjson.OPT_NAIVE_UTC), datetimes=True
+        ) == naive.replace(tzinfo=UTC)
+
+    def test_datetimes_not_matching(self):
+        """
+        loads() datetimes=True strings that are not RFC 3339 are str
+        """
+        for val in (
+            "",
+            "noon",
+            "2020-01-01 12:30:00",
+            "2020-02-30",
+            "2019-02-29",
+            "0000-01-01",
+            "2020-13-01",
+            "2020-1-01",
+            "12:30",
+            "24:00:00",
+            "23:59:60",
+            "12:30:00.",
+            "12:30:00+01",
+            "12:30:00+0100",
+            "12:30:00+24:00",
+            "2020-01-01T12:30:00Zx",
+            "2020-01-01T",
+            "١٢:٣٠:٠٠",
+        ):
+            assert orjson.loads(orjson.dumps(val), datetimes=True) == val
+
+    def test_datetimes_keys(self):
+        """
+        loads() datetimes=True does not change keys
+        """
+        assert orjson.loads('{"2020-01-01": "2020-01-01"}', datetimes=True) == {
+            "2020-01-01": datetime.date(2020, 1, 1)
+        }
+
+    def test_datetimes_nested(self):
+        """
+        loads() datetimes=True in containers and with other arguments
+        """
+        doc = b'{"a": ["2020-01-01", {"b": "12:00:00Z"}]}'
+        noon = datetime.time(12, tzinfo=UTC)
+        expected = {"a": [datetime.date(2020, 1, 1), {"b": noon}]}
+        assert orjson.loads(doc, datetimes=True) == expected
+        assert orjson.Decoder(datetimes=True).decode(doc) == expected
+        assert orjson.loads(doc, datetimes=True, tuples=True) == {
+            "a": (datetime.date(2020, 1, 1), {"b": noon})
+        }
+
+    def test_datetimes_binary(self):
+        """
+        unpackb() and loads_cbor() datetimes=True
+        """
+        assert orjson.unpackb(b"\xaa2020-01-01", datetimes=True) == datetime.date(
+            2020, 1, 1
+        )
+        assert orjson.loads_cbor(b"\x6a2020-01-01", datetimes=True) == (
+            datetime.date(2020, 1, 1)
+        )
+
+    def test_datetimes_invalid(self):
+        """
+        loads() datetimes must be a bool
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("1", datetimes=1)
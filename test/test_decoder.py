@@ -0,0 +1,163 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import decimal
+import sys
+import threading
+
+import pytest
+
+import orjson
+
+
+class TestDecoder:
+    def test_decoder(self):
+        """
+        Decoder.decode() without arguments is loads()
+        """
+        decoder = orjson.Decoder()
+        for val in (b'{"a": [1, 2.5, "b"]}', '{"a": [1, 2.5, "b"]}'):
+            assert decoder.decode(val) == {"a": [1, 2.5, "b"]}
+        assert decoder.decode(bytearray(b"[]")) == []
+        assert decoder.decode(memoryview(b"null")) is None
+
+    def test_decoder_args(self):
+        """
+        Decoder.decode() uses the arguments given to Decoder
+        """
+        decoder = orjson.Decoder(
+            parse_float=decimal.Decimal,
+            object_pairs_hook=list,
+            tuples=True,
+        )
+        for _ in range(3):
+            assert decoder.decode('{"a": [0.1]}') == [("a", (decimal.Decimal("0.1"),))]
+
+    def test_decoder_flags(self):
+        """
+        Decoder allow_nan, jsonc, duplicate_keys
+        """
+        assert orjson.Decoder(allow_nan=True).decode("[NaN]")[0] != 0
+        assert orjson.Decoder(jsonc=True).decode("[1, // a\n2,]") == [1, 2]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.Decoder(duplicate_keys="error").decode('{"a": 1, "a": 2}')
+
+    def test_decoder_limits_per_call(self):
+        """
+        Decoder max_items and max_bytes apply to each call
+        """
+        decoder = orjson.Decoder(max_items=2, max_bytes=6)
+        for _ in range(10):
+            assert decoder.decode(b"[1, 2]") == [1, 2]
+        with pytest.raises(orjson.JSONDecodeError):
+            decoder.decode(b"[1,2,3]")
+        assert decoder.decode(b"[1]") == [1]
+
+    def test_decoder_offset_length(self):
+        """
+        Decoder.decode() offset and length
+        """
+        decoder = orjson.Decoder()
+        assert decoder.decode(b"xx[1]yy", offset=2, length=3) == [1]
+        assert decoder.decode(b"xx[1]", offset=2) == [1]
+        with pytest.raises(orjson.JSONDecodeError):
+            decoder.decode(b"xx[1]yy")
+        with pytest.raises(orjson.JSONDecodeError):
+            decoder.decode(b"[1]", offset=4)
+
+    def test_decoder_offset_length_not_bound(self):
+        """
+        Decoder does not accept offset and length
+        """
+        for arg in ("offset", "length"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.Decoder(**{arg: 1})
+
+    def test_decoder_invalid(self):
+        """
+        Decoder invalid arguments
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.Decoder(object_hook=1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.Decoder(max_depth=-1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.Decoder(unknown=True)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.Decoder(list)
+
+    def test_decoder_decode_invalid(self):
+        """
+        Decoder.decode() invalid arguments
+        """
+        decoder = orjson.Decoder()
+        with pytest.raises(orjson.JSONDecodeError):
+            decoder.decode()
+        with pytest.raises(orjson.JSONDecodeError):
+            decoder.decode(b"[]", b"[]")
+        with pytest.raises(orjson.JSONDecodeError):
+            decoder.decode(b"[]", max_items=1)
+        with pytest.raises(orjson.JSONDecodeError):
+            decoder.decode(1)
+
+    def test_decoder_refcount(self):
+        """
+        Decoder holds a reference to its hooks until it is freed
+        """
+
+        def object_hook(obj):
+            return obj
+
+        before = sys.getrefcount(object_hook)
+        decoder = orjson.Decoder(object_hook=object_hook, parse_int=object_hook)
+        assert sys.getrefcount(object_hook) == before + 2
+        del decoder
+        assert sys.getrefcount(object_hook) == before
+
+    def test_decoder_hook_outlives_caller(self):
+        """
+        Decoder hooks are usable after the caller drops its reference
+        """
+        decoder = orjson.Decoder(object_hook=lambda obj: sorted(obj))
+        assert decoder.decode('{"b": 1, "a": 2}') == ["a", "b"]
+
+    def test_decoder_reentrant(self):
+        """
+        Decoder.decode() called from one of its own hooks
+        """
+
+        def object_hook(obj):
+            if "inner" in obj:
+                return decoder.decode(obj["inner"])
+            return obj
+
+        decoder = orjson.Decoder(object_hook=object_hook, max_items=3)
+        assert decoder.decode('{"inner": "[1, 2, 3]"}') == [1, 2, 3]
+
+    def test_decoder_threads(self):
+        """
+        Decoder used by several threads at once
+        """
+        decoder = orjson.Decoder(max_items=100)
+        doc = orjson.dumps([{"a": idx} for idx in range(40)])
+        results = []
+
+        def decode():
+            for _ in range(100):
+                results.append(decoder.decode(doc) == [{"a": idx} for idx in range(40)])
+
+        threads = [threading.Thread(target=decode) for _ in range(4)]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+        assert len(results) == 400
+        assert all(results)
+
+    def test_decoder_not_subclassable(self):
+        """
+        Decoder cannot be subclassed
+        """
+        with pytest.raises(TypeError):
+
+            class Subclass(orjson.Decoder):  # type: ignore
+                pass
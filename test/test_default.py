@@ -95,6 +95,7 @@ class TestType:
         except Exception as err:
             assert isinstance(err, orjson.JSONEncodeError)
             assert str(err) == "Type is not JSON serializable: Custom"
+            assert isinstance(err.__cause__, NotImplementedError)
             ran = True
         assert ran
 
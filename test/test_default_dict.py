@@ -0,0 +1,185 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import decimal
+import ipaddress
+import uuid
+
+import pytest
+
+import orjson
+
+
+class Base:
+    def __init__(self, val):
+        self.val = val
+
+
+class Child(Base):
+    pass
+
+
+class GrandChild(Child):
+    pass
+
+
+class Other:
+    pass
+
+
+@dataclasses.dataclass
+class Record:
+    value: object
+
+
+class TestDefaultDict:
+    def test_default_dict(self):
+        """
+        dumps() default dict of types to callables
+        """
+        assert (
+            orjson.dumps(
+                [decimal.Decimal("1.10"), ipaddress.ip_address("127.0.0.1"), {1, 2}],
+                default={
+                    decimal.Decimal: str,
+                    ipaddress.IPv4Address: str,
+                    set: sorted,
+                },
+            )
+            == b'["1.10","127.0.0.1",[1,2]]'
+        )
+
+    def test_default_dict_exact_type_first(self):
+        """
+        dumps() default dict exact type takes precedence over a base class
+        """
+        default = {Base: lambda obj: "base", Child: lambda obj: "child"}
+        assert orjson.dumps(
+            [Base(1), Child(2), GrandChild(3)], default=default
+        ) == (b'["base","child","child"]')
+
+    def test_default_dict_mro(self):
+        """
+        dumps() default dict base class
+        """
+        assert (
+            orjson.dumps(GrandChild(1), default={Base: lambda obj: obj.val})
+            == b"1"
+        )
+
+    def test_default_dict_object(self):
+        """
+        dumps() default dict object matches every type
+        """
+        assert (
+            orjson.dumps(
+                [Other(), Base(1)], default={object: lambda obj: type(obj).__name__}
+            )
+            == b'["Other","Base"]'
+        )
+
+    def test_default_dict_no_match(self):
+        """
+        dumps() default dict type not in it
+        """
+        with pytest.raises(orjson.JSONEncodeError) as exc_info:
+            orjson.dumps({"a": Other()}, default={Base: str})
+        assert str(exc_info.value) == "Type is not JSON serializable: Other"
+
+    def test_default_dict_empty(self):
+        """
+        dumps() default empty dict
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Other(), default={})
+
+    def test_default_dict_natively_supported(self):
+        """
+        dumps() default dict does not apply to supported types
+        """
+        val = uuid.UUID("7202d115-7ff3-4c81-a7c1-2a1f067b1ece")
+        assert orjson.dumps([1, "a", val], default={int: str, str: len, uuid.UUID: str}) == (
+            b'[1,"a","7202d115-7ff3-4c81-a7c1-2a1f067b1ece"]'
+        )
+
+    def test_default_dict_returns_unsupported(self):
+        """
+        dumps() default dict converter returning a type handled by another
+        """
+        default = {
+            Base: lambda obj: decimal.Decimal(obj.val),
+            decimal.Decimal: str,
+        }
+        assert orjson.dumps(Base(1), default=default) == b'"1"'
+
+    def test_default_dict_converter_raises(self):
+        """
+        dumps() default dict converter raising
+        """
+
+        def convert(obj):
+            raise ValueError
+
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Base(1), default={Base: convert})
+
+    def test_default_dict_recursion(self):
+        """
+        dumps() default dict converter returning the same type
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Base(1), default={Base: lambda obj: Base(obj.val)})
+
+    def test_default_dict_dataclass(self):
+        """
+        dumps() default dict value of a dataclass field
+        """
+        assert (
+            orjson.dumps(Record(decimal.Decimal("2.5")), default={decimal.Decimal: float})
+            == b'{"value":2.5}'
+        )
+
+    def test_default_dict_fallback(self):
+        """
+        dumps() default dict type not in it with OPT_FALLBACK_STR
+        """
+        assert (
+            orjson.dumps(
+                [Base(1), decimal.Decimal("1")],
+                default={decimal.Decimal: float},
+                option=orjson.OPT_FALLBACK_STR,
+            )
+            == orjson.dumps([str(Base(1)), 1.0])
+        )
+
+    def test_default_dict_path(self):
+        """
+        dumps() default dict with OPT_DEFAULT_PATH
+        """
+        assert (
+            orjson.dumps(
+                {"a": [Base(1)]},
+                default={Base: lambda obj, path: path},
+                option=orjson.OPT_DEFAULT_PATH,
+            )
+            == b'{"a":["/a/0"]}'
+        )
+
+    def test_default_dict_subclass(self):
+        """
+        dumps() default dict subclass is called
+        """
+
+        class Converters(dict):
+            def __call__(self, obj):
+                return "called"
+
+        assert orjson.dumps(Base(1), default=Converters({Base: str})) == b'"called"'
+
+    def test_default_dict_stats(self):
+        """
+        dumps() default dict calls are counted in stats
+        """
+        stats = {}
+        orjson.dumps([Base(1), Base(2)], default={Base: str}, stats=stats)
+        assert stats["default_calls"] == 2
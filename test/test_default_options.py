@@ -0,0 +1,131 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import datetime
+
+import pytest
+
+import orjson
+
+
+@dataclasses.dataclass
+class Dataclass:
+    b: int
+    a: datetime.datetime
+
+
+class TestDefaultOptions:
+    def teardown_method(self, _method=None):
+        orjson.set_default_options(None)
+
+    def test_default_options_unset(self):
+        """
+        get_default_options() is 0 by default
+        """
+        assert orjson.get_default_options() == 0
+
+    def test_default_options(self):
+        """
+        set_default_options() applies to dumps()
+        """
+        try:
+            orjson.set_default_options(orjson.OPT_SORT_KEYS | orjson.OPT_NAIVE_UTC)
+            assert (
+                orjson.get_default_options()
+                == orjson.OPT_SORT_KEYS | orjson.OPT_NAIVE_UTC
+            )
+            assert (
+                orjson.dumps({"b": datetime.datetime(1970, 1, 1), "a": 1})
+                == b'{"a":1,"b":"1970-01-01T00:00:00+00:00"}'
+            )
+        finally:
+            self.teardown_method()
+
+    def test_default_options_overridden(self):
+        """
+        set_default_options() replaced by option
+        """
+        try:
+            orjson.set_default_options(orjson.OPT_SORT_KEYS)
+            assert (
+                orjson.dumps({"b": 1, "a": 2}, option=orjson.OPT_APPEND_NEWLINE)
+                == b'{"b":1,"a":2}\n'
+            )
+            assert orjson.dumps({"b": 1, "a": 2}, option=0) == b'{"b":1,"a":2}'
+            assert orjson.dumps({"b": 1, "a": 2}, None, 0) == b'{"b":1,"a":2}'
+        finally:
+            self.teardown_method()
+
+    def test_default_options_option_none(self):
+        """
+        set_default_options() applies to option=None
+        """
+        try:
+            orjson.set_default_options(orjson.OPT_SORT_KEYS)
+            assert orjson.dumps({"b": 1, "a": 2}, option=None) == b'{"a":2,"b":1}'
+        finally:
+            self.teardown_method()
+
+    def test_default_options_kwarg_true(self):
+        """
+        set_default_options() combines with keyword arguments
+        """
+        try:
+            orjson.set_default_options(orjson.OPT_SORT_KEYS)
+            assert (
+                orjson.dumps({"b": 1, "a": 2}, append_newline=True)
+                == b'{"a":2,"b":1}\n'
+            )
+        finally:
+            self.teardown_method()
+
+    def test_default_options_kwarg_false(self):
+        """
+        set_default_options() disabled by keyword argument
+        """
+        try:
+            orjson.set_default_options(orjson.OPT_SORT_KEYS)
+            assert orjson.dumps({"b": 1, "a": 2}, sort_keys=False) == b'{"b":1,"a":2}'
+            assert (
+                orjson.dumps({"b": 1, "a": 2}, option=None, sort_keys=False)
+                == b'{"b":1,"a":2}'
+            )
+        finally:
+            self.teardown_method()
+
+    def test_default_options_reset(self):
+        """
+        set_default_options(None) and set_default_options(0)
+        """
+        try:
+            for reset in (None, 0):
+                orjson.set_default_options(orjson.OPT_SORT_KEYS)
+                orjson.set_default_options(reset)
+                assert orjson.get_default_options() == 0
+                assert orjson.dumps({"b": 1, "a": 2}) == b'{"b":1,"a":2}'
+        finally:
+            self.teardown_method()
+
+    def test_default_options_compile(self):
+        """
+        compile() applies default options when called
+        """
+        try:
+            orjson.set_default_options(orjson.OPT_NAIVE_UTC | orjson.OPT_UTC_Z)
+            serialize = orjson.compile(Dataclass)
+            orjson.set_default_options(None)
+            assert (
+                serialize(Dataclass(1, datetime.datetime(1970, 1, 1)))
+                == b'{"b":1,"a":"1970-01-01T00:00:00Z"}'
+            )
+        finally:
+            self.teardown_method()
+
+    def test_default_options_invalid(self):
+        """
+        set_default_options() invalid option
+        """
+        for val in (-1, 1 << 30, True, "1", 1.0):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.set_default_options(val)
+        assert orjson.get_default_options() == 0
@@ -0,0 +1,220 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import decimal
+
+import pytest
+
+import orjson
+
+try:
+    import numpy
+except ImportError:
+    numpy = None  # type: ignore
+
+
+class Custom:
+    pass
+
+
+@dataclasses.dataclass
+class Record:
+    id: int
+    value: object
+
+
+def collect(paths):
+    def default(obj, path):
+        paths.append(path)
+        return None
+
+    return default
+
+
+class TestDefaultPath:
+    def test_default_path_top_level(self):
+        """
+        OPT_DEFAULT_PATH object passed to dumps()
+        """
+        paths = []
+        assert (
+            orjson.dumps(Custom(), default=collect(paths), option=orjson.OPT_DEFAULT_PATH)
+            == b"null"
+        )
+        assert paths == [""]
+
+    def test_default_path(self):
+        """
+        OPT_DEFAULT_PATH nested in arrays and objects
+        """
+        paths = []
+        obj = {
+            "a": Custom(),
+            "b": [1, Custom(), {"c": (Custom(),)}],
+            "d": {"e": {"f": Custom()}},
+        }
+        assert (
+            orjson.dumps(obj, default=collect(paths), option=orjson.OPT_DEFAULT_PATH)
+            == b'{"a":null,"b":[1,null,{"c":[null]}],"d":{"e":{"f":null}}}'
+        )
+        assert paths == ["/a", "/b/1", "/b/2/c/0", "/d/e/f"]
+
+    def test_default_path_escape(self):
+        """
+        OPT_DEFAULT_PATH escapes ~ and / in keys
+        """
+        paths = []
+        obj = {"a/b": Custom(), "c~d": Custom(), "": Custom(), "ключ": Custom()}
+        orjson.dumps(obj, default=collect(paths), option=orjson.OPT_DEFAULT_PATH)
+        assert paths == ["/a~1b", "/c~0d", "/", "/ключ"]
+
+    def test_default_path_after_container(self):
+        """
+        OPT_DEFAULT_PATH after a nested container ends
+        """
+        paths = []
+        obj = [[1, [2]], {"a": {"b": 1}, "c": Custom()}, Custom()]
+        orjson.dumps(obj, default=collect(paths), option=orjson.OPT_DEFAULT_PATH)
+        assert paths == ["/1/c", "/2"]
+
+    def test_default_path_returned(self):
+        """
+        OPT_DEFAULT_PATH within what default returns
+        """
+        paths = []
+
+        def default(obj, path):
+            paths.append(path)
+            if isinstance(obj, Custom):
+                return {"inner": [decimal.Decimal("1")]}
+            return str(obj)
+
+        assert (
+            orjson.dumps(
+                {"a": Custom()}, default=default, option=orjson.OPT_DEFAULT_PATH
+            )
+            == b'{"a":{"inner":["1"]}}'
+        )
+        assert paths == ["/a", "/a/inner/0"]
+
+    def test_default_path_dataclass(self):
+        """
+        OPT_DEFAULT_PATH dataclass field
+        """
+        paths = []
+        orjson.dumps(
+            [Record(1, Custom())], default=collect(paths), option=orjson.OPT_DEFAULT_PATH
+        )
+        assert paths == ["/0/value"]
+
+    def test_default_path_sort_keys(self):
+        """
+        OPT_DEFAULT_PATH with OPT_SORT_KEYS
+        """
+        paths = []
+        orjson.dumps(
+            {"b": Custom(), "a": Custom()},
+            default=collect(paths),
+            option=orjson.OPT_DEFAULT_PATH | orjson.OPT_SORT_KEYS,
+        )
+        assert paths == ["/a", "/b"]
+
+    def test_default_path_non_str_keys(self):
+        """
+        OPT_DEFAULT_PATH with OPT_NON_STR_KEYS
+        """
+        paths = []
+        orjson.dumps(
+            {1: Custom(), None: [Custom()]},
+            default=collect(paths),
+            option=orjson.OPT_DEFAULT_PATH | orjson.OPT_NON_STR_KEYS,
+        )
+        assert paths == ["/1", "/null/0"]
+
+    def test_default_path_include_keys(self):
+        """
+        OPT_DEFAULT_PATH with include_keys
+        """
+        paths = []
+        orjson.dumps(
+            {"a": Custom(), "b": {"c": 1, "d": Custom()}},
+            default=collect(paths),
+            option=orjson.OPT_DEFAULT_PATH,
+            include_keys={"b.d"},
+        )
+        assert paths == ["/b/d"]
+
+    def test_default_path_indent(self):
+        """
+        OPT_DEFAULT_PATH with OPT_INDENT_2
+        """
+        paths = []
+        assert orjson.dumps(
+            [1, {"a": Custom()}],
+            default=collect(paths),
+            option=orjson.OPT_DEFAULT_PATH | orjson.OPT_INDENT_2,
+        ) == b'[\n  1,\n  {\n    "a": null\n  }\n]'
+        assert paths == ["/1/a"]
+
+    def test_default_path_per_field(self):
+        """
+        OPT_DEFAULT_PATH serializing a type depending on its field
+        """
+
+        def default(obj, path):
+            if isinstance(obj, decimal.Decimal):
+                return str(obj) if path.endswith("/price") else float(obj)
+            raise TypeError
+
+        assert (
+            orjson.dumps(
+                {"price": decimal.Decimal("1.10"), "weight": decimal.Decimal("2.5")},
+                default=default,
+                option=orjson.OPT_DEFAULT_PATH,
+            )
+            == b'{"price":"1.10","weight":2.5}'
+        )
+
+    def test_default_path_error(self):
+        """
+        OPT_DEFAULT_PATH default raising
+        """
+
+        def default(obj, path):
+            raise TypeError(path)
+
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps([Custom()], default=default, option=orjson.OPT_DEFAULT_PATH)
+
+    def test_default_path_one_argument(self):
+        """
+        OPT_DEFAULT_PATH default taking one argument fails
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps([Custom()], default=lambda obj: None, option=orjson.OPT_DEFAULT_PATH)
+
+    def test_default_path_without_option(self):
+        """
+        default is called with only the object without OPT_DEFAULT_PATH
+        """
+        calls = []
+
+        def default(obj, *args):
+            calls.append(args)
+            return None
+
+        orjson.dumps({"a": Custom()}, default=default)
+        assert calls == [()]
+
+    @pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+    def test_default_path_numpy(self):
+        """
+        OPT_DEFAULT_PATH numpy array
+        """
+        paths = []
+        orjson.dumps(
+            {"a": numpy.array([1, 2], numpy.complex64)},
+            default=collect(paths),
+            option=orjson.OPT_DEFAULT_PATH | orjson.OPT_SERIALIZE_NUMPY,
+        )
+        assert paths == ["/a"]
@@ -0,0 +1,119 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections
+import types
+
+import pytest
+
+import orjson
+
+
+class LowerDict(dict):
+    def __init__(self, obj):
+        super().__init__((key.lower(), val) for key, val in obj.items())
+
+    def __getitem__(self, key):
+        return super().__getitem__(key.lower())
+
+
+class TestDictClass:
+    def test_dict_class(self):
+        """
+        dict_class of nested objects, including empty ones
+        """
+        val = orjson.loads(
+            '{"b": {"a": 1}, "c": [{}], "d": 2}', dict_class=collections.OrderedDict
+        )
+        assert type(val) is collections.OrderedDict
+        assert type(val["b"]) is collections.OrderedDict
+        assert type(val["c"][0]) is collections.OrderedDict
+        assert list(val) == ["b", "c", "d"]
+        assert type(orjson.loads("{}", dict_class=collections.OrderedDict)) is (
+            collections.OrderedDict
+        )
+
+    def test_dict_class_init(self):
+        """
+        dict_class is called with the dict of each object
+        """
+        val = orjson.loads('{"Content-Type": {"X": 1}}', dict_class=LowerDict)
+        assert type(val) is LowerDict
+        assert val["CONTENT-TYPE"]["x"] == 1
+
+    def test_dict_class_dict(self):
+        """
+        dict_class of None or dict
+        """
+        for cls in (None, dict):
+            assert type(orjson.loads('{"a": {}}', dict_class=cls)["a"]) is dict
+
+    def test_dict_class_duplicate_keys(self):
+        """
+        dict_class is called after duplicate_keys is applied
+        """
+        val = orjson.loads(
+            '{"a": 1, "a": 2}',
+            dict_class=collections.OrderedDict,
+            duplicate_keys="list",
+        )
+        assert val == collections.OrderedDict([("a", [1, 2])])
+
+    def test_dict_class_object_hook(self):
+        """
+        dict_class instance is given to object_hook
+        """
+        types_seen = []
+
+        def hook(obj):
+            types_seen.append(type(obj))
+            return obj
+
+        orjson.loads('{"a": {"b": 1}}', dict_class=LowerDict, object_hook=hook)
+        assert types_seen == [LowerDict, LowerDict]
+
+    def test_dict_class_immutable(self):
+        """
+        dict_class instance is made read-only by immutable
+        """
+        val = orjson.loads('{"A": [1]}', dict_class=LowerDict, immutable=True)
+        assert isinstance(val, types.MappingProxyType)
+        assert val["a"] == (1,)
+
+    def test_dict_class_exception(self):
+        """
+        dict_class propagates an exception raised by it
+        """
+
+        class Error(dict):
+            def __init__(self, obj):
+                raise ValueError("dict_class")
+
+        with pytest.raises(ValueError):
+            orjson.loads('[{"a": 1}]', dict_class=Error)
+
+    def test_dict_class_decoder(self):
+        """
+        dict_class of Decoder, Parser, load(), and unpackb()
+        """
+        decoder = orjson.Decoder(dict_class=collections.OrderedDict)
+        assert type(decoder.decode(b'{"a": 1}')) is collections.OrderedDict
+        parser = orjson.Parser(dict_class=collections.OrderedDict)
+        assert type(parser.feed(b'{"a": 1}')[0]) is collections.OrderedDict
+        val = orjson.unpackb(b"\x81\xa1a\x01", dict_class=collections.OrderedDict)
+        assert type(val) is collections.OrderedDict
+
+    def test_dict_class_invalid(self):
+        """
+        dict_class that is not a subclass of dict or cannot be combined
+        """
+        for cls in (1, list, collections.UserDict, dict()):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads("{}", dict_class=cls)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("{}", dict_class=LowerDict, object_pairs_hook=list)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(
+                "{}",
+                dict_class=LowerDict,
+                schema=orjson.Schema({"type": "object"}),
+            )
@@ -0,0 +1,200 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import datetime
+import os
+import tempfile
+import threading
+
+import pytest
+
+import orjson
+
+from .util import DOC
+
+
+def read(fd):
+    os.lseek(fd, 0, os.SEEK_SET)
+    return os.read(fd, 1 << 24)
+
+
+class TestDumpFd:
+    def test_dump_fd(self):
+        """
+        dump_fd() writes the output of dumps() and returns its length
+        """
+        with tempfile.TemporaryFile() as fileobj:
+            fd = fileobj.fileno()
+            assert orjson.dump_fd(DOC, fd) == len(orjson.dumps(DOC))
+            assert orjson.dump_fd([], fd) == 2
+            assert read(fd) == orjson.dumps(DOC) + b"[]"
+
+    def test_dump_fd_fileno(self):
+        """
+        dump_fd() of an object with fileno()
+        """
+        with tempfile.TemporaryFile() as fileobj:
+            orjson.dump_fd(DOC, fileobj)
+            assert read(fileobj.fileno()) == orjson.dumps(DOC)
+
+    def test_dump_fd_options(self):
+        """
+        dump_fd() with default and options
+        """
+        obj = {"b": datetime.date(2000, 1, 1), "a": object()}
+        with tempfile.TemporaryFile() as fileobj:
+            fd = fileobj.fileno()
+            orjson.dump_fd(obj, fd, str, orjson.OPT_SORT_KEYS, indent=2)
+            expected = orjson.dumps(obj, str, orjson.OPT_SORT_KEYS, indent=2)
+            assert read(fd) == expected
+
+    def test_dump_fd_kwargs(self):
+        """
+        dump_fd() separators, float_precision, and max_size, which limits
+        each line
+        """
+        obj = {"a": [1.125, 2]}
+        kwargs = {"separators": (", ", ": "), "float_precision": 2}
+        with tempfile.TemporaryFile() as fileobj:
+            fd = fileobj.fileno()
+            orjson.dump_fd(obj, fd, **kwargs)
+            assert read(fd) == orjson.dumps(obj, **kwargs)
+        with tempfile.TemporaryFile() as fileobj:
+            fd = fileobj.fileno()
+            assert orjson.dump_fd([[1], [2]], fd, lines=True, max_size=4) == 8
+            with pytest.raises(orjson.JSONEncodeSizeError):
+                orjson.dump_fd([[1], [2, 3]], fd, lines=True, max_size=4)
+            assert read(fd) == b"[1]\n[2]\n[1]\n"
+
+    def test_dump_fd_lines(self):
+        """
+        dump_fd() lines=True writes each item of an iterable as a line
+        """
+        rows = [{"id": idx, "name": "x" * (idx % 7)} for idx in range(5000)]
+        expected = b"".join(orjson.dumps(row) + b"\n" for row in rows)
+        with tempfile.TemporaryFile() as fileobj:
+            fd = fileobj.fileno()
+            assert orjson.dump_fd(rows, fd, lines=True) == len(expected)
+            assert orjson.dump_fd(iter(rows), fd, lines=True) == len(expected)
+            assert orjson.dump_fd([], fd, lines=True) == 0
+            assert read(fd) == expected * 2
+
+    def test_dump_fd_lines_append_newline(self):
+        """
+        dump_fd() lines=True does not write a blank line for
+        OPT_APPEND_NEWLINE
+        """
+        with tempfile.TemporaryFile() as fileobj:
+            fd = fileobj.fileno()
+            orjson.dump_fd([1, 2], fd, option=orjson.OPT_APPEND_NEWLINE, lines=True)
+            assert read(fd) == b"1\n2\n"
+
+    def test_dump_fd_lines_error(self):
+        """
+        dump_fd() lines=True writes the lines before an item that fails
+        """
+
+        def gen():
+            yield 1
+            raise ValueError("gen")
+
+        with tempfile.TemporaryFile() as fileobj:
+            fd = fileobj.fileno()
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dump_fd([1, 2, object(), 3], fd, lines=True)
+            assert read(fd) == b"1\n2\n"
+            with pytest.raises(ValueError):
+                orjson.dump_fd(gen(), fd, lines=True)
+            assert read(fd) == b"1\n2\n1\n"
+            with pytest.raises(TypeError):
+                orjson.dump_fd(1, fd, lines=True)
+
+    def test_dump_fd_pipe(self):
+        """
+        dump_fd() to a pipe
+        """
+        read_fd, write_fd = os.pipe()
+        try:
+            orjson.dump_fd(["a", "b"], write_fd, lines=True)
+            assert os.read(read_fd, 100) == b'"a"\n"b"\n'
+        finally:
+            os.close(read_fd)
+            os.close(write_fd)
+
+    def test_dump_fd_pipe_partial(self):
+        """
+        dump_fd() to a pipe read by another thread, which writes in parts
+        """
+        rows = [["x" * 1000, idx] for idx in range(2000)]
+        expected = b"".join(orjson.dumps(row) + b"\n" for row in rows)
+        chunks = []
+        read_fd, write_fd = os.pipe()
+
+        def reader():
+            while True:
+                chunk = os.read(read_fd, 4096)
+                if not chunk:
+                    break
+                chunks.append(chunk)
+
+        thread = threading.Thread(target=reader)
+        thread.start()
+        try:
+            assert orjson.dump_fd(rows, write_fd, lines=True) == len(expected)
+            assert orjson.dump_fd(rows, write_fd) == len(orjson.dumps(rows))
+        finally:
+            os.close(write_fd)
+            thread.join()
+            os.close(read_fd)
+        assert b"".join(chunks) == expected + orjson.dumps(rows)
+
+    def test_dump_fd_nonblocking(self):
+        """
+        dump_fd() to a full non-blocking pipe gives the bytes written
+        """
+        read_fd, write_fd = os.pipe()
+        os.set_blocking(write_fd, False)
+        try:
+            obj = ["x" * (1 << 20)]
+            doc = orjson.dumps(obj)
+            with pytest.raises(BlockingIOError) as exc_info:
+                orjson.dump_fd(obj, write_fd)
+            written = exc_info.value.characters_written
+            assert 0 < written < len(doc)
+            assert "os error" not in str(exc_info.value)
+            assert os.read(read_fd, written) == doc[:written]
+        finally:
+            os.close(read_fd)
+            os.close(write_fd)
+
+    def test_dump_fd_error(self):
+        """
+        dump_fd() of an unsupported type or to a file descriptor not writable
+        """
+        with tempfile.TemporaryFile() as fileobj:
+            fd = fileobj.fileno()
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dump_fd(object(), fd)
+            assert read(fd) == b""
+        fd = os.open(os.devnull, os.O_RDONLY)
+        try:
+            with pytest.raises(OSError):
+                orjson.dump_fd([], fd)
+        finally:
+            os.close(fd)
+        with pytest.raises(ValueError):
+            orjson.dump_fd([], -1)
+        with pytest.raises(TypeError):
+            orjson.dump_fd([], 1.5)
+
+    def test_dump_fd_args(self):
+        """
+        dump_fd() invalid arguments
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dump_fd([])
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dump_fd([], 1, None, None, None)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dump_fd([], 1, lines=1)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dump_fd([], 1, offset=1)
@@ -0,0 +1,155 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import array
+import datetime
+
+import pytest
+
+import orjson
+
+from .util import DOC
+
+try:
+    from multiprocessing import shared_memory
+except ImportError:
+    shared_memory = None  # type: ignore
+
+
+class TestDumpsInto:
+    def test_dumps_into(self):
+        """
+        dumps_into() writes the output of dumps() and returns its length
+        """
+        expected = orjson.dumps(DOC)
+        buffer = bytearray(100)
+        assert orjson.dumps_into(DOC, buffer) == len(expected)
+        assert buffer[: len(expected)] == expected
+        assert buffer[len(expected) :] == bytes(100 - len(expected))
+
+    def test_dumps_into_offset(self):
+        """
+        dumps_into() offset
+        """
+        buffer = bytearray(b"x" * 10)
+        assert orjson.dumps_into([1], buffer, offset=4) == 3
+        assert buffer == b"xxxx[1]xxx"
+        assert orjson.dumps_into([], buffer, offset=8) == 2
+        assert buffer == b"xxxx[1]x[]"
+
+    def test_dumps_into_large(self):
+        """
+        dumps_into() output larger than the stack buffer of dumps()
+        """
+        obj = ["a" * 1000, list(range(10000))]
+        expected = orjson.dumps(obj)
+        buffer = bytearray(len(expected) + 1)
+        assert orjson.dumps_into(obj, buffer, offset=1) == len(expected)
+        assert buffer[1:] == expected
+
+    def test_dumps_into_buffers(self):
+        """
+        dumps_into() memoryview and array.array
+        """
+        buffer = bytearray(10)
+        assert orjson.dumps_into([1], memoryview(buffer)[2:]) == 3
+        assert buffer[2:5] == b"[1]"
+        arr = array.array("i", [0, 0])
+        assert orjson.dumps_into("ab", arr) == 4
+        assert arr.tobytes() == b'"ab"\x00\x00\x00\x00'
+
+    @pytest.mark.skipif(shared_memory is None, reason="shared_memory unavailable")
+    def test_dumps_into_shared_memory(self):
+        """
+        dumps_into() multiprocessing.shared_memory.SharedMemory
+        """
+        shm = shared_memory.SharedMemory(create=True, size=4096)
+        try:
+            length = orjson.dumps_into(DOC, shm.buf)
+            assert orjson.loads(shm.buf[:length]) == DOC
+        finally:
+            shm.close()
+            shm.unlink()
+
+    def test_dumps_into_options(self):
+        """
+        dumps_into() with default and options
+        """
+        obj = {"b": datetime.date(2000, 1, 1), "a": object()}
+        expected = orjson.dumps(obj, str, orjson.OPT_SORT_KEYS, indent=2)
+        buffer = bytearray(100)
+        length = orjson.dumps_into(obj, buffer, str, orjson.OPT_SORT_KEYS, indent=2)
+        assert buffer[:length] == expected
+        length = orjson.dumps_into([], buffer, option=orjson.OPT_APPEND_NEWLINE)
+        assert buffer[:length] == b"[]\n"
+
+    def test_dumps_into_kwargs(self):
+        """
+        dumps_into() separators, float_precision, and max_size
+        """
+        obj = {"a": [1.125, 2]}
+        kwargs = {"separators": (", ", ": "), "float_precision": 2}
+        expected = orjson.dumps(obj, **kwargs)
+        buffer = bytearray(100)
+        length = orjson.dumps_into(obj, buffer, **kwargs)
+        assert buffer[:length] == expected
+        with pytest.raises(orjson.JSONEncodeSizeError) as exc:
+            orjson.dumps_into(obj, buffer, max_size=4)
+        assert str(exc.value) == "Output exceeds max_size of 4 bytes"
+        with pytest.raises(orjson.JSONEncodeSizeError) as exc:
+            orjson.dumps_into("a" * 200, buffer, max_size=1000)
+        assert "buffer" in str(exc.value)
+        assert buffer[:length] == expected
+
+    def test_dumps_into_size(self):
+        """
+        dumps_into() raises JSONEncodeSizeError for output that does not fit
+        and leaves the buffer unmodified
+        """
+        buffer = bytearray(b"x" * 10)
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps_into("a" * 9, buffer)
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps_into([1], buffer, offset=8)
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps_into([], buffer, offset=10)
+        assert buffer == b"x" * 10
+        assert orjson.dumps_into("a" * 8, buffer) == 10
+        assert orjson.dumps_into(1, buffer, offset=9) == 1
+
+    def test_dumps_into_resize(self):
+        """
+        dumps_into() bytearray cannot be resized by default while serializing
+        """
+        buffer = bytearray(10)
+
+        def default(obj):
+            buffer.extend(b"x")
+            return None
+
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps_into([object()], buffer, default)
+        assert buffer == bytes(10)
+
+    def test_dumps_into_error(self):
+        """
+        dumps_into() of an unsupported type, to a buffer not writable, and
+        with invalid arguments
+        """
+        buffer = bytearray(10)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps_into(object(), buffer)
+        with pytest.raises(BufferError):
+            orjson.dumps_into([], b"x" * 10)
+        with pytest.raises(TypeError):
+            orjson.dumps_into([], None)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps_into([], buffer, offset=11)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps_into([], buffer, offset=-1)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps_into([], buffer, offset=1.0)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps_into([])
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps_into([], buffer, lines=True)
+        assert buffer == bytes(10)
@@ -0,0 +1,165 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import sys
+
+import pytest
+
+import orjson
+
+
+class TestDuplicateKeys:
+    def test_duplicate_keys_default(self):
+        """
+        loads() keeps the last value by default
+        """
+        assert orjson.loads('{"a": 1, "b": 2, "a": 3}') == {"a": 3, "b": 2}
+
+    def test_duplicate_keys_last(self):
+        """
+        loads() duplicate_keys="last"
+        """
+        assert orjson.loads('{"a": 1, "b": 2, "a": 3}', duplicate_keys="last") == {
+            "a": 3,
+            "b": 2,
+        }
+
+    def test_duplicate_keys_none(self):
+        """
+        loads() duplicate_keys=None
+        """
+        assert orjson.loads('{"a": 1, "a": 3}', duplicate_keys=None) == {"a": 3}
+
+    def test_duplicate_keys_first(self):
+        """
+        loads() duplicate_keys="first"
+        """
+        assert orjson.loads(
+            '{"a": 1, "b": 2, "a": 3, "a": 4}', duplicate_keys="first"
+        ) == {"a": 1, "b": 2}
+
+    def test_duplicate_keys_first_order(self):
+        """
+        loads() duplicate_keys="first" preserves the order of first appearance
+        """
+        assert list(
+            orjson.loads('{"b": 1, "a": 2, "b": 3}', duplicate_keys="first")
+        ) == ["b", "a"]
+
+    def test_duplicate_keys_error(self):
+        """
+        loads() duplicate_keys="error"
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads('{"a": 1, "b": 2, "a": 3}', duplicate_keys="error")
+        assert "Duplicate key \"a\"" in str(exc_info.value)
+
+    def test_duplicate_keys_error_nested(self):
+        """
+        loads() duplicate_keys="error" in a nested object
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('[{"a": {"b": 1, "b": 1}}]', duplicate_keys="error")
+
+    def test_duplicate_keys_error_position(self):
+        """
+        loads() duplicate_keys="error" reports the position of the duplicate
+        """
+        for kwargs in ({"duplicate_keys": "error"}, {"strict": True}):
+            with pytest.raises(orjson.JSONDecodeError) as exc_info:
+                orjson.loads('[{"a": {"b": 1,\n "b": 1}}]', **kwargs)
+            assert exc_info.value.pos == 17
+            assert exc_info.value.lineno == 2
+            assert exc_info.value.colno == 2
+
+    def test_duplicate_keys_error_unique(self):
+        """
+        loads() duplicate_keys="error" without duplicates
+        """
+        assert orjson.loads(
+            '{"a": {"a": 1}, "b": [{"a": 2}, {"a": 3}]}', duplicate_keys="error"
+        ) == {"a": {"a": 1}, "b": [{"a": 2}, {"a": 3}]}
+
+    def test_duplicate_keys_error_refcount(self):
+        """
+        loads() duplicate_keys="error" does not leak
+        """
+        key = "duplicate_keys_refcount"
+        doc = '[{"%s": 1}, {"a": 1, "a": 2}]' % key
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(doc, duplicate_keys="error")
+        before = sys.getrefcount(key)
+        for _ in range(100):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(doc, duplicate_keys="error")
+        assert sys.getrefcount(key) <= before
+
+    def test_duplicate_keys_list(self):
+        """
+        loads() duplicate_keys="list"
+        """
+        assert orjson.loads(
+            '{"a": 1, "b": 2, "a": 3, "a": 4}', duplicate_keys="list"
+        ) == {"a": [1, 3, 4], "b": 2}
+
+    def test_duplicate_keys_list_values(self):
+        """
+        loads() duplicate_keys="list" does not extend list values
+        """
+        assert orjson.loads(
+            '{"a": [1], "a": [2], "b": [3]}', duplicate_keys="list"
+        ) == {"a": [[1], [2]], "b": [3]}
+
+    def test_duplicate_keys_list_nested(self):
+        """
+        loads() duplicate_keys="list" in a nested object
+        """
+        assert orjson.loads(
+            '{"a": {"b": 1, "b": {"c": 2, "c": 3}}}', duplicate_keys="list"
+        ) == {"a": {"b": [1, {"c": [2, 3]}]}}
+
+    def test_duplicate_keys_object_hook(self):
+        """
+        loads() object_hook is given the object after duplicate_keys
+        """
+        assert orjson.loads(
+            '{"a": 1, "a": 2}', duplicate_keys="first", object_hook=lambda obj: obj
+        ) == {"a": 1}
+
+    def test_duplicate_keys_object_pairs_hook(self):
+        """
+        loads() duplicate_keys has no effect with object_pairs_hook
+        """
+        assert orjson.loads(
+            '{"a": 1, "a": 2}',
+            duplicate_keys="error",
+            object_pairs_hook=lambda pairs: pairs,
+        ) == [("a", 1), ("a", 2)]
+
+    def test_duplicate_keys_invalid(self):
+        """
+        loads() duplicate_keys invalid
+        """
+        for val in ("LAST", "", "raise", 1, True, b"first"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads("{}", duplicate_keys=val)
+
+    def test_duplicate_keys_load(self):
+        """
+        load() duplicate_keys
+        """
+        assert orjson.load(
+            io.BytesIO(b'{"a": 1, "a": 2}'), duplicate_keys="list"
+        ) == {"a": [1, 2]}
+
+    def test_duplicate_keys_load_chunked(self):
+        """
+        load() duplicate_keys with chunk_size
+        """
+        assert orjson.load(
+            io.BytesIO(b'{"a": 1, "a": 2}'), chunk_size=3, duplicate_keys="first"
+        ) == {"a": 1}
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(
+                io.BytesIO(b'{"a": 1, "a": 2}'), chunk_size=3, duplicate_keys="error"
+            )
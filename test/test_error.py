@@ -1,12 +1,14 @@
 # SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+import gzip
+import io
 import json
 
 import pytest
 
 import orjson
 
-from .util import read_fixture_str
+from .util import has_compression, read_fixture_str
 
 ASCII_TEST = b"""\
 {
@@ -93,3 +95,130 @@ class TestJsonDecodeError:
             "lineno": 1,
             "colno": 7,
         }
+
+    def test_snippet(self):
+        """
+        JSONDecodeError snippet is the input around the error
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads(ASCII_TEST)
+        exc = exc_info.value
+        assert exc.snippet == '  "d: "qwe"'
+        assert exc.snippet[min(exc.colno - 1, 32)] == "q"
+
+    def test_snippet_line(self):
+        """
+        JSONDecodeError snippet does not extend past the line of the error
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads('[\n1,\r\n"a" "b"\r\n]')
+        exc = exc_info.value
+        assert exc.lineno == 3
+        assert exc.snippet == '"a" "b"'
+        assert exc.snippet[exc.colno - 1] == '"'
+
+    def test_snippet_long_line(self):
+        """
+        JSONDecodeError snippet is at most 32 characters on either side
+        """
+        data = "[" + "1," * 100 + "x" + ",1" * 100 + "]"
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads(data)
+        exc = exc_info.value
+        assert exc.pos == 201
+        assert exc.snippet == "1," * 16 + "x" + ",1" * 16
+        assert exc.snippet[min(exc.colno - 1, 32)] == "x"
+
+    def test_snippet_non_ascii(self):
+        """
+        JSONDecodeError snippet is in characters
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads("""["東京😊", x]""")
+        exc = exc_info.value
+        assert exc.pos == 8
+        assert exc.snippet == """["東京😊", x]"""
+        assert exc.snippet[exc.colno - 1] == "x"
+
+    def test_snippet_end(self):
+        """
+        JSONDecodeError snippet at the end of the input
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads("[1, 2")
+        assert exc_info.value.snippet == "[1, 2"
+
+    def test_snippet_empty(self):
+        """
+        JSONDecodeError snippet of an error without a position
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads("")
+        assert exc_info.value.snippet == ""
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads("[1]", max_depth=-1)
+        assert exc_info.value.snippet == ""
+
+    def test_snippet_loads_lines(self):
+        """
+        JSONDecodeError snippet of loads_lines() is within the line
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            list(orjson.loads_lines(b'{"id":1}\n{"id":x}\n'))
+        exc = exc_info.value
+        assert exc.lineno == 2
+        assert exc.snippet == '{"id":x}'
+
+    def test_load_chunked_location(self):
+        """
+        JSONDecodeError of load() with chunk_size has pos, lineno, colno, and
+        the snippet of the input read
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.load(io.BytesIO(b"[1,\n  2,,]"), chunk_size=2)
+        exc = exc_info.value
+        assert (exc.pos, exc.lineno, exc.colno) == (8, 2, 5)
+        assert str(exc) == f"{exc.msg}: line 2 column 5 (char 8)"
+        assert exc.doc == ""
+        assert exc.snippet.startswith("  2,,")
+
+    def test_load_chunked_location_long(self):
+        """
+        JSONDecodeError of load() with chunk_size far into the input, after
+        non-ASCII characters
+        """
+        data = ("[" + '"東京",' * 1000 + "\n" + "1," * 100 + "x]").encode()
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.load(io.BytesIO(data), chunk_size=64)
+        exc = exc_info.value
+        assert exc.pos == 5002 + 200
+        assert (exc.lineno, exc.colno) == (2, 201)
+        assert exc.snippet == "1," * 16 + "x"
+        assert exc.snippet[min(exc.colno - 1, 32)] == "x"
+
+    @pytest.mark.skipif(
+        not has_compression(), reason="built without the compression feature"
+    )
+    def test_loads_compressed_location(self):
+        """
+        JSONDecodeError of loads_compressed() has pos, lineno, and colno
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads_compressed(gzip.compress(b'{"a":\n [1, x]}'))
+        exc = exc_info.value
+        assert (exc.pos, exc.lineno, exc.colno) == (11, 2, 6)
+        assert exc.snippet == " [1, x"
+
+    def test_unpackb_location(self):
+        """
+        JSONDecodeError of unpackb() and loads_cbor() has the byte of the
+        error as pos
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.unpackb(b"\x92\x01\xc1")
+        exc = exc_info.value
+        assert (exc.msg, exc.pos) == ("invalid MessagePack type 0xc1", 2)
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads_cbor(b"\x82\x01\xff")
+        exc = exc_info.value
+        assert (exc.msg, exc.pos) == ("unexpected break", 2)
@@ -0,0 +1,349 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import datetime
+import decimal
+import ipaddress
+
+import pytest
+
+import orjson
+
+
+class Custom:
+    def __repr__(self):
+        return "<Custom>"
+
+
+class ReprRaises:
+    def __repr__(self):
+        raise ValueError("repr")
+
+
+class ReprNonAscii:
+    def __repr__(self):
+        return "<é🐈>"
+
+
+class Lazy:
+    def __str__(self):
+        return "translated"
+
+    def __repr__(self):
+        return "<Lazy>"
+
+
+class StrRaises:
+    def __str__(self):
+        raise ValueError("str")
+
+
+class Point:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+
+class Slots:
+    __slots__ = ("a",)
+
+    def __init__(self):
+        self.a = 1
+
+    def __repr__(self):
+        return "<Slots>"
+
+
+@dataclasses.dataclass
+class Dataclass:
+    a: int
+
+
+class TestFallbackRepr:
+    def test_fallback_repr(self):
+        """
+        OPT_FALLBACK_REPR
+        """
+        assert (
+            orjson.dumps(
+                {"a": Custom(), "b": [decimal.Decimal("1.5")]},
+                option=orjson.OPT_FALLBACK_REPR,
+            )
+            == b'{"a":"<Custom>","b":["Decimal(\'1.5\')"]}'
+        )
+
+    def test_fallback_repr_unset(self):
+        """
+        dumps() unsupported type without OPT_FALLBACK_REPR raises
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Custom())
+
+    def test_fallback_repr_escape(self):
+        """
+        OPT_FALLBACK_REPR escapes and encodes the repr
+        """
+        assert orjson.dumps(
+            ReprNonAscii(), option=orjson.OPT_FALLBACK_REPR
+        ) == '"<é🐈>"'.encode("utf-8")
+        assert (
+            orjson.dumps({1, 2}, option=orjson.OPT_FALLBACK_REPR) == b'"{1, 2}"'
+        )
+        assert orjson.dumps(b'a"\n', option=orjson.OPT_FALLBACK_REPR) == (
+            orjson.dumps(repr(b'a"\n'))
+        )
+
+    def test_fallback_repr_supported(self):
+        """
+        OPT_FALLBACK_REPR does not affect supported types
+        """
+        obj = {"a": [1, 1.5, "b", None, True], "c": Dataclass(1)}
+        assert orjson.dumps(obj, option=orjson.OPT_FALLBACK_REPR) == orjson.dumps(
+            obj
+        )
+
+    def test_fallback_repr_default(self):
+        """
+        OPT_FALLBACK_REPR applies if default raises
+        """
+
+        def default(obj):
+            if isinstance(obj, decimal.Decimal):
+                return str(obj)
+            raise TypeError
+
+        assert (
+            orjson.dumps(
+                [decimal.Decimal("1.5"), Custom()],
+                default=default,
+                option=orjson.OPT_FALLBACK_REPR,
+            )
+            == b'["1.5","<Custom>"]'
+        )
+
+    def test_fallback_repr_default_returns_unsupported(self):
+        """
+        OPT_FALLBACK_REPR on an object returned by default
+        """
+
+        def default(obj):
+            if isinstance(obj, decimal.Decimal):
+                return Custom()
+            raise TypeError
+
+        assert (
+            orjson.dumps(
+                decimal.Decimal("1"),
+                default=default,
+                option=orjson.OPT_FALLBACK_REPR,
+            )
+            == b'"<Custom>"'
+        )
+
+    def test_fallback_repr_passthrough(self):
+        """
+        OPT_FALLBACK_REPR with a type passed through
+        """
+        assert (
+            orjson.dumps(
+                datetime.date(2000, 1, 2),
+                option=orjson.OPT_PASSTHROUGH_DATETIME | orjson.OPT_FALLBACK_REPR,
+            )
+            == b'"datetime.date(2000, 1, 2)"'
+        )
+
+    def test_fallback_repr_raises(self):
+        """
+        OPT_FALLBACK_REPR when repr() raises
+        """
+        with pytest.raises(orjson.JSONEncodeError) as exc_info:
+            orjson.dumps(ReprRaises(), option=orjson.OPT_FALLBACK_REPR)
+        assert isinstance(exc_info.value.__cause__, ValueError)
+        assert str(exc_info.value.__cause__) == "repr"
+
+    def test_fallback_repr_dict_key(self):
+        """
+        OPT_FALLBACK_REPR does not apply to dict keys
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(
+                {Custom(): 1},
+                option=orjson.OPT_FALLBACK_REPR | orjson.OPT_NON_STR_KEYS,
+            )
+
+    def test_fallback_repr_max_str_len(self):
+        """
+        OPT_FALLBACK_REPR with max_str_len
+        """
+        assert (
+            orjson.dumps(Custom(), option=orjson.OPT_FALLBACK_REPR, max_str_len=3)
+            == b'"<Cu..."'
+        )
+
+    def test_fallback_repr_kwarg(self):
+        """
+        dumps() fallback_repr=True is OPT_FALLBACK_REPR
+        """
+        assert orjson.dumps(Custom(), fallback_repr=True) == b'"<Custom>"'
+
+
+class TestFallbackStr:
+    def test_fallback_str(self):
+        """
+        OPT_FALLBACK_STR
+        """
+        assert (
+            orjson.dumps(
+                {"a": Lazy(), "b": [ipaddress.ip_address("127.0.0.1")]},
+                option=orjson.OPT_FALLBACK_STR,
+            )
+            == b'{"a":"translated","b":["127.0.0.1"]}'
+        )
+
+    def test_fallback_str_and_repr(self):
+        """
+        OPT_FALLBACK_STR takes precedence over OPT_FALLBACK_REPR
+        """
+        assert (
+            orjson.dumps(
+                Lazy(), option=orjson.OPT_FALLBACK_STR | orjson.OPT_FALLBACK_REPR
+            )
+            == b'"translated"'
+        )
+        assert orjson.dumps(Lazy(), option=orjson.OPT_FALLBACK_REPR) == b'"<Lazy>"'
+
+    def test_fallback_str_default(self):
+        """
+        OPT_FALLBACK_STR applies if default raises
+        """
+
+        def default(obj):
+            if isinstance(obj, decimal.Decimal):
+                return float(obj)
+            raise TypeError
+
+        assert (
+            orjson.dumps(
+                [decimal.Decimal("1.5"), Lazy()],
+                default=default,
+                option=orjson.OPT_FALLBACK_STR,
+            )
+            == b'[1.5,"translated"]'
+        )
+
+    def test_fallback_str_supported(self):
+        """
+        OPT_FALLBACK_STR does not affect supported types
+        """
+        obj = {"a": [1, 1.5, "b", None, True], "c": Dataclass(1)}
+        assert orjson.dumps(obj, option=orjson.OPT_FALLBACK_STR) == orjson.dumps(obj)
+
+    def test_fallback_str_raises(self):
+        """
+        OPT_FALLBACK_STR when str() raises
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(StrRaises(), option=orjson.OPT_FALLBACK_STR)
+
+    def test_fallback_str_kwarg(self):
+        """
+        dumps() fallback_str=True is OPT_FALLBACK_STR
+        """
+        assert orjson.dumps(Lazy(), fallback_str=True) == b'"translated"'
+
+
+class TestFallbackVars:
+    def test_fallback_vars(self):
+        """
+        OPT_FALLBACK_VARS
+        """
+        assert (
+            orjson.dumps(
+                {"a": Point(1, Point(2, [Point(3, 4)]))},
+                option=orjson.OPT_FALLBACK_VARS,
+            )
+            == b'{"a":{"x":1,"y":{"x":2,"y":[{"x":3,"y":4}]}}}'
+        )
+
+    def test_fallback_vars_empty(self):
+        """
+        OPT_FALLBACK_VARS object without attributes
+        """
+        assert orjson.dumps(Custom(), option=orjson.OPT_FALLBACK_VARS) == b"{}"
+
+    def test_fallback_vars_no_dict(self):
+        """
+        OPT_FALLBACK_VARS object without __dict__
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Slots(), option=orjson.OPT_FALLBACK_VARS)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(decimal.Decimal("1"), option=orjson.OPT_FALLBACK_VARS)
+
+    def test_fallback_vars_repr(self):
+        """
+        OPT_FALLBACK_VARS with OPT_FALLBACK_REPR for objects without __dict__
+        """
+        assert (
+            orjson.dumps(
+                [Point(1, 2), Slots()],
+                option=orjson.OPT_FALLBACK_VARS | orjson.OPT_FALLBACK_REPR,
+            )
+            == b'[{"x":1,"y":2},"<Slots>"]'
+        )
+
+    def test_fallback_vars_class(self):
+        """
+        OPT_FALLBACK_VARS does not apply to a class, whose __dict__ is not a dict
+        """
+        assert (
+            orjson.dumps(
+                Point, option=orjson.OPT_FALLBACK_VARS | orjson.OPT_FALLBACK_STR
+            )
+            == orjson.dumps(str(Point))
+        )
+
+    def test_fallback_vars_default(self):
+        """
+        OPT_FALLBACK_VARS applies if default raises
+        """
+
+        def default(obj):
+            if isinstance(obj, Point):
+                return [obj.x, obj.y]
+            raise TypeError
+
+        assert (
+            orjson.dumps(
+                [Point(1, 2), Lazy()], default=default, option=orjson.OPT_FALLBACK_VARS
+            )
+            == b"[[1,2],{}]"
+        )
+
+    def test_fallback_vars_cycle(self):
+        """
+        OPT_FALLBACK_VARS object referencing itself
+        """
+        obj = Point(1, 2)
+        obj.y = obj
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(obj, option=orjson.OPT_FALLBACK_VARS)
+
+    def test_fallback_vars_options(self):
+        """
+        OPT_FALLBACK_VARS with other options applying to the dict
+        """
+        assert (
+            orjson.dumps(
+                Point(2, 1),
+                option=orjson.OPT_FALLBACK_VARS | orjson.OPT_SORT_KEYS,
+                include_keys={"y"},
+            )
+            == b'{"y":1}'
+        )
+
+    def test_fallback_vars_kwarg(self):
+        """
+        dumps() fallback_vars=True is OPT_FALLBACK_VARS
+        """
+        assert orjson.dumps(Point(1, 2), fallback_vars=True) == b'{"x":1,"y":2}'
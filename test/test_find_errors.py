@@ -0,0 +1,175 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import pytest
+
+import orjson
+
+from .util import read_fixture_bytes
+
+
+def errors(doc, **kwargs):
+    return [(err.msg, err.pos) for err in orjson.find_errors(doc, **kwargs)]
+
+
+class TestFindErrors:
+    def test_find_errors_valid(self):
+        """
+        find_errors() of a valid document is empty
+        """
+        for doc in (b'{"a": [1, 2.5e3, -0, "\\u00e9\\ud83d\\ude00"]}', "1", " null "):
+            assert orjson.find_errors(doc) == []
+        assert orjson.find_errors(read_fixture_bytes("twitter.json.xz")) == []
+
+    def test_find_errors_multiple(self):
+        """
+        find_errors() continues after each error
+        """
+        assert errors(b'[1 2, tru, 01, "x\\q", ]') == [
+            ("unexpected character", 3),
+            ("invalid literal", 6),
+            ("invalid number", 11),
+            ("invalid escaped character in string", 17),
+            ("unexpected character", 22),
+        ]
+        assert errors('{a: 1, "b" 2, "c":, "d": \'x\'}') == [
+            ("unexpected character", 1),
+            ("unexpected character", 11),
+            ("unexpected character", 18),
+            ("unexpected character", 25),
+        ]
+
+    def test_find_errors_brackets(self):
+        """
+        find_errors() closes the containers a bracket closes
+        """
+        assert errors(b'{"a": [1, 2}') == [("unexpected character", 11)]
+        assert errors(b'[{"a": 1]') == [("unexpected character", 8)]
+        assert errors(b'[1}') == [
+            ("unexpected character", 2),
+            ("unexpected end of data", 3),
+        ]
+        assert errors(b'[[1, 2') == [("unexpected end of data", 6)]
+        assert errors(b'{"a": 1}}') == [("unexpected content after document", 8)]
+
+    def test_find_errors_after_document(self):
+        """
+        find_errors() reports content after the document once and continues
+        with the values in it
+        """
+        assert errors(b'{"a": 1}}\n{"b" 2}\n[3, tru]') == [
+            ("unexpected content after document", 8),
+            ("unexpected character", 15),
+            ("invalid literal", 22),
+        ]
+        assert errors(b"1 ] 2 :x [3,") == [
+            ("unexpected content after document", 2),
+            ("unexpected end of data", 12),
+        ]
+
+    def test_find_errors_line(self):
+        """
+        find_errors() ends a string at the end of its line
+        """
+        doc = b'{\n  "a": "x,\n  "b": 1,\n  "c": [1 2]\n}'
+        assert [
+            (err.msg, err.lineno, err.colno) for err in orjson.find_errors(doc)
+        ] == [
+            ("unexpected control character in string", 2, 11),
+            ("unexpected character", 3, 3),
+            ("unexpected character", 4, 11),
+        ]
+
+    def test_find_errors_exception(self):
+        """
+        find_errors() errors are JSONDecodeError of the whole document
+        """
+        doc = '[1, "é", x]'
+        (err,) = orjson.find_errors(doc)
+        assert isinstance(err, orjson.JSONDecodeError)
+        assert err.doc is doc
+        assert err.pos == 9
+        assert str(err) == "invalid literal: line 1 column 10 (char 9)"
+
+    def test_find_errors_utf8(self):
+        """
+        find_errors() invalid UTF-8 in bytes, counted as one character
+        """
+        doc = b'["\xc3\xa9\xff\xfe", x]'
+        assert errors(doc) == [
+            ("invalid utf-8 encoding in string", 3),
+            ("invalid utf-8 encoding in string", 4),
+            ("invalid literal", 8),
+        ]
+        assert orjson.find_errors(doc)[0].doc == doc.decode("utf-8", "replace")
+        assert [err.msg for err in orjson.find_errors('["\ud800"]')] == [
+            "str is not valid UTF-8: surrogates not allowed"
+        ]
+
+    def test_find_errors_kwargs(self):
+        """
+        find_errors() allow_nan, jsonc, max_depth, and surrogates
+        """
+        assert errors(b"[NaN, 1e400]") == [
+            ("invalid literal", 1),
+            ("number is infinity when parsed as double", 6),
+        ]
+        assert errors(b"[NaN, -Infinity, 1e400]", allow_nan=True) == []
+        doc = b'{"a": [1,], // x\n /* y */ "b": 2,}'
+        assert errors(doc)
+        assert errors(doc, jsonc=True) == []
+        assert errors(b'{"a": 1 /* x', jsonc=True) == [
+            ("unclosed multiline comment", 8),
+            ("unexpected end of data", 12),
+        ]
+        assert errors(b'["\\ud800", "\\udc00", "\\ud800\\u0041"]') == [
+            ("no matched low surrogate in string", 2),
+            ("invalid high surrogate in string", 12),
+            ("invalid low surrogate in string", 22),
+        ]
+        assert errors(b'["\\ud800"]', surrogates="surrogatepass") == []
+        assert errors(b"[[[1]], [[[2]]]]", max_depth=3) == [
+            ("recursion limit exceeded", 10)
+        ]
+
+    def test_find_errors_depth(self):
+        """
+        find_errors() reports nesting deeper than loads() accepts once
+        """
+        doc = b"[" * 2000 + b"]" * 2000
+        assert not orjson.is_valid(doc)
+        assert errors(doc) == [("recursion limit exceeded", 1024)]
+        assert errors(b"[" * 2000) == [
+            ("recursion limit exceeded", 1024),
+            ("unexpected end of data", 2000),
+        ]
+
+    def test_find_errors_max_errors(self):
+        """
+        find_errors() max_errors
+        """
+        doc = b"[x, x, x, x]"
+        assert len(errors(doc)) == 4
+        assert errors(doc, max_errors=2) == [
+            ("invalid literal", 1),
+            ("invalid literal", 4),
+        ]
+        assert errors(doc, max_errors=0) == []
+
+    def test_find_errors_empty(self):
+        """
+        find_errors() empty input and a byte order mark
+        """
+        assert errors(b"") == [("unexpected end of data", 0)]
+        assert errors(b"\xef\xbb\xbf[1]") == [("byte order mark (BOM) is not supported", 0)]
+
+    def test_find_errors_invalid(self):
+        """
+        find_errors() arguments that are invalid or not accepted
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.find_errors(1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.find_errors()
+        for kwargs in ({"strict": True}, {"max_errors": -1}, {"jsonc": 1}, {"x": 1}):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.find_errors(b"1", **kwargs)
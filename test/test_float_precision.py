@@ -0,0 +1,90 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import pytest
+
+import orjson
+
+
+class TestFloatPrecision:
+    def test_float_precision(self):
+        """
+        dumps() float_precision rounds floats
+        """
+        assert (
+            orjson.dumps({"lat": 51.50735091, "lng": -0.12775829}, float_precision=6)
+            == b'{"lat":51.507351,"lng":-0.127758}'
+        )
+
+    def test_float_precision_matches_round(self):
+        """
+        dumps() float_precision is equivalent to round()
+        """
+        vals = [
+            0.1,
+            2.675,
+            -2.675,
+            1.0000005,
+            123456.7890123,
+            1e-7,
+            -1e-7,
+            3.141592653589793,
+            1.7976931348623157e308,
+            5e-324,
+            9007199254740993.0,
+        ]
+        for precision in range(0, 16):
+            assert orjson.dumps(vals, float_precision=precision) == orjson.dumps(
+                [round(val, precision) for val in vals]
+            )
+
+    def test_float_precision_zero(self):
+        """
+        dumps() float_precision=0 keeps the value a float
+        """
+        assert orjson.dumps([2.5, 3.5, 1e20], float_precision=0) == b"[2.0,4.0,1e20]"
+
+    def test_float_precision_negative_zero(self):
+        """
+        dumps() float_precision rounding to negative zero
+        """
+        assert orjson.dumps(-1e-9, float_precision=6) == b"-0.0"
+
+    def test_float_precision_nonfinite(self):
+        """
+        dumps() float_precision NaN and infinity
+        """
+        assert (
+            orjson.dumps([float("nan"), float("inf")], float_precision=2)
+            == b"[null,null]"
+        )
+
+    def test_float_precision_int(self):
+        """
+        dumps() float_precision does not affect int
+        """
+        assert orjson.dumps([1, 12345678901], float_precision=2) == b"[1,12345678901]"
+
+    def test_float_precision_none(self):
+        """
+        dumps() float_precision=None
+        """
+        assert orjson.dumps(0.123456789, float_precision=None) == b"0.123456789"
+
+    def test_float_precision_nested(self):
+        """
+        dumps() float_precision in nested containers and OPT_INDENT_2
+        """
+        assert (
+            orjson.dumps(
+                {"a": [(1.23456,)]}, float_precision=2, option=orjson.OPT_INDENT_2
+            )
+            == b'{\n  "a": [\n    [\n      1.23\n    ]\n  ]\n}'
+        )
+
+    def test_float_precision_invalid(self):
+        """
+        dumps() float_precision invalid
+        """
+        for val in (-1, 16, 1.5, "2", True, 1 << 64):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps(1.0, float_precision=val)
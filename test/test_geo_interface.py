@@ -0,0 +1,119 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+
+import pytest
+
+import orjson
+
+
+class Point:
+    def __init__(self, x, y):
+        self.x = x
+        self.y = y
+
+    @property
+    def __geo_interface__(self):
+        return {"type": "Point", "coordinates": (self.x, self.y)}
+
+
+class Feature:
+    def __init__(self, geometry, properties):
+        self.geometry = geometry
+        self.properties = properties
+
+    @property
+    def __geo_interface__(self):
+        return {
+            "type": "Feature",
+            "geometry": self.geometry,
+            "properties": self.properties,
+        }
+
+
+class Raises:
+    @property
+    def __geo_interface__(self):
+        raise ValueError
+
+
+class Recursive:
+    @property
+    def __geo_interface__(self):
+        return self
+
+
+class GeoDict(dict):
+    __geo_interface__ = {"type": "Point", "coordinates": (0, 0)}
+
+
+@dataclasses.dataclass
+class GeoDataclass:
+    a: int
+
+    @property
+    def __geo_interface__(self):
+        return {"type": "Point", "coordinates": (0, 0)}
+
+
+class TestGeoInterface:
+    def test_geo_interface(self):
+        """
+        dumps() OPT_SERIALIZE_GEO_INTERFACE
+        """
+        assert (
+            orjson.dumps(Point(1.0, 2.5), option=orjson.OPT_SERIALIZE_GEO_INTERFACE)
+            == b'{"type":"Point","coordinates":[1.0,2.5]}'
+        )
+
+    def test_geo_interface_nested(self):
+        """
+        dumps() OPT_SERIALIZE_GEO_INTERFACE nested objects
+        """
+        obj = [Feature(Point(1, 2), {"name": "a"})]
+        assert (
+            orjson.dumps(obj, option=orjson.OPT_SERIALIZE_GEO_INTERFACE)
+            == b'[{"type":"Feature","geometry":{"type":"Point","coordinates":[1,2]},"properties":{"name":"a"}}]'
+        )
+
+    def test_geo_interface_disabled(self):
+        """
+        dumps() __geo_interface__ without option
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Point(1, 2))
+
+    def test_geo_interface_disabled_default(self):
+        """
+        dumps() __geo_interface__ without option uses default
+        """
+        assert (
+            orjson.dumps(Point(1, 2), default=lambda obj: obj.__geo_interface__)
+            == b'{"type":"Point","coordinates":[1,2]}'
+        )
+
+    def test_geo_interface_raises(self):
+        """
+        dumps() OPT_SERIALIZE_GEO_INTERFACE attribute raises
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Raises(), option=orjson.OPT_SERIALIZE_GEO_INTERFACE)
+
+    def test_geo_interface_recursive(self):
+        """
+        dumps() OPT_SERIALIZE_GEO_INTERFACE returning itself
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Recursive(), option=orjson.OPT_SERIALIZE_GEO_INTERFACE)
+
+    def test_geo_interface_precedence(self):
+        """
+        dumps() OPT_SERIALIZE_GEO_INTERFACE does not affect native types
+        """
+        assert (
+            orjson.dumps(
+                [GeoDict(a=1), GeoDataclass(1)],
+                option=orjson.OPT_SERIALIZE_GEO_INTERFACE,
+            )
+            == b'[{"a":1},{"a":1}]'
+        )
@@ -0,0 +1,160 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import sys
+import types
+
+import pytest
+
+import orjson
+
+from .util import Custom, raise_custom
+
+
+class TestImmutable:
+    def test_immutable_default(self):
+        """
+        loads() deserializes to mutable objects by default
+        """
+        assert type(orjson.loads('{"a": [1]}')) is dict
+        assert type(orjson.loads('{"a": [1]}', immutable=False)["a"]) is list
+
+    def test_immutable(self):
+        """
+        loads() immutable=True
+        """
+        res = orjson.loads('{"a": [1, {"b": null}], "c": {}}', immutable=True)
+        assert type(res) is types.MappingProxyType
+        assert type(res["a"]) is tuple
+        assert type(res["a"][1]) is types.MappingProxyType
+        assert type(res["c"]) is types.MappingProxyType
+        assert res == {"a": (1, {"b": None}), "c": {}}
+
+    def test_immutable_read_only(self):
+        """
+        loads() immutable=True objects cannot be modified
+        """
+        res = orjson.loads('{"a": {"b": [1]}}', immutable=True)
+        with pytest.raises(TypeError):
+            res["a"] = 1
+        with pytest.raises(TypeError):
+            res["a"]["c"] = 1
+        with pytest.raises(TypeError):
+            del res["a"]
+        with pytest.raises(AttributeError):
+            res["a"]["b"].append(2)
+
+    def test_immutable_empty(self):
+        """
+        loads() immutable=True empty document
+        """
+        for val in ("{}", b"{}", " { } "):
+            res = orjson.loads(val, immutable=True)
+            assert type(res) is types.MappingProxyType
+            assert res == {}
+        assert orjson.loads("[]", immutable=True) == ()
+
+    def test_immutable_scalar(self):
+        """
+        loads() immutable=True scalar
+        """
+        assert orjson.loads('"a"', immutable=True) == "a"
+        assert orjson.loads("1", immutable=True) == 1
+
+    def test_immutable_tuples_false(self):
+        """
+        loads() immutable=True deserializes arrays to tuple regardless of tuples
+        """
+        assert orjson.loads("[[1]]", immutable=True, tuples=False) == ((1,),)
+
+    def test_immutable_duplicate_keys_list(self):
+        """
+        loads() immutable=True collects duplicate values into a tuple
+        """
+        res = orjson.loads(
+            '{"a": 1, "b": [2], "a": 3, "c": 4, "a": 5, "c": 6}',
+            immutable=True,
+            duplicate_keys="list",
+        )
+        assert res == {"a": (1, 3, 5), "b": (2,), "c": (4, 6)}
+        assert type(res["a"]) is tuple
+
+    def test_immutable_object_hook(self):
+        """
+        loads() immutable=True object_hook is called with a read-only mapping
+        """
+        seen = []
+        assert orjson.loads(
+            '[{"a": 1}, {}]',
+            immutable=True,
+            object_hook=lambda obj: seen.append(type(obj)) or dict(obj),
+        ) == ({"a": 1}, {})
+        assert seen == [types.MappingProxyType] * 2
+
+    def test_immutable_object_pairs_hook(self):
+        """
+        loads() immutable=True object_pairs_hook is given a list of pairs
+        """
+        assert orjson.loads(
+            '{"a": [1]}', immutable=True, object_pairs_hook=lambda pairs: pairs
+        ) == [("a", (1,))]
+
+    def test_immutable_exception_refcount(self):
+        """
+        loads() immutable=True exception does not leak
+        """
+        obj = object()
+        before = sys.getrefcount(obj)
+        for _ in range(100):
+            with pytest.raises(Custom):
+                orjson.loads(
+                    '[{"a": 1, "b": {"c": 2}}, 3]',
+                    immutable=True,
+                    parse_int=lambda _: obj,
+                    object_hook=lambda val: raise_custom(val) if "b" in val else val,
+                )
+        assert sys.getrefcount(obj) == before
+
+    def test_immutable_refcount(self):
+        """
+        loads() immutable=True result does not leak
+        """
+        obj = object()
+        before = sys.getrefcount(obj)
+        for _ in range(100):
+            orjson.loads(
+                '{"a": 1, "a": 2, "b": {"c": [3]}}',
+                immutable=True,
+                duplicate_keys="list",
+                parse_int=lambda _: obj,
+            )
+        assert sys.getrefcount(obj) == before
+
+    def test_immutable_invalid(self):
+        """
+        loads() immutable not a bool
+        """
+        for val in (1, None, "true"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads("{}", immutable=val)
+
+    def test_immutable_load(self):
+        """
+        load() immutable
+        """
+        res = orjson.load(io.BytesIO(b'{"a": [1]}'), immutable=True)
+        assert type(res) is types.MappingProxyType
+        assert res["a"] == (1,)
+
+    def test_immutable_load_chunked(self):
+        """
+        load() immutable with chunk_size
+        """
+        res = orjson.load(
+            io.BytesIO(b'{"a": [1], "a": 2}'),
+            chunk_size=2,
+            immutable=True,
+            duplicate_keys="list",
+        )
+        assert type(res) is types.MappingProxyType
+        assert res["a"] == ((1,), 2)
@@ -0,0 +1,240 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+
+import pytest
+
+import orjson
+
+
+@dataclasses.dataclass
+class User:
+    name: str
+    email: str
+
+
+@dataclasses.dataclass
+class Record:
+    id: int
+    user: User
+
+
+class Obj:
+    def __init__(self, idx):
+        self.a = idx
+        self.b = idx
+        self.c = idx
+
+
+class TestIncludeKeys:
+    def test_include_keys(self):
+        """
+        dumps() include_keys top-level
+        """
+        assert (
+            orjson.dumps({"a": 1, "b": 2, "c": 3}, include_keys={"a", "c"})
+            == b'{"a":1,"c":3}'
+        )
+
+    def test_include_keys_first_omitted(self):
+        """
+        dumps() include_keys omitting the first entry
+        """
+        assert (
+            orjson.dumps({"a": 1, "b": 2, "c": 3}, include_keys=["c"])
+            == b'{"c":3}'
+        )
+
+    def test_include_keys_none_match(self):
+        """
+        dumps() include_keys with no key matching
+        """
+        assert orjson.dumps({"a": 1}, include_keys={"b"}) == b"{}"
+        assert orjson.dumps({"a": 1}, include_keys=set()) == b"{}"
+        assert (
+            orjson.dumps({"a": 1}, include_keys={"b"}, option=orjson.OPT_INDENT_2)
+            == b"{}"
+        )
+
+    def test_include_keys_whole_value(self):
+        """
+        dumps() include_keys writes the value of a top-level key whole
+        """
+        assert (
+            orjson.dumps(
+                {"a": {"b": 1, "c": [{"d": 2}]}, "e": 3}, include_keys={"a"}
+            )
+            == b'{"a":{"b":1,"c":[{"d":2}]}}'
+        )
+
+    def test_include_keys_path(self):
+        """
+        dumps() include_keys dotted path
+        """
+        obj = {"a": {"b": 1, "c": {"d": 2, "e": 3}}, "f": 4}
+        assert orjson.dumps(obj, include_keys={"a.c.e", "f"}) == (
+            b'{"a":{"c":{"e":3}},"f":4}'
+        )
+        assert orjson.dumps(obj, include_keys={"a.b", "a.c.d"}) == (
+            b'{"a":{"b":1,"c":{"d":2}}}'
+        )
+
+    def test_include_keys_path_and_prefix(self):
+        """
+        dumps() include_keys with a path and its prefix writes the prefix whole
+        """
+        obj = {"a": {"b": 1, "c": 2}}
+        expected = b'{"a":{"b":1,"c":2}}'
+        assert orjson.dumps(obj, include_keys=["a.b", "a"]) == expected
+        assert orjson.dumps(obj, include_keys=["a", "a.b"]) == expected
+
+    def test_include_keys_path_scalar(self):
+        """
+        dumps() include_keys path through a value that is not an object
+        """
+        assert (
+            orjson.dumps({"a": 1, "b": "c"}, include_keys={"a.x", "b.y"})
+            == b'{"a":1,"b":"c"}'
+        )
+
+    def test_include_keys_list(self):
+        """
+        dumps() include_keys applies to each element of a list
+        """
+        obj = [{"id": 1, "x": 2}, {"id": 3, "x": 4}, 5, [{"id": 6, "x": 7}]]
+        assert orjson.dumps(obj, include_keys={"id"}) == (
+            b'[{"id":1},{"id":3},5,[{"id":6}]]'
+        )
+
+    def test_include_keys_path_list(self):
+        """
+        dumps() include_keys path through a list
+        """
+        obj = {"items": [{"id": 1, "x": 2}, ({"id": 3, "x": 4},)], "y": 5}
+        assert orjson.dumps(obj, include_keys={"items.id"}) == (
+            b'{"items":[{"id":1},[{"id":3}]]}'
+        )
+
+    def test_include_keys_values_not_serialized(self):
+        """
+        dumps() include_keys does not serialize omitted values
+        """
+        assert (
+            orjson.dumps({"a": object(), "b": 1}, include_keys={"b"}) == b'{"b":1}'
+        )
+
+    def test_include_keys_non_ascii(self):
+        """
+        dumps() include_keys non-ASCII keys
+        """
+        assert (
+            orjson.dumps({"ключ": {"значение": 1, "x": 2}, "y": 3}, include_keys={"ключ.значение"})
+            == '{"ключ":{"значение":1}}'.encode("utf-8")
+        )
+
+    def test_include_keys_sort_keys(self):
+        """
+        dumps() include_keys with OPT_SORT_KEYS
+        """
+        assert (
+            orjson.dumps(
+                {"c": 1, "b": {"z": 1, "y": 2}, "a": 3},
+                option=orjson.OPT_SORT_KEYS,
+                include_keys={"b.y", "c"},
+            )
+            == b'{"b":{"y":2},"c":1}'
+        )
+
+    def test_include_keys_non_str_keys(self):
+        """
+        dumps() include_keys with OPT_NON_STR_KEYS
+        """
+        assert (
+            orjson.dumps(
+                {1: {2: "a", 3: "b"}, 4: "c"},
+                option=orjson.OPT_NON_STR_KEYS,
+                include_keys={"1.3"},
+            )
+            == b'{"1":{"3":"b"}}'
+        )
+
+    def test_include_keys_indent(self):
+        """
+        dumps() include_keys with OPT_INDENT_2
+        """
+        assert (
+            orjson.dumps(
+                {"a": 1, "b": {"c": 2, "d": 3}},
+                option=orjson.OPT_INDENT_2,
+                include_keys={"b.d"},
+            )
+            == b'{\n  "b": {\n    "d": 3\n  }\n}'
+        )
+
+    def test_include_keys_dataclass(self):
+        """
+        dumps() include_keys dataclass
+        """
+        assert (
+            orjson.dumps(Record(1, User("a", "b")), include_keys={"user.name"})
+            == b'{"user":{"name":"a"}}'
+        )
+
+    def test_include_keys_shared_keys(self):
+        """
+        dumps() include_keys dicts sharing keys
+        """
+        obj = [vars(Obj(idx)) for idx in range(3)]
+        assert orjson.dumps(obj, include_keys={"b"}) == (
+            b'[{"b":0},{"b":1},{"b":2}]'
+        )
+        assert orjson.dumps(obj) == (
+            b'[{"a":0,"b":0,"c":0},{"a":1,"b":1,"c":1},{"a":2,"b":2,"c":2}]'
+        )
+
+    def test_include_keys_default(self):
+        """
+        dumps() include_keys in an object returned by default
+        """
+        assert (
+            orjson.dumps(
+                {"a": Obj(1), "b": 2},
+                default=vars,
+                include_keys={"a.c"},
+            )
+            == b'{"a":{"c":1}}'
+        )
+
+    def test_include_keys_redact_keys(self):
+        """
+        dumps() include_keys with redact_keys
+        """
+        assert (
+            orjson.dumps(
+                {"a": {"token": 1, "b": 2}, "c": 3},
+                include_keys={"a"},
+                redact_keys={"token"},
+            )
+            == b'{"a":{"token":"[REDACTED]","b":2}}'
+        )
+
+    def test_include_keys_scalar(self):
+        """
+        dumps() include_keys of a value that is not an object
+        """
+        assert orjson.dumps(1, include_keys={"a"}) == b"1"
+        assert orjson.dumps([], include_keys={"a"}) == b"[]"
+
+    def test_include_keys_none(self):
+        """
+        dumps() include_keys=None
+        """
+        assert orjson.dumps({"a": 1}, include_keys=None) == b'{"a":1}'
+
+    def test_include_keys_invalid(self):
+        """
+        dumps() include_keys invalid
+        """
+        for val in ("a", 1, [1], ["a", b"b"], ["a..b"], ["a."], [""]):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps({}, include_keys=val)
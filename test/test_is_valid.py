@@ -0,0 +1,151 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import array
+
+import pytest
+
+import orjson
+
+INVALID = (
+    b"",
+    b"[1,",
+    b"[1] x",
+    b'{"a" 1}',
+    b"[tru]",
+    b"01",
+    b"[NaN]",
+    b"1e400",
+    b'"\\x"',
+    b'["a", \x01]',
+    b"\xff",
+    "[1, \ud800]",
+    '["äö", x]',
+)
+
+
+def loads_pos(doc, **kwargs):
+    with pytest.raises(orjson.JSONDecodeError) as exc:
+        orjson.loads(doc, **kwargs)
+    return exc.value.pos
+
+
+class TestIsValid:
+    def test_is_valid(self):
+        """
+        is_valid() valid document
+        """
+        for doc in (b"[]", b'{"a": [1, 2.5, "b", true, null]}', "1", bytearray(b"{}")):
+            assert orjson.is_valid(doc) is True
+            assert orjson.is_valid(doc, position=True) is None
+
+    def test_is_valid_invalid(self):
+        """
+        is_valid() invalid document
+        """
+        for doc in INVALID:
+            assert orjson.is_valid(doc) is False
+
+    def test_is_valid_position(self):
+        """
+        is_valid() position is the pos of the error loads() raises
+        """
+        for doc in INVALID:
+            assert orjson.is_valid(doc, position=True) == loads_pos(doc)
+
+    def test_is_valid_buffer(self):
+        """
+        is_valid() memoryview and buffer input
+        """
+        assert orjson.is_valid(memoryview(b"[1]")) is True
+        assert orjson.is_valid(array.array("b", b"[1]")) is True
+        assert orjson.is_valid(array.array("b", b"[1")) is False
+        assert orjson.is_valid(memoryview(b"[1, x]"), position=True) == 4
+
+    def test_is_valid_allow_nan(self):
+        """
+        is_valid() allow_nan
+        """
+        for doc in (b"[NaN, -Infinity]", b"1e400"):
+            assert orjson.is_valid(doc) is False
+            assert orjson.is_valid(doc, allow_nan=True) is True
+
+    def test_is_valid_jsonc(self):
+        """
+        is_valid() jsonc
+        """
+        doc = b'{\n  // a\n  "a": [1,],\n}'
+        assert orjson.is_valid(doc) is False
+        assert orjson.is_valid(doc, jsonc=True) is True
+
+    def test_is_valid_strict(self):
+        """
+        is_valid() strict
+        """
+        for doc in (
+            b'{"a": 1, "b": {"c": 2, "c": 3}}',
+            b"[100000000000000000000000]",
+            b"1e-400",
+        ):
+            assert orjson.is_valid(doc) is True
+            assert orjson.is_valid(doc, strict=True) is False
+            assert orjson.is_valid(doc, strict=True, position=True) == loads_pos(
+                doc, strict=True
+            )
+        assert orjson.is_valid(b'[{"a": 1}, {"a": 2}]', strict=True) is True
+        assert orjson.is_valid(b"[0e-400, 18446744073709551615]", strict=True) is True
+
+    def test_is_valid_duplicate_keys(self):
+        """
+        is_valid() duplicate_keys
+        """
+        doc = b'{"a": 1, "a": 2}'
+        assert orjson.is_valid(doc, duplicate_keys="error") is False
+        assert orjson.is_valid(doc, duplicate_keys="first", strict=True) is True
+
+    def test_is_valid_limits(self):
+        """
+        is_valid() max_depth, max_items, max_string_len, and max_bytes
+        """
+        assert orjson.is_valid(b"[[1]]", max_depth=2) is True
+        assert orjson.is_valid(b"[[[1]]]", max_depth=2) is False
+        assert orjson.is_valid(b'[1, {"a": 2}]', max_items=3) is True
+        assert orjson.is_valid(b'[1, {"a": 2}]', max_items=2) is False
+        assert orjson.is_valid(b'{"abc": "de"}', max_string_len=3) is True
+        assert orjson.is_valid(b'{"abcd": "de"}', max_string_len=3) is False
+        assert orjson.is_valid(b'["abcd"]', max_string_len=3) is False
+        assert orjson.is_valid(b"[1, 2]", max_bytes=6) is True
+        assert orjson.is_valid(b"[1, 2]", max_bytes=5) is False
+
+    def test_is_valid_max_depth_default(self):
+        """
+        is_valid() allows 1024 nested containers by default, as loads() does
+        """
+        assert orjson.is_valid(b"[" * 1024 + b"]" * 1024) is True
+        assert orjson.is_valid(b"[" * 1025 + b"]" * 1025) is False
+        assert orjson.is_valid(b"[" * 1025 + b"]" * 1025, max_depth=None) is False
+
+    def test_is_valid_large(self):
+        """
+        is_valid() document larger than the parser's shared buffer
+        """
+        doc = b"[" + b'{"a": "b"},' * 100000 + b"1]"
+        assert orjson.is_valid(doc) is True
+        assert orjson.is_valid(doc[:-1], position=True) == len(doc) - 1
+        assert orjson.is_valid(doc, max_items=100000) is False
+
+    def test_is_valid_arguments(self):
+        """
+        is_valid() invalid arguments raise
+        """
+        for args, kwargs in (
+            ((), {}),
+            ((b"1", b"1"), {}),
+            ((1,), {}),
+            ((b"1",), {"position": 1}),
+            ((b"1",), {"parse_float": float}),
+            ((b"1",), {"schema": None}),
+            ((b"1",), {"offset": 0}),
+            ((b"1",), {"strict": True, "allow_nan": True}),
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.is_valid(*args, **kwargs)
@@ -0,0 +1,200 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import array
+import io
+import os
+import tempfile
+
+import pytest
+
+import orjson
+
+from .util import RaisingReader, read_fixture_bytes
+
+
+class TestIterArray:
+    def test_iter_array_bytes(self):
+        """
+        iter_array() bytes
+        """
+        data = b'[1, "a", {"b": [2, 3]}, [], null, true, 1.5, "]", "\\"],"]'
+        assert list(orjson.iter_array(data)) == orjson.loads(data)
+
+    def test_iter_array_str(self):
+        """
+        iter_array() str
+        """
+        assert list(orjson.iter_array('["\xe9", {"\U0001f408": "}"}]')) == [
+            "\xe9",
+            {"\U0001f408": "}"},
+        ]
+
+    def test_iter_array_buffer(self):
+        """
+        iter_array() bytearray, memoryview, and array.array
+        """
+        for val in (
+            bytearray(b"[1,2]"),
+            memoryview(b"[1,2]"),
+            array.array("B", b"[1,2]"),
+        ):
+            assert list(orjson.iter_array(val)) == [1, 2]
+
+    def test_iter_array_lazy(self):
+        """
+        iter_array() deserializes each element when it is reached
+        """
+        it = orjson.iter_array(b'[1, 2, {"a": }]')
+        assert next(it) == 1
+        assert next(it) == 2
+        with pytest.raises(orjson.JSONDecodeError):
+            next(it)
+        assert list(it) == []
+
+    def test_iter_array_iterator(self):
+        """
+        iter_array() returns an iterator
+        """
+        it = orjson.iter_array(b"[1]")
+        assert iter(it) is it
+        assert type(it).__name__ == "ArrayIterator"
+
+    def test_iter_array_empty(self):
+        """
+        iter_array() empty array
+        """
+        assert list(orjson.iter_array(b"[]")) == []
+        assert list(orjson.iter_array(b" \n[ \t]\n")) == []
+
+    def test_iter_array_whitespace(self):
+        """
+        iter_array() whitespace around elements
+        """
+        assert list(orjson.iter_array(b"\n[\n 1 ,\n\t2\r\n,3]\n")) == [1, 2, 3]
+
+    def test_iter_array_binary_file(self):
+        """
+        iter_array() binary file
+        """
+        data = orjson.dumps([{"key": "x" * 1000, "idx": idx} for idx in range(500)])
+        assert list(orjson.iter_array(io.BytesIO(data))) == orjson.loads(data)
+
+    def test_iter_array_text_file(self):
+        """
+        iter_array() text file
+        """
+        assert list(orjson.iter_array(io.StringIO('["\U0001f408", 1]'))) == [
+            "\U0001f408",
+            1,
+        ]
+
+    def test_iter_array_fd(self):
+        """
+        iter_array() file descriptor is read and not closed
+        """
+        with tempfile.TemporaryFile() as fileobj:
+            fileobj.write(b'[{"a":1},{"b":2}]')
+            fileobj.flush()
+            fileobj.seek(0)
+            fd = fileobj.fileno()
+            assert list(orjson.iter_array(fd)) == [{"a": 1}, {"b": 2}]
+            os.fstat(fd)
+
+    def test_iter_array_fixture(self):
+        """
+        iter_array() fixture
+        """
+        data = orjson.dumps(orjson.loads(read_fixture_bytes("twitter.json.xz"))["statuses"])
+        assert list(orjson.iter_array(io.BytesIO(data))) == orjson.loads(data)
+
+    def test_iter_array_not_array(self):
+        """
+        iter_array() input that is not an array
+        """
+        for val in (b'{"a":1}', b"1", b"", b"  "):
+            with pytest.raises(orjson.JSONDecodeError):
+                next(orjson.iter_array(val))
+
+    def test_iter_array_invalid(self):
+        """
+        iter_array() invalid array
+        """
+        for val in (b"[1,]", b"[1 2]", b"[1", b"[1,", b"[,1]", b'["a]', b"[{]"):
+            with pytest.raises(orjson.JSONDecodeError):
+                list(orjson.iter_array(val))
+
+    def test_iter_array_invalid_location(self):
+        """
+        iter_array() invalid array reports the offset of the error in the input
+        """
+        for val, msg, pos in (
+            (b"[1,2,]", "unexpected character", 5),
+            (b"[,]", "unexpected character", 1),
+            (b"[1, ,2]", "unexpected character", 4),
+            (b"[1 2]", "unexpected character", 3),
+            (b"[1,\n", "unexpected end of data", 4),
+            (b" {}", "Input must be a JSON array", 1),
+            (b"[1]  x", "unexpected content after document", 5),
+        ):
+            for data in (val, io.BytesIO(val)):
+                with pytest.raises(orjson.JSONDecodeError) as exc:
+                    list(orjson.iter_array(data))
+                assert exc.value.msg == msg
+                assert exc.value.pos == pos
+
+    def test_iter_array_element_location(self):
+        """
+        iter_array() invalid element reports the position in the input
+        """
+        for data in ('["\xe9",\n  {"a" 1}]', b'["\xc3\xa9",\n  {"a" 1}]'):
+            it = orjson.iter_array(data)
+            assert next(it) == "\xe9"
+            with pytest.raises(orjson.JSONDecodeError) as exc:
+                next(it)
+            assert exc.value.pos == 13
+            assert exc.value.lineno == 2
+            assert exc.value.colno == 8
+            assert exc.value.snippet == '{"a" 1}'
+
+    def test_iter_array_str_location(self):
+        """
+        iter_array() error position in str input is in characters
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            list(orjson.iter_array('["\xe9" 1]'))
+        assert exc.value.pos == 5
+        assert exc.value.colno == 6
+
+    def test_iter_array_trailing(self):
+        """
+        iter_array() characters after the array
+        """
+        it = orjson.iter_array(b"[1] [2]")
+        assert next(it) == 1
+        with pytest.raises(orjson.JSONDecodeError):
+            next(it)
+
+    def test_iter_array_invalid_utf8(self):
+        """
+        iter_array() invalid UTF-8
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.iter_array(b'["\xff"]')
+        with pytest.raises(orjson.JSONDecodeError):
+            list(orjson.iter_array(io.BytesIO(b'["\xff"]')))
+
+    def test_iter_array_read_exception(self):
+        """
+        iter_array() propagates an exception raised reading
+        """
+        it = orjson.iter_array(RaisingReader(b"[1, 2"))
+        assert next(it) == 1
+        with pytest.raises(ValueError):
+            next(it)
+
+    def test_iter_array_invalid_input(self):
+        """
+        iter_array() input that is not data or a file
+        """
+        with pytest.raises(AttributeError):
+            orjson.iter_array(object())
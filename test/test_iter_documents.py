@@ -0,0 +1,131 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import tempfile
+
+import pytest
+
+import orjson
+
+
+class TestIterDocuments:
+    def test_iter_documents(self):
+        """
+        iter_documents() concatenated with and without whitespace
+        """
+        data = b'{"a":1}{"b":2}\n[\n  3\n]  [4]\t{"c":[5]}'
+        assert list(orjson.iter_documents(data)) == [
+            {"a": 1},
+            {"b": 2},
+            [3],
+            [4],
+            {"c": [5]},
+        ]
+
+    def test_iter_documents_scalars(self):
+        """
+        iter_documents() number and literal end at whitespace or a document
+        """
+        data = b'1 2\n-3.5"a""b"true[1]null{}false'
+        assert list(orjson.iter_documents(data)) == [
+            1,
+            2,
+            -3.5,
+            "a",
+            "b",
+            True,
+            [1],
+            None,
+            {},
+            False,
+        ]
+
+    def test_iter_documents_strings(self):
+        """
+        iter_documents() brackets and escaped quotes in strings
+        """
+        data = b'{"a":"}]\\"{["}["\\\\","]"]"\\"}"'
+        assert list(orjson.iter_documents(data)) == [
+            {"a": '}]"{['},
+            ["\\", "]"],
+            '"}',
+        ]
+
+    def test_iter_documents_buffer(self):
+        """
+        iter_documents() bytearray, memoryview, and str
+        """
+        for val in (bytearray(b"[1][2]"), memoryview(b"[1][2]"), "[1][2]"):
+            assert list(orjson.iter_documents(val)) == [[1], [2]]
+        assert list(orjson.iter_documents('"\xe9""\U0001f408"')) == [
+            "\xe9",
+            "\U0001f408",
+        ]
+
+    def test_iter_documents_empty(self):
+        """
+        iter_documents() empty and whitespace
+        """
+        assert list(orjson.iter_documents(b"")) == []
+        assert list(orjson.iter_documents(b" \n\t\r ")) == []
+
+    def test_iter_documents_binary_file(self):
+        """
+        iter_documents() binary file with documents longer than a read
+        """
+        records = [{"key": "x" * 10000, "idx": idx} for idx in range(100)]
+        data = b"".join(orjson.dumps(record) for record in records)
+        assert list(orjson.iter_documents(io.BytesIO(data))) == records
+        data = b" ".join(str(idx).encode() for idx in range(100000))
+        assert list(orjson.iter_documents(io.BytesIO(data))) == list(range(100000))
+
+    def test_iter_documents_text_file(self):
+        """
+        iter_documents() text file
+        """
+        with tempfile.TemporaryFile("w+", encoding="utf-8") as fileobj:
+            fileobj.write('{"a":"\xe9"}\n{"b":\n2}')
+            fileobj.seek(0)
+            assert list(orjson.iter_documents(fileobj)) == [{"a": "\xe9"}, {"b": 2}]
+
+    def test_iter_documents_lazy(self):
+        """
+        iter_documents() deserializes each document when it is reached
+        """
+        it = orjson.iter_documents(b'[1] {"a":} [2]')
+        assert next(it) == [1]
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            next(it)
+        assert exc_info.value.snippet == '{"a":}'
+        assert list(it) == []
+
+    def test_iter_documents_location(self):
+        """
+        iter_documents() error position is of the character in the input
+        """
+        for data in ('{"a":"\xe9"}\n{"b":}', b'{"a":"\xc3\xa9"}\n{"b":}'):
+            with pytest.raises(orjson.JSONDecodeError) as exc_info:
+                list(orjson.iter_documents(data))
+            assert exc_info.value.pos == 15
+            assert exc_info.value.lineno == 2
+            assert exc_info.value.colno == 6
+            assert str(exc_info.value) == (
+                "unexpected character: line 2 column 6 (char 15)"
+            )
+
+    def test_iter_documents_invalid(self):
+        """
+        iter_documents() invalid documents
+        """
+        for data in (b"[1] ]", b"[1] [", b'[1] "a', b"[1] nul", b"[1] 1x"):
+            it = orjson.iter_documents(data)
+            assert next(it) == [1]
+            with pytest.raises(orjson.JSONDecodeError):
+                next(it)
+
+    def test_iter_documents_invalid_input(self):
+        """
+        iter_documents() input that is not data or a file
+        """
+        with pytest.raises(AttributeError):
+            orjson.iter_documents(object())
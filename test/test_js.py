@@ -0,0 +1,254 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import datetime
+
+import pytest
+
+import orjson
+
+try:
+    import zoneinfo
+except ImportError:
+    zoneinfo = None  # type: ignore
+
+
+class Obj:
+    def __init__(self, idx):
+        self.a = idx
+        setattr(self, "b\u2028", idx)
+
+
+@dataclasses.dataclass
+class Dataclass:
+    a: int
+    b: datetime.time
+
+
+class TestJs:
+    def test_js_datetime_utc(self):
+        """
+        OPT_JS datetime in UTC is milliseconds and Z
+        """
+        assert (
+            orjson.dumps(
+                datetime.datetime(
+                    1970, 1, 1, 0, 0, 0, 123456, tzinfo=datetime.timezone.utc
+                ),
+                option=orjson.OPT_JS,
+            )
+            == b'"1970-01-01T00:00:00.123Z"'
+        )
+
+    def test_js_datetime_zero_fraction(self):
+        """
+        OPT_JS datetime always has three fractional digits
+        """
+        assert (
+            orjson.dumps(
+                datetime.datetime(1970, 1, 1, 0, 0, 0, tzinfo=datetime.timezone.utc),
+                option=orjson.OPT_JS,
+            )
+            == b'"1970-01-01T00:00:00.000Z"'
+        )
+        assert (
+            orjson.dumps(
+                datetime.datetime(
+                    1970, 1, 1, 0, 0, 0, 999, tzinfo=datetime.timezone.utc
+                ),
+                option=orjson.OPT_JS,
+            )
+            == b'"1970-01-01T00:00:00.000Z"'
+        )
+        assert (
+            orjson.dumps(
+                datetime.datetime(
+                    1970, 1, 1, 0, 0, 0, 7000, tzinfo=datetime.timezone.utc
+                ),
+                option=orjson.OPT_JS,
+            )
+            == b'"1970-01-01T00:00:00.007Z"'
+        )
+
+    def test_js_datetime_offset(self):
+        """
+        OPT_JS datetime with a non-UTC offset keeps the offset
+        """
+        assert (
+            orjson.dumps(
+                datetime.datetime(
+                    1970,
+                    1,
+                    1,
+                    0,
+                    0,
+                    0,
+                    45000,
+                    tzinfo=datetime.timezone(datetime.timedelta(hours=-5)),
+                ),
+                option=orjson.OPT_JS,
+            )
+            == b'"1970-01-01T00:00:00.045-05:00"'
+        )
+
+    @pytest.mark.skipif(zoneinfo is None, reason="zoneinfo not available")
+    def test_js_datetime_zoneinfo(self):
+        """
+        OPT_JS datetime with zoneinfo UTC
+        """
+        assert (
+            orjson.dumps(
+                datetime.datetime(
+                    2020, 6, 1, 12, 30, 1, 500000, tzinfo=zoneinfo.ZoneInfo("UTC")
+                ),
+                option=orjson.OPT_JS,
+            )
+            == b'"2020-06-01T12:30:01.500Z"'
+        )
+
+    def test_js_datetime_naive(self):
+        """
+        OPT_JS naive datetime has no offset unless OPT_NAIVE_UTC
+        """
+        obj = datetime.datetime(1970, 1, 1, 0, 0, 0, 1000)
+        assert orjson.dumps(obj, option=orjson.OPT_JS) == b'"1970-01-01T00:00:00.001"'
+        assert (
+            orjson.dumps(obj, option=orjson.OPT_JS | orjson.OPT_NAIVE_UTC)
+            == b'"1970-01-01T00:00:00.001Z"'
+        )
+
+    def test_js_datetime_omit_microseconds(self):
+        """
+        OPT_JS | OPT_OMIT_MICROSECONDS has no fraction
+        """
+        assert (
+            orjson.dumps(
+                datetime.datetime(
+                    1970, 1, 1, 0, 0, 0, 123456, tzinfo=datetime.timezone.utc
+                ),
+                option=orjson.OPT_JS | orjson.OPT_OMIT_MICROSECONDS,
+            )
+            == b'"1970-01-01T00:00:00Z"'
+        )
+
+    def test_js_time(self):
+        """
+        OPT_JS time is milliseconds
+        """
+        assert (
+            orjson.dumps(datetime.time(12, 0, 15, 290), option=orjson.OPT_JS)
+            == b'"12:00:15.000"'
+        )
+        assert (
+            orjson.dumps(datetime.time(12, 0, 15, 12345), option=orjson.OPT_JS)
+            == b'"12:00:15.012"'
+        )
+
+    def test_js_date(self):
+        """
+        OPT_JS date is unchanged
+        """
+        assert (
+            orjson.dumps(datetime.date(2000, 1, 13), option=orjson.OPT_JS)
+            == b'"2000-01-13"'
+        )
+
+    def test_js_datetime_key(self):
+        """
+        OPT_JS datetime key with OPT_NON_STR_KEYS
+        """
+        assert (
+            orjson.dumps(
+                {datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc): 1},
+                option=orjson.OPT_JS | orjson.OPT_NON_STR_KEYS,
+            )
+            == b'{"1970-01-01T00:00:00.000Z":1}'
+        )
+
+    def test_js_int(self):
+        """
+        OPT_JS enforces 53-bit integers
+        """
+        assert orjson.dumps(9007199254740991, option=orjson.OPT_JS) == b"9007199254740991"
+        assert (
+            orjson.dumps(-9007199254740991, option=orjson.OPT_JS)
+            == b"-9007199254740991"
+        )
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(9007199254740992, option=orjson.OPT_JS)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps([-9007199254740992], option=orjson.OPT_JS)
+
+    def test_js_line_separator(self):
+        """
+        OPT_JS escapes U+2028 and U+2029
+        """
+        for obj in (
+            "a\u2028b\u2029c",
+            "\xe9\u2028",
+            "\U0001f600\u2029",
+            "\u2029",
+        ):
+            assert orjson.dumps(obj) == b'"' + obj.encode("utf-8") + b'"'
+            assert orjson.loads(orjson.dumps(obj, option=orjson.OPT_JS)) == obj
+        assert orjson.dumps("a\u2028b\u2029c", option=orjson.OPT_JS) == (
+            b'"a\\u2028b\\u2029c"'
+        )
+        assert orjson.dumps("\U0001f600\u2029", option=orjson.OPT_JS) == (
+            b'"\xf0\x9f\x98\x80\\u2029"'
+        )
+        assert orjson.dumps("\u202a\u2027", option=orjson.OPT_JS) == (
+            b'"' + "\u202a\u2027".encode("utf-8") + b'"'
+        )
+
+    def test_js_line_separator_utf8(self):
+        """
+        OPT_JS escapes U+2028 and U+2029 in a str with a cached UTF-8
+        representation, including one also needing other escapes
+        """
+        obj = "a\u2028\"b\u2029"
+        obj.encode("utf-8")
+        assert orjson.dumps({obj: obj}, option=orjson.OPT_JS) == (
+            b'{"a\\u2028\\"b\\u2029":"a\\u2028\\"b\\u2029"}'
+        )
+        assert orjson.dumps(
+            {obj: 1}, option=orjson.OPT_JS | orjson.OPT_SORT_KEYS
+        ) == (b'{"a\\u2028\\"b\\u2029":1}')
+
+    def test_js_line_separator_shared_keys(self):
+        """
+        OPT_JS escapes keys of dicts sharing keys whether or not they were
+        serialized before without it
+        """
+        obj = [vars(Obj(idx)) for idx in range(3)]
+        expected = b'[{"a":0,"b\xe2\x80\xa8":0},{"a":1,"b\xe2\x80\xa8":1},{"a":2,"b\xe2\x80\xa8":2}]'
+        assert orjson.dumps(obj) == expected
+        assert orjson.dumps(obj, option=orjson.OPT_JS) == (
+            b'[{"a":0,"b\\u2028":0},{"a":1,"b\\u2028":1},{"a":2,"b\\u2028":2}]'
+        )
+        assert orjson.dumps(obj) == expected
+
+    def test_js_compile(self):
+        """
+        compile() with OPT_JS
+        """
+        ser = orjson.compile(Dataclass, option=orjson.OPT_JS)
+        assert ser(Dataclass(1, datetime.time(1, 2, 3, 4567))) == (
+            b'{"a":1,"b":"01:02:03.004"}'
+        )
+        with pytest.raises(orjson.JSONEncodeError):
+            ser(Dataclass(1 << 53, datetime.time()))
+
+    def test_js_kwarg(self):
+        """
+        dumps() js=True is OPT_JS
+        """
+        obj = ["\u2028", datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)]
+        assert orjson.dumps(obj, js=True) == orjson.dumps(obj, option=orjson.OPT_JS)
+        assert orjson.dumps(obj, option=orjson.OPT_JS, js=False) == orjson.dumps(obj)
+
+    def test_js_options_flag(self):
+        """
+        orjson.Options.JS is OPT_JS
+        """
+        assert orjson.Options.JS == orjson.OPT_JS
@@ -0,0 +1,119 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+
+import pytest
+
+import orjson
+
+CONFIG = b"""// configuration
+{
+    /* the port
+       to listen on */
+    "port": 8080, // inline
+    "hosts": [
+        "a", // first
+        "b",
+    ],
+    "url": "http://example.com/*not a comment*/",
+}
+/* trailing */
+"""
+
+
+class TestJsonc:
+    def test_jsonc_default(self):
+        """
+        loads() refuses comments and trailing commas by default
+        """
+        for val in (b"[1]//", b"/**/[1]", b"[1,]", b'{"a": 1,}'):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val)
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val, jsonc=False)
+
+    def test_jsonc(self):
+        """
+        loads() jsonc=True
+        """
+        assert orjson.loads(CONFIG, jsonc=True) == {
+            "port": 8080,
+            "hosts": ["a", "b"],
+            "url": "http://example.com/*not a comment*/",
+        }
+
+    def test_jsonc_str(self):
+        """
+        loads() jsonc=True str
+        """
+        assert orjson.loads(CONFIG.decode("utf-8"), jsonc=True)["port"] == 8080
+
+    def test_jsonc_line_comment_eof(self):
+        """
+        loads() jsonc=True line comment at the end of input
+        """
+        assert orjson.loads("[1] // end", jsonc=True) == [1]
+
+    def test_jsonc_comment_between_tokens(self):
+        """
+        loads() jsonc=True comments between every token
+        """
+        assert orjson.loads(
+            '/*a*/{/*b*/"a"/*c*/:/*d*/[/*e*/1/*f*/,/*g*/2/*h*/]/*i*/}/*j*/',
+            jsonc=True,
+        ) == {"a": [1, 2]}
+
+    def test_jsonc_trailing_comma(self):
+        """
+        loads() jsonc=True trailing commas
+        """
+        assert orjson.loads('[1, [2,], {"a": {},},]', jsonc=True) == [
+            1,
+            [2],
+            {"a": {}},
+        ]
+
+    def test_jsonc_invalid(self):
+        """
+        loads() jsonc=True still refuses other syntax
+        """
+        for val in (
+            "[,]",
+            "{,}",
+            "[1,,]",
+            "[1] /* unterminated",
+            "[1] / not a comment",
+            "{a: 1}",
+            "['a']",
+            "[1]#",
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val, jsonc=True)
+
+    def test_jsonc_strict(self):
+        """
+        loads() strict=True and jsonc=True are exclusive
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("[]", strict=True, jsonc=True)
+
+    def test_jsonc_not_bool(self):
+        """
+        loads() jsonc not a bool
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("[]", jsonc="yes")
+
+    def test_jsonc_load(self):
+        """
+        load() jsonc
+        """
+        assert orjson.load(io.BytesIO(CONFIG), jsonc=True)["hosts"] == ["a", "b"]
+
+    def test_jsonc_load_chunked(self):
+        """
+        load() jsonc with chunk_size
+        """
+        assert orjson.load(io.BytesIO(CONFIG), chunk_size=3, jsonc=True)[
+            "hosts"
+        ] == ["a", "b"]
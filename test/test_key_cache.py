@@ -0,0 +1,96 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import sys
+
+import orjson
+
+SHORT_KEY = "short"
+LONG_KEY = "long_" + "k" * 100
+LONG_KEY_NON_ASCII = "\U0001f408é平" * 30
+
+
+def keys(objs):
+    return [next(iter(each)) for each in objs]
+
+
+class TestKeyCache:
+    def test_key_cache_short(self):
+        """
+        loads() short keys repeated in a document are one str
+        """
+        objs = orjson.loads(orjson.dumps([{SHORT_KEY: idx} for idx in range(100)]))
+        assert len({id(key) for key in keys(objs)}) == 1
+
+    def test_key_cache_long(self):
+        """
+        loads() long keys repeated in a document are one str
+        """
+        for key in (LONG_KEY, LONG_KEY_NON_ASCII):
+            doc = orjson.dumps([{key: idx} for idx in range(100)])
+            objs = orjson.loads(doc)
+            assert all(each == key for each in keys(objs))
+            assert len({id(each) for each in keys(objs)}) == 1
+
+    def test_key_cache_long_escaped(self):
+        """
+        loads() long keys written differently are the same str
+        """
+        doc = '[{"%s": 1}, {"\\u006c%s": 2}]' % (LONG_KEY, LONG_KEY[1:])
+        objs = orjson.loads(doc)
+        assert keys(objs) == [LONG_KEY, LONG_KEY]
+        assert keys(objs)[0] is keys(objs)[1]
+
+    def test_key_cache_long_distinct(self):
+        """
+        loads() distinct long keys are not confused
+        """
+        doc = orjson.dumps(
+            [{LONG_KEY + str(idx % 7): idx} for idx in range(2000)]
+        )
+        objs = orjson.loads(doc)
+        assert keys(objs) == [LONG_KEY + str(idx % 7) for idx in range(2000)]
+
+    def test_key_cache_long_many(self):
+        """
+        loads() more distinct long keys than are cached
+        """
+        doc = orjson.dumps({LONG_KEY + str(idx): idx for idx in range(3000)})
+        obj = orjson.loads(doc)
+        assert len(obj) == 3000
+        assert obj[LONG_KEY + "2999"] == 2999
+
+    def test_key_cache_long_per_call(self):
+        """
+        loads() long keys are not kept after the call
+        """
+        doc = orjson.dumps([{LONG_KEY: 1}, {LONG_KEY: 2}])
+        first = keys(orjson.loads(doc))[0]
+        second = keys(orjson.loads(doc))[0]
+        assert first == second
+        assert first is not second
+        assert sys.getrefcount(first) == sys.getrefcount(second)
+
+    def test_key_cache_long_hash(self):
+        """
+        loads() cached long keys are hashed
+        """
+        doc = orjson.dumps([{LONG_KEY: 1, "a": 2}, {"a": 3, LONG_KEY: 4}])
+        objs = orjson.loads(doc)
+        assert [each[LONG_KEY] for each in objs] == [1, 4]
+
+    def test_key_cache_long_object_pairs_hook(self):
+        """
+        loads() long keys with object_pairs_hook
+        """
+        doc = orjson.dumps([{LONG_KEY: 1}, {LONG_KEY: 2}])
+        objs = orjson.loads(doc, object_pairs_hook=list)
+        assert objs[0][0][0] is objs[1][0][0]
+
+    def test_key_cache_long_load_chunked(self):
+        """
+        load() long keys with chunk_size
+        """
+        doc = orjson.dumps([{LONG_KEY: idx} for idx in range(100)])
+        objs = orjson.load(io.BytesIO(doc), chunk_size=16)
+        assert len({id(each) for each in keys(objs)}) == 1
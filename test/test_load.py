@@ -0,0 +1,234 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import os
+import tempfile
+
+import pytest
+
+import orjson
+
+from .util import RaisingReader, read_fixture_bytes
+
+
+class ChunkedReader(io.RawIOBase):
+    """
+    Binary file whose readinto() reads at most a few bytes at a time
+    """
+
+    def __init__(self, data, chunk=3):
+        self.data = data
+        self.pos = 0
+        self.chunk = chunk
+
+    def readable(self):
+        return True
+
+    def readinto(self, buf):
+        size = min(len(buf), self.chunk, len(self.data) - self.pos)
+        buf[:size] = self.data[self.pos : self.pos + size]
+        self.pos += size
+        return size
+
+
+class KeptViewReader(io.RawIOBase):
+    def __init__(self, data):
+        self.data = data
+        self.views = []
+
+    def readable(self):
+        return True
+
+    def readinto(self, buf):
+        self.views.append(buf)
+        size = len(self.data)
+        buf[:size] = self.data
+        self.data = b""
+        return size
+
+
+class NonBlockingReader(io.RawIOBase):
+    def readable(self):
+        return True
+
+    def readinto(self, buf):
+        return None
+
+
+class InvalidLengthReader(io.RawIOBase):
+    def readable(self):
+        return True
+
+    def readinto(self, buf):
+        return len(buf) + 1
+
+
+class TestLoad:
+    def test_load_binary_file(self):
+        """
+        load() binary file
+        """
+        with tempfile.TemporaryFile() as fileobj:
+            fileobj.write(b'{"a":[1,2.5,"\xc3\xa9"]}')
+            fileobj.seek(0)
+            assert orjson.load(fileobj) == {"a": [1, 2.5, "\xe9"]}
+
+    def test_load_current_position(self):
+        """
+        load() reads from the current position
+        """
+        fileobj = io.BytesIO(b"garbage[1]")
+        fileobj.seek(7)
+        assert orjson.load(fileobj) == [1]
+
+    def test_load_bytesio(self):
+        """
+        load() io.BytesIO
+        """
+        assert orjson.load(io.BytesIO(b"[]")) == []
+
+    def test_load_text_file(self):
+        """
+        load() text file
+        """
+        with tempfile.TemporaryFile("w+", encoding="utf-8") as fileobj:
+            fileobj.write('{"a":"\xe9"}')
+            fileobj.seek(0)
+            assert orjson.load(fileobj) == {"a": "\xe9"}
+        assert orjson.load(io.StringIO("[true]")) == [True]
+
+    def test_load_fd(self):
+        """
+        load() file descriptor is read and not closed
+        """
+        with tempfile.TemporaryFile() as fileobj:
+            fileobj.write(b'{"a":null}')
+            fileobj.flush()
+            fileobj.seek(0)
+            fd = fileobj.fileno()
+            assert orjson.load(fd) == {"a": None}
+            os.fstat(fd)
+
+    def test_load_pipe(self):
+        """
+        load() pipe
+        """
+        read_fd, write_fd = os.pipe()
+        os.write(write_fd, b"[1,2]")
+        os.close(write_fd)
+        try:
+            assert orjson.load(read_fd) == [1, 2]
+        finally:
+            os.close(read_fd)
+
+    def test_load_fd_invalid(self):
+        """
+        load() invalid file descriptor
+        """
+        read_fd, write_fd = os.pipe()
+        os.close(read_fd)
+        os.close(write_fd)
+        with pytest.raises(OSError):
+            orjson.load(read_fd)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(-1)
+
+    def test_load_chunked(self):
+        """
+        load() file returning less than requested from readinto()
+        """
+        data = orjson.dumps({"a": list(range(100)), "b": "x" * 100})
+        assert orjson.load(ChunkedReader(data)) == orjson.loads(data)
+
+    def test_load_large(self):
+        """
+        load() file larger than the initial buffer
+        """
+        data = orjson.dumps([{"key": "x" * 1000, "idx": idx} for idx in range(500)])
+        assert len(data) > 4 * 64 * 1024
+        assert orjson.load(io.BytesIO(data)) == orjson.loads(data)
+        assert orjson.load(ChunkedReader(data, 40000)) == orjson.loads(data)
+
+    def test_load_fixture(self):
+        """
+        load() fixture
+        """
+        data = read_fixture_bytes("twitter.json.xz")
+        assert orjson.load(io.BytesIO(data)) == orjson.loads(data)
+
+    def test_load_view_released(self):
+        """
+        load() releases the buffer given to readinto()
+        """
+        reader = KeptViewReader(b"[1]")
+        assert orjson.load(reader) == [1]
+        for view in reader.views:
+            with pytest.raises(ValueError):
+                view[0]
+
+    def test_load_empty(self):
+        """
+        load() empty file
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b""))
+
+    def test_load_invalid_json(self):
+        """
+        load() invalid JSON
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.load(io.BytesIO(b'{"a":'))
+        assert exc_info.value.doc == '{"a":'
+
+    def test_load_invalid_utf8(self):
+        """
+        load() invalid UTF-8
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b'"\xff"'))
+
+    def test_load_invalid_utf8_long(self):
+        """
+        load() and loads() validate the UTF-8 of long non-ASCII input in full
+        """
+        val = "\U0001f408\xe9一a" * 1000
+        data = orjson.dumps([val, {val: val}])
+        assert orjson.load(io.BytesIO(data)) == [val, {val: val}]
+        for idx in (2, 4000, len(data) - 40):
+            invalid = data[:idx] + b"\xff" + data[idx + 1 :]
+            with pytest.raises(orjson.JSONDecodeError) as exc_info:
+                orjson.load(io.BytesIO(invalid))
+            assert exc_info.value.msg == "str is not valid UTF-8: surrogates not allowed"
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(invalid)
+
+    def test_load_read_exception(self):
+        """
+        load() propagates an exception raised reading
+        """
+        with pytest.raises(ValueError):
+            orjson.load(RaisingReader())
+
+    def test_load_non_blocking(self):
+        """
+        load() file with no data available
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(NonBlockingReader())
+
+    def test_load_invalid_length(self):
+        """
+        load() file readinto() returning more than the buffer
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(InvalidLengthReader())
+
+    def test_load_not_file(self):
+        """
+        load() object that is not a file
+        """
+        with pytest.raises(AttributeError):
+            orjson.load(object())
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.StringIO())
@@ -0,0 +1,226 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import os
+import tempfile
+
+import pytest
+
+import orjson
+
+from .util import RaisingReader, read_fixture_bytes
+
+
+class RecordingReader(io.RawIOBase):
+    """
+    Binary file recording the size of each readinto() buffer
+    """
+
+    def __init__(self, data):
+        self.data = data
+        self.pos = 0
+        self.sizes = []
+
+    def readable(self):
+        return True
+
+    def readinto(self, buf):
+        self.sizes.append(len(buf))
+        size = min(len(buf), len(self.data) - self.pos)
+        buf[:size] = self.data[self.pos : self.pos + size]
+        self.pos += size
+        return size
+
+
+class ReadOnlyFile:
+    def __init__(self, data):
+        self.data = data
+        self.pos = 0
+
+    def read(self, size):
+        val = self.data[self.pos : self.pos + size]
+        self.pos += size
+        return val
+
+
+class TestLoadChunked:
+    def test_load_chunked_binary(self):
+        """
+        load() chunk_size binary file
+        """
+        data = orjson.dumps({"a": [1, 2.5, "\xe9", None, True], "b": {"c": []}})
+        assert orjson.load(io.BytesIO(data), chunk_size=4) == orjson.loads(data)
+
+    def test_load_chunked_bounded_reads(self):
+        """
+        load() chunk_size reads at most chunk_size bytes at a time
+        """
+        data = orjson.dumps([{"key": "x" * 100, "idx": idx} for idx in range(1000)])
+        reader = RecordingReader(data)
+        assert orjson.load(reader, chunk_size=512) == orjson.loads(data)
+        assert len(reader.sizes) > len(data) // 512
+        assert max(reader.sizes) <= 512
+
+    def test_load_chunked_split_character(self):
+        """
+        load() chunk_size splitting a multibyte character across chunks
+        """
+        data = '["\U0001f408\xe9一"]'.encode("utf-8")
+        for size in range(1, len(data) + 1):
+            assert orjson.load(io.BytesIO(data), chunk_size=size) == [
+                "\U0001f408\xe9一"
+            ]
+
+    def test_load_chunked_text(self):
+        """
+        load() chunk_size text file
+        """
+        assert orjson.load(io.StringIO('{"a":"\U0001f408"}'), chunk_size=1) == {
+            "a": "\U0001f408"
+        }
+
+    def test_load_chunked_read(self):
+        """
+        load() chunk_size object with only read()
+        """
+        assert orjson.load(ReadOnlyFile(b"[1,2,3]"), chunk_size=2) == [1, 2, 3]
+        assert orjson.load(ReadOnlyFile("[1,2,3]"), chunk_size=2) == [1, 2, 3]
+
+    def test_load_chunked_read_invalid_type(self):
+        """
+        load() chunk_size read() returning neither bytes nor str
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(ReadOnlyFile([1, 2]), chunk_size=2)
+
+    def test_load_chunked_fd(self):
+        """
+        load() chunk_size file descriptor is read and not closed
+        """
+        with tempfile.TemporaryFile() as fileobj:
+            fileobj.write(b'{"a":[null,1]}')
+            fileobj.flush()
+            fileobj.seek(0)
+            fd = fileobj.fileno()
+            assert orjson.load(fd, chunk_size=3) == {"a": [None, 1]}
+            os.fstat(fd)
+
+    def test_load_chunked_pipe(self):
+        """
+        load() chunk_size pipe
+        """
+        read_fd, write_fd = os.pipe()
+        os.write(write_fd, b'["a","b"]')
+        os.close(write_fd)
+        try:
+            assert orjson.load(read_fd, chunk_size=2) == ["a", "b"]
+        finally:
+            os.close(read_fd)
+
+    def test_load_chunked_fd_invalid(self):
+        """
+        load() chunk_size invalid file descriptor
+        """
+        read_fd, write_fd = os.pipe()
+        os.close(read_fd)
+        os.close(write_fd)
+        with pytest.raises(OSError):
+            orjson.load(read_fd, chunk_size=2)
+
+    def test_load_chunked_fixture(self):
+        """
+        load() chunk_size fixture
+        """
+        data = read_fixture_bytes("twitter.json.xz")
+        assert orjson.load(io.BytesIO(data), chunk_size=4096) == orjson.loads(data)
+
+    def test_load_chunked_numbers(self):
+        """
+        load() chunk_size numbers are read as loads() reads them
+        """
+        data = (
+            b"[0.1, -0.0, 1e-7, 2.5E+3, 5e-324, "
+            b"1.7976931348623157e308, 18446744073709551616]"
+        )
+        assert orjson.load(io.BytesIO(data), chunk_size=4) == orjson.loads(data)
+        raw = orjson.load(io.BytesIO(data), chunk_size=4, raw_numbers=True)
+        assert raw == orjson.loads(data, raw_numbers=True)
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.load(io.BytesIO(b"[1e400]"), chunk_size=4)
+        assert exc_info.value.msg == "number is infinity when parsed as double"
+
+    def test_load_chunked_none(self):
+        """
+        load() chunk_size None reads the whole file
+        """
+        assert orjson.load(io.BytesIO(b"[]"), chunk_size=None) == []
+
+    def test_load_chunked_invalid_json(self):
+        """
+        load() chunk_size invalid JSON reports the line and column
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.load(io.BytesIO(b'{"a":\n[1,}'), chunk_size=2)
+        assert "line 2" in str(exc_info.value)
+        assert exc_info.value.doc == ""
+
+    def test_load_chunked_trailing(self):
+        """
+        load() chunk_size trailing characters
+        """
+        assert orjson.load(io.BytesIO(b"[1] \n"), chunk_size=2) == [1]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b"[1] [2]"), chunk_size=2)
+
+    def test_load_chunked_empty(self):
+        """
+        load() chunk_size empty file
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b""), chunk_size=2)
+
+    def test_load_chunked_invalid_utf8(self):
+        """
+        load() chunk_size invalid UTF-8
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b'["\xff"]'), chunk_size=2)
+
+    def test_load_chunked_long_strings(self):
+        """
+        load() chunk_size long non-ASCII strings are validated
+        """
+        val = "\U0001f408\xe9一a" * 100
+        data = orjson.dumps([val, {val: val}])
+        assert orjson.load(io.BytesIO(data), chunk_size=64) == [val, {val: val}]
+        for idx in (2, 200, len(val.encode("utf-8"))):
+            invalid = data[:idx] + b"\xff" + data[idx + 1 :]
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.load(io.BytesIO(invalid), chunk_size=64)
+
+    def test_load_chunked_read_exception(self):
+        """
+        load() chunk_size propagates an exception raised reading
+        """
+        with pytest.raises(ValueError) as exc_info:
+            orjson.load(RaisingReader(), chunk_size=2)
+        assert str(exc_info.value) == "read failed"
+
+    def test_load_chunked_invalid_size(self):
+        """
+        load() chunk_size that is not a positive int
+        """
+        for val in (0, -1, "1", 1.5):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.load(io.BytesIO(b"[]"), chunk_size=val)
+
+    def test_load_invalid_arguments(self):
+        """
+        load() invalid arguments
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load()
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b"[]"), 1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b"[]"), size=1)
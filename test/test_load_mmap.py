@@ -0,0 +1,113 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import mmap
+import os
+import pathlib
+import tempfile
+
+import pytest
+
+import orjson
+
+from .util import read_fixture_bytes
+
+
+def write_file(directory, data):
+    path = os.path.join(directory, "document.json")
+    with open(path, "wb") as fileobj:
+        fileobj.write(data)
+    return path
+
+
+class TestLoadMmap:
+    def test_load_mmap(self):
+        """
+        load_mmap() str, bytes, and os.PathLike paths
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            path = write_file(directory, b'{"a": [1, 2], "b": "\xc3\xa9"}')
+            for each in (path, os.fsencode(path), pathlib.Path(path)):
+                assert orjson.load_mmap(each) == {"a": [1, 2], "b": "é"}
+
+    def test_load_mmap_fixture(self):
+        """
+        load_mmap() fixture
+        """
+        data = read_fixture_bytes("twitter.json.xz")
+        with tempfile.TemporaryDirectory() as directory:
+            path = write_file(directory, data)
+            assert orjson.load_mmap(path) == orjson.loads(data)
+
+    def test_load_mmap_kwargs(self):
+        """
+        load_mmap() arguments of loads()
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            path = write_file(directory, b'xx[1, 2.5, {"a": 1}]yy')
+            assert orjson.load_mmap(path, offset=2, length=18, tuples=True) == (
+                1,
+                2.5,
+                {"a": 1},
+            )
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.load_mmap(path, offset=2, length=18, max_items=2)
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.load_mmap(path, chunk_size=2)
+
+    def test_load_mmap_empty(self):
+        """
+        load_mmap() empty file
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            path = write_file(directory, b"")
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.load_mmap(path)
+
+    def test_load_mmap_invalid(self):
+        """
+        load_mmap() invalid JSON and UTF-8
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            for data in (b"[1,", b'"\xff"'):
+                path = write_file(directory, data)
+                with pytest.raises(orjson.JSONDecodeError):
+                    orjson.load_mmap(path)
+            assert orjson.load_mmap(path, replace_invalid_utf8=True) == "�"
+
+    def test_load_mmap_missing(self):
+        """
+        load_mmap() propagates the exception opening the file
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            with pytest.raises(FileNotFoundError):
+                orjson.load_mmap(os.path.join(directory, "missing.json"))
+        with pytest.raises(TypeError):
+            orjson.load_mmap(1.5)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load_mmap()
+
+    def test_load_mmap_hook_exception(self):
+        """
+        load_mmap() propagates an exception raised by a hook
+        """
+
+        def hook(_):
+            raise ValueError("hook")
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = write_file(directory, b"[1]")
+            with pytest.raises(ValueError):
+                orjson.load_mmap(path, parse_int=hook)
+            # the file is closed and unmapped, so it can be removed
+            os.remove(path)
+
+    def test_load_mmap_iter(self):
+        """
+        iter_array() and loads_lines() of an mmap.mmap
+        """
+        with tempfile.TemporaryDirectory() as directory:
+            path = write_file(directory, b"[1, 2]\n[3]\n")
+            with open(path, "rb") as fileobj:
+                with mmap.mmap(fileobj.fileno(), 0, access=mmap.ACCESS_READ) as mapped:
+                    assert list(orjson.loads_lines(mapped)) == [[1, 2], [3]]
+                    assert list(orjson.iter_array(mapped[:6])) == [1, 2]
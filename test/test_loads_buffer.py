@@ -0,0 +1,133 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import array
+import mmap
+import sys
+import tempfile
+
+import pytest
+
+import orjson
+
+try:
+    import numpy
+except ImportError:
+    numpy = None  # type: ignore
+
+
+class BytesSubclass(bytes):
+    pass
+
+
+class TestLoadsBuffer:
+    def test_loads_mmap(self):
+        """
+        loads() mmap.mmap of a file
+        """
+        with tempfile.TemporaryFile() as fileobj:
+            fileobj.write(b'{"a":[1,2.5,"b"]}')
+            fileobj.flush()
+            with mmap.mmap(fileobj.fileno(), 0, access=mmap.ACCESS_READ) as buf:
+                assert orjson.loads(buf) == {"a": [1, 2.5, "b"]}
+
+    def test_loads_anonymous_mmap(self):
+        """
+        loads() writable mmap.mmap
+        """
+        data = b'["\xc3\xa9"]'
+        buf = mmap.mmap(-1, len(data))
+        buf.write(data)
+        assert orjson.loads(buf) == ["\xe9"]
+        buf.close()
+
+    def test_loads_array(self):
+        """
+        loads() array.array of bytes
+        """
+        assert orjson.loads(array.array("B", b"[1,2,3]")) == [1, 2, 3]
+
+    def test_loads_buffer_format(self):
+        """
+        loads() buffer of items other than bytes raises TypeError
+        """
+        assert orjson.loads(array.array("b", b"[1]")) == [1]
+        assert orjson.loads(memoryview(b"[2]").cast("c")) == [2]
+        for buf in (array.array("i", [0]), memoryview(b"[1] ").cast("I")):
+            with pytest.raises(TypeError) as exc_info:
+                orjson.loads(buf)
+            assert not isinstance(exc_info.value, orjson.JSONDecodeError)
+
+    def test_loads_bytes_subclass(self):
+        """
+        loads() bytes subclass
+        """
+        assert orjson.loads(BytesSubclass(b'{"a":true}')) == {"a": True}
+
+    def test_loads_buffer_empty(self):
+        """
+        loads() empty buffer
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(array.array("B"))
+
+    def test_loads_buffer_invalid_json(self):
+        """
+        loads() buffer of invalid JSON reports the document
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads(array.array("B", b"[1,"))
+        assert exc_info.value.doc == "[1,"
+
+    def test_loads_buffer_invalid_utf8(self):
+        """
+        loads() buffer of invalid UTF-8
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(array.array("B", b'"\xff"'))
+
+    def test_loads_buffer_released(self):
+        """
+        loads() releases the buffer
+        """
+        arr = array.array("B", b"[]")
+        assert orjson.loads(arr) == []
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(array.array("B", b"["))
+        # an array with an exported buffer cannot be resized
+        arr.append(32)
+        assert orjson.loads(arr) == []
+
+    def test_loads_buffer_refcount(self):
+        """
+        loads() does not leak a reference to the buffer
+        """
+        arr = array.array("B", b"{}")
+        before = sys.getrefcount(arr)
+        for _ in range(100):
+            orjson.loads(arr)
+        assert sys.getrefcount(arr) == before
+
+    def test_loads_not_buffer(self):
+        """
+        loads() object not supporting the buffer protocol
+        """
+        for val in (1, None, [], object()):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val)
+
+    @pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+    def test_loads_numpy(self):
+        """
+        loads() numpy array of bytes
+        """
+        arr = numpy.frombuffer(b'{"a":1}', dtype=numpy.uint8)
+        assert orjson.loads(arr) == {"a": 1}
+
+    @pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+    def test_loads_numpy_not_contiguous(self):
+        """
+        loads() numpy array that is not C contiguous
+        """
+        arr = numpy.frombuffer(b"[[11]]", dtype=numpy.uint8)[::2]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(arr)
@@ -0,0 +1,269 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import datetime
+import decimal
+import struct
+import uuid
+
+import pytest
+
+import orjson
+
+
+def head(major, arg):
+    """
+    Encode the head of an item of major type major and argument arg.
+    """
+    if arg < 24:
+        return bytes([major << 5 | arg])
+    for info, fmt, hi in ((24, ">B", 2**8), (25, ">H", 2**16), (26, ">I", 2**32)):
+        if arg < hi:
+            return bytes([major << 5 | info]) + struct.pack(fmt, arg)
+    return bytes([major << 5 | 27]) + struct.pack(">Q", arg)
+
+
+def encode(obj):
+    """
+    Serialize obj to CBOR using the smallest head of each item.
+    """
+    if obj is None:
+        return b"\xf6"
+    if obj is False:
+        return b"\xf4"
+    if obj is True:
+        return b"\xf5"
+    if isinstance(obj, int):
+        if obj >= 0:
+            return head(0, obj)
+        return head(1, -1 - obj)
+    if isinstance(obj, float):
+        return b"\xfb" + struct.pack(">d", obj)
+    if isinstance(obj, str):
+        data = obj.encode("utf-8")
+        return head(3, len(data)) + data
+    if isinstance(obj, bytes):
+        return head(2, len(obj)) + obj
+    if isinstance(obj, list):
+        return head(4, len(obj)) + b"".join(encode(each) for each in obj)
+    if isinstance(obj, dict):
+        return head(5, len(obj)) + b"".join(
+            encode(k) + encode(v) for k, v in obj.items()
+        )
+    raise TypeError(obj)
+
+
+def error(data, **kwargs):
+    with pytest.raises(orjson.JSONDecodeError) as exc:
+        orjson.loads_cbor(data, **kwargs)
+    return exc.value.msg
+
+
+def error_at(data, **kwargs):
+    with pytest.raises(orjson.JSONDecodeError) as exc:
+        orjson.loads_cbor(data, **kwargs)
+    return exc.value.msg, exc.value.pos
+
+
+class TestLoadsCbor:
+    def test_loads_cbor(self):
+        """
+        loads_cbor() deserializes the objects loads() does
+        """
+        obj = {
+            "a": [1, -1, 200, -200, 70000, -70000, 2**40, -(2**40), 2**64 - 1],
+            "b": [1.5, -0.0, "", "ä" * 40, True, False, None],
+            "c": {"d": [], "e": {}},
+            "f": list(range(30)),
+            "g": -(2**63),
+        }
+        assert orjson.loads_cbor(encode(obj)) == obj
+        assert orjson.loads_cbor(encode(obj)) == orjson.loads(orjson.dumps(obj))
+
+    def test_loads_cbor_negative_beyond_64_bits(self):
+        """
+        loads_cbor() negative integer less than -2**63
+        """
+        assert orjson.loads_cbor(encode(-(2**64))) == -(2**64)
+        assert error(encode(-(2**64)), strict=True).startswith(
+            "integer is out of range"
+        )
+
+    def test_loads_cbor_floats(self):
+        """
+        loads_cbor() float 16 and float 32
+        """
+        assert orjson.loads_cbor(b"\xf9\x3e\x00") == 1.5
+        assert orjson.loads_cbor(b"\xf9\x00\x01") == 2.0**-24
+        assert orjson.loads_cbor(b"\xf9\xc4\x00") == -4.0
+        assert orjson.loads_cbor(b"\xfa" + struct.pack(">f", 0.5)) == 0.5
+
+    def test_loads_cbor_simple(self):
+        """
+        loads_cbor() undefined to None and other simple values refused
+        """
+        assert orjson.loads_cbor(b"\xf7") is None
+        assert error_at(b"\xf0") == ("CBOR simple values are not supported", 0)
+        assert error_at(b"\xf8\x20") == ("CBOR simple values are not supported", 0)
+
+    def test_loads_cbor_bytes(self):
+        """
+        loads_cbor() byte strings to bytes
+        """
+        assert orjson.loads_cbor(encode([b"", b"\x00\xff"])) == [b"", b"\x00\xff"]
+
+    def test_loads_cbor_indefinite(self):
+        """
+        loads_cbor() strings and containers of indefinite length
+        """
+        assert orjson.loads_cbor(b"\x7f\x62ab\x61c\xff") == "abc"
+        assert orjson.loads_cbor(b"\x5f\x41a\x40\x41b\xff") == b"ab"
+        assert orjson.loads_cbor(b"\x9f\x01\x9f\xff\xff") == [1, []]
+        assert orjson.loads_cbor(b"\xbf\x61a\x01\x61b\x9f\xff\xff") == {
+            "a": 1,
+            "b": [],
+        }
+        assert error_at(b"\x7f\x41a\xff") == ("invalid chunk of CBOR string", 1)
+        assert error_at(b"\x9f\x01") == ("unexpected end of data", 2)
+        assert error(b"\x9f\x01\x02\xff", max_items=1) == (
+            "Document exceeds max_items of 1"
+        )
+
+    def test_loads_cbor_datetime(self):
+        """
+        loads_cbor() date/time string and epoch-based date/time
+        """
+        assert orjson.loads_cbor(
+            b"\xc0" + encode("2020-01-01T12:30:00.5+01:00")
+        ) == datetime.datetime(
+            2020,
+            1,
+            1,
+            12,
+            30,
+            0,
+            500000,
+            tzinfo=datetime.timezone(datetime.timedelta(hours=1)),
+        )
+        utc = datetime.datetime(2020, 1, 1, tzinfo=datetime.timezone.utc)
+        assert orjson.loads_cbor(b"\xc0" + encode("2020-01-01T00:00:00Z")) == utc
+        assert orjson.loads_cbor(b"\xc1" + encode(1577836800)) == utc
+        assert orjson.loads_cbor(b"\xc1" + encode(1577836800.25)) == (
+            utc + datetime.timedelta(microseconds=250000)
+        )
+        assert error_at(b"\xc0" + encode("2020-01-01")) == (
+            "invalid RFC 3339 date/time", 1
+        )
+        assert error_at(b"\xc0\x01") == ("invalid item for CBOR tag 0", 1)
+        assert error_at(b"\xc1" + encode(2**62)) == ("timestamp is out of range", 0)
+
+    def test_loads_cbor_bignum(self):
+        """
+        loads_cbor() bignums
+        """
+        assert orjson.loads_cbor(b"\xc2\x41\x01") == 1
+        assert orjson.loads_cbor(b"\xc3\x41\x01") == -2
+        big = 2**100
+        data = big.to_bytes(13, "big")
+        assert orjson.loads_cbor(b"\xc2" + encode(data)) == big
+        assert orjson.loads_cbor(b"\xc3" + encode(data)) == -1 - big
+        assert orjson.loads_cbor(b"\xc2" + encode(b"\x00" * 20 + b"\x07")) == 7
+        assert orjson.loads_cbor(b"\xc2" + encode(data), parse_int=str) == str(big)
+        assert error(b"\xc2" + encode(data), strict=True).startswith(
+            "integer is out of range"
+        )
+
+    def test_loads_cbor_uuid(self):
+        """
+        loads_cbor() UUID
+        """
+        val = uuid.UUID("7202d115-7ff3-4c81-a7c1-2a1f067b1ece")
+        assert orjson.loads_cbor(b"\xd8\x25" + encode(val.bytes)) == val
+        assert error_at(b"\xd8\x25" + encode(b"\x00")) == ("UUID must be 16 bytes", 2)
+
+    def test_loads_cbor_tags(self):
+        """
+        loads_cbor() self-described CBOR and unsupported tags
+        """
+        assert orjson.loads_cbor(b"\xd9\xd9\xf7" + encode([1])) == [1]
+        assert error_at(b"\xd8\x20" + encode("a")) == (
+            "CBOR tag 32 is not supported", 0
+        )
+
+    def test_loads_cbor_hooks(self):
+        """
+        loads_cbor() parse_float, parse_int, object_hook, object_pairs_hook
+        """
+        data = encode({"a": [0.1, 2], "b": {"c": 1}})
+        assert orjson.loads_cbor(data, parse_float=decimal.Decimal) == {
+            "a": [decimal.Decimal("0.1"), 2],
+            "b": {"c": 1},
+        }
+        assert orjson.loads_cbor(data, parse_int=str)["a"] == [0.1, "2"]
+        assert orjson.loads_cbor(data, object_hook=len) == 2
+        assert orjson.loads_cbor(data, object_pairs_hook=lambda pairs: pairs) == [
+            ("a", [0.1, 2]),
+            ("b", [("c", 1)]),
+        ]
+        assert orjson.loads_cbor(
+            b"\xbf\x61a\x01\xff", object_pairs_hook=lambda pairs: pairs
+        ) == [("a", 1)]
+
+    def test_loads_cbor_duplicate_keys(self):
+        """
+        loads_cbor() duplicate_keys and strict
+        """
+        data = b"\xa2\x61a\x01\x61a\x02"
+        assert orjson.loads_cbor(data) == {"a": 2}
+        assert orjson.loads_cbor(data, duplicate_keys="list") == {"a": [1, 2]}
+        assert error(data, strict=True) == 'Duplicate key "a"'
+
+    def test_loads_cbor_limits(self):
+        """
+        loads_cbor() max_depth, NaN, max_string_len, max_bytes, schema
+        """
+        assert error_at(encode([[[1]]]), max_depth=2) == ("recursion limit exceeded", 2)
+        assert error(b"\xd9\xd9\xf7" * 1025 + b"\x01").startswith(
+            "recursion limit exceeded"
+        )
+        assert error_at(b"\xf9\x7c\x00") == ("nan or inf number is not allowed", 0)
+        assert orjson.loads_cbor(b"\xf9\x7c\x00", allow_nan=True) == float("inf")
+        assert error(b"\x7f\x62ab\x62cd\xff", max_string_len=3) == (
+            "String exceeds max_string_len of 3 characters"
+        )
+        assert error(encode([1, 2]), max_bytes=2) == (
+            "Input exceeds max_bytes of 2 bytes"
+        )
+        schema = orjson.Schema({"type": "array", "items": {"type": "integer"}})
+        assert error(encode([1, "2"]), schema=schema) == (
+            'Expected integer, got string at "/1"'
+        )
+
+    def test_loads_cbor_invalid(self):
+        """
+        loads_cbor() invalid CBOR
+        """
+        assert error_at(b"") == ("unexpected end of data", 0)
+        assert error_at(b"\x82\x01") == ("unexpected end of data", 1)
+        assert error_at(b"\x19\x01") == ("unexpected end of data", 1)
+        assert error_at(b"\x01\x02") == ("unexpected content after document", 1)
+        assert error_at(b"\x1c") == ("invalid CBOR item", 0)
+        assert error_at(b"\x81\xff") == ("unexpected break", 1)
+        assert error_at(b"\x62\xff\xfe") == ("invalid utf-8 encoding in string", 0)
+        assert error_at(b"\xa1\x01\x02") == ("map key must be a string", 1)
+
+    def test_loads_cbor_arguments(self):
+        """
+        loads_cbor() invalid input and arguments
+        """
+        data = encode({"a": 1})
+        for each in (data, bytearray(data), memoryview(data)):
+            assert orjson.loads_cbor(each) == {"a": 1}
+        for args, kwargs in (
+            (("\x01",), {}),
+            ((), {}),
+            ((b"\x01", b"\x01"), {}),
+            ((b"\x01",), {"jsonc": True}),
+            ((b"\x01",), {"unknown": 1}),
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_cbor(*args, **kwargs)
@@ -0,0 +1,131 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections
+import decimal
+
+import pytest
+
+import orjson
+
+from .util import read_fixture_bytes
+
+
+class TestLoadsColumns:
+    def test_loads_columns(self):
+        """
+        loads_columns() of objects with the same keys
+        """
+        assert orjson.loads_columns(
+            b'[{"a": 1, "b": "x"}, {"a": 2, "b": "y"}, {"b": "z", "a": 3}]'
+        ) == {"a": [1, 2, 3], "b": ["x", "y", "z"]}
+
+    def test_loads_columns_missing(self):
+        """
+        loads_columns() None for a key an object does not have
+        """
+        val = orjson.loads_columns(
+            '[{"a": 1, "b": 2}, {}, {"c": [3]}, {"a": {"d": 4}}, {"b": null}]'
+        )
+        assert val == {
+            "a": [1, None, None, {"d": 4}, None],
+            "b": [2, None, None, None, None],
+            "c": [None, None, [3], None, None],
+        }
+        assert list(val) == ["a", "b", "c"]
+
+    def test_loads_columns_empty(self):
+        """
+        loads_columns() of an empty array or of empty objects
+        """
+        assert orjson.loads_columns(b"[]") == {}
+        assert orjson.loads_columns(b"[{}, {}]") == {}
+
+    def test_loads_columns_keys(self):
+        """
+        loads_columns() key of a column is one str
+        """
+        val = orjson.loads_columns(b'[{"key": 1}]')
+        (key,) = val
+        assert key is next(iter(orjson.loads(b'{"key": 1}')))
+
+    def test_loads_columns_fixture(self):
+        """
+        loads_columns() of the statuses of a fixture
+        """
+        statuses = orjson.loads(read_fixture_bytes("twitter.json.xz"))["statuses"]
+        val = orjson.loads_columns(orjson.dumps(statuses))
+        keys = {key for status in statuses for key in status}
+        assert set(val) == keys
+        for key in keys:
+            assert val[key] == [status.get(key) for status in statuses]
+
+    def test_loads_columns_not_records(self):
+        """
+        loads_columns() of a document that is not an array of objects
+        """
+        for doc in (b"{}", b"1", b'"a"', b"[1]", b'[{"a": 1}, []]', b"[{}, null]"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_columns(doc)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_columns(b'[{"a": 1}')
+
+    def test_loads_columns_duplicate_keys(self):
+        """
+        loads_columns() duplicate_keys within an object
+        """
+        doc = b'[{"a": 1, "a": 2}, {"a": 3}]'
+        assert orjson.loads_columns(doc) == {"a": [2, 3]}
+        assert orjson.loads_columns(doc, duplicate_keys="first") == {"a": [1, 3]}
+        for duplicate_keys in ("error", "list"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_columns(doc, duplicate_keys=duplicate_keys)
+
+    def test_loads_columns_kwargs(self):
+        """
+        loads_columns() arguments of loads(), applied to the values
+        """
+        val = orjson.loads_columns(
+            b'[{"a": 1.5, "b": {"c": [1]}}]',
+            parse_float=decimal.Decimal,
+            dict_class=collections.OrderedDict,
+            tuples=True,
+        )
+        assert val == {"a": [decimal.Decimal("1.5")], "b": [{"c": (1,)}]}
+        assert type(val) is dict
+        assert type(val["b"][0]) is collections.OrderedDict
+        assert orjson.loads_columns(b'xx[{"a": 1}]', offset=2) == {"a": [1]}
+
+    def test_loads_columns_limits(self):
+        """
+        loads_columns() max_depth and max_items count the array and objects
+        """
+        assert orjson.loads_columns(b'[{"a": 1}]', max_depth=2) == {"a": [1]}
+        assert orjson.loads_columns(b"[]", max_depth=1) == {}
+        for doc, kwargs in (
+            (b'[{"a": [1]}]', {"max_depth": 2}),
+            (b'[{"a": 1}]', {"max_depth": 1}),
+            (b'[{"a": 1}, {"a": 2}]', {"max_items": 3}),
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_columns(doc, **kwargs)
+
+    def test_loads_columns_hook_exception(self):
+        """
+        loads_columns() propagates an exception raised by a hook
+        """
+
+        def hook(_):
+            raise ValueError("hook")
+
+        with pytest.raises(ValueError):
+            orjson.loads_columns(b'[{"a": 1}, {"a": 2}]', parse_int=hook)
+
+    def test_loads_columns_invalid(self):
+        """
+        loads_columns() arguments that are invalid or not accepted
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_columns()
+        for kwargs in ({"numpy": True}, {"threads": 2}, {"x": 1}):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_columns(b"[]", **kwargs)
@@ -0,0 +1,195 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import datetime
+import gzip
+import io
+import os
+import tempfile
+
+import pytest
+
+import orjson
+
+from .util import RaisingReader, has_compression
+
+# largest block of a zstd frame with the window size used by zstd_frame()
+ZSTD_BLOCK_SIZE = 128 * 1024
+
+
+def zstd_frame(data):
+    """
+    Encode data as a zstd frame of uncompressed blocks.
+    """
+    frame = b"\x28\xb5\x2f\xfd\x00\x38"
+    blocks = [
+        data[idx : idx + ZSTD_BLOCK_SIZE]
+        for idx in range(0, len(data), ZSTD_BLOCK_SIZE)
+    ] or [b""]
+    for idx, block in enumerate(blocks):
+        last = idx == len(blocks) - 1
+        frame += (len(block) << 3 | last).to_bytes(3, "little") + block
+    return frame
+
+
+def error(data, **kwargs):
+    with pytest.raises(orjson.JSONDecodeError) as exc:
+        orjson.loads_compressed(data, **kwargs)
+    return str(exc.value).split(": line ")[0]
+
+
+@pytest.mark.skipif(
+    not has_compression(), reason="built without the compression feature"
+)
+class TestLoadsCompressed:
+    def test_loads_compressed_gzip(self):
+        """
+        loads_compressed() gzip
+        """
+        obj = {"a": [1, 2.5, "ä", None, True], "b": {}}
+        data = gzip.compress(orjson.dumps(obj))
+        for each in (data, bytearray(data), memoryview(data)):
+            assert orjson.loads_compressed(each) == obj
+            assert orjson.loads_compressed(each, compression="gzip") == obj
+
+    def test_loads_compressed_zstd(self):
+        """
+        loads_compressed() zstd
+        """
+        obj = {"a": [1, 2.5, "ä", None, True], "b": {}}
+        data = zstd_frame(orjson.dumps(obj))
+        assert orjson.loads_compressed(data) == obj
+        assert orjson.loads_compressed(data, compression="zstd") == obj
+
+    def test_loads_compressed_large(self):
+        """
+        loads_compressed() document decompressed in more than one chunk
+        """
+        obj = [{"key": "x" * 100, "idx": idx} for idx in range(10000)]
+        data = orjson.dumps(obj)
+        assert orjson.loads_compressed(gzip.compress(data)) == obj
+        assert orjson.loads_compressed(zstd_frame(data)) == obj
+
+    def test_loads_compressed_members(self):
+        """
+        loads_compressed() gzip members and zstd frames are one document
+        """
+        assert orjson.loads_compressed(
+            gzip.compress(b'{"a": ') + gzip.compress(b"[1]}")
+        ) == {"a": [1]}
+        assert orjson.loads_compressed(zstd_frame(b"[1,") + zstd_frame(b"2]")) == [
+            1,
+            2,
+        ]
+
+    def test_loads_compressed_file(self):
+        """
+        loads_compressed() binary file object and file descriptor
+        """
+        data = gzip.compress(orjson.dumps([{"a": "x" * 100000}] * 10))
+        assert orjson.loads_compressed(io.BytesIO(data)) == [{"a": "x" * 100000}] * 10
+        with tempfile.TemporaryFile() as fileobj:
+            fileobj.write(zstd_frame(b'{"a": 1}'))
+            fileobj.flush()
+            fileobj.seek(0)
+            fd = fileobj.fileno()
+            assert orjson.loads_compressed(fd) == {"a": 1}
+            os.fstat(fd)
+
+    def test_loads_compressed_arguments(self):
+        """
+        loads_compressed() arguments of loads()
+        """
+        data = gzip.compress(b'{"a": [1.5, "2020-01-01"]}')
+        assert orjson.loads_compressed(data, tuples=True, datetimes=True) == {
+            "a": (1.5, datetime.date(2020, 1, 1))
+        }
+        assert orjson.loads_compressed(data, parse_float=str) == {
+            "a": ["1.5", "2020-01-01"]
+        }
+        assert orjson.loads_compressed(gzip.compress(b"[1 /* a */]"), jsonc=True) == [
+            1
+        ]
+        schema = orjson.Schema({"type": "array", "items": {"type": "integer"}})
+        assert error(gzip.compress(b'[1, "2"]'), schema=schema) == (
+            'Expected integer, got string at "/1"'
+        )
+        assert error(data, max_depth=1).startswith("recursion limit exceeded")
+
+    def test_loads_compressed_max_bytes(self):
+        """
+        loads_compressed() max_bytes limits the decompressed document
+        """
+        data = gzip.compress(b"[" + b"0," * 1000000 + b"0]")
+        assert len(data) < 10000
+        assert error(data, max_bytes=10000) == "Input exceeds max_bytes of 10000 bytes"
+        assert orjson.loads_compressed(gzip.compress(b"[1]"), max_bytes=3) == [1]
+
+    def test_loads_compressed_invalid_json(self):
+        """
+        loads_compressed() decompressed document is not valid JSON
+        """
+        assert error(gzip.compress(b"[1, 2")).startswith("unexpected end of data")
+        assert error(zstd_frame(b"[1] 2")).startswith("unexpected content after document")
+        assert error(gzip.compress(b'"\xff"')).startswith("invalid utf-8 encoding in string")
+
+    def test_loads_compressed_invalid(self):
+        """
+        loads_compressed() input not valid for its format
+        """
+        data = gzip.compress(b"[1]")
+        assert error(data[:-4]) == "Input is not valid gzip: unexpected end of file"
+        assert error(b"\x1f\x8b\x00") == (
+            "Input is not valid gzip: unexpected end of file"
+        )
+        assert error(zstd_frame(b"[1]")[:-1]) == (
+            "Input is not valid zstd: incomplete frame"
+        )
+        assert error(data, compression="zstd").startswith("Input is not valid zstd")
+        assert error(zstd_frame(b"[1]"), compression="gzip").startswith(
+            "Input is not valid gzip"
+        )
+        for val in (b"", b"[1]", b"\x1f"):
+            assert error(val) == "Input is not compressed with gzip or zstd"
+
+    def test_loads_compressed_read_exception(self):
+        """
+        loads_compressed() propagates an exception raised reading
+        """
+
+        with pytest.raises(ValueError):
+            orjson.loads_compressed(RaisingReader())
+
+    def test_loads_compressed_invalid_arguments(self):
+        """
+        loads_compressed() invalid input and arguments
+        """
+        data = gzip.compress(b"[1]")
+        for args, kwargs in (
+            ((), {}),
+            ((data, data), {}),
+            ((data,), {"compression": "bz2"}),
+            ((data,), {"compression": 1}),
+            ((data,), {"offset": 0}),
+            ((data,), {"length": 1}),
+            ((data,), {"threads": 2}),
+            ((data,), {"unknown": 1}),
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_compressed(*args, **kwargs)
+        assert orjson.loads_compressed(data, compression=None) == [1]
+        with pytest.raises(AttributeError):
+            orjson.loads_compressed(object())
+
+
+@pytest.mark.skipif(has_compression(), reason="built with the compression feature")
+class TestLoadsCompressedUnavailable:
+    def test_loads_compressed_unavailable(self):
+        """
+        loads_compressed() raises JSONDecodeError if orjson is built without
+        the compression feature
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads_compressed(gzip.compress(b"1"))
+        assert exc.value.msg == (
+            "loads_compressed() requires orjson built with the compression feature"
+        )
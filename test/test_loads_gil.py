@@ -0,0 +1,95 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import array
+import io
+import threading
+
+import pytest
+
+import orjson
+
+# larger than the buffer parsed into while holding the GIL
+DOC = [{"a": [idx, str(idx) * 10, None, True, 1.5]} for idx in range(40000)]
+DATA = orjson.dumps(DOC)
+
+
+class TestLoadsGil:
+    def test_loads_large(self):
+        """
+        loads() large input of each type
+        """
+        assert len(DATA) > 1024 * 1024
+        for val in (
+            DATA,
+            DATA.decode("utf-8"),
+            bytearray(DATA),
+            memoryview(DATA),
+            array.array("B", DATA),
+        ):
+            assert orjson.loads(val) == DOC
+
+    def test_loads_large_offset(self):
+        """
+        loads() large input with offset and length
+        """
+        assert orjson.loads(b"xx" + DATA + b"xx", offset=2, length=len(DATA)) == DOC
+
+    def test_loads_large_invalid(self):
+        """
+        loads() large invalid input
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads(DATA[:-1])
+        assert exc.value.pos == len(DATA) - 1
+
+    def test_loads_large_args(self):
+        """
+        loads() large input with arguments
+        """
+        assert orjson.loads(DATA, parse_int=str)[-1]["a"][0] == "39999"
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(DATA, max_depth=2)
+
+    def test_loads_large_threads(self):
+        """
+        loads() large input in threads at once
+        """
+        results = []
+        data = [orjson.dumps(DOC[idx:]) for idx in range(8)]
+
+        def target(idx):
+            for _ in range(4):
+                results.append(orjson.loads(data[idx]) == DOC[idx:])
+
+        threads = [threading.Thread(target=target, args=(idx,)) for idx in range(8)]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+        assert results == [True] * 32
+
+    def test_loads_small_threads(self):
+        """
+        loads() small and large input in threads at once
+        """
+        results = []
+
+        def target(data, expected):
+            for _ in range(20):
+                results.append(orjson.loads(data) == expected)
+
+        threads = [
+            threading.Thread(target=target, args=(DATA, DOC)),
+            threading.Thread(target=target, args=(b'{"a": [1, 2]}', {"a": [1, 2]})),
+        ]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+        assert results == [True] * 40
+
+    def test_load_large(self):
+        """
+        load() large input
+        """
+        assert orjson.load(io.BytesIO(DATA)) == DOC
@@ -0,0 +1,254 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import sys
+from typing import Any, Dict, List, Optional
+
+import pytest
+
+import orjson
+
+
+@dataclasses.dataclass
+class Point:
+    x: int
+    y: int
+
+
+@dataclasses.dataclass
+class Shape:
+    name: str
+    points: List[Point]
+    tags: Dict[str, float]
+    center: Optional[Point] = None
+    extra: Any = None
+
+
+@dataclasses.dataclass
+class Node:
+    value: int
+    children: "List[Node]" = dataclasses.field(default_factory=list)
+
+
+@dataclasses.dataclass
+class Aliased:
+    first_name: str = dataclasses.field(metadata={"orjson": {"alias": "firstName"}})
+
+
+@dataclasses.dataclass
+class Defaults:
+    a: int
+    b: str = "b"
+    c: List[int] = dataclasses.field(default_factory=list)
+    d: int = dataclasses.field(default=0, init=False)
+
+
+@dataclasses.dataclass
+class PostInit:
+    a: int
+
+    def __post_init__(self):
+        if self.a < 0:
+            raise ValueError(self.a)
+
+
+@dataclasses.dataclass(frozen=True)
+class Frozen:
+    a: int
+
+
+class TestLoadsInto:
+    def test_loads_into(self):
+        """
+        loads_into() dataclass
+        """
+        assert orjson.loads_into(Point, b'{"x": 1, "y": 2}') == Point(1, 2)
+        assert orjson.loads_into(Point, '{"y": 2, "x": 1}') == Point(1, 2)
+
+    def test_loads_into_nested(self):
+        """
+        loads_into() nested dataclasses, List, Dict, Optional
+        """
+        doc = (
+            b'{"name": "a", "points": [{"x": 1, "y": 2}, {"x": 3, "y": 4}],'
+            b' "tags": {"b": 1.5, "c": 2}, "center": {"x": 0, "y": 0},'
+            b' "extra": {"d": [1]}}'
+        )
+        shape = orjson.loads_into(Shape, doc)
+        assert shape == Shape(
+            "a", [Point(1, 2), Point(3, 4)], {"b": 1.5, "c": 2.0}, Point(0, 0), {"d": [1]}
+        )
+        assert type(shape.tags["c"]) is float
+
+    def test_loads_into_optional(self):
+        """
+        loads_into() Optional field of null or absent
+        """
+        doc = b'{"name": "a", "points": [], "tags": {}, "center": null}'
+        assert orjson.loads_into(Shape, doc).center is None
+        doc = b'{"name": "a", "points": [], "tags": {}}'
+        assert orjson.loads_into(Shape, doc).center is None
+
+    def test_loads_into_recursive(self):
+        """
+        loads_into() recursive dataclass with a forward reference
+        """
+        doc = b'{"value": 1, "children": [{"value": 2, "children": [{"value": 3}]}]}'
+        assert orjson.loads_into(Node, doc) == Node(1, [Node(2, [Node(3)])])
+
+    def test_loads_into_top_level_hint(self):
+        """
+        loads_into() type hint other than a dataclass
+        """
+        assert orjson.loads_into(List[Point], b'[{"x": 1, "y": 2}]') == [Point(1, 2)]
+        assert orjson.loads_into(Dict[str, Point], b'{"a": {"x": 1, "y": 2}}') == {
+            "a": Point(1, 2)
+        }
+        assert orjson.loads_into(Optional[Point], b"null") is None
+        assert orjson.loads_into(int, b"1") == 1
+        assert orjson.loads_into(Any, b"[1]") == [1]
+
+    @pytest.mark.skipif(sys.version_info < (3, 10), reason="X | None")
+    def test_loads_into_builtin_generics(self):
+        """
+        loads_into() list[X], dict[str, X], X | None
+        """
+        hint = eval("list[dict[str, Point | None]]")
+        assert orjson.loads_into(hint, b'[{"a": {"x": 1, "y": 2}, "b": null}]') == [
+            {"a": Point(1, 2), "b": None}
+        ]
+
+    def test_loads_into_alias(self):
+        """
+        loads_into() dataclass field alias is the key
+        """
+        assert orjson.loads_into(Aliased, b'{"firstName": "a"}') == Aliased("a")
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_into(Aliased, b'{"first_name": "a"}')
+
+    def test_loads_into_defaults(self):
+        """
+        loads_into() fields with defaults may be absent, init=False is ignored
+        """
+        obj = orjson.loads_into(Defaults, b'{"a": 1, "d": 5}')
+        assert obj == Defaults(1)
+        assert obj.d == 0
+
+    def test_loads_into_unknown_keys(self):
+        """
+        loads_into() keys that are not fields are ignored
+        """
+        assert orjson.loads_into(Point, b'{"x": 1, "y": 2, "z": 3}') == Point(1, 2)
+
+    def test_loads_into_missing(self):
+        """
+        loads_into() required field absent
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads_into(List[Point], b'[{"x": 1, "y": 2}, {"x": 1}]')
+        assert str(exc.value).startswith('Missing field "y" of Point at "/1"')
+
+    def test_loads_into_mismatch(self):
+        """
+        loads_into() value of the wrong type reports its location
+        """
+        doc = b'{"name": "a", "points": [{"x": 1, "y": "2"}], "tags": {}}'
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads_into(Shape, doc)
+        assert str(exc.value).startswith('Expected int, got str at "/points/0/y"')
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads_into(Point, b"[]")
+        assert str(exc.value).startswith("Expected Point, got list")
+
+    def test_loads_into_strict_types(self):
+        """
+        loads_into() bool is not int, int is float
+        """
+        for doc in (b'{"x": true, "y": 1}', b'{"x": 1.0, "y": 1}'):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_into(Point, doc)
+        assert orjson.loads_into(float, b"1") == 1.0
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_into(float, b"true")
+
+    def test_loads_into_post_init(self):
+        """
+        loads_into() calls __init__ and __post_init__
+        """
+        assert orjson.loads_into(PostInit, b'{"a": 1}') == PostInit(1)
+        with pytest.raises(ValueError):
+            orjson.loads_into(PostInit, b'{"a": -1}')
+        assert orjson.loads_into(Frozen, b'{"a": 1}') == Frozen(1)
+
+    def test_loads_into_invalid_json(self):
+        """
+        loads_into() invalid JSON
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_into(Point, b'{"x": 1,')
+
+    def test_loads_into_invalid_type(self):
+        """
+        loads_into() type not supported
+        """
+        for hint in (object, 1, "Point", Dict[int, Point]):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_into(hint, b"{}")
+
+    def test_loads_into_args(self):
+        """
+        loads_into() keyword arguments of loads()
+        """
+        assert orjson.loads_into(
+            Point, b'xx{"x": 1, "y": 2}', offset=2, max_items=2
+        ) == Point(1, 2)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_into(Point, b'{"x": 1, "y": 2}', max_items=1)
+        for arg in (
+            "parse_float",
+            "parse_int",
+            "object_hook",
+            "object_pairs_hook",
+            "tuples",
+            "immutable",
+            "datetimes",
+            "uuids",
+            "numpy",
+            "raw_numbers",
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_into(Point, b'{"x": 1, "y": 2}', **{arg: None})
+
+    def test_loads_into_positional(self):
+        """
+        loads_into() takes 2 positional arguments
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_into(Point)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_into(Point, b"{}", b"{}")
+
+    def test_loads_into_unresolved_hint(self):
+        """
+        loads_into() type hint that cannot be resolved propagates NameError
+        """
+
+        @dataclasses.dataclass
+        class Unresolved:
+            a: "Undefined"  # type: ignore # noqa
+
+        with pytest.raises(NameError):
+            orjson.loads_into(Unresolved, b'{"a": 1}')
+
+    def test_loads_into_refcount(self):
+        """
+        loads_into() does not leak
+        """
+        val = "a" * 64
+        doc = orjson.dumps({"name": val, "points": [{"x": 1, "y": "2"}], "tags": {}})
+        before = sys.getrefcount(Point)
+        for _ in range(100):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_into(Shape, doc)
+            orjson.loads_into(List[Point], b'[{"x": 1, "y": 2}]')
+        assert sys.getrefcount(Point) == before
@@ -0,0 +1,113 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections
+import json
+import types
+
+import pytest
+
+import orjson
+
+from .util import errors_equal
+
+# long enough to be deserialized using a structural index if built with it,
+# with escapes and runs of backslashes across each block of 64 bytes
+DOC = [
+    {
+        "a" + "\\" * (idx % 5): [idx, '"],}[{\\' * (idx % 7), None, 1.5, -(2**70)],
+        "b": {"cé\U0001f600": [[], {}, "\n\t\x1f/"], "d": [True, False, -0.0]},
+    }
+    for idx in range(20000)
+]
+DATA = json.dumps(DOC).encode("utf-8")
+
+
+class TestLoadsLarge:
+    def test_large(self):
+        """
+        loads() of a document of more than 1MiB
+        """
+        assert len(DATA) > 1024 * 1024
+        assert orjson.loads(DATA) == DOC
+        assert orjson.loads(DATA.decode("utf-8")) == DOC
+        assert orjson.loads(json.dumps(DOC, ensure_ascii=False)) == DOC
+
+    def test_large_whitespace(self):
+        """
+        loads() of a large document with whitespace between tokens
+        """
+        data = json.dumps(DOC, indent="\t", separators=(" ,\r\n", " : "))
+        assert orjson.loads(data) == DOC
+
+    def test_large_scalar(self):
+        """
+        loads() of a large string or number
+        """
+        val = "a\\\"" * 400000
+        assert orjson.loads(json.dumps(val)) == val
+        assert orjson.loads(" " * 2000000 + "123 ") == 123
+
+    def test_large_kwargs(self):
+        """
+        loads() of a large document with options
+        """
+        val = orjson.loads(DATA, tuples=True)
+        assert val[0]["a"] == (0, "", None, 1.5, -(2**70))
+        val = orjson.loads(DATA, dict_class=collections.OrderedDict)
+        assert type(val[1]["b"]) is collections.OrderedDict
+        val = orjson.loads(DATA, immutable=True)
+        assert isinstance(val[2], types.MappingProxyType)
+        val = orjson.loads(DATA, object_hook=len)
+        assert val[3] == 2
+        data = b'[{"a": 1, "a": 2}, ' + DATA[1:]
+        assert orjson.loads(data, duplicate_keys="first")[0] == {"a": 1}
+        assert orjson.loads(data, duplicate_keys="list")[0] == {"a": [1, 2]}
+
+    def test_large_limits(self):
+        """
+        loads() of a large document exceeding a limit
+        """
+        assert orjson.loads(DATA, max_depth=5) == DOC
+        for kwargs in (
+            {"max_depth": 4},
+            {"max_items": 100000},
+            {"max_string_len": 10},
+            {"max_string_bytes": 100000},
+        ):
+            assert errors_equal(DATA, 1, **kwargs)
+        data = b'[{"a": 1, "a": 2}, ' + DATA[1:]
+        assert errors_equal(data, 1, duplicate_keys="error")
+
+    def test_large_invalid(self):
+        """
+        loads() of an invalid large document reports the error of the parser
+        """
+        size = len(DATA)
+        for data in (
+            DATA[:-1],
+            DATA + b"]",
+            DATA + b" 1",
+            DATA[:-1] + b",]",
+            DATA.replace(b"true", b"tru", 1),
+            DATA.replace(b"1.5", b"1.", 1),
+            DATA.replace(b"1.5", b"1e999", 1),
+            DATA.replace(b"1.5", b"01", 1),
+            DATA.replace(b'"b"', b'"\x01"', 1),
+            DATA.replace(b'"b"', b'"\\x"', 1),
+            DATA.replace(b'"b"', b'"\\ud800"', 1),
+            DATA.replace(b'"b": ', b'"b" ', 1),
+            DATA[: size // 2] + b'"' + DATA[size // 2 :],
+            DATA[: size // 2] + b"\\" + DATA[size // 2 :],
+        ):
+            assert errors_equal(data, 1)
+
+    def test_large_exception(self):
+        """
+        loads() of a large document propagates an exception raised by a hook
+        """
+
+        def hook(_):
+            raise ValueError("hook")
+
+        with pytest.raises(ValueError):
+            orjson.loads(DATA, object_hook=hook)
@@ -0,0 +1,270 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import sys
+import tempfile
+
+import pytest
+
+import orjson
+
+
+class TestLoadsLimits:
+    def test_limits_none(self):
+        """
+        loads() max_items, max_string_len, max_bytes of None are the default
+        """
+        assert orjson.loads(
+            '{"a": [1, 2, "bcd"]}', max_items=None, max_string_len=None, max_bytes=None
+        ) == {"a": [1, 2, "bcd"]}
+
+    def test_max_items(self):
+        """
+        loads() max_items counts array elements and object members
+        """
+        doc = '{"a": [1, 2], "b": {"c": 3}}'
+        assert orjson.loads(doc, max_items=5) == {"a": [1, 2], "b": {"c": 3}}
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads(doc, max_items=4)
+        assert "max_items" in str(exc.value)
+
+    def test_max_items_total(self):
+        """
+        loads() max_items is for the whole document, not each container
+        """
+        doc = "[" + ",".join(["[1, 2]"] * 10) + "]"
+        assert orjson.loads(doc, max_items=30)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(doc, max_items=29)
+
+    def test_max_items_zero(self):
+        """
+        loads() max_items=0 allows scalars and empty containers
+        """
+        assert orjson.loads("1", max_items=0) == 1
+        assert orjson.loads('[{}, []]', max_items=2) == [{}, []]
+        assert orjson.loads("[]", max_items=0) == []
+        assert orjson.loads("{}", max_items=0) == {}
+        for val in ("[1]", '{"a": 1}', "[[]]"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val, max_items=0)
+
+    def test_max_items_object_pairs_hook(self):
+        """
+        loads() max_items with object_pairs_hook
+        """
+        assert orjson.loads('{"a": 1, "b": 2}', max_items=2, object_pairs_hook=list)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('{"a": 1, "b": 2}', max_items=1, object_pairs_hook=list)
+
+    def test_max_items_refcount(self):
+        """
+        loads() max_items exceeded does not leak
+        """
+        val = "a" * 64
+        doc = orjson.dumps([{"a": [val, val]}, [val, [val]]])
+        before = sys.getrefcount(val)
+        for _ in range(100):
+            for max_items in range(6):
+                with pytest.raises(orjson.JSONDecodeError):
+                    orjson.loads(doc, max_items=max_items)
+        assert sys.getrefcount(val) == before
+
+    def test_max_string_len(self):
+        """
+        loads() max_string_len
+        """
+        assert orjson.loads('["abc"]', max_string_len=3) == ["abc"]
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads('["abcd"]', max_string_len=3)
+        assert "max_string_len" in str(exc.value)
+
+    def test_max_string_len_key(self):
+        """
+        loads() max_string_len applies to keys
+        """
+        assert orjson.loads('{"abc": 1}', max_string_len=3) == {"abc": 1}
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('{"abcd": 1}', max_string_len=3)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('{"abcd": 1}', max_string_len=3, object_pairs_hook=list)
+
+    def test_max_string_len_code_points(self):
+        """
+        loads() max_string_len counts code points, not bytes
+        """
+        assert orjson.loads('"\U0001f408é平"', max_string_len=3) == (
+            "\U0001f408é平"
+        )
+        assert orjson.loads('"\\u00e9\\u00e9"', max_string_len=2) == "éé"
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('"\U0001f408é平"', max_string_len=2)
+
+    def test_max_string_len_zero(self):
+        """
+        loads() max_string_len=0 allows only empty strings
+        """
+        assert orjson.loads('["", {"": 1}]', max_string_len=0) == ["", {"": 1}]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('"a"', max_string_len=0)
+
+    def test_max_string_len_numbers(self):
+        """
+        loads() max_string_len does not apply to numbers
+        """
+        assert orjson.loads("[12345, 1.2345]", max_string_len=1) == [12345, 1.2345]
+
+    def test_limits_position(self):
+        """
+        loads() max_items, max_string_len, and max_string_bytes exceeded
+        report the position of the element, member, or string that exceeds
+        them
+        """
+        for doc, kwargs, pos in (
+            ("[1,2,3]", {"max_items": 2}, 5),
+            ('{"a": 1, "b": 2, "c": 3}', {"max_items": 2}, 17),
+            ('[1, [2, 3], {"x": [4]}]', {"max_items": 4}, 8),
+            ('[1, "abcd"]', {"max_string_len": 3}, 4),
+            ('{"abcd": 1}', {"max_string_len": 3}, 1),
+            ('["ab", "cd", "ef"]', {"max_string_bytes": 5}, 13),
+            ('{"é": "ééé", "a": "éé"}', {"max_string_bytes": 9}, 18),
+        ):
+            with pytest.raises(orjson.JSONDecodeError) as exc:
+                orjson.loads(doc, **kwargs)
+            assert exc.value.pos == pos
+
+    def test_max_bytes(self):
+        """
+        loads() max_bytes
+        """
+        assert orjson.loads(b"[1, 2]", max_bytes=6) == [1, 2]
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads(b"[1, 2]", max_bytes=5)
+        assert "max_bytes" in str(exc.value)
+
+    def test_max_bytes_position(self):
+        """
+        loads() max_bytes exceeded reports the position of the first byte past
+        it
+        """
+        for doc in ('[1,\n "é", 3]', b'[1,\n "\xc3\xa9", 3]'):
+            with pytest.raises(orjson.JSONDecodeError) as exc:
+                orjson.loads(doc, max_bytes=8)
+            assert exc.value.pos == 7
+            assert exc.value.lineno == 2
+            assert exc.value.colno == 4
+
+    def test_max_bytes_whitespace(self):
+        """
+        loads() max_bytes includes whitespace
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(b"[]" + b" " * 10, max_bytes=10)
+
+    def test_max_bytes_str(self):
+        """
+        loads() max_bytes of str is its length encoded as UTF-8
+        """
+        assert orjson.loads('"é"', max_bytes=4) == "é"
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('"é"', max_bytes=3)
+
+    def test_max_bytes_buffer(self):
+        """
+        loads() max_bytes of bytearray and memoryview
+        """
+        for val in (bytearray(b"[1, 2]"), memoryview(b"[1, 2]")):
+            assert orjson.loads(val, max_bytes=6) == [1, 2]
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val, max_bytes=5)
+
+    def test_max_bytes_short(self):
+        """
+        loads() max_bytes applies to short documents
+        """
+        for val in ("[]", "{}", '""'):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val, max_bytes=1)
+
+    def test_limits_invalid(self):
+        """
+        loads() max_items, max_string_len, max_bytes invalid
+        """
+        for arg in ("max_items", "max_string_len", "max_string_bytes", "max_bytes"):
+            for val in (-1, 1.5, "1", True, 2**64):
+                with pytest.raises(orjson.JSONDecodeError):
+                    orjson.loads("[]", **{arg: val})
+
+    def test_load_limits(self):
+        """
+        load() max_items, max_string_len, max_bytes
+        """
+        for kwargs in ({"max_items": 1}, {"max_string_len": 2}, {"max_bytes": 9}):
+            assert orjson.load(io.BytesIO(b'["ab"]'), **kwargs) == ["ab"]
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.load(io.BytesIO(b'["abc", 1]'), **kwargs)
+
+    def test_load_max_bytes_text(self):
+        """
+        load() max_bytes of a text file
+        """
+        assert orjson.load(io.StringIO("[1]"), max_bytes=3) == [1]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.StringIO("[1] "), max_bytes=3)
+
+    def test_load_max_bytes_fd(self):
+        """
+        load() max_bytes of a file descriptor
+        """
+        with tempfile.TemporaryFile() as fileh:
+            fileh.write(b"[1, 2]")
+            fileh.flush()
+            for chunk_size in (None, 2):
+                fileh.seek(0)
+                assert orjson.load(
+                    fileh.fileno(), chunk_size=chunk_size, max_bytes=6
+                ) == [1, 2]
+                fileh.seek(0)
+                with pytest.raises(orjson.JSONDecodeError):
+                    orjson.load(fileh.fileno(), chunk_size=chunk_size, max_bytes=5)
+
+    def test_load_max_bytes_position(self):
+        """
+        load() max_bytes exceeded reports the position of the first byte past
+        it
+        """
+        for chunk_size in (None, 2):
+            with pytest.raises(orjson.JSONDecodeError) as exc:
+                orjson.load(
+                    io.BytesIO(b"[1,\n2, 3]"), chunk_size=chunk_size, max_bytes=6
+                )
+            assert exc.value.pos == 6
+            assert exc.value.lineno == 2
+            assert exc.value.colno == 3
+
+    def test_load_max_bytes_stops_reading(self):
+        """
+        load() max_bytes stops reading a large file
+        """
+        fileh = io.BytesIO(b"[" + b"1," * (1024 * 1024) + b"1]")
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(fileh, max_bytes=16)
+        assert fileh.tell() < 1024 * 1024
+
+    def test_load_limits_chunked(self):
+        """
+        load() max_items, max_string_len, max_bytes with chunk_size
+        """
+        for kwargs in ({"max_items": 1}, {"max_string_len": 2}, {"max_bytes": 9}):
+            assert orjson.load(io.BytesIO(b'["ab"]'), chunk_size=2, **kwargs) == ["ab"]
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.load(io.BytesIO(b'["abc", 1]'), chunk_size=2, **kwargs)
+
+    def test_load_max_bytes_chunked_stops_reading(self):
+        """
+        load() max_bytes with chunk_size stops reading a large file
+        """
+        fileh = io.BytesIO(b'"' + b"a" * (1024 * 1024) + b'"')
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(fileh, chunk_size=64, max_bytes=256)
+        assert fileh.tell() < 1024
@@ -0,0 +1,171 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import os
+import tempfile
+
+import pytest
+
+import orjson
+
+from .util import RaisingReader
+
+
+class TestLoadsLines:
+    def test_loads_lines_bytes(self):
+        """
+        loads_lines() bytes
+        """
+        data = b'{"a":1}\n[1,2]\n"b"\nnull\n'
+        assert list(orjson.loads_lines(data)) == [{"a": 1}, [1, 2], "b", None]
+
+    def test_loads_lines_str(self):
+        """
+        loads_lines() str
+        """
+        assert list(orjson.loads_lines('"\xe9"\n"\U0001f408"')) == [
+            "\xe9",
+            "\U0001f408",
+        ]
+
+    def test_loads_lines_buffer(self):
+        """
+        loads_lines() bytearray and memoryview
+        """
+        for val in (bytearray(b"1\n2\n"), memoryview(b"1\n2\n")):
+            assert list(orjson.loads_lines(val)) == [1, 2]
+
+    def test_loads_lines_no_trailing_newline(self):
+        """
+        loads_lines() last line without a newline
+        """
+        assert list(orjson.loads_lines(b"1\n2")) == [1, 2]
+
+    def test_loads_lines_blank(self):
+        """
+        loads_lines() skips blank lines
+        """
+        assert list(orjson.loads_lines(b"\n1\n\n  \n\t\r\n2\n\n")) == [1, 2]
+        assert list(orjson.loads_lines(b"")) == []
+        assert list(orjson.loads_lines(b"\n\n")) == []
+
+    def test_loads_lines_crlf(self):
+        """
+        loads_lines() CRLF line endings
+        """
+        assert list(orjson.loads_lines(b'{"a":1}\r\n{"b":2}\r\n')) == [
+            {"a": 1},
+            {"b": 2},
+        ]
+
+    def test_loads_lines_lazy(self):
+        """
+        loads_lines() deserializes each line when it is reached
+        """
+        it = orjson.loads_lines(b"1\n2\n{\n3\n")
+        assert next(it) == 1
+        assert next(it) == 2
+        with pytest.raises(orjson.JSONDecodeError):
+            next(it)
+        assert list(it) == []
+
+    def test_loads_lines_error_lineno(self):
+        """
+        loads_lines() error reports the line number in the input
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            list(orjson.loads_lines(b'1\n\n{"a":1}\n{"a":}\n2\n'))
+        assert exc_info.value.lineno == 4
+        assert exc_info.value.colno == 6
+        assert exc_info.value.pos == 16
+        assert exc_info.value.snippet == '{"a":}'
+        assert "line 4 column 6 (char 16)" in str(exc_info.value)
+
+    def test_loads_lines_error_pos_str(self):
+        """
+        loads_lines() error position in str input is in characters
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            list(orjson.loads_lines('"\xe9\xe9"\n {x}\n'))
+        assert exc_info.value.lineno == 2
+        assert exc_info.value.colno == 3
+        assert exc_info.value.pos == 7
+
+    def test_loads_lines_error_file_lineno(self):
+        """
+        loads_lines() error in a file reports the line number
+        """
+        data = b"[1]\n" * 100000 + b"[\n"
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            for _ in orjson.loads_lines(io.BytesIO(data)):
+                pass
+        assert exc_info.value.lineno == 100001
+
+    def test_loads_lines_multiple_documents(self):
+        """
+        loads_lines() line with more than one document
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            list(orjson.loads_lines(b"1 2\n"))
+
+    def test_loads_lines_multiline_document(self):
+        """
+        loads_lines() document split across lines
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            list(orjson.loads_lines(b"[1,\n2]\n"))
+
+    def test_loads_lines_binary_file(self):
+        """
+        loads_lines() binary file with lines longer than a read
+        """
+        records = [{"key": "x" * 10000, "idx": idx} for idx in range(100)]
+        data = b"\n".join(orjson.dumps(record) for record in records)
+        assert list(orjson.loads_lines(io.BytesIO(data))) == records
+
+    def test_loads_lines_text_file(self):
+        """
+        loads_lines() text file
+        """
+        with tempfile.TemporaryFile("w+", encoding="utf-8") as fileobj:
+            fileobj.write('{"a":"\xe9"}\n{"b":2}\n')
+            fileobj.seek(0)
+            assert list(orjson.loads_lines(fileobj)) == [{"a": "\xe9"}, {"b": 2}]
+
+    def test_loads_lines_fd(self):
+        """
+        loads_lines() file descriptor is read and not closed
+        """
+        with tempfile.TemporaryFile() as fileobj:
+            fileobj.write(b"1\n2\n")
+            fileobj.flush()
+            fileobj.seek(0)
+            fd = fileobj.fileno()
+            assert list(orjson.loads_lines(fd)) == [1, 2]
+            os.fstat(fd)
+
+    def test_loads_lines_invalid_utf8(self):
+        """
+        loads_lines() invalid UTF-8
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_lines(b'1\n"\xff"\n')
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            list(orjson.loads_lines(io.BytesIO(b'1\n"\xff"\n')))
+        assert exc_info.value.lineno == 2
+
+    def test_loads_lines_read_exception(self):
+        """
+        loads_lines() propagates an exception raised reading
+        """
+        it = orjson.loads_lines(RaisingReader(b"1\n2"))
+        assert next(it) == 1
+        with pytest.raises(ValueError):
+            next(it)
+
+    def test_loads_lines_invalid_input(self):
+        """
+        loads_lines() input that is not data or a file
+        """
+        with pytest.raises(AttributeError):
+            orjson.loads_lines(object())
@@ -0,0 +1,206 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import gzip
+import io
+import threading
+
+import pytest
+
+import orjson
+
+
+def nested_array(depth):
+    return "[" * depth + "]" * depth
+
+
+def nested_object(depth):
+    return '{"a":' * (depth - 1) + "{}" + "}" * (depth - 1)
+
+
+def nesting_is_iterative():
+    """
+    Whether the decoder is yyjson, which does not recurse to nest containers.
+    """
+    try:
+        orjson.loads("[[1]]", max_items=1)
+    except orjson.JSONDecodeError as exc:
+        return exc.pos == 0
+    return False
+
+
+def depth_of(obj, child):
+    depth = 0
+    while obj is not None:
+        depth += 1
+        obj = child(obj)
+    return depth
+
+
+class TestLoadsMaxDepth:
+    def test_loads_max_depth_default(self):
+        """
+        loads() allows 1024 nested containers by default
+        """
+        assert orjson.loads(nested_array(1024)) is not None
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(nested_array(1025))
+
+    def test_loads_max_depth_default_object(self):
+        """
+        loads() allows 1024 nested objects by default
+        """
+        assert orjson.loads(nested_object(1024)) is not None
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(nested_object(1025))
+
+    def test_loads_max_depth_none(self):
+        """
+        loads() max_depth=None is the default
+        """
+        assert orjson.loads(nested_array(1024), max_depth=None) is not None
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(nested_array(1025), max_depth=None)
+
+    def test_loads_max_depth(self):
+        """
+        loads() max_depth
+        """
+        assert orjson.loads("[[[]]]", max_depth=3) == [[[]]]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("[[[]]]", max_depth=2)
+
+    def test_loads_max_depth_mixed(self):
+        """
+        loads() max_depth counts arrays and objects
+        """
+        assert orjson.loads('[{"a": [1]}]', max_depth=3) == [{"a": [1]}]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('[{"a": [1]}]', max_depth=2)
+
+    def test_loads_max_depth_zero(self):
+        """
+        loads() max_depth=0 allows only scalars
+        """
+        assert orjson.loads('"a"', max_depth=0) == "a"
+        for val in ("[]", "{}"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val, max_depth=0)
+
+    def test_loads_max_depth_siblings(self):
+        """
+        loads() max_depth is not the number of containers
+        """
+        assert orjson.loads("[" + ",".join(["[]"] * 1000) + "]", max_depth=2)
+
+    @pytest.mark.skipif(
+        not nesting_is_iterative(), reason="decoder recurses to nest containers"
+    )
+    def test_loads_max_depth_higher(self):
+        """
+        loads() max_depth above the default
+        """
+        assert orjson.loads(nested_array(2000), max_depth=2000) is not None
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(nested_array(2001), max_depth=2000)
+
+    @pytest.mark.skipif(
+        not nesting_is_iterative(), reason="decoder recurses to nest containers"
+    )
+    def test_loads_max_depth_deep(self):
+        """
+        loads() nesting is limited by max_depth and not by the stack
+        """
+        depth = 100000
+        results = []
+
+        def target():
+            results.append(orjson.loads(nested_array(depth), max_depth=depth))
+            results.append(orjson.loads(nested_object(depth), max_depth=depth))
+            results.append(
+                orjson.loads(
+                    nested_object(depth), max_depth=depth, object_pairs_hook=list
+                )
+            )
+            results.append(orjson.is_valid(nested_array(depth), max_depth=depth))
+
+        previous = threading.stack_size(256 * 1024)
+        try:
+            thread = threading.Thread(target=target)
+            thread.start()
+            thread.join()
+        finally:
+            threading.stack_size(previous)
+        array, obj, pairs, valid = results
+        assert depth_of(array, lambda val: val[0] if val else None) == depth
+        assert depth_of(obj, lambda val: val.get("a")) == depth
+        assert depth_of(pairs, lambda val: val[0][1] if val else None) == depth
+        assert valid is True
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(nested_array(depth + 1), max_depth=depth)
+
+    def test_loads_max_depth_object_pairs_hook(self):
+        """
+        loads() max_depth with object_pairs_hook
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(nested_object(3), max_depth=2, object_pairs_hook=list)
+
+    def test_loads_max_depth_hostile(self):
+        """
+        loads() hostile nesting
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(nested_array(1024 * 1024))
+
+    def test_loads_max_depth_invalid(self):
+        """
+        loads() max_depth invalid
+        """
+        for val in (-1, 1.5, "1", 2**32):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads("[]", max_depth=val)
+
+    def test_load_max_depth(self):
+        """
+        load() max_depth
+        """
+        assert orjson.load(io.BytesIO(b"[[]]"), max_depth=2) == [[]]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b"[[]]"), max_depth=1)
+
+    def test_load_max_depth_chunked(self):
+        """
+        load() max_depth with chunk_size
+        """
+        assert orjson.load(io.BytesIO(b"[[]]"), chunk_size=1, max_depth=2) == [[]]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b"[[]]"), chunk_size=1, max_depth=1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(nested_array(1025).encode()), chunk_size=64)
+
+    def test_load_max_depth_chunked_deep(self):
+        """
+        load() with chunk_size allows no more than 1024 nested containers,
+        whatever max_depth is, as it uses the stack for each
+        """
+        data = nested_array(1024).encode()
+        assert orjson.load(io.BytesIO(data), chunk_size=64, max_depth=2000)
+        data = nested_array(100000).encode()
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(data), chunk_size=4096, max_depth=200000)
+
+    def test_loads_max_depth_stack(self):
+        """
+        loads_compressed(), unpackb(), and loads_cbor() allow no more than
+        1024 nested containers, whatever max_depth is
+        """
+        depth = 100000
+        data = gzip.compress(nested_array(depth).encode())
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_compressed(data, max_depth=depth)
+        assert orjson.unpackb(b"\x91" * 1023 + b"\x90", max_depth=2000)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.unpackb(b"\x91" * depth + b"\x90", max_depth=depth)
+        assert orjson.loads_cbor(b"\x81" * 1023 + b"\x80", max_depth=2000)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_cbor(b"\x81" * depth + b"\x80", max_depth=depth)
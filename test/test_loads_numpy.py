@@ -0,0 +1,173 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+
+import pytest
+
+import orjson
+
+try:
+    import numpy
+except ImportError:
+    numpy = None  # type: ignore
+
+
+def assert_ndarray(obj, dtype, values):
+    assert isinstance(obj, numpy.ndarray)
+    assert obj.dtype == dtype
+    assert obj.shape == (len(values),)
+    assert obj.tolist() == values
+
+
+@pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+class TestLoadsNumpy:
+    def test_loads_numpy_int64(self):
+        """
+        loads() numpy=True array of integers
+        """
+        assert_ndarray(
+            orjson.loads("[1, -2, 9223372036854775807]", numpy=True),
+            numpy.int64,
+            [1, -2, 9223372036854775807],
+        )
+
+    def test_loads_numpy_uint64(self):
+        """
+        loads() numpy=True array of non-negative integers beyond int64
+        """
+        assert_ndarray(
+            orjson.loads("[0, 18446744073709551615]", numpy=True),
+            numpy.uint64,
+            [0, 18446744073709551615],
+        )
+
+    def test_loads_numpy_float64(self):
+        """
+        loads() numpy=True array with a float
+        """
+        assert_ndarray(
+            orjson.loads("[1.5, 2, -3e2, 9007199254740992]", numpy=True),
+            numpy.float64,
+            [1.5, 2.0, -300.0, 9007199254740992.0],
+        )
+
+    def test_loads_numpy_list(self):
+        """
+        loads() numpy=True arrays not of numbers that fit in one dtype
+        """
+        for doc, expected in (
+            ("[]", []),
+            ("[1, null]", [1, None]),
+            ('[1, "2"]', [1, "2"]),
+            ("[true, 1]", [True, 1]),
+            ("[-1, 18446744073709551615]", [-1, 18446744073709551615]),
+            ("[0.5, 9007199254740993]", [0.5, 9007199254740993]),
+            ("[1, 100000000000000000000]", [1, 100000000000000000000]),
+        ):
+            res = orjson.loads(doc, numpy=True)
+            assert type(res) is list
+            assert res == expected
+
+    def test_loads_numpy_nested(self):
+        """
+        loads() numpy=True nested arrays are each an ndarray
+        """
+        res = orjson.loads('{"a": [[1, 2], [0.5]], "b": [[1], 2]}', numpy=True)
+        assert_ndarray(res["a"][0], numpy.int64, [1, 2])
+        assert_ndarray(res["a"][1], numpy.float64, [0.5])
+        assert type(res["b"]) is list
+        assert_ndarray(res["b"][0], numpy.int64, [1])
+
+    def test_loads_numpy_writeable(self):
+        """
+        loads() numpy=True arrays are writeable unless immutable
+        """
+        res = orjson.loads("[1, 2]", numpy=True)
+        res[0] = 3
+        assert res.tolist() == [3, 2]
+        res = orjson.loads("[[1, 2]]", numpy=True, immutable=True)
+        assert type(res) is tuple
+        assert not res[0].flags.writeable
+
+    def test_loads_numpy_large(self):
+        """
+        loads() numpy=True long array, also parsed in threads
+        """
+        data = orjson.dumps([idx / 3 for idx in range(100000)])
+        expected = orjson.loads(data)
+        assert orjson.loads(data, numpy=True).tolist() == expected
+        assert orjson.loads(data, numpy=True, threads=4).tolist() == expected
+
+    def test_loads_numpy_arguments(self):
+        """
+        loads() numpy=True with other arguments and functions
+        """
+        assert_ndarray(
+            orjson.loads("[1, 2.5]", numpy=True, strict=True), numpy.float64, [1, 2.5]
+        )
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("[1, 100000000000000000000]", numpy=True, strict=True)
+        assert_ndarray(
+            orjson.loads("[NaN, 1]", numpy=True, allow_nan=True)[1:],
+            numpy.float64,
+            [1.0],
+        )
+        assert_ndarray(
+            orjson.load(io.BytesIO(b"[1, 2.5]"), numpy=True, chunk_size=2),
+            numpy.float64,
+            [1.0, 2.5],
+        )
+        assert_ndarray(
+            orjson.Decoder(numpy=True).decode(b"[1, 2]"), numpy.int64, [1, 2]
+        )
+        assert_ndarray(
+            orjson.loads_prefix(b"[1, 2] 3", numpy=True)[0], numpy.int64, [1, 2]
+        )
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("[1, 2]", numpy=True, max_items=1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("[1.5]", numpy=True, parse_float=str)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("[1]", numpy=True, parse_int=str)
+
+    def test_loads_numpy_roundtrip(self):
+        """
+        loads() numpy=True is the inverse of dumps() OPT_SERIALIZE_NUMPY
+        """
+        for arr in (
+            numpy.array([1, -2, 3], numpy.int64),
+            numpy.array([0.5, 1e300, -2.0], numpy.float64),
+        ):
+            data = orjson.dumps(arr, option=orjson.OPT_SERIALIZE_NUMPY)
+            res = orjson.loads(data, numpy=True)
+            assert res.dtype == arr.dtype
+            assert (res == arr).all()
+
+
+class TestLoadsNumpyArguments:
+    def test_loads_numpy_default(self):
+        """
+        loads() numpy=False by default
+        """
+        assert orjson.loads("[1, 2]") == [1, 2]
+        assert orjson.loads("[1, 2]", numpy=False) == [1, 2]
+
+    def test_loads_numpy_invalid(self):
+        """
+        loads() numpy must be a bool and is only of JSON functions
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("[1]", numpy=1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.unpackb(b"\x91\x01", numpy=True)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_cbor(b"\x81\x01", numpy=True)
+
+    @pytest.mark.skipif(numpy is not None, reason="numpy is installed")
+    def test_loads_numpy_not_installed(self):
+        """
+        loads() numpy=True without numpy installed
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads("[1]", numpy=True)
+        assert str(exc.value).startswith("numpy is not installed")
@@ -0,0 +1,143 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import array
+import io
+import sys
+
+import pytest
+
+import orjson
+
+FRAMES = b'\x00\x00\x00\x08{"a": 1}\x00\x00\x00\x03[2]'
+
+
+class TestLoadsOffset:
+    def test_offset_length(self):
+        """
+        loads() offset and length
+        """
+        assert orjson.loads(FRAMES, offset=4, length=8) == {"a": 1}
+        assert orjson.loads(FRAMES, offset=16, length=3) == [2]
+
+    def test_offset(self):
+        """
+        loads() offset without length is to the end of the input
+        """
+        assert orjson.loads(FRAMES, offset=16) == [2]
+        assert orjson.loads(b"[1]", offset=0) == [1]
+
+    def test_length(self):
+        """
+        loads() length without offset is from the start of the input
+        """
+        assert orjson.loads(b"[1]xyz", length=3) == [1]
+
+    def test_offset_length_none(self):
+        """
+        loads() offset and length of None are the default
+        """
+        assert orjson.loads(b"[1]", offset=None, length=None) == [1]
+
+    def test_offset_length_types(self):
+        """
+        loads() offset and length of bytes-like input
+        """
+        for val in (
+            FRAMES,
+            bytearray(FRAMES),
+            memoryview(FRAMES),
+            array.array("B", FRAMES),
+        ):
+            assert orjson.loads(val, offset=16, length=3) == [2]
+
+    def test_offset_length_utf8(self):
+        """
+        loads() offset and length only the range must be valid UTF-8
+        """
+        assert orjson.loads(b"\xff[1]\xff", offset=1, length=3) == [1]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(b"\xff[1]\xff", offset=1)
+
+    def test_offset_length_range(self):
+        """
+        loads() offset and length not within the input
+        """
+        for kwargs in (
+            {"offset": 20},
+            {"offset": 17, "length": 3},
+            {"length": 20},
+            {"offset": 1, "length": sys.maxsize},
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(FRAMES, **kwargs)
+
+    def test_offset_length_empty(self):
+        """
+        loads() offset and length of no bytes
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(b"[1]", offset=3)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(b"[1]", length=0)
+
+    def test_offset_length_partial(self):
+        """
+        loads() offset and length of part of a document
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(FRAMES, offset=4, length=7)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(FRAMES, offset=4, length=9)
+
+    def test_offset_length_error_pos(self):
+        """
+        loads() offset error position is within the range
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads(b"xx[1, x]", offset=2)
+        assert exc.value.pos == 4
+        assert exc.value.doc == "[1, x]"
+
+    def test_offset_length_str(self):
+        """
+        loads() offset and length of str
+        """
+        for kwargs in ({"offset": 0}, {"length": 3}):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads("[1]", **kwargs)
+
+    def test_offset_length_max_bytes(self):
+        """
+        loads() max_bytes applies to the range
+        """
+        assert orjson.loads(FRAMES, offset=16, length=3, max_bytes=3) == [2]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(FRAMES, offset=4, length=8, max_bytes=7)
+
+    def test_offset_length_invalid(self):
+        """
+        loads() offset and length invalid
+        """
+        for arg in ("offset", "length"):
+            for val in (-1, 1.5, "1", True):
+                with pytest.raises(orjson.JSONDecodeError):
+                    orjson.loads(b"[1]", **{arg: val})
+
+    def test_offset_length_refcount(self):
+        """
+        loads() offset and length release the buffer
+        """
+        buf = bytearray(FRAMES)
+        for _ in range(100):
+            orjson.loads(buf, offset=16, length=3)
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(buf, offset=4, length=7)
+        buf.extend(b" ")
+
+    def test_load_offset_length(self):
+        """
+        load() does not take offset and length
+        """
+        for arg in ("offset", "length"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.load(io.BytesIO(b"[1]"), **{arg: 0})
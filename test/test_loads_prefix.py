@@ -0,0 +1,130 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import datetime
+
+import pytest
+
+import orjson
+
+
+def concatenated(data):
+    """
+    Deserialize each document of data, concatenated with or without
+    whitespace between them.
+    """
+    docs = []
+    pos = 0
+    while data[pos:].strip():
+        obj, pos = orjson.loads_prefix(data, offset=pos)
+        docs.append(obj)
+    return docs
+
+
+class TestLoadsPrefix:
+    def test_loads_prefix(self):
+        """
+        loads_prefix() returns the value and the index at which it ends
+        """
+        assert orjson.loads_prefix(b'{"a": 1} {"b": 2}') == ({"a": 1}, 8)
+        assert orjson.loads_prefix(b"[1, [2]]") == ([1, [2]], 8)
+        assert orjson.loads_prefix(b"  true\n") == (True, 6)
+        assert orjson.loads_prefix(b'"a"b') == ("a", 3)
+        assert orjson.loads_prefix(b"null,") == (None, 4)
+
+    def test_loads_prefix_number(self):
+        """
+        loads_prefix() number ends at the first character not of it
+        """
+        assert orjson.loads_prefix(b"12 34") == (12, 2)
+        assert orjson.loads_prefix(b"-1.5e3]") == (-1500.0, 6)
+        assert orjson.loads_prefix(b"1{}") == (1, 1)
+
+    def test_loads_prefix_concatenated(self):
+        """
+        loads_prefix() documents concatenated with and without whitespace
+        """
+        assert concatenated(b'{"a": 1}{"b": 2}\n[3] "4" 5') == [
+            {"a": 1},
+            {"b": 2},
+            [3],
+            "4",
+            5,
+        ]
+
+    def test_loads_prefix_binary(self):
+        """
+        loads_prefix() value followed by data that is not UTF-8
+        """
+        data = b'{"a": "\xc3\xa4"}\x00\xff\xfe' + bytes(range(256))
+        for each in (data, bytearray(data), memoryview(data)):
+            assert orjson.loads_prefix(each) == ({"a": "ä"}, 11)
+        assert orjson.loads_prefix(b"12\xff") == (12, 2)
+
+    def test_loads_prefix_binary_invalid(self):
+        """
+        loads_prefix() value not ending before data that is not UTF-8
+        """
+        for data, pos in ((b'["\xff"]', 2), (b"[1\xff", 2), (b"\xff", 0)):
+            with pytest.raises(orjson.JSONDecodeError) as exc:
+                orjson.loads_prefix(data)
+            assert str(exc.value).startswith("str is not valid UTF-8")
+            assert exc.value.pos == pos
+
+    def test_loads_prefix_str(self):
+        """
+        loads_prefix() index of str is in characters
+        """
+        assert orjson.loads_prefix('["äö€😀"] 1') == (["äö€😀"], 8)
+        data = '"😀" "a"'
+        obj, pos = orjson.loads_prefix(data)
+        assert obj == "😀"
+        assert data[pos:] == ' "a"'
+
+    def test_loads_prefix_offset(self):
+        """
+        loads_prefix() index is from the start of the input, not offset
+        """
+        data = b'\x00\x00{"a": 1}[2]'
+        assert orjson.loads_prefix(data, offset=2) == ({"a": 1}, 10)
+        assert orjson.loads_prefix(data, offset=10) == ([2], 13)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_prefix(data, offset=2, length=4)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_prefix("1", offset=0)
+
+    def test_loads_prefix_arguments(self):
+        """
+        loads_prefix() arguments of loads()
+        """
+        assert orjson.loads_prefix(b'{"a": [1]} x', tuples=True) == ({"a": (1,)}, 10)
+        assert orjson.loads_prefix(b'"2020-01-01" x', datetimes=True) == (
+            datetime.date(2020, 1, 1),
+            12,
+        )
+        assert orjson.loads_prefix(b"[1 /* a */] x", jsonc=True) == ([1], 11)
+        assert orjson.loads_prefix(b"1.5 x", parse_float=str) == ("1.5", 3)
+        schema = orjson.Schema({"type": "integer"})
+        assert orjson.loads_prefix(b"1 x", schema=schema) == (1, 1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_prefix(b'"1" x', schema=schema)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_prefix(b"[[1]] x", max_depth=1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_prefix(b"1 2", max_bytes=2)
+
+    def test_loads_prefix_invalid(self):
+        """
+        loads_prefix() input not starting with a value
+        """
+        for data in (b"", b"   ", b"x", b"[1", b'{"a" 1}', b"]"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_prefix(data)
+        for args, kwargs in (
+            ((), {}),
+            ((b"1", b"1"), {}),
+            ((b"1",), {"threads": 2}),
+            ((b"1",), {"unknown": 1}),
+            ((1,), {}),
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads_prefix(*args, **kwargs)
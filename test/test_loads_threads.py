@@ -0,0 +1,139 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+
+import pytest
+
+import orjson
+
+from .util import errors_equal
+
+# long enough to be split into parts for several threads
+DOC = [
+    {"a": [idx, '"],}[{\\' * (idx % 3), None, 1.5], "b": {"c": [[], {}]}}
+    for idx in range(100000)
+]
+DATA = orjson.dumps(DOC)
+
+
+class TestLoadsThreads:
+    def test_threads(self):
+        """
+        loads() threads
+        """
+        assert len(DATA) > 4 * 1024 * 1024
+        for threads in (None, 1, 2, 3, 4, 16):
+            assert orjson.loads(DATA, threads=threads) == DOC
+
+    def test_threads_types(self):
+        """
+        loads() threads of each input type
+        """
+        for val in (DATA.decode("utf-8"), bytearray(DATA), memoryview(DATA)):
+            assert orjson.loads(val, threads=4) == DOC
+
+    def test_threads_whitespace(self):
+        """
+        loads() threads with whitespace between elements
+        """
+        data = b" \n[ " + DATA[1:-1].replace(b"},{", b"} ,\n\t{") + b" ]\r\n"
+        assert orjson.loads(data, threads=4) == DOC
+
+    def test_threads_nested_array(self):
+        """
+        loads() threads of an array of arrays
+        """
+        doc = [[idx, [str(idx)]] for idx in range(400000)]
+        assert orjson.loads(orjson.dumps(doc), threads=4) == doc
+
+    def test_threads_not_array(self):
+        """
+        loads() threads of a document that is not an array
+        """
+        doc = {"a": DOC}
+        assert orjson.loads(orjson.dumps(doc), threads=4) == doc
+        assert orjson.loads(b'"' + b"a" * (4 * 1024 * 1024) + b'"', threads=4)
+
+    def test_threads_small(self):
+        """
+        loads() threads of a short document
+        """
+        assert orjson.loads("[1, [2], {}]", threads=4) == [1, [2], {}]
+
+    def test_threads_invalid_json(self):
+        """
+        loads() threads invalid document has the same error
+        """
+        half = len(DATA) // 2
+        for data in (
+            DATA[:-1],
+            DATA + b"x",
+            DATA[:-1] + b"}",
+            DATA.replace(b"},{", b"},,{", 1),
+            DATA.replace(b"},{", b"}{", 1),
+            DATA[:half] + b"\x01" + DATA[half + 1 :],
+            DATA[:-1] + b",]",
+            b"[" + b" " * (4 * 1024 * 1024) + b",1]",
+            b"[1," + b" " * (4 * 1024 * 1024) + b"]",
+        ):
+            assert errors_equal(data, 4)
+
+    def test_threads_args(self):
+        """
+        loads() threads with arguments
+        """
+        for kwargs in (
+            {"tuples": True},
+            {"immutable": True},
+            {"parse_int": str},
+            {"parse_float": str},
+            {"object_hook": len},
+            {"object_pairs_hook": list},
+            {"max_depth": 5},
+        ):
+            assert orjson.loads(DATA, threads=4, **kwargs) == orjson.loads(
+                DATA, **kwargs
+            )
+
+    def test_threads_limits(self):
+        """
+        loads() threads with limits has the same error
+        """
+        for kwargs in (
+            {"max_depth": 0},
+            {"max_depth": 4},
+            {"max_items": 100},
+            {"max_items": len(DOC)},
+            {"max_string_len": 10},
+        ):
+            assert errors_equal(DATA, 4, **kwargs)
+
+    def test_threads_duplicate_keys(self):
+        """
+        loads() threads with duplicate keys
+        """
+        data = b"[" + b",".join([b'{"a": 1, "a": 2}'] * 300000) + b"]"
+        assert orjson.loads(data, threads=4) == [{"a": 2}] * 300000
+        assert errors_equal(data, 4, duplicate_keys="error")
+
+    def test_threads_jsonc(self):
+        """
+        loads() threads with jsonc
+        """
+        data = DATA[:-1] + b", /* ] */ // ,\n]"
+        assert orjson.loads(data, threads=4, jsonc=True) == DOC
+
+    def test_threads_invalid(self):
+        """
+        loads() threads invalid
+        """
+        for val in (0, -1, 1.5, "1", True):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads("[]", threads=val)
+
+    def test_load_threads(self):
+        """
+        load() threads
+        """
+        assert orjson.load(io.BytesIO(DATA), threads=4) == DOC
+        assert orjson.load(io.BytesIO(DATA), threads=4, chunk_size=65536) == DOC
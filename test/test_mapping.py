@@ -0,0 +1,219 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections.abc
+import types
+
+import pytest
+
+import orjson
+
+
+class Frozen(collections.abc.Mapping):
+    def __init__(self, *args, **kwargs):
+        self._data = dict(*args, **kwargs)
+
+    def __getitem__(self, key):
+        return self._data[key]
+
+    def __iter__(self):
+        return iter(self._data)
+
+    def __len__(self):
+        return len(self._data)
+
+
+class Computed(collections.abc.Mapping):
+    def __getitem__(self, key):
+        return [key] * 2
+
+    def __iter__(self):
+        return iter(("a", "b"))
+
+    def __len__(self):
+        return 2
+
+
+class Registered:
+    def keys(self):
+        return ["a"]
+
+    def __getitem__(self, key):
+        return 1
+
+
+collections.abc.Mapping.register(Registered)
+
+
+class Raises(collections.abc.Mapping):
+    def __getitem__(self, key):
+        raise KeyError(key)
+
+    def __iter__(self):
+        return iter(("a",))
+
+    def __len__(self):
+        return 1
+
+
+class TestMapping:
+    def test_mapping(self):
+        """
+        OPT_SERIALIZE_MAPPING
+        """
+        assert (
+            orjson.dumps(
+                Frozen(a=1, b=Frozen(c=[2])), option=orjson.OPT_SERIALIZE_MAPPING
+            )
+            == b'{"a":1,"b":{"c":[2]}}'
+        )
+
+    def test_mapping_unset(self):
+        """
+        dumps() Mapping without OPT_SERIALIZE_MAPPING raises
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Frozen(a=1))
+
+    def test_mapping_proxy(self):
+        """
+        OPT_SERIALIZE_MAPPING types.MappingProxyType
+        """
+        assert (
+            orjson.dumps(
+                types.MappingProxyType({"a": 1}), option=orjson.OPT_SERIALIZE_MAPPING
+            )
+            == b'{"a":1}'
+        )
+
+    def test_mapping_registered(self):
+        """
+        OPT_SERIALIZE_MAPPING type registered with Mapping
+        """
+        assert (
+            orjson.dumps(Registered(), option=orjson.OPT_SERIALIZE_MAPPING)
+            == b'{"a":1}'
+        )
+
+    def test_mapping_computed_values(self):
+        """
+        OPT_SERIALIZE_MAPPING values created by __getitem__
+        """
+        assert (
+            orjson.dumps([Computed(), Computed()], option=orjson.OPT_SERIALIZE_MAPPING)
+            == b'[{"a":["a","a"],"b":["b","b"]},{"a":["a","a"],"b":["b","b"]}]'
+        )
+
+    def test_mapping_empty(self):
+        """
+        OPT_SERIALIZE_MAPPING empty
+        """
+        assert orjson.dumps(Frozen(), option=orjson.OPT_SERIALIZE_MAPPING) == b"{}"
+        assert (
+            orjson.dumps(
+                Frozen(), option=orjson.OPT_SERIALIZE_MAPPING | orjson.OPT_INDENT_2
+            )
+            == b"{}"
+        )
+
+    def test_mapping_indent(self):
+        """
+        OPT_SERIALIZE_MAPPING with OPT_INDENT_2
+        """
+        assert (
+            orjson.dumps(
+                Frozen(a=1, b=[]),
+                option=orjson.OPT_SERIALIZE_MAPPING | orjson.OPT_INDENT_2,
+            )
+            == b'{\n  "a": 1,\n  "b": []\n}'
+        )
+
+    def test_mapping_sort_keys(self):
+        """
+        OPT_SERIALIZE_MAPPING with OPT_SORT_KEYS
+        """
+        assert (
+            orjson.dumps(
+                Frozen(b=1, a=2, c=3),
+                option=orjson.OPT_SERIALIZE_MAPPING | orjson.OPT_SORT_KEYS,
+            )
+            == b'{"a":2,"b":1,"c":3}'
+        )
+
+    def test_mapping_non_str_keys(self):
+        """
+        OPT_SERIALIZE_MAPPING keys that are not str
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Frozen({1: 2}), option=orjson.OPT_SERIALIZE_MAPPING)
+        assert (
+            orjson.dumps(
+                Frozen({1: 2, None: 3}),
+                option=orjson.OPT_SERIALIZE_MAPPING | orjson.OPT_NON_STR_KEYS,
+            )
+            == b'{"1":2,"null":3}'
+        )
+
+    def test_mapping_getitem_raises(self):
+        """
+        OPT_SERIALIZE_MAPPING __getitem__ raising propagates the exception
+        """
+        with pytest.raises(KeyError):
+            orjson.dumps(Raises(), option=orjson.OPT_SERIALIZE_MAPPING)
+
+    def test_mapping_keys_raises(self):
+        """
+        OPT_SERIALIZE_MAPPING keys() and items() raising propagate the
+        exception
+        """
+
+        class KeysRaises(Raises):
+            def keys(self):
+                raise ValueError("keys")
+
+            def items(self):
+                raise ValueError("items")
+
+            def getall(self, key):
+                return []
+
+        for option in (orjson.OPT_SERIALIZE_MAPPING, orjson.OPT_SERIALIZE_MULTIDICT):
+            with pytest.raises(ValueError):
+                orjson.dumps([KeysRaises()], option=option)
+
+    def test_mapping_dict_native(self):
+        """
+        OPT_SERIALIZE_MAPPING does not change dict
+        """
+        obj = {"a": [1, {"b": 2}]}
+        assert orjson.dumps(obj, option=orjson.OPT_SERIALIZE_MAPPING) == orjson.dumps(
+            obj
+        )
+
+    def test_mapping_redact_include_keys(self):
+        """
+        OPT_SERIALIZE_MAPPING with redact_keys and include_keys
+        """
+        assert (
+            orjson.dumps(
+                Frozen(a=Frozen(token=1, b=2), c=3),
+                option=orjson.OPT_SERIALIZE_MAPPING,
+                redact_keys={"token"},
+                include_keys={"a"},
+            )
+            == b'{"a":{"token":"[REDACTED]","b":2}}'
+        )
+
+    def test_mapping_max_depth(self):
+        """
+        OPT_SERIALIZE_MAPPING nesting counts toward max_depth
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(
+                Frozen(a=Frozen(b=1)), option=orjson.OPT_SERIALIZE_MAPPING, max_depth=1
+            )
+
+    def test_mapping_kwarg(self):
+        """
+        dumps() serialize_mapping=True is OPT_SERIALIZE_MAPPING
+        """
+        assert orjson.dumps(Frozen(a=1), serialize_mapping=True) == b'{"a":1}'
@@ -0,0 +1,94 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+
+import pytest
+
+import orjson
+
+
+def nested_list(depth):
+    obj = []
+    for _ in range(depth - 1):
+        obj = [obj]
+    return obj
+
+
+@dataclasses.dataclass
+class Node:
+    child: object
+
+
+class TestMaxDepth:
+    def test_max_depth_default(self):
+        """
+        dumps() allows 1024 nested containers by default
+        """
+        assert orjson.dumps(nested_list(1024)) == b"[" * 1024 + b"]" * 1024
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(nested_list(1025))
+
+    def test_max_depth_deep(self):
+        """
+        dumps() nesting beyond the stack with max_depth
+        """
+        depth = 200000
+        assert (
+            orjson.dumps(nested_list(depth), max_depth=depth)
+            == b"[" * depth + b"]" * depth
+        )
+
+    def test_max_depth_limit(self):
+        """
+        dumps() max_depth is the number of nested containers
+        """
+        assert orjson.dumps([[[]]], max_depth=3) == b"[[[]]]"
+        for obj in ([[[]]], [[{}]], [[()]], [[Node(1)]], [{"a": [1]}]):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps(obj, max_depth=2)
+        assert orjson.dumps(1, max_depth=0) == b"1"
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps([], max_depth=0)
+
+    def test_max_depth_mixed(self):
+        """
+        dumps() deeply nested dict, list, tuple, and dataclass
+        """
+        obj = None
+        ref = b"null"
+        for idx in range(5000):
+            kind = idx % 4
+            if kind == 0:
+                obj = [1, obj]
+                ref = b"[1," + ref + b"]"
+            elif kind == 1:
+                obj = {"a": obj, "b": 2}
+                ref = b'{"a":' + ref + b',"b":2}'
+            elif kind == 2:
+                obj = (obj,)
+                ref = b"[" + ref + b"]"
+            else:
+                obj = Node(obj)
+                ref = b'{"child":' + ref + b"}"
+        for option in (None, orjson.OPT_SORT_KEYS, orjson.OPT_NON_STR_KEYS):
+            assert orjson.dumps(obj, option=option, max_depth=5000) == ref
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(obj, max_depth=4999)
+
+    def test_max_depth_circular(self):
+        """
+        dumps() circular reference raises at max_depth
+        """
+        obj = {}
+        obj["a"] = [obj]
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(obj, max_depth=100000)
+
+    def test_max_depth_invalid(self):
+        """
+        dumps() max_depth must be a non-negative int or None
+        """
+        assert orjson.dumps([], max_depth=None) == b"[]"
+        for val in (-1, 1.0, "1", 2**32):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps([], max_depth=val)
@@ -0,0 +1,86 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import pytest
+
+import orjson
+
+from .util import read_fixture_obj
+
+
+class TestMaxSize:
+    def test_max_size_under(self):
+        """
+        dumps() max_size not exceeded
+        """
+        assert orjson.dumps([1, 2, 3], max_size=7) == b"[1,2,3]"
+
+    def test_max_size_exceeded(self):
+        """
+        dumps() max_size exceeded raises JSONEncodeSizeError
+        """
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps([1, 2, 3], max_size=6)
+
+    def test_max_size_zero(self):
+        """
+        dumps() max_size=0
+        """
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps(1, max_size=0)
+
+    def test_max_size_none(self):
+        """
+        dumps() max_size=None is unlimited
+        """
+        assert orjson.dumps("a" * 4096, max_size=None) == b'"' + b"a" * 4096 + b'"'
+
+    def test_max_size_subclass(self):
+        """
+        dumps() JSONEncodeSizeError is a subclass of JSONEncodeError
+        """
+        assert issubclass(orjson.JSONEncodeSizeError, orjson.JSONEncodeError)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps({"a": "b" * 2048}, max_size=1024)
+
+    def test_max_size_grow(self):
+        """
+        dumps() max_size larger than the initial buffer
+        """
+        val = ["a" * 1000 for _ in range(10)]
+        assert orjson.dumps(val, max_size=20000) == orjson.dumps(val)
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps(val, max_size=5000)
+
+    def test_max_size_append_newline(self):
+        """
+        dumps() max_size includes OPT_APPEND_NEWLINE
+        """
+        assert orjson.dumps([], option=orjson.OPT_APPEND_NEWLINE, max_size=3) == b"[]\n"
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps([], option=orjson.OPT_APPEND_NEWLINE, max_size=2)
+
+    def test_max_size_indent(self):
+        """
+        dumps() max_size with OPT_INDENT_2
+        """
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps({"a": [1, 2]}, option=orjson.OPT_INDENT_2, max_size=10)
+
+    def test_max_size_twitter(self):
+        """
+        dumps() max_size twitter.json
+        """
+        val = read_fixture_obj("twitter.json.xz")
+        size = len(orjson.dumps(val))
+        assert len(orjson.dumps(val, max_size=size)) == size
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps(val, max_size=size - 1)
+
+    def test_max_size_invalid(self):
+        """
+        dumps() max_size invalid
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps([], max_size=-1)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps([], max_size="1")
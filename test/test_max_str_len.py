@@ -0,0 +1,104 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import enum
+
+import pytest
+
+import orjson
+
+
+class StrEnum(str, enum.Enum):
+    AAA = "aaaaa"
+
+
+class StrSubclass(str):
+    pass
+
+
+class TestMaxStrLen:
+    def test_max_str_len(self):
+        """
+        dumps() max_str_len
+        """
+        assert orjson.dumps("abcdefghij", max_str_len=3) == b'"abc..."'
+        assert orjson.dumps("abc", max_str_len=3) == b'"abc"'
+        assert orjson.dumps("ab", max_str_len=3) == b'"ab"'
+        assert orjson.dumps("", max_str_len=0) == b'""'
+        assert orjson.dumps("a", max_str_len=0) == b'"..."'
+
+    def test_max_str_len_code_points(self):
+        """
+        dumps() max_str_len counts code points of non-ASCII strings
+        """
+        for val in ("éééé", "ключключ", "漢字漢字", "🐈🐈🐈🐈"):
+            assert orjson.dumps(val, max_str_len=2) == (
+                '"' + val[:2] + '..."'
+            ).encode("utf-8")
+            assert orjson.dumps(val, max_str_len=len(val)) == orjson.dumps(val)
+
+    def test_max_str_len_escape(self):
+        """
+        dumps() max_str_len escapes the truncated string
+        """
+        assert orjson.dumps('a"\nbcd', max_str_len=3) == b'"a\\"\\n..."'
+        assert orjson.dumps('é"\nbcd', max_str_len=3) == '"é\\"\\n..."'.encode(
+            "utf-8"
+        )
+
+    def test_max_str_len_keys(self):
+        """
+        dumps() max_str_len does not truncate keys
+        """
+        assert (
+            orjson.dumps({"abcdef": "abcdef"}, max_str_len=2)
+            == b'{"abcdef":"ab..."}'
+        )
+
+    def test_max_str_len_nested(self):
+        """
+        dumps() max_str_len in containers
+        """
+        assert (
+            orjson.dumps(["abcd", {"a": ("abcd", 1)}], max_str_len=2)
+            == b'["ab...",{"a":["ab...",1]}]'
+        )
+
+    def test_max_str_len_subclass(self):
+        """
+        dumps() max_str_len str subclass and enum
+        """
+        assert (
+            orjson.dumps(
+                [StrSubclass("abcdé"), StrEnum.AAA],
+                option=orjson.OPT_PASSTHROUGH_SUBCLASS,
+                default=str,
+                max_str_len=2,
+            )
+            == b'["ab...","aa..."]'
+        )
+        assert orjson.dumps(StrSubclass("éabcd"), max_str_len=2) == (
+            '"éa..."'.encode("utf-8")
+        )
+
+    def test_max_str_len_js(self):
+        """
+        dumps() max_str_len with OPT_JS
+        """
+        assert (
+            orjson.dumps("a\u2028b\u2029c", max_str_len=4, option=orjson.OPT_JS)
+            == b'"a\\u2028b\\u2029..."'
+        )
+
+    def test_max_str_len_none(self):
+        """
+        dumps() max_str_len=None
+        """
+        assert orjson.dumps("abcdef", max_str_len=None) == b'"abcdef"'
+
+    def test_max_str_len_invalid(self):
+        """
+        dumps() max_str_len invalid
+        """
+        for val in (-1, 1.5, "1", True):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps("a", max_str_len=val)
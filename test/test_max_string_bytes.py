@@ -0,0 +1,91 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import sys
+
+import pytest
+
+import orjson
+
+
+class TestMaxStringBytes:
+    def test_max_string_bytes(self):
+        """
+        loads() max_string_bytes is the total of the strings of a document
+        """
+        doc = '["ab", "cd", {"ef": "g"}]'
+        assert orjson.loads(doc, max_string_bytes=7) == ["ab", "cd", {"ef": "g"}]
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads(doc, max_string_bytes=6)
+        assert "max_string_bytes" in str(exc.value)
+
+    def test_max_string_bytes_keys(self):
+        """
+        loads() max_string_bytes counts each key, including repeated keys
+        """
+        doc = '[{"ab": 1}, {"ab": 2}]'
+        assert orjson.loads(doc, max_string_bytes=4) == [{"ab": 1}, {"ab": 2}]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(doc, max_string_bytes=3)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(doc, max_string_bytes=3, object_pairs_hook=list)
+
+    def test_max_string_bytes_decoded(self):
+        """
+        loads() max_string_bytes counts UTF-8 once escapes are decoded
+        """
+        assert orjson.loads('"\\u00e9\\n"', max_string_bytes=3) == "é\n"
+        assert orjson.loads('"\U0001f408"', max_string_bytes=4) == "\U0001f408"
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('"\U0001f408"', max_string_bytes=3)
+
+    def test_max_string_bytes_zero(self):
+        """
+        loads() max_string_bytes=0 allows only empty strings and numbers
+        """
+        assert orjson.loads('["", {"": 1.5}]', max_string_bytes=0) == ["", {"": 1.5}]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('"a"', max_string_bytes=0)
+
+    def test_max_string_bytes_per_call(self):
+        """
+        Decoder max_string_bytes applies to each call separately
+        """
+        decoder = orjson.Decoder(max_string_bytes=3)
+        for _ in range(3):
+            assert decoder.decode(b'["abc"]') == ["abc"]
+        parser = orjson.Parser(max_string_bytes=3)
+        assert parser.feed(b'"abc" "def" ') == ["abc", "def"]
+
+    def test_max_string_bytes_load(self):
+        """
+        load() max_string_bytes, with and without chunk_size
+        """
+        for chunk_size in (None, 2):
+            fileh = io.BytesIO(b'["ab", "c"]')
+            assert orjson.load(fileh, chunk_size=chunk_size, max_string_bytes=3)
+            fileh = io.BytesIO(b'["ab", "cd"]')
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.load(fileh, chunk_size=chunk_size, max_string_bytes=3)
+
+    def test_max_string_bytes_is_valid(self):
+        """
+        is_valid() and unpackb() max_string_bytes
+        """
+        assert orjson.is_valid(b'{"a": "bc"}', max_string_bytes=3)
+        assert not orjson.is_valid(b'{"a": "bc"}', max_string_bytes=2)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.unpackb(b"\x92\xa2ab\xa1c", max_string_bytes=2)
+
+    def test_max_string_bytes_refcount(self):
+        """
+        loads() max_string_bytes exceeded does not leak
+        """
+        val = "a" * 64
+        doc = orjson.dumps([{"a": [val, val]}, [val, {"b": val}]])
+        before = sys.getrefcount(val)
+        for _ in range(100):
+            for max_string_bytes in range(0, 256, 16):
+                with pytest.raises(orjson.JSONDecodeError):
+                    orjson.loads(doc, max_string_bytes=max_string_bytes)
+        assert sys.getrefcount(val) == before
@@ -0,0 +1,158 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections.abc
+
+import pytest
+
+import orjson
+
+try:
+    import multidict
+except ImportError:
+    multidict = None  # type: ignore
+
+
+class MultiDict(collections.abc.Mapping):
+    def __init__(self, items=()):
+        self._items = list(items)
+
+    def __getitem__(self, key):
+        for k, v in self._items:
+            if k == key:
+                return v
+        raise KeyError(key)
+
+    def __iter__(self):
+        return iter([k for k, _ in self._items])
+
+    def __len__(self):
+        return len(self._items)
+
+    def items(self):
+        return list(self._items)
+
+    def getall(self, key):
+        return [v for k, v in self._items if k == key]
+
+
+class BadItems(MultiDict):
+    def items(self):
+        return [("a", 1, 2)]
+
+
+class TestMultiDict:
+    def test_multidict(self):
+        """
+        OPT_SERIALIZE_MULTIDICT repeats keys in order
+        """
+        assert (
+            orjson.dumps(
+                MultiDict([("a", 1), ("b", [2]), ("a", MultiDict([("c", 3)]))]),
+                option=orjson.OPT_SERIALIZE_MULTIDICT,
+            )
+            == b'{"a":1,"b":[2],"a":{"c":3}}'
+        )
+
+    def test_multidict_unset(self):
+        """
+        dumps() multidict without an option raises
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(MultiDict([("a", 1)]))
+
+    def test_multidict_mapping(self):
+        """
+        OPT_SERIALIZE_MAPPING writes the first value of each key of a multidict
+        """
+        assert (
+            orjson.dumps(
+                MultiDict([("a", 1), ("b", 2), ("a", 3)]),
+                option=orjson.OPT_SERIALIZE_MAPPING,
+            )
+            == b'{"a":1,"b":2}'
+        )
+        assert (
+            orjson.dumps(
+                MultiDict([("a", 1), ("b", 2), ("a", 3)]),
+                option=orjson.OPT_SERIALIZE_MAPPING | orjson.OPT_SERIALIZE_MULTIDICT,
+            )
+            == b'{"a":1,"b":2,"a":3}'
+        )
+
+    def test_multidict_sort_keys(self):
+        """
+        OPT_SERIALIZE_MULTIDICT with OPT_SORT_KEYS keeps the order of values
+        """
+        assert (
+            orjson.dumps(
+                MultiDict([("b", 1), ("a", 2), ("b", 3), ("a", 4)]),
+                option=orjson.OPT_SERIALIZE_MULTIDICT | orjson.OPT_SORT_KEYS,
+            )
+            == b'{"a":2,"a":4,"b":1,"b":3}'
+        )
+
+    def test_multidict_empty(self):
+        """
+        OPT_SERIALIZE_MULTIDICT empty
+        """
+        assert (
+            orjson.dumps(MultiDict(), option=orjson.OPT_SERIALIZE_MULTIDICT) == b"{}"
+        )
+
+    def test_multidict_redact_include_keys(self):
+        """
+        OPT_SERIALIZE_MULTIDICT with redact_keys and include_keys
+        """
+        assert (
+            orjson.dumps(
+                MultiDict([("a", 1), ("cookie", 2), ("b", 3), ("cookie", 4)]),
+                option=orjson.OPT_SERIALIZE_MULTIDICT,
+                redact_keys={"cookie"},
+                include_keys={"cookie", "b"},
+            )
+            == b'{"cookie":"[REDACTED]","b":3,"cookie":"[REDACTED]"}'
+        )
+
+    def test_multidict_non_str_keys(self):
+        """
+        OPT_SERIALIZE_MULTIDICT keys that are not str
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(MultiDict([(1, 2)]), option=orjson.OPT_SERIALIZE_MULTIDICT)
+        assert (
+            orjson.dumps(
+                MultiDict([(1, 2), (1, 3)]),
+                option=orjson.OPT_SERIALIZE_MULTIDICT | orjson.OPT_NON_STR_KEYS,
+            )
+            == b'{"1":2,"1":3}'
+        )
+
+    def test_multidict_bad_items(self):
+        """
+        OPT_SERIALIZE_MULTIDICT items() not of pairs
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(BadItems([("a", 1)]), option=orjson.OPT_SERIALIZE_MULTIDICT)
+
+    def test_multidict_kwarg(self):
+        """
+        dumps() serialize_multidict=True is OPT_SERIALIZE_MULTIDICT
+        """
+        assert (
+            orjson.dumps(MultiDict([("a", 1), ("a", 2)]), serialize_multidict=True)
+            == b'{"a":1,"a":2}'
+        )
+
+    @pytest.mark.skipif(multidict is None, reason="multidict is not installed")
+    def test_multidict_library(self):
+        """
+        OPT_SERIALIZE_MULTIDICT multidict.MultiDict and CIMultiDict
+        """
+        for cls in (multidict.MultiDict, multidict.CIMultiDict):
+            assert (
+                orjson.dumps(
+                    cls([("a", "1"), ("b", "2"), ("a", "3")]),
+                    option=orjson.OPT_SERIALIZE_MULTIDICT,
+                )
+                == b'{"a":"1","b":"2","a":"3"}'
+            )
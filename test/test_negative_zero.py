@@ -0,0 +1,98 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import pytest
+
+import orjson
+
+try:
+    import numpy
+except ImportError:
+    numpy = None  # type: ignore
+
+
+class TestNegativeZero:
+    def test_negative_zero_default(self):
+        """
+        dumps() -0.0 is unchanged without OPT_NORMALIZE_NEGATIVE_ZERO
+        """
+        assert orjson.dumps(-0.0) == b"-0.0"
+
+    def test_normalize_negative_zero(self):
+        """
+        OPT_NORMALIZE_NEGATIVE_ZERO
+        """
+        assert (
+            orjson.dumps(
+                [-0.0, 0.0, -1.0, 1e-320, -1e-320],
+                option=orjson.OPT_NORMALIZE_NEGATIVE_ZERO,
+            )
+            == b"[0.0,0.0,-1.0,1e-320,-1e-320]"
+        )
+
+    def test_normalize_negative_zero_int(self):
+        """
+        OPT_NORMALIZE_NEGATIVE_ZERO int
+        """
+        assert (
+            orjson.dumps([-0, 0], option=orjson.OPT_NORMALIZE_NEGATIVE_ZERO)
+            == b"[0,0]"
+        )
+
+    def test_normalize_negative_zero_float_precision(self):
+        """
+        OPT_NORMALIZE_NEGATIVE_ZERO with a value rounded to negative zero
+        """
+        assert orjson.dumps(-0.001, float_precision=2) == b"-0.0"
+        assert (
+            orjson.dumps(
+                [-0.001, -0.0],
+                option=orjson.OPT_NORMALIZE_NEGATIVE_ZERO,
+                float_precision=2,
+            )
+            == b"[0.0,0.0]"
+        )
+
+    def test_normalize_negative_zero_key(self):
+        """
+        OPT_NORMALIZE_NEGATIVE_ZERO float key
+        """
+        assert (
+            orjson.dumps(
+                {-0.0: 1},
+                option=orjson.OPT_NORMALIZE_NEGATIVE_ZERO | orjson.OPT_NON_STR_KEYS,
+            )
+            == b'{"0.0":1}'
+        )
+
+    def test_normalize_negative_zero_combined(self):
+        """
+        OPT_NORMALIZE_NEGATIVE_ZERO with OPT_NORMALIZE_FLOAT and
+        OPT_NUMBERS_AS_STRINGS
+        """
+        assert (
+            orjson.dumps(
+                -0.0,
+                option=orjson.OPT_NORMALIZE_NEGATIVE_ZERO
+                | orjson.OPT_NORMALIZE_FLOAT
+                | orjson.OPT_NUMBERS_AS_STRINGS,
+            )
+            == b'"0.0"'
+        )
+
+    def test_normalize_negative_zero_kwarg(self):
+        """
+        dumps() normalize_negative_zero=True is OPT_NORMALIZE_NEGATIVE_ZERO
+        """
+        assert orjson.dumps(-0.0, normalize_negative_zero=True) == b"0.0"
+
+    @pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+    def test_normalize_negative_zero_numpy(self):
+        """
+        OPT_NORMALIZE_NEGATIVE_ZERO numpy float64 and float32
+        """
+        opt = orjson.OPT_NORMALIZE_NEGATIVE_ZERO | orjson.OPT_SERIALIZE_NUMPY
+        assert orjson.dumps(numpy.float32(-0.0), option=opt) == b"0.0"
+        assert (
+            orjson.dumps(numpy.array([-0.0, 1.0], numpy.float64), option=opt)
+            == b"[0.0,1.0]"
+        )
@@ -0,0 +1,146 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import random
+
+import pytest
+
+import orjson
+
+try:
+    import numpy
+except ImportError:
+    numpy = None  # type: ignore
+
+
+def js_number(val):
+    """
+    Number.prototype.toString() with a ".0" fraction on integral values
+    """
+    if val == 0:
+        return "-0.0" if str(val).startswith("-") else "0.0"
+    sign = "-" if val < 0 else ""
+    mantissa, _, exponent = repr(abs(val)).partition("e")
+    integral, _, fraction = mantissa.partition(".")
+    if integral.strip("0"):
+        point = len(integral.lstrip("0"))
+    else:
+        point = len(fraction.lstrip("0")) - len(fraction)
+    digits = (integral + fraction).strip("0")
+    n = point + int(exponent or 0)
+    k = len(digits)
+    if k <= n <= 21:
+        out = digits + "0" * (n - k) + ".0"
+    elif 0 < n <= 21:
+        out = digits[:n] + "." + digits[n:]
+    elif -6 < n <= 0:
+        out = "0." + "0" * -n + digits
+    else:
+        out = digits[0] + ("." + digits[1:] if k > 1 else "")
+        out += "e" + ("+" if n > 0 else "-") + str(abs(n - 1))
+    return sign + out
+
+
+class TestNormalizeFloat:
+    @pytest.mark.parametrize(
+        "val,expected",
+        [
+            (0.0, b"0.0"),
+            (-0.0, b"-0.0"),
+            (1.0, b"1.0"),
+            (-1.5, b"-1.5"),
+            (123.456, b"123.456"),
+            (1e16, b"10000000000000000.0"),
+            (1.2345e20, b"123450000000000000000.0"),
+            (1e21, b"1e+21"),
+            (1.5e21, b"1.5e+21"),
+            (1.7976931348623157e308, b"1.7976931348623157e+308"),
+            (0.1, b"0.1"),
+            (0.0001, b"0.0001"),
+            (0.000001, b"0.000001"),
+            (0.0000015, b"0.0000015"),
+            (1e-7, b"1e-7"),
+            (-1.5e-7, b"-1.5e-7"),
+            (5e-324, b"5e-324"),
+            (12345678.9, b"12345678.9"),
+        ],
+    )
+    def test_normalize_float(self, val, expected):
+        """
+        OPT_NORMALIZE_FLOAT
+        """
+        assert orjson.dumps(val, option=orjson.OPT_NORMALIZE_FLOAT) == expected
+        assert float(expected) == val
+
+    def test_normalize_float_roundtrip(self):
+        """
+        OPT_NORMALIZE_FLOAT round-trips and matches the reference notation
+        """
+        rng = random.Random(0)
+        for _ in range(10000):
+            val = rng.uniform(-1, 1) * 10 ** rng.randint(-30, 30)
+            out = orjson.dumps(val, option=orjson.OPT_NORMALIZE_FLOAT)
+            assert orjson.loads(out) == val
+            assert out.decode("ascii") == js_number(val)
+
+    def test_normalize_float_nonfinite(self):
+        """
+        OPT_NORMALIZE_FLOAT NaN and infinity are null
+        """
+        assert (
+            orjson.dumps(
+                [float("nan"), float("inf")], option=orjson.OPT_NORMALIZE_FLOAT
+            )
+            == b"[null,null]"
+        )
+
+    def test_normalize_float_int(self):
+        """
+        OPT_NORMALIZE_FLOAT does not affect int
+        """
+        assert (
+            orjson.dumps([1, 10**18], option=orjson.OPT_NORMALIZE_FLOAT)
+            == b"[1,1000000000000000000]"
+        )
+
+    def test_normalize_float_precision(self):
+        """
+        OPT_NORMALIZE_FLOAT with float_precision
+        """
+        assert (
+            orjson.dumps(
+                [1.23456e-7, 3.14159], option=orjson.OPT_NORMALIZE_FLOAT, float_precision=9
+            )
+            == b"[1.23e-7,3.14159]"
+        )
+
+    def test_normalize_float_numbers_as_strings(self):
+        """
+        OPT_NORMALIZE_FLOAT | OPT_NUMBERS_AS_STRINGS
+        """
+        assert (
+            orjson.dumps(
+                1e21,
+                option=orjson.OPT_NORMALIZE_FLOAT | orjson.OPT_NUMBERS_AS_STRINGS,
+            )
+            == b'"1e+21"'
+        )
+
+    def test_normalize_float_kwarg(self):
+        """
+        dumps() normalize_float=True is OPT_NORMALIZE_FLOAT
+        """
+        assert orjson.dumps(1e-7, normalize_float=True) == b"1e-7"
+        assert orjson.dumps(1e16, normalize_float=True) == b"10000000000000000.0"
+
+    @pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+    def test_normalize_float_numpy(self):
+        """
+        OPT_NORMALIZE_FLOAT numpy float64 and float32
+        """
+        opt = orjson.OPT_NORMALIZE_FLOAT | orjson.OPT_SERIALIZE_NUMPY
+        assert orjson.dumps(numpy.float64(1e21), option=opt) == b"1e+21"
+        assert orjson.dumps(numpy.float32(1e-7), option=opt) == b"1e-7"
+        assert (
+            orjson.dumps(numpy.array([1e16, 0.5], numpy.float64), option=opt)
+            == b"[10000000000000000.0,0.5]"
+        )
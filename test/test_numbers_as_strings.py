@@ -0,0 +1,174 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import enum
+
+import pytest
+
+import orjson
+
+try:
+    import numpy
+except ImportError:
+    numpy = None  # type: ignore
+
+
+class IntEnum(enum.IntEnum):
+    ONE = 1
+
+
+@dataclasses.dataclass
+class Dataclass:
+    a: int
+    b: float
+
+
+class TestNumbersAsStrings:
+    def test_numbers_as_strings_int(self):
+        """
+        OPT_NUMBERS_AS_STRINGS int
+        """
+        for val in (0, 1, -1, 9223372036854775807, 18446744073709551615):
+            assert orjson.dumps(val, option=orjson.OPT_NUMBERS_AS_STRINGS) == (
+                b'"' + str(val).encode("ascii") + b'"'
+            )
+
+    def test_numbers_as_strings_int_64bit(self):
+        """
+        OPT_NUMBERS_AS_STRINGS int out of range still raises
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(18446744073709551616, option=orjson.OPT_NUMBERS_AS_STRINGS)
+
+    def test_numbers_as_strings_strict_integer(self):
+        """
+        OPT_NUMBERS_AS_STRINGS | OPT_STRICT_INTEGER
+        """
+        opt = orjson.OPT_NUMBERS_AS_STRINGS | orjson.OPT_STRICT_INTEGER
+        assert orjson.dumps(9007199254740991, option=opt) == b'"9007199254740991"'
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(9007199254740992, option=opt)
+
+    def test_numbers_as_strings_float(self):
+        """
+        OPT_NUMBERS_AS_STRINGS float
+        """
+        assert (
+            orjson.dumps([1.5, -0.0, 1e100], option=orjson.OPT_NUMBERS_AS_STRINGS)
+            == b'["1.5","-0.0","1e100"]'
+        )
+
+    def test_numbers_as_strings_float_nonfinite(self):
+        """
+        OPT_NUMBERS_AS_STRINGS float NaN and infinity are null
+        """
+        assert (
+            orjson.dumps(
+                [float("nan"), float("inf"), float("-inf")],
+                option=orjson.OPT_NUMBERS_AS_STRINGS,
+            )
+            == b"[null,null,null]"
+        )
+
+    def test_numbers_as_strings_float_precision(self):
+        """
+        OPT_NUMBERS_AS_STRINGS with float_precision
+        """
+        assert (
+            orjson.dumps(
+                3.14159, option=orjson.OPT_NUMBERS_AS_STRINGS, float_precision=2
+            )
+            == b'"3.14"'
+        )
+
+    def test_numbers_as_strings_bool_none(self):
+        """
+        OPT_NUMBERS_AS_STRINGS does not affect bool or None
+        """
+        assert (
+            orjson.dumps([True, False, None], option=orjson.OPT_NUMBERS_AS_STRINGS)
+            == b"[true,false,null]"
+        )
+
+    def test_numbers_as_strings_nested(self):
+        """
+        OPT_NUMBERS_AS_STRINGS in containers and keys
+        """
+        assert (
+            orjson.dumps(
+                {"a": [1, {"b": 2.5}], 3: (4,)},
+                option=orjson.OPT_NUMBERS_AS_STRINGS | orjson.OPT_NON_STR_KEYS,
+            )
+            == b'{"a":["1",{"b":"2.5"}],"3":["4"]}'
+        )
+
+    def test_numbers_as_strings_indent(self):
+        """
+        OPT_NUMBERS_AS_STRINGS | OPT_INDENT_2
+        """
+        assert (
+            orjson.dumps(
+                {"a": 1}, option=orjson.OPT_NUMBERS_AS_STRINGS | orjson.OPT_INDENT_2
+            )
+            == b'{\n  "a": "1"\n}'
+        )
+
+    def test_numbers_as_strings_subclass(self):
+        """
+        OPT_NUMBERS_AS_STRINGS int subclass and enum
+        """
+        assert (
+            orjson.dumps([IntEnum.ONE], option=orjson.OPT_NUMBERS_AS_STRINGS)
+            == b'["1"]'
+        )
+
+    def test_numbers_as_strings_default(self):
+        """
+        OPT_NUMBERS_AS_STRINGS applies to numbers returned by default
+        """
+        assert (
+            orjson.dumps(
+                1j, default=lambda obj: obj.imag, option=orjson.OPT_NUMBERS_AS_STRINGS
+            )
+            == b'"1.0"'
+        )
+
+    def test_numbers_as_strings_compile(self):
+        """
+        compile() with OPT_NUMBERS_AS_STRINGS
+        """
+        ser = orjson.compile(Dataclass, option=orjson.OPT_NUMBERS_AS_STRINGS)
+        assert ser(Dataclass(1, 2.0)) == b'{"a":"1","b":"2.0"}'
+
+    def test_numbers_as_strings_kwarg(self):
+        """
+        dumps() numbers_as_strings=True is OPT_NUMBERS_AS_STRINGS
+        """
+        assert orjson.dumps([1, 2.5], numbers_as_strings=True) == b'["1","2.5"]'
+
+    def test_numbers_as_strings_max_size(self):
+        """
+        OPT_NUMBERS_AS_STRINGS counts the quotes toward max_size
+        """
+        assert (
+            orjson.dumps(1, option=orjson.OPT_NUMBERS_AS_STRINGS, max_size=3) == b'"1"'
+        )
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps(1, option=orjson.OPT_NUMBERS_AS_STRINGS, max_size=2)
+
+    @pytest.mark.skipif(numpy is None, reason="numpy is not installed")
+    def test_numbers_as_strings_numpy(self):
+        """
+        OPT_NUMBERS_AS_STRINGS numpy scalars and arrays
+        """
+        opt = orjson.OPT_NUMBERS_AS_STRINGS | orjson.OPT_SERIALIZE_NUMPY
+        assert orjson.dumps(numpy.int64(3), option=opt) == b'"3"'
+        assert orjson.dumps(numpy.float32(0.5), option=opt) == b'"0.5"'
+        assert (
+            orjson.dumps(numpy.array([[1, 2]], numpy.int32), option=opt)
+            == b'[["1","2"]]'
+        )
+        assert (
+            orjson.dumps(numpy.array([1.5], numpy.float64), option=opt) == b'["1.5"]'
+        )
+        assert orjson.dumps(numpy.array([True]), option=opt) == b"[true]"
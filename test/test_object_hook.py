@@ -0,0 +1,164 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import decimal
+import io
+import sys
+
+import pytest
+
+import orjson
+
+from .util import Custom, raise_custom
+
+
+@dataclasses.dataclass
+class Point:
+    x: int
+    y: int
+
+
+class TestObjectHook:
+    def test_object_hook(self):
+        """
+        loads() object_hook return value is used
+        """
+        assert orjson.loads(
+            '[{"x": 1, "y": 2}, {"x": 3, "y": 4}]',
+            object_hook=lambda obj: Point(**obj),
+        ) == [Point(1, 2), Point(3, 4)]
+
+    def test_object_hook_order(self):
+        """
+        loads() object_hook is called innermost first
+        """
+        seen = []
+
+        def object_hook(obj):
+            seen.append(obj)
+            return len(seen)
+
+        assert orjson.loads(
+            '{"a": {"b": {}}, "c": {"d": 1}}', object_hook=object_hook
+        ) == 4
+        assert seen == [{}, {"b": 1}, {"d": 1}, {"a": 2, "c": 3}]
+
+    def test_object_hook_empty(self):
+        """
+        loads() object_hook is called with an empty dict
+        """
+        assert orjson.loads("[{}]", object_hook=lambda obj: "empty") == ["empty"]
+
+    def test_object_hook_empty_document(self):
+        """
+        loads() object_hook is called with an empty document object
+        """
+        assert orjson.loads("{}", object_hook=lambda obj: "empty") == "empty"
+        assert orjson.loads(b"{}", object_pairs_hook=lambda obj: "empty") == "empty"
+
+    def test_object_hook_dict(self):
+        """
+        loads() object_hook is called with a dict
+        """
+        seen = []
+        orjson.loads('{"a": 1}', object_hook=lambda obj: seen.append(type(obj)))
+        assert seen == [dict]
+
+    def test_object_hook_not_called_for_array(self):
+        """
+        loads() object_hook is not called without objects
+        """
+        assert orjson.loads("[1, [2], \"a\"]", object_hook=raise_custom) == [
+            1,
+            [2],
+            "a",
+        ]
+
+    def test_object_hook_none(self):
+        """
+        loads() object_hook=None is the default
+        """
+        assert orjson.loads('{"a": 1}', object_hook=None) == {"a": 1}
+
+    def test_object_hook_exception(self):
+        """
+        loads() exception raised by object_hook propagates
+        """
+        with pytest.raises(Custom):
+            orjson.loads('[1, {"a": [2, {"b": 3}]}]', object_hook=raise_custom)
+
+    def test_object_hook_exception_refcount(self):
+        """
+        loads() exception raised by object_hook does not leak
+        """
+        obj = object()
+        calls = []
+
+        def object_hook(val):
+            calls.append(None)
+            if len(calls) % 2 == 0:
+                raise Custom()
+            return obj
+
+        before = sys.getrefcount(obj)
+        for _ in range(100):
+            with pytest.raises(Custom):
+                orjson.loads('[{"a": 1}, {"b": 2}]', object_hook=object_hook)
+        assert sys.getrefcount(obj) == before
+
+    def test_object_hook_invalid(self):
+        """
+        loads() object_hook not callable
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("{}", object_hook=1)
+
+    def test_object_hook_with_parse_float(self):
+        """
+        loads() object_hook is given values from parse_float
+        """
+        assert orjson.loads(
+            '{"a": 0.1}',
+            parse_float=decimal.Decimal,
+            object_hook=lambda obj: sorted(obj.items()),
+        ) == [("a", decimal.Decimal("0.1"))]
+
+    def test_object_hook_load(self):
+        """
+        load() object_hook
+        """
+        assert orjson.load(
+            io.BytesIO(b'{"x": 1, "y": 2}'), object_hook=lambda obj: Point(**obj)
+        ) == Point(1, 2)
+
+    def test_object_hook_load_chunked(self):
+        """
+        load() object_hook with chunk_size
+        """
+        doc = b"[" + b",".join(b'{"x": 1, "y": 2}' for _ in range(100)) + b"]"
+        assert orjson.load(
+            io.BytesIO(doc), chunk_size=4, object_hook=lambda obj: Point(**obj)
+        ) == [Point(1, 2)] * 100
+
+    def test_object_hook_load_chunked_exception_refcount(self):
+        """
+        load() exception raised by object_hook with chunk_size does not leak
+        """
+        obj = object()
+        calls = []
+
+        def object_hook(val):
+            calls.append(None)
+            if len(calls) % 2 == 0:
+                raise Custom()
+            return obj
+
+        before = sys.getrefcount(obj)
+        for _ in range(100):
+            with pytest.raises(Custom):
+                orjson.load(
+                    io.BytesIO(b'[{"a": 1}, {"b": 2}]'),
+                    chunk_size=4,
+                    object_hook=object_hook,
+                )
+        assert sys.getrefcount(obj) == before
@@ -0,0 +1,125 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections
+import io
+import sys
+
+import pytest
+
+import orjson
+
+from .util import Custom, raise_custom
+
+
+def identity(val):
+    return val
+
+
+class TestObjectPairsHook:
+    def test_object_pairs_hook(self):
+        """
+        loads() object_pairs_hook is called with a list of tuples
+        """
+        res = orjson.loads('{"a": 1, "b": [2]}', object_pairs_hook=identity)
+        assert res == [("a", 1), ("b", [2])]
+        assert type(res) is list
+        assert all(type(each) is tuple for each in res)
+
+    def test_object_pairs_hook_duplicate(self):
+        """
+        loads() object_pairs_hook is given duplicate keys in order
+        """
+        assert orjson.loads(
+            '{"b": 1, "a": 2, "b": 3}', object_pairs_hook=identity
+        ) == [("b", 1), ("a", 2), ("b", 3)]
+
+    def test_object_pairs_hook_ordereddict(self):
+        """
+        loads() object_pairs_hook=collections.OrderedDict
+        """
+        res = orjson.loads(
+            '{"z": {"y": 1}, "a": 2}', object_pairs_hook=collections.OrderedDict
+        )
+        assert type(res) is collections.OrderedDict
+        assert type(res["z"]) is collections.OrderedDict
+        assert list(res.keys()) == ["z", "a"]
+
+    def test_object_pairs_hook_empty(self):
+        """
+        loads() object_pairs_hook is called with an empty list
+        """
+        assert orjson.loads("[{}]", object_pairs_hook=identity) == [[]]
+
+    def test_object_pairs_hook_nested(self):
+        """
+        loads() object_pairs_hook is called innermost first
+        """
+        assert orjson.loads(
+            '{"a": {"b": {"c": 1}}}', object_pairs_hook=identity
+        ) == [("a", [("b", [("c", 1)])])]
+
+    def test_object_pairs_hook_priority(self):
+        """
+        loads() object_pairs_hook takes priority over object_hook
+        """
+        assert orjson.loads(
+            '{"a": 1}', object_hook=raise_custom, object_pairs_hook=identity
+        ) == [("a", 1)]
+
+    def test_object_pairs_hook_none(self):
+        """
+        loads() object_pairs_hook=None is the default
+        """
+        assert orjson.loads('{"a": 1}', object_pairs_hook=None) == {"a": 1}
+
+    def test_object_pairs_hook_exception(self):
+        """
+        loads() exception raised by object_pairs_hook propagates
+        """
+        with pytest.raises(Custom):
+            orjson.loads('[{"a": {"b": 1}}]', object_pairs_hook=raise_custom)
+
+    def test_object_pairs_hook_exception_refcount(self):
+        """
+        loads() exception raised by object_pairs_hook does not leak
+        """
+        obj = object()
+        calls = []
+
+        def object_pairs_hook(val):
+            calls.append(None)
+            if len(calls) % 2 == 0:
+                raise Custom()
+            return obj
+
+        before = sys.getrefcount(obj)
+        for _ in range(100):
+            with pytest.raises(Custom):
+                orjson.loads(
+                    '{"a": {"b": 1}, "c": {"d": 2}}', object_pairs_hook=object_pairs_hook
+                )
+        assert sys.getrefcount(obj) == before
+
+    def test_object_pairs_hook_invalid(self):
+        """
+        loads() object_pairs_hook not callable
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("{}", object_pairs_hook=1)
+
+    def test_object_pairs_hook_load(self):
+        """
+        load() object_pairs_hook
+        """
+        assert orjson.load(
+            io.BytesIO(b'{"a": 1, "a": 2}'), object_pairs_hook=identity
+        ) == [("a", 1), ("a", 2)]
+
+    def test_object_pairs_hook_load_chunked(self):
+        """
+        load() object_pairs_hook with chunk_size
+        """
+        doc = b"[" + b",".join(b'{"a": 1, "a": 2}' for _ in range(100)) + b"]"
+        assert orjson.load(
+            io.BytesIO(doc), chunk_size=3, object_pairs_hook=identity
+        ) == [[("a", 1), ("a", 2)]] * 100
@@ -0,0 +1,130 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections.abc
+import dataclasses
+import datetime
+
+import pytest
+
+import orjson
+
+
+class TestObTypeCache:
+    def test_obtype_cache_option_change(self):
+        """
+        type is classified again when an option affecting it differs
+        """
+
+        @dataclasses.dataclass
+        class Dataclass:
+            a: int
+
+        class Subclass(str):
+            pass
+
+        obj = [Dataclass(1), Subclass("a"), datetime.date(2000, 1, 1)]
+        for _ in range(2):
+            assert orjson.dumps(obj) == b'[{"a":1},"a","2000-01-01"]'
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps(obj[:1], option=orjson.OPT_PASSTHROUGH_DATACLASS)
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps(obj[1:2], option=orjson.OPT_PASSTHROUGH_SUBCLASS)
+            assert (
+                orjson.dumps(
+                    obj,
+                    option=orjson.OPT_PASSTHROUGH_DATACLASS
+                    | orjson.OPT_PASSTHROUGH_SUBCLASS,
+                    default=lambda o: "default",
+                )
+                == b'["default","default","2000-01-01"]'
+            )
+
+    def test_obtype_cache_type_modified(self):
+        """
+        type is classified again after it is modified
+        """
+
+        class Custom:
+            pass
+
+        obj = Custom()
+        for _ in range(2):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps(obj)
+        Custom.__annotations__ = {"a": int}
+        Custom.a = 1
+        dataclasses.dataclass(Custom)
+        assert orjson.dumps(obj) == b'{"a":1}'
+
+    def test_obtype_cache_type_reused(self):
+        """
+        new type at the address of a freed type is not classified by it
+        """
+        for idx in range(100):
+            if idx % 2:
+
+                @dataclasses.dataclass
+                class Custom:
+                    a: int = 1
+
+                assert orjson.dumps(Custom()) == b'{"a":1}'
+            else:
+
+                class Custom:  # type: ignore
+                    pass
+
+                assert orjson.dumps(Custom(), default=lambda o: None) == b"null"
+            del Custom
+
+    def test_obtype_cache_geo_interface_instance(self):
+        """
+        __geo_interface__ set on an instance is not cached by type
+        """
+
+        class Custom:
+            pass
+
+        plain = Custom()
+        geo = Custom()
+        geo.__geo_interface__ = {"type": "Point", "coordinates": [1, 2]}
+        for _ in range(2):
+            assert (
+                orjson.dumps(
+                    [plain, geo],
+                    option=orjson.OPT_SERIALIZE_GEO_INTERFACE,
+                    default=lambda o: None,
+                )
+                == b'[null,{"type":"Point","coordinates":[1,2]}]'
+            )
+
+    def test_obtype_cache_abc_registered(self):
+        """
+        type registered with Mapping or Sequence after it is serialized is
+        classified again
+        """
+
+        class Mapping:
+            def keys(self):
+                return ["a"]
+
+            def __getitem__(self, key):
+                return 1
+
+        class Sequence:
+            def __len__(self):
+                return 1
+
+            def __getitem__(self, idx):
+                if idx:
+                    raise IndexError(idx)
+                return 1
+
+        option = orjson.OPT_SERIALIZE_MAPPING | orjson.OPT_SERIALIZE_SEQUENCE
+        obj = [Mapping(), Sequence()]
+        for _ in range(2):
+            assert orjson.dumps(obj, option=option, default=str) == orjson.dumps(
+                [str(each) for each in obj]
+            )
+        collections.abc.Mapping.register(Mapping)
+        collections.abc.Sequence.register(Sequence)
+        assert orjson.dumps(obj, option=option) == b'[{"a":1},[1]]'
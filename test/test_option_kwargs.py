@@ -0,0 +1,123 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import datetime
+
+import pytest
+
+import orjson
+
+
+@dataclasses.dataclass
+class Dataclass:
+    a: int
+
+
+class TestOptionKwargs:
+    def test_option_kwarg_sort_keys(self):
+        """
+        dumps() sort_keys=True
+        """
+        assert orjson.dumps({"b": 1, "a": 2}, sort_keys=True) == b'{"a":2,"b":1}'
+
+    def test_option_kwarg_false(self):
+        """
+        dumps() option keyword argument False has no effect
+        """
+        assert orjson.dumps({"b": 1, "a": 2}, sort_keys=False) == b'{"b":1,"a":2}'
+
+    def test_option_kwarg_clears_option(self):
+        """
+        dumps() option keyword argument False clears option
+        """
+        assert (
+            orjson.dumps(
+                {"b": 1, "a": 2},
+                option=orjson.OPT_SORT_KEYS | orjson.OPT_APPEND_NEWLINE,
+                sort_keys=False,
+            )
+            == b'{"b":1,"a":2}\n'
+        )
+
+    def test_option_kwarg_combined(self):
+        """
+        dumps() option keyword arguments combine with option
+        """
+        assert (
+            orjson.dumps(
+                {"b": datetime.datetime(1970, 1, 1), "a": 2},
+                option=orjson.OPT_SORT_KEYS,
+                naive_utc=True,
+                utc_z=True,
+                append_newline=True,
+            )
+            == b'{"a":2,"b":"1970-01-01T00:00:00Z"}\n'
+        )
+
+    def test_option_kwarg_indent(self):
+        """
+        dumps() indent=2
+        """
+        assert orjson.dumps([1], indent=2) == orjson.dumps(
+            [1], option=orjson.OPT_INDENT_2
+        )
+        assert orjson.dumps([1], option=orjson.OPT_INDENT_2, indent=None) == b"[1]"
+
+    def test_option_kwarg_indent_invalid(self):
+        """
+        dumps() indent other than 2 or None
+        """
+        for val in (0, 4, True, "  ", 2.0):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps([1], indent=val)
+
+    def test_option_kwarg_not_bool(self):
+        """
+        dumps() option keyword argument not bool
+        """
+        for val in (1, 0, None, "true"):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps([1], sort_keys=val)
+
+    def test_option_kwarg_unknown(self):
+        """
+        dumps() unknown keyword argument
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps([1], sort_key=True)
+
+    def test_option_kwarg_each(self):
+        """
+        dumps() every option has a keyword argument
+        """
+        names = [
+            name
+            for name in dir(orjson)
+            if name.startswith("OPT_")
+            and name not in ("OPT_SERIALIZE_DATACLASS", "OPT_SERIALIZE_UUID")
+        ]
+        assert names
+        for name in names:
+            kwarg = name[4:].lower()
+            if kwarg == "indent_2":
+                orjson.dumps([1], indent=2)
+            else:
+                orjson.dumps([1], **{kwarg: True})
+
+    def test_option_kwarg_passthrough_dataclass(self):
+        """
+        dumps() passthrough_dataclass=True
+        """
+        assert (
+            orjson.dumps(
+                Dataclass(1), passthrough_dataclass=True, default=lambda obj: obj.a
+            )
+            == b"1"
+        )
+
+    def test_option_kwarg_compile(self):
+        """
+        compile() option keyword arguments
+        """
+        serialize = orjson.compile(Dataclass, append_newline=True)
+        assert serialize(Dataclass(1)) == b'{"a":1}\n'
@@ -0,0 +1,97 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import enum
+
+import pytest
+
+import orjson
+
+DEPRECATED = ("OPT_SERIALIZE_DATACLASS", "OPT_SERIALIZE_UUID")
+
+
+class TestOptionsFlag:
+    def test_options_flag_type(self):
+        """
+        orjson.Options is an IntFlag
+        """
+        assert issubclass(orjson.Options, enum.IntFlag)
+        assert orjson.Options.__module__ == "orjson"
+
+    def test_options_flag_members(self):
+        """
+        orjson.Options has a member for each OPT_ constant
+        """
+        constants = {
+            name[4:]: getattr(orjson, name)
+            for name in dir(orjson)
+            if name.startswith("OPT_") and name not in DEPRECATED
+        }
+        members = {member.name: member.value for member in orjson.Options}
+        assert members == constants
+
+    def test_options_flag_dumps(self):
+        """
+        dumps() option is orjson.Options
+        """
+        assert (
+            orjson.dumps(
+                {"b": 1, "a": 2},
+                option=orjson.Options.SORT_KEYS | orjson.Options.APPEND_NEWLINE,
+            )
+            == b'{"a":2,"b":1}\n'
+        )
+
+    def test_options_flag_mixed(self):
+        """
+        orjson.Options composes with OPT_ constants
+        """
+        opt = orjson.Options.SORT_KEYS | orjson.OPT_APPEND_NEWLINE
+        assert isinstance(opt, orjson.Options)
+        assert orjson.dumps({"b": 1, "a": 2}, option=opt) == b'{"a":2,"b":1}\n'
+
+    def test_options_flag_repr(self):
+        """
+        orjson.Options repr names the options
+        """
+        assert "SORT_KEYS" in repr(orjson.Options.SORT_KEYS | orjson.Options.UTC_Z)
+        assert "UTC_Z" in repr(orjson.Options.SORT_KEYS | orjson.Options.UTC_Z)
+
+    def test_options_flag_compile(self):
+        """
+        compile() and set_default_options() option is orjson.Options
+        """
+        try:
+            orjson.set_default_options(orjson.Options.SORT_KEYS)
+            assert orjson.get_default_options() == orjson.OPT_SORT_KEYS
+        finally:
+            orjson.set_default_options(None)
+
+    def test_option_none(self):
+        """
+        dumps() option=None
+        """
+        assert orjson.dumps([1], option=None) == b"[1]"
+
+    def test_option_unknown_bits(self):
+        """
+        dumps() option with unknown bits names them
+        """
+        with pytest.raises(orjson.JSONEncodeError) as exc_info:
+            orjson.dumps([1], option=orjson.OPT_SORT_KEYS | 1 << 30)
+        assert "0x40000000" in str(exc_info.value)
+
+    def test_option_bool(self):
+        """
+        dumps() option is not bool
+        """
+        for val in (True, False):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps([1], option=val)
+
+    def test_option_overflow(self):
+        """
+        dumps() option overflow
+        """
+        for val in (1 << 64, -(1 << 64)):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps([1], option=val)
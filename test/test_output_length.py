@@ -0,0 +1,45 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import pytest
+
+import orjson
+
+
+class TestOutputLength:
+    def test_output_length_boundary(self):
+        """
+        dumps() output of lengths around the initial buffer
+        """
+        for length in range(1000, 1050):
+            obj = "a" * (length - 2)
+            output = orjson.dumps(obj)
+            assert len(output) == length
+            assert output == b'"' + obj.encode("utf-8") + b'"'
+            assert orjson.dumps(obj, option=orjson.OPT_APPEND_NEWLINE) == output + b"\n"
+
+    def test_output_length_values(self):
+        """
+        dumps() many small values written across the initial buffer
+        """
+        for count in (1, 100, 300, 1000):
+            obj = [{"key": idx, "value": "é" * (idx % 5)} for idx in range(count)]
+            assert orjson.loads(orjson.dumps(obj)) == obj
+            assert orjson.loads(orjson.dumps(obj, option=orjson.OPT_INDENT_2)) == obj
+
+    def test_output_length_max_size(self):
+        """
+        dumps() max_size around the initial buffer
+        """
+        for length in (2, 1023, 1024, 1025, 4096):
+            obj = "a" * (length - 2)
+            assert len(orjson.dumps(obj, max_size=length)) == length
+            with pytest.raises(orjson.JSONEncodeSizeError):
+                orjson.dumps(obj, max_size=length - 1)
+
+    def test_output_length_error(self):
+        """
+        dumps() raising after writing to the initial buffer or beyond it
+        """
+        for length in (10, 2000):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps(["a" * length, object()])
@@ -0,0 +1,196 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import pytest
+
+import orjson
+
+
+def gen_raises():
+    yield ("a", 1)
+    raise ValueError("gen")
+
+
+class TestPairs:
+    def test_pairs_iter(self):
+        """
+        OPT_SERIALIZE_PAIRS iterator of pairs
+        """
+        assert (
+            orjson.dumps(
+                iter([("a", 1), ("b", [2]), ("a", {"c": 3})]),
+                option=orjson.OPT_SERIALIZE_PAIRS,
+            )
+            == b'{"a":1,"b":[2],"a":{"c":3}}'
+        )
+
+    def test_pairs_dict_items(self):
+        """
+        OPT_SERIALIZE_PAIRS dict.items()
+        """
+        assert (
+            orjson.dumps({"a": 1, "b": 2}.items(), option=orjson.OPT_SERIALIZE_PAIRS)
+            == b'{"a":1,"b":2}'
+        )
+
+    def test_pairs_generator_zip(self):
+        """
+        OPT_SERIALIZE_PAIRS generator and zip()
+        """
+        assert (
+            orjson.dumps(
+                {
+                    "a": ((str(idx), idx) for idx in range(3)),
+                    "b": zip(("x", "y"), (1.0, 2.0)),
+                },
+                option=orjson.OPT_SERIALIZE_PAIRS,
+            )
+            == b'{"a":{"0":0,"1":1,"2":2},"b":{"x":1.0,"y":2.0}}'
+        )
+
+    def test_pairs_nested(self):
+        """
+        OPT_SERIALIZE_PAIRS value that is pairs
+        """
+        assert (
+            orjson.dumps(
+                iter([("a", iter([("b", 1)]))]), option=orjson.OPT_SERIALIZE_PAIRS
+            )
+            == b'{"a":{"b":1}}'
+        )
+
+    def test_pairs_unset(self):
+        """
+        dumps() iterator without OPT_SERIALIZE_PAIRS raises
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(iter([("a", 1)]))
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps({"a": 1}.items())
+
+    def test_pairs_list(self):
+        """
+        OPT_SERIALIZE_PAIRS does not change a list of pairs but does iter() of it
+        """
+        assert (
+            orjson.dumps([("a", 1)], option=orjson.OPT_SERIALIZE_PAIRS)
+            == b'[["a",1]]'
+        )
+        assert (
+            orjson.dumps(iter([("a", 1)]), option=orjson.OPT_SERIALIZE_PAIRS)
+            == b'{"a":1}'
+        )
+
+    def test_pairs_sort_keys(self):
+        """
+        OPT_SERIALIZE_PAIRS with OPT_SORT_KEYS keeps the order of repeated keys
+        """
+        assert (
+            orjson.dumps(
+                iter([("b", 1), ("a", 2), ("b", 3), ("a", 4)]),
+                option=orjson.OPT_SERIALIZE_PAIRS | orjson.OPT_SORT_KEYS,
+            )
+            == b'{"a":2,"a":4,"b":1,"b":3}'
+        )
+        assert (
+            orjson.dumps(
+                {"b": 1, "a": 2}.items(),
+                option=orjson.OPT_SERIALIZE_PAIRS | orjson.OPT_SORT_KEYS,
+            )
+            == b'{"a":2,"b":1}'
+        )
+
+    def test_pairs_sort_keys_invalid(self):
+        """
+        OPT_SERIALIZE_PAIRS with OPT_SORT_KEYS item that is not a pair or raising
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(
+                iter([("a", 1), ["b", 2]]),
+                option=orjson.OPT_SERIALIZE_PAIRS | orjson.OPT_SORT_KEYS,
+            )
+        with pytest.raises(ValueError):
+            orjson.dumps(
+                gen_raises(), option=orjson.OPT_SERIALIZE_PAIRS | orjson.OPT_SORT_KEYS
+            )
+
+    def test_pairs_empty(self):
+        """
+        OPT_SERIALIZE_PAIRS empty
+        """
+        assert orjson.dumps(iter(()), option=orjson.OPT_SERIALIZE_PAIRS) == b"{}"
+        assert (
+            orjson.dumps(
+                iter(()), option=orjson.OPT_SERIALIZE_PAIRS | orjson.OPT_INDENT_2
+            )
+            == b"{}"
+        )
+
+    def test_pairs_indent(self):
+        """
+        OPT_SERIALIZE_PAIRS with OPT_INDENT_2
+        """
+        assert (
+            orjson.dumps(
+                iter([("a", 1), ("a", [])]),
+                option=orjson.OPT_SERIALIZE_PAIRS | orjson.OPT_INDENT_2,
+            )
+            == b'{\n  "a": 1,\n  "a": []\n}'
+        )
+
+    def test_pairs_invalid_item(self):
+        """
+        OPT_SERIALIZE_PAIRS item that is not a pair
+        """
+        for val in ([("a", 1, 2)], [["a", 1]], ["ab"], [("a",)]):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps(iter(val), option=orjson.OPT_SERIALIZE_PAIRS)
+
+    def test_pairs_iterator_raises(self):
+        """
+        OPT_SERIALIZE_PAIRS iterator raising
+        """
+        with pytest.raises(ValueError):
+            orjson.dumps(gen_raises(), option=orjson.OPT_SERIALIZE_PAIRS)
+
+    def test_pairs_non_str_keys(self):
+        """
+        OPT_SERIALIZE_PAIRS keys that are not str
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(iter([(1, 2)]), option=orjson.OPT_SERIALIZE_PAIRS)
+        assert (
+            orjson.dumps(
+                iter([(1, 2), (None, 3)]),
+                option=orjson.OPT_SERIALIZE_PAIRS | orjson.OPT_NON_STR_KEYS,
+            )
+            == b'{"1":2,"null":3}'
+        )
+
+    def test_pairs_redact_include_keys(self):
+        """
+        OPT_SERIALIZE_PAIRS with redact_keys and include_keys
+        """
+        assert (
+            orjson.dumps(
+                iter([("a", 1), ("token", 2), ("b", 3)]),
+                option=orjson.OPT_SERIALIZE_PAIRS,
+                redact_keys={"token"},
+                include_keys={"token", "b"},
+            )
+            == b'{"token":"[REDACTED]","b":3}'
+        )
+
+    def test_pairs_max_depth(self):
+        """
+        OPT_SERIALIZE_PAIRS nesting counts toward max_depth
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(
+                iter([("a", {"b": 1})]), option=orjson.OPT_SERIALIZE_PAIRS, max_depth=1
+            )
+
+    def test_pairs_kwarg(self):
+        """
+        dumps() serialize_pairs=True is OPT_SERIALIZE_PAIRS
+        """
+        assert orjson.dumps(iter([("a", 1)]), serialize_pairs=True) == b'{"a":1}'
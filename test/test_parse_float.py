@@ -0,0 +1,152 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import decimal
+import io
+import sys
+
+import pytest
+
+import orjson
+
+from .util import Custom, raise_custom
+
+
+class TestParseFloat:
+    def test_parse_float_decimal(self):
+        """
+        loads() parse_float=decimal.Decimal is exact
+        """
+        res = orjson.loads("0.1", parse_float=decimal.Decimal)
+        assert res == decimal.Decimal("0.1")
+        assert type(res) is decimal.Decimal
+
+    def test_parse_float_literal(self):
+        """
+        loads() parse_float is called with the literal
+        """
+        seen = []
+
+        def parse_float(val):
+            seen.append(val)
+            return val
+
+        assert orjson.loads(
+            "[1.0, -0.5e-3, 2E+10, 3.14159265358979323846264338327950288]",
+            parse_float=parse_float,
+        ) == ["1.0", "-0.5e-3", "2E+10", "3.14159265358979323846264338327950288"]
+        assert seen == [
+            "1.0",
+            "-0.5e-3",
+            "2E+10",
+            "3.14159265358979323846264338327950288",
+        ]
+        assert all(type(each) is str for each in seen)
+
+    def test_parse_float_int_unaffected(self):
+        """
+        loads() parse_float is not called for integers
+        """
+        assert orjson.loads(
+            "[0, -1, 18446744073709551615, -9223372036854775808]",
+            parse_float=raise_custom,
+        ) == [0, -1, 18446744073709551615, -9223372036854775808]
+
+    def test_parse_float_infinity(self):
+        """
+        loads() parse_float is given numbers too large for a float
+        """
+        assert orjson.loads("1e400", parse_float=decimal.Decimal) == decimal.Decimal(
+            "1e400"
+        )
+
+    def test_parse_float_nested(self):
+        """
+        loads() parse_float in nested containers
+        """
+        assert orjson.loads(
+            '{"a": [{"b": 1.5}, 2.5], "c": {"d": [[3.5]]}}', parse_float=decimal.Decimal
+        ) == {
+            "a": [{"b": decimal.Decimal("1.5")}, decimal.Decimal("2.5")],
+            "c": {"d": [[decimal.Decimal("3.5")]]},
+        }
+
+    def test_parse_float_none(self):
+        """
+        loads() parse_float=None is the default
+        """
+        res = orjson.loads("[1.5]", parse_float=None)
+        assert res == [1.5]
+        assert type(res[0]) is float
+
+    def test_parse_float_default(self):
+        """
+        loads() float behavior unchanged without parse_float
+        """
+        assert orjson.loads("[0.1, 1e2, 1]") == [0.1, 100.0, 1]
+        pytest.raises(orjson.JSONDecodeError, orjson.loads, "1e400")
+
+    def test_parse_float_exception(self):
+        """
+        loads() exception raised by parse_float propagates
+        """
+        with pytest.raises(Custom):
+            orjson.loads('{"a": [1, 2.0]}', parse_float=raise_custom)
+
+    def test_parse_float_exception_refcount(self):
+        """
+        loads() exception raised by parse_float does not leak the key
+        """
+        key = "parse_float_refcount_key"
+        doc = '{"%s": [2.0]}' % key
+        with pytest.raises(Custom):
+            orjson.loads(doc, parse_float=raise_custom)
+        before = sys.getrefcount(key)
+        for _ in range(100):
+            with pytest.raises(Custom):
+                orjson.loads(doc, parse_float=raise_custom)
+        assert sys.getrefcount(key) <= before
+
+    def test_parse_float_invalid(self):
+        """
+        loads() parse_float not callable
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("1.5", parse_float=1)
+
+    def test_parse_float_unexpected_keyword(self):
+        """
+        loads() unexpected keyword argument
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads("1.5", unknown=int)
+        assert str(exc.value) == "loads() got an unexpected keyword argument: 'unknown'"
+
+    def test_parse_float_positional(self):
+        """
+        loads() parse_float is keyword-only
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("1.5", decimal.Decimal)
+
+    def test_parse_float_load(self):
+        """
+        load() parse_float
+        """
+        assert orjson.load(
+            io.BytesIO(b'{"a": 0.1}'), parse_float=decimal.Decimal
+        ) == {"a": decimal.Decimal("0.1")}
+
+    def test_parse_float_load_chunked(self):
+        """
+        load() parse_float with chunk_size
+        """
+        doc = b"[" + b",".join(b"0.1" for _ in range(1000)) + b"]"
+        res = orjson.load(io.BytesIO(doc), chunk_size=7, parse_float=decimal.Decimal)
+        assert res == [decimal.Decimal("0.1")] * 1000
+
+    def test_parse_float_load_chunked_exception(self):
+        """
+        load() exception raised by parse_float with chunk_size propagates
+        """
+        with pytest.raises(Custom):
+            orjson.load(io.BytesIO(b"[1, 2.5]"), chunk_size=3, parse_float=raise_custom)
@@ -0,0 +1,112 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import decimal
+import io
+
+import pytest
+
+import orjson
+
+from .util import Custom, raise_custom
+
+
+class BoundedInt(int):
+    pass
+
+
+def bounded(val):
+    res = int(val)
+    if not -(2**31) <= res < 2**31:
+        raise OverflowError(val)
+    return BoundedInt(res)
+
+
+class TestParseInt:
+    def test_parse_int_literal(self):
+        """
+        loads() parse_int is called with the literal
+        """
+        seen = []
+
+        def parse_int(val):
+            seen.append(val)
+            return val
+
+        assert orjson.loads("[0, -1, 42]", parse_int=parse_int) == ["0", "-1", "42"]
+        assert seen == ["0", "-1", "42"]
+
+    def test_parse_int_wrap(self):
+        """
+        loads() parse_int return value is used
+        """
+        res = orjson.loads('{"a": [1, 2]}', parse_int=bounded)
+        assert res == {"a": [1, 2]}
+        assert all(type(each) is BoundedInt for each in res["a"])
+
+    def test_parse_int_bounds_check(self):
+        """
+        loads() parse_int rejecting a value propagates its exception
+        """
+        with pytest.raises(OverflowError):
+            orjson.loads("[1, 4294967296]", parse_int=bounded)
+
+    def test_parse_int_large(self):
+        """
+        loads() parse_int is given integers of any size
+        """
+        assert orjson.loads(
+            "[18446744073709551615, 18446744073709551616, -9223372036854775809]",
+            parse_int=str,
+        ) == ["18446744073709551615", "18446744073709551616", "-9223372036854775809"]
+
+    def test_parse_int_float_unaffected(self):
+        """
+        loads() parse_int is not called for floats
+        """
+        res = orjson.loads("[1.0, 1e2, -0.5]", parse_int=raise_custom)
+        assert res == [1.0, 100.0, -0.5]
+        assert all(type(each) is float for each in res)
+
+    def test_parse_int_none(self):
+        """
+        loads() parse_int=None is the default
+        """
+        res = orjson.loads("[1]", parse_int=None)
+        assert res == [1]
+        assert type(res[0]) is int
+
+    def test_parse_int_with_parse_float(self):
+        """
+        loads() parse_int and parse_float together
+        """
+        assert orjson.loads(
+            "[1, 0.1]", parse_int=decimal.Decimal, parse_float=decimal.Decimal
+        ) == [decimal.Decimal("1"), decimal.Decimal("0.1")]
+
+    def test_parse_int_exception(self):
+        """
+        loads() exception raised by parse_int propagates
+        """
+        with pytest.raises(Custom):
+            orjson.loads('{"a": {"b": 1}}', parse_int=raise_custom)
+
+    def test_parse_int_invalid(self):
+        """
+        loads() parse_int not callable
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("1", parse_int=1)
+
+    def test_parse_int_load(self):
+        """
+        load() parse_int
+        """
+        assert orjson.load(io.BytesIO(b"[1, 2.5]"), parse_int=str) == ["1", 2.5]
+
+    def test_parse_int_load_chunked(self):
+        """
+        load() parse_int with chunk_size
+        """
+        doc = b"[" + b",".join(b"7" for _ in range(1000)) + b"]"
+        res = orjson.load(io.BytesIO(doc), chunk_size=5, parse_int=str)
+        assert res == ["7"] * 1000
@@ -0,0 +1,218 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import array
+import decimal
+
+import pytest
+
+import orjson
+
+DOCUMENT = b'{"a": [1, "]}"]}\n[2, {"b": "\\""}] "c\xc3\xa9" 3 true\r\n[] 4.5'
+EXPECTED = [{"a": [1, "]}"]}, [2, {"b": '"'}], "cé", 3, True, [], 4.5]
+
+
+def feed_all(parser, chunks):
+    values = []
+    for chunk in chunks:
+        values.extend(parser.feed(chunk))
+    return values + parser.close()
+
+
+class TestParser:
+    def test_parser(self):
+        """
+        Parser feed() returns the documents that have ended
+        """
+        parser = orjson.Parser()
+        assert parser.feed(b'{"a": 1}[') == [{"a": 1}]
+        assert parser.feed(b"2") == []
+        assert parser.feed(b'] "x" 1') == [[2], "x"]
+        assert parser.feed(b"2 ") == [12]
+        assert parser.close() == []
+
+    def test_parser_chunks(self):
+        """
+        Parser fed in chunks of each size, including splitting a character
+        """
+        for size in range(1, len(DOCUMENT) + 1):
+            chunks = [DOCUMENT[i : i + size] for i in range(0, len(DOCUMENT), size)]
+            assert feed_all(orjson.Parser(), chunks) == EXPECTED
+
+    def test_parser_input_types(self):
+        """
+        Parser feed() of bytes, bytearray, memoryview, str, and a buffer
+        """
+        parser = orjson.Parser()
+        assert parser.feed(bytearray(b"[1]")) == [[1]]
+        assert parser.feed(memoryview(b"[2]")) == [[2]]
+        assert parser.feed("[3, ") == []
+        assert parser.feed(array.array("B", b"4]")) == [[3, 4]]
+        assert parser.feed(b"") == []
+        assert parser.close() == []
+
+    def test_parser_close(self):
+        """
+        Parser close() ends a document that ends with the input
+        """
+        parser = orjson.Parser()
+        assert parser.feed(b"1 2") == [1]
+        assert parser.close() == [2]
+        parser = orjson.Parser()
+        assert parser.feed(b"  \n") == []
+        assert parser.close() == []
+
+    def test_parser_closed(self):
+        """
+        Parser cannot be fed or closed after it is closed
+        """
+        parser = orjson.Parser()
+        parser.close()
+        with pytest.raises(orjson.JSONDecodeError):
+            parser.feed(b"1")
+        with pytest.raises(orjson.JSONDecodeError):
+            parser.close()
+
+    def test_parser_invalid(self):
+        """
+        Parser raises for an invalid or incomplete document, then is closed
+        """
+        for chunks in ([b'{"a": tru}'], [b"[1]]"], [b"[1,"], [b'"\xff"']):
+            parser = orjson.Parser()
+            with pytest.raises(orjson.JSONDecodeError):
+                feed_all(parser, chunks)
+            with pytest.raises(orjson.JSONDecodeError):
+                parser.feed(b"1 ")
+        parser = orjson.Parser()
+        with pytest.raises(orjson.JSONDecodeError):
+            parser.feed(1)
+        assert parser.feed(b"[1]") == [[1]]
+
+    def test_parser_invalid_after_documents(self):
+        """
+        Parser returns the documents before an invalid one, then raises
+        """
+        parser = orjson.Parser()
+        assert parser.feed(b'[1] {"a": 2}\n[3, tru]') == [[1], {"a": 2}]
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            parser.feed(b"4 ")
+        assert exc.value.msg == "invalid literal"
+        assert exc.value.pos == 17
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            parser.close()
+        assert exc.value.msg == "Parser is closed"
+
+    def test_parser_invalid_fed(self):
+        """
+        Parser raises from the feed() of the input that makes the document
+        invalid, before the document has ended
+        """
+        for chunks, pos in (
+            ([b'{"a": 1', b", 2"], 9),
+            ([b"[1, ", b"tx"], 4),
+            ([b'["a', b"\\x"], 4),
+            ([b"[1", b" 2"], 3),
+        ):
+            parser = orjson.Parser()
+            for chunk in chunks[:-1]:
+                assert parser.feed(chunk) == []
+            with pytest.raises(orjson.JSONDecodeError) as exc_info:
+                parser.feed(chunks[-1])
+            assert exc_info.value.pos == pos
+        parser = orjson.Parser()
+        assert parser.feed(b"[1] [01") == [[1]]
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            parser.feed(b"")
+        assert exc_info.value.msg == "invalid number"
+        assert exc_info.value.pos == 5
+
+    def test_parser_location(self):
+        """
+        Parser error position is in all of the input fed
+        """
+        parser = orjson.Parser()
+        assert parser.feed(b'{"a":1}\n') == [{"a": 1}]
+        assert parser.feed('"\xe9" {"b":}') == ["\xe9"]
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            parser.close()
+        assert exc.value.pos == 17
+        assert exc.value.lineno == 2
+        assert exc.value.colno == 10
+        parser = orjson.Parser()
+        assert parser.feed('"\xe9"\n[1, ') == ["\xe9"]
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            parser.close()
+        assert exc.value.pos == 8
+        assert exc.value.lineno == 2
+        assert exc.value.colno == 5
+
+    def test_parser_kwargs(self):
+        """
+        Parser arguments of loads()
+        """
+        parser = orjson.Parser(parse_float=decimal.Decimal, tuples=True)
+        assert parser.feed(b"[1.10] ") == [(decimal.Decimal("1.10"),)]
+        parser = orjson.Parser(replace_invalid_utf8=True)
+        assert parser.feed(b'"\xff"') == ["�"]
+
+    def test_parser_limits(self):
+        """
+        Parser max_items applies to each document, and max_bytes also to a
+        document that has not ended
+        """
+        parser = orjson.Parser(max_items=2)
+        assert parser.feed(b"[1, 2] [3, 4]") == [[1, 2], [3, 4]]
+        parser = orjson.Parser(max_bytes=4)
+        assert parser.feed(b"[1] [2]") == [[1], [2]]
+        assert parser.feed(b" [3, ") == []
+        with pytest.raises(orjson.JSONDecodeError):
+            parser.feed(b" ")
+
+    def test_parser_hook(self):
+        """
+        Parser cannot be fed or closed by one of its own hooks
+        """
+        parser = None
+
+        def hook(obj):
+            parser.feed(b"1")
+
+        parser = orjson.Parser(object_hook=hook)
+        with pytest.raises(orjson.JSONDecodeError):
+            parser.feed(b"{}")
+
+        def error(obj):
+            raise ValueError("hook")
+
+        parser = orjson.Parser(object_hook=error)
+        with pytest.raises(ValueError):
+            parser.feed(b"{}")
+
+    def test_parser_hook_after_documents(self):
+        """
+        Parser returns the documents before one a hook raised for, then
+        raises the hook's exception
+        """
+
+        def hook(obj):
+            if "b" in obj:
+                raise ValueError("hook")
+            return obj
+
+        parser = orjson.Parser(object_hook=hook)
+        assert parser.feed(b'{"a": 1} {"b": 2} {"c": 3}') == [{"a": 1}]
+        with pytest.raises(ValueError):
+            parser.feed(b"4 ")
+        with pytest.raises(orjson.JSONDecodeError):
+            parser.close()
+
+    def test_parser_invalid_kwargs(self):
+        """
+        Parser() arguments that are not accepted
+        """
+        for kwargs in ({"offset": 1}, {"length": 1}, {"jsonc": True}, {"x": 1}):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.Parser(**kwargs)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.Parser(1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.Parser(strict=True, allow_nan=True)
@@ -0,0 +1,111 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+
+import pytest
+
+import orjson
+
+
+class TestRawNumber:
+    def test_raw_numbers(self):
+        """
+        loads() raw_numbers deserializes numbers as written
+        """
+        assert orjson.loads(b"[1.00, 1e2, -0, 2]", raw_numbers=True) == [
+            orjson.RawNumber("1.00"),
+            orjson.RawNumber("1e2"),
+            orjson.RawNumber("-0"),
+            orjson.RawNumber("2"),
+        ]
+
+    def test_raw_numbers_roundtrip(self):
+        """
+        dumps() RawNumber is serialized as the literal it was deserialized from
+        """
+        data = (
+            b'{"a":1.00,"b":[1e2,-0,1E-5,0.1,-12.50],'
+            b'"c":123456789012345678901234567890,"d":"1.00","e":null}'
+        )
+        assert orjson.dumps(orjson.loads(data, raw_numbers=True)) == data
+
+    def test_raw_numbers_stream(self):
+        """
+        load() raw_numbers read in chunks
+        """
+        obj = orjson.load(io.BytesIO(b"[1.0, -0, 2]"), raw_numbers=True, chunk_size=2)
+        assert orjson.dumps(obj) == b"[1.0,-0,2]"
+
+    def test_raw_numbers_decoder(self):
+        """
+        Decoder raw_numbers
+        """
+        decoder = orjson.Decoder(raw_numbers=True)
+        assert decoder.decode(b'{"a": 1.50}') == {"a": orjson.RawNumber("1.50")}
+
+    def test_raw_numbers_nan(self):
+        """
+        loads() raw_numbers NaN and Infinity are float
+        """
+        obj = orjson.loads(b"[1.0, Infinity]", raw_numbers=True, allow_nan=True)
+        assert obj == [orjson.RawNumber("1.0"), float("inf")]
+
+    def test_raw_numbers_strict(self):
+        """
+        loads() raw_numbers with strict has no loss of precision to reject
+        """
+        assert orjson.loads(b"[1e400, 1e-400]", raw_numbers=True, strict=True) == [
+            orjson.RawNumber("1e400"),
+            orjson.RawNumber("1e-400"),
+        ]
+
+    def test_raw_numbers_invalid(self):
+        """
+        loads() raw_numbers combined with arguments that conflict
+        """
+        for kwargs in ({"parse_float": str}, {"parse_int": str}):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(b"1", raw_numbers=True, **kwargs)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(b"1", raw_numbers=1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.unpackb(b"\x01", raw_numbers=True)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_cbor(b"\x01", raw_numbers=True)
+
+    def test_raw_number(self):
+        """
+        RawNumber() str, repr, float, equality, and hash
+        """
+        num = orjson.RawNumber("1.50")
+        assert str(num) == "1.50"
+        assert repr(num) == "RawNumber('1.50')"
+        assert float(num) == 1.5
+        assert num == orjson.RawNumber("1.50")
+        assert num != orjson.RawNumber("1.5")
+        assert num != 1.5
+        assert num != "1.50"
+        assert hash(num) == hash(orjson.RawNumber("1.50"))
+
+    def test_raw_number_invalid(self):
+        """
+        RawNumber() argument that is not a JSON number
+        """
+        for val in ("01", "1.", ".5", "-", "1e", "+1", " 1", "NaN", "", 1, 1.5, b"1"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.RawNumber(val)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.RawNumber()
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.RawNumber(literal="1")
+
+    def test_raw_number_dumps(self):
+        """
+        dumps() RawNumber with options
+        """
+        num = orjson.RawNumber("1.50")
+        assert orjson.dumps([num], option=orjson.OPT_NUMBERS_AS_STRINGS) == b'["1.50"]'
+        assert orjson.dumps({num: 1}, option=orjson.OPT_NON_STR_KEYS) == b'{"1.50":1}'
+        stats = {}
+        orjson.dumps([num], stats=stats)
+        assert stats["types"] == {"list": 1, "raw_number": 1}
@@ -0,0 +1,185 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+
+import pytest
+
+import orjson
+
+
+@dataclasses.dataclass
+class User:
+    name: str
+    password: str
+
+
+class Obj:
+    def __init__(self, idx):
+        self.a = idx
+        self.token = object()
+
+
+class TestRedactKeys:
+    def test_redact_keys(self):
+        """
+        dumps() redact_keys
+        """
+        assert (
+            orjson.dumps(
+                {"user": "a", "password": "b", "token": 1},
+                redact_keys={"password", "token"},
+            )
+            == b'{"user":"a","password":"[REDACTED]","token":"[REDACTED]"}'
+        )
+
+    def test_redact_keys_nested(self):
+        """
+        dumps() redact_keys at any depth
+        """
+        assert (
+            orjson.dumps(
+                [{"a": {"password": {"b": 1}}, "c": [{"password": [1, 2]}]}],
+                redact_keys={"password"},
+            )
+            == b'[{"a":{"password":"[REDACTED]"},"c":[{"password":"[REDACTED]"}]}]'
+        )
+
+    def test_redact_keys_value_not_serialized(self):
+        """
+        dumps() redact_keys does not serialize the value
+        """
+        assert (
+            orjson.dumps({"password": object(), "a": 1}, redact_keys=["password"])
+            == b'{"password":"[REDACTED]","a":1}'
+        )
+
+    def test_redact_keys_last(self):
+        """
+        dumps() redact_keys on the last key of an object
+        """
+        assert (
+            orjson.dumps({"a": 1, "password": 2}, redact_keys=("password",))
+            == b'{"a":1,"password":"[REDACTED]"}'
+        )
+
+    def test_redact_keys_no_match(self):
+        """
+        dumps() redact_keys matches keys exactly
+        """
+        obj = {"Password": 1, "password_hint": 2, "pass": 3}
+        assert orjson.dumps(obj, redact_keys={"password"}) == orjson.dumps(obj)
+
+    def test_redact_keys_not_values(self):
+        """
+        dumps() redact_keys does not redact values equal to a key
+        """
+        assert (
+            orjson.dumps(["password", {"a": "password"}], redact_keys={"password"})
+            == b'["password",{"a":"password"}]'
+        )
+
+    def test_redact_keys_non_ascii(self):
+        """
+        dumps() redact_keys non-ASCII key
+        """
+        assert (
+            orjson.dumps({"пароль": 1, "a": 2}, redact_keys={"пароль"})
+            == '{"пароль":"[REDACTED]","a":2}'.encode("utf-8")
+        )
+
+    def test_redact_keys_sort_keys(self):
+        """
+        dumps() redact_keys with OPT_SORT_KEYS
+        """
+        assert (
+            orjson.dumps(
+                {"token": 1, "b": 2, "a": 3},
+                option=orjson.OPT_SORT_KEYS,
+                redact_keys={"token"},
+            )
+            == b'{"a":3,"b":2,"token":"[REDACTED]"}'
+        )
+
+    def test_redact_keys_non_str_keys(self):
+        """
+        dumps() redact_keys with OPT_NON_STR_KEYS
+        """
+        assert (
+            orjson.dumps(
+                {1: "a", "token": "b", 2: "c"},
+                option=orjson.OPT_NON_STR_KEYS,
+                redact_keys={"token", "2"},
+            )
+            == b'{"1":"a","token":"[REDACTED]","2":"[REDACTED]"}'
+        )
+
+    def test_redact_keys_indent(self):
+        """
+        dumps() redact_keys with OPT_INDENT_2
+        """
+        assert (
+            orjson.dumps(
+                {"a": 1, "token": [1]},
+                option=orjson.OPT_INDENT_2,
+                redact_keys={"token"},
+            )
+            == b'{\n  "a": 1,\n  "token": "[REDACTED]"\n}'
+        )
+
+    def test_redact_keys_dataclass(self):
+        """
+        dumps() redact_keys dataclass field
+        """
+        assert (
+            orjson.dumps(User("a", "b"), redact_keys={"password"})
+            == b'{"name":"a","password":"[REDACTED]"}'
+        )
+
+    def test_redact_keys_shared_keys(self):
+        """
+        dumps() redact_keys dicts sharing keys
+        """
+        assert (
+            orjson.dumps([vars(Obj(idx)) for idx in range(3)], redact_keys={"token"})
+            == b'[{"a":0,"token":"[REDACTED]"},{"a":1,"token":"[REDACTED]"},{"a":2,"token":"[REDACTED]"}]'
+        )
+
+    def test_redact_keys_default(self):
+        """
+        dumps() redact_keys in a dict returned by default
+        """
+        assert (
+            orjson.dumps(
+                Obj(1), default=lambda obj: {"token": obj.token}, redact_keys={"token"}
+            )
+            == b'{"token":"[REDACTED]"}'
+        )
+
+    def test_redact_keys_empty(self):
+        """
+        dumps() redact_keys empty or None
+        """
+        obj = {"password": 1}
+        assert orjson.dumps(obj, redact_keys=set()) == b'{"password":1}'
+        assert orjson.dumps(obj, redact_keys=None) == b'{"password":1}'
+
+    def test_redact_keys_iterable(self):
+        """
+        dumps() redact_keys any iterable of str
+        """
+        assert (
+            orjson.dumps({"a": 1, "b": 2}, redact_keys=iter(["b"]))
+            == b'{"a":1,"b":"[REDACTED]"}'
+        )
+        assert (
+            orjson.dumps({"a": 1, "b": 2}, redact_keys={"b": None}.keys())
+            == b'{"a":1,"b":"[REDACTED]"}'
+        )
+
+    def test_redact_keys_invalid(self):
+        """
+        dumps() redact_keys invalid
+        """
+        for val in ("password", 1, [1], ["a", b"b"], [["a"]]):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps({}, redact_keys=val)
@@ -0,0 +1,124 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import array
+import gzip
+import io
+
+import pytest
+
+import orjson
+
+from .util import has_compression
+
+DOCUMENTS = (
+    b'["a\xffb", "\xe2\x82", "\xe2\x82\xac"]',
+    b'{"k\xc3":"\xf0\x9f\x98"}',
+    b'["\xed\xa0\x80", "\xc0\xaf"]',
+    b'"\xe2\x82\xac\x80\xe2\x82\xac"',
+)
+
+
+class TestReplaceInvalidUtf8:
+    def test_replace_invalid_utf8(self):
+        """
+        loads() replace_invalid_utf8 replaces as bytes.decode() does
+        """
+        for doc in DOCUMENTS:
+            expected = orjson.loads(doc.decode("utf-8", "replace"))
+            for val in (doc, bytearray(doc), memoryview(doc), array.array("B", doc)):
+                assert orjson.loads(val, replace_invalid_utf8=True) == expected
+
+    def test_replace_invalid_utf8_default(self):
+        """
+        loads() invalid UTF-8 is rejected by default
+        """
+        for doc in DOCUMENTS:
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(doc)
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(doc, replace_invalid_utf8=False)
+
+    def test_replace_invalid_utf8_offset(self):
+        """
+        loads() replace_invalid_utf8 with offset and length
+        """
+        doc = b'\xff["\xff"]\xff'
+        assert orjson.loads(doc, offset=1, length=5, replace_invalid_utf8=True) == [
+            "�"
+        ]
+
+    def test_replace_invalid_utf8_syntax(self):
+        """
+        loads() replace_invalid_utf8 does not accept a replacement outside of
+        a string
+        """
+        for doc in (b"[1]\xff", b"\xff1", b'"\xe2'):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(doc, replace_invalid_utf8=True)
+
+    def test_replace_invalid_utf8_str(self):
+        """
+        loads() replace_invalid_utf8 does not apply to str with surrogates
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('"\ud800"', replace_invalid_utf8=True)
+
+    def test_replace_invalid_utf8_max_bytes(self):
+        """
+        loads() replace_invalid_utf8 max_bytes is of the input
+        """
+        doc = b'"\xff\xff"'
+        assert orjson.loads(doc, max_bytes=4, replace_invalid_utf8=True) == "��"
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(doc, max_bytes=3, replace_invalid_utf8=True)
+
+    def test_replace_invalid_utf8_load(self):
+        """
+        load() replace_invalid_utf8, read in full and in chunks
+        """
+        for doc in DOCUMENTS:
+            expected = orjson.loads(doc.decode("utf-8", "replace"))
+            assert orjson.load(io.BytesIO(doc), replace_invalid_utf8=True) == expected
+            for chunk_size in (1, 2, 3, 1024):
+                assert (
+                    orjson.load(
+                        io.BytesIO(doc),
+                        chunk_size=chunk_size,
+                        replace_invalid_utf8=True,
+                    )
+                    == expected
+                )
+
+    @pytest.mark.skipif(
+        not has_compression(), reason="built without the compression feature"
+    )
+    def test_replace_invalid_utf8_compressed(self):
+        """
+        loads_compressed() replace_invalid_utf8
+        """
+        for doc in DOCUMENTS:
+            expected = orjson.loads(doc.decode("utf-8", "replace"))
+            assert (
+                orjson.loads_compressed(gzip.compress(doc), replace_invalid_utf8=True)
+                == expected
+            )
+
+    def test_replace_invalid_utf8_decoder(self):
+        """
+        Decoder replace_invalid_utf8
+        """
+        decoder = orjson.Decoder(replace_invalid_utf8=True)
+        assert decoder.decode(b'{"a": "\xff"}') == {"a": "�"}
+
+    def test_replace_invalid_utf8_invalid(self):
+        """
+        replace_invalid_utf8 that is not a bool or is not supported
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(b"1", replace_invalid_utf8=1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_prefix(b"1", replace_invalid_utf8=True)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.unpackb(b"\x01", replace_invalid_utf8=True)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_cbor(b"\x01", replace_invalid_utf8=True)
@@ -0,0 +1,329 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import sys
+from typing import List
+
+import pytest
+
+import orjson
+
+POINT = {
+    "type": "object",
+    "properties": {"x": {"type": "integer"}, "y": {"type": "integer"}},
+    "required": ["x", "y"],
+    "additionalProperties": False,
+}
+
+
+def error(schema, doc, **kwargs):
+    with pytest.raises(orjson.JSONDecodeError) as exc:
+        orjson.loads(doc, schema=orjson.Schema(schema), **kwargs)
+    return str(exc.value).split(": line ")[0]
+
+
+class TestSchema:
+    def test_schema(self):
+        """
+        loads() schema valid document
+        """
+        schema = orjson.Schema(POINT)
+        assert orjson.loads(b'{"x": 1, "y": 2}', schema=schema) == {"x": 1, "y": 2}
+        assert orjson.loads('{"y": 2, "x": -1}', schema=schema) == {"x": -1, "y": 2}
+
+    def test_schema_path(self):
+        """
+        loads() schema violation reports the path of the value
+        """
+        schema = {"type": "object", "properties": {"points": {"items": POINT}}}
+        doc = b'{"points": [{"x": 1, "y": 2}, {"x": 1, "y": "2"}]}'
+        assert error(schema, doc) == 'Expected integer, got string at "/points/1/y"'
+
+    def test_schema_position(self):
+        """
+        loads() schema violation is at the value
+        """
+        schema = orjson.Schema({"properties": {"points": {"items": POINT}}})
+        doc = '{\n  "points": [\n    {"x": 1, "y": 2},\n    {"x": 1, "y": "2"}\n  ]\n}'
+        with pytest.raises(orjson.JSONDecodeError) as exc_info:
+            orjson.loads(doc, schema=schema)
+        assert exc_info.value.pos == doc.index('"2"')
+        assert (exc_info.value.lineno, exc_info.value.colno) == (4, 19)
+
+    def test_schema_position_scanned(self):
+        """
+        loads() schema violation is at the value kept of duplicate keys, past
+        comments and escaped keys
+        """
+        schema = orjson.Schema({"properties": {"a/\"": {"items": {"type": "integer"}}}})
+        for doc, kwargs in (
+            ('{"a/\\"": [1], "a/\\"": [2, "x"]}', {}),
+            ('{"a/\\"": [2, "x"], "a/\\"": [1]}', {"duplicate_keys": "first"}),
+            ('{"a\\/\\"": [2, /* ] */ "x",]}', {"jsonc": True}),
+        ):
+            with pytest.raises(orjson.JSONDecodeError) as exc_info:
+                orjson.loads(doc, schema=schema, **kwargs)
+            assert exc_info.value.pos == doc.index('"x"')
+
+    def test_schema_path_escaped(self):
+        """
+        loads() schema path escapes ~ and / in keys
+        """
+        schema = {"properties": {"a/b~c": {"type": "string"}}}
+        assert error(schema, b'{"a/b~c": 1}') == 'Expected string, got number at "/a~1b~0c"'
+
+    def test_schema_type(self):
+        """
+        loads() schema type, including a list of types
+        """
+        assert orjson.loads(b"1.0", schema=orjson.Schema({"type": "integer"})) == 1.0
+        assert error({"type": "integer"}, b"1.5") == "Expected integer, got number"
+        assert error({"type": "number"}, b"true") == "Expected number, got boolean"
+        schema = orjson.Schema({"type": ["string", "null"]})
+        assert orjson.loads(b"null", schema=schema) is None
+        assert error({"type": ["string", "null"]}, b"[]") == (
+            "Expected null or string, got array"
+        )
+
+    def test_schema_required(self):
+        """
+        loads() schema required and additionalProperties
+        """
+        assert error(POINT, b'{"x": 1}') == 'Missing required property "y"'
+        assert error(POINT, b'{"x": 1, "y": 2, "z": 3}') == 'Property "z" is not allowed'
+        schema = {"additionalProperties": {"type": "string"}}
+        assert error(schema, b'{"a": "b", "c": 1}') == 'Expected string, got number at "/c"'
+
+    def test_schema_items(self):
+        """
+        loads() schema items, prefixItems, and items as a list
+        """
+        schema = {"prefixItems": [{"type": "string"}], "items": {"type": "integer"}}
+        assert orjson.loads(b'["a", 1, 2]', schema=orjson.Schema(schema)) == ["a", 1, 2]
+        assert error(schema, b'["a", 1, 2.5]') == 'Expected integer, got number at "/2"'
+        schema = {"items": [{"type": "string"}], "additionalItems": False}
+        assert error(schema, b'["a", 1]') == 'Value is not allowed at "/1"'
+
+    def test_schema_bounds(self):
+        """
+        loads() schema numeric, length, and size bounds
+        """
+        assert error({"minimum": 2}, b"1") == "Expected a number at least 2, got 1"
+        assert error({"exclusiveMaximum": 2}, b"2") == "Expected a number less than 2, got 2"
+        assert error({"multipleOf": 0.5}, b"1.25") == "Expected a multiple of 0.5, got 1.25"
+        assert error({"maxLength": 2}, '"äöü"') == (
+            "Expected string of at most 2 characters, got 3"
+        )
+        assert error({"minItems": 1}, b"[]") == "Expected array of at least 1 items, got 0"
+        assert error({"maxProperties": 0}, b'{"a": 1}') == (
+            "Expected object of at most 0 properties, got 1"
+        )
+
+    def test_schema_enum_const(self):
+        """
+        loads() schema enum and const
+        """
+        schema = orjson.Schema({"enum": ["a", 1, None, [1, {"b": True}]]})
+        for doc in (b'"a"', b"1.0", b"null", b'[1, {"b": true}]'):
+            orjson.loads(doc, schema=schema)
+        assert error({"enum": ["a", 1]}, b'"b"') == "Value is not one of enum"
+        assert error({"enum": [[1]]}, b"[1, 2]") == "Value is not one of enum"
+        assert error({"const": True}, b"1") == "Value is not const"
+
+    def test_schema_combinators(self):
+        """
+        loads() schema allOf, anyOf, oneOf, not
+        """
+        assert error({"allOf": [{"minimum": 0}, {"maximum": 1}]}, b"2") == (
+            "Expected a number at most 1, got 2"
+        )
+        assert error({"anyOf": [{"type": "string"}, {"type": "null"}]}, b"1") == (
+            "Value does not match any schema of anyOf"
+        )
+        assert error({"oneOf": [{"minimum": 0}, {"maximum": 1}]}, b"0.5") == (
+            "Value matches 2 schemas of oneOf, not 1"
+        )
+        assert error({"not": {"type": "null"}}, b"null") == (
+            "Value matches the schema of not"
+        )
+
+    def test_schema_ref(self):
+        """
+        loads() schema $ref within the schema, including recursively
+        """
+        schema = {
+            "$defs": {
+                "node": {
+                    "type": "object",
+                    "properties": {
+                        "value": {"type": "integer"},
+                        "children": {"type": "array", "items": {"$ref": "#/$defs/node"}},
+                    },
+                    "required": ["value"],
+                }
+            },
+            "$ref": "#/$defs/node",
+        }
+        doc = b'{"value": 1, "children": [{"value": 2, "children": [{"value": "3"}]}]}'
+        assert error(schema, doc) == (
+            'Expected integer, got string at "/children/0/children/0/value"'
+        )
+        schema = {"items": {"$ref": "#"}, "maxItems": 1}
+        assert orjson.loads(b"[[[]]]", schema=orjson.Schema(schema)) == [[[]]]
+        assert error(schema, b"[[[], []]]") == (
+            'Expected array of at most 1 items, got 2 at "/0"'
+        )
+
+    def test_schema_ref_loop(self):
+        """
+        loads() schema $ref referring to itself without a value
+        """
+        assert error({"$ref": "#"}, b"1") == "$ref recursion limit exceeded"
+
+    def test_schema_boolean(self):
+        """
+        loads() schema true and false
+        """
+        assert orjson.loads(b"[1]", schema=orjson.Schema(True)) == [1]
+        assert error(False, b"[]") == "Value is not allowed"
+
+    def test_schema_empty_document(self):
+        """
+        loads() schema applies to [], {}, and ""
+        """
+        assert error({"type": "integer"}, b"[]") == "Expected integer, got array"
+        assert error({"type": "integer"}, b"{}") == "Expected integer, got object"
+        assert error({"type": "integer"}, b'""') == "Expected integer, got string"
+
+    def test_schema_big_int(self):
+        """
+        loads() schema number beyond 64 bits
+        """
+        assert error({"maximum": 0}, b"100000000000000000000000") == (
+            "Expected a number at most 0, got 100000000000000000000000"
+        )
+
+    def test_schema_strict(self):
+        """
+        loads() schema with strict
+        """
+        schema = orjson.Schema({"items": {"type": "integer", "maximum": 10}})
+        assert orjson.loads(b"[1, 2]", schema=schema, strict=True) == [1, 2]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(b"[1, 20]", schema=schema, strict=True)
+
+    def test_schema_max_depth(self):
+        """
+        loads() schema does not hide the error of nesting too deeply
+        """
+        schema = orjson.Schema({"items": {"$ref": "#"}})
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.loads(b"[" * 3000 + b"]" * 3000, schema=schema)
+        assert "recursion" in str(exc.value)
+
+    def test_schema_load(self):
+        """
+        load() schema, including in chunks
+        """
+        schema = orjson.Schema(POINT)
+        assert orjson.load(io.BytesIO(b'{"x": 1, "y": 2}'), schema=schema) == {
+            "x": 1,
+            "y": 2,
+        }
+        for kwargs in ({}, {"chunk_size": 4}):
+            with pytest.raises(orjson.JSONDecodeError) as exc:
+                orjson.load(io.BytesIO(b'{"x": 1, "y": null}'), schema=schema, **kwargs)
+            assert 'at "/y"' in str(exc.value)
+
+    def test_schema_decoder(self):
+        """
+        Decoder and loads_into() schema
+        """
+        schema = orjson.Schema({"items": POINT})
+        decoder = orjson.Decoder(schema=schema)
+        del schema
+        assert decoder.decode(b'[{"x": 1, "y": 2}]') == [{"x": 1, "y": 2}]
+        with pytest.raises(orjson.JSONDecodeError):
+            decoder.decode(b'[{"x": 1}]')
+        schema = orjson.Schema({"items": POINT, "maxItems": 1})
+        assert orjson.loads_into(List[dict], b'[{"x": 1, "y": 2}]', schema=schema) == [
+            {"x": 1, "y": 2}
+        ]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_into(List[dict], b"[{}, {}]", schema=schema)
+
+    def test_schema_invalid(self):
+        """
+        Schema() invalid or unsupported schema
+        """
+        for schema in (
+            1,
+            [],
+            {"type": "int"},
+            {"type": 1},
+            {"minimum": "1"},
+            {"maxItems": -1},
+            {"multipleOf": 0},
+            {"required": [1]},
+            {"anyOf": []},
+            {"properties": {"a": 1}},
+            {"$ref": "other.json#/a"},
+            {"$ref": "#/$defs/missing"},
+            {"pattern": "^a"},
+            {"uniqueItems": True},
+            {"if": {}, "then": {}},
+        ):
+            with pytest.raises(orjson.JSONDecodeError) as exc:
+                orjson.Schema(schema)
+            assert str(exc.value).startswith("Invalid schema: ")
+
+    def test_schema_annotations_ignored(self):
+        """
+        Schema() ignores keywords that are not assertions
+        """
+        schema = orjson.Schema(
+            {"$schema": "x", "title": "a", "description": "b", "format": "date"}
+        )
+        assert orjson.loads(b'"c"', schema=schema) == "c"
+
+    def test_schema_arguments(self):
+        """
+        Schema() takes one positional argument; schema must be a Schema
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.Schema()
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.Schema({}, {})
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads(b"1", schema={"type": "integer"})
+        assert orjson.loads(b"1", schema=None) == 1
+
+    def test_schema_incompatible(self):
+        """
+        loads() schema with arguments changing the objects made
+        """
+        schema = orjson.Schema({})
+        for kwargs in (
+            {"parse_float": float},
+            {"object_hook": dict},
+            {"tuples": True},
+            {"immutable": True},
+            {"datetimes": True},
+            {"uuids": True},
+            {"numpy": True},
+            {"raw_numbers": True},
+            {"duplicate_keys": "list"},
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(b"1", schema=schema, **kwargs)
+
+    def test_schema_refcount(self):
+        """
+        Decoder holds a reference to its schema until it is freed
+        """
+        schema = orjson.Schema({})
+        before = sys.getrefcount(schema)
+        decoder = orjson.Decoder(schema=schema)
+        assert sys.getrefcount(schema) == before + 1
+        del decoder
+        assert sys.getrefcount(schema) == before
@@ -0,0 +1,145 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import pytest
+
+import orjson
+
+DOC = (
+    b'{"items": [{"price": 1, "name": "a"}, {"price": 2.5, "extra": {"price": 9}}],'
+    b' "price": 0, "tags": ["x", "y", "z"]}'
+)
+
+
+class TestSelect:
+    def test_select(self):
+        """
+        select() child and wildcard
+        """
+        assert orjson.select(DOC, "$.items[*].price") == [1, 2.5]
+        assert orjson.select(DOC, "$.items.*.name") == ["a"]
+        assert orjson.select(DOC, "$.price") == [0]
+        assert orjson.select(DOC, "$.missing") == []
+
+    def test_select_input_types(self):
+        """
+        select() bytes, bytearray, memoryview, str
+        """
+        for data in (DOC, bytearray(DOC), memoryview(DOC), DOC.decode()):
+            assert orjson.select(data, "$.tags[1]") == ["y"]
+
+    def test_select_root(self):
+        """
+        select() $ is the document
+        """
+        assert orjson.select(DOC, "$") == [orjson.loads(DOC)]
+        assert orjson.select(b"1", "$") == [1]
+
+    def test_select_bracket(self):
+        """
+        select() bracketed names, indexes, and slices
+        """
+        assert orjson.select(DOC, "$['items'][0]['name']") == ["a"]
+        assert orjson.select(DOC, '$["tags"][2]') == ["z"]
+        assert orjson.select(DOC, "$.tags[1:]") == ["y", "z"]
+        assert orjson.select(DOC, "$.tags[:2]") == ["x", "y"]
+        assert orjson.select(DOC, "$.tags[5]") == []
+        assert orjson.select(b'{"a b": 1, "c\'d": 2}', "$['c\\'d']") == [2]
+
+    def test_select_descendant(self):
+        """
+        select() .. in document order, a value before its descendants, each
+        value at most once
+        """
+        assert orjson.select(DOC, "$..price") == [1, 2.5, 9, 0]
+        assert orjson.select(b"[[1, [2]], [3]]", "$..*") == [[1, [2]], 1, [2], 2, [3], 3]
+        assert orjson.select(b'{"a": {"a": {"a": 1}}}', "$..a..a") == [{"a": 1}, 1]
+
+    def test_select_paths(self):
+        """
+        select() paths=True returns normalized paths
+        """
+        assert orjson.select(DOC, "$..price", paths=True) == [
+            "$['items'][0]['price']",
+            "$['items'][1]['price']",
+            "$['items'][1]['extra']['price']",
+            "$['price']",
+        ]
+        assert orjson.select(b'{"a\'\\n": [1]}', "$.*[0]", paths=True) == [
+            "$['a\\'\\n'][0]"
+        ]
+        assert orjson.select(b"[]", "$", paths=True) == ["$"]
+
+    def test_select_escaped_key(self):
+        """
+        select() key with escapes is compared unescaped
+        """
+        assert orjson.select(b'{"\\u00e4\\"": 1}', "$['ä\"']") == [1]
+
+    def test_select_skips_unselected(self):
+        """
+        select() does not deserialize values that are not selected
+        """
+        doc = b'{"a": [1, 2, 3], "b": {"c": "\\ud800"}, "d": 1}'
+        assert orjson.select(doc, "$.d") == [1]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.select(doc, "$.b.c")
+
+    def test_select_invalid_json(self):
+        """
+        select() structure of the document is checked
+        """
+        for doc in (
+            b"",
+            b'{"a": 1',
+            b'{"a" 1}',
+            b'{"a": 1 "b": 2}',
+            b'{"a": 1} x',
+            b"[1, 2",
+            b'{"a": "b}',
+            b"{1: 2}",
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.select(doc, "$.a")
+
+    def test_select_error_position(self):
+        """
+        select() error reports where in the document it is
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            orjson.select(b'{"a": 1,\n "b" 2}', "$.b")
+        assert exc.value.lineno == 2
+        assert exc.value.colno == 6
+
+    def test_select_invalid_path(self):
+        """
+        select() invalid or unsupported query
+        """
+        for query in ("items", "$.", "$[-1]", "$[x]", '$["a"', "$[1:-1]", "$a"):
+            with pytest.raises(orjson.JSONDecodeError) as exc:
+                orjson.select(DOC, query)
+            assert str(exc.value).startswith("Invalid path: ")
+
+    def test_select_arguments(self):
+        """
+        select() arguments
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.select(DOC)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.select(DOC, b"$")
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.select(DOC, "$", paths=1)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.select(DOC, "$", unknown=True)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.select(1, "$")
+
+    def test_select_deep(self):
+        """
+        select() skips deep values, but does not descend beyond the
+        recursion limit
+        """
+        doc = b'{"a": ' + b"[" * 2000 + b"]" * 2000 + b', "b": 1}'
+        assert orjson.select(doc, "$.b") == [1]
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.select(doc, "$..b")
@@ -0,0 +1,83 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import json
+
+import pytest
+
+import orjson
+
+
+@dataclasses.dataclass
+class Dataclass:
+    a: int
+    b: list
+
+
+OBJ = {
+    "a": [1, 2.5, None, True, "b"],
+    "c": {"d": {}, "e": []},
+    "f": [[], [{}], {"g": [1]}],
+}
+
+
+class TestSeparators:
+    def test_separators_default(self):
+        """
+        dumps() separators=None is compact
+        """
+        assert orjson.dumps(OBJ, separators=None) == orjson.dumps(OBJ)
+        assert orjson.dumps(OBJ, separators=(",", ":")) == orjson.dumps(OBJ)
+
+    def test_separators_spaced(self):
+        """
+        dumps() separators is equivalent to that of json.dumps()
+        """
+        for separators in ((", ", ": "), [" ,", ":  "], ("\n,\t", "\r:")):
+            assert orjson.dumps(OBJ, separators=separators) == json.dumps(
+                OBJ, separators=separators
+            ).encode("utf-8")
+
+    def test_separators_indent(self):
+        """
+        dumps() separators with OPT_INDENT_2 is equivalent to those of
+        json.dumps() with indent=2
+        """
+        for separators in ((",", ":"), (", ", ": "), (",", " : ")):
+            assert orjson.dumps(
+                OBJ, option=orjson.OPT_INDENT_2, separators=separators
+            ) == json.dumps(OBJ, indent=2, separators=separators).encode("utf-8")
+
+    def test_separators_types(self):
+        """
+        dumps() separators applies to keys and containers of each type
+        """
+        obj = [Dataclass(1, [2, 3]), {1: 2, "a": (3, 4)}]
+        assert (
+            orjson.dumps(
+                obj,
+                separators=(", ", ": "),
+                option=orjson.OPT_NON_STR_KEYS | orjson.OPT_SORT_KEYS,
+            )
+            == b'[{"a": 1, "b": [2, 3]}, {"1": 2, "a": [3, 4]}]'
+        )
+
+    def test_separators_invalid(self):
+        """
+        dumps() separators that are not a tuple or list of "," and ":" each
+        surrounded by whitespace raise JSONEncodeError
+        """
+        for separators in (
+            ",:",
+            (",",),
+            (",", ":", ""),
+            (b",", b":"),
+            (";", ":"),
+            (",", "="),
+            ("", ":"),
+            (",,", ":"),
+            (", x", ":"),
+            ("\xa0,", ":"),
+        ):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps([1], separators=separators)
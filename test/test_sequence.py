@@ -0,0 +1,190 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import collections
+import collections.abc
+
+import pytest
+
+import orjson
+
+
+class Immutable(collections.abc.Sequence):
+    def __init__(self, *items):
+        self._items = items
+
+    def __getitem__(self, idx):
+        return self._items[idx]
+
+    def __len__(self):
+        return len(self._items)
+
+
+class Computed(collections.abc.Sequence):
+    def __getitem__(self, idx):
+        if idx >= 2:
+            raise IndexError(idx)
+        return {"idx": [idx]}
+
+    def __len__(self):
+        return 2
+
+
+class Short(collections.abc.Sequence):
+    def __getitem__(self, idx):
+        raise IndexError(idx)
+
+    def __len__(self):
+        return 1
+
+
+class Unsized(collections.abc.Sequence):
+    def __getitem__(self, idx):
+        raise IndexError(idx)
+
+    def __len__(self):
+        return 1 // 0
+
+
+Point = collections.namedtuple("Point", ("x", "y"))
+
+
+class TestSequence:
+    def test_sequence(self):
+        """
+        OPT_SERIALIZE_SEQUENCE
+        """
+        assert (
+            orjson.dumps(
+                Immutable(1, "a", Immutable(None, [2])),
+                option=orjson.OPT_SERIALIZE_SEQUENCE,
+            )
+            == b'[1,"a",[null,[2]]]'
+        )
+
+    def test_sequence_unset(self):
+        """
+        dumps() Sequence without OPT_SERIALIZE_SEQUENCE raises
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(Immutable(1))
+
+    def test_sequence_range(self):
+        """
+        OPT_SERIALIZE_SEQUENCE range
+        """
+        assert (
+            orjson.dumps(range(3), option=orjson.OPT_SERIALIZE_SEQUENCE) == b"[0,1,2]"
+        )
+
+    def test_sequence_namedtuple(self):
+        """
+        OPT_SERIALIZE_SEQUENCE namedtuple
+        """
+        assert (
+            orjson.dumps(Point(1, 2), option=orjson.OPT_SERIALIZE_SEQUENCE)
+            == b"[1,2]"
+        )
+
+    def test_sequence_computed(self):
+        """
+        OPT_SERIALIZE_SEQUENCE elements created by __getitem__
+        """
+        assert (
+            orjson.dumps([Computed(), Computed()], option=orjson.OPT_SERIALIZE_SEQUENCE)
+            == b'[[{"idx":[0]},{"idx":[1]}],[{"idx":[0]},{"idx":[1]}]]'
+        )
+
+    def test_sequence_empty(self):
+        """
+        OPT_SERIALIZE_SEQUENCE empty
+        """
+        assert orjson.dumps(Immutable(), option=orjson.OPT_SERIALIZE_SEQUENCE) == b"[]"
+        assert (
+            orjson.dumps(
+                Immutable(),
+                option=orjson.OPT_SERIALIZE_SEQUENCE | orjson.OPT_INDENT_2,
+            )
+            == b"[]"
+        )
+
+    def test_sequence_indent(self):
+        """
+        OPT_SERIALIZE_SEQUENCE with OPT_INDENT_2
+        """
+        assert (
+            orjson.dumps(
+                Immutable(1, {"a": 2}),
+                option=orjson.OPT_SERIALIZE_SEQUENCE | orjson.OPT_INDENT_2,
+            )
+            == b'[\n  1,\n  {\n    "a": 2\n  }\n]'
+        )
+
+    def test_sequence_bytes(self):
+        """
+        OPT_SERIALIZE_SEQUENCE does not apply to bytes, bytearray, memoryview
+        """
+        for obj in (b"a", bytearray(b"a"), memoryview(b"a")):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps(obj, option=orjson.OPT_SERIALIZE_SEQUENCE)
+
+    def test_sequence_getitem_raises(self):
+        """
+        OPT_SERIALIZE_SEQUENCE __getitem__ raising before __len__ elements
+        """
+        with pytest.raises(IndexError):
+            orjson.dumps(Short(), option=orjson.OPT_SERIALIZE_SEQUENCE)
+
+    def test_sequence_len_raises(self):
+        """
+        OPT_SERIALIZE_SEQUENCE __len__ raising
+        """
+        with pytest.raises(ZeroDivisionError):
+            orjson.dumps(Unsized(), option=orjson.OPT_SERIALIZE_SEQUENCE)
+
+    def test_sequence_user_string(self):
+        """
+        OPT_SERIALIZE_SEQUENCE does not apply to collections.UserString
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(
+                collections.UserString("ab"), option=orjson.OPT_SERIALIZE_SEQUENCE
+            )
+
+    def test_sequence_native(self):
+        """
+        OPT_SERIALIZE_SEQUENCE does not change list, tuple, or str
+        """
+        obj = [1, (2, "ab")]
+        assert orjson.dumps(obj, option=orjson.OPT_SERIALIZE_SEQUENCE) == orjson.dumps(
+            obj
+        )
+
+    def test_sequence_include_keys(self):
+        """
+        OPT_SERIALIZE_SEQUENCE with include_keys
+        """
+        assert (
+            orjson.dumps(
+                {"a": Immutable({"b": 1, "c": 2}), "d": 3},
+                option=orjson.OPT_SERIALIZE_SEQUENCE,
+                include_keys={"a.b"},
+            )
+            == b'{"a":[{"b":1}]}'
+        )
+
+    def test_sequence_max_depth(self):
+        """
+        OPT_SERIALIZE_SEQUENCE nesting counts toward max_depth
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(
+                Immutable(Immutable(1)),
+                option=orjson.OPT_SERIALIZE_SEQUENCE,
+                max_depth=1,
+            )
+
+    def test_sequence_kwarg(self):
+        """
+        dumps() serialize_sequence=True is OPT_SERIALIZE_SEQUENCE
+        """
+        assert orjson.dumps(range(2), serialize_sequence=True) == b"[0,1]"
@@ -0,0 +1,67 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import json
+
+import pytest
+
+import orjson
+
+
+class Obj:
+    def __init__(self, idx):
+        self.a = idx
+        self.b_é = "b"
+        setattr(self, 'c"\n', [idx])
+
+
+def ref(obj, **kwargs):
+    return json.dumps(obj, ensure_ascii=False, **kwargs).encode("utf-8")
+
+
+class TestSharedKeys:
+    def test_shared_keys(self):
+        """
+        dumps() dicts sharing keys
+        """
+        obj = [vars(Obj(idx)) for idx in range(100)]
+        assert orjson.dumps(obj) == ref(obj, separators=(",", ":"))
+        assert orjson.dumps(obj, option=orjson.OPT_INDENT_2) == ref(obj, indent=2)
+        assert orjson.dumps(obj) == ref(obj, separators=(",", ":"))
+
+    def test_shared_keys_order(self):
+        """
+        dumps() dicts sharing keys inserted in a different order or missing one
+        """
+        first = Obj(1)
+        second = Obj(2)
+        del second.a
+        second.a = 3
+        third = Obj(3)
+        del third.b_é
+        obj = [vars(first), vars(second), vars(third), vars(first)]
+        for _ in range(2):
+            assert orjson.dumps(obj) == ref(obj, separators=(",", ":"))
+
+    def test_shared_keys_class(self):
+        """
+        dumps() dicts of instances of classes created and freed repeatedly
+        """
+        for idx in range(100):
+
+            class Custom:
+                def __init__(self):
+                    setattr(self, "key%d" % idx, idx)
+                    self.other = None
+
+            obj = [vars(Custom()), vars(Custom())]
+            assert orjson.dumps(obj) == ref(obj, separators=(",", ":"))
+            del Custom, obj
+
+    def test_shared_keys_invalid(self):
+        """
+        dumps() dict sharing keys with an invalid key
+        """
+        obj = Obj(1)
+        setattr(obj, "\ud800", 1)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(vars(obj))
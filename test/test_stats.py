@@ -0,0 +1,134 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import datetime
+
+import pytest
+
+import orjson
+
+from .util import read_fixture_obj
+
+
+@dataclasses.dataclass
+class Point:
+    x: int
+    y: int
+
+
+class Custom:
+    pass
+
+
+class TestStats:
+    def test_stats_keys(self):
+        """
+        dumps() stats populates dict
+        """
+        stats = {}
+        output = orjson.dumps([1, "a", None], stats=stats)
+        assert stats == {
+            "bytes": len(output),
+            "max_depth": 1,
+            "default_calls": 0,
+            "types": {"list": 1, "int": 1, "str": 1, "none": 1},
+        }
+
+    def test_stats_none(self):
+        """
+        dumps() stats=None
+        """
+        assert orjson.dumps([], stats=None) == b"[]"
+
+    def test_stats_invalid(self):
+        """
+        dumps() stats must be dict
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps([], stats=[])
+
+    def test_stats_types(self):
+        """
+        dumps() stats counts of each type
+        """
+        stats = {}
+        orjson.dumps(
+            {
+                "a": [Point(1, 2), Point(3, 4)],
+                "b": (1.5, True),
+                "c": datetime.date(2000, 1, 1),
+            },
+            stats=stats,
+        )
+        assert stats["types"] == {
+            "dict": 1,
+            "list": 1,
+            "dataclass": 2,
+            "int": 4,
+            "tuple": 1,
+            "float": 1,
+            "bool": 1,
+            "date": 1,
+        }
+
+    def test_stats_max_depth(self):
+        """
+        dumps() stats max_depth
+        """
+        stats = {}
+        orjson.dumps({"a": [[{"b": [1]}]]}, stats=stats)
+        assert stats["max_depth"] == 5
+        orjson.dumps(1, stats=stats)
+        assert stats["max_depth"] == 0
+
+    def test_stats_default_calls(self):
+        """
+        dumps() stats default_calls
+        """
+        stats = {}
+        orjson.dumps([Custom(), Custom(), 1], default=lambda _: "x", stats=stats)
+        assert stats["default_calls"] == 2
+        assert stats["types"]["unknown"] == 2
+        assert stats["types"]["str"] == 2
+
+    def test_stats_error(self):
+        """
+        dumps() stats is populated on error
+        """
+        stats = {}
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps([1, 2, Custom()], stats=stats)
+        assert stats["types"] == {"list": 1, "int": 2, "unknown": 1}
+
+    def test_stats_max_size(self):
+        """
+        dumps() stats bytes on max_size exceeded
+        """
+        stats = {}
+        with pytest.raises(orjson.JSONEncodeSizeError):
+            orjson.dumps(["a" * 1000, "b" * 1000], max_size=1500, stats=stats)
+        assert stats["bytes"] <= 1500
+
+    def test_stats_nested_dumps(self):
+        """
+        dumps() stats are not shared with dumps() called from default
+        """
+        outer = {}
+        inner = {}
+
+        def default(obj):
+            return orjson.dumps([1, 2, 3], stats=inner).decode("utf-8")
+
+        orjson.dumps([Custom()], default=default, stats=outer)
+        assert inner["types"] == {"list": 1, "int": 3}
+        assert outer["types"] == {"list": 1, "unknown": 1, "str": 1}
+
+    def test_stats_twitter(self):
+        """
+        dumps() stats twitter.json
+        """
+        val = read_fixture_obj("twitter.json.xz")
+        stats = {}
+        output = orjson.dumps(val, option=orjson.OPT_APPEND_NEWLINE, stats=stats)
+        assert stats["bytes"] == len(output)
+        assert stats["types"]["dict"] > 0
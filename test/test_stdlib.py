@@ -0,0 +1,200 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import datetime
+import io
+import json
+import sys
+
+import pytest
+
+import orjson
+import orjson.stdlib
+
+
+@dataclasses.dataclass
+class Dataclass:
+    a: int
+
+
+class Encoder(json.JSONEncoder):
+    def default(self, o):
+        return "custom"
+
+
+def dumps_equal(obj, **kwargs):
+    return orjson.stdlib.dumps(obj, **kwargs) == json.dumps(obj, **kwargs)
+
+
+def raises_equal(func, *args, **kwargs):
+    with pytest.raises(Exception) as stdlib:
+        getattr(orjson.stdlib, func)(*args, **kwargs)
+    with pytest.raises(Exception) as expected:
+        getattr(json, func)(*args, **kwargs)
+    return type(stdlib.value) is type(expected.value) and str(stdlib.value) == str(
+        expected.value
+    )
+
+
+class TestStdlib:
+    def test_stdlib_import(self):
+        """
+        orjson.stdlib is importable and has the names of json
+        """
+        assert sys.modules["orjson.stdlib"] is orjson.stdlib
+        assert "stdlib" in orjson.__all__
+        assert orjson.stdlib.JSONDecodeError is json.JSONDecodeError
+        assert orjson.stdlib.JSONEncoder is json.JSONEncoder
+        assert orjson.stdlib.JSONDecoder is json.JSONDecoder
+        assert set(orjson.stdlib.__all__) == set(json.__all__)
+
+    def test_stdlib_dumps(self):
+        """
+        orjson.stdlib.dumps() returns the str of json.dumps()
+        """
+        obj = {"a": [1, 2.5, None, True, False, "b"], "c": {"d": []}}
+        assert isinstance(orjson.stdlib.dumps(obj), str)
+        assert dumps_equal(obj)
+        assert dumps_equal(obj, sort_keys=True)
+        assert dumps_equal((1, "a"))
+        assert dumps_equal(obj, indent=2)
+
+    def test_stdlib_dumps_separators(self):
+        """
+        orjson.stdlib.dumps() writes separators as json.dumps() does
+        """
+        obj = {"a": [1, {"b": []}, {}], "c": {"d": None}}
+        assert orjson.stdlib.dumps(obj) == '{"a": [1, {"b": []}, {}], "c": {"d": null}}'
+        for separators in ((",", ":"), [" , ", " :\t"], (", ", ": ")):
+            assert dumps_equal(obj, separators=separators)
+            assert dumps_equal(obj, separators=separators, indent=2)
+        assert dumps_equal(obj, separators=(";", "="))
+
+    def test_stdlib_dumps_ensure_ascii(self):
+        """
+        orjson.stdlib.dumps() escapes non-ASCII characters unless ensure_ascii
+        is false
+        """
+        obj = ["aé好\U0001f600\x7f\x1f ", {"é": "\U0001f600"}]
+        assert dumps_equal(obj)
+        assert dumps_equal(obj, ensure_ascii=False)
+
+    def test_stdlib_dumps_float(self):
+        """
+        orjson.stdlib.dumps() writes floats as repr() does
+        """
+        for val in (
+            0.0,
+            -0.0,
+            1.0,
+            0.1,
+            1e15,
+            1e16,
+            1.5e16,
+            1e-4,
+            1e-5,
+            1.5e-5,
+            -1.2345e-7,
+            5e-324,
+            1.7976931348623157e308,
+        ):
+            assert orjson.stdlib.dumps(val) == repr(val)
+
+    def test_stdlib_dumps_default(self):
+        """
+        orjson.stdlib.dumps() passes datetime objects and dataclasses to
+        default as json.dumps() does
+        """
+        obj = [datetime.datetime(2020, 1, 1, 12), datetime.date(2020, 1, 1)]
+        assert dumps_equal(obj, default=str)
+        assert dumps_equal(Dataclass(1), default=dataclasses.asdict)
+        assert raises_equal("dumps", Dataclass(1))
+        assert raises_equal("dumps", object())
+
+    def test_stdlib_dumps_fallback(self):
+        """
+        orjson.stdlib.dumps() gives arguments and objects orjson does not
+        serialize as json.dumps() does to json.dumps()
+        """
+        obj = {1: float("nan"), None: float("inf"), 2.5: -(2**70)}
+        assert orjson.stdlib.dumps(obj) == json.dumps(obj)
+        assert orjson.stdlib.dumps(obj, sort_keys=False) == json.dumps(obj)
+        assert orjson.stdlib.dumps({(1,): 1}, skipkeys=True) == "{}"
+        assert orjson.stdlib.dumps([1], indent=4) == json.dumps([1], indent=4)
+        assert orjson.stdlib.dumps([1], indent="\t") == json.dumps([1], indent="\t")
+        assert orjson.stdlib.dumps([1, {"a": 1}], separators=(", ", ": ")) == (
+            '[1, {"a": 1}]'
+        )
+        assert orjson.stdlib.dumps(object(), cls=Encoder) == '"custom"'
+        assert orjson.stdlib.dumps("\ud800") == json.dumps("\ud800")
+        assert raises_equal("dumps", [float("nan")], allow_nan=False)
+        assert raises_equal("dumps", {(1,): 1})
+        assert raises_equal("dumps", 1, unknown=True)
+        obj = []
+        obj.append(obj)
+        assert raises_equal("dumps", obj)
+
+    def test_stdlib_dump(self):
+        """
+        orjson.stdlib.dump() writes what orjson.stdlib.dumps() returns
+        """
+        obj = {"a": [1, "é"]}
+        fp = io.StringIO()
+        assert orjson.stdlib.dump(obj, fp, ensure_ascii=False) is None
+        assert fp.getvalue() == orjson.stdlib.dumps(obj, ensure_ascii=False)
+        assert raises_equal("dump", obj, None)
+
+    def test_stdlib_loads(self):
+        """
+        orjson.stdlib.loads() deserializes as json.loads() does
+        """
+        for data in (
+            '{"a": [1, 2.5, null, true, false, "\\u00e9"], "a": {}}',
+            "[NaN, Infinity, -Infinity, 1e400]",
+            "123456789012345678901234567890",
+            '"\\ud800"',
+        ):
+            assert repr(orjson.stdlib.loads(data)) == repr(json.loads(data))
+            assert repr(orjson.stdlib.loads(data.encode("utf-8"))) == repr(
+                json.loads(data)
+            )
+        data = '[{"a": 1.5, "b": 2}]'
+        for kwargs in (
+            {"parse_float": str},
+            {"parse_int": float},
+            {"object_hook": len},
+            {"object_pairs_hook": list},
+            {"parse_constant": str},
+            {"strict": True},
+        ):
+            assert orjson.stdlib.loads(data, **kwargs) == json.loads(data, **kwargs)
+        assert orjson.stdlib.loads('"\x01"', strict=False) == "\x01"
+        assert orjson.stdlib.loads("[1]".encode("utf-16")) == [1]
+
+    def test_stdlib_loads_invalid(self):
+        """
+        orjson.stdlib.loads() raises the exception of json.loads()
+        """
+        for data in ("[1,", "﻿[1]", '"\x01"', "", b"\xff", 1, None):
+            assert raises_equal("loads", data)
+        assert raises_equal("loads", "[]", unknown=True)
+
+    def test_stdlib_loads_exception(self):
+        """
+        orjson.stdlib.loads() propagates an exception raised by a hook
+        """
+
+        def hook(_):
+            raise ValueError("hook")
+
+        with pytest.raises(ValueError):
+            orjson.stdlib.loads('{"a": 1}', object_hook=hook)
+
+    def test_stdlib_load(self):
+        """
+        orjson.stdlib.load() deserializes what fp.read() returns
+        """
+        assert orjson.stdlib.load(io.StringIO('{"a": [1]}')) == {"a": [1]}
+        assert orjson.stdlib.load(io.BytesIO(b"[NaN]"), parse_constant=str) == [
+            "NaN"
+        ]
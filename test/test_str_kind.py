@@ -0,0 +1,89 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import json
+
+import pytest
+
+import orjson
+
+STRINGS = (
+    "é",
+    "café\t\"\\\x00\x7f",
+    "ÿ" * 5000,
+    "€中\n",
+    "中" * 5000,
+    "\U0001f600é\x1f",
+    "\U0001f600" * 5000,
+    "aĀ\U00010000߿ࠀ￿\U0010ffff",
+)
+
+
+class TestStrKind:
+    @pytest.mark.parametrize("obj", STRINGS)
+    def test_str_kind(self, obj):
+        """
+        str of each kind is escaped and encoded as UTF-8
+        """
+        ref = json.dumps(obj, ensure_ascii=False).encode("utf-8")
+        assert orjson.dumps(obj) == ref
+        assert orjson.loads(orjson.dumps(obj)) == obj
+
+    @pytest.mark.parametrize("obj", STRINGS)
+    def test_str_kind_key(self, obj):
+        """
+        dict key of each kind is escaped and encoded as UTF-8
+        """
+        ref = json.dumps({obj: 1}, ensure_ascii=False, separators=(",", ":"))
+        assert orjson.dumps({obj: 1}) == ref.encode("utf-8")
+        assert orjson.dumps({"a": 1, obj: 2}, option=orjson.OPT_INDENT_2) == (
+            json.dumps({"a": 1, obj: 2}, ensure_ascii=False, indent=2).encode("utf-8")
+        )
+
+    @pytest.mark.parametrize("obj", ("\ud800", "é\udfff", "\U0001f600\ud83d"))
+    def test_str_kind_surrogate(self, obj):
+        """
+        str of each kind containing a surrogate raises
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(obj)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps({obj: 1})
+
+    def test_str_kind_max_size(self):
+        """
+        max_size applies to the UTF-8 length of str
+        """
+        obj = "中" * 10
+        assert orjson.dumps(obj, max_size=32) == json.dumps(
+            obj, ensure_ascii=False
+        ).encode("utf-8")
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(obj, max_size=31)
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps(["é" * 100000], max_size=100)
+
+    def test_str_ascii_escape_position(self):
+        """
+        ASCII str with a character to escape at any position
+        """
+        for length in range(1, 34):
+            for idx in range(length):
+                for char in ("\x00", "\x1f", '"', "\\"):
+                    obj = "a" * idx + char + "~" * (length - idx - 1)
+                    ref = json.dumps(obj).encode("utf-8")
+                    assert orjson.dumps(obj) == ref
+                    assert orjson.dumps({obj: 1}) == b"{" + ref + b":1}"
+
+    def test_str_utf8_escape_position(self):
+        """
+        str subclass, written as UTF-8, with a character to escape at any position
+        """
+
+        class SubStr(str):
+            pass
+
+        for length in range(1, 34):
+            for idx in range(length):
+                obj = SubStr("é" * idx + "\x1f" + " !#[]\x7f\xff"[: length - idx - 1])
+                ref = json.dumps(obj, ensure_ascii=False).encode("utf-8")
+                assert orjson.dumps(obj) == ref
@@ -0,0 +1,190 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import decimal
+import io
+
+import pytest
+
+import orjson
+
+# input refused regardless of strict
+NONCONFORMING = (
+    b"\xef\xbb\xbf[]",
+    b'"a\x01b"',
+    b'"a\tb"',
+    b'"\\x41"',
+    b'"\\ud800"',
+    b'"\\udc00"',
+    b"01",
+    b"-01",
+    b"+1",
+    b".5",
+    b"1.",
+    b"0x10",
+    b"[\x0c1]",
+    b"[\xc2\xa01]",
+    b"'a'",
+    b"{a: 1}",
+    b"[1]//",
+    b"[1,]",
+    b"[]\x00",
+    b"",
+    b" ",
+    b"NaN",
+    b"1e400",
+)
+
+
+class TestStrict:
+    def test_nonconforming(self):
+        """
+        loads() refuses nonconforming input without strict
+        """
+        for val in NONCONFORMING:
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val)
+
+    def test_strict_nonconforming(self):
+        """
+        loads() strict=True refuses nonconforming input
+        """
+        for val in NONCONFORMING:
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val, strict=True)
+
+    def test_strict_conforming(self):
+        """
+        loads() strict=True deserializes conforming input
+        """
+        doc = (
+            '{"a": [1, -2, 0.5, -0.0, 1e10, 1E-10, 0e-400, 18446744073709551615, '
+            '-9223372036854775808], "b": {"c": null, "d": true, "e": false}, '
+            '"f": "\\ud83d\\ude00\\u0000\\n"}'
+        )
+        assert orjson.loads(doc, strict=True) == orjson.loads(doc)
+
+    def test_strict_false(self):
+        """
+        loads() strict=False
+        """
+        assert orjson.loads('{"a": 1, "a": 2}', strict=False) == {"a": 2}
+
+    def test_strict_duplicate_keys(self):
+        """
+        loads() strict=True refuses duplicate keys
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('{"a": 1, "a": 2}', strict=True)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('[{"b": {"a": 1, "a": 2}}]', strict=True)
+
+    def test_strict_duplicate_keys_explicit(self):
+        """
+        loads() strict=True with an explicit duplicate_keys
+        """
+        assert orjson.loads(
+            '{"a": 1, "a": 2}', strict=True, duplicate_keys="first"
+        ) == {"a": 1}
+        assert orjson.loads('{"a": 1, "a": 2}', strict=True, duplicate_keys="list") == {
+            "a": [1, 2]
+        }
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads('{"a": 1, "a": 2}', strict=True, duplicate_keys="error")
+
+    def test_strict_object_pairs_hook(self):
+        """
+        loads() strict=True gives duplicate keys to object_pairs_hook
+        """
+        assert orjson.loads(
+            '{"a": 1, "a": 2}', strict=True, object_pairs_hook=lambda pairs: pairs
+        ) == [("a", 1), ("a", 2)]
+
+    def test_strict_integer_range(self):
+        """
+        loads() strict=True refuses integers outside 64 bits
+        """
+        assert orjson.loads("18446744073709551616") == 18446744073709551616
+        for val in (
+            "18446744073709551616",
+            "-9223372036854775809",
+            "[1e2, 1" + "0" * 30 + "]",
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val, strict=True)
+
+    def test_strict_integer_range_parse_int(self):
+        """
+        loads() strict=True gives integers outside 64 bits to parse_int
+        """
+        assert (
+            orjson.loads("18446744073709551616", strict=True, parse_int=int)
+            == 18446744073709551616
+        )
+
+    def test_strict_underflow(self):
+        """
+        loads() strict=True refuses numbers that underflow to zero
+        """
+        assert orjson.loads("1e-400") == 0.0
+        for val in ("1e-400", "-1e-400", "0.0001e-400", "[1.5e-999]"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(val, strict=True)
+
+    def test_strict_underflow_parse_float(self):
+        """
+        loads() strict=True gives numbers that underflow to parse_float
+        """
+        assert orjson.loads(
+            "1e-400", strict=True, parse_float=decimal.Decimal
+        ) == decimal.Decimal("1e-400")
+
+    def test_strict_zero(self):
+        """
+        loads() strict=True deserializes zero
+        """
+        for val in ("0", "-0", "0.0", "-0.000", "0e10", "0.0E-400"):
+            assert orjson.loads(val, strict=True) == 0
+
+    def test_strict_denormal(self):
+        """
+        loads() strict=True deserializes subnormal numbers
+        """
+        assert orjson.loads("5e-324", strict=True) == 5e-324
+
+    def test_strict_allow_nan(self):
+        """
+        loads() strict=True and allow_nan=True are exclusive
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("[]", strict=True, allow_nan=True)
+
+    def test_strict_invalid(self):
+        """
+        loads() strict not a bool
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("[]", strict=1)
+
+    def test_strict_load(self):
+        """
+        load() strict
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b'{"a": 1, "a": 2}'), strict=True)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b"1e-400"), strict=True)
+
+    def test_strict_load_chunked(self):
+        """
+        load() strict with chunk_size
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(io.BytesIO(b'{"a": 1, "a": 2}'), chunk_size=2, strict=True)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.load(
+                io.BytesIO(b"[18446744073709551616]"), chunk_size=2, strict=True
+            )
+        assert orjson.load(io.BytesIO(b"[1, 2.5]"), chunk_size=2, strict=True) == [
+            1,
+            2.5,
+        ]
@@ -0,0 +1,114 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+
+import pytest
+
+import orjson
+
+
+class TestSurrogates:
+    def test_surrogates_reject(self):
+        """
+        loads() lone surrogates are rejected by default
+        """
+        for doc in (b'"\\ud800"', b'"\\udc00"', b'"\\ud800\\u0041"', b'{"\\ud800":1}'):
+            for surrogates in (None, "reject"):
+                with pytest.raises(orjson.JSONDecodeError):
+                    orjson.loads(doc, surrogates=surrogates)
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(doc)
+
+    def test_surrogates_replace(self):
+        """
+        loads() surrogates="replace"
+        """
+        assert orjson.loads(b'"\\ud800"', surrogates="replace") == "�"
+        assert orjson.loads(b'"a\\udc00b"', surrogates="replace") == "a�b"
+        assert orjson.loads(b'"\\ud800\\u0041"', surrogates="replace") == "�A"
+        assert orjson.loads(b'"\\ud800\\n"', surrogates="replace") == "�\n"
+        assert (
+            orjson.loads(b'"\\ud800\\ud800\\udc00"', surrogates="replace")
+            == "�\U00010000"
+        )
+        assert orjson.loads(b'{"\\udfff":"\\ud800"}', surrogates="replace") == {
+            "�": "�"
+        }
+
+    def test_surrogates_surrogatepass(self):
+        """
+        loads() surrogates="surrogatepass"
+        """
+        assert orjson.loads(b'"\\ud800"', surrogates="surrogatepass") == "\ud800"
+        assert orjson.loads(b'"a\\udc00b"', surrogates="surrogatepass") == "a\udc00b"
+        assert (
+            orjson.loads(b'"\\ud800\\ud800\\udc00"', surrogates="surrogatepass")
+            == "\ud800\U00010000"
+        )
+        assert orjson.loads(
+            b'[{"\\ud800":"\\udfff"},{"\\ud800":1}]', surrogates="surrogatepass"
+        ) == [{"\ud800": "\udfff"}, {"\ud800": 1}]
+
+    def test_surrogates_matches_json(self):
+        """
+        loads() surrogates="surrogatepass" matches the standard library
+        """
+        import json
+
+        val = ["\ud800", "a\udfffb", "😀", {"\udbff": "é\ud800"}]
+        doc = json.dumps(val)
+        assert orjson.loads(doc, surrogates="surrogatepass") == json.loads(doc)
+
+    def test_surrogates_pair(self):
+        """
+        loads() surrogates does not change surrogate pairs
+        """
+        for surrogates in (None, "reject", "replace", "surrogatepass"):
+            assert orjson.loads(b'"\\ud83d\\ude00"', surrogates=surrogates) == "😀"
+
+    def test_surrogates_stream(self):
+        """
+        load() surrogates read in chunks
+        """
+        doc = b'["\\ud800", "\\ud83d\\ude00"]'
+        assert orjson.load(
+            io.BytesIO(doc), surrogates="replace", chunk_size=2
+        ) == ["�", "😀"]
+        assert orjson.load(
+            io.BytesIO(doc), surrogates="surrogatepass", chunk_size=2
+        ) == ["\ud800", "😀"]
+
+    def test_surrogates_decoder(self):
+        """
+        Decoder surrogates
+        """
+        decoder = orjson.Decoder(surrogates="replace")
+        assert decoder.decode(b'"\\ud800"') == "�"
+
+    def test_surrogates_is_valid(self):
+        """
+        is_valid() surrogates
+        """
+        assert not orjson.is_valid(b'"\\ud800"')
+        assert orjson.is_valid(b'"\\ud800"', surrogates="replace")
+        assert orjson.is_valid(b'"\\ud800"', surrogates="surrogatepass")
+
+    def test_surrogates_invalid_utf8(self):
+        """
+        loads() surrogates does not accept surrogates not escaped
+        """
+        for surrogates in ("replace", "surrogatepass"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(b'"\xed\xa0\x80"', surrogates=surrogates)
+
+    def test_surrogates_invalid(self):
+        """
+        loads() surrogates that is not known
+        """
+        for surrogates in ("ignore", "Replace", 1, True):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads(b'"a"', surrogates=surrogates)
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.unpackb(b"\x01", surrogates="replace")
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads_cbor(b"\x01", surrogates="replace")
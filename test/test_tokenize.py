@@ -0,0 +1,162 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+
+import pytest
+
+import orjson
+
+
+def error(doc):
+    with pytest.raises(orjson.JSONDecodeError) as exc:
+        list(orjson.tokenize(doc))
+    return exc.value.msg
+
+
+class TestTokenize:
+    def test_tokenize(self):
+        """
+        tokenize() events, values, and offsets
+        """
+        assert list(orjson.tokenize(b'{"a": [1, 2.5, "b", true, false, null]}')) == [
+            ("start_object", None, 0),
+            ("key", "a", 1),
+            ("start_array", None, 6),
+            ("number", 1, 7),
+            ("number", 2.5, 10),
+            ("string", "b", 15),
+            ("boolean", True, 20),
+            ("boolean", False, 26),
+            ("null", None, 33),
+            ("end_array", None, 37),
+            ("end_object", None, 38),
+        ]
+
+    def test_tokenize_scalar(self):
+        """
+        tokenize() document that is not a container
+        """
+        assert list(orjson.tokenize(b"  -1e3 ")) == [("number", -1000.0, 2)]
+        assert list(orjson.tokenize(b'"a"')) == [("string", "a", 0)]
+
+    def test_tokenize_empty_containers(self):
+        """
+        tokenize() empty and nested containers
+        """
+        assert list(orjson.tokenize(b"[[], {}]")) == [
+            ("start_array", None, 0),
+            ("start_array", None, 1),
+            ("end_array", None, 2),
+            ("start_object", None, 5),
+            ("end_object", None, 6),
+            ("end_array", None, 7),
+        ]
+
+    def test_tokenize_escaped(self):
+        """
+        tokenize() string and key with escapes
+        """
+        assert list(orjson.tokenize(b'{"\\u00e4\\"": "a\\nb\\ud83d\\ude00"}')) == [
+            ("start_object", None, 0),
+            ("key", 'ä"', 1),
+            ("string", "a\nb\U0001f600", 13),
+            ("end_object", None, 31),
+        ]
+
+    def test_tokenize_big_int(self):
+        """
+        tokenize() number beyond 64 bits
+        """
+        assert list(orjson.tokenize(b"[100000000000000000000000]"))[1] == (
+            "number",
+            100000000000000000000000,
+            1,
+        )
+
+    def test_tokenize_offset_utf8(self):
+        """
+        tokenize() offset is of the UTF-8 encoded document
+        """
+        assert list(orjson.tokenize('["äö", 1]'))[2] == ("number", 1, 9)
+
+    def test_tokenize_file(self):
+        """
+        tokenize() file read in chunks
+        """
+        doc = b"[" + b",".join(b'{"a": "%d"}' % i for i in range(10000)) + b"]"
+        tokens = list(orjson.tokenize(io.BytesIO(doc)))
+        assert tokens == list(orjson.tokenize(doc))
+        assert len(tokens) == 40002
+        assert tokens[-3] == ("string", "9999", len(doc) - 8)
+
+    def test_tokenize_lazy(self):
+        """
+        tokenize() returns the tokens before an error
+        """
+        it = orjson.tokenize(b"[1, x]")
+        assert next(it) == ("start_array", None, 0)
+        assert next(it) == ("number", 1, 1)
+        with pytest.raises(orjson.JSONDecodeError):
+            next(it)
+        with pytest.raises(StopIteration):
+            next(it)
+
+    def test_tokenize_invalid(self):
+        """
+        tokenize() invalid document
+        """
+        assert error(b"") == "unexpected end of data"
+        assert error(b"[1] x") == "unexpected content after document"
+        assert error(b'{"a" 1}') == "unexpected character"
+        assert error(b"[1 2]") == "unexpected character"
+        assert error(b"[1,]") == "unexpected character"
+        assert error(b'{"a": 1,}') == "unexpected character"
+        assert error(b"[tru]") == "invalid literal"
+        assert error(b"+1") == "unexpected character"
+        assert error(b'"abc') == "unclosed string"
+        assert error(b'"a\nb"') == "unexpected control character in string"
+
+    def test_tokenize_invalid_number(self):
+        """
+        tokenize() invalid number
+        """
+        for doc in (b"01", b"1.", b"-", b"1e", b"1.e2", b"[-01]"):
+            assert error(doc) == "invalid number"
+
+    def test_tokenize_location(self):
+        """
+        tokenize() error offset, line, and column
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            list(orjson.tokenize(b"[1,\n  x]"))
+        assert exc.value.pos == 6
+        assert exc.value.lineno == 2
+        assert exc.value.colno == 3
+        assert str(exc.value) == "unexpected character: line 2 column 3 (char 6)"
+
+    def test_tokenize_location_str(self):
+        """
+        tokenize() error position in str input is in characters
+        """
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            list(orjson.tokenize('["\xe9",\n tru]'))
+        assert exc.value.msg == "invalid literal"
+        assert exc.value.pos == 7
+        assert exc.value.lineno == 2
+        assert exc.value.colno == 2
+
+    def test_tokenize_max_depth(self):
+        """
+        tokenize() nesting too deeply
+        """
+        list(orjson.tokenize(b"[" * 1024 + b"]" * 1024))
+        with pytest.raises(orjson.JSONDecodeError) as exc:
+            list(orjson.tokenize(b"[" * 1025 + b"]" * 1025))
+        assert "recursion" in str(exc.value)
+
+    def test_tokenize_invalid_input(self):
+        """
+        tokenize() input that is not a document or file
+        """
+        with pytest.raises(AttributeError):
+            orjson.tokenize(1.5)
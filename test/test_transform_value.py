@@ -0,0 +1,236 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import dataclasses
+import sys
+
+import pytest
+
+import orjson
+
+
+@dataclasses.dataclass
+class Meters:
+    value: float
+
+
+class Lazy:
+    def __init__(self, value):
+        self.value = value
+
+
+def resolve(obj):
+    if isinstance(obj, Lazy):
+        return obj.value
+    return obj
+
+
+def mask(obj):
+    if isinstance(obj, str) and obj.startswith("sk-"):
+        return "sk-***"
+    return obj
+
+
+class TestTransformValue:
+    def test_transform(self):
+        """
+        dumps() transform replaces values
+        """
+        assert (
+            orjson.dumps({"keys": ["sk-abc", "pk-def"]}, transform=mask)
+            == b'{"keys":["sk-***","pk-def"]}'
+        )
+
+    def test_transform_top_level(self):
+        """
+        dumps() transform of the object passed to dumps()
+        """
+        assert orjson.dumps("sk-abc", transform=mask) == b'"sk-***"'
+        assert orjson.dumps(Lazy([1, 2]), transform=resolve) == b"[1,2]"
+
+    def test_transform_every_value(self):
+        """
+        dumps() transform is called with every value once
+        """
+        seen = []
+
+        def transform(obj):
+            seen.append(obj)
+            return obj
+
+        obj = {"a": [1, (2.5, None)], "b": {"c": True}}
+        assert orjson.dumps(obj, transform=transform) == orjson.dumps(obj)
+        assert seen == [
+            obj,
+            obj["a"],
+            1,
+            obj["a"][1],
+            2.5,
+            None,
+            obj["b"],
+            True,
+        ]
+
+    def test_transform_keys(self):
+        """
+        dumps() transform is not called with keys
+        """
+        assert (
+            orjson.dumps({"sk-a": "sk-b"}, transform=mask) == b'{"sk-a":"sk-***"}'
+        )
+
+    def test_transform_result_not_transformed(self):
+        """
+        dumps() transform is not called with what it returns
+        """
+        calls = []
+
+        def transform(obj):
+            calls.append(obj)
+            if isinstance(obj, int):
+                return obj + 1
+            return obj
+
+        assert orjson.dumps(1, transform=transform) == b"2"
+        assert calls == [1]
+
+    def test_transform_container_result(self):
+        """
+        dumps() transform elements of a container it returns
+        """
+
+        def transform(obj):
+            if isinstance(obj, Lazy):
+                return [obj.value, "sk-abc"]
+            return mask(obj)
+
+        assert (
+            orjson.dumps({"a": Lazy(Lazy(1))}, transform=transform)
+            == b'{"a":[[1,"sk-***"],"sk-***"]}'
+        )
+
+    def test_transform_container_result_refcount(self):
+        """
+        dumps() transform releases what it returns
+        """
+        value = [1, 2]
+
+        def transform(obj):
+            if isinstance(obj, Lazy):
+                return {"a": value}
+            return obj
+
+        before = sys.getrefcount(value)
+        for _ in range(100):
+            assert orjson.dumps([Lazy(0), Lazy(1)], transform=transform) == (
+                b'[{"a":[1,2]},{"a":[1,2]}]'
+            )
+        assert sys.getrefcount(value) == before
+
+    def test_transform_unit_conversion(self):
+        """
+        dumps() transform dataclass to a value
+        """
+
+        def transform(obj):
+            if isinstance(obj, Meters):
+                return obj.value * 100
+            return obj
+
+        assert (
+            orjson.dumps({"height": Meters(1.5)}, transform=transform)
+            == b'{"height":150.0}'
+        )
+
+    def test_transform_default(self):
+        """
+        dumps() transform is called before default and with what it returns
+        """
+        calls = []
+
+        def transform(obj):
+            calls.append(type(obj).__name__)
+            return mask(obj)
+
+        assert (
+            orjson.dumps(Lazy("sk-abc"), default=lambda obj: obj.value, transform=transform)
+            == b'"sk-***"'
+        )
+        assert calls == ["Lazy", "str"]
+
+    def test_transform_unsupported(self):
+        """
+        dumps() transform returning an unsupported type
+        """
+        with pytest.raises(orjson.JSONEncodeError):
+            orjson.dumps([1], transform=lambda obj: object() if obj == 1 else obj)
+
+    def test_transform_redact_keys(self):
+        """
+        dumps() transform is not called with redacted values
+        """
+        seen = []
+
+        def transform(obj):
+            seen.append(obj)
+            return obj
+
+        assert (
+            orjson.dumps({"token": 1}, transform=transform, redact_keys={"token"})
+            == b'{"token":"[REDACTED]"}'
+        )
+        assert seen == [{"token": 1}]
+
+    def test_transform_include_keys(self):
+        """
+        dumps() transform is not called with omitted values
+        """
+        seen = []
+
+        def transform(obj):
+            seen.append(obj)
+            return obj
+
+        assert (
+            orjson.dumps({"a": 1, "b": 2}, transform=transform, include_keys={"b"})
+            == b'{"b":2}'
+        )
+        assert seen == [{"a": 1, "b": 2}, 2]
+
+    def test_transform_indent(self):
+        """
+        dumps() transform with OPT_INDENT_2
+        """
+        assert (
+            orjson.dumps(
+                {"a": Lazy([1])}, transform=resolve, option=orjson.OPT_INDENT_2
+            )
+            == b'{\n  "a": [\n    1\n  ]\n}'
+        )
+
+    def test_transform_exception(self):
+        """
+        dumps() transform raises an exception
+        """
+
+        def transform(obj):
+            if obj == 2:
+                raise ValueError("invalid")
+            return obj
+
+        with pytest.raises(orjson.JSONEncodeError) as exc_info:
+            orjson.dumps([1, 2], transform=transform)
+        assert str(exc_info.value) == "transform raised an exception"
+
+    def test_transform_none(self):
+        """
+        dumps() transform=None
+        """
+        assert orjson.dumps([1], transform=None) == b"[1]"
+
+    def test_transform_invalid(self):
+        """
+        dumps() transform not callable
+        """
+        for val in (1, "a", [len]):
+            with pytest.raises(orjson.JSONEncodeError):
+                orjson.dumps([], transform=val)
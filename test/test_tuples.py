@@ -0,0 +1,105 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import io
+import sys
+
+import pytest
+
+import orjson
+
+from .util import Custom, raise_custom
+
+
+class TestTuples:
+    def test_tuples_default(self):
+        """
+        loads() deserializes arrays to list by default
+        """
+        assert type(orjson.loads("[1]")) is list
+        assert type(orjson.loads("[1]", tuples=False)) is list
+
+    def test_tuples(self):
+        """
+        loads() tuples=True
+        """
+        res = orjson.loads('[1, [2, "a"], {"b": [null, []]}]', tuples=True)
+        assert res == (1, (2, "a"), {"b": (None, ())})
+        assert type(res[1]) is tuple
+        assert type(res[2]["b"][1]) is tuple
+
+    def test_tuples_empty(self):
+        """
+        loads() tuples=True empty array
+        """
+        for val in ("[]", b"[]", " [ ] "):
+            assert orjson.loads(val, tuples=True) == ()
+
+    def test_tuples_hashable(self):
+        """
+        loads() tuples=True result is hashable
+        """
+        res = orjson.loads('[[1, 2], ["a", [true, null]], 1.5]', tuples=True)
+        assert {res: 1}[((1, 2), ("a", (True, None)), 1.5)] == 1
+
+    def test_tuples_large(self):
+        """
+        loads() tuples=True large array
+        """
+        doc = orjson.dumps(list(range(10000)))
+        assert orjson.loads(doc, tuples=True) == tuple(range(10000))
+
+    def test_tuples_object_pairs_hook(self):
+        """
+        loads() tuples=True does not change the list given to object_pairs_hook
+        """
+        assert orjson.loads(
+            '{"a": [1]}', tuples=True, object_pairs_hook=lambda pairs: pairs
+        ) == [("a", (1,))]
+
+    def test_tuples_duplicate_keys_list(self):
+        """
+        loads() tuples=True does not change duplicate_keys="list"
+        """
+        assert orjson.loads(
+            '{"a": [1], "a": 2}', tuples=True, duplicate_keys="list"
+        ) == {"a": [(1,), 2]}
+
+    def test_tuples_exception_refcount(self):
+        """
+        loads() tuples=True exception does not leak
+        """
+        obj = object()
+        before = sys.getrefcount(obj)
+        for _ in range(100):
+            with pytest.raises(Custom):
+                orjson.loads(
+                    '[1, [2, {"b": 3}], 4]',
+                    tuples=True,
+                    parse_int=lambda _: obj,
+                    object_hook=raise_custom,
+                )
+        assert sys.getrefcount(obj) == before
+
+    def test_tuples_invalid(self):
+        """
+        loads() tuples not a bool
+        """
+        for val in (1, None, "true"):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.loads("[]", tuples=val)
+
+    def test_tuples_load(self):
+        """
+        load() tuples
+        """
+        assert orjson.load(io.BytesIO(b"[1, [2]]"), tuples=True) == (1, (2,))
+
+    def test_tuples_load_chunked(self):
+        """
+        load() tuples with chunk_size
+        """
+        assert orjson.load(io.BytesIO(b"[1, [2], []]"), chunk_size=2, tuples=True) == (
+            1,
+            (2,),
+            (),
+        )
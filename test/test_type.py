@@ -244,6 +244,26 @@ class TestType:
             assert orjson.loads(str(val)) == val
             assert orjson.dumps(val) == str(val).encode("utf-8")
 
+    def test_int_digit_boundaries(self):
+        """
+        int at the boundaries of 30-bit digits
+        """
+        for val in (
+            0,
+            1,
+            -1,
+            1073741823,
+            1073741824,
+            -1073741823,
+            -1073741824,
+            1152921504606846975,
+            1152921504606846976,
+            -1152921504606846975,
+            -1152921504606846976,
+        ):
+            assert orjson.dumps(val) == str(val).encode("utf-8")
+            assert orjson.dumps([val]) == f"[{val}]".encode("utf-8")
+
     def test_uint_64(self):
         """
         uint 64-bit
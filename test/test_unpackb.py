@@ -0,0 +1,230 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import decimal
+import struct
+
+import pytest
+
+import orjson
+
+
+def pack(obj):
+    """
+    Serialize obj to MessagePack using the smallest format of each value.
+    """
+    if obj is None:
+        return b"\xc0"
+    if obj is False:
+        return b"\xc2"
+    if obj is True:
+        return b"\xc3"
+    if isinstance(obj, int):
+        if 0 <= obj < 128:
+            return bytes([obj])
+        if -32 <= obj < 0:
+            return struct.pack(">b", obj)
+        for fmt, marker, lo, hi in (
+            (">B", 0xCC, 0, 2**8),
+            (">H", 0xCD, 0, 2**16),
+            (">I", 0xCE, 0, 2**32),
+            (">Q", 0xCF, 0, 2**64),
+            (">b", 0xD0, -(2**7), 2**7),
+            (">h", 0xD1, -(2**15), 2**15),
+            (">i", 0xD2, -(2**31), 2**31),
+            (">q", 0xD3, -(2**63), 2**63),
+        ):
+            if lo <= obj < hi:
+                return bytes([marker]) + struct.pack(fmt, obj)
+    if isinstance(obj, float):
+        return b"\xcb" + struct.pack(">d", obj)
+    if isinstance(obj, str):
+        data = obj.encode("utf-8")
+        if len(data) < 32:
+            return bytes([0xA0 | len(data)]) + data
+        return b"\xda" + struct.pack(">H", len(data)) + data
+    if isinstance(obj, bytes):
+        return b"\xc4" + bytes([len(obj)]) + obj
+    if isinstance(obj, list):
+        if len(obj) < 16:
+            head = bytes([0x90 | len(obj)])
+        else:
+            head = b"\xdd" + struct.pack(">I", len(obj))
+        return head + b"".join(pack(each) for each in obj)
+    if isinstance(obj, dict):
+        if len(obj) < 16:
+            head = bytes([0x80 | len(obj)])
+        else:
+            head = b"\xdf" + struct.pack(">I", len(obj))
+        return head + b"".join(pack(k) + pack(v) for k, v in obj.items())
+    raise TypeError(obj)
+
+
+def error(data, **kwargs):
+    with pytest.raises(orjson.JSONDecodeError) as exc:
+        orjson.unpackb(data, **kwargs)
+    return exc.value.msg
+
+
+def error_at(data, **kwargs):
+    with pytest.raises(orjson.JSONDecodeError) as exc:
+        orjson.unpackb(data, **kwargs)
+    return exc.value.msg, exc.value.pos
+
+
+class TestUnpackb:
+    def test_unpackb(self):
+        """
+        unpackb() deserializes the objects loads() does
+        """
+        obj = {
+            "a": [1, -1, 200, -200, 70000, -70000, 2**40, -(2**40), 2**64 - 1],
+            "b": [1.5, -0.0, "", "ä" * 40, True, False, None],
+            "c": {"d": [], "e": {}},
+            "f": list(range(20)),
+        }
+        assert orjson.unpackb(pack(obj)) == obj
+        assert orjson.unpackb(pack(obj)) == orjson.loads(orjson.dumps(obj))
+
+    def test_unpackb_float32(self):
+        """
+        unpackb() float 32
+        """
+        assert orjson.unpackb(b"\xca" + struct.pack(">f", 0.5)) == 0.5
+
+    def test_unpackb_bin(self):
+        """
+        unpackb() bin to bytes
+        """
+        assert orjson.unpackb(pack([b"", b"\x00\xff"])) == [b"", b"\x00\xff"]
+        assert orjson.unpackb(b"\xc5\x00\x01a") == b"a"
+        assert orjson.unpackb(b"\xc6\x00\x00\x00\x01a") == b"a"
+
+    def test_unpackb_input(self):
+        """
+        unpackb() bytes-like input, and offset and length
+        """
+        data = pack({"a": 1})
+        for each in (data, bytearray(data), memoryview(data)):
+            assert orjson.unpackb(each) == {"a": 1}
+        assert orjson.unpackb(b"\x00" + data + b"\x00", offset=1, length=len(data)) == {
+            "a": 1
+        }
+        for each in ("\x01", 1, None):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.unpackb(each)
+
+    def test_unpackb_hooks(self):
+        """
+        unpackb() parse_float, parse_int, object_hook, object_pairs_hook
+        """
+        data = pack({"a": [0.1, 2], "b": {"c": 1}})
+        assert orjson.unpackb(data, parse_float=decimal.Decimal) == {
+            "a": [decimal.Decimal("0.1"), 2],
+            "b": {"c": 1},
+        }
+        assert orjson.unpackb(data, parse_int=str)["a"] == [0.1, "2"]
+        assert orjson.unpackb(data, object_hook=len) == 2
+        assert orjson.unpackb(data, object_pairs_hook=lambda pairs: pairs) == [
+            ("a", [0.1, 2]),
+            ("b", [("c", 1)]),
+        ]
+
+    def test_unpackb_hook_raises(self):
+        """
+        unpackb() exception raised by a hook propagates
+        """
+
+        def hook(val):
+            raise ZeroDivisionError
+
+        with pytest.raises(ZeroDivisionError):
+            orjson.unpackb(pack([1, {"a": 1}]), object_hook=hook)
+
+    def test_unpackb_duplicate_keys(self):
+        """
+        unpackb() duplicate_keys and strict
+        """
+        data = b"\x82\xa1a\x01\xa1a\x02"
+        assert orjson.unpackb(data) == {"a": 2}
+        assert orjson.unpackb(data, duplicate_keys="first") == {"a": 1}
+        assert orjson.unpackb(data, duplicate_keys="list") == {"a": [1, 2]}
+        assert error(data, strict=True) == 'Duplicate key "a"'
+
+    def test_unpackb_tuples_immutable(self):
+        """
+        unpackb() tuples and immutable
+        """
+        assert orjson.unpackb(pack([[1]]), tuples=True) == ((1,),)
+        obj = orjson.unpackb(pack({"a": [1]}), immutable=True)
+        assert obj == {"a": (1,)}
+        with pytest.raises(TypeError):
+            obj["b"] = 2
+
+    def test_unpackb_nan(self):
+        """
+        unpackb() NaN and infinity only with allow_nan
+        """
+        data = pack([float("inf")])
+        assert error_at(data) == ("nan or inf number is not allowed", 1)
+        assert orjson.unpackb(data, allow_nan=True) == [float("inf")]
+
+    def test_unpackb_limits(self):
+        """
+        unpackb() max_depth, max_items, max_string_len, max_bytes
+        """
+        assert error_at(pack([[[1]]]), max_depth=2) == ("recursion limit exceeded", 2)
+        assert orjson.unpackb(pack([[1]]), max_depth=2) == [[1]]
+        data = b"\x91" * 1025 + b"\x90"
+        assert error(data).startswith("recursion limit exceeded")
+        assert error(pack({"a": [1, 2]}), max_items=2) == (
+            "Document exceeds max_items of 2"
+        )
+        assert error(pack(["abcd"]), max_string_len=3) == (
+            "String exceeds max_string_len of 3 characters"
+        )
+        assert error(pack({"abcd": 1}), max_string_len=3) == (
+            "String exceeds max_string_len of 3 characters"
+        )
+        assert error(pack([1, 2]), max_bytes=2) == "Input exceeds max_bytes of 2 bytes"
+
+    def test_unpackb_schema(self):
+        """
+        unpackb() schema
+        """
+        schema = orjson.Schema({"type": "array", "items": {"type": "integer"}})
+        assert orjson.unpackb(pack([1, 2]), schema=schema) == [1, 2]
+        assert error(pack([1, "2"]), schema=schema) == (
+            'Expected integer, got string at "/1"'
+        )
+
+    def test_unpackb_invalid(self):
+        """
+        unpackb() invalid MessagePack
+        """
+        assert error_at(b"") == ("unexpected end of data", 0)
+        assert error_at(b"\x92\x01") == ("unexpected end of data", 1)
+        assert error_at(b"\x92\x01\x92") == ("unexpected end of data", 3)
+        assert error_at(b"\xcd\x01") == ("unexpected end of data", 1)
+        assert error_at(b"\xa3ab") == ("unexpected end of data", 1)
+        assert error_at(b"\xdd\xff\xff\xff\xff") == ("unexpected end of data", 5)
+        assert error_at(b"\x01\x02") == ("unexpected content after document", 1)
+        assert error_at(b"\x91\xc1") == ("invalid MessagePack type 0xc1", 1)
+        assert error_at(b"\xd4\x01\x00") == (
+            "MessagePack extension types are not supported", 0
+        )
+        assert error_at(b"\xa2\xff\xfe") == ("invalid utf-8 encoding in string", 1)
+        assert error_at(b"\x81\x01\x02") == ("map key must be a string", 1)
+
+    def test_unpackb_arguments(self):
+        """
+        unpackb() invalid arguments
+        """
+        for args, kwargs in (
+            ((), {}),
+            ((b"\x01", b"\x01"), {}),
+            ((b"\x01",), {"jsonc": True}),
+            ((b"\x01",), {"threads": 2}),
+            ((b"\x01",), {"unknown": 1}),
+        ):
+            with pytest.raises(orjson.JSONDecodeError):
+                orjson.unpackb(*args, **kwargs)
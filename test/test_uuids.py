@@ -0,0 +1,96 @@
+# SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+import uuid
+
+import pytest
+
+import orjson
+
+VAL = uuid.UUID("7202d115-7ff3-4c81-a7c1-2a1f067b1ece")
+
+
+class TestUuids:
+    def test_uuids_default(self):
+        """
+        loads() deserializes UUID strings to str by default
+        """
+        assert orjson.loads(orjson.dumps(VAL)) == str(VAL)
+        assert orjson.loads(orjson.dumps(VAL), uuids=False) == str(VAL)
+
+    def test_uuids(self):
+        """
+        loads() uuids=True
+        """
+        res = orjson.loads(orjson.dumps(VAL), uuids=True)
+        assert type(res) is uuid.UUID
+        assert res == VAL
+
+    def test_uuids_case(self):
+        """
+        loads() uuids=True hexadecimal digits in either case
+        """
+        assert orjson.loads(orjson.dumps(str(VAL).upper()), uuids=True) == VAL
+        assert orjson.loads(
+            '"00000000-0000-0000-0000-000000000000"', uuids=True
+        ) == uuid.UUID(int=0)
+        assert orjson.loads(
+            '"ffffffff-FFFF-ffff-FFFF-ffffffffffff"', uuids=True
+        ) == uuid.UUID(int=2**128 - 1)
+
+    def test_uuids_roundtrip(self):
+        """
+        loads() uuids=True is the inverse of dumps()
+        """
+        obj = {"a": [uuid.uuid4() for _ in range(10)], "b": VAL}
+        assert orjson.loads(orjson.dumps(obj), uuids=True) == obj
+
+    def test_uuids_not_matching(self):
+        """
+        loads() uuids=True strings that are not canonical UUIDs are str
+        """
+        for val in (
+            "",
+            VAL.hex,
+            "{%s}" % VAL,
+            "urn:uuid:%s" % VAL,
+            str(VAL)[:-1],
+            str(VAL) + "0",
+            str(VAL).replace("-", "_"),
+            "7202d115-7ff3-4c81-a7c1-2a1f067b1ecg",
+            "7202d11-57ff3-4c81-a7c1-2a1f067b1ece",
+            "7202d115-7ff3-4c81-a7c1-2a1f067b1eç",
+        ):
+            assert orjson.loads(orjson.dumps(val), uuids=True) == val
+
+    def test_uuids_keys(self):
+        """
+        loads() uuids=True does not change keys
+        """
+        assert orjson.loads(
+            orjson.dumps({str(VAL): str(VAL)}), uuids=True
+        ) == {str(VAL): VAL}
+
+    def test_uuids_datetimes(self):
+        """
+        loads() uuids=True with datetimes=True and Decoder
+        """
+        doc = orjson.dumps([str(VAL), "2020-01-01"])
+        res = orjson.loads(doc, uuids=True, datetimes=True)
+        assert res[0] == VAL
+        assert str(res[1]) == "2020-01-01"
+        assert orjson.Decoder(uuids=True).decode(doc) == [VAL, "2020-01-01"]
+
+    def test_uuids_binary(self):
+        """
+        unpackb() and loads_cbor() uuids=True
+        """
+        data = str(VAL).encode()
+        assert orjson.unpackb(b"\xd9\x24" + data, uuids=True) == VAL
+        assert orjson.loads_cbor(b"\x78\x24" + data, uuids=True) == VAL
+
+    def test_uuids_invalid(self):
+        """
+        loads() uuids must be a bool
+        """
+        with pytest.raises(orjson.JSONDecodeError):
+            orjson.loads("1", uuids=1)
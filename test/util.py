@@ -1,10 +1,14 @@
 # SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+import gzip
+import io
 import lzma
 import os
 from pathlib import Path
 from typing import Any, Dict
 
+import pytest
+
 import orjson
 
 dirname = os.path.join(os.path.dirname(__file__), "../data")
@@ -13,6 +17,9 @@ STR_CACHE: Dict[str, str] = {}
 
 OBJ_CACHE: Dict[str, Any] = {}
 
+# a small document of every JSON type for tests of reading and writing it
+DOC = {"a": [1, 2.5, None], "b": "cé", "c": {"d": [True, False]}}
+
 
 def read_fixture_bytes(filename, subdir=None):
     if subdir is None:
@@ -37,3 +44,53 @@ def read_fixture_obj(filename):
     if not filename in OBJ_CACHE:
         OBJ_CACHE[filename] = orjson.loads(read_fixture_str(filename))
     return OBJ_CACHE[filename]
+
+
+def has_compression():
+    """
+    Whether orjson is built with the compression feature of loads_compressed()
+    """
+    try:
+        orjson.loads_compressed(gzip.compress(b"1"))
+    except orjson.JSONDecodeError:
+        return False
+    return True
+
+
+class Custom(Exception):
+    pass
+
+
+def raise_custom(val):
+    raise Custom(val)
+
+
+class RaisingReader(io.RawIOBase):
+    """
+    A binary file returning data once and then raising on read
+    """
+
+    def __init__(self, data=b""):
+        self.data = data
+
+    def readable(self):
+        return True
+
+    def readinto(self, buf):
+        if not self.data:
+            raise ValueError("read failed")
+        size = len(self.data)
+        buf[:size] = self.data
+        self.data = b""
+        return size
+
+
+def errors_equal(data, threads, **kwargs):
+    """
+    Whether loads() raises the same error with and without threads
+    """
+    with pytest.raises(orjson.JSONDecodeError) as serial:
+        orjson.loads(data, **kwargs)
+    with pytest.raises(orjson.JSONDecodeError) as parallel:
+        orjson.loads(data, threads=threads, **kwargs)
+    return str(serial.value) == str(parallel.value)